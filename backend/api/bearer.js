@@ -0,0 +1,15 @@
+const crypto = require('crypto');
+
+// Bearer token checks for the operator-only routes.
+
+// Whether the request is authorized with `Bearer <token>`. Both sides are hashed first, so
+// they are compared in constant time whatever their lengths.
+function hasBearerToken(req, token) {
+  const digest = (value) => crypto.createHash('sha256').update(value).digest();
+  return crypto.timingSafeEqual(
+    digest(req.get('Authorization') || ''),
+    digest(`Bearer ${token}`)
+  );
+}
+
+module.exports = { hasBearerToken };
@@ -1,6 +1,9 @@
 const express = require('express');
 const cors = require('cors');
 const dotenv = require('dotenv');
+const { revocationsRouter } = require('./revocations');
+const { meterRequests, organizationsRouter, ownUsage } = require('./tenants');
+const { widgetRouter } = require('./widget');
 
 dotenv.config();
 
@@ -51,7 +54,19 @@ const streams = [
   }
 ];
 
+// Incident response: bulk access revocation, restricted to operators
+app.use('/api/admin/revocations', revocationsRouter());
+
+// Multi-tenant deployments: organizations, their API keys and usage, restricted to operators
+app.use('/api/admin/organizations', organizationsRouter());
+
+// Every other API request is metered against its organization's daily quota; the operator
+// routes above carry the admin token instead of an API key
+app.use('/api', meterRequests());
+
 // Routes
+app.get('/api/usage', ownUsage);
+
 app.get('/api/streams', (req, res) => {
   res.json(streams);
 });
@@ -65,6 +80,9 @@ app.get('/api/streams/:id', (req, res) => {
   }
 });
 
+// Embeddable pay-per-second player: session intents and their join status
+app.use('/api/widget', widgetRouter());
+
 app.get('/health', (req, res) => {
   res.json({ status: 'ok' });
 });
@@ -0,0 +1,194 @@
+const express = require('express');
+const fs = require('fs');
+const { ApiPromise, WsProvider, Keyring } = require('@polkadot/api');
+const { hasBearerToken } = require('./bearer');
+
+// Bulk access revocation for incident response.
+//
+// Operators cut a hijacked stream's viewers or a compromised account's sessions in one
+// request. Each request is carried out with the chain's admin key through `sudo` and the
+// outcome is appended to an audit log, one JSON object per line.
+
+const NODE_WS_URL = process.env.NODE_WS_URL || 'ws://127.0.0.1:9944';
+const ADMIN_SURI = process.env.ADMIN_SURI;
+const ADMIN_TOKEN = process.env.ADMIN_TOKEN;
+const AUDIT_LOG_PATH = process.env.AUDIT_LOG_PATH || 'revocations.log';
+
+// Upper bound on `force_leave_stream` calls for one stream, so a request always ends
+const MAX_STREAM_ROUNDS = 64;
+
+let apiPromise;
+
+function chainApi() {
+  if (!apiPromise) {
+    apiPromise = ApiPromise.create({ provider: new WsProvider(NODE_WS_URL) });
+  }
+  return apiPromise;
+}
+
+function adminPair() {
+  return new Keyring({ type: 'sr25519' }).addFromUri(ADMIN_SURI);
+}
+
+// Dispatch `call` as root and resolve with the block hash and the events it emitted
+async function sudo(api, call) {
+  return new Promise((resolve, reject) => {
+    let unsubscribe;
+    api.tx.sudo
+      .sudo(call)
+      .signAndSend(adminPair(), ({ status, events, dispatchError }) => {
+        if (!status.isInBlock) {
+          return;
+        }
+        if (unsubscribe) {
+          unsubscribe();
+        }
+
+        const sudid = events.find(({ event }) => api.events.sudo.Sudid.is(event));
+        const result = sudid && sudid.event.data[0];
+        const error = dispatchError || (result && result.isErr && result.asErr);
+        if (error) {
+          reject(new Error(describeError(api, error)));
+        } else {
+          resolve({ blockHash: status.asInBlock.toHex(), events: events.map(({ event }) => event) });
+        }
+      })
+      .then((unsub) => {
+        unsubscribe = unsub;
+      })
+      .catch(reject);
+  });
+}
+
+function describeError(api, error) {
+  if (error.isModule) {
+    const { section, name } = api.registry.findMetaError(error.asModule);
+    return `${section}.${name}`;
+  }
+  return error.toString();
+}
+
+// The viewers refunded by a revocation, from its `ViewerRefunded` events
+function refunds(api, events) {
+  return events
+    .filter((event) => api.events.tickStream.ViewerRefunded.is(event))
+    .map((event) => ({
+      streamId: event.data[0].toString(),
+      viewer: event.data[1].toString(),
+      refunded: event.data[2].toString()
+    }));
+}
+
+function audit(entry) {
+  fs.appendFileSync(AUDIT_LOG_PATH, `${JSON.stringify(entry)}\n`);
+}
+
+function readAuditLog() {
+  if (!fs.existsSync(AUDIT_LOG_PATH)) {
+    return [];
+  }
+  return fs
+    .readFileSync(AUDIT_LOG_PATH, 'utf8')
+    .split('\n')
+    .filter((line) => line.length > 0)
+    .map((line) => JSON.parse(line));
+}
+
+function requireAdmin(req, res, next) {
+  if (!ADMIN_TOKEN || !ADMIN_SURI) {
+    return res.status(503).json({ error: 'Revocation is not configured' });
+  }
+  if (!hasBearerToken(req, ADMIN_TOKEN)) {
+    return res.status(401).json({ error: 'Unauthorized' });
+  }
+  next();
+}
+
+function revocationsRouter() {
+  const router = express.Router();
+  router.use(requireAdmin);
+
+  // Force every viewer off a stream
+  router.post('/streams/:streamId', async (req, res) => {
+    const { streamId } = req.params;
+    const entry = {
+      at: new Date().toISOString(),
+      operator: req.get('X-Operator') || 'unknown',
+      target: { stream: streamId },
+      reason: req.body.reason || null,
+      blocks: [],
+      revoked: []
+    };
+
+    try {
+      const api = await chainApi();
+      const batch = api.consts.tickStream.maxViewersPerBatch.toNumber();
+      for (let round = 0; round < MAX_STREAM_ROUNDS; round++) {
+        const { blockHash, events } = await sudo(
+          api,
+          api.tx.tickStream.forceLeaveStream(streamId, batch)
+        );
+        entry.blocks.push(blockHash);
+        entry.revoked.push(...refunds(api, events));
+
+        const revoked = events.find((event) =>
+          api.events.tickStream.StreamAccessRevoked.is(event)
+        );
+        if (!revoked || !revoked.data[2].isTrue) {
+          break;
+        }
+      }
+      audit(entry);
+      res.json(entry);
+    } catch (error) {
+      audit({ ...entry, error: error.message });
+      res.status(500).json({ error: error.message, revoked: entry.revoked });
+    }
+  });
+
+  // Force an account off every stream it is watching
+  router.post('/accounts/:account', async (req, res) => {
+    const { account } = req.params;
+    const entry = {
+      at: new Date().toISOString(),
+      operator: req.get('X-Operator') || 'unknown',
+      target: { account },
+      reason: req.body.reason || null,
+      blocks: [],
+      revoked: []
+    };
+
+    try {
+      const api = await chainApi();
+      const who = api.createType('AccountId', account).toString();
+      const streams = (await api.query.tickStream.balances.keys())
+        .map(({ args: [streamId, viewer] }) => [streamId.toString(), viewer.toString()])
+        .filter(([, viewer]) => viewer === who)
+        .map(([streamId]) => streamId);
+
+      const batch = api.consts.tickStream.maxViewersPerBatch.toNumber();
+      for (let start = 0; start < streams.length; start += batch) {
+        const { blockHash, events } = await sudo(
+          api,
+          api.tx.tickStream.forceLeaveAccount(who, streams.slice(start, start + batch))
+        );
+        entry.blocks.push(blockHash);
+        entry.revoked.push(...refunds(api, events));
+      }
+      audit(entry);
+      res.json(entry);
+    } catch (error) {
+      audit({ ...entry, error: error.message });
+      res.status(500).json({ error: error.message, revoked: entry.revoked });
+    }
+  });
+
+  // The audit log, most recent revocation first
+  router.get('/', (req, res) => {
+    res.json(readAuditLog().reverse());
+  });
+
+  return router;
+}
+
+module.exports = { revocationsRouter };
@@ -0,0 +1,335 @@
+const express = require('express');
+const crypto = require('crypto');
+const fs = require('fs');
+const path = require('path');
+const { Pool } = require('pg');
+const { hasBearerToken } = require('./bearer');
+
+// Per-organization API keys, quotas and usage for multi-tenant gateway deployments.
+//
+// Operators create organizations with a daily request quota and issue them API keys. Every
+// API request must then carry one of its organization's keys in `X-API-Key`, and is counted
+// against the organization's quota for the UTC day; requests over quota are refused with
+// 429 and counted separately. Organizations, keys and usage are kept in the indexer's
+// database. Without `INDEXER_DATABASE_URL` the gateway stays single-tenant and open.
+
+const DATABASE_URL = process.env.INDEXER_DATABASE_URL;
+const ADMIN_TOKEN = process.env.ADMIN_TOKEN;
+// Days a usage report covers when it is not given a range
+const DEFAULT_REPORT_DAYS = 30;
+// Upper bound on the days of one usage report
+const MAX_REPORT_DAYS = 366;
+
+const TODAY = "(NOW() AT TIME ZONE 'UTC')::DATE";
+
+let databasePromise;
+
+// Connect on first use and create the tenant tables if they do not exist yet
+function database() {
+  if (!databasePromise) {
+    const pool = new Pool({ connectionString: DATABASE_URL });
+    const schema = fs.readFileSync(path.join(__dirname, 'tenants.sql'), 'utf8');
+    databasePromise = pool
+      .query(schema)
+      .then(() => pool)
+      .catch((error) => {
+        databasePromise = undefined;
+        throw error;
+      });
+  }
+  return databasePromise;
+}
+
+function hashKey(key) {
+  return crypto.createHash('sha256').update(key).digest();
+}
+
+function isDay(day) {
+  return /^\d{4}-\d{2}-\d{2}$/.test(day) && !Number.isNaN(Date.parse(day));
+}
+
+function isQuota(quota) {
+  return Number.isInteger(quota) && quota >= 0 && quota <= 2147483647;
+}
+
+// Seconds until the quotas start over at the next UTC midnight
+function secondsUntilReset() {
+  const now = new Date();
+  const midnight = Date.UTC(now.getUTCFullYear(), now.getUTCMonth(), now.getUTCDate() + 1);
+  return Math.ceil((midnight - now.getTime()) / 1000);
+}
+
+// Count the request against its organization's quota for today, unless it is used up.
+// Resolves with the requests served today, or `null` if the request is over quota.
+async function meter(db, organizationId, quota) {
+  const served = await db.query(
+    `INSERT INTO api_usage (organization_id, day, requests)
+      SELECT $1, ${TODAY}, 1 WHERE $2 > 0
+    ON CONFLICT (organization_id, day) DO UPDATE SET requests = api_usage.requests + 1
+      WHERE api_usage.requests < $2
+    RETURNING requests`,
+    [organizationId, quota]
+  );
+  if (served.rows.length > 0) {
+    return served.rows[0].requests;
+  }
+  await db.query(
+    `INSERT INTO api_usage (organization_id, day, rejected) VALUES ($1, ${TODAY}, 1)
+    ON CONFLICT (organization_id, day) DO UPDATE SET rejected = api_usage.rejected + 1`,
+    [organizationId]
+  );
+  return null;
+}
+
+// Authenticate the request by its API key and meter it, leaving its organization on
+// `req.organization`
+function meterRequests() {
+  return async (req, res, next) => {
+    if (!DATABASE_URL) {
+      return next();
+    }
+    const key = req.get('X-API-Key');
+    if (!key) {
+      return res.status(401).json({ error: 'An API key is required in X-API-Key' });
+    }
+
+    try {
+      const db = await database();
+      const { rows } = await db.query(
+        `SELECT o.id, o.name, o.daily_quota FROM api_keys k
+        JOIN api_organizations o ON o.id = k.organization_id
+        WHERE k.key_hash = $1 AND k.revoked_at IS NULL`,
+        [hashKey(key)]
+      );
+      if (rows.length === 0) {
+        return res.status(401).json({ error: 'Unknown or revoked API key' });
+      }
+      const { id, name, daily_quota: dailyQuota } = rows[0];
+
+      const served = await meter(db, id, dailyQuota);
+      res.set('X-Quota-Limit', String(dailyQuota));
+      res.set('X-Quota-Remaining', String(served === null ? 0 : dailyQuota - served));
+      if (served === null) {
+        res.set('Retry-After', String(secondsUntilReset()));
+        return res.status(429).json({ error: 'Daily quota exceeded' });
+      }
+      req.organization = { id: String(id), name, dailyQuota };
+      next();
+    } catch (error) {
+      console.error(`Tenants: could not meter a request: ${error.message}`);
+      res.status(503).json({ error: 'Metering is unavailable' });
+    }
+  };
+}
+
+// An organization's usage per day from `from` to `to`, both UTC days and included
+async function usageReport(db, organization, from, to) {
+  const { rows } = await db.query(
+    `SELECT TO_CHAR(day, 'YYYY-MM-DD') AS day, requests, rejected FROM api_usage
+    WHERE organization_id = $1 AND day BETWEEN $2 AND $3
+    ORDER BY day`,
+    [organization.id, from, to]
+  );
+  const totals = rows.reduce(
+    (sum, { requests, rejected }) => ({
+      requests: sum.requests + requests,
+      rejected: sum.rejected + rejected
+    }),
+    { requests: 0, rejected: 0 }
+  );
+  return { organization, from, to, days: rows, totals };
+}
+
+// Answer a usage report request for `organization`, over the range in its query
+async function sendUsageReport(req, res, organization) {
+  const today = new Date().toISOString().slice(0, 10);
+  const to = req.query.to || today;
+  if (!isDay(to)) {
+    return res.status(400).json({ error: 'from and to must be days as YYYY-MM-DD' });
+  }
+  const from =
+    req.query.from ||
+    new Date(Date.parse(to) - (DEFAULT_REPORT_DAYS - 1) * 86400000).toISOString().slice(0, 10);
+  if (!isDay(from)) {
+    return res.status(400).json({ error: 'from and to must be days as YYYY-MM-DD' });
+  }
+  const days = (Date.parse(to) - Date.parse(from)) / 86400000 + 1;
+  if (days < 1 || days > MAX_REPORT_DAYS) {
+    return res
+      .status(400)
+      .json({ error: `A report covers between 1 and ${MAX_REPORT_DAYS} days` });
+  }
+
+  try {
+    const db = await database();
+    res.json(await usageReport(db, organization, from, to));
+  } catch (error) {
+    res.status(500).json({ error: error.message });
+  }
+}
+
+// The calling organization's own usage report
+function ownUsage(req, res) {
+  if (!req.organization) {
+    return res.status(404).json({ error: 'The gateway is not multi-tenant' });
+  }
+  sendUsageReport(req, res, req.organization);
+}
+
+function requireOperator(req, res, next) {
+  if (!ADMIN_TOKEN || !DATABASE_URL) {
+    return res.status(503).json({ error: 'Tenanting is not configured' });
+  }
+  if (!hasBearerToken(req, ADMIN_TOKEN)) {
+    return res.status(401).json({ error: 'Unauthorized' });
+  }
+  next();
+}
+
+function organizationView({ id, name, daily_quota: dailyQuota, created_at: createdAt }) {
+  return { id: String(id), name, dailyQuota, createdAt };
+}
+
+async function findOrganization(db, id) {
+  if (!/^\d+$/.test(id)) {
+    return null;
+  }
+  const { rows } = await db.query('SELECT * FROM api_organizations WHERE id = $1', [id]);
+  return rows.length > 0 ? organizationView(rows[0]) : null;
+}
+
+function organizationsRouter() {
+  const router = express.Router();
+  router.use(requireOperator);
+
+  // Every organization, with its requests so far today
+  router.get('/', async (req, res) => {
+    try {
+      const db = await database();
+      const { rows } = await db.query(
+        `SELECT o.*, COALESCE(u.requests, 0) AS requests_today,
+          COALESCE(u.rejected, 0) AS rejected_today
+        FROM api_organizations o
+        LEFT JOIN api_usage u ON u.organization_id = o.id AND u.day = ${TODAY}
+        ORDER BY o.id`
+      );
+      res.json(
+        rows.map((row) => ({
+          ...organizationView(row),
+          requestsToday: row.requests_today,
+          rejectedToday: row.rejected_today
+        }))
+      );
+    } catch (error) {
+      res.status(500).json({ error: error.message });
+    }
+  });
+
+  // Add an organization with a daily quota of requests
+  router.post('/', async (req, res) => {
+    const { name, dailyQuota } = req.body;
+    if (typeof name !== 'string' || name.trim().length === 0) {
+      return res.status(400).json({ error: 'name must be a non-empty string' });
+    }
+    if (!isQuota(dailyQuota)) {
+      return res.status(400).json({ error: 'dailyQuota must be a non-negative integer' });
+    }
+
+    try {
+      const db = await database();
+      const { rows } = await db.query(
+        `INSERT INTO api_organizations (name, daily_quota) VALUES ($1, $2)
+        ON CONFLICT (name) DO NOTHING
+        RETURNING *`,
+        [name.trim(), dailyQuota]
+      );
+      if (rows.length === 0) {
+        return res.status(409).json({ error: 'An organization with that name exists' });
+      }
+      res.status(201).json(organizationView(rows[0]));
+    } catch (error) {
+      res.status(500).json({ error: error.message });
+    }
+  });
+
+  // Change an organization's quota, from the current day on. A quota of 0 suspends it.
+  router.patch('/:id', async (req, res) => {
+    const { dailyQuota } = req.body;
+    if (!isQuota(dailyQuota)) {
+      return res.status(400).json({ error: 'dailyQuota must be a non-negative integer' });
+    }
+
+    try {
+      const db = await database();
+      if (!(await findOrganization(db, req.params.id))) {
+        return res.status(404).json({ error: 'Organization not found' });
+      }
+      const { rows } = await db.query(
+        'UPDATE api_organizations SET daily_quota = $2 WHERE id = $1 RETURNING *',
+        [req.params.id, dailyQuota]
+      );
+      res.json(organizationView(rows[0]));
+    } catch (error) {
+      res.status(500).json({ error: error.message });
+    }
+  });
+
+  // Issue an organization a new API key. The key is only ever shown in this response.
+  router.post('/:id/keys', async (req, res) => {
+    try {
+      const db = await database();
+      if (!(await findOrganization(db, req.params.id))) {
+        return res.status(404).json({ error: 'Organization not found' });
+      }
+      const key = `vk_${crypto.randomBytes(32).toString('hex')}`;
+      const { rows } = await db.query(
+        `INSERT INTO api_keys (organization_id, key_hash) VALUES ($1, $2)
+        RETURNING id, created_at`,
+        [req.params.id, hashKey(key)]
+      );
+      res.status(201).json({ id: String(rows[0].id), key, createdAt: rows[0].created_at });
+    } catch (error) {
+      res.status(500).json({ error: error.message });
+    }
+  });
+
+  // Revoke one of an organization's API keys
+  router.delete('/:id/keys/:keyId', async (req, res) => {
+    if (!/^\d+$/.test(req.params.id) || !/^\d+$/.test(req.params.keyId)) {
+      return res.status(404).json({ error: 'Key not found' });
+    }
+
+    try {
+      const db = await database();
+      const { rowCount } = await db.query(
+        `UPDATE api_keys SET revoked_at = NOW()
+        WHERE id = $2 AND organization_id = $1 AND revoked_at IS NULL`,
+        [req.params.id, req.params.keyId]
+      );
+      if (rowCount === 0) {
+        return res.status(404).json({ error: 'Key not found' });
+      }
+      res.status(204).end();
+    } catch (error) {
+      res.status(500).json({ error: error.message });
+    }
+  });
+
+  // An organization's usage per day, over the last 30 days unless `from` and `to` are given
+  router.get('/:id/usage', async (req, res) => {
+    try {
+      const db = await database();
+      const organization = await findOrganization(db, req.params.id);
+      if (!organization) {
+        return res.status(404).json({ error: 'Organization not found' });
+      }
+      await sendUsageReport(req, res, organization);
+    } catch (error) {
+      res.status(500).json({ error: error.message });
+    }
+  });
+
+  return router;
+}
+
+module.exports = { meterRequests, organizationsRouter, ownUsage };
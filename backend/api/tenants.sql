@@ -0,0 +1,27 @@
+-- Organizations sharing the gateway, their API keys and their metered usage, kept next to
+-- the indexer's tables in the analytics database.
+CREATE TABLE IF NOT EXISTS api_organizations (
+	id BIGSERIAL PRIMARY KEY,
+	name TEXT NOT NULL UNIQUE,
+	-- The most requests the organization may make per UTC day
+	daily_quota INTEGER NOT NULL,
+	created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Keys are only stored as their SHA-256 hash, so the database cannot leak them.
+CREATE TABLE IF NOT EXISTS api_keys (
+	id BIGSERIAL PRIMARY KEY,
+	organization_id BIGINT NOT NULL REFERENCES api_organizations (id) ON DELETE CASCADE,
+	key_hash BYTEA NOT NULL UNIQUE,
+	created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+	revoked_at TIMESTAMPTZ
+);
+
+-- Each organization's requests per UTC day: those served and those refused over quota.
+CREATE TABLE IF NOT EXISTS api_usage (
+	organization_id BIGINT NOT NULL REFERENCES api_organizations (id) ON DELETE CASCADE,
+	day DATE NOT NULL,
+	requests INTEGER NOT NULL DEFAULT 0,
+	rejected INTEGER NOT NULL DEFAULT 0,
+	PRIMARY KEY (organization_id, day)
+);
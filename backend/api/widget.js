@@ -0,0 +1,311 @@
+const express = require('express');
+const crypto = require('crypto');
+const dns = require('dns').promises;
+const https = require('https');
+const net = require('net');
+const QRCode = require('qrcode');
+const { ApiPromise, WsProvider } = require('@polkadot/api');
+
+// The server side of the embeddable pay-per-second player.
+//
+// A website embedding the widget opens a session for a viewer and a stream, shows the viewer
+// the returned deep link or QR code to sign `join_stream_v2` in their wallet, and polls the
+// session until the join is finalized. Sites may instead pass an https webhook URL, which is
+// sent the session every time its status changes. Webhooks never reach loopback, private or
+// link-local addresses, and operators can limit them to `WIDGET_WEBHOOK_HOSTS`.
+//
+// Sessions are kept in memory: they only live until the join finalizes or they expire.
+
+const NODE_WS_URL = process.env.NODE_WS_URL || 'ws://127.0.0.1:9944';
+const WALLET_LINK_URL = process.env.WALLET_LINK_URL || 'vilokanam://sign';
+const WEBHOOK_SECRET = process.env.WIDGET_WEBHOOK_SECRET;
+// The only hosts webhooks may be sent to, comma separated, or any public host if unset
+const WEBHOOK_HOSTS = (process.env.WIDGET_WEBHOOK_HOSTS || '')
+  .split(',')
+  .map((host) => host.trim().toLowerCase())
+  .filter((host) => host.length > 0);
+// Seconds a session waits for its join before it expires
+const SESSION_TTL = Number(process.env.WIDGET_SESSION_TTL || 900);
+// Seconds a finished session can still be polled
+const SESSION_RETENTION = 3600;
+// Upper bound on the watch time one session reserves, so a typo cannot lock a wallet
+const MAX_SECONDS = 24 * 60 * 60;
+// Upper bound on sessions kept at once, so the gateway's memory stays bounded
+const MAX_SESSIONS = 10000;
+// Seconds a webhook may take to respond before it is given up on
+const WEBHOOK_TIMEOUT = 10;
+
+// Addresses of the gateway's own networks rather than the embedding site's
+const INTERNAL_ADDRESSES = new net.BlockList();
+for (const [network, prefix] of [
+  ['0.0.0.0', 8],
+  ['10.0.0.0', 8],
+  ['100.64.0.0', 10],
+  ['127.0.0.0', 8],
+  ['169.254.0.0', 16],
+  ['172.16.0.0', 12],
+  ['192.168.0.0', 16],
+  ['224.0.0.0', 3]
+]) {
+  INTERNAL_ADDRESSES.addSubnet(network, prefix, 'ipv4');
+}
+for (const [network, prefix] of [
+  ['::', 127],
+  ['fc00::', 7],
+  ['fe80::', 10],
+  ['ff00::', 8]
+]) {
+  INTERNAL_ADDRESSES.addSubnet(network, prefix, 'ipv6');
+}
+
+const sessions = new Map();
+
+let apiPromise;
+let watching;
+
+function chainApi() {
+  if (!apiPromise) {
+    apiPromise = ApiPromise.create({ provider: new WsProvider(NODE_WS_URL) });
+  }
+  return apiPromise;
+}
+
+// Mark sessions whose join finalized in each new finalized block, from its `ViewerJoined`
+// events. The derive fills in the blocks finalized along with a newer one, which the plain
+// RPC subscription skips.
+function watchFinalized(api) {
+  if (!watching) {
+    watching = api.derive.chain.subscribeFinalizedHeads(async (header) => {
+      try {
+        const at = await api.at(header.hash);
+        const events = await at.query.system.events();
+        for (const { event } of events) {
+          if (!api.events.tickStream.ViewerJoined.is(event)) {
+            continue;
+          }
+          const streamId = event.data[0].toString();
+          const viewer = event.data[1].toString();
+          for (const session of sessions.values()) {
+            if (
+              session.status === 'pending' &&
+              session.streamId === streamId &&
+              session.viewer === viewer
+            ) {
+              update(session, {
+                status: 'joined',
+                blockNumber: header.number.toNumber(),
+                blockHash: header.hash.toHex()
+              });
+            }
+          }
+        }
+      } catch (error) {
+        console.error(`Widget: could not read block ${header.hash.toHex()}: ${error.message}`);
+      }
+      sweep();
+    });
+  }
+  return watching;
+}
+
+// Expire sessions that waited too long and forget those finished long enough ago
+function sweep() {
+  const now = Date.now();
+  for (const [id, session] of sessions) {
+    if (session.status === 'pending' && now >= Date.parse(session.expiresAt)) {
+      update(session, { status: 'expired' });
+    }
+    const retainedUntil = Date.parse(session.updatedAt) + SESSION_RETENTION * 1000;
+    if (session.status !== 'pending' && now >= retainedUntil) {
+      sessions.delete(id);
+    }
+  }
+}
+
+function update(session, changes) {
+  Object.assign(session, changes, { updatedAt: new Date().toISOString() });
+  notify(session);
+}
+
+// Post the session to its webhook, signed with `WIDGET_WEBHOOK_SECRET` if set
+async function notify(session) {
+  if (!session.webhookUrl) {
+    return;
+  }
+  const body = JSON.stringify(view(session));
+  const headers = { 'Content-Type': 'application/json' };
+  if (WEBHOOK_SECRET) {
+    const signature = crypto.createHmac('sha256', WEBHOOK_SECRET).update(body).digest('hex');
+    headers['X-Widget-Signature'] = `sha256=${signature}`;
+  }
+  try {
+    // The host is resolved again, as it may point elsewhere since the session was opened
+    const addresses = await webhookAddresses(session.webhookUrl);
+    if (!addresses) {
+      throw new Error('the host no longer resolves to public addresses only');
+    }
+    await post(session.webhookUrl, addresses[0], headers, body);
+  } catch (error) {
+    console.error(`Widget: webhook for session ${session.id} failed: ${error.message}`);
+  }
+}
+
+// POST `body` to `url`, connecting to the checked `address` rather than resolving the host
+// again, so it cannot be rebound to an internal address in between. Redirects are not
+// followed.
+function post(url, { address, family }, headers, body) {
+  return new Promise((resolve, reject) => {
+    const request = https.request(
+      url,
+      {
+        method: 'POST',
+        headers: { ...headers, 'Content-Length': Buffer.byteLength(body) },
+        lookup: (hostname, options, callback) =>
+          options.all ? callback(null, [{ address, family }]) : callback(null, address, family),
+        timeout: WEBHOOK_TIMEOUT * 1000
+      },
+      (response) => {
+        response.resume();
+        if (response.statusCode >= 300) {
+          reject(new Error(`responded with ${response.statusCode}`));
+        } else {
+          response.on('end', resolve);
+        }
+      }
+    );
+    request.on('timeout', () => request.destroy(new Error('timed out')));
+    request.on('error', reject);
+    request.end(body);
+  });
+}
+
+// A session as returned to the embedding site, without its webhook
+function view(session) {
+  const { webhookUrl, ...rest } = session;
+  return rest;
+}
+
+// The addresses the host of `url` resolves to, if it is an https URL on a permitted host
+// that only resolves to public addresses, or null
+async function webhookAddresses(url) {
+  let parsed;
+  try {
+    parsed = new URL(url);
+  } catch (error) {
+    return null;
+  }
+  if (parsed.protocol !== 'https:' || parsed.username || parsed.password) {
+    return null;
+  }
+  const host = parsed.hostname.replace(/^\[(.*)\]$/, '$1').toLowerCase();
+  if (WEBHOOK_HOSTS.length > 0 && !WEBHOOK_HOSTS.includes(host)) {
+    return null;
+  }
+  let addresses;
+  try {
+    addresses = await dns.lookup(host, { all: true, verbatim: true });
+  } catch (error) {
+    return null;
+  }
+  const internal = ({ address, family }) =>
+    INTERNAL_ADDRESSES.check(address, family === 6 ? 'ipv6' : 'ipv4');
+  return addresses.length > 0 && !addresses.some(internal) ? addresses : null;
+}
+
+async function isWebhookUrl(url) {
+  return (await webhookAddresses(url)) !== null;
+}
+
+function widgetRouter() {
+  const router = express.Router();
+
+  // Open a session for `viewer` to join `streamId` for `seconds` of watch time
+  router.post('/sessions', async (req, res) => {
+    const { streamId, viewer, seconds, maxPricePerSecond, referrer, webhookUrl } = req.body;
+    if (!/^\d+$/.test(String(streamId))) {
+      return res.status(400).json({ error: 'streamId must be a stream id' });
+    }
+    if (!Number.isInteger(seconds) || seconds <= 0 || seconds > MAX_SECONDS) {
+      return res.status(400).json({ error: `seconds must be between 1 and ${MAX_SECONDS}` });
+    }
+    if (webhookUrl !== undefined && !(await isWebhookUrl(webhookUrl))) {
+      return res
+        .status(400)
+        .json({ error: 'webhookUrl must be an https URL on a permitted, public host' });
+    }
+
+    sweep();
+    if (sessions.size >= MAX_SESSIONS) {
+      return res.status(503).json({ error: 'Too many open sessions, try again later' });
+    }
+
+    try {
+      const api = await chainApi();
+      let who;
+      try {
+        who = api.createType('AccountId', viewer).toString();
+      } catch (error) {
+        return res.status(400).json({ error: 'viewer must be an account address' });
+      }
+
+      const stream = await api.query.tickStream.streams(String(streamId));
+      if (stream.isNone) {
+        return res.status(404).json({ error: 'Stream not found' });
+      }
+      if (stream.unwrap().status.isEnded) {
+        return res.status(409).json({ error: 'Stream has ended' });
+      }
+
+      // Unless the site caps the price, the viewer agrees to the price they are shown
+      const maxPrice = maxPricePerSecond ?? stream.unwrap().pricePerSecond.toString();
+      const call = api.tx.tickStream.joinStreamV2(
+        String(streamId),
+        seconds,
+        referrer || null,
+        maxPrice
+      );
+      const callHex = call.method.toHex();
+      const genesisHash = api.genesisHash.toHex();
+      const deepLink =
+        `${WALLET_LINK_URL}?genesis=${genesisHash}&call=${callHex}` +
+        `&address=${encodeURIComponent(who)}`;
+
+      const now = new Date();
+      const session = {
+        id: crypto.randomUUID(),
+        status: 'pending',
+        streamId: String(streamId),
+        viewer: who,
+        seconds,
+        maxPricePerSecond: String(maxPrice),
+        call: callHex,
+        genesisHash,
+        deepLink,
+        qrCode: await QRCode.toDataURL(deepLink),
+        createdAt: now.toISOString(),
+        updatedAt: now.toISOString(),
+        expiresAt: new Date(now.getTime() + SESSION_TTL * 1000).toISOString(),
+        webhookUrl
+      };
+      await watchFinalized(api);
+      sessions.set(session.id, session);
+      res.status(201).json(view(session));
+    } catch (error) {
+      res.status(500).json({ error: error.message });
+    }
+  });
+
+  // A session's status: `pending` until the join finalizes, then `joined`, or `expired`
+  router.get('/sessions/:id', (req, res) => {
+    sweep();
+    const session = sessions.get(req.params.id);
+    if (!session) {
+      return res.status(404).json({ error: 'Session not found' });
+    }
+    res.json(view(session));
+  });
+
+  return router;
+}
+
+module.exports = { widgetRouter };
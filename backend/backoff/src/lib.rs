@@ -0,0 +1,49 @@
+//! Exponential backoff between retries after RPC errors, shared by the ticker and indexer.
+
+use std::time::Duration;
+
+/// Doubles the wait between retries, up to a limit, until a retry succeeds.
+#[derive(Debug)]
+pub struct Backoff {
+	/// The wait before the first retry.
+	initial: Duration,
+	/// The longest wait between retries.
+	max: Duration,
+	/// The wait before the next retry.
+	next: Duration,
+}
+
+impl Backoff {
+	/// A backoff waiting `initial` before the first retry and at most `max` before any.
+	pub fn new(initial: Duration, max: Duration) -> Self {
+		let initial = initial.min(max);
+		Self { initial, max, next: initial }
+	}
+
+	/// How long to wait before retrying, doubling the wait after that.
+	pub fn next_delay(&mut self) -> Duration {
+		let delay = self.next;
+		self.next = self.next.saturating_mul(2).min(self.max);
+		delay
+	}
+
+	/// Start over from the initial wait, once a retry has succeeded.
+	pub fn reset(&mut self) {
+		self.next = self.initial;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn waits_double_up_to_the_limit() {
+		let mut backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(5));
+		let delays = (0..5).map(|_| backoff.next_delay().as_secs()).collect::<Vec<_>>();
+		assert_eq!(delays, [1, 2, 4, 5, 5]);
+
+		backoff.reset();
+		assert_eq!(backoff.next_delay(), Duration::from_secs(1));
+	}
+}
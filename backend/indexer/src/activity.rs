@@ -0,0 +1,122 @@
+//! Per-account activity of a block, folded from its tick-stream events.
+//!
+//! Unlike the per-stream statistics, this is personal data: it is what an account's data
+//! export contains and what its erasure removes.
+
+use std::collections::BTreeMap;
+use subxt::ext::codec::Decode;
+
+/// An account's raw 32-byte ID.
+pub type Account = [u8; 32];
+
+/// What one account did on one stream in one block.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccountActivity {
+	/// Whether the account joined the stream.
+	pub joined: bool,
+	/// The seconds of watch time the account was charged for.
+	pub seconds_watched: u64,
+	/// What the account paid for watch time, in the smallest unit.
+	pub spent: u128,
+	/// What the account tipped, in the smallest unit.
+	pub tipped: u128,
+	/// The messages sent with the account's tips.
+	pub tip_messages: Vec<Vec<u8>>,
+}
+
+/// A tick-stream event about one account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AccountEvent {
+	Joined { account: Account, stream_id: u128 },
+	Watched { account: Account, stream_id: u128, seconds: u32, spent: u128 },
+	Tipped { account: Account, stream_id: u128, amount: u128, message: Option<Vec<u8>> },
+}
+
+impl AccountEvent {
+	/// The `TickStream` event `variant` with the SCALE-encoded `fields`, if it is about one
+	/// account.
+	pub fn decode(variant: &str, mut fields: &[u8]) -> Option<Self> {
+		let fields = &mut fields;
+		match variant {
+			"ViewerJoined" => {
+				let (stream_id, account) = <(u128, Account)>::decode(fields).ok()?;
+				Some(Self::Joined { account, stream_id })
+			},
+			"TickRecorded" => {
+				let (stream_id, account, seconds, spent) =
+					<(u128, Account, u32, u128)>::decode(fields).ok()?;
+				Some(Self::Watched { account, stream_id, seconds, spent })
+			},
+			"TipReceived" => {
+				let (stream_id, account, amount, message) =
+					<(u128, Account, u128, Option<Vec<u8>>)>::decode(fields).ok()?;
+				Some(Self::Tipped { account, stream_id, amount, message })
+			},
+			_ => None,
+		}
+	}
+}
+
+/// Fold the events of one block into each account's activity per stream.
+pub fn aggregate(
+	events: impl IntoIterator<Item = AccountEvent>,
+) -> BTreeMap<(Account, u128), AccountActivity> {
+	let mut accounts = BTreeMap::<(Account, u128), AccountActivity>::new();
+	for event in events {
+		match event {
+			AccountEvent::Joined { account, stream_id } =>
+				accounts.entry((account, stream_id)).or_default().joined = true,
+			AccountEvent::Watched { account, stream_id, seconds, spent } => {
+				let activity = accounts.entry((account, stream_id)).or_default();
+				activity.seconds_watched = activity.seconds_watched.saturating_add(seconds.into());
+				activity.spent = activity.spent.saturating_add(spent);
+			},
+			AccountEvent::Tipped { account, stream_id, amount, message } => {
+				let activity = accounts.entry((account, stream_id)).or_default();
+				activity.tipped = activity.tipped.saturating_add(amount);
+				activity.tip_messages.extend(message);
+			},
+		}
+	}
+	accounts
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use subxt::ext::codec::Encode;
+
+	#[test]
+	fn accounts_activity_is_summed_per_stream() {
+		let tick = (7u128, [1u8; 32], 6u32, 60u128, Some(5u128)).encode();
+		let tip = (7u128, [1u8; 32], 100u128, Some(b"gg".to_vec())).encode();
+		let joined = (8u128, [1u8; 32]).encode();
+		let events = [
+			("TickRecorded", &tick),
+			("TipReceived", &tip),
+			("ViewerJoined", &joined),
+			("TickRecorded", &tick),
+		]
+		.into_iter()
+		.filter_map(|(variant, fields)| AccountEvent::decode(variant, fields))
+		.collect::<Vec<_>>();
+		assert_eq!(AccountEvent::decode("StreamStarted", &7u128.encode()), None);
+
+		assert_eq!(
+			aggregate(events),
+			BTreeMap::from([
+				(
+					([1; 32], 7),
+					AccountActivity {
+						joined: false,
+						seconds_watched: 12,
+						spent: 120,
+						tipped: 100,
+						tip_messages: vec![b"gg".to_vec()],
+					}
+				),
+				(([1; 32], 8), AccountActivity { joined: true, ..Default::default() }),
+			])
+		);
+	}
+}
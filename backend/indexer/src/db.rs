@@ -0,0 +1,323 @@
+//! The PostgreSQL tables the analytics dashboard reads.
+//!
+//! Each block's statistics are written in one transaction together with the block's hash, so
+//! writing a block again changes nothing, and a block seen with a different hash than the one
+//! indexed at its height replaces it and every block indexed above it.
+//!
+//! Accounts' own activity is kept apart from the per-stream statistics, so an account's
+//! erasure deletes it without touching the aggregates, and leaves a tombstone that keeps it
+//! from being indexed again.
+//!
+//! The schema is built by versioned migrations, each applied once and in order on connecting,
+//! and recorded in `schema_migrations`. Applied migrations never change: the schema is changed
+//! by appending one.
+
+use crate::{
+	activity::{Account, AccountActivity},
+	stats::BlockStats,
+};
+use std::collections::BTreeMap;
+use tokio_postgres::{Client, NoTls};
+
+/// A change to the schema.
+struct Migration {
+	version: i32,
+	name: &'static str,
+	sql: &'static str,
+	/// Whether blocks indexed before the migration lack data it adds until they are backfilled.
+	backfill: bool,
+}
+
+/// Every migration, in the order they are applied.
+const MIGRATIONS: &[Migration] = &[
+	Migration {
+		version: 1,
+		name: "stream statistics",
+		sql: include_str!("migrations/0001_stream_statistics.sql"),
+		backfill: false,
+	},
+	Migration {
+		version: 2,
+		name: "account activity",
+		sql: include_str!("migrations/0002_account_activity.sql"),
+		backfill: true,
+	},
+];
+
+/// The advisory lock held while migrating, so processes connecting at once migrate one after
+/// another.
+const MIGRATION_LOCK: i64 = 0x7469_636b_7374_7265;
+
+/// A connection to the analytics database.
+pub struct Database {
+	client: Client,
+}
+
+impl Database {
+	/// Connect to the database at `url` and apply the migrations it has not had yet.
+	pub async fn connect(url: &str) -> Result<Self, tokio_postgres::Error> {
+		let (client, connection) = tokio_postgres::connect(url, NoTls).await?;
+		tokio::spawn(async move {
+			if let Err(e) = connection.await {
+				eprintln!("Database connection closed: {}", e);
+			}
+		});
+		let mut db = Self { client };
+		let applied = db.migrate().await?;
+		if applied.iter().any(|migration| migration.backfill) {
+			let row = db.client.query_one("SELECT MIN(number) FROM indexed_blocks", &[]).await?;
+			if let Some(first) = row.get::<_, Option<i64>>(0) {
+				println!(
+					"Blocks indexed before these migrations lack their data; run `indexer \
+					backfill --from-block {}` to index them again",
+					first
+				);
+			}
+		}
+		Ok(db)
+	}
+
+	/// Apply the migrations not applied yet, in order and in one transaction, returning them.
+	async fn migrate(&mut self) -> Result<Vec<&'static Migration>, tokio_postgres::Error> {
+		let tx = self.client.transaction().await?;
+		tx.execute("SELECT pg_advisory_xact_lock($1)", &[&MIGRATION_LOCK]).await?;
+		tx.batch_execute(
+			"CREATE TABLE IF NOT EXISTS schema_migrations (
+				version INTEGER PRIMARY KEY,
+				name TEXT NOT NULL,
+				applied_at TIMESTAMPTZ NOT NULL
+			)",
+		)
+		.await?;
+		let done = tx
+			.query("SELECT version FROM schema_migrations", &[])
+			.await?
+			.into_iter()
+			.map(|row| row.get::<_, i32>(0))
+			.collect::<Vec<_>>();
+
+		let mut applied = Vec::new();
+		for migration in MIGRATIONS.iter().filter(|migration| !done.contains(&migration.version)) {
+			tx.batch_execute(migration.sql).await?;
+			tx.execute(
+				"INSERT INTO schema_migrations (version, name, applied_at) VALUES ($1, $2, NOW())",
+				&[&migration.version, &migration.name],
+			)
+			.await?;
+			println!("Applied migration {}: {}", migration.version, migration.name);
+			applied.push(migration);
+		}
+		tx.commit().await?;
+		Ok(applied)
+	}
+
+	/// The highest block indexed, if any.
+	pub async fn last_indexed(&self) -> Result<Option<u64>, tokio_postgres::Error> {
+		let row = self.client.query_one("SELECT MAX(number) FROM indexed_blocks", &[]).await?;
+		Ok(row.get::<_, Option<i64>>(0).map(|number| number as u64))
+	}
+
+	/// Write the statistics of the block `number` with `hash`, made at `timestamp` Unix
+	/// milliseconds, returning whether they were written. A block already indexed is left as
+	/// it is, unless `reindex` is set, in which case what was indexed for it is replaced.
+	pub async fn write_block(
+		&mut self,
+		number: u64,
+		hash: &[u8],
+		timestamp: u64,
+		streams: &BTreeMap<u128, BlockStats>,
+		accounts: &BTreeMap<(Account, u128), AccountActivity>,
+		reindex: bool,
+	) -> Result<bool, tokio_postgres::Error> {
+		let number = number as i64;
+		let timestamp = timestamp as i64;
+		let tx = self.client.transaction().await?;
+
+		let indexed = tx
+			.query_opt("SELECT hash FROM indexed_blocks WHERE number = $1", &[&number])
+			.await?
+			.map(|row| row.get::<_, Vec<u8>>(0));
+		match indexed {
+			Some(indexed) if indexed == hash && !reindex => return Ok(false),
+			Some(indexed) if indexed == hash => {
+				tx.execute("DELETE FROM indexed_blocks WHERE number = $1", &[&number]).await?;
+			},
+			// A reorg: what was indexed from the old fork down is stale
+			Some(_) => {
+				tx.execute("DELETE FROM indexed_blocks WHERE number >= $1", &[&number]).await?;
+			},
+			None => {},
+		}
+
+		tx.execute(
+			"INSERT INTO indexed_blocks (number, hash, timestamp)
+			VALUES ($1, $2, TO_TIMESTAMP($3::BIGINT / 1000.0))",
+			&[&number, &hash, &timestamp],
+		)
+		.await?;
+		let insert = tx
+			.prepare(
+				"INSERT INTO stream_block_stats
+					(stream_id, block_number, minute, viewers, seconds_watched, revenue)
+				VALUES (
+					$1::TEXT::NUMERIC,
+					$2,
+					DATE_TRUNC('minute', TO_TIMESTAMP($3::BIGINT / 1000.0)),
+					$4,
+					$5,
+					$6::TEXT::NUMERIC
+				)
+				ON CONFLICT (stream_id, block_number) DO UPDATE SET
+					minute = EXCLUDED.minute,
+					viewers = EXCLUDED.viewers,
+					seconds_watched = EXCLUDED.seconds_watched,
+					revenue = EXCLUDED.revenue",
+			)
+			.await?;
+		for (stream_id, stats) in streams {
+			let viewers = stats.viewers.map(|viewers| viewers as i32);
+			let seconds_watched = stats.seconds_watched as i64;
+			tx.execute(
+				&insert,
+				&[
+					&stream_id.to_string(),
+					&number,
+					&timestamp,
+					&viewers,
+					&seconds_watched,
+					&stats.revenue.to_string(),
+				],
+			)
+			.await?;
+		}
+		let insert = tx
+			.prepare(
+				"INSERT INTO account_block_activity
+					(account, stream_id, block_number, joined, seconds_watched, spent, tipped,
+					tip_messages)
+				SELECT
+					$1::BYTEA,
+					$2::TEXT::NUMERIC,
+					$3::BIGINT,
+					$4::BOOLEAN,
+					$5::BIGINT,
+					$6::TEXT::NUMERIC,
+					$7::TEXT::NUMERIC,
+					$8::BYTEA[]
+				WHERE NOT EXISTS (SELECT 1 FROM erased_accounts WHERE account = $1::BYTEA)",
+			)
+			.await?;
+		for ((account, stream_id), activity) in accounts {
+			tx.execute(
+				&insert,
+				&[
+					&&account[..],
+					&stream_id.to_string(),
+					&number,
+					&activity.joined,
+					&(activity.seconds_watched as i64),
+					&activity.spent.to_string(),
+					&activity.tipped.to_string(),
+					&activity.tip_messages,
+				],
+			)
+			.await?;
+		}
+
+		tx.commit().await?;
+		Ok(true)
+	}
+
+	/// Everything indexed about `account`, oldest first.
+	pub async fn account_activity(
+		&self,
+		account: &Account,
+	) -> Result<Vec<ActivityRecord>, tokio_postgres::Error> {
+		let rows = self
+			.client
+			.query(
+				"SELECT
+					a.stream_id::TEXT,
+					a.block_number,
+					(EXTRACT(EPOCH FROM b.timestamp) * 1000)::BIGINT,
+					a.joined,
+					a.seconds_watched,
+					a.spent::TEXT,
+					a.tipped::TEXT,
+					a.tip_messages
+				FROM account_block_activity a
+				JOIN indexed_blocks b ON b.number = a.block_number
+				WHERE a.account = $1
+				ORDER BY a.block_number, a.stream_id",
+				&[&&account[..]],
+			)
+			.await?;
+		let number = |text: String| text.parse::<u128>().unwrap_or_default();
+		Ok(rows
+			.into_iter()
+			.map(|row| ActivityRecord {
+				stream_id: number(row.get(0)),
+				block_number: row.get::<_, i64>(1) as u64,
+				timestamp: row.get::<_, i64>(2) as u64,
+				activity: AccountActivity {
+					joined: row.get(3),
+					seconds_watched: row.get::<_, i64>(4) as u64,
+					spent: number(row.get(5)),
+					tipped: number(row.get(6)),
+					tip_messages: row.get(7),
+				},
+			})
+			.collect())
+	}
+
+	/// When `account`'s data was erased, in Unix milliseconds, if it was.
+	pub async fn erased_at(&self, account: &Account) -> Result<Option<u64>, tokio_postgres::Error> {
+		let row = self
+			.client
+			.query_opt(
+				"SELECT (EXTRACT(EPOCH FROM erased_at) * 1000)::BIGINT
+				FROM erased_accounts WHERE account = $1",
+				&[&&account[..]],
+			)
+			.await?;
+		Ok(row.map(|row| row.get::<_, i64>(0) as u64))
+	}
+
+	/// Delete everything indexed about `account` and stop indexing it, returning the number
+	/// of records deleted.
+	pub async fn erase_account(&mut self, account: &Account) -> Result<u64, tokio_postgres::Error> {
+		let tx = self.client.transaction().await?;
+		let deleted = tx
+			.execute("DELETE FROM account_block_activity WHERE account = $1", &[&&account[..]])
+			.await?;
+		tx.execute(
+			"INSERT INTO erased_accounts (account, erased_at) VALUES ($1, NOW())
+			ON CONFLICT (account) DO NOTHING",
+			&[&&account[..]],
+		)
+		.await?;
+		tx.commit().await?;
+		Ok(deleted)
+	}
+}
+
+/// What an account did on a stream in an indexed block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ActivityRecord {
+	pub stream_id: u128,
+	pub block_number: u64,
+	/// When the block was made, in Unix milliseconds.
+	pub timestamp: u64,
+	pub activity: AccountActivity,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn migrations_are_numbered_in_order() {
+		let versions = MIGRATIONS.iter().map(|migration| migration.version).collect::<Vec<_>>();
+		assert_eq!(versions, (1..=MIGRATIONS.len() as i32).collect::<Vec<_>>());
+	}
+}
@@ -0,0 +1,279 @@
+use backoff::Backoff;
+use clap::{Parser, Subcommand};
+use futures::StreamExt;
+use std::{net::SocketAddr, time::Duration};
+use subxt::{
+	backend::{legacy::LegacyRpcMethods, rpc::RpcClient},
+	blocks::Block,
+	utils::H256,
+	OnlineClient, SubstrateConfig,
+};
+use tokio::sync::mpsc;
+
+mod activity;
+mod db;
+mod privacy;
+mod runtime;
+mod sink;
+mod stats;
+use activity::AccountEvent;
+use db::Database;
+use runtime::Runtimes;
+use sink::{Point, Sink, SinkOptions};
+use stats::StreamEvent;
+
+/// Indexes the tick-stream events of every finalized block into PostgreSQL, as per-stream
+/// time series for the analytics dashboard
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+	/// The URL of the Substrate node to connect to
+	#[clap(long, default_value = "ws://127.0.0.1:9944")]
+	url: String,
+
+	/// The PostgreSQL connection string of the analytics database
+	#[clap(long, default_value = "postgres://postgres@localhost/vilokanam")]
+	database_url: String,
+
+	/// The block to start from when the database is empty, instead of the latest finalized
+	/// one; `backfill` indexes blocks again once the database is not
+	#[clap(long)]
+	from_block: Option<u64>,
+
+	/// The longest wait between reconnection attempts after an error, in seconds
+	#[clap(long, default_value = "60")]
+	max_backoff: u64,
+
+	/// Also push each block's per-stream metrics to the InfluxDB 2 server at this URL, such
+	/// as `http://localhost:8086`
+	#[clap(long, conflicts_with = "timescale_url")]
+	influx_url: Option<String>,
+
+	/// The InfluxDB organization metrics are pushed to
+	#[clap(long, default_value = "vilokanam")]
+	influx_org: String,
+
+	/// The InfluxDB bucket metrics are pushed to
+	#[clap(long, default_value = "streams")]
+	influx_bucket: String,
+
+	/// The API token metrics are pushed to InfluxDB with
+	#[clap(long)]
+	influx_token: Option<String>,
+
+	/// Also push each block's per-stream metrics to the TimescaleDB database at this
+	/// PostgreSQL connection string
+	#[clap(long)]
+	timescale_url: Option<String>,
+
+	/// The most metrics pushed in one write
+	#[clap(long, default_value = "500")]
+	sink_batch_size: usize,
+
+	/// The longest metrics wait before they are pushed, in seconds
+	#[clap(long, default_value = "10")]
+	sink_flush_interval: u64,
+
+	/// How many times a failed push is retried before its metrics are dropped
+	#[clap(long, default_value = "5")]
+	sink_max_retries: u32,
+
+	/// Answer accounts' signed requests to export or erase their indexed data on this address
+	#[clap(long)]
+	privacy_api_addr: Option<SocketAddr>,
+
+	/// How far an account data request's timestamp may be from this process' clock, in
+	/// seconds
+	#[clap(long, default_value = "300")]
+	request_window: u64,
+
+	#[clap(subcommand)]
+	command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+	/// Index finalized blocks again from a block on, replacing what was indexed for them, then
+	/// exit. Metrics are not pushed to InfluxDB or TimescaleDB.
+	Backfill {
+		/// The first block to index again
+		#[clap(long)]
+		from_block: u64,
+
+		/// The last block to index again, instead of the latest finalized one
+		#[clap(long)]
+		to_block: Option<u64>,
+	},
+}
+
+type Client = OnlineClient<SubstrateConfig>;
+type Error = Box<dyn std::error::Error>;
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+	let args = Args::parse();
+
+	if let Some(Command::Backfill { from_block, to_block }) = args.command {
+		return backfill(&args, from_block, to_block).await
+	}
+
+	println!("Indexing tick-stream events from {} into the analytics database...", args.url);
+
+	let sink = match (&args.influx_url, &args.timescale_url) {
+		(Some(url), _) => Some(Sink::influx(
+			url,
+			args.influx_org.clone(),
+			args.influx_bucket.clone(),
+			args.influx_token.clone(),
+		)),
+		(None, Some(url)) => Some(Sink::timescale(url.clone())),
+		(None, None) => None,
+	};
+	if let Some(addr) = args.privacy_api_addr {
+		let window = Duration::from_secs(args.request_window);
+		let server = privacy::serve(addr, args.database_url.clone(), window);
+		tokio::spawn(async move {
+			if let Err(e) = server.await {
+				eprintln!("Account data server stopped: {}", e);
+			}
+		});
+	}
+
+	let metrics = sink.map(|sink| {
+		sink.spawn(SinkOptions {
+			batch_size: args.sink_batch_size,
+			flush_interval: Duration::from_secs(args.sink_flush_interval.max(1)),
+			max_retries: args.sink_max_retries,
+			max_backoff: Duration::from_secs(args.max_backoff),
+		})
+	});
+
+	let mut backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(args.max_backoff));
+	loop {
+		let result = follow(&args, &mut backoff, metrics.as_ref()).await;
+		let delay = backoff.next_delay();
+		match result {
+			Ok(()) => println!("Block subscription ended, reconnecting in {:?}", delay),
+			Err(e) => eprintln!("Error: {}, reconnecting in {:?}", e, delay),
+		}
+		tokio::time::sleep(delay).await;
+	}
+}
+
+/// Index every finalized block until the subscription ends or an RPC call or write fails.
+///
+/// Indexing resumes after the last block in the database, so blocks finalized while the
+/// indexer was away are caught up on before the newest. Newly indexed blocks' metrics are
+/// sent on `metrics`, if given.
+async fn follow(
+	args: &Args,
+	backoff: &mut Backoff,
+	metrics: Option<&mpsc::Sender<Vec<Point>>>,
+) -> Result<(), Error> {
+	let (mut db, legacy, client) = connect(args).await?;
+	let mut runtimes = Runtimes::new(legacy.clone(), client.clone());
+
+	let mut next = match db.last_indexed().await? {
+		Some(last) => Some(last + 1),
+		None => args.from_block,
+	};
+	let mut blocks = client.blocks().subscribe_finalized().await?;
+	while let Some(block) = blocks.next().await {
+		let block = block?;
+		let number = u64::from(block.number());
+		for missed in next.unwrap_or(number)..number {
+			let missed = client.blocks().at(block_hash(&legacy, missed).await?).await?;
+			index_block(&missed, &mut db, &mut runtimes, metrics, false).await?;
+		}
+		index_block(&block, &mut db, &mut runtimes, metrics, false).await?;
+		next = Some(number + 1);
+		backoff.reset();
+	}
+
+	Ok(())
+}
+
+/// Index the finalized blocks `from` to `to`, or to the latest finalized block, again, each
+/// with the metadata of the runtime that built it.
+///
+/// Every block is replaced in its own transaction, so an interrupted backfill can simply be
+/// run again, and indexing can carry on alongside it.
+async fn backfill(args: &Args, from: u64, to: Option<u64>) -> Result<(), Error> {
+	let (mut db, legacy, client) = connect(args).await?;
+	let mut runtimes = Runtimes::new(legacy.clone(), client.clone());
+
+	let finalized = u64::from(client.blocks().at_latest().await?.number());
+	let to = to.map_or(finalized, |to| to.min(finalized));
+	println!("Backfilling blocks {} to {} from {}...", from, to, args.url);
+	for number in from..=to {
+		let block = client.blocks().at(block_hash(&legacy, number).await?).await?;
+		index_block(&block, &mut db, &mut runtimes, None, true).await?;
+	}
+	println!("Backfilled {} blocks", (to + 1).saturating_sub(from));
+	Ok(())
+}
+
+/// Connect to the database, migrating it if needed, and to the node.
+async fn connect(
+	args: &Args,
+) -> Result<(Database, LegacyRpcMethods<SubstrateConfig>, Client), Error> {
+	let db = Database::connect(&args.database_url).await?;
+	let rpc = RpcClient::from_url(&args.url).await?;
+	let legacy = LegacyRpcMethods::<SubstrateConfig>::new(rpc.clone());
+	let client = Client::from_rpc_client(rpc).await?;
+	Ok((db, legacy, client))
+}
+
+/// The hash of the block `number` on the best chain.
+async fn block_hash(
+	legacy: &LegacyRpcMethods<SubstrateConfig>,
+	number: u64,
+) -> Result<H256, Error> {
+	Ok(legacy
+		.chain_get_block_hash(Some(number.into()))
+		.await?
+		.ok_or_else(|| format!("Block {} not found", number))?)
+}
+
+/// Write the per-stream statistics of `block` to the database, decoded with the metadata of
+/// the runtime that built it, and send them on `metrics` if they were written. What was
+/// indexed for the block is replaced if `reindex` is set.
+async fn index_block(
+	block: &Block<SubstrateConfig, Client>,
+	db: &mut Database,
+	runtimes: &mut Runtimes,
+	metrics: Option<&mpsc::Sender<Vec<Point>>>,
+	reindex: bool,
+) -> Result<(), Error> {
+	runtimes.prepare(block).await?;
+
+	let now = subxt::dynamic::storage("Timestamp", "Now", ());
+	let timestamp = match block.storage().fetch(&now).await? {
+		Some(now) => now.to_value()?.as_u128().unwrap_or_default() as u64,
+		None => 0,
+	};
+
+	let mut events = Vec::new();
+	let mut account_events = Vec::new();
+	for event in block.events().await?.iter() {
+		let event = event?;
+		if event.pallet_name() != "TickStream" {
+			continue
+		}
+		let fields = event.field_values()?;
+		events.extend(StreamEvent::from_fields(event.variant_name(), &fields));
+		account_events.extend(AccountEvent::decode(event.variant_name(), event.field_bytes()));
+	}
+
+	let streams = stats::aggregate(events);
+	let accounts = activity::aggregate(account_events);
+	let number = u64::from(block.number());
+	let hash = block.hash();
+	if db.write_block(number, hash.as_ref(), timestamp, &streams, &accounts, reindex).await? {
+		println!("Indexed block {}: {} streams active", number, streams.len());
+		if let Some(metrics) = metrics.filter(|_| !streams.is_empty()) {
+			metrics.send(Point::from_block(number, timestamp, &streams)).await?;
+		}
+	}
+	Ok(())
+}
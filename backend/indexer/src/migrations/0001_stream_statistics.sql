@@ -0,0 +1,38 @@
+-- Per-stream statistics for the analytics dashboard. Databases created before migrations were
+-- versioned already have these, so everything is only created if missing.
+
+-- Every block indexed, so a block seen again with another hash replaces what was indexed
+-- for the old one.
+CREATE TABLE IF NOT EXISTS indexed_blocks (
+	number BIGINT PRIMARY KEY,
+	hash BYTEA NOT NULL,
+	timestamp TIMESTAMPTZ NOT NULL
+);
+
+-- What happened on each stream in each block. Amounts are in the smallest unit.
+CREATE TABLE IF NOT EXISTS stream_block_stats (
+	stream_id NUMERIC(39, 0) NOT NULL,
+	block_number BIGINT NOT NULL REFERENCES indexed_blocks (number) ON DELETE CASCADE,
+	minute TIMESTAMPTZ NOT NULL,
+	-- The viewer count at the end of the block, if it changed in the block
+	viewers INTEGER,
+	seconds_watched BIGINT NOT NULL,
+	revenue NUMERIC(39, 0) NOT NULL,
+	PRIMARY KEY (stream_id, block_number)
+);
+
+CREATE INDEX IF NOT EXISTS stream_block_stats_by_minute
+	ON stream_block_stats (stream_id, minute);
+
+-- The time series the dashboard charts: each stream's last viewer count, watch time and
+-- revenue per minute.
+CREATE OR REPLACE VIEW stream_minutes AS
+SELECT
+	stream_id,
+	minute,
+	(ARRAY_AGG(viewers ORDER BY block_number DESC) FILTER (WHERE viewers IS NOT NULL))[1]
+		AS viewers,
+	SUM(seconds_watched) AS seconds_watched,
+	SUM(revenue) AS revenue
+FROM stream_block_stats
+GROUP BY stream_id, minute;
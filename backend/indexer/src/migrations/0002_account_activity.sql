@@ -0,0 +1,23 @@
+-- Accounts' own activity, for their data exports and erasures. Blocks indexed before this
+-- have none until they are backfilled. Databases created before migrations were versioned may
+-- already have these, so they are only created if missing.
+
+-- What each account did on each stream in each block: the personal data an account's export
+-- contains and its erasure removes. Amounts are in the smallest unit.
+CREATE TABLE IF NOT EXISTS account_block_activity (
+	account BYTEA NOT NULL,
+	stream_id NUMERIC(39, 0) NOT NULL,
+	block_number BIGINT NOT NULL REFERENCES indexed_blocks (number) ON DELETE CASCADE,
+	joined BOOLEAN NOT NULL,
+	seconds_watched BIGINT NOT NULL,
+	spent NUMERIC(39, 0) NOT NULL,
+	tipped NUMERIC(39, 0) NOT NULL,
+	tip_messages BYTEA[] NOT NULL,
+	PRIMARY KEY (account, stream_id, block_number)
+);
+
+-- Accounts whose data was erased at their request. Nothing more is indexed about them.
+CREATE TABLE IF NOT EXISTS erased_accounts (
+	account BYTEA PRIMARY KEY,
+	erased_at TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,245 @@
+//! Account data requests: an account downloads everything the indexer holds about it, or has
+//! it erased.
+//!
+//! Both requests are signed by the account itself:
+//!
+//! ```text
+//! POST /accounts/export
+//! POST /accounts/erase
+//! { "account": "5Grw...", "at": 1700000000000, "signature": "0x..." }
+//! ```
+//!
+//! `at` is the client's clock in Unix milliseconds and `signature` is the account's sr25519
+//! signature over the SCALE encoding of `(b"vilokanam:account-data", action, at)`, where
+//! `action` is `b"export"` or `b"erase"`, either bare or wrapped in `<Bytes>…</Bytes>` as
+//! browser wallets sign raw payloads.
+//!
+//! An export is a JSON archive of the account's joins, watch time, spend, tips and tip
+//! messages per stream and block. An erasure deletes them and tombstones the account, so
+//! nothing more is indexed about it; the per-stream statistics it counted towards are
+//! anonymous and stay. Neither reaches the chain: its blocks, and so every event and call of
+//! the account, stay as they are, and both responses list what remains there.
+
+use crate::{activity::Account, db::Database};
+use axum::{
+	extract::State,
+	http::{header, StatusCode},
+	response::{IntoResponse, Response},
+	routing::post,
+	Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+	net::SocketAddr,
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use subxt::{ext::codec::Encode, utils::AccountId32};
+use subxt_signer::sr25519;
+
+/// What erasure leaves in place, as it is part of the chain.
+const RETAINED_ON_CHAIN: [&str; 4] = [
+	"Every extrinsic the account signed, such as joins, top-ups and tips",
+	"Every event about the account, including tick settlements and tip messages",
+	"The account's balances and the tick-stream pallet's storage about it, such as reserves, \
+	 watch time and viewing stats, until the pallet removes them",
+	"The statements and proofs nodes derive from the above",
+];
+
+/// A signed request about an account's data.
+#[derive(Debug, Deserialize)]
+pub struct AccountRequest {
+	/// The account, as an SS58 address.
+	pub account: AccountId32,
+	/// When the request was sent, in Unix milliseconds.
+	pub at: u64,
+	/// The account's signature over the request, hex encoded.
+	pub signature: String,
+}
+
+impl AccountRequest {
+	/// The bytes the account signs to request `action`.
+	pub fn message(action: &[u8], at: u64) -> Vec<u8> {
+		(b"vilokanam:account-data", action, at).encode()
+	}
+
+	/// Whether the request carries the account's signature for `action`.
+	pub fn is_signed_for(&self, action: &[u8]) -> bool {
+		let signature = self.signature.trim_start_matches("0x");
+		let Ok(Ok(signature)) = hex::decode(signature).map(<[u8; 64]>::try_from) else {
+			return false;
+		};
+		let signature = sr25519::Signature(signature);
+		let public = sr25519::PublicKey(self.account.0);
+		let message = Self::message(action, self.at);
+		let wrapped = [&b"<Bytes>"[..], &message[..], &b"</Bytes>"[..]].concat();
+		sr25519::verify(&signature, &message, &public) ||
+			sr25519::verify(&signature, &wrapped, &public)
+	}
+}
+
+/// Everything indexed about an account.
+#[derive(Debug, Serialize)]
+pub struct AccountArchive {
+	pub account: String,
+	/// When the archive was made, in Unix milliseconds.
+	pub exported_at: u64,
+	/// When the account's data was erased, in Unix milliseconds, if it was.
+	pub erased_at: Option<u64>,
+	pub activity: Vec<ActivityEntry>,
+	pub retained_on_chain: [&'static str; 4],
+}
+
+/// What the account did on one stream in one block.
+#[derive(Debug, Serialize)]
+pub struct ActivityEntry {
+	pub stream_id: String,
+	pub block_number: u64,
+	/// When the block was made, in Unix milliseconds.
+	pub timestamp: u64,
+	pub joined: bool,
+	pub seconds_watched: u64,
+	/// Paid for watch time, in the smallest unit, as a decimal string.
+	pub spent: String,
+	/// Tipped, in the smallest unit, as a decimal string.
+	pub tipped: String,
+	pub tip_messages: Vec<String>,
+}
+
+/// What an erasure removed.
+#[derive(Debug, Serialize)]
+pub struct ErasureReceipt {
+	pub account: String,
+	pub records_erased: u64,
+	pub retained_on_chain: [&'static str; 4],
+}
+
+/// Shared with every request.
+#[derive(Clone)]
+struct Server {
+	database_url: String,
+	/// How far a request's `at` may be from this process' clock.
+	window: Duration,
+}
+
+/// Answer account data requests on `addr` until the process exits.
+///
+/// Requests sent more than `window` ago, or dated that far ahead, are turned away, so a
+/// captured request cannot be replayed later.
+pub async fn serve(
+	addr: SocketAddr,
+	database_url: String,
+	window: Duration,
+) -> std::io::Result<()> {
+	let app = Router::new()
+		.route("/accounts/export", post(export))
+		.route("/accounts/erase", post(erase))
+		.with_state(Server { database_url, window });
+	let listener = tokio::net::TcpListener::bind(addr).await?;
+	println!("Accepting account data requests on http://{}/accounts", addr);
+	axum::serve(listener, app).await
+}
+
+async fn export(
+	State(server): State<Server>,
+	Json(request): Json<AccountRequest>,
+) -> Result<Response, StatusCode> {
+	let account = server.authorize(&request, b"export")?;
+	let db = server.database().await?;
+	let activity = db.account_activity(&account).await.map_err(internal_error)?;
+	let erased_at = db.erased_at(&account).await.map_err(internal_error)?;
+
+	let archive = AccountArchive {
+		account: request.account.to_string(),
+		exported_at: now().as_millis() as u64,
+		erased_at,
+		activity: activity
+			.into_iter()
+			.map(|record| ActivityEntry {
+				stream_id: record.stream_id.to_string(),
+				block_number: record.block_number,
+				timestamp: record.timestamp,
+				joined: record.activity.joined,
+				seconds_watched: record.activity.seconds_watched,
+				spent: record.activity.spent.to_string(),
+				tipped: record.activity.tipped.to_string(),
+				tip_messages: record
+					.activity
+					.tip_messages
+					.iter()
+					.map(|message| String::from_utf8_lossy(message).into_owned())
+					.collect(),
+			})
+			.collect(),
+		retained_on_chain: RETAINED_ON_CHAIN,
+	};
+	let attachment = format!("attachment; filename=\"vilokanam-{}.json\"", request.account);
+	Ok(([(header::CONTENT_DISPOSITION, attachment)], Json(archive)).into_response())
+}
+
+async fn erase(
+	State(server): State<Server>,
+	Json(request): Json<AccountRequest>,
+) -> Result<Json<ErasureReceipt>, StatusCode> {
+	let account = server.authorize(&request, b"erase")?;
+	let mut db = server.database().await?;
+	let records_erased = db.erase_account(&account).await.map_err(internal_error)?;
+	println!("Erased {} records of {} at its request", records_erased, request.account);
+
+	Ok(Json(ErasureReceipt {
+		account: request.account.to_string(),
+		records_erased,
+		retained_on_chain: RETAINED_ON_CHAIN,
+	}))
+}
+
+impl Server {
+	/// The account `request` is about, if it is recent and signed for `action`.
+	fn authorize(&self, request: &AccountRequest, action: &[u8]) -> Result<Account, StatusCode> {
+		let sent = Duration::from_millis(request.at);
+		let now = now();
+		if now.saturating_sub(sent) > self.window || sent.saturating_sub(now) > self.window {
+			return Err(StatusCode::BAD_REQUEST);
+		}
+		if !request.is_signed_for(action) {
+			return Err(StatusCode::UNAUTHORIZED);
+		}
+		Ok(request.account.0)
+	}
+
+	async fn database(&self) -> Result<Database, StatusCode> {
+		Database::connect(&self.database_url).await.map_err(internal_error)
+	}
+}
+
+fn now() -> Duration {
+	SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default()
+}
+
+fn internal_error(e: tokio_postgres::Error) -> StatusCode {
+	eprintln!("Account data request failed: {}", e);
+	StatusCode::INTERNAL_SERVER_ERROR
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::str::FromStr;
+	use subxt_signer::SecretUri;
+
+	#[test]
+	fn requests_must_be_signed_for_their_action() {
+		let uri = SecretUri::from_str("//Alice").unwrap();
+		let keypair = sr25519::Keypair::from_uri(&uri).unwrap();
+		let signature = keypair.sign(&AccountRequest::message(b"export", 1_000));
+		let mut request = AccountRequest {
+			account: AccountId32(keypair.public_key().0),
+			at: 1_000,
+			signature: format!("0x{}", hex::encode(signature.0)),
+		};
+		assert!(request.is_signed_for(b"export"));
+		assert!(!request.is_signed_for(b"erase"));
+
+		request.at = 2_000;
+		assert!(!request.is_signed_for(b"export"));
+	}
+}
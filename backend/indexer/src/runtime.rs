@@ -0,0 +1,47 @@
+//! The runtime metadata each block's events and storage are decoded with.
+//!
+//! Events change shape across runtime upgrades, so a block is read with the metadata of the
+//! runtime that built it rather than the latest one. The metadata is only fetched again when a
+//! block's runtime version differs from the previous block's, so following the chain or
+//! backfilling a range fetches it once per runtime upgrade.
+
+use subxt::{
+	backend::legacy::LegacyRpcMethods, blocks::Block, ext::codec::Decode, Metadata,
+	OnlineClient, SubstrateConfig,
+};
+
+type Client = OnlineClient<SubstrateConfig>;
+type Error = Box<dyn std::error::Error>;
+
+/// Switches a client's metadata to that of the runtime of each block read with it.
+pub struct Runtimes {
+	legacy: LegacyRpcMethods<SubstrateConfig>,
+	client: Client,
+	/// The spec version of the runtime whose metadata the client holds, once one was fetched.
+	spec_version: Option<u32>,
+}
+
+impl Runtimes {
+	pub fn new(legacy: LegacyRpcMethods<SubstrateConfig>, client: Client) -> Self {
+		Self { legacy, client, spec_version: None }
+	}
+
+	/// Make the client decode `block` with the metadata of the runtime that built it.
+	pub async fn prepare(&mut self, block: &Block<SubstrateConfig, Client>) -> Result<(), Error> {
+		// A block upgrading the runtime is still built by the runtime before it, which is the
+		// one in its parent's state
+		let at = match u64::from(block.number()) {
+			0 => block.hash(),
+			_ => block.header().parent_hash,
+		};
+		let version = self.legacy.state_get_runtime_version(Some(at)).await?;
+		if self.spec_version == Some(version.spec_version) {
+			return Ok(());
+		}
+		let metadata = self.legacy.state_get_metadata(Some(at)).await?.into_raw();
+		self.client.set_metadata(Metadata::decode(&mut &metadata[..])?);
+		self.spec_version = Some(version.spec_version);
+		println!("Decoding blocks with the metadata of runtime version {}", version.spec_version);
+		Ok(())
+	}
+}
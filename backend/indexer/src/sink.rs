@@ -0,0 +1,277 @@
+//! Pushes each indexed block's per-stream metrics to an external time-series database, so
+//! existing Grafana stacks can chart them.
+//!
+//! Metrics are buffered and written in batches of up to `batch_size`, at least every
+//! `flush_interval`. A failed write is retried with a growing wait, up to `max_retries`
+//! times, before its batch is dropped; indexing carries on either way. Writes are keyed by
+//! stream and block time, so a retried or re-indexed block overwrites what was pushed for it.
+//!
+//! InfluxDB receives the `stream_interval` measurement, tagged with `stream_id`, with the
+//! integer fields `viewers` (when it changed), `seconds_watched` and `ticks` and the unsigned
+//! field `revenue`, capped at `u64::MAX`. TimescaleDB receives rows of the
+//! `stream_interval_metrics` hypertable, created if it does not exist.
+
+use backoff::Backoff;
+use crate::stats::BlockStats;
+use std::{collections::BTreeMap, fmt::Write, time::Duration};
+use tokio::sync::mpsc;
+
+/// Creates the TimescaleDB hypertable if it does not exist yet.
+const TIMESCALE_SCHEMA: &str = include_str!("timescale.sql");
+
+/// The batches of metrics that can wait for the sink before indexing waits for it.
+const QUEUE_LENGTH: usize = 1024;
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+
+/// What happened on one stream in one indexed block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Point {
+	pub stream_id: u128,
+	pub block_number: u64,
+	/// When the block was made, in Unix milliseconds.
+	pub timestamp: u64,
+	pub stats: BlockStats,
+}
+
+impl Point {
+	/// The points of every stream active in the block `block_number` made at `timestamp`.
+	pub fn from_block(
+		block_number: u64,
+		timestamp: u64,
+		streams: &BTreeMap<u128, BlockStats>,
+	) -> Vec<Self> {
+		streams
+			.iter()
+			.map(|(stream_id, stats)| Self {
+				stream_id: *stream_id,
+				block_number,
+				timestamp,
+				stats: stats.clone(),
+			})
+			.collect()
+	}
+}
+
+/// Where metrics are pushed.
+pub enum Sink {
+	/// An InfluxDB 2 server's write API.
+	Influx {
+		client: reqwest::Client,
+		/// The write API's URL.
+		url: String,
+		org: String,
+		bucket: String,
+		token: Option<String>,
+	},
+	/// A TimescaleDB database, connected to on the first write and after errors.
+	Timescale { url: String, client: Option<tokio_postgres::Client> },
+}
+
+/// How the sink batches and retries.
+#[derive(Clone, Copy, Debug)]
+pub struct SinkOptions {
+	/// The most points in one write.
+	pub batch_size: usize,
+	/// The longest a point waits to be written.
+	pub flush_interval: Duration,
+	/// How many times a failed write is retried before its points are dropped.
+	pub max_retries: u32,
+	/// The longest wait between retries.
+	pub max_backoff: Duration,
+}
+
+impl Sink {
+	/// A sink writing to the InfluxDB server at `url`, such as `http://localhost:8086`.
+	pub fn influx(url: &str, org: String, bucket: String, token: Option<String>) -> Self {
+		let url = format!("{}/api/v2/write", url.trim_end_matches('/'));
+		Self::Influx { client: reqwest::Client::new(), url, org, bucket, token }
+	}
+
+	/// A sink writing to the TimescaleDB database at the connection string `url`.
+	pub fn timescale(url: String) -> Self {
+		Self::Timescale { url, client: None }
+	}
+
+	/// Push metrics in the background; indexing sends each block's points on the returned
+	/// channel.
+	pub fn spawn(self, options: SinkOptions) -> mpsc::Sender<Vec<Point>> {
+		let (sender, receiver) = mpsc::channel(QUEUE_LENGTH);
+		tokio::spawn(self.run(options, receiver));
+		sender
+	}
+
+	async fn run(mut self, options: SinkOptions, mut points: mpsc::Receiver<Vec<Point>>) {
+		let batch_size = options.batch_size.max(1);
+		let mut pending = Vec::new();
+		let mut flush = tokio::time::interval(options.flush_interval);
+		loop {
+			let open = tokio::select! {
+				received = points.recv() => match received {
+					Some(received) => {
+						pending.extend(received);
+						if pending.len() < batch_size {
+							continue;
+						}
+						true
+					},
+					None => false,
+				},
+				_ = flush.tick() => true,
+			};
+
+			for batch in std::mem::take(&mut pending).chunks(batch_size) {
+				self.write_with_retries(batch, &options).await;
+			}
+			if !open {
+				return;
+			}
+		}
+	}
+
+	/// Write `points`, retrying failures up to `max_retries` times before dropping them.
+	async fn write_with_retries(&mut self, points: &[Point], options: &SinkOptions) {
+		let mut backoff = Backoff::new(Duration::from_secs(1), options.max_backoff);
+		for attempt in 0..=options.max_retries {
+			let error = match self.write(points).await {
+				Ok(()) => return,
+				Err(e) => e,
+			};
+			if attempt == options.max_retries {
+				eprintln!(
+					"Dropping {} metrics after {} failed pushes: {}",
+					points.len(),
+					attempt + 1,
+					error
+				);
+				return;
+			}
+			let delay = backoff.next_delay();
+			eprintln!("Pushing metrics failed: {}, retrying in {:?}", error, delay);
+			tokio::time::sleep(delay).await;
+		}
+	}
+
+	async fn write(&mut self, points: &[Point]) -> Result<(), Error> {
+		match self {
+			Self::Influx { client, url, org, bucket, token } => {
+				let query = [("org", &org[..]), ("bucket", &bucket[..]), ("precision", "ms")];
+				let mut request = client.post(&url[..]).query(&query).body(line_protocol(points));
+				if let Some(token) = token {
+					request = request.header("Authorization", format!("Token {}", token));
+				}
+				request.send().await?.error_for_status()?;
+				Ok(())
+			},
+			Self::Timescale { url, client } => {
+				let result = write_timescale(url, client, points).await;
+				if result.is_err() {
+					// Connect afresh for the retry
+					*client = None;
+				}
+				result
+			},
+		}
+	}
+}
+
+/// `points` in InfluxDB's line protocol, with millisecond timestamps.
+pub fn line_protocol(points: &[Point]) -> String {
+	let mut lines = String::new();
+	for Point { stream_id, timestamp, stats, .. } in points {
+		let _ = write!(lines, "stream_interval,stream_id={} ", stream_id);
+		if let Some(viewers) = stats.viewers {
+			let _ = write!(lines, "viewers={}i,", viewers);
+		}
+		let revenue = u64::try_from(stats.revenue).unwrap_or(u64::MAX);
+		let _ = writeln!(
+			lines,
+			"seconds_watched={}i,ticks={}i,revenue={}u {}",
+			stats.seconds_watched, stats.ticks, revenue, timestamp
+		);
+	}
+	lines
+}
+
+/// Write `points` to TimescaleDB in one transaction, connecting first if `client` is not.
+async fn write_timescale(
+	url: &str,
+	client: &mut Option<tokio_postgres::Client>,
+	points: &[Point],
+) -> Result<(), Error> {
+	let client = match client {
+		Some(client) => client,
+		unconnected @ None => {
+			let (connected, connection) =
+				tokio_postgres::connect(url, tokio_postgres::NoTls).await?;
+			tokio::spawn(async move {
+				if let Err(e) = connection.await {
+					eprintln!("TimescaleDB connection closed: {}", e);
+				}
+			});
+			connected.batch_execute(TIMESCALE_SCHEMA).await?;
+			unconnected.insert(connected)
+		},
+	};
+
+	let tx = client.transaction().await?;
+	let insert = tx
+		.prepare(
+			"INSERT INTO stream_interval_metrics
+				(time, stream_id, block_number, viewers, seconds_watched, ticks, revenue)
+			VALUES (
+				TO_TIMESTAMP($1::BIGINT / 1000.0),
+				$2::TEXT::NUMERIC,
+				$3,
+				$4,
+				$5,
+				$6,
+				$7::TEXT::NUMERIC
+			)
+			ON CONFLICT (stream_id, time) DO UPDATE SET
+				block_number = EXCLUDED.block_number,
+				viewers = EXCLUDED.viewers,
+				seconds_watched = EXCLUDED.seconds_watched,
+				ticks = EXCLUDED.ticks,
+				revenue = EXCLUDED.revenue",
+		)
+		.await?;
+	for point in points {
+		tx.execute(
+			&insert,
+			&[
+				&(point.timestamp as i64),
+				&point.stream_id.to_string(),
+				&(point.block_number as i64),
+				&point.stats.viewers.map(|viewers| viewers as i32),
+				&(point.stats.seconds_watched as i64),
+				&(point.stats.ticks as i32),
+				&point.stats.revenue.to_string(),
+			],
+		)
+		.await?;
+	}
+	tx.commit().await?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn points_are_written_as_line_protocol() {
+		let streams = BTreeMap::from([
+			(1, BlockStats { viewers: Some(2), seconds_watched: 10, ticks: 2, revenue: 200 }),
+			(2, BlockStats { viewers: None, seconds_watched: 3, ticks: 1, revenue: u128::MAX }),
+		]);
+		let points = Point::from_block(7, 1_700_000_000_000, &streams);
+		assert_eq!(
+			line_protocol(&points),
+			"stream_interval,stream_id=1 viewers=2i,seconds_watched=10i,ticks=2i,revenue=200u \
+			1700000000000\n\
+			stream_interval,stream_id=2 seconds_watched=3i,ticks=1i,revenue=18446744073709551615u \
+			1700000000000\n"
+		);
+	}
+}
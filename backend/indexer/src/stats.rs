@@ -0,0 +1,111 @@
+//! Per-stream statistics of a block, folded from its tick-stream events.
+
+use std::collections::BTreeMap;
+use subxt::{dynamic::At, ext::scale_value::Composite};
+
+/// What happened on one stream in one block.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BlockStats {
+	/// The stream's viewer count at the end of the block, if it changed in the block.
+	pub viewers: Option<u32>,
+	/// The seconds of watch time viewers were charged for.
+	pub seconds_watched: u64,
+	/// The ticks viewers were charged in.
+	pub ticks: u32,
+	/// What viewers paid for watch time and in tips, in the smallest unit.
+	pub revenue: u128,
+}
+
+/// A tick-stream event that counts towards a stream's statistics.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StreamEvent {
+	TickRecorded { stream_id: u128, ticks: u32, amount: u128 },
+	ViewerCountChanged { stream_id: u128, viewers: u32 },
+	TipReceived { stream_id: u128, amount: u128 },
+}
+
+impl StreamEvent {
+	/// The `TickStream` event `variant` with `fields`, if it counts towards statistics.
+	pub fn from_fields<T>(variant: &str, fields: &Composite<T>) -> Option<Self> {
+		let number = |name: &str| fields.at(name).and_then(|value| value.as_u128());
+		let stream_id = number("stream_id")?;
+		match variant {
+			"TickRecorded" => Some(Self::TickRecorded {
+				stream_id,
+				ticks: number("ticks")? as u32,
+				amount: number("amount")?,
+			}),
+			"ViewerCountChanged" =>
+				Some(Self::ViewerCountChanged { stream_id, viewers: number("viewers")? as u32 }),
+			"TipReceived" => Some(Self::TipReceived { stream_id, amount: number("amount")? }),
+			_ => None,
+		}
+	}
+}
+
+/// Fold the events of one block, in the order they were deposited, into statistics per
+/// stream.
+pub fn aggregate(events: impl IntoIterator<Item = StreamEvent>) -> BTreeMap<u128, BlockStats> {
+	let mut streams = BTreeMap::<u128, BlockStats>::new();
+	for event in events {
+		match event {
+			StreamEvent::TickRecorded { stream_id, ticks, amount } => {
+				let stats = streams.entry(stream_id).or_default();
+				stats.seconds_watched = stats.seconds_watched.saturating_add(ticks.into());
+				stats.ticks = stats.ticks.saturating_add(1);
+				stats.revenue = stats.revenue.saturating_add(amount);
+			},
+			StreamEvent::ViewerCountChanged { stream_id, viewers } =>
+				streams.entry(stream_id).or_default().viewers = Some(viewers),
+			StreamEvent::TipReceived { stream_id, amount } => {
+				let stats = streams.entry(stream_id).or_default();
+				stats.revenue = stats.revenue.saturating_add(amount);
+			},
+		}
+	}
+	streams
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use subxt::dynamic::Value;
+
+	#[test]
+	fn only_events_counting_towards_statistics_are_read() {
+		let fields = Composite::named([
+			("stream_id", Value::u128(7)),
+			("viewer", Value::from_bytes([1; 32])),
+			("ticks", Value::u128(6)),
+			("amount", Value::u128(60)),
+			("fiat_value", Value::unnamed_variant("None", [])),
+		]);
+		assert_eq!(
+			StreamEvent::from_fields("TickRecorded", &fields),
+			Some(StreamEvent::TickRecorded { stream_id: 7, ticks: 6, amount: 60 })
+		);
+		assert_eq!(StreamEvent::from_fields("ViewerRefunded", &fields), None);
+
+		let fields = Composite::named([("stream_id", Value::u128(7))]);
+		assert_eq!(StreamEvent::from_fields("ViewerCountChanged", &fields), None);
+	}
+
+	#[test]
+	fn blocks_are_summed_per_stream() {
+		let stats = aggregate([
+			StreamEvent::ViewerCountChanged { stream_id: 1, viewers: 2 },
+			StreamEvent::TickRecorded { stream_id: 1, ticks: 6, amount: 60 },
+			StreamEvent::TickRecorded { stream_id: 2, ticks: 3, amount: 9 },
+			StreamEvent::TickRecorded { stream_id: 1, ticks: 4, amount: 40 },
+			StreamEvent::TipReceived { stream_id: 1, amount: 100 },
+			StreamEvent::ViewerCountChanged { stream_id: 1, viewers: 1 },
+		]);
+		assert_eq!(
+			stats,
+			BTreeMap::from([
+				(1, BlockStats { viewers: Some(1), seconds_watched: 10, ticks: 2, revenue: 200 }),
+				(2, BlockStats { viewers: None, seconds_watched: 3, ticks: 1, revenue: 9 }),
+			])
+		);
+	}
+}
@@ -0,0 +1,15 @@
+-- What happened on each stream in each indexed block, as a TimescaleDB hypertable for
+-- dashboards kept outside the analytics database. Amounts are in the smallest unit.
+CREATE TABLE IF NOT EXISTS stream_interval_metrics (
+	time TIMESTAMPTZ NOT NULL,
+	stream_id NUMERIC(39, 0) NOT NULL,
+	block_number BIGINT NOT NULL,
+	-- The viewer count at the end of the block, if it changed in the block
+	viewers INTEGER,
+	seconds_watched BIGINT NOT NULL,
+	ticks INTEGER NOT NULL,
+	revenue NUMERIC(39, 0) NOT NULL,
+	PRIMARY KEY (stream_id, time)
+);
+
+SELECT create_hypertable('stream_interval_metrics', 'time', if_not_exists => TRUE);
@@ -1,16 +1,89 @@
 use sc_chain_spec::{ChainSpecExtension, ChainSpecGroup};
+use sc_network::config::MultiaddrWithPeerId;
+use sc_telemetry::TelemetryEndpoints;
 use serde::{Deserialize, Serialize};
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_consensus_grandpa::AuthorityId as GrandpaId;
 use sp_core::{sr25519, Pair, Public};
 use sp_runtime::traits::{IdentifyAccount, Verify};
+use std::path::Path;
 use vilokanam_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-	SystemConfig, WASM_BINARY,
+	opaque::SessionKeys,
+	tick_stream::{GenesisStream, StreamCategory},
+	AccountId, AuraConfig, Balance, BalancesConfig, GenesisConfig, GrandpaConfig, Permill,
+	PlatformCouncilMembershipConfig, SessionConfig, Signature, SudoConfig, SystemConfig,
+	TickStreamConfig, WASM_BINARY,
 };
 
-// The URL for the telemetry server.
-// const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
+/// The network a chain specification is for, each with the boot nodes and telemetry servers
+/// its nodes connect to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Environment {
+	/// Single-machine dev and local chains, which have neither.
+	Development,
+	/// The staging network, where releases run before they reach the testnet.
+	Staging,
+	/// The public testnet.
+	Testnet,
+}
+
+/// Where an environment's nodes find their first peers and report telemetry.
+#[derive(Debug, Clone, Copy)]
+pub struct Endpoints {
+	/// Boot nodes, as multiaddresses ending in the node's peer ID.
+	pub bootnodes: &'static [&'static str],
+	/// Telemetry servers, with the verbosity each is sent.
+	pub telemetry: &'static [(&'static str, u8)],
+}
+
+impl Environment {
+	/// The endpoints compiled into this environment's chain specification.
+	pub fn endpoints(self) -> Endpoints {
+		match self {
+			Self::Development => Endpoints { bootnodes: &[], telemetry: &[] },
+			Self::Staging => Endpoints {
+				bootnodes: &[
+					"/dns/boot-0.staging.vilokanam.com/tcp/30333/p2p/\
+					 12D3KooWRDYdsT4GeZX3eBe5u3ZfMoHdWbL3vGFCRgCowqhJFdSB",
+					"/dns/boot-1.staging.vilokanam.com/tcp/30333/p2p/\
+					 12D3KooWSD6vPrbFKrWb8evPXkgTd6U6hQ82DWmRxi4t3AFkLdC5",
+				],
+				telemetry: &[("wss://telemetry.staging.vilokanam.com/submit/", 1)],
+			},
+			Self::Testnet => Endpoints {
+				bootnodes: &[
+					"/dns/boot-0.testnet.vilokanam.com/tcp/30333/p2p/\
+					 12D3KooWKz6JmoUz5DRhauDdgb7ryGvecJtbMCembd42kui3h1tk",
+					"/dns/boot-1.testnet.vilokanam.com/tcp/30333/p2p/\
+					 12D3KooWJyiH8jyT9ov93ZPPMckUVnJ8eYbtGwBi87akf5itFTPv",
+				],
+				telemetry: &[
+					("wss://telemetry.testnet.vilokanam.com/submit/", 0),
+					("wss://telemetry.polkadot.io/submit/", 0),
+				],
+			},
+		}
+	}
+}
+
+impl Endpoints {
+	fn boot_nodes(&self) -> Result<Vec<MultiaddrWithPeerId>, String> {
+		self.bootnodes
+			.iter()
+			.map(|addr| addr.parse().map_err(|e| format!("Invalid boot node {}: {}", addr, e)))
+			.collect()
+	}
+
+	fn telemetry_endpoints(&self) -> Result<Option<TelemetryEndpoints>, String> {
+		if self.telemetry.is_empty() {
+			return Ok(None)
+		}
+		let endpoints = self.telemetry.iter().map(|(url, verbosity)| (url.to_string(), *verbosity));
+		TelemetryEndpoints::new(endpoints.collect())
+			.map(Some)
+			.map_err(|e| format!("Invalid telemetry endpoint: {}", e))
+	}
+}
 
 /// Specialized `ChainSpec`. This is a specialization of the general Substrate ChainSpec type.
 pub type ChainSpec = sc_service::GenericChainSpec<GenesisConfig>;
@@ -32,16 +105,24 @@ where
 	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
 
-/// Generate an Aura authority key.
-pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
+/// Generate a validator's account and its Aura and GRANDPA session keys.
+pub fn authority_keys_from_seed(s: &str) -> (AccountId, AuraId, GrandpaId) {
 	(
+		get_account_id_from_seed::<sr25519::Public>(s),
 		get_from_seed::<AuraId>(s),
 		get_from_seed::<GrandpaId>(s),
 	)
 }
 
-/// Development chain specification.
-pub fn development_config() -> Result<ChainSpec, String> {
+/// The seed the development chain draws featured streams with when run with
+/// `--dev-deterministic`.
+pub const DETERMINISTIC_RANDOMNESS_SEED: [u8; 32] = *b"vilokanam/dev-deterministic/seed";
+
+/// Development chain specification, drawing featured streams with `randomness_seed` if given.
+pub fn development_config(
+	randomness_seed: Option<[u8; 32]>,
+	endpoints: Endpoints,
+) -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
 
 	Ok(ChainSpec::from_genesis(
@@ -61,11 +142,18 @@ pub fn development_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
 				],
+				// Streams to try the frontend on, as Stream #0 and Stream #1
+				vec![
+					demo_stream("Alice", 1_000_000, b"Alice plays chess", StreamCategory::Gaming),
+					demo_stream("Bob", 2_500_000, b"Bob's jazz hour", StreamCategory::Music),
+				],
+				vec![get_account_id_from_seed::<sr25519::Public>("Alice")],
+				randomness_seed,
 				true,
 			)
 		},
-		vec![],
-		None,
+		endpoints.boot_nodes()?,
+		endpoints.telemetry_endpoints()?,
 		None,
 		None,
 		None,
@@ -77,13 +165,60 @@ pub fn development_config() -> Result<ChainSpec, String> {
 }
 
 /// Local testnet chain specification.
-pub fn local_testnet_config() -> Result<ChainSpec, String> {
+pub fn local_testnet_config(endpoints: Endpoints) -> Result<ChainSpec, String> {
+	testnet_config_for("Local Testnet", "local_testnet", sc_service::ChainType::Local, endpoints)
+}
+
+/// Staging network chain specification, validated by Alice and Bob.
+pub fn staging_config(endpoints: Endpoints) -> Result<ChainSpec, String> {
+	testnet_config_for(
+		"Vilokanam Staging",
+		"vilokanam_staging",
+		sc_service::ChainType::Live,
+		endpoints,
+	)
+}
+
+/// Public testnet chain specification, validated by Alice and Bob.
+pub fn testnet_config(endpoints: Endpoints) -> Result<ChainSpec, String> {
+	testnet_config_for(
+		"Vilokanam Testnet",
+		"vilokanam_testnet",
+		sc_service::ChainType::Live,
+		endpoints,
+	)
+}
+
+/// Load the chain specification in the JSON file at `path`, with `endpoints` in place of its
+/// own boot nodes and telemetry servers if given.
+pub fn from_json_file(path: &Path, endpoints: Option<Endpoints>) -> Result<ChainSpec, String> {
+	let Some(endpoints) = endpoints else { return ChainSpec::from_json_file(path.to_path_buf()) };
+
+	let json = std::fs::read(path)
+		.map_err(|e| format!("Error opening spec file {}: {}", path.display(), e))?;
+	let mut spec: serde_json::Value =
+		serde_json::from_slice(&json).map_err(|e| format!("Error parsing spec file: {}", e))?;
+	let fields = spec.as_object_mut().ok_or("Error parsing spec file: not a JSON object")?;
+	fields.insert("bootNodes".into(), endpoints.bootnodes.into());
+	let telemetry = endpoints.telemetry_endpoints()?;
+	fields.insert("telemetryEndpoints".into(), serde_json::json!(telemetry));
+	let json = serde_json::to_vec(&spec).map_err(|e| format!("Error writing spec: {}", e))?;
+	ChainSpec::from_json_bytes(json)
+}
+
+/// A chain specification for several validators, named `name` and identified as `id`.
+fn testnet_config_for(
+	name: &str,
+	id: &str,
+	chain_type: sc_service::ChainType,
+	endpoints: Endpoints,
+) -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
 
 	Ok(ChainSpec::from_genesis(
-		"Local Testnet",
-		"local_testnet",
-		sc_service::ChainType::Local,
+		name,
+		id,
+		chain_type,
 		move || {
 			testnet_genesis(
 				wasm_binary,
@@ -106,11 +241,17 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 				],
+				vec![],
+				vec![
+					get_account_id_from_seed::<sr25519::Public>("Alice"),
+					get_account_id_from_seed::<sr25519::Public>("Bob"),
+				],
+				None,
 				true,
 			)
 		},
-		vec![],
-		None,
+		endpoints.boot_nodes()?,
+		endpoints.telemetry_endpoints()?,
 		None,
 		None,
 		None,
@@ -121,12 +262,29 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 	))
 }
 
+/// A stream created at genesis by the account of `seed`, charging `price_per_second`.
+fn demo_stream(
+	seed: &str,
+	price_per_second: Balance,
+	title: &[u8],
+	category: StreamCategory,
+) -> GenesisStream<AccountId, Balance> {
+	GenesisStream {
+		creator: get_account_id_from_seed::<sr25519::Public>(seed),
+		price_per_second,
+		metadata: Some((title.to_vec(), category, Vec::new())),
+	}
+}
+
 /// Configure initial storage state for FRAME modules.
 fn testnet_genesis(
 	wasm_binary: &[u8],
-	initial_authorities: Vec<(AuraId, GrandpaId)>,
+	initial_authorities: Vec<(AccountId, AuraId, GrandpaId)>,
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
+	streams: Vec<GenesisStream<AccountId, Balance>>,
+	reporters: Vec<AccountId>,
+	randomness_seed: Option<[u8; 32]>,
 	_enable_println: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
@@ -138,16 +296,46 @@ fn testnet_genesis(
 			// Configure endowed accounts with initial balance of 1 << 60.
 			balances: endowed_accounts.iter().cloned().map(|k| (k, 1 << 60)).collect(),
 		},
-		aura: AuraConfig {
-			authorities: initial_authorities.iter().map(|x| (x.0.clone())).collect(),
-		},
-		grandpa: GrandpaConfig {
-			authorities: initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect(),
+		// Aura and GRANDPA take their authorities from the session keys below.
+		aura: AuraConfig { authorities: vec![] },
+		grandpa: GrandpaConfig { authorities: vec![] },
+		session: SessionConfig {
+			keys: initial_authorities
+				.iter()
+				.map(|(account, aura, grandpa)| {
+					(
+						account.clone(),
+						account.clone(),
+						SessionKeys { aura: aura.clone(), grandpa: grandpa.clone() },
+					)
+				})
+				.collect(),
+			..Default::default()
 		},
 		sudo: SudoConfig {
 			// Assign network admin rights.
 			key: Some(root_key),
 		},
 		transaction_payment: Default::default(),
+		// The council takes its members from the membership pallet.
+		platform_council: Default::default(),
+		platform_council_membership: PlatformCouncilMembershipConfig {
+			members: endowed_accounts
+				.iter()
+				.take(3)
+				.cloned()
+				.collect::<Vec<_>>()
+				.try_into()
+				.expect("fewer members than the council maximum; qed"),
+			..Default::default()
+		},
+		assets: Default::default(),
+		tick_stream: TickStreamConfig {
+			streams,
+			platform_fee: Permill::from_percent(10),
+			tick_threshold: None,
+			reporters,
+			randomness_seed,
+		},
 	}
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 use clap::Parser;
+use std::path::PathBuf;
 
 #[derive(Debug, Parser)]
 #[clap(
@@ -12,6 +13,62 @@ pub struct Cli {
 
 	#[clap(flatten)]
 	pub run: sc_cli::RunCmd,
+
+	/// What the node is run for.
+	#[clap(long, value_enum, default_value_t = NodeRole::Full)]
+	pub role: NodeRole,
+
+	/// Settle due ticks in an inherent of every block this node authors, so settlement does
+	/// not depend on unsigned ticks getting through a congested pool.
+	#[clap(long)]
+	pub settlement_inherent: bool,
+
+	/// Seal and finalize a block as soon as a transaction enters the pool, or when
+	/// `engine_createBlock` is called, instead of waiting for Aura slots and GRANDPA votes. For
+	/// local development and integration tests only.
+	#[clap(long)]
+	pub dev_instant_seal: bool,
+
+	/// Run a private dev chain that repeats itself exactly from run to run, for end-to-end
+	/// tests of settlement timing. Blocks are sealed on demand as with `--dev-instant-seal`,
+	/// each stamped one slot after its parent from a fixed start; ticks are settled in an
+	/// inherent instead of by offchain workers; featured streams are drawn with a fixed seed;
+	/// and the node neither discovers nor accepts peers. Start the node with `--dev --tmp`.
+	#[clap(long)]
+	pub dev_deterministic: bool,
+
+	/// Put the dev account's Aura, GRANDPA, tick reporter and payout statement keys in the
+	/// keystore at startup, so a dev chain authors blocks and reports ticks without
+	/// `author_insertKey` calls. The account is the one chosen with `--alice` and the like,
+	/// or Alice. For development and local chains only.
+	#[clap(long)]
+	pub insert_dev_keys: bool,
+
+	/// Put the keys listed in this JSON file in the keystore at startup, such as a production
+	/// node's tick reporter and payout statement keys. The file holds an array of
+	/// `{"keyType": "tick", "suri": "<secret phrase>"}` entries, with key types `aura`,
+	/// `gran`, `tick` or `stmt`; keep it readable by the node's user only.
+	#[clap(long, value_name = "PATH")]
+	pub keys_file: Option<PathBuf>,
+
+	/// Connect to the boot nodes and report to the telemetry servers of this environment,
+	/// instead of those in the chain specification. Each built-in chain uses its own by
+	/// default: `staging` and `testnet` those of their network, `dev` and `local` none.
+	/// `--bootnodes` and `--telemetry-url` still add to and replace them.
+	#[clap(long, value_enum, value_name = "ENVIRONMENT")]
+	pub endpoints: Option<crate::chain_spec::Environment>,
+}
+
+/// The part a node plays in the network, beyond what `sc_cli::RunCmd` configures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum NodeRole {
+	/// A regular node, authoring blocks and submitting ticks if its keys allow.
+	Full,
+	/// A node that only serves reads: it never authors blocks, runs no offchain workers so
+	/// submits no unsigned ticks, refuses transactions over `author_*` RPCs, and answers
+	/// tick-stream queries from finalized state through a cache. Unless it keeps archive
+	/// state, a replica started on an empty database warp syncs as with `--sync warp`.
+	ReadReplica,
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -45,14 +102,12 @@ pub enum Subcommand {
 	#[clap(subcommand)]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
 
-	/// Try some command against runtime state.
-	#[cfg(feature = "try-runtime")]
-	TryRuntime(try_runtime_cli::TryRuntimeCmd),
-
-	/// Try some command against runtime state. Note: `try-runtime` feature must be enabled.
-	#[cfg(not(feature = "try-runtime"))]
+	/// Try some command against runtime state. Moved to the standalone `try-runtime` CLI.
 	TryRuntime,
 
 	/// Db meta columns information, e.g. to increase or decrease the capacity of the column.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Settle a simulated audience in memory to compare candidate fee parameters.
+	SimulateEconomics(crate::simulate::SimulateEconomicsCmd),
 }
\ No newline at end of file
@@ -0,0 +1,101 @@
+//! Keys put in the keystore as the node starts, so that it authors blocks, reports ticks and
+//! signs payout statements without `author_insertKey` calls.
+
+use crate::rpc::streaming::STATEMENT_KEY_TYPE;
+use serde::Deserialize;
+use sp_core::crypto::{key_types, KeyTypeId};
+use sp_keystore::KeystorePtr;
+use std::path::Path;
+
+/// The signature scheme of a key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Scheme {
+	/// Schnorr signatures over Ristretto, for Aura and the pallet's keys.
+	Sr25519,
+	/// Ed25519 signatures, for GRANDPA.
+	Ed25519,
+}
+
+/// Every key a node of the chain can use, with its scheme.
+const KEY_TYPES: [(KeyTypeId, Scheme); 4] = [
+	(key_types::AURA, Scheme::Sr25519),
+	(key_types::GRANDPA, Scheme::Ed25519),
+	(tick_stream::REPORTER_KEY_TYPE, Scheme::Sr25519),
+	(STATEMENT_KEY_TYPE, Scheme::Sr25519),
+];
+
+/// One key listed in a `--keys-file`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct KeyEntry {
+	/// The four-character key type, such as `tick` for tick reporting or `stmt` for payout
+	/// statements.
+	pub key_type: String,
+	/// The secret URI the key is derived from: a mnemonic or seed, with any derivation path.
+	pub suri: String,
+}
+
+/// Put every key type's key derived from `seed`, such as `//Alice`, in the keystore.
+pub fn insert_dev_keys(keystore: &KeystorePtr, seed: &str) -> Result<(), String> {
+	for (key_type, scheme) in KEY_TYPES {
+		insert(keystore, key_type, scheme, seed)?;
+	}
+	log::info!("Inserted the development keys of {} into the keystore", seed);
+	Ok(())
+}
+
+/// Put the keys listed in the JSON file at `path`, an array of `KeyEntry`, in the keystore.
+/// Returns the number of keys inserted.
+pub fn insert_keys_from_file(keystore: &KeystorePtr, path: &Path) -> Result<usize, String> {
+	let file = std::fs::File::open(path)
+		.map_err(|e| format!("Cannot open the keys file {}: {}", path.display(), e))?;
+	warn_if_readable_by_others(&file, path);
+	let entries: Vec<KeyEntry> = serde_json::from_reader(std::io::BufReader::new(file))
+		.map_err(|e| format!("Invalid keys file {}: {}", path.display(), e))?;
+
+	for entry in &entries {
+		let (key_type, scheme) = KEY_TYPES
+			.into_iter()
+			.find(|(key_type, _)| key_type.0 == entry.key_type.as_bytes())
+			.ok_or_else(|| format!("Unknown key type `{}` in the keys file", entry.key_type))?;
+		insert(keystore, key_type, scheme, &entry.suri)?;
+	}
+	log::info!("Inserted {} keys from {} into the keystore", entries.len(), path.display());
+	Ok(entries.len())
+}
+
+/// Derive a `key_type` key from `suri` and store it.
+///
+/// Keys are stored by type and public key, so inserting one the keystore holds already
+/// changes nothing.
+fn insert(
+	keystore: &KeystorePtr,
+	key_type: KeyTypeId,
+	scheme: Scheme,
+	suri: &str,
+) -> Result<(), String> {
+	let inserted = match scheme {
+		Scheme::Sr25519 => keystore.sr25519_generate_new(key_type, Some(suri)).map(|_| ()),
+		Scheme::Ed25519 => keystore.ed25519_generate_new(key_type, Some(suri)).map(|_| ()),
+	};
+	inserted.map_err(|e| {
+		let name = String::from_utf8_lossy(&key_type.0).into_owned();
+		format!("Failed to add the `{}` key: {}", name, e)
+	})
+}
+
+/// Warn when the keys file, which holds secrets, can be read by other users.
+#[cfg(unix)]
+fn warn_if_readable_by_others(file: &std::fs::File, path: &Path) {
+	use std::os::unix::fs::PermissionsExt;
+
+	if file.metadata().map_or(false, |metadata| metadata.permissions().mode() & 0o077 != 0) {
+		log::warn!(
+			"The keys file {} can be read by other users; restrict it with `chmod 600`",
+			path.display()
+		);
+	}
+}
+
+#[cfg(not(unix))]
+fn warn_if_readable_by_others(_file: &std::fs::File, _path: &Path) {}
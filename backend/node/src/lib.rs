@@ -1,5 +1,9 @@
 pub mod chain_spec;
 pub mod cli;
 pub mod command;
+pub mod keys;
+pub mod metrics;
 pub mod rpc;
-pub mod service;
\ No newline at end of file
+pub mod service;
+pub mod settlement;
+pub mod simulate;
\ No newline at end of file
@@ -5,8 +5,12 @@
 mod chain_spec;
 mod cli;
 mod command;
+mod keys;
+mod metrics;
 mod rpc;
 mod service;
+mod settlement;
+mod simulate;
 
 fn main() -> sc_cli::Result<()> {
 	command::run()
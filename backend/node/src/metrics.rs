@@ -0,0 +1,144 @@
+//! Prometheus metrics for tick settlement and stream payments.
+//!
+//! Every new best block is read for its tick-stream events and live streams, and the
+//! transaction pool's refusals of the offchain worker's unsigned ticks are read from offchain
+//! storage, so dashboards can follow payment health next to the default Substrate metrics.
+
+use codec::Decode;
+use futures::StreamExt;
+use sc_client_api::{Backend, BlockchainEvents, StorageProvider};
+use sp_core::{offchain::OffchainStorage, storage::StorageKey, twox_128, H256};
+use std::sync::Arc;
+use substrate_prometheus_endpoint::{register, Counter, Gauge, PrometheusError, Registry, F64, U64};
+use tick_stream::{StreamStatus, OCW_REJECTED_TICKS_KEY};
+use vilokanam_runtime::{opaque::Block, AccountId, AssetId, Balance, BlockNumber, RuntimeEvent};
+
+type Stream = tick_stream::Stream<AccountId, Balance, BlockNumber, AssetId>;
+
+/// The tick-stream metrics of a node.
+pub struct TickStreamMetrics {
+	active_streams: Gauge<U64>,
+	block_ticks: Gauge<U64>,
+	ticks: Counter<U64>,
+	block_payment_volume: Gauge<F64>,
+	payment_volume: Counter<F64>,
+	rejected_ticks: Counter<U64>,
+}
+
+impl TickStreamMetrics {
+	/// Create the metrics and register them with `registry`.
+	pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			active_streams: register(
+				Gauge::new("vilokanam_active_streams", "Streams that are live")?,
+				registry,
+			)?,
+			block_ticks: register(
+				Gauge::new(
+					"vilokanam_block_ticks",
+					"Seconds of watch time settled in the best block",
+				)?,
+				registry,
+			)?,
+			ticks: register(
+				Counter::new("vilokanam_ticks_total", "Seconds of watch time settled")?,
+				registry,
+			)?,
+			block_payment_volume: register(
+				Gauge::new(
+					"vilokanam_block_payment_volume",
+					"Value paid for watch time and tips in the best block, in plancks",
+				)?,
+				registry,
+			)?,
+			payment_volume: register(
+				Counter::new(
+					"vilokanam_payment_volume_total",
+					"Value paid for watch time and tips, in plancks",
+				)?,
+				registry,
+			)?,
+			rejected_ticks: register(
+				Counter::new(
+					"vilokanam_rejected_unsigned_ticks_total",
+					"Unsigned ticks from the offchain worker refused by the transaction pool",
+				)?,
+				registry,
+			)?,
+		})
+	}
+
+	/// Update the metrics from every new best block until the client shuts down.
+	pub async fn run<C, B>(self, client: Arc<C>, backend: Arc<B>)
+	where
+		B: Backend<Block>,
+		C: BlockchainEvents<Block> + StorageProvider<Block, B>,
+	{
+		let mut imports = client.import_notification_stream();
+		while let Some(notification) = imports.next().await {
+			if notification.is_new_best {
+				self.record_block(&*client, notification.hash);
+				self.record_rejected_ticks(&*backend);
+			}
+		}
+	}
+
+	/// Record the settlements and live streams of block `hash`.
+	fn record_block<C, B>(&self, client: &C, hash: H256)
+	where
+		B: Backend<Block>,
+		C: StorageProvider<Block, B>,
+	{
+		let key = StorageKey([twox_128(b"System"), twox_128(b"Events")].concat());
+		let records = match client.storage(hash, &key) {
+			Ok(Some(data)) => Vec::<frame_system::EventRecord<RuntimeEvent, H256>>::decode(
+				&mut &data.0[..],
+			)
+			.unwrap_or_default(),
+			_ => Vec::new(),
+		};
+
+		let (mut ticks, mut volume) = (0u64, 0u128);
+		for record in records {
+			match record.event {
+				RuntimeEvent::TickStream(tick_stream::Event::TickRecorded {
+					ticks: settled,
+					amount,
+					..
+				}) => {
+					ticks = ticks.saturating_add(settled.into());
+					volume = volume.saturating_add(amount);
+				},
+				RuntimeEvent::TickStream(tick_stream::Event::TipReceived { amount, .. }) =>
+					volume = volume.saturating_add(amount),
+				_ => {},
+			}
+		}
+		self.block_ticks.set(ticks);
+		self.ticks.inc_by(ticks);
+		self.block_payment_volume.set(volume as f64);
+		self.payment_volume.inc_by(volume as f64);
+
+		let prefix = StorageKey([twox_128(b"TickStream"), twox_128(b"Streams")].concat());
+		if let Ok(streams) = client.storage_pairs(hash, Some(&prefix), None) {
+			let live = streams
+				.filter(|(_, data)| {
+					Stream::decode(&mut &data.0[..])
+						.is_ok_and(|stream| stream.status == StreamStatus::Live)
+				})
+				.count();
+			self.active_streams.set(live as u64);
+		}
+	}
+
+	/// Catch the rejected ticks counter up with the offchain worker's tally, which outlives
+	/// restarts of the node.
+	fn record_rejected_ticks<B: Backend<Block>>(&self, backend: &B) {
+		let Some(storage) = backend.offchain_storage() else { return };
+		let rejected = storage
+			.get(sp_offchain::STORAGE_PREFIX, OCW_REJECTED_TICKS_KEY)
+			.and_then(|data| u64::decode(&mut &data[..]).ok())
+			.unwrap_or_default();
+		self.rejected_ticks.inc_by(rejected.saturating_sub(self.rejected_ticks.get()));
+	}
+}
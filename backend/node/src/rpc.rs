@@ -1,36 +1,87 @@
-use vilokanam_runtime::{opaque::Block, AccountId, Balance, Index};
+use vilokanam_runtime::{opaque::Block, AccountId, AssetId, Balance, BlockNumber, Hash, Index};
+use futures::channel::mpsc;
+use sc_consensus_manual_seal::EngineCommand;
+use sc_rpc::SubscriptionTaskExecutor;
+use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::TransactionPool;
+use sp_keystore::KeystorePtr;
 use std::sync::Arc;
 use jsonrpsee::RpcModule;
 
-pub struct FullDeps<C, P> {
+pub mod archive;
+pub mod cache;
+pub mod errors;
+pub mod events;
+pub mod streaming;
+#[cfg(feature = "try-runtime")]
+pub mod upgrade;
+
+pub struct FullDeps<C, P, B> {
 	/// The client instance to use.
 	pub client: Arc<C>,
+	/// The database backend, whose state upgrade dry runs execute against.
+	pub backend: Arc<B>,
 	/// Transaction pool instance.
 	pub pool: Arc<P>,
+	/// The node keystore, used to sign payout statements.
+	pub keystore: KeystorePtr,
+	/// Executor for RPC subscription tasks.
+	pub subscription_executor: SubscriptionTaskExecutor,
+	/// Whether the node runs as a read replica, caching tick-stream queries.
+	pub read_replica: bool,
+	/// Whether to deny unsafe calls.
+	pub deny_unsafe: DenyUnsafe,
+	/// Where `engine_*` requests go, on nodes that seal blocks on demand.
+	pub command_sink: Option<mpsc::Sender<EngineCommand<Hash>>>,
 }
 
+/// Tick-stream query results a read replica keeps for the current finalized block.
+const READ_REPLICA_CACHE_CAPACITY: usize = 16_384;
+
 /// Instantiate all RPC extensions.
-pub fn create_full<C, P>(
-	deps: FullDeps<C, P>,
+pub fn create_full<C, P, B>(
+	deps: FullDeps<C, P, B>,
 ) -> Result<RpcModule<()>, Box<dyn std::error::Error + Send + Sync>>
 where
 	C: sp_api::ProvideRuntimeApi<Block>,
 	C: sc_client_api::BlockBackend<Block>,
 	C: sc_client_api::BlockchainEvents<Block>,
+	C: sc_client_api::StorageProvider<Block, B>,
+	C: sp_blockchain::HeaderBackend<Block>,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: tick_stream::TickStreamApi<Block, AccountId, Balance, BlockNumber, AssetId>,
+	C::Api: sp_block_builder::BlockBuilder<Block>,
 	P: TransactionPool + 'static,
+	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
 {
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
+	use archive::{Archive, ArchiveApiServer};
+	use events::{StreamEvents, StreamEventsApiServer};
+	use streaming::{TickStream, TickStreamApiServer};
 
 	let mut module = RpcModule::new(());
-	let FullDeps { client, pool } = deps;
+	#[cfg(feature = "try-runtime")]
+	{
+		use upgrade::{Upgrade, UpgradeApiServer};
+		let upgrade = Upgrade::new(deps.client.clone(), deps.backend.clone(), deps.deny_unsafe);
+		module.merge(upgrade.into_rpc())?;
+	}
+	let FullDeps { client, pool, keystore, subscription_executor, read_replica, command_sink, .. } =
+		deps;
+	let cache = read_replica.then(|| Arc::new(cache::QueryCache::new(READ_REPLICA_CACHE_CAPACITY)));
 
 	module.merge(System::new(client.clone(), pool.clone()).into_rpc())?;
-	module.merge(TransactionPayment::new(client).into_rpc())?;
+	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	module.merge(TickStream::new(client.clone(), keystore, cache).into_rpc())?;
+	module.merge(Archive::<_, B>::new(client.clone()).into_rpc())?;
+	module.merge(StreamEvents::<_, B>::new(client, subscription_executor).into_rpc())?;
+	if let Some(command_sink) = command_sink {
+		use sc_consensus_manual_seal::rpc::{ManualSeal, ManualSealApiServer};
+		module.merge(ManualSeal::new(command_sink).into_rpc())?;
+	}
 
 	Ok(module)
 }
\ No newline at end of file
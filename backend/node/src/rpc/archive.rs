@@ -0,0 +1,254 @@
+//! A stream as it stood at a past block, for explorers.
+//!
+//! The stream is read straight from the state at the block rather than through the runtime
+//! API, so blocks from before an upgrade are answered too: the pallet's storage version at the
+//! block says which layout the stream was stored in. Only archive nodes keep the state of old
+//! blocks; pruned nodes answer for recent blocks only.
+
+use super::streaming::StreamStatusInfo;
+use codec::{Decode, Encode};
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{ErrorObject, ErrorObjectOwned},
+};
+use sc_client_api::{Backend, StorageProvider};
+use serde::Serialize;
+use sp_blockchain::HeaderBackend;
+use sp_core::{blake2_128, storage::StorageKey, twox_128, H256};
+use std::{marker::PhantomData, sync::Arc};
+use tick_stream::{migrations, ContentType, Stream};
+use vilokanam_runtime::{opaque::Block, AccountId, AssetId, Balance, BlockNumber};
+
+/// Error code for a block the node does not know.
+const UNKNOWN_BLOCK: i32 = 5;
+/// Error code for a block whose state the node has pruned.
+const STATE_PRUNED: i32 = 6;
+/// Error code for state that does not decode in the layout its storage version names.
+const UNDECODABLE_STATE: i32 = 7;
+
+/// The pallet's name in the runtime, which prefixes its storage keys.
+const PALLET: &[u8] = b"TickStream";
+/// The key, under the pallet's prefix, of its storage version.
+const STORAGE_VERSION_KEY: &[u8] = b":__STORAGE_VERSION__:";
+
+/// How a stream is delivered.
+#[derive(Clone, Copy, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ContentTypeInfo {
+	/// Broadcast live.
+	Live,
+	/// Played on demand.
+	Vod,
+}
+
+impl From<ContentType> for ContentTypeInfo {
+	fn from(content_type: ContentType) -> Self {
+		match content_type {
+			ContentType::Live => Self::Live,
+			ContentType::Vod => Self::Vod,
+		}
+	}
+}
+
+/// A stream as stored at a past block, with figures derived from the state around it.
+///
+/// Fields the pallet did not store yet at the block's storage version are `null`.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoricalStream {
+	/// The block the stream was read at.
+	pub block_hash: H256,
+	/// The number of that block.
+	pub block_number: BlockNumber,
+	/// The pallet's storage version at the block, which fixes the stream's layout.
+	pub storage_version: u16,
+	/// The account that created the stream.
+	pub creator: AccountId,
+	/// The price of a second of the stream, as a decimal string.
+	pub price_per_second: String,
+	/// The block the stream was last ticked in.
+	pub last_tick: BlockNumber,
+	/// The stream's state.
+	pub status: StreamStatusInfo,
+	/// How many blocks the stream could stay on air after going live.
+	pub max_duration: Option<BlockNumber>,
+	/// The asset the stream was priced in, or `null` for the native token.
+	pub asset_id: Option<AssetId>,
+	/// The account the creator's earnings were paid to, if not the creator.
+	pub payout_account: Option<AccountId>,
+	/// How the stream was delivered.
+	pub content_type: Option<ContentTypeInfo>,
+	/// The number of viewers watching at the block.
+	pub viewer_count: u32,
+	/// The number of ticks recorded for the stream up to the block.
+	pub tick_count: u32,
+	/// Blocks since the stream was last ticked.
+	pub blocks_since_last_tick: BlockNumber,
+	/// What the stream's viewers were charged per second between them at its list price, as
+	/// a decimal string.
+	pub earnings_per_second: String,
+}
+
+#[rpc(client, server)]
+pub trait ArchiveApi {
+	/// The stream `stream_id` as it stood at block `at`, or `null` if it did not exist then.
+	#[method(name = "tickStream_streamAt")]
+	fn stream_at(&self, stream_id: u128, at: H256) -> RpcResult<Option<HistoricalStream>>;
+}
+
+/// Implements the [`ArchiveApiServer`] RPC trait.
+pub struct Archive<C, B> {
+	client: Arc<C>,
+	_backend: PhantomData<B>,
+}
+
+impl<C, B> Archive<C, B> {
+	/// Create a new instance of the historical stream RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _backend: PhantomData }
+	}
+}
+
+impl<C, B> Archive<C, B>
+where
+	B: Backend<Block>,
+	C: StorageProvider<Block, B>,
+{
+	/// The raw value of `key` at `at`.
+	fn storage(&self, at: H256, key: Vec<u8>) -> RpcResult<Option<Vec<u8>>> {
+		let value = self.client.storage(at, &StorageKey(key)).map_err(|err| {
+			ErrorObject::owned(
+				STATE_PRUNED,
+				"State unavailable; query an archive node",
+				Some(err.to_string()),
+			)
+		})?;
+		Ok(value.map(|data| data.0))
+	}
+
+	/// The value of `key` at `at`, decoded as `T`.
+	fn decoded<T: Decode>(&self, at: H256, key: Vec<u8>) -> RpcResult<Option<T>> {
+		self.storage(at, key)?
+			.map(|data| T::decode(&mut &data[..]).map_err(undecodable))
+			.transpose()
+	}
+}
+
+impl<C, B> ArchiveApiServer for Archive<C, B>
+where
+	B: Backend<Block> + Send + Sync + 'static,
+	C: HeaderBackend<Block> + StorageProvider<Block, B> + Send + Sync + 'static,
+{
+	fn stream_at(&self, stream_id: u128, at: H256) -> RpcResult<Option<HistoricalStream>> {
+		let block_number = self
+			.client
+			.number(at)
+			.ok()
+			.flatten()
+			.ok_or_else(|| ErrorObject::owned(UNKNOWN_BLOCK, "Unknown block", Some(at)))?;
+
+		// Chains without a storage version were at version 1
+		let version_key = [twox_128(PALLET), twox_128(STORAGE_VERSION_KEY)].concat();
+		let storage_version = self.decoded::<u16>(at, version_key)?.unwrap_or(1);
+
+		let Some(data) = self.storage(at, map_key(b"Streams", stream_id))? else {
+			return Ok(None)
+		};
+		let stream = decode_stream(storage_version, &data).map_err(undecodable)?;
+
+		let viewers = self
+			.decoded::<Vec<AccountId>>(at, map_key(b"StreamViewers", stream_id))?
+			.unwrap_or_default();
+		let tick_count =
+			self.decoded::<u32>(at, map_key(b"TickCount", stream_id))?.unwrap_or_default();
+		let viewer_count = viewers.len() as u32;
+		let earnings_per_second = stream.price_per_second.saturating_mul(viewer_count.into());
+		let blocks_since_last_tick = block_number.saturating_sub(stream.last_tick);
+
+		Ok(Some(HistoricalStream {
+			block_hash: at,
+			block_number,
+			storage_version,
+			creator: stream.creator,
+			price_per_second: stream.price_per_second.to_string(),
+			last_tick: stream.last_tick,
+			status: stream.status.into(),
+			max_duration: stream.max_duration,
+			asset_id: stream.asset_id,
+			payout_account: stream.payout_account,
+			content_type: (storage_version >= 6).then(|| stream.content_type.into()),
+			viewer_count,
+			tick_count,
+			blocks_since_last_tick,
+			earnings_per_second: earnings_per_second.to_string(),
+		}))
+	}
+}
+
+/// The storage key of the entry for `stream_id` in the pallet's `Blake2_128Concat` map `item`.
+fn map_key(item: &[u8], stream_id: u128) -> Vec<u8> {
+	let id = stream_id.encode();
+	[&twox_128(PALLET)[..], &twox_128(item), &blake2_128(&id), &id].concat()
+}
+
+/// A stream stored at `storage_version`, in the current layout. Fields the layout lacks keep
+/// their defaults.
+fn decode_stream(
+	storage_version: u16,
+	mut data: &[u8],
+) -> Result<Stream<AccountId, Balance, BlockNumber, AssetId>, codec::Error> {
+	let stream = match storage_version {
+		0..=1 => {
+			let old = migrations::v1::Stream::<AccountId, Balance, BlockNumber>::decode(&mut data)?;
+			Stream {
+				creator: old.creator,
+				price_per_second: old.price_per_second,
+				last_tick: old.last_tick,
+				status: old.status,
+				max_duration: old.max_duration,
+				asset_id: None,
+				payout_account: None,
+				content_type: ContentType::Live,
+			}
+		},
+		2..=4 => {
+			let old =
+				migrations::v4::Stream::<AccountId, Balance, BlockNumber, AssetId>::decode(
+					&mut data,
+				)?;
+			Stream {
+				creator: old.creator,
+				price_per_second: old.price_per_second,
+				last_tick: old.last_tick,
+				status: old.status,
+				max_duration: old.max_duration,
+				asset_id: old.asset_id,
+				payout_account: None,
+				content_type: ContentType::Live,
+			}
+		},
+		5 => {
+			let old =
+				migrations::v5::Stream::<AccountId, Balance, BlockNumber, AssetId>::decode(
+					&mut data,
+				)?;
+			Stream {
+				creator: old.creator,
+				price_per_second: old.price_per_second,
+				last_tick: old.last_tick,
+				status: old.status,
+				max_duration: old.max_duration,
+				asset_id: old.asset_id,
+				payout_account: old.payout_account,
+				content_type: ContentType::Live,
+			}
+		},
+		_ => Stream::decode(&mut data)?,
+	};
+	Ok(stream)
+}
+
+fn undecodable(err: codec::Error) -> ErrorObjectOwned {
+	ErrorObject::owned(UNDECODABLE_STATE, "Undecodable state", Some(err.to_string()))
+}
@@ -0,0 +1,66 @@
+//! Cache of tick-stream query results for read replicas.
+//!
+//! A result read at a given block never changes, so entries are keyed by block hash and
+//! never go stale. Only the most recent block's results are kept: the cache is emptied as
+//! soon as a query for another block comes in.
+
+use codec::Encode;
+use sp_core::H256;
+use std::{
+	any::Any,
+	collections::HashMap,
+	sync::{Arc, Mutex},
+};
+
+type Entry = Arc<dyn Any + Send + Sync>;
+
+/// Query results at one block, keyed by method name and SCALE-encoded parameters.
+pub struct QueryCache {
+	capacity: usize,
+	entries: Mutex<(H256, HashMap<(&'static str, Vec<u8>), Entry>)>,
+}
+
+impl QueryCache {
+	/// Create a cache holding at most `capacity` results.
+	pub fn new(capacity: usize) -> Self {
+		Self { capacity, entries: Mutex::new((H256::zero(), HashMap::new())) }
+	}
+
+	/// The result of `method` with `params` at block `at`, computed with `query` on a miss.
+	///
+	/// Errors are not cached.
+	pub fn get_or_try_insert<T, E>(
+		&self,
+		method: &'static str,
+		at: H256,
+		params: impl Encode,
+		query: impl FnOnce() -> Result<T, E>,
+	) -> Result<T, E>
+	where
+		T: Clone + Send + Sync + 'static,
+	{
+		let key = (method, params.encode());
+		{
+			let (cached_at, entries) = &*self.entries.lock().expect("cache lock poisoned");
+			if *cached_at == at {
+				let cached = entries.get(&key).and_then(|entry| entry.downcast_ref::<T>());
+				if let Some(result) = cached {
+					return Ok(result.clone())
+				}
+			}
+		}
+
+		// Queried without holding the lock, so a slow query does not hold up the others
+		let result = query()?;
+
+		let (cached_at, entries) = &mut *self.entries.lock().expect("cache lock poisoned");
+		if *cached_at != at {
+			*cached_at = at;
+			entries.clear();
+		}
+		if entries.len() < self.capacity {
+			entries.insert(key, Arc::new(result.clone()));
+		}
+		Ok(result)
+	}
+}
@@ -0,0 +1,125 @@
+//! Mapping of runtime failures to JSON-RPC errors shared by the custom RPC methods.
+//!
+//! Clients match on the error code instead of parsing messages. The codes are:
+//!
+//! | Code | Meaning |
+//! |------|---------|
+//! | 1 | The runtime API call failed; `data` holds the node's description |
+//! | 10 | The transaction is invalid; `data` holds the reason |
+//! | 11 | The transaction's validity could not be determined; `data` holds the reason |
+//! | 20 | `DispatchError::Other` |
+//! | 21 | `DispatchError::CannotLookup` |
+//! | 22 | `DispatchError::BadOrigin` |
+//! | 23 | `DispatchError::ConsumerRemaining` |
+//! | 24 | `DispatchError::NoProviders` |
+//! | 25 | `DispatchError::TooManyConsumers` |
+//! | 26 | `DispatchError::Token`; `data` names the token error |
+//! | 27 | `DispatchError::Arithmetic`; `data` names the arithmetic error |
+//! | 28 | `DispatchError::Transactional`; `data` names the transactional error |
+//! | 29 | `DispatchError::Exhausted` |
+//! | 30 | `DispatchError::Corruption` |
+//! | 31 | `DispatchError::Unavailable` |
+//! | 32 | `DispatchError::RootNotAllowed` |
+//! | 10000 + 256 × pallet index + error index | A pallet error; see [`ModuleErrorData`] |
+//!
+//! A pallet error's message is `Pallet.Variant`, e.g. `TickStream.InsufficientBalance`, and
+//! its code stays the same for as long as the pallet's index and error order do.
+
+use jsonrpsee::types::error::{ErrorObject, ErrorObjectOwned};
+use serde::Serialize;
+use sp_runtime::{transaction_validity::TransactionValidityError, DispatchError, ModuleError};
+use vilokanam_runtime::RuntimeError;
+
+/// Error code for failures inside the runtime API call.
+pub const RUNTIME_ERROR: i32 = 1;
+/// Error code for a transaction the runtime rejects as invalid.
+pub const INVALID_TRANSACTION: i32 = 10;
+/// Error code for a transaction whose validity the runtime cannot determine.
+pub const UNKNOWN_TRANSACTION: i32 = 11;
+/// Error code of the first pallet error; see the module docs.
+pub const MODULE_ERROR_BASE: i32 = 10_000;
+
+/// The `data` of a pallet error.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleErrorData {
+	/// The pallet's name in the runtime, e.g. `TickStream`.
+	pub pallet: String,
+	/// The pallet's index in the runtime.
+	pub pallet_index: u8,
+	/// The error variant's name, e.g. `InsufficientBalance`.
+	pub error: String,
+	/// The error variant's index in the pallet.
+	pub error_index: u8,
+}
+
+/// A failure to call into the runtime.
+pub fn runtime_error(err: impl std::fmt::Debug) -> ErrorObjectOwned {
+	ErrorObject::owned(RUNTIME_ERROR, "Runtime error", Some(format!("{:?}", err)))
+}
+
+/// A transaction the runtime would not include.
+pub fn validity_error(err: TransactionValidityError) -> ErrorObjectOwned {
+	match err {
+		TransactionValidityError::Invalid(invalid) => ErrorObject::owned(
+			INVALID_TRANSACTION,
+			"Invalid transaction",
+			Some(format!("{:?}", invalid)),
+		),
+		TransactionValidityError::Unknown(unknown) => ErrorObject::owned(
+			UNKNOWN_TRANSACTION,
+			"Unknown transaction validity",
+			Some(format!("{:?}", unknown)),
+		),
+	}
+}
+
+/// A call that failed to dispatch, named after the error it failed with.
+pub fn dispatch_error(err: DispatchError) -> ErrorObjectOwned {
+	let (code, message) = match err {
+		DispatchError::Module(module) => return module_error(module),
+		DispatchError::Other(reason) =>
+			return ErrorObject::owned(20, "Other", (!reason.is_empty()).then_some(reason)),
+		DispatchError::CannotLookup => (21, "CannotLookup"),
+		DispatchError::BadOrigin => (22, "BadOrigin"),
+		DispatchError::ConsumerRemaining => (23, "ConsumerRemaining"),
+		DispatchError::NoProviders => (24, "NoProviders"),
+		DispatchError::TooManyConsumers => (25, "TooManyConsumers"),
+		DispatchError::Token(token) =>
+			return ErrorObject::owned(26, "Token", Some(format!("{:?}", token))),
+		DispatchError::Arithmetic(arithmetic) =>
+			return ErrorObject::owned(27, "Arithmetic", Some(format!("{:?}", arithmetic))),
+		DispatchError::Transactional(transactional) =>
+			return ErrorObject::owned(28, "Transactional", Some(format!("{:?}", transactional))),
+		DispatchError::Exhausted => (29, "Exhausted"),
+		DispatchError::Corruption => (30, "Corruption"),
+		DispatchError::Unavailable => (31, "Unavailable"),
+		DispatchError::RootNotAllowed => (32, "RootNotAllowed"),
+	};
+	ErrorObject::owned(code, message, None::<()>)
+}
+
+/// A pallet error, named from the runtime's error types.
+fn module_error(module: ModuleError) -> ErrorObjectOwned {
+	let pallet_index = module.index;
+	let error_index = module.error[0];
+	let code = MODULE_ERROR_BASE + 256 * i32::from(pallet_index) + i32::from(error_index);
+
+	// The runtime error's debug output is `Pallet(Variant)`, or `Pallet(Variant { .. })`
+	// for errors with fields
+	let (pallet, error) = RuntimeError::from_dispatch_error(DispatchError::Module(module))
+		.and_then(|runtime_error| {
+			let debug = format!("{:?}", runtime_error);
+			let (pallet, error) = debug.split_once('(')?;
+			let error = error.strip_suffix(')')?;
+			let error = error.split([' ', '(']).next().unwrap_or(error);
+			Some((pallet.to_string(), error.to_string()))
+		})
+		.unwrap_or_else(|| (format!("Pallet{}", pallet_index), format!("Error{}", error_index)));
+
+	ErrorObject::owned(
+		code,
+		format!("{}.{}", pallet, error),
+		Some(ModuleErrorData { pallet, pallet_index, error, error_index }),
+	)
+}
@@ -0,0 +1,198 @@
+//! Subscription to the tick-stream events of a single stream.
+//!
+//! Events are read from each finalized block and pushed to subscribers, so dashboards can
+//! update watch time and earnings without polling storage.
+
+use codec::Decode;
+use futures::{stream, FutureExt, StreamExt};
+use jsonrpsee::{proc_macros::rpc, PendingSubscriptionSink};
+use sc_client_api::{Backend, BlockchainEvents, StorageProvider};
+use sc_rpc::SubscriptionTaskExecutor;
+use serde::Serialize;
+use sp_core::{storage::StorageKey, twox_128, H256};
+use std::{marker::PhantomData, sync::Arc};
+use vilokanam_runtime::{opaque::Block, AccountId, RuntimeEvent};
+
+/// A tick-stream event concerning one stream, as pushed to subscribers.
+///
+/// `sequence` is the event's position among the tick-stream events of its block, which
+/// orders events totally within a block. It is `null` for blocks from before the runtime
+/// numbered its events.
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum StreamEvent {
+	/// A viewer has been charged for watch time.
+	TickProcessed {
+		block_hash: H256,
+		sequence: Option<u32>,
+		viewer: AccountId,
+		ticks: u32,
+		/// The amount charged, as a decimal string.
+		amount: String,
+	},
+	/// A viewer has joined the stream.
+	ViewerJoined { block_hash: H256, sequence: Option<u32>, viewer: AccountId },
+	/// A viewer has left the stream and their remaining reserve was returned.
+	ViewerLeft {
+		block_hash: H256,
+		sequence: Option<u32>,
+		viewer: AccountId,
+		/// The reserve returned, as a decimal string.
+		refunded: String,
+	},
+	/// A viewer ran out of funds and has been taken off the stream, so their player should
+	/// stop.
+	ViewerEjected {
+		block_hash: H256,
+		sequence: Option<u32>,
+		viewer: AccountId,
+		/// What the viewer was short of for their last ticks, as a decimal string.
+		shortfall: String,
+	},
+	/// The number of viewers watching the stream has changed.
+	ViewerCountChanged { block_hash: H256, sequence: Option<u32>, viewers: u32 },
+	/// Escrowed earnings have been withdrawn.
+	Withdrawn {
+		block_hash: H256,
+		sequence: Option<u32>,
+		who: AccountId,
+		/// The amount withdrawn, as a decimal string.
+		amount: String,
+	},
+	/// A viewer has tipped the creator, for display as an overlay.
+	TipReceived {
+		block_hash: H256,
+		sequence: Option<u32>,
+		tipper: AccountId,
+		/// The amount tipped, as a decimal string.
+		amount: String,
+		/// The attached message, with invalid UTF-8 replaced.
+		message: Option<String>,
+	},
+}
+
+#[rpc(client, server)]
+pub trait StreamEventsApi {
+	/// Push the tick, join, leave, ejection, viewer count, withdrawal and tip events of a
+	/// stream as blocks are finalized.
+	#[subscription(
+		name = "tickStream_subscribeStreamEvents" => "tickStream_streamEvent",
+		unsubscribe = "tickStream_unsubscribeStreamEvents",
+		item = StreamEvent
+	)]
+	fn subscribe_stream_events(&self, stream_id: u128);
+}
+
+/// Implements the [`StreamEventsApiServer`] RPC trait.
+pub struct StreamEvents<C, B> {
+	client: Arc<C>,
+	executor: SubscriptionTaskExecutor,
+	_backend: PhantomData<B>,
+}
+
+impl<C, B> StreamEvents<C, B> {
+	/// Create a new instance of the stream events RPC handler.
+	pub fn new(client: Arc<C>, executor: SubscriptionTaskExecutor) -> Self {
+		Self { client, executor, _backend: PhantomData }
+	}
+}
+
+impl<C, B> StreamEventsApiServer for StreamEvents<C, B>
+where
+	B: Backend<Block> + Send + Sync + 'static,
+	C: BlockchainEvents<Block> + StorageProvider<Block, B> + Send + Sync + 'static,
+{
+	fn subscribe_stream_events(&self, pending: PendingSubscriptionSink, stream_id: u128) {
+		let client = self.client.clone();
+		let events = self.client.finality_notification_stream().flat_map(move |notification| {
+			// Blocks finalized implicitly along with the newest come first, oldest to newest
+			let blocks = notification.tree_route.iter().copied().chain([notification.hash]);
+			let events = blocks.flat_map(|hash| stream_events(&*client, hash, stream_id));
+			stream::iter(events.collect::<Vec<_>>())
+		});
+
+		self.executor.spawn(
+			"tick-stream-events-subscription",
+			Some("rpc"),
+			sc_rpc::utils::pipe_from_stream(pending, events).boxed(),
+		);
+	}
+}
+
+/// The events of `stream_id` deposited in block `hash`.
+fn stream_events<C, B>(client: &C, hash: H256, stream_id: u128) -> Vec<StreamEvent>
+where
+	B: Backend<Block>,
+	C: StorageProvider<Block, B>,
+{
+	let key = StorageKey([twox_128(b"System"), twox_128(b"Events")].concat());
+	let records = match client.storage(hash, &key) {
+		Ok(Some(data)) => Vec::<frame_system::EventRecord<RuntimeEvent, H256>>::decode(
+			&mut &data.0[..],
+		)
+		.unwrap_or_default(),
+		_ => return Vec::new(),
+	};
+
+	records
+		.into_iter()
+		.filter_map(|record| {
+			let RuntimeEvent::TickStream(event) = record.event else { return None };
+			let block_hash = hash;
+			let sequence = record
+				.topics
+				.iter()
+				.find_map(|topic| tick_stream::event_sequence(topic.as_bytes()));
+			match event {
+				tick_stream::Event::TickRecorded { stream_id: id, viewer, ticks, amount, .. }
+					if id == stream_id =>
+					Some(StreamEvent::TickProcessed {
+						block_hash,
+						sequence,
+						viewer,
+						ticks,
+						amount: amount.to_string(),
+					}),
+				tick_stream::Event::ViewerJoined { stream_id: id, viewer } if id == stream_id =>
+					Some(StreamEvent::ViewerJoined { block_hash, sequence, viewer }),
+				tick_stream::Event::ViewerRefunded { stream_id: id, viewer, amount }
+					if id == stream_id =>
+					Some(StreamEvent::ViewerLeft {
+						block_hash,
+						sequence,
+						viewer,
+						refunded: amount.to_string(),
+					}),
+				tick_stream::Event::ViewerFundsExhausted { stream_id: id, viewer, shortfall, .. }
+					if id == stream_id =>
+					Some(StreamEvent::ViewerEjected {
+						block_hash,
+						sequence,
+						viewer,
+						shortfall: shortfall.to_string(),
+					}),
+				tick_stream::Event::ViewerCountChanged { stream_id: id, viewers }
+					if id == stream_id =>
+					Some(StreamEvent::ViewerCountChanged { block_hash, sequence, viewers }),
+				tick_stream::Event::Withdrawn { stream_id: id, who, amount } if id == stream_id =>
+					Some(StreamEvent::Withdrawn {
+						block_hash,
+						sequence,
+						who,
+						amount: amount.to_string(),
+					}),
+				tick_stream::Event::TipReceived { stream_id: id, tipper, amount, message }
+					if id == stream_id =>
+					Some(StreamEvent::TipReceived {
+						block_hash,
+						sequence,
+						tipper,
+						amount: amount.to_string(),
+						message: message
+							.map(|message| String::from_utf8_lossy(&message).into_owned()),
+					}),
+				_ => None,
+			}
+		})
+		.collect()
+}
@@ -0,0 +1,754 @@
+//! Custom RPC methods for the tick-stream pallet.
+
+use super::{
+	cache::QueryCache,
+	errors::{dispatch_error, runtime_error, validity_error},
+};
+use codec::{Decode, Encode};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, types::error::ErrorObject};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_block_builder::BlockBuilder;
+use sp_blockchain::HeaderBackend;
+use sp_core::{crypto::KeyTypeId, sr25519, Bytes, H256};
+use sp_keystore::KeystorePtr;
+use sp_runtime::traits::Block as BlockT;
+use std::sync::Arc;
+use tick_stream::{
+	ConfigFingerprint, ConfigParameter, EraEarnings, EraSettlementProof, ReportingUnit,
+	SessionState, SettlementHealth, StorageFootprint, StreamEarnings, StreamFilter, StreamReport,
+	StreamStatus, StreamSummary, TickStreamApi as TickStreamRuntimeApi, UpcomingStream,
+	WatchStats,
+};
+use vilokanam_runtime::{opaque::Block, AccountId, AssetId, Balance, BlockNumber};
+
+/// Key type of the node key used to sign payout statements.
+pub const STATEMENT_KEY_TYPE: KeyTypeId = KeyTypeId(*b"stmt");
+
+/// Domain separator prefixed to every signed payout statement.
+const PAYOUT_STATEMENT_DOMAIN: &[u8] = b"vilokanam/payout-statement/v1";
+
+/// Error code for a node without a statement key in its keystore.
+const NO_STATEMENT_KEY: i32 = 2;
+/// Error code for bytes that do not decode as an extrinsic.
+const UNDECODABLE_EXTRINSIC: i32 = 3;
+
+/// A creator's earnings for an era, signed by the node's statement key.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedPayoutStatement {
+	/// The creator the statement is for.
+	pub creator: AccountId,
+	/// The era the statement covers.
+	pub era: u32,
+	/// The block whose state the statement was produced from.
+	pub block_hash: H256,
+	/// Seconds of watch time settled in the era.
+	pub seconds: u64,
+	/// Total amount charged to viewers, as a decimal string.
+	pub gross: String,
+	/// The canonical SCALE encoding that was signed.
+	pub scale: Bytes,
+	/// The sr25519 public key of the signing node.
+	pub signer: sr25519::Public,
+	/// The sr25519 signature over `scale`.
+	pub signature: Bytes,
+}
+
+/// A viewer's standing on a stream, for "balance remaining" meters.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ViewerSessionInfo {
+	/// The balance still reserved for the stream, as a decimal string.
+	pub reserved: String,
+	/// Seconds of the stream the viewer has been charged for.
+	pub seconds_watched: u64,
+	/// Seconds the reserve covers at the viewer's price; `null` if they watch for free.
+	pub seconds_remaining: Option<u64>,
+	/// Whether ticks are stopped until the viewer resumes, as they reached a spending limit.
+	pub paused: bool,
+	/// Seconds still to be watched for free, under the stream's preview or the grace at the
+	/// start of a session.
+	pub free_seconds_left: u32,
+}
+
+/// A viewer's lifetime watch time and spend on one stream, for loyalty features.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ViewerStatsInfo {
+	/// The stream watched.
+	pub stream_id: u128,
+	/// Seconds of the stream the viewer has been charged for across all sessions.
+	pub total_seconds: u64,
+	/// Total amount the viewer has been charged for the stream, as a decimal string.
+	pub total_spent: String,
+	/// The block the viewer was last charged in.
+	pub last_watched_block: BlockNumber,
+}
+
+/// The entry count and size of one storage item of the pallet.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageItemFootprint {
+	/// The storage item's name.
+	pub name: String,
+	/// The number of entries stored.
+	pub entries: u32,
+	/// The size of all keys and values stored, in bytes.
+	pub bytes: u64,
+}
+
+/// One of the pallet's parameters, by name.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigParameterInfo {
+	/// The constant's or storage item's name.
+	pub name: String,
+	/// Its SCALE-encoded value.
+	pub value: Bytes,
+}
+
+/// The pallet's parameters at a block, with a hash to compare runtimes by.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigFingerprintInfo {
+	/// The block the parameters were read at.
+	pub block_hash: H256,
+	/// The blake2-256 hash of the encoded parameters; equal hashes mean equal parameters.
+	pub hash: H256,
+	/// Every parameter, sorted by name.
+	pub parameters: Vec<ConfigParameterInfo>,
+}
+
+/// The unit a stream's earnings are reported in.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReportingUnitInfo {
+	/// The native token.
+	Native,
+	/// An asset, by id.
+	Asset(AssetId),
+	/// The oracle's fiat currency, in its smallest unit.
+	Fiat,
+}
+
+impl From<ReportingUnit<AssetId>> for ReportingUnitInfo {
+	fn from(unit: ReportingUnit<AssetId>) -> Self {
+		match unit {
+			ReportingUnit::Native => Self::Native,
+			ReportingUnit::Asset(asset_id) => Self::Asset(asset_id),
+			ReportingUnit::Fiat => Self::Fiat,
+		}
+	}
+}
+
+/// A stream's earnings over an era, in the unit its creator reports in.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamReportInfo {
+	/// The unit `gross` is in.
+	pub unit: ReportingUnitInfo,
+	/// Seconds of watch time settled in the era.
+	pub seconds: u64,
+	/// Total charged to viewers and subscribers, at the rate of each settlement, as a decimal
+	/// string.
+	pub gross: String,
+	/// In fiat reports, what was paid while the oracle had no rate, in the stream's own
+	/// currency, as a decimal string.
+	pub unpriced: String,
+}
+
+/// What a stream earned over an era, with the proof that leads from it to the root committed to
+/// in the header of the era's first block after it.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EraSettlementProofInfo {
+	/// The era the earnings are for.
+	pub era: u32,
+	/// The era's committed root.
+	pub root: H256,
+	/// The number of streams, and so of leaves, under the root.
+	pub streams: u32,
+	/// Seconds of watch time settled.
+	pub seconds: u64,
+	/// Total charged to viewers and subscribers, in the stream's currency, as a decimal string.
+	pub gross: String,
+	/// Fiat value of the payments made while the oracle had a rate, as a decimal string.
+	pub fiat: String,
+	/// The part of `gross` paid while the oracle had no rate, as a decimal string.
+	pub unpriced: String,
+	/// The position of the stream's leaf.
+	pub index: u32,
+	/// The sibling hashes from the leaf up.
+	pub proof: Vec<H256>,
+}
+
+/// A stream's lifecycle state.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StreamStatusInfo {
+	/// Registered but not live yet.
+	Created,
+	/// On air.
+	Live,
+	/// Temporarily off air.
+	Paused,
+	/// Over.
+	Ended,
+}
+
+impl From<StreamStatus> for StreamStatusInfo {
+	fn from(status: StreamStatus) -> Self {
+		match status {
+			StreamStatus::Created => Self::Created,
+			StreamStatus::Live => Self::Live,
+			StreamStatus::Paused => Self::Paused,
+			StreamStatus::Ended => Self::Ended,
+		}
+	}
+}
+
+impl From<StreamStatusInfo> for StreamStatus {
+	fn from(status: StreamStatusInfo) -> Self {
+		match status {
+			StreamStatusInfo::Created => Self::Created,
+			StreamStatusInfo::Live => Self::Live,
+			StreamStatusInfo::Paused => Self::Paused,
+			StreamStatusInfo::Ended => Self::Ended,
+		}
+	}
+}
+
+/// Where a viewer's session on a stream stands.
+#[derive(Clone, Copy, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SessionStateInfo {
+	/// Joined and not charged yet.
+	Reserved,
+	/// Being charged.
+	Active,
+	/// Paused on the viewer's spending limit until they resume.
+	GracePaused,
+	/// No longer charged, with the reserve awaiting its refund.
+	Expired,
+	/// Over and refunded.
+	Settled,
+}
+
+impl From<SessionState> for SessionStateInfo {
+	fn from(state: SessionState) -> Self {
+		match state {
+			SessionState::Reserved => Self::Reserved,
+			SessionState::Active => Self::Active,
+			SessionState::GracePaused => Self::GracePaused,
+			SessionState::Expired => Self::Expired,
+			SessionState::Settled => Self::Settled,
+		}
+	}
+}
+
+/// Whether ticks are being settled, for uptime checks.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettlementHealthInfo {
+	/// The block ticks were last settled in.
+	pub last_settled: BlockNumber,
+	/// Whether no ticks have been settled for the runtime's `AlertAfterBlocks` while viewers
+	/// watch live streams.
+	pub stalled: bool,
+}
+
+/// Which streams to list; fields left out match any stream.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StreamFilterParams {
+	/// Only streams in this state.
+	pub status: Option<StreamStatusInfo>,
+	/// Only streams created by this account.
+	pub creator: Option<AccountId>,
+}
+
+/// A stream as listed on the catalog page.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamSummaryInfo {
+	/// The stream's id.
+	pub stream_id: u128,
+	/// The account that created the stream.
+	pub creator: AccountId,
+	/// The price of a second of the stream, as a decimal string.
+	pub price_per_second: String,
+	/// The stream's state.
+	pub status: StreamStatusInfo,
+	/// The number of viewers currently watching.
+	pub viewer_count: u32,
+}
+
+/// A scheduled stream in a viewer's "coming up" feed.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpcomingStreamInfo {
+	/// The stream's id.
+	pub stream_id: u128,
+	/// The account that created the stream.
+	pub creator: AccountId,
+	/// The stream's title, if its creator has set one.
+	pub title: Option<String>,
+	/// The planned start in unix seconds, UTC.
+	pub starts_at: u64,
+	/// The creator's offset from UTC in minutes, for showing the start in their local time.
+	pub utc_offset_minutes: i16,
+	/// The block the stream is expected to go live in.
+	pub estimated_block: BlockNumber,
+}
+
+#[rpc(client, server)]
+pub trait TickStreamApi {
+	/// Produce a signed statement of a creator's earnings for an era.
+	#[method(name = "tickStream_payoutStatement")]
+	fn payout_statement(
+		&self,
+		creator: AccountId,
+		era: u32,
+		at: Option<H256>,
+	) -> RpcResult<SignedPayoutStatement>;
+
+	/// A viewer's reserve and watch time on a stream, or `null` if they are not watching it.
+	#[method(name = "tickStream_viewerSession")]
+	fn viewer_session(
+		&self,
+		stream_id: u128,
+		account: AccountId,
+		at: Option<H256>,
+	) -> RpcResult<Option<ViewerSessionInfo>>;
+
+	/// A viewer's lifetime watch time and spend on every stream they have watched.
+	#[method(name = "tickStream_viewerStats")]
+	fn viewer_stats(&self, account: AccountId, at: Option<H256>) -> RpcResult<Vec<ViewerStatsInfo>>;
+
+	/// Apply a signed extrinsic on top of a block without keeping the result.
+	///
+	/// Returns `null` if it would succeed, and otherwise the error it would fail with, named
+	/// as described in the `errors` module.
+	#[method(name = "tickStream_dryRun")]
+	fn dry_run(&self, extrinsic: Bytes, at: Option<H256>) -> RpcResult<()>;
+
+	/// The entry count and size of every storage item of the pallet, for pruning and deposit
+	/// planning. Walks the whole pallet state.
+	#[method(name = "tickStream_storageFootprint")]
+	fn storage_footprint(&self, at: Option<H256>) -> RpcResult<Vec<StorageItemFootprint>>;
+
+	/// The pallet's config constants and governance-set fees and caps, with a hash over them.
+	/// Comparing the results at blocks either side of an upgrade shows which parameters it
+	/// changed.
+	#[method(name = "tickStream_configFingerprint")]
+	fn config_fingerprint(&self, at: Option<H256>) -> RpcResult<ConfigFingerprintInfo>;
+
+	/// A stream's earnings over an era in the unit its creator reports in, or `null` if the
+	/// stream does not exist.
+	#[method(name = "tickStream_streamReport")]
+	fn stream_report(
+		&self,
+		stream_id: u128,
+		era: u32,
+		at: Option<H256>,
+	) -> RpcResult<Option<StreamReportInfo>>;
+
+	/// What a stream earned over a finished era, with the Merkle proof of it against the root
+	/// in the header of the era's first block after it, or `null` if the stream settled
+	/// nothing in the era or the era has not ended.
+	#[method(name = "tickStream_eraSettlementProof")]
+	fn era_settlement_proof(
+		&self,
+		era: u32,
+		stream_id: u128,
+		at: Option<H256>,
+	) -> RpcResult<Option<EraSettlementProofInfo>>;
+
+	/// The state of a viewer's session on a stream, or `null` if they have none.
+	#[method(name = "tickStream_sessionState")]
+	fn session_state(
+		&self,
+		stream_id: u128,
+		account: AccountId,
+		at: Option<H256>,
+	) -> RpcResult<Option<SessionStateInfo>>;
+
+	/// Whether settlement has stalled chain-wide, as when the ticker fleet is down, and the
+	/// block ticks were last settled in.
+	#[method(name = "tickStream_settlementHealth")]
+	fn settlement_health(&self, at: Option<H256>) -> RpcResult<SettlementHealthInfo>;
+
+	/// Page through the streams matching `filter`, skipping the first `offset`. At most
+	/// `limit` streams are returned, and never more than the runtime's page size.
+	#[method(name = "tickStream_listStreams")]
+	fn list_streams(
+		&self,
+		offset: u32,
+		limit: u32,
+		filter: Option<StreamFilterParams>,
+		at: Option<H256>,
+	) -> RpcResult<Vec<StreamSummaryInfo>>;
+
+	/// Page through the scheduled streams of every creator whose streams `viewer` follows,
+	/// soonest first, skipping the first `offset`. At most `limit` streams are returned, and
+	/// never more than the runtime's page size.
+	#[method(name = "tickStream_upcomingStreams")]
+	fn upcoming_streams(
+		&self,
+		viewer: AccountId,
+		offset: u32,
+		limit: u32,
+		at: Option<H256>,
+	) -> RpcResult<Vec<UpcomingStreamInfo>>;
+}
+
+/// Implements the [`TickStreamApiServer`] RPC trait.
+pub struct TickStream<C> {
+	client: Arc<C>,
+	keystore: KeystorePtr,
+	/// Set on read replicas, which answer from finalized state and cache the answers.
+	cache: Option<Arc<QueryCache>>,
+}
+
+impl<C> TickStream<C> {
+	/// Create a new instance of the tick-stream RPC handler.
+	pub fn new(client: Arc<C>, keystore: KeystorePtr, cache: Option<Arc<QueryCache>>) -> Self {
+		Self { client, keystore, cache }
+	}
+}
+
+impl<C: HeaderBackend<Block>> TickStream<C> {
+	/// The block to query: `at` if given, otherwise the best block, or the finalized block on
+	/// read replicas so that cached answers are reused until finality moves on.
+	fn resolve(&self, at: Option<H256>) -> H256 {
+		at.unwrap_or_else(|| {
+			let info = self.client.info();
+			if self.cache.is_some() {
+				info.finalized_hash
+			} else {
+				info.best_hash
+			}
+		})
+	}
+
+	/// Run `query` at `at`, through the cache on read replicas.
+	fn cached<T, P>(
+		&self,
+		method: &'static str,
+		at: H256,
+		params: P,
+		query: impl FnOnce() -> RpcResult<T>,
+	) -> RpcResult<T>
+	where
+		T: Clone + Send + Sync + 'static,
+		P: Encode,
+	{
+		match &self.cache {
+			Some(cache) => cache.get_or_try_insert(method, at, params, query),
+			None => query(),
+		}
+	}
+}
+
+impl<C> TickStreamApiServer for TickStream<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: TickStreamRuntimeApi<Block, AccountId, Balance, BlockNumber, AssetId>,
+	C::Api: BlockBuilder<Block>,
+{
+	fn payout_statement(
+		&self,
+		creator: AccountId,
+		era: u32,
+		at: Option<H256>,
+	) -> RpcResult<SignedPayoutStatement> {
+		let at = self.resolve(at);
+		let EraEarnings { seconds, gross } = self
+			.client
+			.runtime_api()
+			.get_creator_earnings(at, creator.clone(), era)
+			.map_err(runtime_error)?;
+
+		let scale = (PAYOUT_STATEMENT_DOMAIN, at, &creator, era, seconds, gross).encode();
+
+		let signer = self
+			.keystore
+			.sr25519_public_keys(STATEMENT_KEY_TYPE)
+			.into_iter()
+			.next()
+			.ok_or_else(|| {
+				ErrorObject::owned(NO_STATEMENT_KEY, "No statement key in keystore", None::<()>)
+			})?;
+		let signature = self
+			.keystore
+			.sr25519_sign(STATEMENT_KEY_TYPE, &signer, &scale)
+			.map_err(runtime_error)?
+			.ok_or_else(|| {
+				ErrorObject::owned(NO_STATEMENT_KEY, "No statement key in keystore", None::<()>)
+			})?;
+
+		Ok(SignedPayoutStatement {
+			creator,
+			era,
+			block_hash: at,
+			seconds,
+			gross: gross.to_string(),
+			scale: scale.into(),
+			signer,
+			signature: signature.0.to_vec().into(),
+		})
+	}
+
+	fn viewer_session(
+		&self,
+		stream_id: u128,
+		account: AccountId,
+		at: Option<H256>,
+	) -> RpcResult<Option<ViewerSessionInfo>> {
+		let at = self.resolve(at);
+		self.cached("viewer_session", at, (stream_id, &account), || {
+			let session = self
+				.client
+				.runtime_api()
+				.get_viewer_session(at, stream_id, account.clone())
+				.map_err(runtime_error)?;
+
+			Ok(session.map(|session| ViewerSessionInfo {
+				reserved: session.reserved.to_string(),
+				seconds_watched: session.seconds_watched,
+				seconds_remaining: session.seconds_remaining,
+				paused: session.paused,
+				free_seconds_left: session.free_seconds_left,
+			}))
+		})
+	}
+
+	fn viewer_stats(
+		&self,
+		account: AccountId,
+		at: Option<H256>,
+	) -> RpcResult<Vec<ViewerStatsInfo>> {
+		let at = self.resolve(at);
+		self.cached("viewer_stats", at, &account, || {
+			let stats = self
+				.client
+				.runtime_api()
+				.get_viewer_stats(at, account.clone())
+				.map_err(runtime_error)?;
+
+			Ok(stats
+				.into_iter()
+				.map(|(stream_id, WatchStats { total_seconds, total_spent, last_watched_block })| {
+					ViewerStatsInfo {
+						stream_id,
+						total_seconds,
+						total_spent: total_spent.to_string(),
+						last_watched_block,
+					}
+				})
+				.collect())
+		})
+	}
+
+	fn dry_run(&self, extrinsic: Bytes, at: Option<H256>) -> RpcResult<()> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let extrinsic = <Block as BlockT>::Extrinsic::decode(&mut &*extrinsic).map_err(|err| {
+			ErrorObject::owned(
+				UNDECODABLE_EXTRINSIC,
+				"Undecodable extrinsic",
+				Some(err.to_string()),
+			)
+		})?;
+
+		self.client
+			.runtime_api()
+			.apply_extrinsic(at, extrinsic)
+			.map_err(runtime_error)?
+			.map_err(validity_error)?
+			.map_err(dispatch_error)?;
+		Ok(())
+	}
+
+	fn storage_footprint(&self, at: Option<H256>) -> RpcResult<Vec<StorageItemFootprint>> {
+		let at = self.resolve(at);
+		self.cached("storage_footprint", at, (), || {
+			let footprint =
+				self.client.runtime_api().get_storage_footprint(at).map_err(runtime_error)?;
+
+			Ok(footprint
+				.into_iter()
+				.map(|StorageFootprint { name, entries, bytes }| StorageItemFootprint {
+					name: String::from_utf8_lossy(&name).into_owned(),
+					entries,
+					bytes,
+				})
+				.collect())
+		})
+	}
+
+	fn config_fingerprint(&self, at: Option<H256>) -> RpcResult<ConfigFingerprintInfo> {
+		let at = self.resolve(at);
+		self.cached("config_fingerprint", at, (), || {
+			let ConfigFingerprint { hash, parameters } =
+				self.client.runtime_api().config_fingerprint(at).map_err(runtime_error)?;
+
+			Ok(ConfigFingerprintInfo {
+				block_hash: at,
+				hash: hash.into(),
+				parameters: parameters
+					.into_iter()
+					.map(|ConfigParameter { name, value }| ConfigParameterInfo {
+						name: String::from_utf8_lossy(&name).into_owned(),
+						value: value.into(),
+					})
+					.collect(),
+			})
+		})
+	}
+
+	fn stream_report(
+		&self,
+		stream_id: u128,
+		era: u32,
+		at: Option<H256>,
+	) -> RpcResult<Option<StreamReportInfo>> {
+		let at = self.resolve(at);
+		self.cached("stream_report", at, (stream_id, era), || {
+			let report = self
+				.client
+				.runtime_api()
+				.get_stream_report(at, stream_id, era)
+				.map_err(runtime_error)?;
+
+			Ok(report.map(|StreamReport { unit, seconds, gross, unpriced }| StreamReportInfo {
+				unit: unit.into(),
+				seconds,
+				gross: gross.to_string(),
+				unpriced: unpriced.to_string(),
+			}))
+		})
+	}
+
+	fn era_settlement_proof(
+		&self,
+		era: u32,
+		stream_id: u128,
+		at: Option<H256>,
+	) -> RpcResult<Option<EraSettlementProofInfo>> {
+		let at = self.resolve(at);
+		self.cached("era_settlement_proof", at, (era, stream_id), || {
+			let proof = self
+				.client
+				.runtime_api()
+				.get_era_settlement_proof(at, era, stream_id)
+				.map_err(runtime_error)?;
+
+			Ok(proof.map(|EraSettlementProof { commitment, earnings, index, proof }| {
+				let StreamEarnings { seconds, gross, fiat, unpriced } = earnings;
+				EraSettlementProofInfo {
+					era: commitment.era,
+					root: commitment.root.into(),
+					streams: commitment.streams,
+					seconds,
+					gross: gross.to_string(),
+					fiat: fiat.to_string(),
+					unpriced: unpriced.to_string(),
+					index,
+					proof: proof.into_iter().map(H256::from).collect(),
+				}
+			}))
+		})
+	}
+
+	fn session_state(
+		&self,
+		stream_id: u128,
+		account: AccountId,
+		at: Option<H256>,
+	) -> RpcResult<Option<SessionStateInfo>> {
+		let at = self.resolve(at);
+		self.cached("session_state", at, (stream_id, &account), || {
+			let state = self
+				.client
+				.runtime_api()
+				.get_session_state(at, stream_id, account.clone())
+				.map_err(runtime_error)?;
+
+			Ok(state.map(Into::into))
+		})
+	}
+
+	fn settlement_health(&self, at: Option<H256>) -> RpcResult<SettlementHealthInfo> {
+		let at = self.resolve(at);
+		self.cached("settlement_health", at, (), || {
+			let SettlementHealth { last_settled, stalled } =
+				self.client.runtime_api().get_settlement_health(at).map_err(runtime_error)?;
+
+			Ok(SettlementHealthInfo { last_settled, stalled })
+		})
+	}
+
+	fn list_streams(
+		&self,
+		offset: u32,
+		limit: u32,
+		filter: Option<StreamFilterParams>,
+		at: Option<H256>,
+	) -> RpcResult<Vec<StreamSummaryInfo>> {
+		let at = self.resolve(at);
+		let StreamFilterParams { status, creator } = filter.unwrap_or_default();
+		let filter = StreamFilter { status: status.map(Into::into), creator };
+		self.cached("list_streams", at, (offset, limit, &filter), || {
+			let streams = self
+				.client
+				.runtime_api()
+				.list_streams(at, offset, limit, filter.clone())
+				.map_err(runtime_error)?;
+
+			Ok(streams
+				.into_iter()
+				.map(
+					|StreamSummary { stream_id, creator, price_per_second, status, viewer_count }| {
+						StreamSummaryInfo {
+							stream_id,
+							creator,
+							price_per_second: price_per_second.to_string(),
+							status: status.into(),
+							viewer_count,
+						}
+					},
+				)
+				.collect())
+		})
+	}
+
+	fn upcoming_streams(
+		&self,
+		viewer: AccountId,
+		offset: u32,
+		limit: u32,
+		at: Option<H256>,
+	) -> RpcResult<Vec<UpcomingStreamInfo>> {
+		let at = self.resolve(at);
+		self.cached("upcoming_streams", at, (&viewer, offset, limit), || {
+			let streams = self
+				.client
+				.runtime_api()
+				.get_upcoming_streams(at, viewer.clone(), offset, limit)
+				.map_err(runtime_error)?;
+
+			Ok(streams
+				.into_iter()
+				.map(|UpcomingStream { stream_id, creator, title, schedule }| UpcomingStreamInfo {
+					stream_id,
+					creator,
+					title: title.map(|title| String::from_utf8_lossy(&title).into_owned()),
+					starts_at: schedule.starts_at,
+					utc_offset_minutes: schedule.utc_offset_minutes,
+					estimated_block: schedule.estimated_block,
+				})
+				.collect())
+		})
+	}
+}
@@ -0,0 +1,214 @@
+//! Dry run of a candidate runtime upgrade, so operators can see what its migrations do to the
+//! chain's state before enacting it.
+//!
+//! The candidate's migrations run against a copy of the state at a block, through its
+//! `TryRuntime` API, and every change they make is discarded afterwards. The candidate must
+//! therefore be built with the `try-runtime` feature. The method is unsafe: it executes
+//! arbitrary code supplied by the caller.
+
+use super::errors::runtime_error;
+use codec::{Decode, Encode};
+use frame_support::traits::StorageInfoTrait;
+use frame_try_runtime::UpgradeCheckSelect;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, types::error::ErrorObject};
+use sc_client_api::Backend;
+use sc_executor::WasmExecutor;
+use sc_rpc_api::DenyUnsafe;
+use serde::Serialize;
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+	hexdisplay::HexDisplay,
+	traits::{CallContext, RuntimeCode, WrappedRuntimeCode},
+	Bytes, H256,
+};
+use sp_runtime::{traits::BlakeTwo256, StateVersion};
+use sp_state_machine::{Backend as StateBackend, OverlayedChanges, StateMachine};
+use std::{collections::BTreeMap, sync::Arc};
+use vilokanam_runtime::{opaque::Block, AllPalletsWithSystem, Weight};
+
+/// Error code for migrations or pre- and post-upgrade checks that failed.
+const MIGRATIONS_FAILED: i32 = 4;
+
+/// A weight, split into its two dimensions.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpgradeWeight {
+	/// Execution time, in picoseconds.
+	pub ref_time: u64,
+	/// Size of the storage proof, in bytes.
+	pub proof_size: u64,
+}
+
+impl From<Weight> for UpgradeWeight {
+	fn from(weight: Weight) -> Self {
+		Self { ref_time: weight.ref_time(), proof_size: weight.proof_size() }
+	}
+}
+
+/// The entries of one storage item the migrations changed.
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageItemChanges {
+	/// `Pallet.Item` for items of the current runtime, the key for well-known keys such as
+	/// `:code`, and otherwise the hex of the 32-byte item prefix.
+	pub name: String,
+	/// Entries that did not exist before.
+	pub inserted: u32,
+	/// Entries whose value changed.
+	pub modified: u32,
+	/// Entries that were removed.
+	pub removed: u32,
+}
+
+/// The outcome of running a candidate runtime's migrations.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpgradeDryRun {
+	/// The block whose state the migrations ran against.
+	pub block_hash: H256,
+	/// The weight the migrations consumed.
+	pub weight: UpgradeWeight,
+	/// The candidate's maximum block weight, which the migrations must fit in.
+	pub max_block_weight: UpgradeWeight,
+	/// Every storage item the migrations changed, by name.
+	pub storage_changes: Vec<StorageItemChanges>,
+	/// Why the pallets' `try_state` checks failed after the upgrade, or `null` if they pass.
+	pub try_state_failure: Option<String>,
+}
+
+#[rpc(client, server)]
+pub trait UpgradeApi {
+	/// Run the migrations of the runtime `wasm` against the state at `at`, or the best block,
+	/// without keeping their changes.
+	#[method(name = "vilokanam_dryRunUpgrade", blocking)]
+	fn dry_run_upgrade(&self, wasm: Bytes, at: Option<H256>) -> RpcResult<UpgradeDryRun>;
+}
+
+/// Implements the [`UpgradeApiServer`] RPC trait.
+pub struct Upgrade<C, B> {
+	client: Arc<C>,
+	backend: Arc<B>,
+	executor: WasmExecutor<sp_io::SubstrateHostFunctions>,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl<C, B> Upgrade<C, B> {
+	/// Create a new instance of the upgrade dry-run RPC handler.
+	pub fn new(client: Arc<C>, backend: Arc<B>, deny_unsafe: DenyUnsafe) -> Self {
+		let executor = WasmExecutor::builder().build();
+		Self { client, backend, executor, deny_unsafe }
+	}
+}
+
+impl<C, B> Upgrade<C, B>
+where
+	B: Backend<Block>,
+{
+	/// Run the candidate's `TryRuntime_on_runtime_upgrade` with `checks` on top of `state`,
+	/// returning the weights it reports and the storage it changed.
+	fn execute(
+		&self,
+		state: &B::State,
+		code: &RuntimeCode,
+		checks: UpgradeCheckSelect,
+	) -> Result<((Weight, Weight), Vec<(Vec<u8>, Option<Vec<u8>>)>), String> {
+		let mut overlay = OverlayedChanges::default();
+		let mut extensions = Default::default();
+		let result = StateMachine::new(
+			state,
+			&mut overlay,
+			&self.executor,
+			"TryRuntime_on_runtime_upgrade",
+			&checks.encode(),
+			&mut extensions,
+			code,
+			CallContext::Offchain,
+		)
+		.execute()
+		.map_err(|err| err.to_string())?;
+
+		let weights = <(Weight, Weight)>::decode(&mut &result[..]).map_err(|err| err.to_string())?;
+		let changes = overlay
+			.drain_storage_changes(state, StateVersion::V1)
+			.map_err(|err| err.to_string())?
+			.main_storage_changes;
+		Ok((weights, changes))
+	}
+}
+
+impl<C, B> UpgradeApiServer for Upgrade<C, B>
+where
+	B: Backend<Block> + Send + Sync + 'static,
+	C: HeaderBackend<Block> + Send + Sync + 'static,
+{
+	fn dry_run_upgrade(&self, wasm: Bytes, at: Option<H256>) -> RpcResult<UpgradeDryRun> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let state = self.backend.state_at(at).map_err(runtime_error)?;
+		let hash = sp_core::blake2_256(&wasm).to_vec();
+		let code = RuntimeCode {
+			code_fetcher: &WrappedRuntimeCode(wasm.0.into()),
+			heap_pages: None,
+			hash,
+		};
+
+		let ((weight, max_block_weight), changes) = self
+			.execute(&state, &code, UpgradeCheckSelect::PreAndPost)
+			.map_err(|err| ErrorObject::owned(MIGRATIONS_FAILED, "Migrations failed", Some(err)))?;
+		// `try_state` runs after the migrations, on a fresh copy of the state
+		let try_state_failure = self.execute(&state, &code, UpgradeCheckSelect::TryState).err();
+
+		Ok(UpgradeDryRun {
+			block_hash: at,
+			weight: weight.into(),
+			max_block_weight: max_block_weight.into(),
+			storage_changes: summarize(&state, changes).map_err(runtime_error)?,
+			try_state_failure,
+		})
+	}
+}
+
+/// Count the inserted, modified and removed entries among `changes` per storage item.
+fn summarize<S: StateBackend<BlakeTwo256>>(
+	state: &S,
+	changes: Vec<(Vec<u8>, Option<Vec<u8>>)>,
+) -> Result<Vec<StorageItemChanges>, S::Error> {
+	let names = AllPalletsWithSystem::storage_info()
+		.into_iter()
+		.map(|info| {
+			let name = format!(
+				"{}.{}",
+				String::from_utf8_lossy(&info.pallet_name),
+				String::from_utf8_lossy(&info.storage_name)
+			);
+			(info.prefix, name)
+		})
+		.collect::<BTreeMap<_, _>>();
+
+	let mut items = BTreeMap::<String, StorageItemChanges>::new();
+	for (key, after) in changes {
+		let before = state.storage(&key)?;
+		let name = if key.starts_with(b":") {
+			String::from_utf8_lossy(&key).into_owned()
+		} else {
+			let prefix = &key[..key.len().min(32)];
+			names.get(prefix).cloned().unwrap_or_else(|| format!("0x{}", HexDisplay::from(&prefix)))
+		};
+		let item = items
+			.entry(name.clone())
+			.or_insert_with(|| StorageItemChanges { name, ..Default::default() });
+		match (before, after) {
+			(None, Some(_)) => item.inserted += 1,
+			(Some(_), None) => item.removed += 1,
+			(before, after) if before != after => item.modified += 1,
+			_ => {},
+		}
+	}
+
+	// Items whose writes all left their entries as they were did not change
+	Ok(items
+		.into_values()
+		.filter(|item| item.inserted + item.modified + item.removed > 0)
+		.collect())
+}
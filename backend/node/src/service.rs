@@ -1,12 +1,27 @@
-use futures::FutureExt;
+use futures::{channel::mpsc, FutureExt, StreamExt};
 use sc_client_api::{Backend, BlockBackend};
 use sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams};
 use sc_consensus_grandpa::SharedVoterState;
-use sc_service::{error::Error as ServiceError, Configuration, TaskManager};
+use sc_consensus_manual_seal::{
+	consensus::{aura::AuraConsensusDataProvider, timestamp::SlotTimestampProvider},
+	EngineCommand, ManualSealParams,
+};
+use crate::{cli::NodeRole, keys};
+use sc_network::config::{NonReservedPeerMode, SyncMode, TransportConfig};
+use sc_service::{
+	config::RpcMethods, error::Error as ServiceError, ChainType, Configuration, TaskManager,
+};
 use sc_telemetry::{Telemetry, TelemetryWorker};
-use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
-use std::{sync::Arc, time::Duration};
-use vilokanam_runtime::{self, opaque::Block, RuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sc_transaction_pool_api::TransactionPool;
+use sp_consensus_aura::{sr25519::AuthorityPair as AuraPair, SlotDuration};
+use sp_keystore::Keystore;
+use sp_timestamp::Timestamp;
+use std::{path::PathBuf, sync::Arc, time::Duration};
+use vilokanam_runtime::{self, opaque::Block, Hash, RuntimeApi};
+
+/// Unix time in milliseconds of the first block of a chain run with `--dev-deterministic`.
+const DETERMINISTIC_START: u64 = 1_700_000_000_000;
 
 pub struct ExecutorDispatch;
 
@@ -146,7 +161,71 @@ fn remote_keystore(_url: &String) -> Result<Arc<sc_keystore::LocalKeystore>, &'s
 }
 
 /// Builds a new service for a full client.
-pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
+pub fn new_full(
+	mut config: Configuration,
+	node_role: NodeRole,
+	settlement_inherent: bool,
+	instant_seal: bool,
+	deterministic: bool,
+	insert_dev_keys: bool,
+	keys_file: Option<PathBuf>,
+) -> Result<TaskManager, ServiceError> {
+	// A deterministic chain is sealed on demand at fixed times, settles ticks in its own
+	// blocks rather than by offchain workers whose signatures differ from run to run, and
+	// keeps to itself
+	let instant_seal = instant_seal || deterministic;
+	let settlement_inherent = settlement_inherent || deterministic;
+	if deterministic {
+		if config.chain_spec.chain_type() != ChainType::Development {
+			return Err(ServiceError::Other(
+				"`--dev-deterministic` runs development chains only; add `--dev`".into(),
+			))
+		}
+		config.offchain_worker.enabled = false;
+		config.telemetry_endpoints = None;
+		config.network.boot_nodes.clear();
+		config.network.listen_addresses.clear();
+		config.network.public_addresses.clear();
+		config.network.default_peers_set.reserved_nodes.clear();
+		config.network.default_peers_set.non_reserved_mode = NonReservedPeerMode::Deny;
+		config.network.default_peers_set.in_peers = 0;
+		config.network.default_peers_set.out_peers = 0;
+		config.network.transport = TransportConfig::MemoryOnly;
+	}
+	let dev_chain =
+		matches!(config.chain_spec.chain_type(), ChainType::Development | ChainType::Local);
+	if insert_dev_keys && !dev_chain {
+		return Err(ServiceError::Other(
+			"`--insert-dev-keys` is for development and local chains; list the keys of other \
+			chains in a `--keys-file`"
+				.into(),
+		))
+	}
+
+	let read_replica = node_role == NodeRole::ReadReplica;
+	if read_replica {
+		if config.role.is_authority() {
+			return Err(ServiceError::Other(
+				"A read replica cannot run as a validator; drop `--validator`".into(),
+			))
+		}
+		if instant_seal {
+			return Err(ServiceError::Other(
+				"A read replica cannot seal blocks; drop `--dev-instant-seal` and \
+				`--dev-deterministic`"
+					.into(),
+			))
+		}
+		// No offchain workers means no unsigned ticks, and a pool without room refuses every
+		// transaction submitted over `author_*`
+		config.offchain_worker.enabled = false;
+		config.transaction_pool.ready.count = 0;
+		config.transaction_pool.ready.total_bytes = 0;
+		config.transaction_pool.future.count = 0;
+		config.transaction_pool.future.total_bytes = 0;
+		config.rpc_methods = RpcMethods::Safe;
+	}
+
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -158,6 +237,40 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 		other: (block_import, grandpa_link, mut telemetry),
 	} = new_partial(&config)?;
 
+	// Nodes started as a dev account report ticks with its key, which the dev and local chain
+	// specs register as a tick reporter
+	if let Some(seed) = &config.dev_key_seed {
+		keystore_container
+			.keystore()
+			.sr25519_generate_new(tick_stream::REPORTER_KEY_TYPE, Some(seed))
+			.map_err(|e| ServiceError::Other(format!("Failed to add the reporter key: {}", e)))?;
+	}
+	if insert_dev_keys {
+		let seed = config.dev_key_seed.as_deref().unwrap_or("//Alice");
+		keys::insert_dev_keys(&keystore_container.keystore(), seed).map_err(ServiceError::Other)?;
+	}
+	if let Some(path) = &keys_file {
+		keys::insert_keys_from_file(&keystore_container.keystore(), path)
+			.map_err(ServiceError::Other)?;
+	}
+
+	// A fresh replica downloads the finalized state with a GRANDPA warp proof instead of
+	// executing every block since genesis, and fills in the older blocks in the background.
+	// Archive replicas serve historical state, which warp sync does not download, so they
+	// still sync in full.
+	let archive = config.state_pruning.as_ref().map_or(false, |pruning| pruning.is_archive());
+	if read_replica &&
+		!archive &&
+		matches!(config.network.sync_mode, SyncMode::Full) &&
+		client.info().best_number == 0
+	{
+		log::info!(
+			"Warp syncing the fresh read replica; run it with `--state-pruning archive` to \
+			execute every block instead"
+		);
+		config.network.sync_mode = SyncMode::Warp;
+	}
+
 	let mut net_config = sc_network::config::FullNetworkConfiguration::new(&config.network);
 
 	let grandpa_protocol_name = sc_consensus_grandpa::protocol_standard_name(
@@ -173,6 +286,8 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 		grandpa_protocol_name.clone(),
 	));
 
+	// Serves warp proofs to syncing peers, and verifies them when this node warp syncs with
+	// `--sync warp`
 	let warp_sync = Arc::new(sc_consensus_grandpa::warp_proof::NetworkProvider::new(
 		backend.clone(),
 		grandpa_link.shared_authority_set().clone(),
@@ -204,20 +319,43 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 	let force_authoring = config.force_authoring;
 	let backoff_authoring_blocks: Option<()> = None;
 	let name = config.network.node_name.clone();
-	let enable_grandpa = !config.disable_grandpa;
+	// Blocks sealed on demand are finalized as they are sealed, so there is nothing to vote on
+	let enable_grandpa = !config.disable_grandpa && !instant_seal;
 	let prometheus_registry = config.prometheus_registry().cloned();
 
+	// `engine_*` requests, which only nodes sealing blocks on demand serve
+	let (command_sink, commands) = instant_seal.then(|| mpsc::channel(1024)).unzip();
+
 	let rpc_extensions_builder = {
 		let client = client.clone();
+		let backend = backend.clone();
 		let pool = transaction_pool.clone();
-
-		Box::new(move |deny_unsafe, _| {
-			let deps =
-				crate::rpc::FullDeps { client: client.clone(), pool: pool.clone() };
-			crate::rpc::create_full(deps).map_err(Into::into)
+		let keystore = keystore_container.keystore();
+
+		Box::new(move |deny_unsafe, subscription_executor| {
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				backend: backend.clone(),
+				pool: pool.clone(),
+				keystore: keystore.clone(),
+				subscription_executor,
+				read_replica,
+				deny_unsafe,
+				command_sink: command_sink.clone(),
+			};
+			crate::rpc::create_full::<_, _, FullBackend>(deps).map_err(Into::into)
 		})
 	};
 
+	if let Some(registry) = prometheus_registry.as_ref() {
+		let metrics = crate::metrics::TickStreamMetrics::register(registry)?;
+		task_manager.spawn_handle().spawn(
+			"tick-stream-metrics",
+			None,
+			metrics.run(client.clone(), backend.clone()),
+		);
+	}
+
 	let _rpc_handlers = sc_service::spawn_tasks(sc_service::SpawnTasksParams {
 		network: network.clone(),
 		client: client.clone(),
@@ -233,7 +371,75 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 		telemetry: telemetry.as_mut(),
 	})?;
 
-	if role.is_authority() {
+	if let Some(commands) = commands {
+		let proposer_factory = sc_basic_authorship::ProposerFactory::new(
+			task_manager.spawn_handle(),
+			client.clone(),
+			transaction_pool.clone(),
+			prometheus_registry.as_ref(),
+			telemetry.as_ref().map(|x| x.handle()),
+		);
+
+		let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
+		let settlement_client = settlement_inherent.then(|| client.clone());
+		let timestamp_client = client.clone();
+
+		// Every transaction the pool takes in is sealed into a block of its own at once
+		let sealing_commands = transaction_pool.import_notification_stream().map(|_| {
+			EngineCommand::SealNewBlock {
+				create_empty: false,
+				finalize: true,
+				parent_hash: None,
+				sender: None,
+			}
+		});
+
+		// Blocks carry Aura pre-digests for slots a slot apart, as the runtime expects of
+		// Aura blocks, but are sealed without waiting for the slots to come
+		let sealing = sc_consensus_manual_seal::run_manual_seal(ManualSealParams {
+			block_import: client.clone(),
+			env: proposer_factory,
+			client: client.clone(),
+			pool: transaction_pool,
+			commands_stream: futures::stream::select(commands, sealing_commands),
+			select_chain,
+			consensus_data_provider: Some(Box::new(AuraConsensusDataProvider::new(
+				client.clone(),
+			))),
+			create_inherent_data_providers: move |parent, ()| {
+				let settlement_client = settlement_client.clone();
+				let timestamp_client = timestamp_client.clone();
+				async move {
+					let timestamp = if deterministic {
+						deterministic_timestamp(&*timestamp_client, parent, slot_duration)?
+					} else {
+						SlotTimestampProvider::new_aura(timestamp_client)?.timestamp()
+					};
+
+					let slot =
+						sp_consensus_aura::inherents::InherentDataProvider::from_timestamp_and_slot_duration(
+							timestamp,
+							slot_duration,
+						);
+					let timestamp = sp_timestamp::InherentDataProvider::new(timestamp);
+
+					let settlement = match settlement_client {
+						Some(client) =>
+							crate::settlement::InherentDataProvider::new(&*client, parent)?,
+						None => Default::default(),
+					};
+
+					Ok((slot, timestamp, settlement))
+				}
+			},
+		});
+
+		task_manager.spawn_essential_handle().spawn_blocking(
+			"instant-seal",
+			Some("block-authoring"),
+			sealing,
+		);
+	} else if role.is_authority() {
 		let proposer_factory = sc_basic_authorship::ProposerFactory::new(
 			task_manager.spawn_handle(),
 			client.clone(),
@@ -243,6 +449,7 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 		);
 
 		let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
+		let settlement_client = settlement_inherent.then(|| client.clone());
 
 		let aura = sc_consensus_aura::start_aura::<AuraPair, _, _, _, _, _, _, _, _, _, _>(
 			StartAuraParams {
@@ -251,16 +458,25 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 				select_chain,
 				block_import,
 				proposer_factory,
-				create_inherent_data_providers: move |_, ()| async move {
-					let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
-
-					let slot =
-						sp_consensus_aura::inherents::InherentDataProvider::from_timestamp_and_slot_duration(
-							*timestamp,
-							slot_duration,
-						);
-
-					Ok((slot, timestamp))
+				create_inherent_data_providers: move |parent, ()| {
+					let settlement_client = settlement_client.clone();
+					async move {
+						let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+
+						let slot =
+							sp_consensus_aura::inherents::InherentDataProvider::from_timestamp_and_slot_duration(
+								*timestamp,
+								slot_duration,
+							);
+
+						let settlement = match settlement_client {
+							Some(client) =>
+								crate::settlement::InherentDataProvider::new(&*client, parent)?,
+							None => Default::default(),
+						};
+
+						Ok((slot, timestamp, settlement))
+					}
 				},
 				force_authoring,
 				backoff_authoring_blocks,
@@ -323,4 +539,18 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 
 	network_starter.start_network();
 	Ok(task_manager)
+}
+
+/// The timestamp of the block on top of `parent` on a chain run with `--dev-deterministic`:
+/// one slot after its parent's, counting from `DETERMINISTIC_START`, whenever it is sealed.
+fn deterministic_timestamp(
+	client: &FullClient,
+	parent: Hash,
+	slot_duration: SlotDuration,
+) -> Result<Timestamp, sp_blockchain::Error> {
+	let parent_number = client
+		.number(parent)?
+		.ok_or_else(|| sp_blockchain::Error::UnknownBlock(format!("{parent:?}")))?;
+	let elapsed = slot_duration.as_millis().saturating_mul(u64::from(parent_number) + 1);
+	Ok(Timestamp::new(DETERMINISTIC_START.saturating_add(elapsed)))
 }
\ No newline at end of file
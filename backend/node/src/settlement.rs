@@ -0,0 +1,46 @@
+//! Inherent data of the ticks due at a block, so blocks this node authors settle them in an
+//! inherent instead of leaving them to unsigned transactions competing in the pool.
+
+use sp_api::{ApiError, ProvideRuntimeApi};
+use sp_inherents::{InherentData, InherentIdentifier};
+use tick_stream::{TickStreamApi, INHERENT_IDENTIFIER};
+use vilokanam_runtime::{opaque::Block, AccountId, AssetId, Balance, BlockNumber, Hash};
+
+/// Provides the ticks due on top of a parent block.
+///
+/// The default provides none, leaving settlement to unsigned transactions.
+#[derive(Default)]
+pub struct InherentDataProvider {
+	ticks: Vec<(u128, AccountId, u32)>,
+}
+
+impl InherentDataProvider {
+	/// Collect the ticks due on top of `parent`, up to a batch's worth.
+	pub fn new<C>(client: &C, parent: Hash) -> Result<Self, ApiError>
+	where
+		C: ProvideRuntimeApi<Block>,
+		C::Api: TickStreamApi<Block, AccountId, Balance, BlockNumber, AssetId>,
+	{
+		let ticks = client.runtime_api().get_due_ticks(parent)?;
+		Ok(Self { ticks })
+	}
+}
+
+#[async_trait::async_trait]
+impl sp_inherents::InherentDataProvider for InherentDataProvider {
+	async fn provide_inherent_data(
+		&self,
+		inherent_data: &mut InherentData,
+	) -> Result<(), sp_inherents::Error> {
+		inherent_data.put_data(INHERENT_IDENTIFIER, &self.ticks)
+	}
+
+	async fn try_handle_error(
+		&self,
+		_identifier: &InherentIdentifier,
+		_error: &[u8],
+	) -> Option<Result<(), sp_inherents::Error>> {
+		// The settlement inherent is not checked against inherent data
+		None
+	}
+}
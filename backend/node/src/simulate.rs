@@ -0,0 +1,234 @@
+//! `simulate-economics`: stress test candidate fee parameters before they go to a vote.
+//!
+//! Generates viewing sessions for a synthetic audience and settles them with the same
+//! [`tick_stream::split_payment`] the pallet uses on-chain, then reports how earnings,
+//! platform income and rounding come out for each candidate platform fee.
+
+use sp_runtime::Permill;
+use vilokanam_runtime::{Balance, MILLISECS_PER_BLOCK};
+
+/// Seconds settled per tick interval, one block.
+const TICK_INTERVAL_SECS: u64 = MILLISECS_PER_BLOCK / 1000;
+
+#[derive(Debug, clap::Parser)]
+pub struct SimulateEconomicsCmd {
+	/// Number of viewers in the simulated audience.
+	#[clap(long, default_value_t = 1_000)]
+	pub viewers: u32,
+
+	/// Number of live streams viewers choose between.
+	#[clap(long, default_value_t = 50)]
+	pub streams: u32,
+
+	/// Number of days to simulate.
+	#[clap(long, default_value_t = 30)]
+	pub days: u32,
+
+	/// Candidate platform fees to compare, in percent.
+	#[clap(long, value_delimiter = ',', default_values_t = vec![0.0, 2.5, 5.0, 10.0])]
+	pub platform_fees: Vec<f64>,
+
+	/// Co-host shares registered on every stream, in percent.
+	#[clap(long, value_delimiter = ',')]
+	pub co_host_shares: Vec<f64>,
+
+	/// Median per-second price of a stream; each stream is priced between half and double it.
+	#[clap(long, default_value_t = 1_000)]
+	pub price: Balance,
+
+	/// Seed for scenario generation, so runs can be reproduced.
+	#[clap(long, default_value_t = 42)]
+	pub seed: u64,
+}
+
+/// One continuous viewing session, settled in tick intervals.
+struct Session {
+	stream: usize,
+	seconds: u64,
+}
+
+/// Totals for one candidate fee.
+#[derive(Default)]
+struct Outcome {
+	volume: Balance,
+	platform: Balance,
+	co_hosts: Balance,
+	creators: Vec<Balance>,
+	/// Exact minus settled platform income, summed over every payment.
+	platform_rounding: f64,
+	/// Exact minus settled co-host income, summed over every payment.
+	co_host_rounding: f64,
+}
+
+impl SimulateEconomicsCmd {
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let co_host_shares = self
+			.co_host_shares
+			.iter()
+			.map(|percent| to_permill(*percent))
+			.collect::<Result<Vec<_>, _>>()?;
+		let total_share: u32 = co_host_shares.iter().map(|share| share.deconstruct()).sum();
+		if total_share > Permill::one().deconstruct() {
+			return Err("co-host shares add up to more than 100%".into());
+		}
+		if self.streams == 0 {
+			return Err("at least one stream is needed".into());
+		}
+
+		let mut rng = XorShift(self.seed.max(1));
+		let prices = (0..self.streams)
+			.map(|_| self.price / 2 + rng.below((self.price * 3 / 2) as u64 + 1) as Balance)
+			.collect::<Vec<_>>();
+		let sessions = self.sessions(&mut rng);
+
+		println!(
+			"{} viewers, {} streams, {} days: {} sessions",
+			self.viewers,
+			self.streams,
+			self.days,
+			sessions.len()
+		);
+		println!(
+			"{:>8} {:>16} {:>14} {:>14} {:>12} {:>12} {:>12} {:>12} {:>10} {:>10}",
+			"fee %",
+			"volume",
+			"platform",
+			"co-hosts",
+			"creator min",
+			"creator p50",
+			"creator p90",
+			"creator max",
+			"plat. rnd",
+			"co-h. rnd",
+		);
+		for percent in &self.platform_fees {
+			let fee = to_permill(*percent)?;
+			let mut outcome = settle(&sessions, &prices, fee, &co_host_shares);
+			outcome.creators.sort_unstable();
+			println!(
+				"{:>8.2} {:>16} {:>14} {:>14} {:>12} {:>12} {:>12} {:>12} {:>10.0} {:>10.0}",
+				percent,
+				outcome.volume,
+				outcome.platform,
+				outcome.co_hosts,
+				percentile(&outcome.creators, 0),
+				percentile(&outcome.creators, 50),
+				percentile(&outcome.creators, 90),
+				percentile(&outcome.creators, 100),
+				outcome.platform_rounding,
+				outcome.co_host_rounding,
+			);
+		}
+
+		Ok(())
+	}
+
+	/// Generate every viewing session of the simulation.
+	///
+	/// Each day a viewer tunes in with even odds, picks a stream with a long-tailed
+	/// popularity and watches for one minute to two hours.
+	fn sessions(&self, rng: &mut XorShift) -> Vec<Session> {
+		// Stream `i` is picked with weight 1 / (i + 1)
+		let weights = (1..=self.streams).map(|rank| 1.0 / rank as f64).collect::<Vec<_>>();
+		let total_weight: f64 = weights.iter().sum();
+
+		let mut sessions = Vec::new();
+		for _ in 0..self.days {
+			for _ in 0..self.viewers {
+				if rng.below(2) == 0 {
+					continue;
+				}
+				let mut pick = rng.unit() * total_weight;
+				let stream = weights
+					.iter()
+					.position(|weight| {
+						pick -= weight;
+						pick <= 0.0
+					})
+					.unwrap_or(weights.len() - 1);
+				sessions.push(Session { stream, seconds: 60 + rng.below(7_141) });
+			}
+		}
+		sessions
+	}
+}
+
+/// Settle every session at `platform_fee`, one tick interval at a time.
+fn settle(
+	sessions: &[Session],
+	prices: &[Balance],
+	platform_fee: Permill,
+	co_host_shares: &[Permill],
+) -> Outcome {
+	let mut outcome = Outcome { creators: vec![0; prices.len()], ..Default::default() };
+	let fee_fraction = platform_fee.deconstruct() as f64 / 1_000_000.0;
+
+	for session in sessions {
+		let full_intervals = session.seconds / TICK_INTERVAL_SECS;
+		let remainder = session.seconds % TICK_INTERVAL_SECS;
+		let payments = [(TICK_INTERVAL_SECS, full_intervals), (remainder, 1)];
+
+		for (seconds, count) in payments {
+			if seconds == 0 || count == 0 {
+				continue;
+			}
+			let amount = prices[session.stream] * seconds as Balance;
+			let (fee, cuts, creator_share) =
+				tick_stream::split_payment(amount, platform_fee, co_host_shares.iter().copied());
+			let count = count as Balance;
+
+			let exact_fee = amount as f64 * fee_fraction;
+			let exact_net = amount as f64 - exact_fee;
+			let exact_cuts: f64 = co_host_shares
+				.iter()
+				.map(|share| exact_net * share.deconstruct() as f64 / 1_000_000.0)
+				.sum();
+			let cuts: Balance = cuts.iter().sum();
+
+			outcome.volume += amount * count;
+			outcome.platform += fee * count;
+			outcome.co_hosts += cuts * count;
+			outcome.creators[session.stream] += creator_share * count;
+			outcome.platform_rounding += (exact_fee - fee as f64) * count as f64;
+			outcome.co_host_rounding += (exact_cuts - cuts as f64) * count as f64;
+		}
+	}
+	outcome
+}
+
+fn to_permill(percent: f64) -> sc_cli::Result<Permill> {
+	if !(0.0..=100.0).contains(&percent) {
+		return Err(format!("{percent}% is not a valid share").into());
+	}
+	Ok(Permill::from_parts((percent * 10_000.0).round() as u32))
+}
+
+/// The value at `percent` of the way through the sorted `values`.
+fn percentile(sorted: &[Balance], percent: usize) -> Balance {
+	match sorted.len() {
+		0 => 0,
+		len => sorted[(len - 1) * percent / 100],
+	}
+}
+
+/// A small deterministic generator; scenarios only need to be reproducible, not secure.
+struct XorShift(u64);
+
+impl XorShift {
+	fn next(&mut self) -> u64 {
+		self.0 ^= self.0 << 13;
+		self.0 ^= self.0 >> 7;
+		self.0 ^= self.0 << 17;
+		self.0
+	}
+
+	/// A value in `0..bound`.
+	fn below(&mut self, bound: u64) -> u64 {
+		self.next() % bound.max(1)
+	}
+
+	/// A value in `[0, 1)`.
+	fn unit(&mut self) -> f64 {
+		(self.next() >> 11) as f64 / (1u64 << 53) as f64
+	}
+}
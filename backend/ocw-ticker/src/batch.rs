@@ -0,0 +1,105 @@
+//! Sizing of `batch_tick` submissions from observed settlement weight.
+
+use std::collections::{BTreeMap, VecDeque};
+
+/// Weight given to each new observation in the moving average, in percent.
+const SMOOTHING_PERCENT: u64 = 20;
+
+/// Tracks what settling one viewer costs and how many viewers fit in a batch.
+#[derive(Debug)]
+pub struct BatchSizer {
+	/// The ref-time weight budget of one batch.
+	budget: u64,
+	/// The most viewers the runtime accepts in one batch.
+	max_batch: u32,
+	/// Moving average of the ref-time weight of settling one viewer.
+	per_viewer: u64,
+}
+
+impl BatchSizer {
+	/// A sizer targeting `target_fullness` percent of `max_block_weight` per batch.
+	pub fn new(max_block_weight: u64, target_fullness: u8, max_batch: u32, per_viewer: u64) -> Self {
+		let budget = max_block_weight / 100 * u64::from(target_fullness.min(100));
+		Self { budget, max_batch: max_batch.max(1), per_viewer: per_viewer.max(1) }
+	}
+
+	/// Record that settling `viewers` viewers in one batch weighed `weight`.
+	pub fn observe(&mut self, viewers: u32, weight: u64) {
+		if viewers == 0 {
+			return;
+		}
+		let sample = (weight / u64::from(viewers)).max(1);
+		self.per_viewer = (self.per_viewer * (100 - SMOOTHING_PERCENT) +
+			sample * SMOOTHING_PERCENT) /
+			100;
+	}
+
+	/// How many viewers the next batch should settle.
+	pub fn batch_size(&self) -> u32 {
+		let fits = self.budget / self.per_viewer.max(1);
+		fits.clamp(1, u64::from(self.max_batch)) as u32
+	}
+}
+
+/// Take up to `size` viewers off the queues, one from each stream in turn, so every live
+/// stream with viewers waiting gets a share of the block.
+pub fn take_round_robin<K: Copy + Ord, V>(
+	queues: &mut BTreeMap<K, VecDeque<V>>,
+	size: usize,
+) -> Vec<(K, V)> {
+	let mut taken = Vec::with_capacity(size);
+	while taken.len() < size {
+		let before = taken.len();
+		for (key, queue) in queues.iter_mut() {
+			if taken.len() == size {
+				break;
+			}
+			if let Some(viewer) = queue.pop_front() {
+				taken.push((*key, viewer));
+			}
+		}
+		if taken.len() == before {
+			break;
+		}
+	}
+	taken
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn batches_shrink_as_settlement_gets_heavier() {
+		let mut sizer = BatchSizer::new(1_000_000, 50, 256, 1_000);
+		assert_eq!(sizer.batch_size(), 256);
+
+		// Ten viewers took half a block: the estimate moves a fifth of the way there
+		sizer.observe(10, 50_000);
+		assert_eq!(sizer.per_viewer, 1_800);
+		assert_eq!(sizer.batch_size(), 256);
+
+		for _ in 0..50 {
+			sizer.observe(10, 50_000);
+		}
+		assert_eq!(sizer.batch_size(), 100);
+	}
+
+	#[test]
+	fn at_least_one_viewer_is_settled() {
+		let sizer = BatchSizer::new(1_000, 10, 256, 1_000_000);
+		assert_eq!(sizer.batch_size(), 1);
+	}
+
+	#[test]
+	fn streams_take_turns_in_a_batch() {
+		let mut queues = BTreeMap::from([
+			(0, VecDeque::from(["a", "b", "c"])),
+			(1, VecDeque::from(["d"])),
+			(2, VecDeque::new()),
+		]);
+		assert_eq!(take_round_robin(&mut queues, 3), [(0, "a"), (1, "d"), (0, "b")]);
+		assert_eq!(take_round_robin(&mut queues, 3), [(0, "c")]);
+		assert!(take_round_robin(&mut queues, 3).is_empty());
+	}
+}
@@ -0,0 +1,223 @@
+//! Viewer heartbeats, so only viewers who still have a stream open are ticked.
+//!
+//! While a stream is open, the viewer's client posts a signed heartbeat every few seconds:
+//!
+//! ```text
+//! POST /heartbeat
+//! { "stream_id": 0, "viewer": "5Grw...", "session": 1, "at": 1700000000000, "signature": "0x..." }
+//! ```
+//!
+//! `session` is the device's session, opened on chain with `open_session`; heartbeats from
+//! sessions that are not open are turned away, so the stream's device limit holds. `at` is
+//! the client's clock in Unix milliseconds and `signature` is the viewer's sr25519 signature
+//! over the SCALE encoding of `(b"heartbeat", stream_id, session, at)`, either bare or
+//! wrapped in `<Bytes>…</Bytes>` as browser wallets sign raw payloads.
+
+use axum::{extract::State, http::StatusCode, routing::post, Json, Router};
+use codec::Encode;
+use crate::monitor::Monitor;
+use serde::Deserialize;
+use std::{
+	collections::HashMap,
+	net::SocketAddr,
+	sync::{Arc, Mutex},
+	time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use subxt::utils::AccountId32;
+use subxt_signer::sr25519;
+
+/// A viewer's claim to still be watching a stream.
+#[derive(Debug, Deserialize)]
+pub struct Heartbeat {
+	/// The stream being watched.
+	pub stream_id: u128,
+	/// The viewer, as an SS58 address.
+	pub viewer: AccountId32,
+	/// The session of the device the viewer is watching on.
+	pub session: u64,
+	/// When the heartbeat was sent, in Unix milliseconds.
+	pub at: u64,
+	/// The viewer's signature over the heartbeat, hex encoded.
+	pub signature: String,
+}
+
+impl Heartbeat {
+	/// The bytes the viewer signs.
+	pub fn message(stream_id: u128, session: u64, at: u64) -> Vec<u8> {
+		(b"heartbeat", stream_id, session, at).encode()
+	}
+
+	/// Whether the heartbeat carries the viewer's signature.
+	pub fn is_signed(&self) -> bool {
+		let signature = self.signature.trim_start_matches("0x");
+		let Ok(Ok(signature)) = hex::decode(signature).map(<[u8; 64]>::try_from) else {
+			return false;
+		};
+		let signature = sr25519::Signature(signature);
+		let public = sr25519::PublicKey(self.viewer.0);
+		let message = Self::message(self.stream_id, self.session, self.at);
+		let wrapped = [&b"<Bytes>"[..], &message[..], &b"</Bytes>"[..]].concat();
+		sr25519::verify(&signature, &message, &public) ||
+			sr25519::verify(&signature, &wrapped, &public)
+	}
+}
+
+/// The last heartbeat from each viewer of each stream, shared with the HTTP server.
+#[derive(Clone, Debug, Default)]
+pub struct Heartbeats(Arc<Mutex<State>>);
+
+#[derive(Debug, Default)]
+struct State {
+	/// When each viewer of each stream last sent a heartbeat.
+	last: HashMap<(u128, AccountId32), Instant>,
+	/// The sessions open on chain for each viewer of each stream.
+	sessions: HashMap<u128, HashMap<AccountId32, Vec<u64>>>,
+}
+
+impl Heartbeats {
+	/// Note that `viewer` is watching `stream_id` now.
+	pub fn record(&self, stream_id: u128, viewer: AccountId32) {
+		self.lock().last.insert((stream_id, viewer), Instant::now());
+	}
+
+	/// Whether `viewer` has sent a heartbeat for `stream_id` within `window`.
+	pub fn is_fresh(&self, stream_id: u128, viewer: &AccountId32, window: Duration) -> bool {
+		self.lock()
+			.last
+			.get(&(stream_id, viewer.clone()))
+			.map_or(false, |at| at.elapsed() <= window)
+	}
+
+	/// Forget heartbeats older than `window`.
+	pub fn prune(&self, window: Duration) {
+		self.lock().last.retain(|_, at| at.elapsed() <= window);
+	}
+
+	/// Replace the sessions known to be open on `stream_id`.
+	pub fn set_sessions(&self, stream_id: u128, sessions: HashMap<AccountId32, Vec<u64>>) {
+		self.lock().sessions.insert(stream_id, sessions);
+	}
+
+	/// Forget the sessions of streams that are no longer live.
+	pub fn retain_streams(&self, live: &[u128]) {
+		self.lock().sessions.retain(|stream_id, _| live.contains(stream_id));
+	}
+
+	/// Whether `viewer` has `session` open on `stream_id`, as of the last refresh.
+	pub fn is_open(&self, stream_id: u128, viewer: &AccountId32, session: u64) -> bool {
+		self.lock()
+			.sessions
+			.get(&stream_id)
+			.and_then(|sessions| sessions.get(viewer))
+			.map_or(false, |sessions| sessions.contains(&session))
+	}
+
+	fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+		self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+	}
+}
+
+/// Shared with every request.
+#[derive(Clone)]
+struct Server {
+	heartbeats: Heartbeats,
+	/// How far a heartbeat's `at` may be from this process' clock.
+	window: Duration,
+}
+
+/// Accept heartbeats on `addr` until the process exits.
+///
+/// Heartbeats sent more than `window` ago, or dated that far ahead, are turned away, so a
+/// captured heartbeat cannot keep a viewer ticking. So are heartbeats from sessions that are
+/// not open on chain, answered with 409 so the client can open one.
+pub async fn serve(
+	addr: SocketAddr,
+	heartbeats: Heartbeats,
+	window: Duration,
+	monitor: Monitor,
+) -> std::io::Result<()> {
+	let app = Router::new()
+		.route("/heartbeat", post(heartbeat))
+		.with_state(Server { heartbeats, window });
+	let listener = tokio::net::TcpListener::bind(addr).await?;
+	monitor.log(format!("Accepting heartbeats on http://{}/heartbeat", addr));
+	axum::serve(listener, app).await
+}
+
+async fn heartbeat(State(server): State<Server>, Json(heartbeat): Json<Heartbeat>) -> StatusCode {
+	let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+	let sent = Duration::from_millis(heartbeat.at);
+	if now.saturating_sub(sent) > server.window || sent.saturating_sub(now) > server.window {
+		return StatusCode::BAD_REQUEST;
+	}
+	if !heartbeat.is_signed() {
+		return StatusCode::UNAUTHORIZED;
+	}
+	if !server.heartbeats.is_open(heartbeat.stream_id, &heartbeat.viewer, heartbeat.session) {
+		return StatusCode::CONFLICT;
+	}
+
+	server.heartbeats.record(heartbeat.stream_id, heartbeat.viewer);
+	StatusCode::NO_CONTENT
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::str::FromStr;
+	use subxt_signer::SecretUri;
+
+	fn signed(stream_id: u128, signed_stream_id: u128) -> Heartbeat {
+		let uri = SecretUri::from_str("//Alice").unwrap();
+		let keypair = sr25519::Keypair::from_uri(&uri).unwrap();
+		let signature = keypair.sign(&Heartbeat::message(signed_stream_id, 1, 1_000));
+		Heartbeat {
+			stream_id,
+			viewer: AccountId32(keypair.public_key().0),
+			session: 1,
+			at: 1_000,
+			signature: format!("0x{}", hex::encode(signature.0)),
+		}
+	}
+
+	#[test]
+	fn heartbeats_must_be_signed_for_their_stream() {
+		assert!(signed(7, 7).is_signed());
+		assert!(!signed(7, 8).is_signed());
+
+		let mut heartbeat = signed(7, 7);
+		heartbeat.session = 2;
+		assert!(!heartbeat.is_signed());
+		heartbeat.signature = "0x1234".into();
+		assert!(!heartbeat.is_signed());
+	}
+
+	#[test]
+	fn only_sessions_open_on_chain_count() {
+		let heartbeats = Heartbeats::default();
+		let viewer = AccountId32([1; 32]);
+		assert!(!heartbeats.is_open(0, &viewer, 1));
+
+		heartbeats.set_sessions(0, HashMap::from([(viewer.clone(), vec![1, 2])]));
+		assert!(heartbeats.is_open(0, &viewer, 2));
+		assert!(!heartbeats.is_open(0, &viewer, 3));
+		assert!(!heartbeats.is_open(1, &viewer, 1));
+
+		heartbeats.retain_streams(&[1]);
+		assert!(!heartbeats.is_open(0, &viewer, 1));
+	}
+
+	#[test]
+	fn heartbeats_go_stale() {
+		let heartbeats = Heartbeats::default();
+		let viewer = AccountId32([1; 32]);
+		heartbeats.record(0, viewer.clone());
+		assert!(heartbeats.is_fresh(0, &viewer, Duration::from_secs(30)));
+		assert!(!heartbeats.is_fresh(1, &viewer, Duration::from_secs(30)));
+
+		std::thread::sleep(Duration::from_millis(5));
+		assert!(!heartbeats.is_fresh(0, &viewer, Duration::ZERO));
+		heartbeats.prune(Duration::ZERO);
+		assert!(!heartbeats.is_fresh(0, &viewer, Duration::from_secs(30)));
+	}
+}
@@ -1,17 +1,38 @@
-use clap::Parser;
-use codec::Encode;
-use sp_core::{sr25519, Pair};
-use sp_runtime::MultiSignature;
+use backoff::Backoff;
+use clap::{Parser, Subcommand};
+use codec::{Decode, Encode};
+use futures::StreamExt;
+use std::{
+	collections::{BTreeMap, HashMap, VecDeque},
+	net::SocketAddr,
+	path::PathBuf,
+	str::FromStr,
+	time::{Duration, Instant},
+};
 use subxt::{
-	config::substrate::BlakeTwo256,
-	rpc::RpcClient,
-	tx::{PairSigner, Payload},
-	utils::H256,
-	Config, OnlineClient, SubstrateConfig,
+	blocks::Block,
+	config::DefaultExtrinsicParamsBuilder,
+	dynamic::{At, Value},
+	events::Phase,
+	ext::scale_value::ValueDef,
+	tx::{Signer, TxProgress},
+	utils::AccountId32,
+	OnlineClient, SubstrateConfig,
 };
-use tokio::time::{sleep, Duration};
+use subxt_signer::{sr25519::Keypair, SecretUri};
+use tokio::sync::mpsc;
+
+mod batch;
+mod heartbeat;
+mod monitor;
+mod scenario;
+use batch::BatchSizer;
+use heartbeat::Heartbeats;
+use monitor::Monitor;
+use scenario::Scenario;
 
-/// Simple CLI for sending tick transactions
+/// Settles the viewers of every live stream who are still watching with unsigned `batch_tick`
+/// extrinsics sized to the chain's blocks, signed as a registered tick reporter
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
 struct Args {
@@ -19,55 +40,545 @@ struct Args {
 	#[clap(long, default_value = "ws://127.0.0.1:9944")]
 	url: String,
 
-	/// The stream ID to send ticks for
-	#[clap(long, default_value = "1")]
-	stream_id: u128,
+	/// The interval between settlements of the same viewer in seconds
+	#[clap(long, default_value = "6")]
+	interval: u64,
 
-	/// The private key URI for the account to use
-	#[clap(long, default_value = "//Alice")]
-	private_key_uri: String,
+	/// The share of a block's weight the batches of one block may use, in percent
+	#[clap(long, default_value = "50")]
+	target_fullness: u8,
 
-	/// The interval between ticks in seconds
-	#[clap(long, default_value = "1")]
-	interval: u64,
+	/// The assumed ref-time weight of settling one viewer until blocks have been observed
+	#[clap(long, default_value = "50000000")]
+	initial_viewer_weight: u64,
+
+	/// The most RPC requests in flight at once when fetching viewers and submitting batches
+	#[clap(long, default_value = "8")]
+	max_concurrent: usize,
+
+	/// The longest wait between reconnection attempts after an RPC error, in seconds
+	#[clap(long, default_value = "60")]
+	max_backoff: u64,
+
+	/// The address to accept viewers' heartbeats on
+	#[clap(long, default_value = "127.0.0.1:9955")]
+	heartbeat_addr: SocketAddr,
+
+	/// How long after their last heartbeat a viewer is still ticked, in seconds
+	#[clap(long, default_value = "30")]
+	heartbeat_staleness: u64,
+
+	/// Sign batches with this secret URI instead of submitting them unsigned, for chains where
+	/// ticking is a signed call
+	#[clap(long, value_name = "SECRET_URI")]
+	signed: Option<String>,
+
+	/// The secret URI of the tick reporter key batches are reported with; its account must be
+	/// registered with `register_reporter`
+	#[clap(long, value_name = "SECRET_URI", default_value = "//Alice")]
+	reporter: String,
+
+	/// Show a live dashboard of every stream's viewers, batches and errors instead of printing
+	/// progress
+	#[clap(long)]
+	tui: bool,
+
+	#[clap(subcommand)]
+	command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+	/// Submit the timed extrinsics of a YAML or TOML scenario instead of settling streams
+	RunScenario {
+		/// The scenario file
+		path: PathBuf,
+	},
 }
 
+type Client = OnlineClient<SubstrateConfig>;
+type Error = Box<dyn std::error::Error>;
+
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
+async fn main() -> Result<(), Error> {
 	let args = Args::parse();
 
-	// Create a client to connect to the node
-	let client = OnlineClient::<SubstrateConfig>::from_url(&args.url).await?;
-
-	// Create a keypair from the private key URI
-	let pair = sr25519::Pair::from_string(&args.private_key_uri, None)?;
-	let signer = PairSigner::new(pair);
+	if let Some(Command::RunScenario { path }) = &args.command {
+		let client = Client::from_url(&args.url).await?;
+		return scenario::run(&client, Scenario::load(path)?).await
+	}
 
-	// Get the account ID
-	let account_id = signer.account_id().clone();
+	let signer = match &args.signed {
+		Some(uri) => Some(Keypair::from_uri(&SecretUri::from_str(uri)?)?),
+		None => None,
+	};
+	let reporter = Keypair::from_uri(&SecretUri::from_str(&args.reporter)?)?;
+	let monitor = Monitor::new(args.tui);
+	if args.tui {
+		let (monitor, url) = (monitor.clone(), args.url.clone());
+		std::thread::spawn(move || monitor.run_dashboard(url));
+	}
+	monitor.log(format!(
+		"Settling the viewers of every live stream every {} seconds...",
+		args.interval
+	));
 
-	println!("Sending ticks every {} seconds to stream {}...", args.interval, args.stream_id);
+	let heartbeats = Heartbeats::default();
+	let staleness = Duration::from_secs(args.heartbeat_staleness);
+	let server =
+		heartbeat::serve(args.heartbeat_addr, heartbeats.clone(), staleness, monitor.clone());
+	let server_monitor = monitor.clone();
+	tokio::spawn(async move {
+		if let Err(e) = server.await {
+			server_monitor.log(format!("Heartbeat server stopped: {}", e));
+		}
+	});
 
+	// Settlement state outlives connections, so viewers are not charged twice for the same
+	// seconds after a reconnect
+	let mut ticker = Ticker::new(&args, heartbeats, signer, reporter, monitor.clone());
+	let mut backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(args.max_backoff));
 	loop {
-		// Create the call data for the tick extrinsic
-		let call_data = (
-			40u8,  // pallet index
-			2u8,   // call index
-			args.stream_id,
-			account_id.encode(),
-			1u32,  // ticks
-		);
+		let result = match Client::from_url(&args.url).await {
+			Ok(client) => ticker.follow(&client, &mut backoff).await,
+			Err(e) => Err(e.into()),
+		};
+		let delay = backoff.next_delay();
+		match result {
+			Ok(()) => monitor.log(format!("Block subscription ended, reconnecting in {:?}", delay)),
+			Err(e) => monitor.log(format!("RPC error: {}, reconnecting in {:?}", e, delay)),
+		}
+		tokio::time::sleep(delay).await;
+	}
+}
+
+/// Settles the viewers of every live stream, each on its own interval.
+struct Ticker<'a> {
+	args: &'a Args,
+	/// Sizes the batches of each block, once the chain's limits are known.
+	sizer: Option<BatchSizer>,
+	/// Viewers waiting to be settled, by stream.
+	queues: BTreeMap<u128, VecDeque<AccountId32>>,
+	/// When each stream's queue was last refilled from storage.
+	last_refill: HashMap<u128, Instant>,
+	/// When each viewer was last settled on each stream.
+	last_settled: HashMap<(u128, AccountId32), Instant>,
+	/// When the live streams were last looked up.
+	last_discovery: Option<Instant>,
+	/// Viewers' heartbeats; only viewers who sent one recently are ticked.
+	heartbeats: Heartbeats,
+	/// Signs batches, if they are not submitted unsigned.
+	signer: Option<Keypair>,
+	/// Signs the reports of batches.
+	reporter: Keypair,
+	/// The last nonce a batch was reported under.
+	reporter_nonce: u64,
+	/// Batches that were not included, handed back by the tasks watching them.
+	rejected: (mpsc::UnboundedSender<Submitted>, mpsc::UnboundedReceiver<Submitted>),
+	/// Where progress is reported.
+	monitor: Monitor,
+}
+
+/// A submitted batch, handed back by the task watching it if it is not included.
+struct Submitted {
+	stream_id: u128,
+	/// When the batch was put together.
+	settled_at: Instant,
+	/// Each viewer in the batch with when they were settled before it.
+	viewers: Vec<(AccountId32, Instant)>,
+}
+
+impl<'a> Ticker<'a> {
+	fn new(
+		args: &'a Args,
+		heartbeats: Heartbeats,
+		signer: Option<Keypair>,
+		reporter: Keypair,
+		monitor: Monitor,
+	) -> Self {
+		Self {
+			args,
+			sizer: None,
+			queues: BTreeMap::new(),
+			last_refill: HashMap::new(),
+			last_settled: HashMap::new(),
+			last_discovery: None,
+			heartbeats,
+			signer,
+			reporter,
+			reporter_nonce: 0,
+			rejected: mpsc::unbounded_channel(),
+			monitor,
+		}
+	}
 
-		// Create the payload
-		let payload = Payload::new("TickStream", "record_tick", call_data);
+	/// Settle viewers in every finalized block until the subscription ends or an RPC call
+	/// fails.
+	///
+	/// Each finalized block feeds back how much settling its batches cost, then carries the
+	/// next batches; the pallet accepts one batch per stream per block.
+	async fn follow(&mut self, client: &Client, backoff: &mut Backoff) -> Result<(), Error> {
+		if self.sizer.is_none() {
+			self.sizer = Some(batch_sizer(client, self.args).await?);
+		}
 
-		// Submit the transaction
-		match client.tx().sign_and_submit_then_watch_default(&payload, &signer).await {
-			Ok(_) => println!("Tick sent for stream {}", args.stream_id),
-			Err(e) => println!("Error sending tick: {}", e),
+		let mut blocks = client.blocks().subscribe_finalized().await?;
+		while let Some(block) = blocks.next().await {
+			let block = block?;
+			if let Some(sizer) = &mut self.sizer {
+				observe_block(&block, sizer, &self.monitor).await?;
+				self.monitor.set_batch_size(sizer.batch_size());
+			}
+			self.monitor.set_block(block.number().into());
+			self.settle(client, block.number().into()).await?;
+			backoff.reset();
 		}
 
-		// Wait for the specified interval
-		sleep(Duration::from_secs(args.interval)).await;
+		Ok(())
 	}
-}
\ No newline at end of file
+
+	/// Submit this block's batches, refreshing the live streams and their viewers when due.
+	///
+	/// The batches settle the interval keyed by the finalized block they are built on, so
+	/// other tickers following the same chain settle it under the same key and the pallet
+	/// charges its viewers only once.
+	async fn settle(&mut self, client: &Client, interval_index: u64) -> Result<(), Error> {
+		let interval = Duration::from_secs(self.args.interval);
+		let staleness = Duration::from_secs(self.args.heartbeat_staleness);
+		let concurrency = self.args.max_concurrent.max(1);
+
+		// Seconds of batches that were not included are charged in the next ones, unless the
+		// viewer has been settled again since
+		while let Ok(rejected) = self.rejected.1.try_recv() {
+			for (viewer, since) in rejected.viewers {
+				let key = (rejected.stream_id, viewer);
+				if self.last_settled.get(&key) == Some(&rejected.settled_at) {
+					self.last_settled.insert(key, since);
+				}
+			}
+		}
+
+		if self.last_discovery.map_or(true, |at| at.elapsed() >= interval) {
+			let live = live_streams(client).await?;
+			self.queues.retain(|stream_id, _| live.contains(stream_id));
+			self.last_refill.retain(|stream_id, _| live.contains(stream_id));
+			self.last_settled.retain(|(stream_id, _), _| live.contains(stream_id));
+			for stream_id in live {
+				self.queues.entry(stream_id).or_default();
+			}
+			self.last_discovery = Some(Instant::now());
+			self.heartbeats.prune(staleness);
+			self.heartbeats.retain_streams(&live);
+			self.monitor.retain_streams(&live);
+		}
+
+		let due = self
+			.queues
+			.iter()
+			.filter(|(stream_id, queue)| {
+				queue.is_empty() &&
+					self.last_refill.get(stream_id).map_or(true, |at| at.elapsed() >= interval)
+			})
+			.map(|(stream_id, _)| *stream_id)
+			.collect::<Vec<_>>();
+		let refills = futures::stream::iter(due)
+			.map(|stream_id| async move {
+				let viewers = stream_viewers(client, stream_id).await;
+				let sessions = stream_sessions(client, stream_id).await;
+				(stream_id, viewers, sessions)
+			})
+			.buffer_unordered(concurrency)
+			.collect::<Vec<_>>()
+			.await;
+		for (stream_id, viewers, sessions) in refills {
+			let (viewers, sessions) = (viewers?, sessions?);
+			let open = sessions.values().map(Vec::len).sum();
+			self.monitor.set_viewers(stream_id, viewers.len(), open);
+			self.queues.entry(stream_id).or_default().extend(viewers);
+			self.heartbeats.set_sessions(stream_id, sessions);
+			self.last_refill.insert(stream_id, Instant::now());
+		}
+
+		let now = Instant::now();
+		let size = self.sizer.as_ref().map_or(1, |sizer| sizer.batch_size()) as usize;
+		let mut batches = BTreeMap::<u128, Vec<(AccountId32, u32, Instant)>>::new();
+		for (stream_id, viewer) in batch::take_round_robin(&mut self.queues, size) {
+			// Viewers who closed the stream are not charged for the time they were away
+			if !self.heartbeats.is_fresh(stream_id, &viewer, staleness) {
+				self.last_settled.insert((stream_id, viewer), now);
+				continue;
+			}
+			let since = *self.last_settled.entry((stream_id, viewer.clone())).or_insert(now);
+			let ticks = now.duration_since(since).as_secs() as u32;
+			if ticks > 0 {
+				batches.entry(stream_id).or_default().push((viewer, ticks, since));
+			}
+		}
+		for (stream_id, queue) in &self.queues {
+			self.monitor.set_queued(*stream_id, queue.len());
+		}
+
+		// Signed batches take consecutive nonces, as several go into the pool at once
+		let mut nonce = match &self.signer {
+			Some(signer) => {
+				let account = Signer::<SubstrateConfig>::account_id(signer);
+				Some(client.tx().account_nonce(&account).await?)
+			},
+			None => None,
+		};
+		// So do reports, counting on from the last nonce used here or on chain; a batch that
+		// lands after a later one is refused and handed back to be charged again
+		if !batches.is_empty() {
+			let reporter = Signer::<SubstrateConfig>::account_id(&self.reporter);
+			self.reporter_nonce = self.reporter_nonce.max(reporter_nonce(client, &reporter).await?);
+		}
+		let batches = batches
+			.into_iter()
+			.map(|(stream_id, batch)| {
+				let signer = self.signer.as_ref().zip(nonce);
+				nonce = nonce.map(|nonce| nonce + 1);
+				self.reporter_nonce += 1;
+				(stream_id, batch, signer, self.reporter_nonce)
+			})
+			.collect::<Vec<_>>();
+
+		let reporter = &self.reporter;
+		let submitted = futures::stream::iter(batches)
+			.map(|(stream_id, batch, signer, report_nonce)| async move {
+				let report = (reporter, report_nonce);
+				let result =
+					submit_batch(client, (stream_id, interval_index), &batch, signer, report).await;
+				(stream_id, batch, result)
+			})
+			.buffer_unordered(concurrency)
+			.collect::<Vec<_>>()
+			.await;
+		for (stream_id, batch, result) in submitted {
+			let viewers =
+				batch.into_iter().map(|(viewer, _, since)| (viewer, since)).collect::<Vec<_>>();
+			match result {
+				Ok(progress) => {
+					for (viewer, _) in &viewers {
+						self.last_settled.insert((stream_id, viewer.clone()), now);
+					}
+					let batch = Submitted { stream_id, settled_at: now, viewers };
+					self.monitor.batch_sent(stream_id);
+					let (rejected, monitor) = (self.rejected.0.clone(), self.monitor.clone());
+					tokio::spawn(watch_batch(progress, batch, rejected, monitor));
+				},
+				Err(e) => {
+					self.monitor.batch_failed(stream_id);
+					let message = format!("Error sending batch for stream {}: {}", stream_id, e);
+					self.monitor.log(message);
+				},
+			}
+		}
+
+		Ok(())
+	}
+}
+
+/// A batch sizer for the chain's block weight and batch limit.
+async fn batch_sizer(client: &Client, args: &Args) -> Result<BatchSizer, Error> {
+	let max_block_weight = client
+		.constants()
+		.at(&subxt::dynamic::constant("System", "BlockWeights"))?
+		.to_value()?
+		.at("max_block")
+		.at("ref_time")
+		.and_then(|ref_time| ref_time.as_u128())
+		.ok_or("BlockWeights constant has no max_block ref_time")? as u64;
+	let max_batch = client
+		.constants()
+		.at(&subxt::dynamic::constant("TickStream", "MaxViewersPerBatch"))?
+		.to_value()?
+		.as_u128()
+		.ok_or("MaxViewersPerBatch constant is not a number")? as u32;
+
+	Ok(BatchSizer::new(
+		max_block_weight,
+		args.target_fullness,
+		max_batch,
+		args.initial_viewer_weight,
+	))
+}
+
+/// Settle `batch` for an interval of `stream_id` with a `batch_tick` reported by the reporter
+/// key under its nonce, unsigned unless a signer and its nonce are given.
+async fn submit_batch(
+	client: &Client,
+	(stream_id, interval_index): (u128, u64),
+	batch: &[(AccountId32, u32, Instant)],
+	signer: Option<(&Keypair, u64)>,
+	(reporter, report_nonce): (&Keypair, u64),
+) -> Result<TxProgress<SubstrateConfig, Client>, subxt::Error> {
+	let ticks = batch.iter().map(|(viewer, ticks, _)| (viewer.0, *ticks)).collect::<Vec<_>>();
+	let signature = reporter.sign(&(stream_id, interval_index, &ticks, report_nonce).encode());
+	let ticks = ticks.into_iter().map(|(viewer, ticks)| {
+		Value::unnamed_composite([Value::from_bytes(viewer), Value::u128(ticks as u128)])
+	});
+	let payload = subxt::dynamic::tx(
+		"TickStream",
+		"batch_tick",
+		vec![
+			Value::u128(stream_id),
+			Value::u128(interval_index as u128),
+			Value::unnamed_composite(ticks),
+			Value::from_bytes(Signer::<SubstrateConfig>::account_id(reporter).0),
+			Value::u128(report_nonce as u128),
+			Value::unnamed_variant("Sr25519", [Value::from_bytes(signature.0)]),
+		],
+	);
+	match signer {
+		Some((signer, nonce)) => {
+			let params =
+				DefaultExtrinsicParamsBuilder::<SubstrateConfig>::new().nonce(nonce).build();
+			client.tx().create_signed(&payload, signer, params).await?.submit_and_watch().await
+		},
+		None => client.tx().create_unsigned(&payload)?.submit_and_watch().await,
+	}
+}
+
+/// Wait for a submitted batch to be finalized and report how many of its viewers were
+/// settled, handing the batch back on `rejected` if it never made it into a block.
+async fn watch_batch(
+	progress: TxProgress<SubstrateConfig, Client>,
+	batch: Submitted,
+	rejected: mpsc::UnboundedSender<Submitted>,
+	monitor: Monitor,
+) {
+	match progress.wait_for_finalized_success().await {
+		Ok(events) => {
+			let settled = events
+				.iter()
+				.filter_map(Result::ok)
+				.find(|event| {
+					event.pallet_name() == "TickStream" && event.variant_name() == "IntervalSettled"
+				})
+				.and_then(|event| event.field_values().ok())
+				.and_then(|fields| fields.at("viewers").and_then(|viewers| viewers.as_u128()));
+			let settled = settled.unwrap_or_default() as u32;
+			monitor.batch_finalized(batch.stream_id, settled, batch.viewers.len());
+			monitor.log(format!(
+				"Batch for stream {} finalized in {:?}: {} of {} viewers settled",
+				batch.stream_id,
+				events.block_hash(),
+				settled,
+				batch.viewers.len(),
+			));
+		},
+		Err(e) => {
+			monitor.batch_dropped(batch.stream_id);
+			monitor.log(format!("Batch for stream {} was not included: {}", batch.stream_id, e));
+			let _ = rejected.send(batch);
+		},
+	}
+}
+
+/// Feed the weight of every `batch_tick` settled in `block` back into the sizer, and note the
+/// streams it settled.
+async fn observe_block(
+	block: &Block<SubstrateConfig, Client>,
+	sizer: &mut BatchSizer,
+	monitor: &Monitor,
+) -> Result<(), subxt::Error> {
+	// `IntervalSettled` and the `ExtrinsicSuccess` carrying the weight share an extrinsic index
+	let mut settled = HashMap::new();
+	let mut weights = HashMap::new();
+	for event in block.events().await?.iter() {
+		let event = event?;
+		let Phase::ApplyExtrinsic(index) = event.phase() else { continue };
+		match (event.pallet_name(), event.variant_name()) {
+			("TickStream", "IntervalSettled") => {
+				let fields = event.field_values()?;
+				let viewers = fields.at("viewers").and_then(|viewers| viewers.as_u128());
+				settled.insert(index, viewers.unwrap_or_default() as u32);
+				if let Some(stream_id) = fields.at("stream_id").and_then(|id| id.as_u128()) {
+					monitor.included(stream_id, block.number().into());
+				}
+			},
+			("System", "ExtrinsicSuccess") => {
+				let fields = event.field_values()?;
+				let ref_time = fields
+					.at("dispatch_info")
+					.at("weight")
+					.at("ref_time")
+					.and_then(|ref_time| ref_time.as_u128());
+				weights.insert(index, ref_time.unwrap_or_default() as u64);
+			},
+			_ => {},
+		}
+	}
+
+	for (index, viewers) in settled {
+		if let Some(weight) = weights.get(&index) {
+			sizer.observe(viewers, *weight);
+		}
+	}
+	Ok(())
+}
+
+/// The IDs of every live stream.
+async fn live_streams(client: &Client) -> Result<Vec<u128>, Error> {
+	let query = subxt::dynamic::storage("TickStream", "Streams", ());
+	let mut streams = client.storage().at_latest().await?.iter(query).await?;
+	let mut live = Vec::new();
+	while let Some(stream) = streams.next().await {
+		let stream = stream?;
+		let value = stream.value.to_value()?;
+		let is_live = matches!(
+			value.at("status").map(|status| &status.value),
+			Some(ValueDef::Variant(variant)) if variant.name == "Live"
+		);
+		if is_live {
+			// `Blake2_128Concat` keys end with the encoded stream ID
+			let key = &stream.key_bytes[stream.key_bytes.len().saturating_sub(16)..];
+			live.push(u128::decode(&mut &key[..])?);
+		}
+	}
+	Ok(live)
+}
+
+/// The last nonce `reporter` reported ticks under, failing unless it is a registered reporter.
+async fn reporter_nonce(client: &Client, reporter: &AccountId32) -> Result<u64, Error> {
+	let query =
+		subxt::dynamic::storage("TickStream", "Reporters", vec![Value::from_bytes(reporter.0)]);
+	let info = client
+		.storage()
+		.at_latest()
+		.await?
+		.fetch(&query)
+		.await?
+		.ok_or_else(|| format!("{} is not a registered tick reporter", reporter))?;
+	Ok(info.to_value()?.at("nonce").and_then(|nonce| nonce.as_u128()).unwrap_or_default() as u64)
+}
+
+/// The accounts currently watching `stream_id`.
+async fn stream_viewers(client: &Client, stream_id: u128) -> Result<Vec<AccountId32>, Error> {
+	let query =
+		subxt::dynamic::storage("TickStream", "StreamViewers", vec![Value::u128(stream_id)]);
+	let viewers = match client.storage().at_latest().await?.fetch(&query).await? {
+		Some(viewers) => Vec::<AccountId32>::decode(&mut viewers.encoded())?,
+		None => Vec::new(),
+	};
+	Ok(viewers)
+}
+
+/// The sessions each viewer of `stream_id` has open, one per device.
+async fn stream_sessions(
+	client: &Client,
+	stream_id: u128,
+) -> Result<HashMap<AccountId32, Vec<u64>>, Error> {
+	let query =
+		subxt::dynamic::storage("TickStream", "ActiveSessions", vec![Value::u128(stream_id)]);
+	let mut entries = client.storage().at_latest().await?.iter(query).await?;
+	let mut sessions = HashMap::new();
+	while let Some(entry) = entries.next().await {
+		let entry = entry?;
+		// `Blake2_128Concat` keys end with the encoded viewer
+		let key = &entry.key_bytes[entry.key_bytes.len().saturating_sub(32)..];
+		let viewer = AccountId32::decode(&mut &key[..])?;
+		sessions.insert(viewer, Vec::<u64>::decode(&mut entry.value.encoded())?);
+	}
+	Ok(sessions)
+}
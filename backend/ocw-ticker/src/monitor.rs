@@ -0,0 +1,314 @@
+//! What the ticker is doing, kept for the `--tui` dashboard.
+//!
+//! The ticker reports each stream's viewers, queue and batches here as it settles them. With
+//! the dashboard on, a thread redraws them every quarter second alongside the ticker's recent
+//! messages, which would otherwise be printed; `q`, `Esc` or `Ctrl-C` quits.
+
+use ratatui::{
+	crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+	layout::{Constraint, Layout},
+	style::{Style, Stylize},
+	text::Line,
+	widgets::{Block, List, Paragraph, Row, Table},
+	Frame,
+};
+use std::{
+	collections::{BTreeMap, VecDeque},
+	fmt::Display,
+	sync::{Arc, Mutex},
+	time::Duration,
+};
+
+/// The most recent messages kept for the dashboard.
+const LOG_LINES: usize = 100;
+
+/// How often the dashboard is redrawn.
+const REFRESH: Duration = Duration::from_millis(250);
+
+/// The ticker's state, shared between it and the dashboard.
+#[derive(Clone, Debug, Default)]
+pub struct Monitor(Arc<Mutex<State>>);
+
+#[derive(Debug, Default)]
+struct State {
+	/// Whether the dashboard is shown; if not, messages are printed.
+	dashboard: bool,
+	/// The last finalized block batches were built on.
+	block: Option<u64>,
+	/// How many viewers a batch settles.
+	batch_size: Option<u32>,
+	/// Every live stream's status.
+	streams: BTreeMap<u128, StreamStatus>,
+	/// The most recent messages, oldest first.
+	log: VecDeque<String>,
+}
+
+/// What is known about one live stream.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StreamStatus {
+	/// Viewers watching on chain, as of the last refill.
+	pub viewers: usize,
+	/// Device sessions open on chain, as of the last refill.
+	pub sessions: usize,
+	/// Viewers waiting to be settled.
+	pub queued: usize,
+	/// Batches submitted and not finalized yet.
+	pub in_flight: usize,
+	/// How many viewers the last finalized batch settled, out of how many it carried.
+	pub last_settled: Option<(u32, usize)>,
+	/// The last finalized block a batch for the stream was included in, from any ticker.
+	pub last_inclusion: Option<u64>,
+	/// Batches that could not be sent or were not included.
+	pub errors: u32,
+}
+
+impl Monitor {
+	/// A monitor showing the dashboard if `dashboard` is set, and printing messages if not.
+	pub fn new(dashboard: bool) -> Self {
+		Self(Arc::new(Mutex::new(State { dashboard, ..Default::default() })))
+	}
+
+	/// Print `message`, or keep it for the dashboard.
+	pub fn log(&self, message: impl Display) {
+		let mut state = self.lock();
+		if !state.dashboard {
+			println!("{}", message);
+			return;
+		}
+		if state.log.len() == LOG_LINES {
+			state.log.pop_front();
+		}
+		state.log.push_back(message.to_string());
+	}
+
+	/// Note that batches are now built on finalized block `number`.
+	pub fn set_block(&self, number: u64) {
+		self.lock().block = Some(number);
+	}
+
+	/// Note that batches now settle up to `size` viewers.
+	pub fn set_batch_size(&self, size: u32) {
+		self.lock().batch_size = Some(size);
+	}
+
+	/// Track `live` streams, forgetting every other.
+	pub fn retain_streams(&self, live: &[u128]) {
+		let mut state = self.lock();
+		state.streams.retain(|stream_id, _| live.contains(stream_id));
+		for stream_id in live {
+			state.streams.entry(*stream_id).or_default();
+		}
+	}
+
+	/// Note how many viewers and sessions `stream_id` has on chain.
+	pub fn set_viewers(&self, stream_id: u128, viewers: usize, sessions: usize) {
+		self.update(stream_id, |status| {
+			status.viewers = viewers;
+			status.sessions = sessions;
+		});
+	}
+
+	/// Note how many viewers of `stream_id` are waiting to be settled.
+	pub fn set_queued(&self, stream_id: u128, queued: usize) {
+		self.update(stream_id, |status| status.queued = queued);
+	}
+
+	/// Note that a batch for `stream_id` has been submitted.
+	pub fn batch_sent(&self, stream_id: u128) {
+		self.update(stream_id, |status| status.in_flight += 1);
+	}
+
+	/// Note that a batch for `stream_id` of `viewers` viewers was finalized, settling `settled`.
+	pub fn batch_finalized(&self, stream_id: u128, settled: u32, viewers: usize) {
+		self.update(stream_id, |status| {
+			status.in_flight = status.in_flight.saturating_sub(1);
+			status.last_settled = Some((settled, viewers));
+		});
+	}
+
+	/// Note that a submitted batch for `stream_id` was not included.
+	pub fn batch_dropped(&self, stream_id: u128) {
+		self.update(stream_id, |status| {
+			status.in_flight = status.in_flight.saturating_sub(1);
+			status.errors += 1;
+		});
+	}
+
+	/// Note that a batch for `stream_id` could not be sent.
+	pub fn batch_failed(&self, stream_id: u128) {
+		self.update(stream_id, |status| status.errors += 1);
+	}
+
+	/// Note that finalized block `number` includes a batch for `stream_id`.
+	pub fn included(&self, stream_id: u128, number: u64) {
+		self.update(stream_id, |status| status.last_inclusion = Some(number));
+	}
+
+	/// The status of `stream_id`, if it is tracked.
+	pub fn stream(&self, stream_id: u128) -> Option<StreamStatus> {
+		self.lock().streams.get(&stream_id).cloned()
+	}
+
+	/// Redraw the dashboard until a quit key is pressed, then restore the terminal and exit
+	/// the process. Blocks, so it runs on its own thread.
+	pub fn run_dashboard(self, url: String) {
+		let mut terminal = ratatui::init();
+		let result = loop {
+			if let Err(e) = terminal.draw(|frame| self.draw(frame, &url)) {
+				break Err(e);
+			}
+			match event::poll(REFRESH).and_then(|ready| ready.then(event::read).transpose()) {
+				Ok(Some(Event::Key(key))) if key.kind == KeyEventKind::Press => {
+					let ctrl_c = key.modifiers.contains(KeyModifiers::CONTROL) &&
+						key.code == KeyCode::Char('c');
+					if ctrl_c || matches!(key.code, KeyCode::Char('q') | KeyCode::Esc) {
+						break Ok(());
+					}
+				},
+				Ok(_) => {},
+				Err(e) => break Err(e),
+			}
+		};
+		ratatui::restore();
+		if let Err(e) = result {
+			println!("Dashboard stopped: {}", e);
+			std::process::exit(1);
+		}
+		std::process::exit(0);
+	}
+
+	/// Render the dashboard for the node at `url` into `frame`.
+	fn draw(&self, frame: &mut Frame, url: &str) {
+		let state = self.lock();
+		let [summary, streams, log] = Layout::vertical([
+			Constraint::Length(3),
+			Constraint::Min(5),
+			Constraint::Length(8),
+		])
+		.areas(frame.area());
+
+		let block = state.block.map_or_else(|| "-".to_string(), |number| format!("#{}", number));
+		let batch_size = state.batch_size.map_or_else(|| "-".to_string(), |size| size.to_string());
+		let line = format!(
+			"{}  finalized {}  batch size {}  streams {}",
+			url,
+			block,
+			batch_size,
+			state.streams.len()
+		);
+		let title = " ocw-ticker (q to quit) ";
+		frame.render_widget(Paragraph::new(line).block(Block::bordered().title(title)), summary);
+
+		let columns =
+			["Stream", "Viewers", "Sessions", "Queued", "Pending", "Settled", "Included", "Errors"];
+		let header = Row::new(columns).style(Style::new().bold());
+		let rows = state.streams.iter().map(|(stream_id, status)| {
+			let settled = status
+				.last_settled
+				.map_or_else(|| "-".to_string(), |(settled, of)| format!("{}/{}", settled, of));
+			let included = status
+				.last_inclusion
+				.map_or_else(|| "-".to_string(), |number| format!("#{}", number));
+			let row = Row::new([
+				stream_id.to_string(),
+				status.viewers.to_string(),
+				status.sessions.to_string(),
+				status.queued.to_string(),
+				status.in_flight.to_string(),
+				settled,
+				included,
+				status.errors.to_string(),
+			]);
+			if status.errors > 0 {
+				row.red()
+			} else {
+				row
+			}
+		});
+		let table = Table::new(rows, [Constraint::Fill(1); 8])
+			.header(header)
+			.block(Block::bordered().title(" Live streams "));
+		frame.render_widget(table, streams);
+
+		let shown = usize::from(log.height.saturating_sub(2));
+		let lines = state.log.iter().skip(state.log.len().saturating_sub(shown));
+		let list = List::new(lines.map(|message| Line::from(message.as_str())))
+			.block(Block::bordered().title(" Messages "));
+		frame.render_widget(list, log);
+	}
+
+	fn update(&self, stream_id: u128, update: impl FnOnce(&mut StreamStatus)) {
+		if let Some(status) = self.lock().streams.get_mut(&stream_id) {
+			update(status);
+		}
+	}
+
+	fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+		self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ratatui::{backend::TestBackend, Terminal};
+
+	#[test]
+	fn streams_follow_their_batches() {
+		let monitor = Monitor::new(true);
+		monitor.retain_streams(&[0, 1]);
+		monitor.set_viewers(0, 3, 4);
+		monitor.batch_sent(0);
+		monitor.batch_sent(0);
+		monitor.batch_finalized(0, 2, 3);
+		monitor.batch_dropped(0);
+		monitor.batch_failed(1);
+		monitor.included(0, 42);
+
+		let status = monitor.stream(0).unwrap();
+		assert_eq!((status.viewers, status.sessions, status.in_flight), (3, 4, 0));
+		assert_eq!(status.last_settled, Some((2, 3)));
+		assert_eq!(status.last_inclusion, Some(42));
+		assert_eq!(status.errors, 1);
+		assert_eq!(monitor.stream(1).unwrap().errors, 1);
+
+		// Streams that are no longer live are forgotten, and untracked ones are not picked up
+		monitor.retain_streams(&[1]);
+		monitor.included(0, 43);
+		assert_eq!(monitor.stream(0), None);
+	}
+
+	#[test]
+	fn the_dashboard_keeps_the_latest_messages() {
+		let monitor = Monitor::new(true);
+		for i in 0..LOG_LINES + 1 {
+			monitor.log(i);
+		}
+		let state = monitor.lock();
+		assert_eq!(state.log.len(), LOG_LINES);
+		assert_eq!(state.log.front().map(String::as_str), Some("1"));
+	}
+
+	#[test]
+	fn the_dashboard_shows_every_live_stream() {
+		let monitor = Monitor::new(true);
+		monitor.retain_streams(&[7]);
+		monitor.set_block(12);
+		monitor.set_viewers(7, 5, 6);
+		monitor.included(7, 11);
+		monitor.log("Batch for stream 7 finalized");
+
+		let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+		terminal.draw(|frame| monitor.draw(frame, "ws://127.0.0.1:9944")).unwrap();
+		let screen = terminal
+			.backend()
+			.buffer()
+			.content()
+			.iter()
+			.map(|cell| cell.symbol())
+			.collect::<String>();
+		assert!(screen.contains("finalized #12"));
+		assert!(screen.contains("#11"));
+		assert!(screen.contains("Batch for stream 7 finalized"));
+	}
+}
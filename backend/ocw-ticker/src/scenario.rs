@@ -0,0 +1,231 @@
+//! Scripted sequences of timed extrinsics, for reproducible demos.
+//!
+//! A scenario names the accounts taking part by their secret URIs and lists the calls each
+//! makes, in seconds from the start of the run:
+//!
+//! ```yaml
+//! accounts:
+//!   creator: "//Alice"
+//!   viewer: "//Bob"
+//! steps:
+//!   - at: 0
+//!     from: creator
+//!     call: TickStream.create_stream
+//!     args: [10, { variant: None }, 0]
+//!   - at: 1
+//!     from: creator
+//!     call: TickStream.start_stream
+//!     args: [0]
+//!   - at: 10
+//!     from: viewer
+//!     call: TickStream.join_stream
+//!     args: [0, 60, { variant: None }]
+//!   - at: 60
+//!     from: creator
+//!     call: TickStream.update_price
+//!     args: [0, 20]
+//! ```
+//!
+//! The same layout can be written in TOML, with a `.toml` extension. Arguments are numbers,
+//! booleans, lists and maps as usual; a string naming an account with a leading `@`, like
+//! `"@viewer"`, is that account's ID, any other string is its bytes, and a map with a
+//! `variant` key, plus an optional `fields` list, is an enum variant.
+
+use serde::Deserialize;
+use std::{
+	collections::{BTreeMap, HashMap},
+	path::Path,
+	str::FromStr,
+	time::Duration,
+};
+use subxt::{
+	config::DefaultExtrinsicParamsBuilder,
+	dynamic::Value,
+	tx::Signer,
+	utils::AccountId32,
+	OnlineClient, SubstrateConfig,
+};
+use subxt_signer::{sr25519::Keypair, SecretUri};
+
+type Error = Box<dyn std::error::Error>;
+
+/// A scripted sequence of extrinsics.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Scenario {
+	/// The secret URI of each account taking part, by name.
+	pub accounts: BTreeMap<String, String>,
+	/// The extrinsics to submit.
+	pub steps: Vec<Step>,
+}
+
+/// One extrinsic of a scenario.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Step {
+	/// Seconds from the start of the run at which to submit the extrinsic.
+	pub at: u64,
+	/// The name of the account signing the extrinsic.
+	pub from: String,
+	/// The call, as `Pallet.call_name`.
+	pub call: String,
+	/// The call's arguments, in order.
+	#[serde(default)]
+	pub args: Vec<Arg>,
+}
+
+/// A call argument as written in a scenario.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum Arg {
+	Bool(bool),
+	Unsigned(u128),
+	Signed(i128),
+	Text(String),
+	List(Vec<Arg>),
+	Map(BTreeMap<String, Arg>),
+}
+
+impl Scenario {
+	/// Read a scenario from a YAML file, or a TOML file if its extension is `.toml`.
+	pub fn load(path: &Path) -> Result<Self, Error> {
+		let text = std::fs::read_to_string(path)?;
+		match path.extension().and_then(|extension| extension.to_str()) {
+			Some("toml") => Ok(toml::from_str(&text)?),
+			_ => Ok(serde_yaml::from_str(&text)?),
+		}
+	}
+}
+
+impl Arg {
+	/// The argument as a dynamic value, with `@name` resolved against `accounts`.
+	fn to_value(&self, accounts: &HashMap<String, AccountId32>) -> Result<Value, Error> {
+		Ok(match self {
+			Arg::Bool(value) => Value::bool(*value),
+			Arg::Unsigned(value) => Value::u128(*value),
+			Arg::Signed(value) => Value::i128(*value),
+			Arg::Text(text) => match text.strip_prefix('@') {
+				Some(name) => {
+					let account =
+						accounts.get(name).ok_or_else(|| format!("unknown account @{}", name))?;
+					Value::from_bytes(account.0)
+				},
+				None => Value::from_bytes(text.as_bytes()),
+			},
+			Arg::List(items) => Value::unnamed_composite(
+				items.iter().map(|item| item.to_value(accounts)).collect::<Result<Vec<_>, _>>()?,
+			),
+			Arg::Map(fields) => match fields.get("variant") {
+				Some(Arg::Text(variant)) => {
+					let values = match fields.get("fields") {
+						Some(Arg::List(items)) => items
+							.iter()
+							.map(|item| item.to_value(accounts))
+							.collect::<Result<Vec<_>, _>>()?,
+						_ => Vec::new(),
+					};
+					Value::unnamed_variant(variant.clone(), values)
+				},
+				_ => Value::named_composite(
+					fields
+						.iter()
+						.map(|(name, field)| Ok((name.clone(), field.to_value(accounts)?)))
+						.collect::<Result<Vec<_>, Error>>()?,
+				),
+			},
+		})
+	}
+}
+
+/// Submit every step of `scenario` at its time, from its account.
+///
+/// Nonces are tracked locally, so an account can make several calls within one block. A
+/// failed step is reported and the run continues.
+pub async fn run(
+	client: &OnlineClient<SubstrateConfig>,
+	scenario: Scenario,
+) -> Result<(), Error> {
+	let mut signers = HashMap::new();
+	let mut accounts = HashMap::new();
+	for (name, uri) in &scenario.accounts {
+		let signer = Keypair::from_uri(&SecretUri::from_str(uri)?)?;
+		accounts.insert(name.clone(), Signer::<SubstrateConfig>::account_id(&signer));
+		signers.insert(name.clone(), signer);
+	}
+
+	let mut nonces = HashMap::new();
+	let mut steps = scenario.steps;
+	steps.sort_by_key(|step| step.at);
+
+	let duration = steps.last().map_or(0, |step| step.at);
+	println!("Running {} steps over {} seconds...", steps.len(), duration);
+	let start = tokio::time::Instant::now();
+	for step in steps {
+		tokio::time::sleep_until(start + Duration::from_secs(step.at)).await;
+
+		let signer =
+			signers.get(&step.from).ok_or_else(|| format!("unknown account {}", step.from))?;
+		let (pallet, call) = step
+			.call
+			.split_once('.')
+			.ok_or_else(|| format!("call {} is not Pallet.call", step.call))?;
+		let args =
+			step.args.iter().map(|arg| arg.to_value(&accounts)).collect::<Result<Vec<_>, _>>()?;
+		let payload = subxt::dynamic::tx(pallet, call, args);
+
+		let nonce = match nonces.get(&step.from) {
+			Some(nonce) => *nonce,
+			None => client.tx().account_nonce(&accounts[&step.from]).await?,
+		};
+		let params = DefaultExtrinsicParamsBuilder::<SubstrateConfig>::new().nonce(nonce).build();
+		match client.tx().create_signed(&payload, signer, params).await?.submit().await {
+			Ok(hash) => {
+				println!("[{:>4}s] {} submitted {} ({:?})", step.at, step.from, step.call, hash);
+				nonces.insert(step.from, nonce + 1);
+			},
+			Err(e) =>
+				println!("[{:>4}s] {} failed to submit {}: {}", step.at, step.from, step.call, e),
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn yaml_and_toml_scenarios_read_the_same() {
+		let yaml = r#"
+accounts:
+  creator: "//Alice"
+steps:
+  - at: 0
+    from: creator
+    call: TickStream.create_stream
+    args: [10, { variant: None }, 0]
+"#;
+		let toml = r#"
+[accounts]
+creator = "//Alice"
+
+[[steps]]
+at = 0
+from = "creator"
+call = "TickStream.create_stream"
+args = [10, { variant = "None" }, 0]
+"#;
+		let scenario: Scenario = serde_yaml::from_str(yaml).unwrap();
+		assert_eq!(scenario, toml::from_str(toml).unwrap());
+		assert_eq!(scenario.steps[0].args[0], Arg::Unsigned(10));
+	}
+
+	#[test]
+	fn account_arguments_resolve_to_their_ids() {
+		let id = AccountId32([7; 32]);
+		let accounts = HashMap::from([("viewer".to_string(), id.clone())]);
+
+		let value = Arg::Text("@viewer".into()).to_value(&accounts).unwrap();
+		assert_eq!(value, Value::from_bytes(id.0));
+		assert!(Arg::Text("@nobody".into()).to_value(&accounts).is_err());
+	}
+}
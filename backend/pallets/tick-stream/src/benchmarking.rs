@@ -0,0 +1,1576 @@
+//! Benchmarks for the tick-stream pallet.
+//!
+//! Payments are benchmarked at their most expensive: a platform fee is charged, every
+//! `MaxCoHosts` slot takes a cut, the payment pushes the viewer below the low balance
+//! warning and their auto leave threshold takes them off the stream.
+
+use super::*;
+use crate::Pallet as TickStream;
+use codec::Encode;
+use frame_benchmarking::v2::*;
+use frame_support::{
+	traits::{
+		fungible::MutateHold, fungibles::Mutate, Currency, EnsureOrigin, Get, ReservableCurrency,
+		UnixTime,
+	},
+	BoundedVec,
+};
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use sp_runtime::{
+	traits::{Hash, IdentifyAccount, Saturating, Zero},
+	Percent, Permill,
+};
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+/// Seconds settled by each tick benchmark.
+const TICKS: u32 = 6;
+
+/// The per-second price of every benchmarked stream.
+fn price<T: Config>() -> BalanceOf<T> {
+	T::Currency::minimum_balance().max(1u32.into())
+}
+
+/// An account with enough free balance for any benchmark.
+fn funded<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&who, price::<T>().saturating_mul(1_000_000u32.into()));
+	who
+}
+
+/// Create a stream owned by `creator` and take it live.
+fn live_stream<T: Config>(creator: &T::AccountId) -> Result<u128, BenchmarkError> {
+	let stream_id = NextStreamId::<T>::get();
+	let origin = RawOrigin::Signed(creator.clone());
+	TickStream::<T>::create_stream(origin.into(), price::<T>(), None, 0)?;
+	TickStream::<T>::start_stream(RawOrigin::Signed(creator.clone()).into(), stream_id)?;
+	Ok(stream_id)
+}
+
+/// A referred viewer who reserved just enough to be warned about their balance after one
+/// tick.
+fn watching<T: Config>(stream_id: u128, index: u32) -> Result<T::AccountId, BenchmarkError> {
+	let viewer = funded::<T>("viewer", index);
+	let seconds = TICKS.saturating_add(T::LowBalanceSeconds::get().saturating_sub(1));
+	let referrer = account("referrer", index, SEED);
+	let origin = RawOrigin::Signed(viewer.clone());
+	TickStream::<T>::join_stream(origin.into(), stream_id, seconds, Some(referrer))?;
+	Ok(viewer)
+}
+
+/// Make `viewer` leave their streams on their next settlement.
+fn leaving<T: Config>(viewer: &T::AccountId) {
+	let threshold = price::<T>().saturating_mul(10_000_000u32.into());
+	AutoLeaveThresholds::<T>::insert(viewer, threshold);
+}
+
+/// Charge a platform fee and give every co-host slot of `stream_id` a cut.
+fn worst_case_split<T: Config>(stream_id: u128) {
+	PlatformFee::<T>::put(Permill::from_percent(5));
+	let co_hosts = T::MaxCoHosts::get();
+	let share = Permill::from_rational(1, co_hosts.saturating_add(1));
+	let split: Vec<(T::AccountId, Permill)> =
+		(0..co_hosts).map(|i| (account("co-host", i, SEED), share)).collect();
+	// Vesting shares cost an extra read per co-host over shares paid straight to escrow
+	for (co_host, _) in &split {
+		SplitVesting::<T>::insert(stream_id, co_host, BlockNumberFor::<T>::from(100u32));
+	}
+	RevenueSplits::<T>::insert(stream_id, BoundedVec::<_, T::MaxCoHosts>::truncate_from(split));
+	// The creator's share is queued to be swept to cold storage
+	if let Some(stream) = Streams::<T>::get(stream_id) {
+		let cold_address = account("cold", 0, SEED);
+		let policy = SweepPolicy { threshold: Zero::zero(), cold_address };
+		SweepPolicies::<T>::insert(&stream.creator, policy);
+	}
+
+	// Payments below the existential deposit cannot open an account
+	let minimum = T::Currency::minimum_balance();
+	T::Currency::make_free_balance_be(&TickStream::<T>::platform_account(), minimum);
+	T::Currency::make_free_balance_be(&TickStream::<T>::escrow_account(stream_id), minimum);
+}
+
+/// Gate `stream_id` with a join gate every viewer meets, so each of its checks is read.
+fn gated<T: Config>(stream_id: u128) {
+	JoinGates::<T>::insert(stream_id, JoinGate::default());
+}
+
+/// A registered tick reporter that signs with the benchmark helper's key.
+fn reporter<T: Config>() -> (T::OffchainPublic, T::AccountId) {
+	let signer = T::BenchmarkHelper::signer();
+	let reporter = signer.clone().into_account();
+	let bond = T::TickReporterBond::get();
+	Reporters::<T>::insert(&reporter, TickReporter { bond, nonce: 0, unbonds_at: None });
+	(signer, reporter)
+}
+
+/// Register `MaxAttestationSources` chains, each signing with the benchmark helper's key.
+fn attestation_sources<T: Config>() -> (T::OffchainPublic, u32) {
+	let signer = T::BenchmarkHelper::signer();
+	let count = T::MaxAttestationSources::get();
+	let source = AttestationSource { signer: signer.clone().into_account(), identity_level: 1 };
+	let sources = (0..count).map(|id| (id, source.clone())).collect::<Vec<_>>();
+	AttestationSources::<T>::put(BoundedVec::truncate_from(sources));
+	(signer, count)
+}
+
+/// Move past the tick rate limits so `TICKS` seconds can be settled.
+fn advance_for_ticks<T: Config>() {
+	let block = frame_system::Pallet::<T>::block_number();
+	frame_system::Pallet::<T>::set_block_number(block.saturating_add(T::TickThreshold::get()));
+	T::BenchmarkHelper::set_time(T::UnixTime::now().as_secs().saturating_add(TICKS.into()));
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn record_tick() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		worst_case_split::<T>(stream_id);
+		let viewer = watching::<T>(stream_id, 0)?;
+		leaving::<T>(&viewer);
+		advance_for_ticks::<T>();
+		let (signer, reporter) = reporter::<T>();
+		let payload = TickStream::<T>::tick_payload(stream_id, &viewer, TICKS, 1);
+		let signature = T::BenchmarkHelper::sign(&signer, &payload);
+
+		#[extrinsic_call]
+		_(RawOrigin::None, stream_id, viewer.clone(), TICKS, reporter, 1, signature);
+
+		assert_eq!(TickCount::<T>::get(stream_id), TICKS);
+		assert!(!ViewerLastTick::<T>::contains_key(stream_id, &viewer));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn join_stream() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		gated::<T>(stream_id);
+		let viewer = funded::<T>("viewer", 0);
+		let referrer: T::AccountId = account("referrer", 0, SEED);
+		// The viewer's credit is paid out of the platform account first
+		let credit = T::Currency::minimum_balance();
+		let platform = TickStream::<T>::platform_account();
+		T::Currency::make_free_balance_be(&platform, credit.saturating_mul(2u32.into()));
+		ViewingCredits::<T>::insert(&viewer, credit);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(viewer.clone()), stream_id, 3_600, Some(referrer.clone()));
+
+		assert!(ViewerLastTick::<T>::contains_key(stream_id, &viewer));
+		assert_eq!(Referrers::<T>::get(stream_id, &viewer), Some(referrer));
+		assert!(!ViewingCredits::<T>::contains_key(&viewer));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn create_stream() {
+		let creator = funded::<T>("creator", 0);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), price::<T>(), Some(100u32.into()), 60);
+
+		assert!(Streams::<T>::contains_key(0));
+	}
+
+	#[benchmark]
+	fn start_stream() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		TickStream::<T>::create_stream(
+			RawOrigin::Signed(creator.clone()).into(),
+			price::<T>(),
+			Some(100u32.into()),
+			0,
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), 0);
+
+		assert_eq!(Streams::<T>::get(0).map(|stream| stream.status), Some(StreamStatus::Live));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn pause_stream() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id);
+
+		assert_eq!(
+			Streams::<T>::get(stream_id).map(|stream| stream.status),
+			Some(StreamStatus::Paused)
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn end_stream(r: Linear<0, { T::MaxRefundsOnEnd::get() }>) -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		for i in 0..r {
+			watching::<T>(stream_id, i)?;
+		}
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id);
+
+		assert_eq!(Balances::<T>::iter_prefix(stream_id).count(), 0);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn batch_tick(n: Linear<1, { T::MaxViewersPerBatch::get() }>) -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		worst_case_split::<T>(stream_id);
+		let mut ticks = Vec::new();
+		for i in 0..n {
+			let viewer = watching::<T>(stream_id, i)?;
+			leaving::<T>(&viewer);
+			ticks.push((viewer, TICKS));
+		}
+		let ticks = BoundedVec::truncate_from(ticks);
+		advance_for_ticks::<T>();
+		let (signer, reporter) = reporter::<T>();
+		let payload = TickStream::<T>::batch_payload(stream_id, 1, &ticks, 1);
+		let signature = T::BenchmarkHelper::sign(&signer, &payload);
+
+		#[extrinsic_call]
+		_(RawOrigin::None, stream_id, 1, ticks, reporter, 1, signature);
+
+		assert_eq!(TickCount::<T>::get(stream_id), n * TICKS);
+		assert_eq!(LastSettledInterval::<T>::get(stream_id), Some(1));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn join_with_voucher() -> Result<(), BenchmarkError> {
+		let stream_id = NextStreamId::<T>::get();
+		let viewer = funded::<T>("viewer", 0);
+		let voucher = JoinVoucher {
+			stream_id,
+			nonce: 0,
+			viewer: Some(viewer.clone()),
+			price_per_second: Some(price::<T>()),
+			seconds: 3_600,
+			expires_at: 100u32.into(),
+		};
+		let signer = T::BenchmarkHelper::signer();
+		let signature = T::BenchmarkHelper::sign(&signer, &voucher.encode());
+		live_stream::<T>(&signer.into_account())?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(viewer), stream_id, voucher, signature);
+
+		assert!(ConsumedVouchers::<T>::contains_key(stream_id, 0));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_platform_fee() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let fee = Permill::from_percent(5);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, fee);
+
+		assert_eq!(PlatformFee::<T>::get(), fee);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_revenue_split(c: Linear<0, { T::MaxCoHosts::get() }>) -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let share = Permill::from_rational(1, c.saturating_add(1));
+		let co_hosts: Vec<(T::AccountId, Permill)> =
+			(0..c).map(|i| (account("co-host", i, SEED), share)).collect();
+		let co_hosts = BoundedVec::truncate_from(co_hosts);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id, co_hosts);
+
+		assert_eq!(RevenueSplits::<T>::get(stream_id).len() as u32, c);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn withdraw() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let amount = price::<T>().saturating_mul(1_000u32.into());
+		T::Currency::make_free_balance_be(&TickStream::<T>::escrow_account(stream_id), amount);
+		Escrow::<T>::insert(stream_id, &creator, amount);
+		// Half is paid out and half forwarded to cold storage
+		let cold_address: T::AccountId = account("cold", 0, SEED);
+		let threshold = price::<T>().saturating_mul(500u32.into());
+		let policy = SweepPolicy { threshold, cold_address: cold_address.clone() };
+		SweepPolicies::<T>::insert(&creator, policy);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator.clone()), stream_id);
+
+		assert!(!Escrow::<T>::contains_key(stream_id, &creator));
+		assert_eq!(T::Currency::free_balance(&cold_address), threshold);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_notification_prefs(f: Linear<0, { T::MaxFollowedStreams::get() }>) {
+		let who = funded::<T>("viewer", 0);
+		let followed = BoundedVec::truncate_from((0..f).map(u128::from).collect::<Vec<_>>());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(who.clone()), followed, NOTIFY_ALL);
+
+		assert!(NotificationPreferences::<T>::contains_key(&who));
+	}
+
+	#[benchmark]
+	fn set_metadata() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let text = BoundedVec::truncate_from(vec![b'x'; T::MaxMetadataLen::get() as usize]);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id, text.clone(), StreamCategory::Other, text);
+
+		assert!(StreamMetadata::<T>::contains_key(stream_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn clear_metadata() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let text = BoundedVec::truncate_from(vec![b'x'; T::MaxMetadataLen::get() as usize]);
+		TickStream::<T>::set_metadata(
+			RawOrigin::Signed(creator.clone()).into(),
+			stream_id,
+			text.clone(),
+			StreamCategory::Other,
+			text,
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id);
+
+		assert!(!StreamMetadata::<T>::contains_key(stream_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn sweep_expired(n: Linear<1, { T::MaxViewersPerBatch::get() }>) -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let mut viewers = Vec::new();
+		for i in 0..n {
+			viewers.push(watching::<T>(stream_id, i)?);
+		}
+		// End the stream without the eager refunds of `end_stream`
+		Streams::<T>::mutate(stream_id, |stream| {
+			if let Some(stream) = stream {
+				stream.status = StreamStatus::Ended;
+			}
+		});
+		let now = frame_system::Pallet::<T>::block_number();
+		EndedAt::<T>::insert(stream_id, now);
+		frame_system::Pallet::<T>::set_block_number(now.saturating_add(T::SettlementWindow::get()));
+		let rewards = T::SweepReward::get().saturating_mul(n.into());
+		T::Currency::make_free_balance_be(
+			&TickStream::<T>::platform_account(),
+			T::Currency::minimum_balance().saturating_add(rewards),
+		);
+		let sweeper = funded::<T>("sweeper", 0);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(sweeper), stream_id, BoundedVec::truncate_from(viewers));
+
+		assert_eq!(Balances::<T>::iter_prefix(stream_id).count(), 0);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn create_promo() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let code_hash = T::Hashing::hash(b"benchmark");
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id, code_hash, Permill::from_percent(50), 100);
+
+		assert!(Promos::<T>::contains_key(stream_id, code_hash));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn commit_promo() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let viewer = funded::<T>("viewer", 0);
+		let commitment = T::Hashing::hash(b"benchmark");
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(viewer.clone()), stream_id, commitment);
+
+		assert!(PromoCommitments::<T>::contains_key(stream_id, &viewer));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn reveal_promo() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let code = BoundedVec::truncate_from(vec![b'x'; T::MaxPromoCodeLen::get() as usize]);
+		TickStream::<T>::create_promo(
+			RawOrigin::Signed(creator).into(),
+			stream_id,
+			T::Hashing::hash(&code),
+			Permill::from_percent(50),
+			100,
+		)?;
+		let viewer = funded::<T>("viewer", 0);
+		let salt = [7u8; 32];
+		TickStream::<T>::commit_promo(
+			RawOrigin::Signed(viewer.clone()).into(),
+			stream_id,
+			TickStream::<T>::promo_commitment(&code, &viewer, &salt),
+		)?;
+		gated::<T>(stream_id);
+		let block = frame_system::Pallet::<T>::block_number();
+		frame_system::Pallet::<T>::set_block_number(block.saturating_add(1u32.into()));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(viewer.clone()), stream_id, code, salt);
+
+		assert!(ViewerPrices::<T>::contains_key(stream_id, &viewer));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn update_price() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id, price::<T>().saturating_mul(2u32.into()));
+
+		assert!(PendingPrices::<T>::contains_key(stream_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn top_up() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let viewer = watching::<T>(stream_id, 0)?;
+		let reserved = Balances::<T>::get(stream_id, &viewer);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(viewer.clone()), stream_id, 3_600);
+
+		assert!(Balances::<T>::get(stream_id, &viewer) > reserved);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_stream() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let handle = HandleOf::<T>::truncate_from(vec![b'a'; T::MaxHandleLen::get() as usize]);
+		let origin = RawOrigin::Signed(creator.clone());
+		TickStream::<T>::claim_handle(origin.into(), stream_id, handle.clone())?;
+		let new_creator = funded::<T>("creator", 1);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id, new_creator.clone());
+
+		assert_eq!(Handles::<T>::get(&handle).map(|claim| claim.owner), Some(new_creator.clone()));
+		assert_eq!(Streams::<T>::get(stream_id).map(|stream| stream.creator), Some(new_creator));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn tip() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		PlatformFee::<T>::put(Permill::from_percent(5));
+		let minimum = T::Currency::minimum_balance();
+		T::Currency::make_free_balance_be(&TickStream::<T>::platform_account(), minimum);
+		let tipper = funded::<T>("viewer", 0);
+		let amount = price::<T>().saturating_mul(100u32.into());
+		let message = BoundedVec::truncate_from(vec![b'x'; T::MaxTipMessageLen::get() as usize]);
+		// Routing the tip to a payout account deposits an extra event
+		Streams::<T>::mutate(stream_id, |stream| {
+			if let Some(stream) = stream {
+				stream.payout_account = Some(account("payout", 0, SEED));
+			}
+		});
+		// The stream keeps its most tips, each with the longest message, so one is dropped
+		let max = T::MaxTipsPerStream::get();
+		let tip = Tip {
+			tipper: tipper.clone(),
+			amount,
+			message: Some(message.to_vec()),
+			block: frame_system::Pallet::<T>::block_number(),
+		};
+		Tips::<T>::insert(stream_id, BoundedVec::truncate_from(vec![tip; max as usize]));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(tipper), stream_id, amount, Some(message));
+
+		assert_eq!(Tips::<T>::get(stream_id).len() as u32, max);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_auto_leave_threshold() {
+		let viewer = funded::<T>("viewer", 0);
+		let threshold = price::<T>().saturating_mul(100u32.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(viewer.clone()), threshold);
+
+		assert_eq!(AutoLeaveThresholds::<T>::get(&viewer), Some(threshold));
+	}
+
+	#[benchmark]
+	fn force_leave_stream(
+		r: Linear<0, { T::MaxViewersPerBatch::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		for i in 0..r {
+			watching::<T>(stream_id, i)?;
+		}
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, stream_id, r);
+
+		assert_eq!(Balances::<T>::iter_prefix(stream_id).count(), 0);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn force_leave_account(
+		s: Linear<0, { T::MaxViewersPerBatch::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let viewer = funded::<T>("viewer", 0);
+		let mut stream_ids = Vec::new();
+		for _ in 0..s {
+			let stream_id = live_stream::<T>(&creator)?;
+			let origin = RawOrigin::Signed(viewer.clone());
+			TickStream::<T>::join_stream(origin.into(), stream_id, 60, None)?;
+			stream_ids.push(stream_id);
+		}
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, viewer.clone(), BoundedVec::truncate_from(stream_ids));
+
+		assert_eq!(T::Currency::reserved_balance(&viewer), 0u32.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn schedule_stream() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		TickStream::<T>::create_stream(
+			RawOrigin::Signed(creator.clone()).into(),
+			price::<T>(),
+			Some(100u32.into()),
+			0,
+		)?;
+		let starts_at = T::UnixTime::now().as_secs() + 3_600;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), 0, starts_at, 330);
+
+		assert!(StreamSchedules::<T>::contains_key(0));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn top_up_budget() {
+		let creator = funded::<T>("creator", 0);
+		let viewer = funded::<T>("viewer", 0);
+		let amount = price::<T>().saturating_mul(3_600u32.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(viewer.clone()), creator.clone(), amount);
+
+		assert_eq!(CreatorBudgets::<T>::get(&viewer, &creator).reserved, amount);
+	}
+
+	#[benchmark]
+	fn release_budget() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let viewer = funded::<T>("viewer", 0);
+		let amount = price::<T>().saturating_mul(3_600u32.into());
+		TickStream::<T>::top_up_budget(
+			RawOrigin::Signed(viewer.clone()).into(),
+			creator.clone(),
+			amount,
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(viewer.clone()), creator.clone());
+
+		assert_eq!(T::Currency::reserved_balance(&viewer), 0u32.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn prune_viewer_stats(n: Linear<1, { T::MaxViewersPerBatch::get() }>) {
+		let viewer: T::AccountId = account("viewer", 0, SEED);
+		let now = frame_system::Pallet::<T>::block_number();
+		let stream_ids = (0..n as u128).collect::<Vec<_>>();
+		for stream_id in &stream_ids {
+			ViewerStats::<T>::insert(
+				&viewer,
+				stream_id,
+				WatchStats {
+					total_seconds: 3_600,
+					total_spent: price::<T>().saturating_mul(3_600u32.into()),
+					last_watched_block: now,
+				},
+			);
+		}
+		frame_system::Pallet::<T>::set_block_number(
+			now.saturating_add(T::ViewerStatsRetention::get()),
+		);
+		let pruner = funded::<T>("pruner", 0);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(pruner), viewer.clone(), BoundedVec::truncate_from(stream_ids));
+
+		assert_eq!(ViewerStats::<T>::iter_prefix(&viewer).count(), 0);
+	}
+
+	#[benchmark]
+	fn create_subscription_tier() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let price = price::<T>().saturating_mul(3_600u32.into());
+		for _ in 1..T::MaxSubscriptionTiers::get() {
+			TickStream::<T>::create_subscription_tier(
+				RawOrigin::Signed(creator.clone()).into(),
+				stream_id,
+				price,
+				100u32.into(),
+			)?;
+		}
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id, price, 100u32.into());
+
+		assert_eq!(
+			SubscriptionTiers::<T>::get(stream_id).len() as u32,
+			T::MaxSubscriptionTiers::get()
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn subscribe() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		worst_case_split::<T>(stream_id);
+		TickStream::<T>::create_subscription_tier(
+			RawOrigin::Signed(creator).into(),
+			stream_id,
+			price::<T>().saturating_mul(3_600u32.into()),
+			100u32.into(),
+		)?;
+		let viewer = funded::<T>("viewer", 0);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(viewer.clone()), stream_id, 0);
+
+		assert!(Subscriptions::<T>::contains_key(stream_id, &viewer));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_split_vesting() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		worst_case_split::<T>(stream_id);
+		let co_host: T::AccountId = account("co-host", T::MaxCoHosts::get() - 1, SEED);
+		SplitVesting::<T>::remove(stream_id, &co_host);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id, co_host.clone(), 100u32.into());
+
+		assert!(SplitVesting::<T>::contains_key(stream_id, &co_host));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn claim_vested() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let co_host: T::AccountId = account("co-host", 0, SEED);
+		let amount = price::<T>().saturating_mul(1_000u32.into());
+		T::Currency::make_free_balance_be(&TickStream::<T>::escrow_account(stream_id), amount);
+		// Half vested, so the schedule is kept for the rest
+		let now = frame_system::Pallet::<T>::block_number();
+		VestingSchedules::<T>::insert(
+			stream_id,
+			&co_host,
+			VestingSchedule {
+				unvested: amount,
+				claimable: Zero::zero(),
+				claimed: Zero::zero(),
+				updated_at: now,
+				ends_at: now.saturating_add(100u32.into()),
+			},
+		);
+		frame_system::Pallet::<T>::set_block_number(now.saturating_add(50u32.into()));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(co_host.clone()), stream_id);
+
+		assert!(VestingSchedules::<T>::get(stream_id, &co_host)
+			.is_some_and(|schedule| !schedule.claimed.is_zero()));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn dispute_ticks() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let viewer = funded::<T>("viewer", 0);
+		let amount = price::<T>().saturating_mul(TICKS.into());
+		T::NativeBalance::hold(&HoldReason::StreamDeposit.into(), &viewer, amount)?;
+		// One payment is disputed and the next kept
+		let due_at = frame_system::Pallet::<T>::block_number().saturating_add(100u32.into());
+		for from_tick in [0, u64::from(TICKS)] {
+			let to_tick = from_tick + u64::from(TICKS);
+			PendingSettlements::<T>::append(
+				stream_id,
+				&viewer,
+				PendingSettlement { from_tick, to_tick, amount, due_at },
+			);
+		}
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(viewer.clone()), stream_id, 0, TICKS.into());
+
+		assert!(Disputes::<T>::contains_key(0));
+		assert_eq!(PendingSettlements::<T>::get(stream_id, &viewer).len(), 1);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn resolve_dispute() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		worst_case_split::<T>(stream_id);
+		let viewer = funded::<T>("viewer", 0);
+		let amount = price::<T>().saturating_mul(TICKS.into());
+		T::NativeBalance::hold(&HoldReason::StreamDeposit.into(), &viewer, amount)?;
+		Disputes::<T>::insert(
+			0,
+			Dispute {
+				stream_id,
+				viewer,
+				reporter: creator.clone(),
+				amount,
+				from_tick: 0,
+				to_tick: TICKS.into(),
+			},
+		);
+		OpenDisputes::<T>::insert(&creator, 1);
+
+		// A rejected dispute splits the payment, which costs more than refunding it
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, 0, false);
+
+		assert!(!Disputes::<T>::contains_key(0));
+		assert_eq!(OpenDisputes::<T>::get(&creator), 0);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn unbond_reporter() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let bonded = price::<T>().saturating_mul(100u32.into());
+		T::NativeBalance::hold(&HoldReason::DisputeBond.into(), &creator, bonded)?;
+		let stake = ReporterStake { bonded, last_reported: Zero::zero() };
+		ReporterStakes::<T>::insert(&creator, stake);
+		let now = frame_system::Pallet::<T>::block_number();
+		frame_system::Pallet::<T>::set_block_number(
+			now.saturating_add(T::SettlementDelay::get()).saturating_add(1u32.into()),
+		);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator.clone()));
+
+		assert!(!ReporterStakes::<T>::contains_key(&creator));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_stream_asset() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = NextStreamId::<T>::get();
+		let origin = RawOrigin::Signed(creator.clone());
+		TickStream::<T>::create_stream(origin.into(), price::<T>(), None, 0)?;
+		// The escrow account is opened in both the native token and the asset
+		let asset = T::BenchmarkHelper::create_asset();
+		let amount = price::<T>().saturating_mul(1_000u32.into());
+		T::Assets::mint_into(asset.clone(), &creator, amount)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id, Some(asset.clone()));
+
+		assert_eq!(Streams::<T>::get(stream_id).and_then(|stream| stream.asset_id), Some(asset));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_join_gate() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let gate = JoinGate {
+			min_account_age: T::MaxGateAccountAge::get(),
+			min_watch_seconds: T::MaxGateWatchSeconds::get(),
+			require_identity: false,
+		};
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id, Some(gate));
+
+		assert!(JoinGates::<T>::contains_key(stream_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_sync_host() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let host = funded::<T>("host", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id, Some(host.clone()));
+
+		assert_eq!(SyncHosts::<T>::get(stream_id), Some(host));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn anchor_position() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let host = funded::<T>("host", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let origin = RawOrigin::Signed(creator);
+		TickStream::<T>::set_sync_host(origin.into(), stream_id, Some(host.clone()))?;
+		// A full history, so the oldest anchor is dropped
+		let anchors = (0..T::MaxPositionAnchors::get())
+			.map(|i| PositionAnchor { block: Zero::zero(), media_timestamp: i.into() })
+			.collect::<Vec<_>>();
+		PositionAnchors::<T>::insert(stream_id, BoundedVec::truncate_from(anchors));
+		let now = frame_system::Pallet::<T>::block_number();
+		frame_system::Pallet::<T>::set_block_number(now.saturating_add(1u32.into()));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(host), stream_id, 1_000);
+
+		let anchors = PositionAnchors::<T>::get(stream_id);
+		assert_eq!(anchors.last().map(|anchor| anchor.media_timestamp), Some(1_000));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn freeze_payouts() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let origin = T::PayoutFreezeOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		// Extending a freeze, which also queues another expiry
+		PayoutFreezes::<T>::insert(&creator, BlockNumberFor::<T>::from(1u32));
+		let duration = BlockNumberFor::<T>::from(100u32);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, creator.clone(), duration);
+
+		let until = frame_system::Pallet::<T>::block_number().saturating_add(duration);
+		assert_eq!(PayoutFreezes::<T>::get(&creator), Some(until));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn unfreeze_payouts() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let origin = T::PayoutFreezeOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		PayoutFreezes::<T>::insert(&creator, BlockNumberFor::<T>::from(100u32));
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, creator.clone());
+
+		assert!(!PayoutFreezes::<T>::contains_key(&creator));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn claim_handle() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let handle = HandleOf::<T>::truncate_from(vec![b'a'; T::MaxHandleLen::get() as usize]);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator.clone()), stream_id, handle.clone());
+
+		assert_eq!(StreamHandles::<T>::get(stream_id), Some(handle));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn release_handle() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let handle = HandleOf::<T>::truncate_from(vec![b'a'; T::MaxHandleLen::get() as usize]);
+		let origin = RawOrigin::Signed(creator.clone());
+		TickStream::<T>::claim_handle(origin.into(), stream_id, handle.clone())?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id);
+
+		assert!(!Handles::<T>::contains_key(&handle));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_spending_limit() {
+		let viewer = funded::<T>("viewer", 0);
+		let limit = Some(price::<T>().saturating_mul(1_000u32.into()));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(viewer.clone()), limit, limit);
+
+		assert!(SpendingLimits::<T>::contains_key(&viewer));
+	}
+
+	#[benchmark]
+	fn resume_session() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let viewer = watching::<T>(stream_id, 0)?;
+		SessionStates::<T>::insert(stream_id, &viewer, SessionState::GracePaused);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(viewer.clone()), stream_id);
+
+		assert_eq!(SessionStates::<T>::get(stream_id, &viewer), Some(SessionState::Active));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_free_preview() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id, 60);
+
+		assert_eq!(FreePreviews::<T>::get(stream_id), 60);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn open_session() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let viewer = watching::<T>(stream_id, 0)?;
+		// The last place an exempt viewer has
+		let origin = RawOrigin::Signed(creator);
+		TickStream::<T>::set_device_exemption(origin.into(), stream_id, viewer.clone(), true)?;
+		let open = (1..T::MaxDevicesPerViewer::get()).map(u64::from).collect::<Vec<_>>();
+		ActiveSessions::<T>::insert(stream_id, &viewer, BoundedVec::truncate_from(open));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(viewer.clone()), stream_id, 0);
+
+		assert!(ActiveSessions::<T>::get(stream_id, &viewer).contains(&0));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn close_session() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let viewer = watching::<T>(stream_id, 0)?;
+		let open = (0..T::MaxDevicesPerViewer::get()).map(u64::from).collect::<Vec<_>>();
+		ActiveSessions::<T>::insert(stream_id, &viewer, BoundedVec::truncate_from(open));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(viewer.clone()), stream_id, 0);
+
+		assert!(!ActiveSessions::<T>::get(stream_id, &viewer).contains(&0));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_device_limit() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let limit = T::MaxDevicesPerViewer::get();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id, limit);
+
+		assert_eq!(DeviceLimits::<T>::get(stream_id), Some(limit).filter(|limit| *limit > 1));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_device_exemption() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let viewer = funded::<T>("viewer", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id, viewer.clone(), true);
+
+		assert!(DeviceLimitExemptions::<T>::contains_key(stream_id, &viewer));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn claim_referral_earnings() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let referrer = funded::<T>("referrer", 0);
+		let amount = price::<T>().saturating_mul(1_000u32.into());
+		T::Currency::make_free_balance_be(&TickStream::<T>::escrow_account(stream_id), amount);
+		ReferralEarnings::<T>::insert(&referrer, stream_id, amount);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(referrer.clone()), stream_id);
+
+		assert!(!ReferralEarnings::<T>::contains_key(&referrer, stream_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_max_viewers() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id, Some(50));
+
+		assert_eq!(MaxViewers::<T>::get(stream_id), Some(50));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_category_fee() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let fee = Permill::from_percent(2);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, StreamCategory::Education, Some(fee));
+
+		assert_eq!(CategoryFees::<T>::get(StreamCategory::Education), Some(fee));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn pre_book() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let viewer = funded::<T>("viewer", 0);
+		let origin = RawOrigin::Signed(creator.clone());
+		TickStream::<T>::create_stream(origin.clone().into(), price::<T>(), None, 0)?;
+		let starts_at = T::UnixTime::now().as_secs() + 3_600;
+		TickStream::<T>::schedule_stream(origin.clone().into(), 0, starts_at, 330)?;
+		TickStream::<T>::set_max_viewers(origin.into(), 0, Some(50))?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(viewer.clone()), 0);
+
+		assert_eq!(PreBookings::<T>::get(0).len(), 1);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn gift_subscriptions(
+		n: Linear<1, { T::MaxGiftRecipients::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		worst_case_split::<T>(stream_id);
+		let origin = RawOrigin::Signed(creator);
+		let price = price::<T>().saturating_mul(3_600u32.into());
+		TickStream::<T>::create_subscription_tier(
+			origin.clone().into(),
+			stream_id,
+			price,
+			100u32.into(),
+		)?;
+		let discount = BulkDiscount { min_passes: 2, discount: Permill::from_percent(10) };
+		TickStream::<T>::set_bulk_discount(origin.into(), stream_id, Some(discount))?;
+		let gifter = funded::<T>("gifter", 0);
+		let recipients = (0..n).map(|i| account("recipient", i, SEED)).collect::<Vec<_>>();
+		let recipients = BoundedVec::try_from(recipients).expect("n is at most MaxGiftRecipients");
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(gifter), stream_id, 0, recipients);
+
+		let recipient: T::AccountId = account("recipient", n - 1, SEED);
+		assert!(Subscriptions::<T>::contains_key(stream_id, &recipient));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_bulk_discount() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let discount = BulkDiscount { min_passes: 5, discount: Permill::from_percent(10) };
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id, Some(discount.clone()));
+
+		assert_eq!(BulkDiscounts::<T>::get(stream_id), Some(discount));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_sweep_policy() {
+		let who = funded::<T>("creator", 0);
+		let cold_address = account("cold", 0, SEED);
+		let policy = SweepPolicy { threshold: price::<T>(), cold_address };
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(who.clone()), Some(policy));
+
+		assert!(SweepPolicies::<T>::contains_key(&who));
+	}
+
+	#[benchmark]
+	fn set_payout_account() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let payout_account: T::AccountId = account("payout", 0, SEED);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id, Some(payout_account.clone()));
+
+		assert_eq!(
+			Streams::<T>::get(stream_id).and_then(|stream| stream.payout_account),
+			Some(payout_account)
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn link_did() -> Result<(), BenchmarkError> {
+		let who = funded::<T>("creator", 0);
+		let did = |c: u8| {
+			let mut did = b"did:web:".to_vec();
+			did.resize(T::MaxDidLen::get() as usize, c);
+			DidOf::<T>::try_from(did).map_err(|_| BenchmarkError::Weightless)
+		};
+		// Relinking also frees the DID linked before
+		let origin = RawOrigin::Signed(who.clone()).into();
+		TickStream::<T>::link_did(origin, did(b'a')?, [0; 32], [0; 64])?;
+		let did = did(b'b')?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(who.clone()), did.clone(), [1; 32], [1; 64]);
+
+		assert_eq!(DidAccounts::<T>::get(&did), Some(who));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn unlink_did() -> Result<(), BenchmarkError> {
+		let who = funded::<T>("creator", 0);
+		let did = DidOf::<T>::truncate_from(b"did:web:example.com".to_vec());
+		TickStream::<T>::link_did(RawOrigin::Signed(who.clone()).into(), did, [0; 32], [0; 64])?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(who.clone()));
+
+		assert!(!DidLinks::<T>::contains_key(&who));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_dust_policy() {
+		let who = funded::<T>("creator", 0);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(who.clone()), DustPolicy::Credit);
+
+		assert_eq!(DustPolicies::<T>::get(&who), Some(DustPolicy::Credit));
+	}
+
+	#[benchmark]
+	fn register_reporter() {
+		let who = funded::<T>("reporter", 0);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(who.clone()));
+
+		assert!(Reporters::<T>::contains_key(&who));
+	}
+
+	#[benchmark]
+	fn deregister_reporter() -> Result<(), BenchmarkError> {
+		let who = funded::<T>("reporter", 0);
+		TickStream::<T>::register_reporter(RawOrigin::Signed(who.clone()).into())?;
+		TickStream::<T>::deregister_reporter(RawOrigin::Signed(who.clone()).into())?;
+		let unbonds_at = frame_system::Pallet::<T>::block_number()
+			.saturating_add(T::ReporterCooldown::get());
+		frame_system::Pallet::<T>::set_block_number(unbonds_at);
+
+		// Returning the bond is the heavier of the two steps
+		#[extrinsic_call]
+		_(RawOrigin::Signed(who.clone()));
+
+		assert!(!Reporters::<T>::contains_key(&who));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn migrate_stream(r: Linear<0, 1_000>) -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		worst_case_split::<T>(stream_id);
+		for i in 0..r {
+			let viewer: T::AccountId = account("viewer", i, SEED);
+			WatchedSeconds::<T>::insert(stream_id, viewer, u64::from(TICKS));
+		}
+		let new_id = NextStreamId::<T>::get().saturating_add(1);
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, stream_id, new_id, r);
+
+		assert!(Streams::<T>::contains_key(new_id));
+		assert_eq!(WatchedSeconds::<T>::iter_prefix(new_id).count(), r as usize);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_content_type() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = NextStreamId::<T>::get();
+		let origin = RawOrigin::Signed(creator.clone());
+		TickStream::<T>::create_stream(origin.into(), price::<T>(), None, 0)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id, ContentType::Vod);
+
+		let content_type = Streams::<T>::get(stream_id).map(|stream| stream.content_type);
+		assert_eq!(content_type, Some(ContentType::Vod));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn report_playback() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = NextStreamId::<T>::get();
+		let origin = RawOrigin::Signed(creator.clone());
+		TickStream::<T>::create_stream(origin.into(), price::<T>(), None, 0)?;
+		let origin = RawOrigin::Signed(creator.clone());
+		TickStream::<T>::set_content_type(origin.into(), stream_id, ContentType::Vod)?;
+		TickStream::<T>::start_stream(RawOrigin::Signed(creator).into(), stream_id)?;
+		worst_case_split::<T>(stream_id);
+		let viewer = watching::<T>(stream_id, 0)?;
+		leaving::<T>(&viewer);
+		advance_for_ticks::<T>();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(viewer.clone()), stream_id, TICKS);
+
+		assert_eq!(TickCount::<T>::get(stream_id), TICKS);
+		assert_eq!(PlaybackPositions::<T>::get(stream_id, &viewer), TICKS);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_global_hold_cap() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let cap = price::<T>().saturating_mul(1_000u32.into());
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, Some(cap));
+
+		assert_eq!(GlobalHoldCap::<T>::get(), Some(cap));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_access_policy() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let asset_id = T::BenchmarkHelper::create_asset();
+		let policy = AccessPolicy::TokenGated { asset_id, min_balance: price::<T>() };
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id, policy.clone());
+
+		assert_eq!(AccessPolicies::<T>::get(stream_id), Some(policy));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn add_to_allowlist() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let viewer: T::AccountId = account("viewer", 0, SEED);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id, viewer.clone());
+
+		assert!(Allowlists::<T>::contains_key(stream_id, &viewer));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_from_allowlist() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		let viewer: T::AccountId = account("viewer", 0, SEED);
+		Allowlists::<T>::insert(stream_id, &viewer, ());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id, viewer.clone());
+
+		assert!(!Allowlists::<T>::contains_key(stream_id, &viewer));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn approve_grant() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		// A creator with no balance of their own, so the grant's transfer creates the account
+		let creator: T::AccountId = account("creator", 0, SEED);
+		let tranche = price::<T>().saturating_mul(1_000u32.into());
+		let platform = TickStream::<T>::platform_account();
+		T::Currency::make_free_balance_be(&platform, tranche.saturating_mul(10u32.into()));
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, creator.clone(), 3, tranche, 10, tranche, 1_000u32.into());
+
+		assert!(CreatorGrants::<T>::contains_key(&creator));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_attestation_source() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		// Updating the last of the registered chains
+		let (_, count) = attestation_sources::<T>();
+		let source = count.saturating_sub(1);
+		let signer: T::AccountId = account("signer", 0, SEED);
+		let config = AttestationSource { signer, identity_level: 2 };
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, source, Some(config.clone()));
+
+		assert!(AttestationSources::<T>::get().contains(&(source, config)));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn import_attestation() -> Result<(), BenchmarkError> {
+		let relayer = funded::<T>("relayer", 0);
+		let viewer: T::AccountId = account("viewer", 0, SEED);
+		// The viewer has an attestation from every chain, the last of which is replaced
+		let (signer, count) = attestation_sources::<T>();
+		let expires_at = frame_system::Pallet::<T>::block_number().saturating_add(100u32.into());
+		let attestation = |source, nonce| ViewerAttestation {
+			source,
+			watch_seconds: 3_600,
+			verification_level: 1,
+			nonce,
+			expires_at,
+		};
+		let attestations = (0..count).map(|source| attestation(source, 1)).collect::<Vec<_>>();
+		ViewerAttestations::<T>::insert(&viewer, BoundedVec::truncate_from(attestations));
+		let attestation = attestation(count.saturating_sub(1), 2);
+		let payload = TickStream::<T>::attestation_payload(&viewer, &attestation);
+		let signature = T::BenchmarkHelper::sign(&signer, &payload);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(relayer), viewer.clone(), attestation.clone(), signature);
+
+		assert!(ViewerAttestations::<T>::get(&viewer).contains(&attestation));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_reporting_unit() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id, ReportingUnit::Fiat);
+
+		assert_eq!(ReportingUnits::<T>::get(stream_id), Some(ReportingUnit::Fiat));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_hedge_policy() -> Result<(), BenchmarkError> {
+		if !T::PayoutSwap::is_supported() {
+			return Err(BenchmarkError::Weightless)
+		}
+		let who = funded::<T>("creator", 0);
+		let policy = HedgePolicy {
+			hedge_percent: Percent::from_percent(50),
+			asset: T::BenchmarkHelper::create_asset(),
+			max_slippage: Permill::from_percent(1),
+		};
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(who.clone()), Some(policy));
+
+		assert!(HedgePolicies::<T>::contains_key(&who));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn create_poll() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let text = vec![b'x'; T::MaxPollTextLen::get() as usize];
+		let options = vec![text.clone(); T::MaxPollOptions::get() as usize];
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(creator),
+			text,
+			options,
+			T::EraLength::get(),
+			T::MaxPollLookbackEras::get(),
+		);
+
+		assert!(Polls::<T>::contains_key(0));
+		Ok(())
+	}
+
+	/// A vote replacing an earlier one, weighed over the longest lookback with watch time in
+	/// every era of it.
+	#[benchmark]
+	fn vote() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let voter = funded::<T>("viewer", 0);
+		let lookback = T::MaxPollLookbackEras::get();
+		frame_system::Pallet::<T>::set_block_number(
+			T::EraLength::get().saturating_mul(lookback.into()),
+		);
+		let options = vec![vec![b'x']; T::MaxPollOptions::get().max(2) as usize];
+		TickStream::<T>::create_poll(
+			RawOrigin::Signed(creator.clone()).into(),
+			b"Next game?".to_vec(),
+			options,
+			T::EraLength::get(),
+			lookback,
+		)?;
+		let era = TickStream::<T>::current_era();
+		for era in era.saturating_sub(lookback)..=era {
+			CreatorWatchTime::<T>::insert(era, (&creator, &voter), 60);
+		}
+		TickStream::<T>::vote(RawOrigin::Signed(voter.clone()).into(), 0, 0)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(voter.clone()), 0, 1);
+
+		assert_eq!(PollVotes::<T>::get(0, &voter).map(|(option, _)| option), Some(1));
+		Ok(())
+	}
+
+	/// A giveaway drawn among ten actively watching viewers.
+	#[benchmark]
+	fn run_giveaway() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stream_id = live_stream::<T>(&creator)?;
+		for index in 0..10 {
+			let viewer = watching::<T>(stream_id, index)?;
+			SessionStates::<T>::insert(stream_id, viewer, SessionState::Active);
+		}
+		let prize = price::<T>().saturating_mul(100u32.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), stream_id, prize);
+
+		assert_eq!(Giveaways::<T>::get(0).map(|giveaway| giveaway.entrants), Some(10));
+		Ok(())
+	}
+
+	/// A reporter bond slashed to nothing, topped back up in full.
+	#[benchmark]
+	fn bond_reporter() -> Result<(), BenchmarkError> {
+		let creator = funded::<T>("creator", 0);
+		let stake = ReporterStake { bonded: Zero::zero(), last_reported: Zero::zero() };
+		ReporterStakes::<T>::insert(&creator, stake);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator.clone()));
+
+		let bonded = ReporterStakes::<T>::get(&creator).map(|stake| stake.bonded);
+		assert_eq!(bonded, Some(T::ReporterBond::get()));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_call_filter_mode() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, CallFilterMode::Frozen);
+
+		assert_eq!(CallFilter::<T>::get(), CallFilterMode::Frozen);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_tick_threshold() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let threshold: BlockNumberFor<T> = 10u32.into();
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, Some(threshold));
+
+		assert_eq!(TickThresholdOverride::<T>::get(), Some(threshold));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_batch_cap() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let cap = T::MaxViewersPerBatch::get();
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, Some(cap));
+
+		assert_eq!(BatchCapOverride::<T>::get(), Some(cap));
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(TickStream, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -15,135 +15,8733 @@ mod tests;
 mod benchmarking;
 
 /// Runtime API for the tick stream pallet
-#[cfg(feature = "std")]
 pub mod rpc;
+pub use rpc::TickStreamApi;
+
+pub mod telemetry;
+pub use telemetry::TrackDispatchErrors;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+pub mod migrations;
+
+pub mod settlement_digest;
+pub use settlement_digest::{EraSettlementCommitment, EraSettlementProof};
+
+use frame_support::{dispatch::DispatchResult, weights::Weight, RuntimeDebug};
+use sp_std::vec::Vec;
+
+/// Prefix of the topic every tick-stream event is deposited with. The event's sequence number
+/// within its block follows as a little-endian `u32`, then zero padding.
+pub const EVENT_SEQUENCE_TOPIC: [u8; 8] = *b"tickseq:";
+
+/// The sequence number an event topic carries, if it is a tick-stream sequence topic.
+pub fn event_sequence(topic: &[u8]) -> Option<u32> {
+	let seq = topic.strip_prefix(&EVENT_SEQUENCE_TOPIC[..])?.get(..4)?;
+	Some(u32::from_le_bytes(seq.try_into().ok()?))
+}
+
+/// Converts native token amounts to fiat at the current exchange rate.
+pub trait FiatOracle<Balance> {
+	/// The fiat value of `amount`, in the smallest fiat unit, or `None` without a rate.
+	fn fiat_value(amount: Balance) -> Option<u128>;
+}
+
+impl<Balance> FiatOracle<Balance> for () {
+	fn fiat_value(_amount: Balance) -> Option<u128> {
+		None
+	}
+}
+
+/// Receives the watch time viewers accrued, in batches once each era is over.
+///
+/// Lets reward pallets build on watch time without being called as every tick settles.
+pub trait OnWatchTime<AccountId> {
+	/// Handle `records` of (viewer, stream id, seconds) accrued during `era`, returning the
+	/// weight used. An era's records can arrive over several calls.
+	fn on_watch_time(era: u32, records: &[(AccountId, u128, u64)]) -> Weight;
+}
+
+impl<AccountId> OnWatchTime<AccountId> for () {
+	fn on_watch_time(_era: u32, _records: &[(AccountId, u128, u64)]) -> Weight {
+		Weight::zero()
+	}
+}
+
+/// Tells which accounts have a verified identity.
+pub trait IdentityVerifier<AccountId> {
+	/// Whether the chain verifies identities at all; streams cannot require one otherwise.
+	fn is_supported() -> bool;
+	/// Whether `who` has a verified identity.
+	fn has_identity(who: &AccountId) -> bool;
+}
+
+impl<AccountId> IdentityVerifier<AccountId> for () {
+	fn is_supported() -> bool {
+		false
+	}
+
+	fn has_identity(_who: &AccountId) -> bool {
+		false
+	}
+}
+
+/// Signs ticks in the offchain worker with the tick reporter keys in the node's keystore.
+pub trait TickSigner<Public, Signature> {
+	/// The reporter keys the node holds.
+	fn reporter_keys() -> Vec<Public>;
+	/// Sign `message` with `reporter`'s key, if the node holds it.
+	fn sign(reporter: &Public, message: &[u8]) -> Option<Signature>;
+}
+
+/// What a tick settlement is about to charge, handed to `SettlementExtension` before any funds
+/// move.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SettlementContext<AccountId, Balance> {
+	pub stream_id: u128,
+	/// The creator the stream's earnings go to.
+	pub creator: AccountId,
+	/// The viewer being charged.
+	pub viewer: AccountId,
+	/// The seconds of watch time settled.
+	pub seconds: u32,
+	/// The viewer's watched seconds on the stream before the settlement.
+	pub from_second: u64,
+	/// The viewer's watched seconds on the stream after the settlement.
+	pub to_second: u64,
+	/// The viewer's price per second.
+	pub price_per_second: Balance,
+	/// The amount charged, which is zero for free previews and subscribers.
+	pub amount: Balance,
+}
+
+/// Runtime-specific rules run as every tick is settled, such as enforcing purchase-order
+/// budgets or notifying an ERP system.
+pub trait SettlementExtension<AccountId, Balance> {
+	/// The most weight `before_settlement` uses, charged for every settlement.
+	fn weight() -> Weight;
+	/// Check or record a settlement before funds move. An error stops the settlement: a
+	/// single tick fails with it, while ticks settled in a batch are skipped.
+	fn before_settlement(context: &SettlementContext<AccountId, Balance>) -> DispatchResult;
+}
+
+impl<AccountId, Balance> SettlementExtension<AccountId, Balance> for () {
+	fn weight() -> Weight {
+		Weight::zero()
+	}
+
+	fn before_settlement(_context: &SettlementContext<AccountId, Balance>) -> DispatchResult {
+		Ok(())
+	}
+}
+
+/// Where watch time settled by `do_settle` comes from, which decides what is left to check.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub(crate) enum SettlementEntry {
+	/// Seconds reported with `record_tick` or `report_playback`, checked in full.
+	Reported,
+	/// A viewer's seconds in `batch_tick` or `settle_inherent`, already checked with
+	/// `ensure_tickable` as the batch weighs its viewers.
+	Batched,
+	/// A tick queued by an earlier block that ran out of settlements. It was checked when it
+	/// was reported, so only the viewer still watching is.
+	Queued,
+}
+
+/// What a batch settled on one stream, as reported in `IntervalSettled`.
+#[derive(Default)]
+pub(crate) struct IntervalTotals<Balance> {
+	/// The viewers charged.
+	pub viewers: u32,
+	pub total_amount: Balance,
+	pub total_fiat_value: u128,
+}
+
+/// Swaps withdrawn earnings into another asset, such as through asset-conversion pools, for
+/// accounts that hedge their payouts.
+pub trait PayoutSwap<AccountId, AssetId, Balance> {
+	/// Whether the chain can swap at all; accounts cannot hedge their payouts otherwise.
+	fn is_supported() -> bool;
+	/// The most weight `quote` and `swap` use together, charged for every withdrawal.
+	fn weight() -> Weight;
+	/// What `amount` of `from`, the native token if `None`, buys of `to` at the current rate,
+	/// or `None` if they cannot be swapped.
+	fn quote(from: Option<AssetId>, to: AssetId, amount: Balance) -> Option<Balance>;
+	/// Swap `amount` of `source`'s `from` for at least `min_out` of `to`, paid to `dest`,
+	/// returning how much of `to` was paid.
+	fn swap(
+		source: &AccountId,
+		dest: &AccountId,
+		from: Option<AssetId>,
+		to: AssetId,
+		amount: Balance,
+		min_out: Balance,
+	) -> Result<Balance, sp_runtime::DispatchError>;
+}
+
+impl<AccountId, AssetId, Balance> PayoutSwap<AccountId, AssetId, Balance> for () {
+	fn is_supported() -> bool {
+		false
+	}
+
+	fn weight() -> Weight {
+		Weight::zero()
+	}
+
+	fn quote(_from: Option<AssetId>, _to: AssetId, _amount: Balance) -> Option<Balance> {
+		None
+	}
+
+	fn swap(
+		_source: &AccountId,
+		_dest: &AccountId,
+		_from: Option<AssetId>,
+		_to: AssetId,
+		_amount: Balance,
+		_min_out: Balance,
+	) -> Result<Balance, sp_runtime::DispatchError> {
+		Err(sp_runtime::DispatchError::Unavailable)
+	}
+}
+
+/// Chain-specific setup the benchmarks cannot do generically.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<Public, Signature, AssetId> {
+	/// A public key whose account can sign join vouchers.
+	fn signer() -> Public;
+	/// Sign `message` with the private key of `signer`.
+	fn sign(signer: &Public, message: &[u8]) -> Signature;
+	/// Set the unix time the pallet reads, in seconds.
+	fn set_time(seconds: u64);
+	/// Create an asset streams can be priced in and return its id.
+	fn create_asset() -> AssetId;
+}
 
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::{dispatch::DispatchResult, pallet_prelude::*};
-	use frame_system::pallet_prelude::*;
-	use sp_core::H256;
-	use sp_runtime::traits::Hash;
+	use codec::FullCodec;
+	use frame_support::{
+		dispatch::DispatchResult,
+		pallet_prelude::*,
+		storage::{with_storage_layer, IterableStorageDoubleMap},
+		traits::{
+			fungible::{self, MutateHold as _},
+			fungibles::{self, Inspect as _, Mutate as _},
+			tokens::{Fortitude, Precision, Preservation, Restriction},
+			Currency, ExistenceRequirement, OnKilledAccount, OnNewAccount, OnRuntimeUpgrade,
+			PartialStorageInfoTrait, Randomness, ReservableCurrency, UnixTime,
+		},
+		PalletId,
+	};
+	use frame_system::{
+		offchain::{SendTransactionTypes, SubmitTransaction},
+		pallet_prelude::*,
+	};
+	use sp_core::{crypto::KeyTypeId, H256};
+	use sp_runtime::{
+		offchain::{
+			storage::StorageValueRef,
+			storage_lock::{BlockAndTime, StorageLock},
+			Duration,
+		},
+		traits::{
+			AccountIdConversion, AtLeast32BitUnsigned, CheckedMul, Hash, IdentifyAccount, One,
+			Saturating, TrailingZeroInput, Verify, Zero,
+		},
+		ModuleError, Perbill, Percent, Permill, SaturatedConversion,
+	};
+	use serde::{Deserialize, Serialize};
+	use sp_std::{
+		collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+		prelude::*,
+	};
+
+	#[cfg(feature = "runtime-benchmarks")]
+	use crate::BenchmarkHelper;
+	use crate::{
+		settlement_digest, EraSettlementCommitment, EraSettlementProof, IntervalTotals,
+		PayoutSwap, SettlementContext, SettlementEntry, SettlementExtension, TickSigner,
+		WeightInfo,
+	};
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	pub type AssetIdOf<T> = <<T as Config>::Assets as fungibles::Inspect<
+		<T as frame_system::Config>::AccountId,
+	>>::AssetId;
+
+	/// Split a tick payment into the platform fee, each co-host's cut and the creator's share.
+	///
+	/// Co-host shares apply to what is left after the platform fee, and the creator receives
+	/// whatever rounding leaves over.
+	pub fn split_payment<Balance: AtLeast32BitUnsigned + Copy>(
+		amount: Balance,
+		platform_fee: Permill,
+		co_host_shares: impl Iterator<Item = Permill>,
+	) -> (Balance, Vec<Balance>, Balance) {
+		let fee = platform_fee * amount;
+		let net = amount.saturating_sub(fee);
+		let mut creator_share = net;
+		let cuts = co_host_shares
+			.map(|share| {
+				let cut = share * net;
+				creator_share = creator_share.saturating_sub(cut);
+				cut
+			})
+			.collect();
+		(fee, cuts, creator_share)
+	}
+
+	/// Draw up to `count` distinct streams, each with a chance proportional to its watch time.
+	///
+	/// The `i`th draw picks the stream whose cumulative watch time range contains
+	/// `blake2_256(seed ++ i as u32 LE)`, read as a little-endian u64, modulo the watch time
+	/// left. `candidates` are taken in ascending stream id order, so anyone holding the seed
+	/// and an era's watch time can recompute the rotation.
+	pub fn select_featured(seed: &[u8], candidates: &[(u128, u64)], count: u32) -> Vec<u128> {
+		let mut candidates =
+			candidates.iter().filter(|(_, seconds)| *seconds > 0).collect::<Vec<_>>();
+		candidates.sort_by_key(|(stream_id, _)| *stream_id);
+
+		let mut featured = Vec::new();
+		for draw in 0..count {
+			let total =
+				candidates.iter().fold(0u64, |total, (_, seconds)| total.saturating_add(*seconds));
+			if total == 0 {
+				break
+			}
+			let mut preimage = seed.to_vec();
+			preimage.extend_from_slice(&draw.to_le_bytes());
+			let random = sp_io::hashing::blake2_256(&preimage);
+			let mut target =
+				u64::from_le_bytes(random[..8].try_into().expect("32 bytes; qed")) % total;
+
+			let index = candidates
+				.iter()
+				.position(|(_, seconds)| match target.checked_sub(*seconds) {
+					Some(rest) => {
+						target = rest;
+						false
+					},
+					None => true,
+				})
+				.unwrap_or(candidates.len() - 1);
+			featured.push(candidates.remove(index).0);
+		}
+		featured
+	}
+
+	/// Draw a giveaway's winner from `entrants`.
+	///
+	/// The winner is the entrant at `blake2_256(seed)`, read as a little-endian u64, modulo
+	/// the number of entrants, with entrants taken in ascending order. Anyone holding the seed
+	/// and the stream's active viewers at the draw can recompute it.
+	pub fn select_winner<AccountId: Ord + Clone>(
+		seed: &[u8],
+		entrants: &[AccountId],
+	) -> Option<AccountId> {
+		let mut entrants = entrants.to_vec();
+		entrants.sort();
+		let random = sp_io::hashing::blake2_256(seed);
+		let index = u64::from_le_bytes(random[..8].try_into().expect("32 bytes; qed")) %
+			(entrants.len() as u64).max(1);
+		entrants.get(index as usize).cloned()
+	}
+
+	/// Number of past eras for which failed-dispatch counters are kept.
+	pub const ERROR_COUNT_HISTORY: u32 = 7;
+
+	/// Most streams `list_streams` returns in one page.
+	pub const MAX_STREAMS_PER_PAGE: u32 = 100;
+
+	/// Prefix of the message a DID's key signs to link the DID to an account: the SCALE
+	/// encoding of this prefix, the account, the DID and the DID document's hash.
+	pub const DID_LINK_CONTEXT: &[u8] = b"vilokanam:did-link:";
+
+	/// Prefix of the message an attestation source signs to vouch for a viewer: the SCALE
+	/// encoding of this prefix, the viewer's account and the attestation.
+	pub const ATTESTATION_CONTEXT: &[u8] = b"vilokanam:attestation:";
+
+	/// The DID methods accounts can link to.
+	const DID_METHODS: [&[u8]; 2] = [b"did:key:", b"did:web:"];
+
+	/// Seconds in the UTC day a viewer's daily spending limit covers.
+	const SECONDS_PER_DAY: u64 = 86_400;
+
+	/// The keystore key type of tick reporters' keys.
+	pub const REPORTER_KEY_TYPE: KeyTypeId = KeyTypeId(*b"tick");
+
+	/// Offchain storage key of the lock held while the worker submits ticks.
+	const OCW_LOCK_KEY: &[u8] = b"tick-stream::ocw-lock";
+	/// Offchain storage key of the last block the worker submitted ticks for.
+	const OCW_LAST_RUN_KEY: &[u8] = b"tick-stream::ocw-last-run";
+	/// Persistent offchain storage key of the number of unsigned ticks the transaction pool
+	/// has refused from the worker, as a SCALE-encoded `u64`. Read by node metrics.
+	pub const OCW_REJECTED_TICKS_KEY: &[u8] = b"tick-stream::ocw-rejected-ticks";
+	/// Persistent offchain storage key of the last nonce the worker signed ticks with.
+	const OCW_REPORTER_NONCE_KEY: &[u8] = b"tick-stream::ocw-reporter-nonce";
+	/// Blocks after which an abandoned worker lock expires.
+	const OCW_LOCK_BLOCK_EXPIRATION: u32 = 3;
+	/// Milliseconds after which an abandoned worker lock expires.
+	const OCW_LOCK_TIMEOUT_MS: u64 = 10_000;
+
+	/// Identifies the inherent data of the ticks a block author found due, a SCALE-encoded
+	/// `Vec<(u128, AccountId, u32)>` of stream, viewer and seconds.
+	pub const INHERENT_IDENTIFIER: InherentIdentifier = *b"tickstrm";
+
+	pub type StreamOf<T> = Stream<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		BlockNumberFor<T>,
+		AssetIdOf<T>,
+	>;
+
+	/// Lifecycle state of a stream.
+	#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum StreamStatus {
+		/// The stream has been registered but has not gone live yet.
+		Created,
+		/// The stream is live and ticks are being settled.
+		Live,
+		/// The stream is temporarily off air; ticks are rejected.
+		Paused,
+		/// The stream is over; viewer reserves are refunded as it ends or swept afterwards.
+		Ended,
+	}
+
+	impl StreamStatus {
+		/// Whether the creator may move a stream from `self` to `next`.
+		pub fn can_transition_to(&self, next: StreamStatus) -> bool {
+			use StreamStatus::*;
+			matches!(
+				(self, next),
+				(Created, Live) | (Paused, Live) | (Live, Paused) | (Created, Ended) |
+					(Live, Ended) | (Paused, Ended)
+			)
+		}
+	}
+
+	/// Where a viewer's session on a stream stands.
+	#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum SessionState {
+		/// The viewer has joined and reserved watch time, and has not been charged yet.
+		Reserved,
+		/// The viewer is being charged for the watch time they reserved.
+		Active,
+		/// Ticks are stopped until the viewer resumes, as they reached a spending limit.
+		GracePaused,
+		/// The session can no longer be charged, as its reserve ran out or its stream ended,
+		/// and what is left of the reserve awaits its refund.
+		Expired,
+		/// The session is over and its reserve has been refunded. Settled sessions are
+		/// forgotten, so this state is only seen in `SessionStateChanged`.
+		Settled,
+	}
+
+	impl SessionState {
+		/// Whether a session may move from `self` to `next`.
+		pub fn can_transition_to(&self, next: SessionState) -> bool {
+			use SessionState::*;
+			matches!(
+				(self, next),
+				(Reserved, Active | GracePaused | Expired | Settled) |
+					(Active, GracePaused | Expired | Settled) |
+					(GracePaused, Active | Expired | Settled) |
+					(Expired, Settled)
+			)
+		}
+	}
+
+	/// What a stream plays, which decides how its viewers are billed.
+	#[derive(
+		Clone,
+		Copy,
+		Default,
+		Encode,
+		Decode,
+		Eq,
+		PartialEq,
+		RuntimeDebug,
+		TypeInfo,
+		MaxEncodedLen,
+	)]
+	pub enum ContentType {
+		/// A broadcast, billed by the ticks reporters submit as viewers watch.
+		#[default]
+		Live,
+		/// A recording, billed by the playback position each viewer reports, once for every
+		/// second of it they watch.
+		Vod,
+	}
+
+	/// Which extrinsics the runtime accepts, for storage migrations and incident response.
+	#[derive(
+		Clone,
+		Copy,
+		Default,
+		Encode,
+		Decode,
+		Eq,
+		PartialEq,
+		RuntimeDebug,
+		TypeInfo,
+		MaxEncodedLen,
+	)]
+	pub enum CallFilterMode {
+		/// Every call is accepted.
+		#[default]
+		Normal,
+		/// Balances are frozen; running streams keep settling and can be paused or ended,
+		/// and viewers can still leave and be refunded.
+		SettlementOnly,
+		/// Balances and settlement are frozen; streams can still be paused or ended, and
+		/// viewers can still leave and be refunded.
+		Frozen,
+	}
+
+	/// A pay-per-second stream.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Stream<AccountId, Balance, BlockNumber, AssetId> {
+		/// The account that created the stream and receives its earnings.
+		pub creator: AccountId,
+		/// The amount charged to a viewer for every second watched.
+		pub price_per_second: Balance,
+		/// The block in which the stream was last ticked.
+		pub last_tick: BlockNumber,
+		/// The current lifecycle state.
+		pub status: StreamStatus,
+		/// How many blocks the stream may stay on air after going live before it is ended.
+		pub max_duration: Option<BlockNumber>,
+		/// The asset the stream is priced and paid in, or `None` for the native token.
+		pub asset_id: Option<AssetId>,
+		/// The account the creator's tips and withdrawals are paid to, if not the creator.
+		pub payout_account: Option<AccountId>,
+		/// Whether the stream is broadcast live or played on demand.
+		pub content_type: ContentType,
+	}
+
+	/// A creator-signed ticket granting a viewer entry to a stream.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct JoinVoucher<AccountId, Balance, BlockNumber> {
+		/// The stream the voucher admits to.
+		pub stream_id: u128,
+		/// Unique per stream; a voucher can be redeemed only once.
+		pub nonce: u64,
+		/// The only account allowed to redeem the voucher, or anyone if `None`.
+		pub viewer: Option<AccountId>,
+		/// The per-second price charged to the holder; zero waives payment. `None` keeps the
+		/// stream price.
+		pub price_per_second: Option<Balance>,
+		/// Seconds of watch time reserved on redemption.
+		pub seconds: u32,
+		/// The last block in which the voucher can be redeemed.
+		pub expires_at: BlockNumber,
+	}
+
+	pub type JoinVoucherOf<T> =
+		JoinVoucher<<T as frame_system::Config>::AccountId, BalanceOf<T>, BlockNumberFor<T>>;
+
+	/// A creator's earnings over one era.
+	#[derive(
+		Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+	)]
+	pub struct EraEarnings<Balance> {
+		/// Seconds of watch time settled.
+		pub seconds: u64,
+		/// Total amount charged to viewers.
+		pub gross: Balance,
+	}
+
+	/// The value a creator has been paid over one era, in the native token and in fiat at the
+	/// rate of each settlement.
+	#[derive(
+		Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+	)]
+	pub struct SettledValue<Balance> {
+		/// Total paid, in the native token.
+		pub native: Balance,
+		/// Fiat value of the payments made while the oracle had a rate.
+		pub fiat: u128,
+		/// The part of `native` paid while the oracle had no rate.
+		pub unpriced: Balance,
+	}
+
+	/// What one stream has been paid over one era, in the currency it is priced in and in
+	/// fiat at the rate of each settlement.
+	#[derive(
+		Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+	)]
+	pub struct StreamEarnings<Balance> {
+		/// Seconds of watch time settled.
+		pub seconds: u64,
+		/// Total charged to viewers and subscribers, in the stream's currency.
+		pub gross: Balance,
+		/// Fiat value of the payments made while the oracle had a rate.
+		pub fiat: u128,
+		/// The part of `gross` paid while the oracle had no rate. The oracle prices the native
+		/// token only, so this is all of `gross` for streams priced in an asset.
+		pub unpriced: Balance,
+	}
+
+	/// The unit a creator has a stream's earnings reported in.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum ReportingUnit<AssetId> {
+		/// The native token.
+		Native,
+		/// An asset streams can be priced in.
+		Asset(AssetId),
+		/// The oracle's fiat currency, in its smallest unit, at the rate of each settlement.
+		Fiat,
+	}
+
+	pub type ReportingUnitOf<T> = ReportingUnit<AssetIdOf<T>>;
+
+	/// A viewer's budget pooled across all of one creator's streams.
+	#[derive(
+		Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+	)]
+	pub struct CreatorBudget<Balance> {
+		/// The balance reserved for any of the creator's streams.
+		pub reserved: Balance,
+		/// Total charged for the creator's streams, from stream reserves and the budget alike.
+		pub spent: Balance,
+	}
+
+	/// A viewer's lifetime watch history on one stream, kept across sessions.
+	#[derive(
+		Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+	)]
+	pub struct WatchStats<Balance, BlockNumber> {
+		/// Seconds of the stream the viewer has been charged for.
+		pub total_seconds: u64,
+		/// Total amount the viewer has been charged for the stream.
+		pub total_spent: Balance,
+		/// The block the viewer was last charged in.
+		pub last_watched_block: BlockNumber,
+	}
+
+	pub type WatchStatsOf<T> = WatchStats<BalanceOf<T>, BlockNumberFor<T>>;
+
+	/// A flat-rate pass to a stream, sold instead of metering watch time.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct SubscriptionTier<Balance, BlockNumber> {
+		/// The price of one pass.
+		pub price: Balance,
+		/// The blocks one pass lasts for.
+		pub duration_blocks: BlockNumber,
+	}
+
+	pub type SubscriptionTierOf<T> = SubscriptionTier<BalanceOf<T>, BlockNumberFor<T>>;
+
+	/// Where an account's earnings above a threshold are forwarded, to keep them out of a hot
+	/// wallet.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct SweepPolicy<AccountId, Balance> {
+		/// The most of a stream's earnings paid to the account itself.
+		pub threshold: Balance,
+		/// The account everything above the threshold is forwarded to.
+		pub cold_address: AccountId,
+	}
+
+	pub type SweepPolicyOf<T> = SweepPolicy<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+	/// The share of an account's withdrawals swapped into a stable asset as they are paid out,
+	/// so less of its earnings ride on the native token's price.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct HedgePolicy<AssetId> {
+		/// The share of each withdrawal swapped.
+		pub hedge_percent: Percent,
+		/// The asset it is swapped into.
+		pub asset: AssetId,
+		/// How far below the quoted rate a swap may fill before it is skipped.
+		pub max_slippage: Permill,
+	}
+
+	pub type HedgePolicyOf<T> = HedgePolicy<AssetIdOf<T>>;
+
+	/// Where a poll stands.
+	#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum PollStatus {
+		/// Taking votes until its end block.
+		Open,
+		/// Closed, with its tallies final. The winner is the option with the most weight, or
+		/// `None` on a tie or if nobody voted.
+		Finalized { winner: Option<u8> },
+	}
+
+	/// A creator's poll of their viewers, such as which game to play next.
+	///
+	/// Each vote weighs the voter's watch time on the creator's streams over the era it is
+	/// cast in and the `lookback_eras` eras before it.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Poll<AccountId, BlockNumber, Text, Options, Tallies> {
+		/// The account whose viewers vote.
+		pub creator: AccountId,
+		pub question: Text,
+		pub options: Options,
+		/// The seconds of watch time behind each option.
+		pub tallies: Tallies,
+		/// The eras before a vote's whose watch time counts towards it.
+		pub lookback_eras: u32,
+		/// The block the poll closes in.
+		pub ends_at: BlockNumber,
+		pub status: PollStatus,
+	}
+
+	/// A giveaway drawn among a stream's active viewers, kept so anyone can check the draw.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Giveaway<AccountId, Balance, BlockNumber, Hash> {
+		pub stream_id: u128,
+		pub winner: AccountId,
+		pub prize: Balance,
+		/// The seed the winner was drawn with.
+		pub seed: Hash,
+		/// The hash of the SCALE-encoded entrants, in ascending order.
+		pub entrants_hash: Hash,
+		/// The number of viewers in the draw.
+		pub entrants: u32,
+		/// The block the giveaway was drawn in.
+		pub drawn_at: BlockNumber,
+	}
+
+	pub type GiveawayOf<T> = Giveaway<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		BlockNumberFor<T>,
+		<T as frame_system::Config>::Hash,
+	>;
+
+	pub type PollTextOf<T> = BoundedVec<u8, <T as Config>::MaxPollTextLen>;
+	pub type PollOf<T> = Poll<
+		<T as frame_system::Config>::AccountId,
+		BlockNumberFor<T>,
+		PollTextOf<T>,
+		BoundedVec<PollTextOf<T>, <T as Config>::MaxPollOptions>,
+		BoundedVec<u64, <T as Config>::MaxPollOptions>,
+	>;
+
+	/// What happens to earnings withdrawn to an empty account that are too little to open
+	/// it with.
+	#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum DustPolicy {
+		/// They stay in escrow until they are enough.
+		Accumulate,
+		/// They are donated to the platform account.
+		Donate,
+		/// They are kept by the platform account as credit towards the next stream the
+		/// account joins. Earnings in an asset accumulate instead.
+		Credit,
+	}
+
+	/// Why funds in the native token are on hold.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// Viewers' reserves, seat bookings, budgets and payments awaiting settlement or a
+		/// dispute's resolution.
+		StreamDeposit,
+		/// The deposit for a handle.
+		HandleDeposit,
+		/// A creator's bond, slashed to viewers whose disputes are upheld.
+		DisputeBond,
+		/// The bond of a registered tick reporter.
+		ReporterBond,
+		/// The part of a creator's onboarding grant that has yet to vest.
+		CreatorGrant,
+	}
+
+	/// A call superseded by a versioned call with a newer signature, kept working so
+	/// integrators can move over between runtime upgrades.
+	#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum LegacyCall {
+		/// `join_stream`, superseded by `join_stream_v2`.
+		JoinStream,
+	}
+
+	/// A discount on passes to a stream bought as gifts in bulk.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct BulkDiscount {
+		/// The fewest passes bought at once that the discount applies to.
+		pub min_passes: u32,
+		/// The discount off the price of every pass.
+		pub discount: Permill,
+	}
+
+	/// An account's link to an external decentralized identifier.
+	///
+	/// The chain does not resolve DIDs, so it cannot check the signature itself; sites
+	/// verifying a creator's identity resolve the DID document, check it against
+	/// `document_hash` and verify `signature` over the payload of `did_link_payload`.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct DidLink<Did, BlockNumber> {
+		/// The DID, such as `did:key:z6Mk...` or `did:web:example.com`.
+		pub did: Did,
+		/// The hash of the DID document at the time of linking.
+		pub document_hash: [u8; 32],
+		/// The signature of the DID's key over the link.
+		pub signature: [u8; 64],
+		/// The block the link was made in.
+		pub linked_at: BlockNumber,
+	}
+
+	pub type DidOf<T> = BoundedVec<u8, <T as Config>::MaxDidLen>;
+	pub type DidLinkOf<T> = DidLink<DidOf<T>, BlockNumberFor<T>>;
+
+	/// A co-host's earnings from a stream that are released linearly over time.
+	///
+	/// Every new payment is added to what is still unvested, and the total is then released
+	/// evenly over the co-host's vesting period starting from that payment.
+	#[derive(
+		Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+	)]
+	pub struct VestingSchedule<Balance, BlockNumber> {
+		/// Earnings not yet released.
+		pub unvested: Balance,
+		/// Earnings released and not yet claimed.
+		pub claimable: Balance,
+		/// Earnings released and claimed so far.
+		pub claimed: Balance,
+		/// The block `unvested` was last released up to.
+		pub updated_at: BlockNumber,
+		/// The block by which all of `unvested` is released.
+		pub ends_at: BlockNumber,
+	}
+
+	pub type VestingScheduleOf<T> = VestingSchedule<BalanceOf<T>, BlockNumberFor<T>>;
+
+	/// What a viewer must meet to join a stream, set by its creator to keep bots out.
+	#[derive(
+		Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+	)]
+	pub struct JoinGate<BlockNumber> {
+		/// Blocks the viewer's account must have existed for.
+		pub min_account_age: BlockNumber,
+		/// Seconds the viewer must have watched across every stream.
+		pub min_watch_seconds: u64,
+		/// Whether the viewer needs a verified identity.
+		pub require_identity: bool,
+	}
+
+	/// Who may join a stream.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum AccessPolicy<AssetId, Balance> {
+		/// Anyone.
+		Public,
+		/// Only the accounts on the stream's allowlist.
+		Allowlist,
+		/// Only accounts holding at least `min_balance` of `asset_id`.
+		TokenGated { asset_id: AssetId, min_balance: Balance },
+	}
+
+	pub type AccessPolicyOf<T> = AccessPolicy<AssetIdOf<T>, BalanceOf<T>>;
+
+	/// An onboarding grant to a new creator, held on their account and released tranche by
+	/// tranche as they reach its milestones.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct CreatorGrant<Balance, BlockNumber> {
+		/// What is released once the creator has taken `streams_target` streams live, or zero
+		/// once it has been.
+		pub streams_tranche: Balance,
+		/// How many streams the creator must take live.
+		pub streams_target: u32,
+		/// What is released once viewers have paid for `viewer_hours_target` hours of the
+		/// creator's streams, or zero once it has been.
+		pub viewer_hours_tranche: Balance,
+		/// How many paid viewer-hours the creator must reach.
+		pub viewer_hours_target: u32,
+		/// Streams the creator has taken live since the grant was approved.
+		pub streams: u32,
+		/// Seconds viewers have paid for on the creator's streams since the grant was approved.
+		pub paid_seconds: u64,
+		/// The block what has not vested by is clawed back.
+		pub deadline: BlockNumber,
+	}
+
+	pub type CreatorGrantOf<T> = CreatorGrant<BalanceOf<T>, BlockNumberFor<T>>;
+
+	/// A milestone of a creator grant.
+	#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum GrantMilestone {
+		/// Taking the target number of streams live.
+		Streams,
+		/// Reaching the target number of paid viewer-hours.
+		ViewerHours,
+	}
+
+	/// A chain viewers' reputation can be imported from, such as a partner parachain.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct AttestationSource<AccountId> {
+		/// The account whose key signs the chain's attestations, usually its bridge's relayer.
+		pub signer: AccountId,
+		/// The lowest verification level on the chain that join gates take as a verified
+		/// identity; never zero.
+		pub identity_level: u8,
+	}
+
+	pub type AttestationSourceOf<T> = AttestationSource<<T as frame_system::Config>::AccountId>;
+
+	/// What a source chain vouches for about one of its viewers.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct ViewerAttestation<BlockNumber> {
+		/// The id of the chain that attested it.
+		pub source: u32,
+		/// Seconds the viewer has watched on the source chain.
+		pub watch_seconds: u64,
+		/// How far the source chain has verified the viewer's identity, zero for not at all.
+		pub verification_level: u8,
+		/// Grows with every attestation the source makes about the viewer, so an older one
+		/// cannot replace a newer one.
+		pub nonce: u64,
+		/// The block from which the attestation no longer counts.
+		pub expires_at: BlockNumber,
+	}
+
+	pub type ViewerAttestationOf<T> = ViewerAttestation<BlockNumberFor<T>>;
+
+	/// Where a stream's media was at a block, anchored by its sync host for watch parties.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct PositionAnchor<BlockNumber> {
+		/// The block the position was anchored in.
+		pub block: BlockNumber,
+		/// The media position at that block, in milliseconds from the start.
+		pub media_timestamp: u64,
+	}
+
+	/// A handle claimed by a creator for one of their streams.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct HandleClaim<AccountId, Balance, Handle> {
+		/// The handle as claimed; it is unique regardless of case.
+		pub handle: Handle,
+		/// The creator who claimed it.
+		pub owner: AccountId,
+		/// The stream it names.
+		pub stream_id: u128,
+		/// The deposit reserved on the owner until the handle is released.
+		pub deposit: Balance,
+	}
+
+	pub type HandleOf<T> = BoundedVec<u8, <T as Config>::MaxHandleLen>;
+
+	/// The most a viewer lets themselves be charged before their sessions are paused.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct SpendingLimit<Balance> {
+		/// The most charged for one session on a stream; `None` for no limit.
+		pub max_per_stream: Option<Balance>,
+		/// The most charged across all streams in a UTC day; `None` for no limit.
+		pub max_per_day: Option<Balance>,
+	}
+	pub type HandleClaimOf<T> =
+		HandleClaim<<T as frame_system::Config>::AccountId, BalanceOf<T>, HandleOf<T>>;
+
+	/// A tick payment held back until the viewer can no longer dispute it.
+	///
+	/// Ticks are numbered by the viewer's watched seconds on the stream, so the payment covers
+	/// ticks `from_tick` up to `to_tick`.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct PendingSettlement<Balance, BlockNumber> {
+		/// The viewer's watched seconds before the payment.
+		pub from_tick: u64,
+		/// The viewer's watched seconds after the payment.
+		pub to_tick: u64,
+		/// The amount still reserved on the viewer.
+		pub amount: Balance,
+		/// The block the payment is made in, unless it is disputed first.
+		pub due_at: BlockNumber,
+	}
+
+	pub type PendingSettlementOf<T> = PendingSettlement<BalanceOf<T>, BlockNumberFor<T>>;
+
+	/// Tick payments a viewer has disputed, frozen until the admin origin resolves them.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Dispute<AccountId, Balance> {
+		pub stream_id: u128,
+		/// The viewer the disputed amount is reserved on.
+		pub viewer: AccountId,
+		/// The creator whose bond backs the disputed ticks.
+		pub reporter: AccountId,
+		/// The total of the disputed payments.
+		pub amount: Balance,
+		pub from_tick: u64,
+		pub to_tick: u64,
+	}
+
+	pub type DisputeOf<T> = Dispute<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+	/// The bond a creator holds against disputes of their streams' ticks.
+	#[derive(
+		Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+	)]
+	pub struct ReporterStake<Balance, BlockNumber> {
+		/// The amount reserved.
+		pub bonded: Balance,
+		/// The last block a payment for one of the creator's streams was held in.
+		pub last_reported: BlockNumber,
+	}
+
+	pub type ReporterStakeOf<T> = ReporterStake<BalanceOf<T>, BlockNumberFor<T>>;
+
+	/// An account allowed to report ticks, against a bond.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct TickReporter<Balance, BlockNumber> {
+		/// The amount reserved.
+		pub bond: Balance,
+		/// The highest nonce of the reporter's ticks recorded so far.
+		pub nonce: u64,
+		/// The block the bond can be returned from, once the reporter has deregistered.
+		pub unbonds_at: Option<BlockNumber>,
+	}
+
+	pub type TickReporterOf<T> = TickReporter<BalanceOf<T>, BlockNumberFor<T>>;
+
+	/// What a stream is about.
+	#[derive(
+		Clone,
+		Copy,
+		Encode,
+		Decode,
+		Eq,
+		PartialEq,
+		RuntimeDebug,
+		TypeInfo,
+		MaxEncodedLen,
+		Serialize,
+		Deserialize,
+	)]
+	pub enum StreamCategory {
+		Gaming,
+		Music,
+		Education,
+		Talk,
+		Sports,
+		Creative,
+		Other,
+	}
+
+	/// Descriptive information about a stream, set by its creator.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct StreamInfo<BlockNumber> {
+		/// The stream title, at most `MaxMetadataLen` bytes.
+		pub title: Vec<u8>,
+		/// What the stream is about.
+		pub category: StreamCategory,
+		/// The IPFS CID of the stream's content or description, at most `MaxMetadataLen` bytes.
+		pub content_cid: Vec<u8>,
+		/// The block the metadata was first set in.
+		pub created_at: BlockNumber,
+	}
+
+	/// A stream created at genesis, so devnets and testnets start with streams to watch.
+	#[derive(Clone, Eq, PartialEq, RuntimeDebug, Serialize, Deserialize)]
+	pub struct GenesisStream<AccountId, Balance> {
+		pub creator: AccountId,
+		pub price_per_second: Balance,
+		/// The stream's title, category and content CID, if it has metadata.
+		pub metadata: Option<(Vec<u8>, StreamCategory, Vec<u8>)>,
+	}
+
+	/// Everything a frontend needs to render a stream.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct StreamDetails<AccountId, Balance, BlockNumber, AssetId> {
+		pub stream: Stream<AccountId, Balance, BlockNumber, AssetId>,
+		pub metadata: Option<StreamInfo<BlockNumber>>,
+		/// The number of viewers currently watching.
+		pub viewers: u32,
+		pub tick_count: u32,
+		/// The handle the stream goes by, as its creator claimed it.
+		pub handle: Option<Vec<u8>>,
+		/// The platform fee taken from the stream's payments, after any category override.
+		pub platform_fee: Permill,
+		/// Who may join the stream.
+		pub access_policy: AccessPolicy<AssetId, Balance>,
+		/// The unit the stream's earnings are reported in.
+		pub reporting_unit: ReportingUnit<AssetId>,
+	}
+
+	/// Which streams `list_streams` returns; every field left `None` matches any stream.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct StreamFilter<AccountId> {
+		/// Only streams in this lifecycle state.
+		pub status: Option<StreamStatus>,
+		/// Only streams created by this account.
+		pub creator: Option<AccountId>,
+	}
+
+	/// A stream as listed in a catalog.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct StreamSummary<AccountId, Balance> {
+		pub stream_id: u128,
+		pub creator: AccountId,
+		pub price_per_second: Balance,
+		pub status: StreamStatus,
+		/// The number of viewers currently watching.
+		pub viewer_count: u32,
+	}
+
+	/// A scheduled stream in a viewer's feed of what is coming up.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct UpcomingStream<AccountId, BlockNumber> {
+		pub stream_id: u128,
+		pub creator: AccountId,
+		/// The stream's title, if its creator has set one.
+		pub title: Option<Vec<u8>>,
+		/// When the stream is planned to go live.
+		pub schedule: StreamSchedule<BlockNumber>,
+	}
+
+	/// A discount a creator offers on a stream.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Promo {
+		/// The share of the per-second price waived.
+		pub discount: Permill,
+		/// How many more times the code can be redeemed.
+		pub remaining_uses: u32,
+	}
+
+	/// When a stream is planned to go live.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct StreamSchedule<BlockNumber> {
+		/// The start time in unix seconds, UTC.
+		pub starts_at: u64,
+		/// The creator's offset from UTC in minutes, for showing the start in local time.
+		pub utc_offset_minutes: i16,
+		/// The block the stream is expected to go live in, re-estimated as blocks drift.
+		pub estimated_block: BlockNumber,
+	}
+
+	/// A tip sent to a creator during a stream.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct Tip<AccountId, Balance, BlockNumber> {
+		/// The account that sent the tip.
+		pub tipper: AccountId,
+		/// The full amount tipped, before the platform fee.
+		pub amount: Balance,
+		/// The message attached to the tip, shown with it on the stream.
+		pub message: Option<Vec<u8>>,
+		/// The block the tip was sent in.
+		pub block: BlockNumber,
+	}
+
+	pub type TipOf<T> =
+		Tip<<T as frame_system::Config>::AccountId, BalanceOf<T>, BlockNumberFor<T>>;
+
+	/// How much state one storage item of the pallet holds.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct StorageFootprint {
+		/// The storage item's name.
+		pub name: Vec<u8>,
+		/// The number of entries stored.
+		pub entries: u32,
+		/// The size of all keys and values stored, in bytes.
+		pub bytes: u64,
+	}
+
+	/// A viewer's standing on a stream they are watching.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct ViewerSession<Balance> {
+		/// The balance still reserved for the stream.
+		pub reserved: Balance,
+		/// Seconds of the stream the viewer has been charged for.
+		pub seconds_watched: u64,
+		/// Seconds the reserve covers at the viewer's price; `None` if they watch for free.
+		pub seconds_remaining: Option<u64>,
+		/// Whether ticks are stopped until the viewer resumes, as they reached a spending limit.
+		pub paused: bool,
+		/// Seconds still to be watched for free, under the stream's preview or the grace at
+		/// the start of a session.
+		pub free_seconds_left: u32,
+	}
+
+	/// What the pallet holds in the native token, against its cap.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct HoldUtilization<Balance> {
+		/// The total held across all accounts.
+		pub held: Balance,
+		/// The most that may be held, if capped.
+		pub cap: Option<Balance>,
+	}
+
+	/// Whether ticks are being settled, for monitoring.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct SettlementHealth<BlockNumber> {
+		/// The block ticks were last settled in.
+		pub last_settled: BlockNumber,
+		/// Whether settlement has been reported stalled.
+		pub stalled: bool,
+	}
+
+	/// A stream's earnings over one era, in the unit its creator reports in.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct StreamReport<Balance, AssetId> {
+		/// The unit `gross` is in.
+		pub unit: ReportingUnit<AssetId>,
+		/// Seconds of watch time settled.
+		pub seconds: u64,
+		/// Total charged to viewers and subscribers, in the smallest denomination of `unit`.
+		pub gross: u128,
+		/// In fiat reports, what was paid while the oracle had no rate and is left out of
+		/// `gross`, in the stream's own currency; zero otherwise.
+		pub unpriced: Balance,
+	}
+
+	pub type StreamReportOf<T> = StreamReport<BalanceOf<T>, AssetIdOf<T>>;
+
+	/// One of the pallet's parameters: a constant of its config, or a value governance sets.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct ConfigParameter {
+		/// The constant's or storage item's name.
+		pub name: Vec<u8>,
+		/// Its value, SCALE encoded.
+		pub value: Vec<u8>,
+	}
+
+	/// The pallet's parameters, with a hash that changes whenever any of them does.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct ConfigFingerprint {
+		/// The blake2-256 hash of the encoded `parameters`.
+		pub hash: [u8; 32],
+		/// Every parameter, sorted by name.
+		pub parameters: Vec<ConfigParameter>,
+	}
+
+	/// Notify when a followed stream goes live.
+	pub const NOTIFY_STREAM_LIVE: u32 = 1 << 0;
+	/// Notify when a followed stream ends.
+	pub const NOTIFY_STREAM_ENDED: u32 = 1 << 1;
+	/// Notify when the reserve for a stream being watched runs low.
+	pub const NOTIFY_LOW_BALANCE: u32 = 1 << 2;
+	/// Notify when earnings are credited or withdrawn.
+	pub const NOTIFY_EARNINGS: u32 = 1 << 3;
+	/// Every notification event type.
+	pub const NOTIFY_ALL: u32 =
+		NOTIFY_STREAM_LIVE | NOTIFY_STREAM_ENDED | NOTIFY_LOW_BALANCE | NOTIFY_EARNINGS;
+
+	/// What an account wants relayers to notify it about.
+	#[derive(Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct NotificationPrefs {
+		/// The streams the account follows.
+		pub followed: Vec<u128>,
+		/// The `NOTIFY_*` event types the account wants pushed.
+		pub events: u32,
+	}
+
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(11);
+
+	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The currency viewers pay creators in. Only reserves made before deposits were held
+		/// with `NativeBalance` are still read from it, to convert them to holds.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Places deposits and bonds in the native token on hold, under a `HoldReason`. Must
+		/// be the same token as `Currency`.
+		type NativeBalance: fungible::MutateHold<
+			Self::AccountId,
+			Reason = Self::RuntimeHoldReason,
+			Balance = BalanceOf<Self>,
+		>;
+
+		/// The overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
+
+		/// The number of blocks in an era, the period over which statistics are aggregated.
+		#[pallet::constant]
+		type EraLength: Get<BlockNumberFor<Self>>;
+
+		/// The maximum number of viewers that can be settled by a single `batch_tick`.
+		#[pallet::constant]
+		type MaxViewersPerBatch: Get<u32>;
+
+		/// Wall-clock time, used to bound ticks by the seconds that actually elapsed.
+		type UnixTime: UnixTime;
+
+		/// The minimum number of blocks between two ticks for the same viewer.
+		#[pallet::constant]
+		type TickThreshold: Get<BlockNumberFor<Self>>;
+
+		/// The priority of unsigned tick transactions.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+
+		/// Off-chain signature type creators sign join vouchers with.
+		type OffchainSignature: Verify<Signer = Self::OffchainPublic> + Parameter;
+
+		/// The public key that verifies an `OffchainSignature`.
+		type OffchainPublic: IdentifyAccount<AccountId = Self::AccountId> + Clone;
+
+		/// The pallet's id, used to derive the account that collects the platform fee.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The maximum number of co-hosts a stream's revenue can be split with.
+		#[pallet::constant]
+		type MaxCoHosts: Get<u32>;
+
+		/// The maximum number of streams an account can follow for notifications.
+		#[pallet::constant]
+		type MaxFollowedStreams: Get<u32>;
+
+		/// The maximum length in bytes of a stream's title and content CID.
+		#[pallet::constant]
+		type MaxMetadataLen: Get<u32>;
+
+		/// The maximum number of viewers refunded when a stream ends; the rest are left for
+		/// `sweep_expired`.
+		#[pallet::constant]
+		type MaxRefundsOnEnd: Get<u32>;
+
+		/// The number of blocks after a stream ends before its leftover reservations can be
+		/// swept.
+		#[pallet::constant]
+		type SettlementWindow: Get<BlockNumberFor<Self>>;
+
+		/// The reward paid from the platform account for every reservation swept.
+		#[pallet::constant]
+		type SweepReward: Get<BalanceOf<Self>>;
+
+		/// The maximum length in bytes of a promo code.
+		#[pallet::constant]
+		type MaxPromoCodeLen: Get<u32>;
+
+		/// The number of blocks between a creator scheduling a new price and it taking effect.
+		#[pallet::constant]
+		type PriceChangeDelay: Get<BlockNumberFor<Self>>;
+
+		/// Source of the seed the featured streams of each era and giveaway winners are drawn
+		/// with.
+		type Randomness: Randomness<Self::Hash, BlockNumberFor<Self>>;
+
+		/// The number of streams featured per era.
+		#[pallet::constant]
+		type FeaturedStreams: Get<u32>;
+
+		/// Viewers are warned with `LowBalanceWarning` once their reserve covers fewer seconds
+		/// than this, so wallets can prompt a top-up before playback stops.
+		#[pallet::constant]
+		type LowBalanceSeconds: Get<u32>;
+
+		/// The longest message that can be attached to a tip.
+		#[pallet::constant]
+		type MaxTipMessageLen: Get<u32>;
+
+		/// The most tips kept per live stream. Older tips are dropped as new ones arrive, and
+		/// are only left in `TipReceived` events.
+		#[pallet::constant]
+		type MaxTipsPerStream: Get<u32>;
+
+		/// The milliseconds between blocks the chain aims for, used to estimate the block a
+		/// scheduled stream goes live in.
+		#[pallet::constant]
+		type ExpectedBlockTime: Get<u64>;
+
+		/// Blocks after a viewer last watched a stream that anyone may prune their stats for it.
+		#[pallet::constant]
+		type ViewerStatsRetention: Get<BlockNumberFor<Self>>;
+
+		/// The maximum number of subscription tiers a stream can offer.
+		#[pallet::constant]
+		type MaxSubscriptionTiers: Get<u32>;
+
+		/// Values settlements in fiat as they happen, for reports that hold their value.
+		type FiatOracle: FiatOracle<BalanceOf<Self>>;
+
+		/// Blocks a tick payment is held before it is paid out, during which the viewer can
+		/// dispute it. Zero pays ticks out as they are recorded.
+		#[pallet::constant]
+		type SettlementDelay: Get<BlockNumberFor<Self>>;
+
+		/// The bond a creator reserves to start streams, slashed to a viewer whose dispute of
+		/// their ticks is upheld.
+		#[pallet::constant]
+		type ReporterBond: Get<BalanceOf<Self>>;
+
+		/// The fungible assets streams can be priced in instead of the native token.
+		type Assets: fungibles::Mutate<Self::AccountId, Balance = BalanceOf<Self>>;
+
+		/// The longest account age a stream's join gate can require.
+		#[pallet::constant]
+		type MaxGateAccountAge: Get<BlockNumberFor<Self>>;
+
+		/// The most watch time a stream's join gate can require, in seconds.
+		#[pallet::constant]
+		type MaxGateWatchSeconds: Get<u64>;
+
+		/// Checks the identity of viewers joining streams that require one.
+		type IdentityVerifier: IdentityVerifier<Self::AccountId>;
+
+		/// Receives each era's watch time per viewer and stream once the era is over.
+		type OnWatchTime: OnWatchTime<Self::AccountId>;
+
+		/// The most watch time records handed to `OnWatchTime` in one block.
+		#[pallet::constant]
+		type WatchTimeBatchSize: Get<u32>;
+
+		/// The number of recent position anchors kept per stream.
+		#[pallet::constant]
+		type MaxPositionAnchors: Get<u32>;
+
+		/// The most ticks settled in one block; ticks beyond it are queued for later blocks.
+		#[pallet::constant]
+		type MaxSettlementsPerBlock: Get<u32>;
+
+		/// The blocks without a settlement, while viewers watch live streams, after which
+		/// settlement is reported stalled.
+		#[pallet::constant]
+		type AlertAfterBlocks: Get<BlockNumberFor<Self>>;
+
+		/// The longest handle a stream can claim, in bytes.
+		#[pallet::constant]
+		type MaxHandleLen: Get<u32>;
+
+		/// The deposit a creator reserves for each handle they hold.
+		#[pallet::constant]
+		type HandleDeposit: Get<BalanceOf<Self>>;
+
+		/// The most devices one account can watch a stream on at once, even when its creator
+		/// exempts the account from the stream's device limit.
+		#[pallet::constant]
+		type MaxDevicesPerViewer: Get<u32>;
+
+		/// The share of each tick payment by a referred viewer that goes to their referrer
+		/// instead of the creator.
+		#[pallet::constant]
+		type ReferralShare: Get<Permill>;
+
+		/// The deposit a viewer reserves to book a seat on a scheduled stream, which becomes
+		/// their reserve for the stream once it goes live.
+		#[pallet::constant]
+		type PreBookingDeposit: Get<BalanceOf<Self>>;
+
+		/// The most seats that can be booked on one scheduled stream.
+		#[pallet::constant]
+		type MaxPreBookings: Get<u32>;
+
+		/// The blocks a scheduled stream has to go live after its estimated start before its
+		/// bookings are cancelled and their deposits refunded.
+		#[pallet::constant]
+		type PreBookingGracePeriod: Get<BlockNumberFor<Self>>;
+
+		/// The most accounts a subscription pass can be gifted to at once.
+		#[pallet::constant]
+		type MaxGiftRecipients: Get<u32>;
+
+		/// The longest DID an account can link to, in bytes.
+		#[pallet::constant]
+		type MaxDidLen: Get<u32>;
+
+		/// The bond an account reserves to report ticks.
+		#[pallet::constant]
+		type TickReporterBond: Get<BalanceOf<Self>>;
+
+		/// The blocks a deregistered tick reporter waits for its bond.
+		#[pallet::constant]
+		type ReporterCooldown: Get<BlockNumberFor<Self>>;
+
+		/// The most chains viewer attestations can be imported from.
+		#[pallet::constant]
+		type MaxAttestationSources: Get<u32>;
+
+		/// The most options a poll can offer.
+		#[pallet::constant]
+		type MaxPollOptions: Get<u32>;
+
+		/// The longest poll question or option, in bytes.
+		#[pallet::constant]
+		type MaxPollTextLen: Get<u32>;
+
+		/// The most eras before a vote whose watch time can weigh it, and so the eras each
+		/// viewer's watch time with each creator is kept for.
+		#[pallet::constant]
+		type MaxPollLookbackEras: Get<u32>;
+
+		/// The seconds at the start of each viewing session that are not charged for, covering
+		/// the time between joining and the player starting.
+		#[pallet::constant]
+		type FirstChargeGraceSeconds: Get<u32>;
+
+		/// Signs the offchain worker's ticks with the node's reporter keys.
+		type TickSigner: TickSigner<Self::OffchainPublic, Self::OffchainSignature>;
+
+		/// Runs the runtime's own rules on every tick settlement before funds move; `()` for
+		/// none.
+		type SettlementExtension: SettlementExtension<Self::AccountId, BalanceOf<Self>>;
+
+		/// Swaps the hedged share of withdrawals into stable assets; `()` if the chain cannot.
+		type PayoutSwap: PayoutSwap<Self::AccountId, AssetIdOf<Self>, BalanceOf<Self>>;
+
+		/// The origin allowed to freeze creators' payouts while suspected fraud is
+		/// investigated, such as a finance council.
+		type PayoutFreezeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The origin allowed to change platform parameters and revoke viewers' access.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Weight information for the extrinsics of this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Signs vouchers and sets the clock for the benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<
+			Self::OffchainPublic,
+			Self::OffchainSignature,
+			AssetIdOf<Self>,
+		>;
+	}
+
+	/// Stores the stream details for each stream
+	#[pallet::storage]
+	#[pallet::getter(fn streams)]
+	pub type Streams<T: Config> = StorageMap<_, Blake2_128Concat, u128, StreamOf<T>, OptionQuery>;
+
+	/// Stores the total balance below which a viewer leaves every stream they are settled on
+	#[pallet::storage]
+	#[pallet::getter(fn auto_leave_threshold)]
+	pub type AutoLeaveThresholds<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, OptionQuery>;
+
+	/// Stores the planned start of each scheduled stream until it goes live
+	#[pallet::storage]
+	#[pallet::getter(fn stream_schedule)]
+	pub type StreamSchedules<T: Config> =
+		StorageMap<_, Blake2_128Concat, u128, StreamSchedule<BlockNumberFor<T>>, OptionQuery>;
+
+	/// Stores the streams expected to go live at each block
+	#[pallet::storage]
+	pub type ScheduledStartsDue<T: Config> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<u128>, ValueQuery>;
+
+	/// Stores the latest tips sent during each live stream, until it ends
+	#[pallet::storage]
+	#[pallet::getter(fn tips)]
+	pub type Tips<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		u128,
+		BoundedVec<TipOf<T>, T::MaxTipsPerStream>,
+		ValueQuery,
+	>;
+
+	/// Stores the block each ended stream ended in
+	#[pallet::storage]
+	#[pallet::getter(fn ended_at)]
+	pub type EndedAt<T: Config> = StorageMap<_, Blake2_128Concat, u128, BlockNumberFor<T>, OptionQuery>;
+
+	/// Stores each stream's promo codes, keyed by the hash of the code
+	#[pallet::storage]
+	#[pallet::getter(fn promos)]
+	pub type Promos<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, u128, Identity, T::Hash, Promo, OptionQuery>;
+
+	/// Stores each viewer's pending promo commitment and the block it was made in
+	#[pallet::storage]
+	#[pallet::getter(fn promo_commitments)]
+	pub type PromoCommitments<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u128,
+		Blake2_128Concat,
+		T::AccountId,
+		(T::Hash, BlockNumberFor<T>),
+		OptionQuery,
+	>;
+
+	/// Stores the descriptive metadata of each stream
+	#[pallet::storage]
+	#[pallet::getter(fn stream_metadata)]
+	pub type StreamMetadata<T: Config> =
+		StorageMap<_, Blake2_128Concat, u128, StreamInfo<BlockNumberFor<T>>, OptionQuery>;
+
+	/// The id the next created stream will get
+	#[pallet::storage]
+	#[pallet::getter(fn next_stream_id)]
+	pub type NextStreamId<T: Config> = StorageValue<_, u128, ValueQuery>;
+
+	/// Stores the tick count for each stream
+	#[pallet::storage]
+	#[pallet::getter(fn tick_count)]
+	pub type TickCount<T: Config> = StorageMap<_, Blake2_128Concat, u128, u32, ValueQuery>;
+
+	/// Stores the viewers for each stream
+	#[pallet::storage]
+	#[pallet::getter(fn stream_viewers)]
+	pub type StreamViewers<T: Config> = StorageMap<_, Blake2_128Concat, u128, Vec<T::AccountId>, ValueQuery>;
+
+	/// Stores the balance each viewer still has reserved for a stream
+	#[pallet::storage]
+	#[pallet::getter(fn balances)]
+	pub type Balances<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u128,
+		Blake2_128Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// Stores the block and unix time (in seconds) each viewer was last ticked at
+	#[pallet::storage]
+	#[pallet::getter(fn viewer_last_tick)]
+	pub type ViewerLastTick<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u128,
+		Blake2_128Concat,
+		T::AccountId,
+		(BlockNumberFor<T>, u64),
+		OptionQuery,
+	>;
+
+	/// Stores the seconds of each stream each viewer has been charged for
+	#[pallet::storage]
+	#[pallet::getter(fn watched_seconds)]
+	pub type WatchedSeconds<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u128,
+		Blake2_128Concat,
+		T::AccountId,
+		u64,
+		ValueQuery,
+	>;
+
+	/// Stores the per-second price a viewer joined at when it differs from the stream's price
+	#[pallet::storage]
+	#[pallet::getter(fn viewer_prices)]
+	pub type ViewerPrices<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u128,
+		Blake2_128Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		OptionQuery,
+	>;
+
+	/// Stores the voucher nonces already redeemed for each stream
+	#[pallet::storage]
+	pub type ConsumedVouchers<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, u128, Twox64Concat, u64, (), OptionQuery>;
+
+	/// Streams scheduled to be ended automatically at a block
+	#[pallet::storage]
+	#[pallet::getter(fn stream_expiries)]
+	pub type StreamExpiries<T: Config> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<u128>, ValueQuery>;
+
+	/// Stores each stream's scheduled price and the block it takes effect in
+	#[pallet::storage]
+	#[pallet::getter(fn pending_prices)]
+	pub type PendingPrices<T: Config> =
+		StorageMap<_, Blake2_128Concat, u128, (BalanceOf<T>, BlockNumberFor<T>), OptionQuery>;
+
+	/// Streams with a price change taking effect at a block
+	#[pallet::storage]
+	#[pallet::getter(fn price_changes_due)]
+	pub type PriceChangesDue<T: Config> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<u128>, ValueQuery>;
+
+	/// Seconds watched per stream per era, kept for the current and previous era
+	#[pallet::storage]
+	#[pallet::getter(fn era_watch_time)]
+	pub type EraWatchTime<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, u32, Blake2_128Concat, u128, u64, ValueQuery>;
+
+	/// Seconds watched per viewer and stream per era, until handed to `OnWatchTime`
+	#[pallet::storage]
+	pub type PendingWatchTime<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		u32,
+		Blake2_128Concat,
+		(T::AccountId, u128),
+		u64,
+		ValueQuery,
+	>;
+
+	/// Seconds each viewer has been charged for on each creator's streams per era, as
+	/// (creator, viewer), kept for `MaxPollLookbackEras` eras to weigh poll votes by
+	#[pallet::storage]
+	pub type CreatorWatchTime<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		u32,
+		Blake2_128Concat,
+		(T::AccountId, T::AccountId),
+		u64,
+		ValueQuery,
+	>;
+
+	/// The oldest era whose watch time has not all been handed to `OnWatchTime`
+	#[pallet::storage]
+	pub type WatchTimeDeliveryEra<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The streams featured in each era and the seed they were drawn with
+	#[pallet::storage]
+	#[pallet::getter(fn featured)]
+	pub type Featured<T: Config> =
+		StorageMap<_, Twox64Concat, u32, (T::Hash, Vec<u128>), OptionQuery>;
+
+	/// Counts failed dispatches per era, keyed by (call index, error index)
+	#[pallet::storage]
+	#[pallet::getter(fn error_counts)]
+	pub type ErrorCounts<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, u32, Blake2_128Concat, (u8, u8), u32, ValueQuery>;
+
+	/// Stores the native and fiat value paid to each creator per era, with the fiat value
+	/// fixed at the rate of each settlement
+	#[pallet::storage]
+	#[pallet::getter(fn creator_settled_value)]
+	pub type CreatorSettledValues<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		u32,
+		SettledValue<BalanceOf<T>>,
+		ValueQuery,
+	>;
+
+	/// Stores each creator's settled earnings per era
+	#[pallet::storage]
+	#[pallet::getter(fn creator_earnings)]
+	pub type CreatorEarnings<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		u32,
+		EraEarnings<BalanceOf<T>>,
+		ValueQuery,
+	>;
+
+	/// Stores each stream's settled earnings per era, with the fiat value fixed at the rate of
+	/// each settlement
+	#[pallet::storage]
+	#[pallet::getter(fn stream_earnings)]
+	pub type StreamEraEarnings<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		u32,
+		Blake2_128Concat,
+		u128,
+		StreamEarnings<BalanceOf<T>>,
+		ValueQuery,
+	>;
+
+	/// Stores the commitment to each finished era's per-stream earnings, also deposited in
+	/// the header of the era's first block after it
+	#[pallet::storage]
+	#[pallet::getter(fn era_settlement_commitment)]
+	pub type EraSettlementCommitments<T: Config> =
+		StorageMap<_, Twox64Concat, u32, EraSettlementCommitment, OptionQuery>;
+
+	/// Stores the unit each stream's earnings are reported in, if its creator has chosen one
+	/// other than the currency the stream is priced in
+	#[pallet::storage]
+	pub type ReportingUnits<T: Config> =
+		StorageMap<_, Blake2_128Concat, u128, ReportingUnitOf<T>, OptionQuery>;
+
+	/// The platform's commission on every tick payment
+	#[pallet::storage]
+	#[pallet::getter(fn platform_fee)]
+	pub type PlatformFee<T: Config> = StorageValue<_, Permill, ValueQuery>;
+
+	/// Stores the co-hosts each stream's revenue is shared with, and their shares
+	#[pallet::storage]
+	#[pallet::getter(fn revenue_splits)]
+	pub type RevenueSplits<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		u128,
+		BoundedVec<(T::AccountId, Permill), T::MaxCoHosts>,
+		ValueQuery,
+	>;
+
+	/// Stores the earnings each creator or co-host can withdraw from a stream's escrow
+	#[pallet::storage]
+	#[pallet::getter(fn escrow)]
+	pub type Escrow<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u128,
+		Blake2_128Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// Stores the period over which each co-host's share of a stream vests, for co-hosts
+	/// whose share does not go straight to escrow
+	#[pallet::storage]
+	#[pallet::getter(fn split_vesting)]
+	pub type SplitVesting<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u128,
+		Blake2_128Concat,
+		T::AccountId,
+		BlockNumberFor<T>,
+		OptionQuery,
+	>;
+
+	/// Stores each co-host's vesting earnings per stream, held in the stream's escrow account
+	#[pallet::storage]
+	#[pallet::getter(fn vesting_schedule)]
+	pub type VestingSchedules<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u128,
+		Blake2_128Concat,
+		T::AccountId,
+		VestingScheduleOf<T>,
+		OptionQuery,
+	>;
+
+	/// Stores the tick payments held for each viewer of a stream, oldest first
+	#[pallet::storage]
+	#[pallet::getter(fn pending_settlements)]
+	pub type PendingSettlements<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u128,
+		Blake2_128Concat,
+		T::AccountId,
+		Vec<PendingSettlementOf<T>>,
+		ValueQuery,
+	>;
+
+	/// Stores the (stream, viewer) pairs with held payments falling due at each block
+	#[pallet::storage]
+	pub type SettlementsDue<T: Config> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<(u128, T::AccountId)>, ValueQuery>;
+
+	/// Stores the disputes awaiting resolution
+	#[pallet::storage]
+	#[pallet::getter(fn dispute)]
+	pub type Disputes<T: Config> = StorageMap<_, Twox64Concat, u32, DisputeOf<T>, OptionQuery>;
+
+	/// Stores the id of the next dispute
+	#[pallet::storage]
+	pub type NextDisputeId<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Stores the number of unresolved disputes against each creator
+	#[pallet::storage]
+	pub type OpenDisputes<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Stores each creator's reporter bond
+	#[pallet::storage]
+	#[pallet::getter(fn reporter_stake)]
+	pub type ReporterStakes<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, ReporterStakeOf<T>, OptionQuery>;
+
+	/// Stores what viewers must meet to join each gated stream
+	#[pallet::storage]
+	#[pallet::getter(fn join_gate)]
+	pub type JoinGates<T: Config> =
+		StorageMap<_, Blake2_128Concat, u128, JoinGate<BlockNumberFor<T>>, OptionQuery>;
+
+	/// Stores the block each account was created in, for accounts created since it was tracked
+	#[pallet::storage]
+	pub type AccountCreatedAt<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BlockNumberFor<T>, OptionQuery>;
+
+	/// Stores the seconds each account has been charged for across every stream
+	#[pallet::storage]
+	#[pallet::getter(fn lifetime_watch_seconds)]
+	pub type LifetimeWatchSeconds<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
+
+	/// Stores the account a creator has let anchor positions on their stream
+	#[pallet::storage]
+	#[pallet::getter(fn sync_host)]
+	pub type SyncHosts<T: Config> =
+		StorageMap<_, Blake2_128Concat, u128, T::AccountId, OptionQuery>;
+
+	/// Stores each stream's most recent position anchors, oldest first
+	#[pallet::storage]
+	#[pallet::getter(fn position_anchors)]
+	pub type PositionAnchors<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		u128,
+		BoundedVec<PositionAnchor<BlockNumberFor<T>>, T::MaxPositionAnchors>,
+		ValueQuery,
+	>;
+
+	/// Stores the block each account's payouts are frozen until
+	#[pallet::storage]
+	#[pallet::getter(fn payout_freeze)]
+	pub type PayoutFreezes<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BlockNumberFor<T>, OptionQuery>;
+
+	/// Stores the accounts whose payout freezes may run out at each block
+	#[pallet::storage]
+	pub type PayoutFreezeExpiries<T: Config> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<T::AccountId>, ValueQuery>;
+
+	/// Stores the block ticks were last settled in and how many it has settled
+	#[pallet::storage]
+	pub type BlockSettlements<T: Config> =
+		StorageValue<_, (BlockNumberFor<T>, u32), ValueQuery>;
+
+	/// Set while no ticks have been settled for `AlertAfterBlocks` with viewers on live streams
+	#[pallet::storage]
+	#[pallet::getter(fn settlement_stalled)]
+	pub type SettlementStalled<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// Stores the ticks left over once a block's settlements ran out, by place in the queue
+	#[pallet::storage]
+	pub type QueuedTicks<T: Config> =
+		StorageMap<_, Twox64Concat, u64, (u128, T::AccountId, u32), OptionQuery>;
+
+	/// Stores the places of the oldest queued tick and of the next one to be queued
+	#[pallet::storage]
+	#[pallet::getter(fn queued_tick_range)]
+	pub type QueuedTickRange<T: Config> = StorageValue<_, (u64, u64), ValueQuery>;
+
+	/// Stores the claim on each handle, by the handle in lowercase
+	#[pallet::storage]
+	#[pallet::getter(fn handle_claim)]
+	pub type Handles<T: Config> =
+		StorageMap<_, Blake2_128Concat, HandleOf<T>, HandleClaimOf<T>, OptionQuery>;
+
+	/// Stores the handle each stream goes by, in lowercase
+	#[pallet::storage]
+	#[pallet::getter(fn stream_handle)]
+	pub type StreamHandles<T: Config> =
+		StorageMap<_, Blake2_128Concat, u128, HandleOf<T>, OptionQuery>;
+
+	/// Stores the seconds of each stream viewers watch for free before they are charged
+	#[pallet::storage]
+	#[pallet::getter(fn free_preview)]
+	pub type FreePreviews<T: Config> = StorageMap<_, Blake2_128Concat, u128, u32, ValueQuery>;
+
+	/// Stores the free preview seconds each viewer has watched of a stream, kept when they
+	/// leave so rejoining does not start the preview over
+	#[pallet::storage]
+	pub type PreviewWatched<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u128,
+		Blake2_128Concat,
+		T::AccountId,
+		u32,
+		ValueQuery,
+	>;
+
+	/// Stores the devices each viewer may watch a stream on at once, where its creator has
+	/// changed it from one
+	#[pallet::storage]
+	pub type DeviceLimits<T: Config> = StorageMap<_, Blake2_128Concat, u128, u32, OptionQuery>;
+
+	/// Stores the viewers exempt from each stream's device limit
+	#[pallet::storage]
+	pub type DeviceLimitExemptions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u128,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+		OptionQuery,
+	>;
+
+	/// Stores the sessions each viewer has open on a stream, one per device
+	#[pallet::storage]
+	#[pallet::getter(fn active_sessions)]
+	pub type ActiveSessions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u128,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<u64, T::MaxDevicesPerViewer>,
+		ValueQuery,
+	>;
+
+	/// Stores who referred each viewer to a stream, for as long as they watch it
+	#[pallet::storage]
+	#[pallet::getter(fn referrer)]
+	pub type Referrers<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u128,
+		Blake2_128Concat,
+		T::AccountId,
+		T::AccountId,
+		OptionQuery,
+	>;
+
+	/// Stores each referrer's unclaimed earnings per stream, held in the stream's escrow
+	#[pallet::storage]
+	#[pallet::getter(fn referral_earnings)]
+	pub type ReferralEarnings<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		u128,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// Indexes the ids of streams by their lifecycle state, for listing them without walking
+	/// every stream
+	#[pallet::storage]
+	pub type StreamsByStatus<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, StreamStatus, Twox64Concat, u128, (), OptionQuery>;
+
+	/// Stores the number of viewers currently watching each stream
+	#[pallet::storage]
+	#[pallet::getter(fn viewer_count)]
+	pub type ViewerCount<T: Config> = StorageMap<_, Blake2_128Concat, u128, u32, ValueQuery>;
+
+	/// Stores the most viewers each capped stream lets watch at once
+	#[pallet::storage]
+	#[pallet::getter(fn max_viewers)]
+	pub type MaxViewers<T: Config> = StorageMap<_, Blake2_128Concat, u128, u32, OptionQuery>;
+
+	/// Stores the platform fees that replace `PlatformFee` for streams in a category
+	#[pallet::storage]
+	#[pallet::getter(fn category_fee)]
+	pub type CategoryFees<T: Config> =
+		StorageMap<_, Twox64Concat, StreamCategory, Permill, OptionQuery>;
+
+	/// Stores the viewers who booked a seat on each scheduled stream and the deposit each
+	/// reserved
+	#[pallet::storage]
+	#[pallet::getter(fn pre_bookings)]
+	pub type PreBookings<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		u128,
+		BoundedVec<(T::AccountId, BalanceOf<T>), T::MaxPreBookings>,
+		ValueQuery,
+	>;
+
+	/// Stores the booked streams whose bookings are cancelled if they are not live by each
+	/// block
+	#[pallet::storage]
+	pub type PreBookingDeadlines<T: Config> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<u128>, ValueQuery>;
+
+	/// Stores the discount each stream gives on passes gifted in bulk
+	#[pallet::storage]
+	#[pallet::getter(fn bulk_discount)]
+	pub type BulkDiscounts<T: Config> =
+		StorageMap<_, Blake2_128Concat, u128, BulkDiscount, OptionQuery>;
+
+	/// Stores the policy each account has set for forwarding its earnings to cold storage
+	#[pallet::storage]
+	#[pallet::getter(fn sweep_policy)]
+	pub type SweepPolicies<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, SweepPolicyOf<T>, OptionQuery>;
+
+	/// Stores the policy each account has set for hedging its withdrawals into a stable asset
+	#[pallet::storage]
+	#[pallet::getter(fn hedge_policy)]
+	pub type HedgePolicies<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, HedgePolicyOf<T>, OptionQuery>;
+
+	/// Stores every poll, open or finalized, by id
+	#[pallet::storage]
+	#[pallet::getter(fn polls)]
+	pub type Polls<T: Config> = StorageMap<_, Twox64Concat, u64, PollOf<T>, OptionQuery>;
+
+	/// Stores the id the next poll gets
+	#[pallet::storage]
+	pub type NextPollId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// Stores the option each account voted for in each poll and the weight of its vote
+	#[pallet::storage]
+	#[pallet::getter(fn poll_vote)]
+	pub type PollVotes<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		u64,
+		Blake2_128Concat,
+		T::AccountId,
+		(u8, u64),
+		OptionQuery,
+	>;
+
+	/// Stores the polls closing at each block
+	#[pallet::storage]
+	pub type PollsEnding<T: Config> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<u64>, ValueQuery>;
+
+	/// Stores every giveaway drawn, with what it takes to verify the draw
+	#[pallet::storage]
+	#[pallet::getter(fn giveaways)]
+	pub type Giveaways<T: Config> = StorageMap<_, Twox64Concat, u64, GiveawayOf<T>, OptionQuery>;
+
+	/// Stores the id the next giveaway gets
+	#[pallet::storage]
+	pub type NextGiveawayId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// Stores the escrowed earnings that have grown past their owner's sweep threshold, to be
+	/// forwarded at the start of the next era
+	#[pallet::storage]
+	pub type SweepsDue<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, u128, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+	/// Stores the DID each account has linked itself to
+	#[pallet::storage]
+	#[pallet::getter(fn did_link)]
+	pub type DidLinks<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, DidLinkOf<T>, OptionQuery>;
+
+	/// Stores the account each linked DID belongs to
+	#[pallet::storage]
+	pub type DidAccounts<T: Config> =
+		StorageMap<_, Blake2_128Concat, DidOf<T>, T::AccountId, OptionQuery>;
+
+	/// Stores how each account wants dust earnings handled, if not accumulated
+	#[pallet::storage]
+	#[pallet::getter(fn dust_policy)]
+	pub type DustPolicies<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, DustPolicy, OptionQuery>;
+
+	/// Stores each account's viewing credit, held by the platform account until the account
+	/// joins a stream
+	#[pallet::storage]
+	#[pallet::getter(fn viewing_credit)]
+	pub type ViewingCredits<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// Stores the tick threshold set at genesis or by the admin origin, which takes the place
+	/// of `TickThreshold`
+	#[pallet::storage]
+	pub type TickThresholdOverride<T: Config> = StorageValue<_, BlockNumberFor<T>, OptionQuery>;
+
+	/// Stores the most viewers a single batch may settle, if the admin origin lowered it below
+	/// `MaxViewersPerBatch`
+	#[pallet::storage]
+	pub type BatchCapOverride<T: Config> = StorageValue<_, u32, OptionQuery>;
+
+	/// Stores the accounts allowed to report ticks, with their bonds
+	#[pallet::storage]
+	#[pallet::getter(fn reporter)]
+	pub type Reporters<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, TickReporterOf<T>, OptionQuery>;
+
+	/// Stores the id each migrated stream was moved to
+	#[pallet::storage]
+	#[pallet::getter(fn migrated_stream)]
+	pub type MigratedStreams<T: Config> = StorageMap<_, Blake2_128Concat, u128, u128, OptionQuery>;
+
+	/// Stores the furthest position in seconds each viewer has played an on-demand stream to,
+	/// kept when they leave so seconds they watched before are not charged again
+	#[pallet::storage]
+	#[pallet::getter(fn playback_position)]
+	pub type PlaybackPositions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u128,
+		Blake2_128Concat,
+		T::AccountId,
+		u32,
+		ValueQuery,
+	>;
+
+	/// Stores the most the pallet may hold in the native token across all accounts, if capped
+	#[pallet::storage]
+	#[pallet::getter(fn global_hold_cap)]
+	pub type GlobalHoldCap<T: Config> = StorageValue<_, BalanceOf<T>, OptionQuery>;
+
+	/// Stores which extrinsics the runtime accepts, read by its call filter
+	#[pallet::storage]
+	#[pallet::getter(fn call_filter_mode)]
+	pub type CallFilter<T: Config> = StorageValue<_, CallFilterMode, ValueQuery>;
+
+	/// Stores the total the pallet holds in the native token across all accounts
+	#[pallet::storage]
+	#[pallet::getter(fn total_held)]
+	pub type TotalHeld<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// Stores who may join each stream that is not open to anyone
+	#[pallet::storage]
+	#[pallet::getter(fn access_policy)]
+	pub type AccessPolicies<T: Config> =
+		StorageMap<_, Blake2_128Concat, u128, AccessPolicyOf<T>, OptionQuery>;
+
+	/// Stores the accounts allowed to join each stream, for streams with an allowlist
+	#[pallet::storage]
+	pub type Allowlists<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u128,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+		OptionQuery,
+	>;
+
+	/// Stores the index of the last tick interval `batch_tick` settled on each stream
+	#[pallet::storage]
+	#[pallet::getter(fn last_settled_interval)]
+	pub type LastSettledInterval<T: Config> =
+		StorageMap<_, Blake2_128Concat, u128, u64, OptionQuery>;
+
+	/// Stores each creator's onboarding grant until it has fully vested or been clawed back
+	#[pallet::storage]
+	#[pallet::getter(fn creator_grant)]
+	pub type CreatorGrants<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, CreatorGrantOf<T>, OptionQuery>;
+
+	/// Stores the creators whose grants lapse at each block
+	#[pallet::storage]
+	pub type GrantDeadlines<T: Config> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<T::AccountId>, ValueQuery>;
+
+	/// Stores the seed featured streams and giveaways are drawn with instead of `Randomness`,
+	/// on chains whose draws must repeat from run to run
+	#[pallet::storage]
+	#[pallet::getter(fn randomness_seed)]
+	pub type RandomnessSeed<T: Config> = StorageValue<_, [u8; 32], OptionQuery>;
+
+	/// Stores the chains viewer attestations are imported from, with their ids
+	#[pallet::storage]
+	#[pallet::getter(fn attestation_sources)]
+	pub type AttestationSources<T: Config> = StorageValue<
+		_,
+		BoundedVec<(u32, AttestationSourceOf<T>), T::MaxAttestationSources>,
+		ValueQuery,
+	>;
+
+	/// Stores the attestations imported for each viewer, at most one per source chain
+	#[pallet::storage]
+	#[pallet::getter(fn viewer_attestations)]
+	pub type ViewerAttestations<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<ViewerAttestationOf<T>, T::MaxAttestationSources>,
+		ValueQuery,
+	>;
+
+	/// Stores the spending limits each viewer has set
+	#[pallet::storage]
+	#[pallet::getter(fn spending_limit)]
+	pub type SpendingLimits<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, SpendingLimit<BalanceOf<T>>, OptionQuery>;
+
+	/// Stores what each viewer has been charged on a stream since joining or resuming it
+	#[pallet::storage]
+	pub type SessionSpending<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u128,
+		Blake2_128Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// Stores the UTC day each viewer was last charged in and what they were charged that day
+	#[pallet::storage]
+	pub type DailySpending<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (u64, BalanceOf<T>), ValueQuery>;
+
+	/// Stores the state of each viewer's session on each stream, until it is settled
+	#[pallet::storage]
+	pub type SessionStates<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u128,
+		Blake2_128Concat,
+		T::AccountId,
+		SessionState,
+		OptionQuery,
+	>;
+
+	/// Stores the number of sessions each viewer has open, which together hold one consumer
+	/// reference on their account
+	#[pallet::storage]
+	#[pallet::getter(fn open_sessions)]
+	pub type OpenSessions<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Stores each viewer's pooled budget and spend per creator
+	#[pallet::storage]
+	#[pallet::getter(fn creator_budget)]
+	pub type CreatorBudgets<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		CreatorBudget<BalanceOf<T>>,
+		ValueQuery,
+	>;
+
+	/// Stores each viewer's watch history per stream, until pruned
+	#[pallet::storage]
+	#[pallet::getter(fn viewer_stats)]
+	pub type ViewerStats<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		u128,
+		WatchStatsOf<T>,
+		OptionQuery,
+	>;
+
+	/// Stores the subscription tiers each stream offers, indexed by position
+	#[pallet::storage]
+	#[pallet::getter(fn subscription_tiers)]
+	pub type SubscriptionTiers<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		u128,
+		BoundedVec<SubscriptionTierOf<T>, T::MaxSubscriptionTiers>,
+		ValueQuery,
+	>;
+
+	/// Stores the block each viewer's subscription to a stream expires at
+	#[pallet::storage]
+	#[pallet::getter(fn subscription)]
+	pub type Subscriptions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u128,
+		Blake2_128Concat,
+		T::AccountId,
+		BlockNumberFor<T>,
+		OptionQuery,
+	>;
+
+	/// Stores each account's notification preferences, read by relayers
+	#[pallet::storage]
+	#[pallet::getter(fn notification_prefs)]
+	pub type NotificationPreferences<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, NotificationPrefs, OptionQuery>;
+
+	/// Stores the block of the pallet's latest event and the sequence number of the next
+	#[pallet::storage]
+	pub type EventSequence<T: Config> =
+		StorageValue<_, (BlockNumberFor<T>, u32), ValueQuery>;
+
+	#[pallet::event]
+	pub enum Event<T: Config> {
+		/// A tick has been recorded for a stream
+		TickRecorded {
+			stream_id: u128,
+			viewer: T::AccountId,
+			ticks: u32,
+			amount: BalanceOf<T>,
+			/// `amount` in fiat at the time of settlement, if the oracle had a rate.
+			fiat_value: Option<u128>,
+		},
+		/// A viewer has joined a stream
+		ViewerJoined {
+			stream_id: u128,
+			viewer: T::AccountId,
+		},
+		/// A new stream has been created
+		StreamCreated {
+			stream_id: u128,
+			creator: T::AccountId,
+			price_per_second: BalanceOf<T>,
+		},
+		/// A stream has moved to a new lifecycle state
+		StreamStatusChanged {
+			stream_id: u128,
+			status: StreamStatus,
+		},
+		/// A viewer's remaining reserve has been returned
+		ViewerRefunded {
+			stream_id: u128,
+			viewer: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// A viewer in a batch could not be settled and was skipped
+		TickSkipped {
+			stream_id: u128,
+			viewer: T::AccountId,
+		},
+		/// A batch of ticks has been settled for a stream
+		IntervalSettled {
+			stream_id: u128,
+			viewers: u32,
+			total_amount: BalanceOf<T>,
+			/// The fiat value of the settlements the oracle had a rate for.
+			total_fiat_value: u128,
+		},
+		/// A stream reached its maximum duration and was ended
+		StreamAutoEnded {
+			stream_id: u128,
+		},
+		/// A join voucher has been redeemed
+		VoucherRedeemed {
+			stream_id: u128,
+			viewer: T::AccountId,
+			nonce: u64,
+		},
+		/// The platform fee has been changed
+		PlatformFeeSet {
+			fee: Permill,
+		},
+		/// A stream's co-hosts have been changed
+		RevenueSplitSet {
+			stream_id: u128,
+			co_hosts: u32,
+		},
+		/// A share of a tick payment has been paid to a beneficiary
+		RevenueSplit {
+			stream_id: u128,
+			beneficiary: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// Escrowed earnings have been withdrawn
+		Withdrawn {
+			stream_id: u128,
+			who: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// An account's notification preferences have been changed
+		NotificationPrefsSet {
+			who: T::AccountId,
+		},
+		/// A stream's metadata has been set or updated
+		MetadataSet {
+			stream_id: u128,
+		},
+		/// A stream's metadata has been removed
+		MetadataCleared {
+			stream_id: u128,
+		},
+		/// Leftover reservations of an ended stream have been refunded
+		ExpiredSwept {
+			stream_id: u128,
+			sweeper: T::AccountId,
+			swept: u32,
+			reward: BalanceOf<T>,
+		},
+		/// A creator has registered a promo code
+		PromoCreated {
+			stream_id: u128,
+			code_hash: T::Hash,
+		},
+		/// A viewer has committed to redeeming a promo code
+		PromoCommitted {
+			stream_id: u128,
+			viewer: T::AccountId,
+		},
+		/// A viewer has redeemed a promo code
+		PromoRedeemed {
+			stream_id: u128,
+			viewer: T::AccountId,
+			price_per_second: BalanceOf<T>,
+		},
+		/// A stream has been handed over to a new creator
+		StreamTransferred {
+			stream_id: u128,
+			from: T::AccountId,
+			to: T::AccountId,
+		},
+		/// A viewer has set or cleared their automatic leave threshold
+		AutoLeaveThresholdSet {
+			who: T::AccountId,
+			threshold: BalanceOf<T>,
+		},
+		/// A viewer's balance fell below their threshold and they were taken off a stream
+		ViewerAutoLeft {
+			stream_id: u128,
+			viewer: T::AccountId,
+		},
+		/// Viewers have been forced off a stream by the platform
+		StreamAccessRevoked {
+			stream_id: u128,
+			revoked: u32,
+			/// Whether reservations are still held on the stream, to be revoked by another call.
+			more_remain: bool,
+		},
+		/// An account has been forced off streams by the platform
+		AccountAccessRevoked {
+			who: T::AccountId,
+			revoked: u32,
+		},
+		/// A creator has scheduled a stream to go live at a wall-clock time
+		StreamScheduled {
+			stream_id: u128,
+			starts_at: u64,
+			utc_offset_minutes: i16,
+			estimated_block: BlockNumberFor<T>,
+		},
+		/// Block times drifted and a scheduled stream's start block was re-estimated
+		ScheduleAdjusted {
+			stream_id: u128,
+			estimated_block: BlockNumberFor<T>,
+		},
+		/// A scheduled stream has gone live
+		ScheduledStreamStarted {
+			stream_id: u128,
+		},
+		/// A viewer has added to their budget for a creator's streams
+		BudgetToppedUp {
+			viewer: T::AccountId,
+			creator: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// A viewer has released what was left of their budget for a creator's streams
+		BudgetReleased {
+			viewer: T::AccountId,
+			creator: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// Watch history of a viewer has been pruned
+		ViewerStatsPruned {
+			viewer: T::AccountId,
+			pruned: u32,
+		},
+		/// A creator has added a subscription tier to a stream
+		SubscriptionTierCreated {
+			stream_id: u128,
+			tier: u32,
+			price: BalanceOf<T>,
+			duration_blocks: BlockNumberFor<T>,
+		},
+		/// A viewer has bought or extended a subscription to a stream
+		Subscribed {
+			stream_id: u128,
+			viewer: T::AccountId,
+			tier: u32,
+			expires_at: BlockNumberFor<T>,
+		},
+		/// A viewer's subscription to a stream has run out and they are metered again
+		SubscriptionExpired {
+			stream_id: u128,
+			viewer: T::AccountId,
+		},
+		/// The period a co-host's share of a stream vests over has been changed
+		SplitVestingSet {
+			stream_id: u128,
+			co_host: T::AccountId,
+			period: BlockNumberFor<T>,
+		},
+		/// Vested earnings have been claimed from a stream's escrow
+		VestedClaimed {
+			stream_id: u128,
+			who: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// A held tick payment has been paid out
+		SettlementFinalized {
+			stream_id: u128,
+			viewer: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// A viewer has disputed held tick payments, which are frozen until resolved
+		TicksDisputed {
+			dispute_id: u32,
+			stream_id: u128,
+			viewer: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// A dispute has been resolved: refunded to the viewer with the reporter's bond slashed
+		/// if upheld, paid out otherwise
+		DisputeResolved {
+			dispute_id: u32,
+			upheld: bool,
+			slashed: BalanceOf<T>,
+		},
+		/// A creator has reserved a reporter bond
+		ReporterBonded {
+			who: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// A creator's reporter bond has been released
+		ReporterUnbonded {
+			who: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// The asset a stream is priced in has been changed
+		StreamAssetSet {
+			stream_id: u128,
+			asset_id: Option<AssetIdOf<T>>,
+		},
+		/// A stream's join gate has been set, or lifted if `None`
+		JoinGateSet {
+			stream_id: u128,
+			gate: Option<JoinGate<BlockNumberFor<T>>>,
+		},
+		/// A stream's sync host has been set, or handed back to the creator if `None`
+		SyncHostSet {
+			stream_id: u128,
+			host: Option<T::AccountId>,
+		},
+		/// A stream's media position has been anchored for watch parties
+		PositionAnchored {
+			stream_id: u128,
+			host: T::AccountId,
+			media_timestamp: u64,
+		},
+		/// An account's payouts have been frozen pending an investigation
+		PayoutsFrozen {
+			who: T::AccountId,
+			until: BlockNumberFor<T>,
+		},
+		/// An account's payout freeze has been extended
+		PayoutFreezeExtended {
+			who: T::AccountId,
+			until: BlockNumberFor<T>,
+		},
+		/// An account's payouts have been released, early or as their freeze ran out
+		PayoutsReleased {
+			who: T::AccountId,
+		},
+		/// A tick was left for a later block, as this block had settled all it may
+		TickQueued {
+			stream_id: u128,
+			viewer: T::AccountId,
+			ticks: u32,
+		},
+		/// A creator has claimed a handle for their stream
+		HandleClaimed {
+			stream_id: u128,
+			handle: HandleOf<T>,
+		},
+		/// A stream no longer goes by its handle
+		HandleReleased {
+			stream_id: u128,
+			handle: HandleOf<T>,
+		},
+		/// A viewer has set or cleared their spending limits
+		SpendingLimitSet {
+			who: T::AccountId,
+			max_per_stream: Option<BalanceOf<T>>,
+			max_per_day: Option<BalanceOf<T>>,
+		},
+		/// Charging a viewer's tick would go over their spending limit, so their session is
+		/// paused until they resume it
+		SpendingLimitReached {
+			stream_id: u128,
+			viewer: T::AccountId,
+		},
+		/// A viewer has chosen to keep watching past their spending limit
+		SessionResumed {
+			stream_id: u128,
+			viewer: T::AccountId,
+		},
+		/// A creator has changed how many seconds of their stream are free to watch
+		FreePreviewSet {
+			stream_id: u128,
+			seconds: u32,
+		},
+		/// A viewer has started watching a stream on a device
+		SessionOpened {
+			stream_id: u128,
+			viewer: T::AccountId,
+			session: u64,
+		},
+		/// A viewer has stopped watching a stream on a device
+		SessionClosed {
+			stream_id: u128,
+			viewer: T::AccountId,
+			session: u64,
+		},
+		/// A creator has changed how many devices each viewer may watch their stream on
+		DeviceLimitSet {
+			stream_id: u128,
+			limit: u32,
+		},
+		/// A creator has exempted a viewer from their stream's device limit, or revoked it
+		DeviceExemptionSet {
+			stream_id: u128,
+			viewer: T::AccountId,
+			exempt: bool,
+		},
+		/// Part of a referred viewer's tick payment has gone to their referrer
+		ReferralPaid {
+			stream_id: u128,
+			referrer: T::AccountId,
+			viewer: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// A referrer has claimed their earnings from a stream
+		ReferralEarningsClaimed {
+			stream_id: u128,
+			who: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// The number of viewers watching a stream has changed
+		ViewerCountChanged {
+			stream_id: u128,
+			viewers: u32,
+		},
+		/// A creator has capped how many viewers may watch their stream at once, or lifted
+		/// the cap
+		MaxViewersSet {
+			stream_id: u128,
+			max_viewers: Option<u32>,
+		},
+		/// The platform fee for streams in a category has been overridden, or reset to the
+		/// platform-wide fee
+		CategoryFeeSet {
+			category: StreamCategory,
+			fee: Option<Permill>,
+		},
+		/// A viewer has booked a seat on a scheduled stream
+		PreBooked {
+			stream_id: u128,
+			viewer: T::AccountId,
+			deposit: BalanceOf<T>,
+		},
+		/// A stream has gone live and the viewers who booked seats have joined it
+		PreBookingsSeated {
+			stream_id: u128,
+			seated: u32,
+		},
+		/// A scheduled stream ended or did not go live in time, and its bookings were refunded
+		PreBookingsCancelled {
+			stream_id: u128,
+			refunded: u32,
+		},
+		/// An account has been gifted a subscription to a stream
+		SubscriptionGifted {
+			stream_id: u128,
+			gifter: T::AccountId,
+			recipient: T::AccountId,
+			tier: u32,
+			expires_at: BlockNumberFor<T>,
+		},
+		/// A stream's discount on passes gifted in bulk has been set, or removed if `None`
+		BulkDiscountSet {
+			stream_id: u128,
+			discount: Option<BulkDiscount>,
+		},
+		/// An account's cold storage sweep policy has been set, or removed if `None`
+		SweepPolicySet {
+			who: T::AccountId,
+			policy: Option<SweepPolicyOf<T>>,
+		},
+		/// Earnings above an account's sweep threshold have been forwarded to its cold address
+		SweptToCold {
+			stream_id: u128,
+			who: T::AccountId,
+			cold_address: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// A stream's payout account has been set, or reset to its creator if `None`
+		PayoutAccountSet {
+			stream_id: u128,
+			creator: T::AccountId,
+			payout_account: Option<T::AccountId>,
+		},
+		/// A stream creator's earnings have been paid to the stream's payout account
+		PayoutRouted {
+			stream_id: u128,
+			creator: T::AccountId,
+			payout_account: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// An account has linked itself to a DID
+		DidLinked { who: T::AccountId, did: DidOf<T>, document_hash: [u8; 32] },
+		/// An account has removed its link to a DID
+		DidUnlinked { who: T::AccountId, did: DidOf<T> },
+		/// An account has chosen how its dust earnings are handled
+		DustPolicySet { who: T::AccountId, policy: DustPolicy },
+		/// Earnings too little to pay out have been donated to the platform account
+		DustDonated { stream_id: u128, who: T::AccountId, amount: BalanceOf<T> },
+		/// Earnings too little to pay out have been turned into viewing credit
+		DustCredited { stream_id: u128, who: T::AccountId, amount: BalanceOf<T> },
+		/// A viewer's viewing credit has been paid to them as they joined a stream
+		ViewingCreditRedeemed { stream_id: u128, who: T::AccountId, amount: BalanceOf<T> },
+		/// An account has reserved a bond to report ticks
+		ReporterRegistered { who: T::AccountId, bond: BalanceOf<T> },
+		/// A tick reporter has stopped reporting; its bond is returned from `unbonds_at`
+		ReporterDeregistered { who: T::AccountId, unbonds_at: BlockNumberFor<T> },
+		/// A deregistered tick reporter's bond has been returned
+		ReporterBondReturned { who: T::AccountId, amount: BalanceOf<T> },
+		/// An account has used a superseded call, which may be removed in a later runtime
+		DeprecatedCallUsed { who: T::AccountId, call: LegacyCall },
+		/// A stream and its viewers have moved to a new id
+		StreamMigrated { old_id: u128, new_id: u128 },
+		/// A stream has been set to be broadcast live or played on demand
+		ContentTypeSet { stream_id: u128, content_type: ContentType },
+		/// The cap on what the pallet holds has been set, or lifted with `None`
+		GlobalHoldCapSet { cap: Option<BalanceOf<T>> },
+		/// A stream's access policy has been set
+		AccessPolicySet { stream_id: u128, policy: AccessPolicyOf<T> },
+		/// An account has been added to a stream's allowlist
+		AddedToAllowlist { stream_id: u128, who: T::AccountId },
+		/// An account has been removed from a stream's allowlist
+		RemovedFromAllowlist { stream_id: u128, who: T::AccountId },
+		/// A viewer could not cover their ticks, was charged what they had left and has been
+		/// taken off the stream
+		ViewerFundsExhausted {
+			stream_id: u128,
+			viewer: T::AccountId,
+			charged: BalanceOf<T>,
+			shortfall: BalanceOf<T>,
+		},
+		/// A creator has been granted `amount`, which vests as they reach its milestones
+		GrantApproved { creator: T::AccountId, amount: BalanceOf<T>, deadline: BlockNumberFor<T> },
+		/// A creator has reached a milestone of their grant and its tranche has been released
+		GrantMilestoneReached {
+			creator: T::AccountId,
+			milestone: GrantMilestone,
+			amount: BalanceOf<T>,
+		},
+		/// A creator's grant has lapsed and what had not vested has gone back to the platform
+		GrantClawedBack { creator: T::AccountId, amount: BalanceOf<T> },
+		/// A chain viewer attestations are imported from has been registered or updated, or
+		/// removed with `None`
+		AttestationSourceSet { source: u32, config: Option<AttestationSourceOf<T>> },
+		/// A source chain's attestation about a viewer has been imported
+		AttestationImported {
+			who: T::AccountId,
+			source: u32,
+			watch_seconds: u64,
+			verification_level: u8,
+		},
+		/// A stream's earnings are now reported in `unit`
+		ReportingUnitSet { stream_id: u128, unit: ReportingUnitOf<T> },
+		/// What every stream earned in `era` has been committed to, in storage and the header
+		EraSettlementsCommitted { era: u32, root: [u8; 32], streams: u32 },
+		/// An account's payout hedging policy has been set, or removed if `None`
+		HedgePolicySet { who: T::AccountId, policy: Option<HedgePolicyOf<T>> },
+		/// Part of a withdrawal has been swapped into the account's hedge asset: `amount` of the
+		/// stream's currency bought `received` of `asset`, where the quote was `quoted`
+		EarningsHedged {
+			stream_id: u128,
+			who: T::AccountId,
+			asset: AssetIdOf<T>,
+			amount: BalanceOf<T>,
+			received: BalanceOf<T>,
+			quoted: BalanceOf<T>,
+		},
+		/// The hedged share of a withdrawal could not be swapped within the account's slippage
+		/// limit, so it has been paid out unswapped
+		HedgeSkipped { stream_id: u128, who: T::AccountId, amount: BalanceOf<T> },
+		/// No ticks have been settled for `AlertAfterBlocks` although viewers are watching live
+		/// streams, as when no reporter or block author is submitting them
+		SettlementStalled { last_settled: BlockNumberFor<T> },
+		/// Settlement is no longer stalled, as ticks are being settled again or no viewers are
+		/// left on live streams
+		SettlementResumed { last_settled: BlockNumberFor<T> },
+		/// A creator has opened a poll of their viewers
+		PollCreated { poll_id: u64, creator: T::AccountId, ends_at: BlockNumberFor<T> },
+		/// A viewer has voted in a poll, with `weight` seconds of watch time, replacing any
+		/// earlier vote of theirs
+		PollVoted { poll_id: u64, voter: T::AccountId, option: u8, weight: u64 },
+		/// A poll has closed with its final tallies, in seconds of watch time per option
+		PollFinalized { poll_id: u64, tallies: Vec<u64>, winner: Option<u8> },
+		/// A giveaway's winner has been drawn among a stream's active viewers and paid
+		GiveawayDrawn {
+			giveaway_id: u64,
+			stream_id: u128,
+			winner: T::AccountId,
+			prize: BalanceOf<T>,
+			seed: T::Hash,
+			entrants: u32,
+		},
+		/// A viewer has tipped a stream's creator
+		TipReceived {
+			stream_id: u128,
+			tipper: T::AccountId,
+			amount: BalanceOf<T>,
+			message: Option<Vec<u8>>,
+		},
+		/// A creator has scheduled a new price for a stream
+		PriceChangeScheduled {
+			stream_id: u128,
+			new_price: BalanceOf<T>,
+			effective_at: BlockNumberFor<T>,
+		},
+		/// A scheduled price has taken effect
+		PriceChanged {
+			stream_id: u128,
+			price_per_second: BalanceOf<T>,
+		},
+		/// The featured streams of a new era have been drawn
+		FeaturedSelected {
+			era: u32,
+			seed: T::Hash,
+			streams: Vec<u128>,
+		},
+		/// A viewer's reserve covers less than `LowBalanceSeconds` of watch time at the price
+		/// they pay
+		LowBalanceWarning {
+			stream_id: u128,
+			viewer: T::AccountId,
+			seconds_remaining: u32,
+		},
+		/// A viewer has reserved more watch time on a stream
+		ToppedUp {
+			stream_id: u128,
+			viewer: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// A viewer's session has moved to another state, from none if it has just begun
+		SessionStateChanged {
+			stream_id: u128,
+			viewer: T::AccountId,
+			from: Option<SessionState>,
+			to: SessionState,
+		},
+		/// A held tick payment could not be paid out and has been given back to the viewer
+		SettlementReturned {
+			stream_id: u128,
+			viewer: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// The runtime's call filter has been switched to another mode
+		CallFilterModeSet { mode: CallFilterMode },
+		/// The tick threshold has been set, or reset to `TickThreshold` with `None`
+		TickThresholdSet { threshold: Option<BlockNumberFor<T>> },
+		/// The batch cap has been set, or reset to `MaxViewersPerBatch` with `None`
+		BatchCapSet { cap: Option<u32> },
+	}
+
+	// Errors inform users that something went wrong.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The stream does not exist
+		StreamNotFound,
+		/// The viewer is not authorized
+		Unauthorized,
+		/// A stream with this id already exists
+		StreamAlreadyExists,
+		/// Only the stream creator may perform this action
+		NotStreamCreator,
+		/// The stream is not live
+		StreamNotLive,
+		/// The stream has ended
+		StreamEnded,
+		/// The stream cannot move to the requested state
+		InvalidStatusTransition,
+		/// The viewer does not have enough reserved or free balance
+		InsufficientBalance,
+		/// The viewer was ticked less than `TickThreshold` blocks ago
+		TickTooEarly,
+		/// More ticks were reported than seconds have elapsed since the last tick
+		TooManyTicks,
+		/// The voucher is for another stream or another viewer
+		InvalidVoucher,
+		/// The voucher was not signed by the stream creator
+		InvalidVoucherSignature,
+		/// The voucher's redemption window has passed
+		VoucherExpired,
+		/// The voucher has already been redeemed
+		VoucherAlreadyUsed,
+		/// Co-host shares add up to more than the whole payment
+		InvalidRevenueSplit,
+		/// There are no escrowed earnings to withdraw
+		NothingToWithdraw,
+		/// The notification preferences name an unknown event type
+		InvalidNotificationPrefs,
+		/// The stream has not ended or its settlement window is still open
+		SettlementWindowOpen,
+		/// None of the given viewers have a reservation left on the stream
+		NothingToSweep,
+		/// No promo with this code exists for the stream
+		PromoNotFound,
+		/// The promo code has no uses left
+		PromoExhausted,
+		/// The viewer has not committed to a promo code for the stream
+		NoPromoCommitment,
+		/// A promo code can only be revealed in a block after its commitment
+		RevealTooEarly,
+		/// The revealed code and salt do not match the viewer's commitment
+		CommitmentMismatch,
+		/// The scheduled start time has already passed
+		StartTimeInPast,
+		/// The offset from UTC is outside -12:00 to +14:00
+		InvalidUtcOffset,
+		/// Nothing is reserved in the budget for this creator
+		NoBudget,
+		/// None of the given stats exist or may be pruned yet
+		NothingToPrune,
+		/// The stream offers no subscription tier with this index
+		TierNotFound,
+		/// The stream already offers `MaxSubscriptionTiers` tiers
+		TooManyTiers,
+		/// The account is not one of the stream's co-hosts
+		NotACoHost,
+		/// No held payments of the viewer's fall within the disputed ticks
+		NothingToDispute,
+		/// No unresolved dispute has this id
+		DisputeNotFound,
+		/// The account holds no reporter bond
+		NoReporterBond,
+		/// The creator has unresolved disputes
+		DisputesOpen,
+		/// Payments for the creator's streams can still be disputed
+		SettlementsPending,
+		/// No asset with this id exists
+		UnknownAsset,
+		/// The stream already holds viewers' funds
+		StreamInUse,
+		/// The join gate asks for more than `MaxGateAccountAge` or `MaxGateWatchSeconds`
+		JoinGateTooStrict,
+		/// The chain does not verify identities, nor import attestations from any chain that does
+		IdentityUnsupported,
+		/// The account is younger than the stream's join gate allows
+		AccountTooNew,
+		/// The account has watched less than the stream's join gate requires
+		NotEnoughWatchTime,
+		/// The stream's join gate requires a verified identity
+		IdentityRequired,
+		/// Only the stream's creator or sync host may anchor its position
+		NotSyncHost,
+		/// The account's payouts are frozen pending an investigation
+		PayoutsFrozen,
+		/// The account's payouts are not frozen
+		PayoutsNotFrozen,
+		/// A payout freeze must last at least one block
+		InvalidFreezeDuration,
+		/// Handles are made of ASCII letters, digits and underscores
+		InvalidHandle,
+		/// Another creator holds the handle
+		HandleTaken,
+		/// The stream goes by a handle already
+		StreamHasHandle,
+		/// The stream has no handle
+		NoHandle,
+		/// The viewer's session is paused on their spending limit
+		SessionPaused,
+		/// The viewer's session is not paused
+		SessionNotPaused,
+		/// The viewer is watching the stream on as many devices as they may
+		TooManyDevices,
+		/// The session is open already
+		SessionAlreadyOpen,
+		/// The viewer has no such session open
+		SessionNotOpen,
+		/// A device limit must be between one and `MaxDevicesPerViewer`
+		InvalidDeviceLimit,
+		/// Viewers cannot be referred by themselves or the stream's creator
+		InvalidReferrer,
+		/// The stream has as many viewers as its creator lets watch at once
+		StreamFull,
+		/// A stream cannot be capped at zero viewers
+		InvalidMaxViewers,
+		/// Only streams scheduled to go live can be booked
+		NotScheduled,
+		/// The viewer already holds a seat on the stream
+		AlreadyBooked,
+		/// The stream has as many bookings as it can hold
+		TooManyBookings,
+		/// Passes must be gifted to at least one account
+		NoRecipients,
+		/// A bulk discount must apply to at least two passes
+		InvalidBulkDiscount,
+		/// Earnings cannot be swept to the account that earned them
+		InvalidColdAddress,
+		/// The amount owed is too large to fit in a balance
+		ArithmeticOverflow,
+		/// Only `did:key` and `did:web` DIDs can be linked
+		InvalidDid,
+		/// The DID is linked to another account
+		DidTaken,
+		/// The account has no linked DID
+		NoDidLink,
+		/// The amount is too little to open the account it is paid to with. Earnings stay in
+		/// escrow until they are enough, and tips must be at least the existential deposit
+		BelowExistentialDeposit,
+		/// The account is already registered to report ticks
+		ReporterAlreadyRegistered,
+		/// The account is not registered to report ticks, or has deregistered
+		NotReporter,
+		/// The deregistered reporter's bond cannot be returned yet
+		ReporterCoolingDown,
+		/// The tick is not signed by the reporter
+		InvalidReporterSignature,
+		/// The reporter has already reported a tick with this nonce or a higher one
+		StaleReporterNonce,
+		/// The stream's price for the viewer is above the most they agreed to pay
+		PriceAboveLimit,
+		/// The migration moves more records than it was weighed for
+		MigrationWitnessTooLow,
+		/// The stream is played on demand, so it is billed by playback position, not by ticks
+		BilledByPlayback,
+		/// The stream is broadcast live, so it has no playback position
+		NotOnDemand,
+		/// Holding the amount would take what the pallet holds past `GlobalHoldCap`
+		GlobalCapReached,
+		/// The stream's access policy does not let the account join
+		AccessDenied,
+		/// The tick interval has already been settled on the stream, or a later one has
+		IntervalAlreadySettled,
+		/// The creator already has a grant that has not vested or lapsed
+		GrantAlreadyApproved,
+		/// A grant needs a duration and something to vest, and each tranche needs a target
+		InvalidGrant,
+		/// Attestations are already imported from as many chains as allowed
+		TooManyAttestationSources,
+		/// An attestation source's identity level must be above zero
+		InvalidAttestationSource,
+		/// No chain with the attestation's source id is registered
+		UnknownAttestationSource,
+		/// The attestation has expired
+		AttestationExpired,
+		/// An attestation from the source with the same or a later nonce has been imported
+		StaleAttestation,
+		/// The attestation is not signed by its source chain's signer
+		InvalidAttestationSignature,
+		/// The stream's earnings cannot be converted to the unit: it must be the currency the
+		/// stream is priced in, or fiat for streams priced in the native token
+		UnsupportedReportingUnit,
+		/// The chain cannot swap earnings, so payouts cannot be hedged
+		HedgingUnsupported,
+		/// The viewer's session cannot move to that state from the one it is in
+		InvalidSessionTransition,
+		/// A poll needs at least two options and at most `MaxPollOptions`
+		InvalidPollOptions,
+		/// A poll's question or an option is longer than `MaxPollTextLen`
+		PollTextTooLong,
+		/// A poll must run for at least one block and look back at most `MaxPollLookbackEras`
+		/// eras
+		InvalidPollWindow,
+		/// No poll exists with the given id
+		PollNotFound,
+		/// The poll has closed
+		PollClosed,
+		/// The poll has no option with that index
+		InvalidPollOption,
+		/// The caller has not watched the poll's creator within its lookback window
+		NoWatchTime,
+		/// A giveaway's prize must not be zero
+		NoPrize,
+		/// No viewer other than the creator is actively watching the stream
+		NoEligibleViewers,
+		/// The creator's reporter bond is below `ReporterBond` since a dispute slashed it, and
+		/// must be topped up with `bond_reporter` before their streams are settled again
+		ReporterBondShort,
+		/// A batch cap must be at least one and at most `MaxViewersPerBatch`
+		InvalidBatchCap,
+		/// The batch settles more viewers than the batch cap allows
+		BatchTooLarge,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			// Queued ticks go first, before their streams can end and refund their viewers
+			Self::settle_queued_ticks()
+				.saturating_add(Self::end_expired_streams(n))
+				.saturating_add(Self::start_scheduled_streams(n))
+				.saturating_add(Self::cancel_unstarted_bookings(n))
+				.saturating_add(Self::reestimate_schedules(n))
+				.saturating_add(Self::apply_price_changes(n))
+				.saturating_add(Self::select_era_featured(n))
+				.saturating_add(Self::commit_era_settlements(n))
+				.saturating_add(Self::prune_error_counts(n))
+				.saturating_add(Self::finalize_settlements(n))
+				.saturating_add(Self::deliver_watch_time())
+				.saturating_add(Self::release_payout_freezes(n))
+				.saturating_add(Self::claw_back_lapsed_grants(n))
+				.saturating_add(Self::sweep_to_cold(n))
+				.saturating_add(Self::watch_settlements(n))
+				.saturating_add(Self::finalize_polls(n))
+				.saturating_add(Self::prune_creator_watch_time(n))
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			crate::migrations::MigrateToV2::<T>::on_runtime_upgrade()
+				.saturating_add(crate::migrations::MigrateToV3::<T>::on_runtime_upgrade())
+				.saturating_add(crate::migrations::MigrateToV4::<T>::on_runtime_upgrade())
+				.saturating_add(crate::migrations::MigrateToV5::<T>::on_runtime_upgrade())
+				.saturating_add(crate::migrations::MigrateToV6::<T>::on_runtime_upgrade())
+				.saturating_add(crate::migrations::MigrateToV7::<T>::on_runtime_upgrade())
+				.saturating_add(crate::migrations::MigrateToV8::<T>::on_runtime_upgrade())
+				.saturating_add(crate::migrations::MigrateToV9::<T>::on_runtime_upgrade())
+				.saturating_add(crate::migrations::MigrateToV10::<T>::on_runtime_upgrade())
+				.saturating_add(crate::migrations::MigrateToV11::<T>::on_runtime_upgrade())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			let v2 = crate::migrations::MigrateToV2::<T>::pre_upgrade()?;
+			let v3 = crate::migrations::MigrateToV3::<T>::pre_upgrade()?;
+			let v4 = crate::migrations::MigrateToV4::<T>::pre_upgrade()?;
+			let v5 = crate::migrations::MigrateToV5::<T>::pre_upgrade()?;
+			let v6 = crate::migrations::MigrateToV6::<T>::pre_upgrade()?;
+			let v7 = crate::migrations::MigrateToV7::<T>::pre_upgrade()?;
+			let v8 = crate::migrations::MigrateToV8::<T>::pre_upgrade()?;
+			let v9 = crate::migrations::MigrateToV9::<T>::pre_upgrade()?;
+			let v10 = crate::migrations::MigrateToV10::<T>::pre_upgrade()?;
+			let v11 = crate::migrations::MigrateToV11::<T>::pre_upgrade()?;
+			Ok((v2, v3, v4, v5, v6, v7, v8, v9, v10, v11).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let (v2, v3, v4, v5, v6, v7, v8, v9, v10, v11) = <(
+				Vec<u8>,
+				Vec<u8>,
+				Vec<u8>,
+				Vec<u8>,
+				Vec<u8>,
+				Vec<u8>,
+				Vec<u8>,
+				Vec<u8>,
+				Vec<u8>,
+				Vec<u8>,
+			)>::decode(&mut &state[..])
+			.map_err(|_| "invalid pre-upgrade state")?;
+			crate::migrations::MigrateToV2::<T>::post_upgrade(v2)?;
+			crate::migrations::MigrateToV3::<T>::post_upgrade(v3)?;
+			crate::migrations::MigrateToV4::<T>::post_upgrade(v4)?;
+			crate::migrations::MigrateToV5::<T>::post_upgrade(v5)?;
+			crate::migrations::MigrateToV6::<T>::post_upgrade(v6)?;
+			crate::migrations::MigrateToV7::<T>::post_upgrade(v7)?;
+			crate::migrations::MigrateToV8::<T>::post_upgrade(v8)?;
+			crate::migrations::MigrateToV9::<T>::post_upgrade(v9)?;
+			crate::migrations::MigrateToV10::<T>::post_upgrade(v10)?;
+			crate::migrations::MigrateToV11::<T>::post_upgrade(v11)
+		}
+
+		/// Submit an unsigned tick for every viewer of a live stream that is due one.
+		///
+		/// The lock keeps concurrent workers on this node from running twice for the same
+		/// block; workers on other validators that submit the same tick are deduplicated in
+		/// the pool, since ticks are tagged by (stream, viewer, last tick).
+		fn offchain_worker(n: BlockNumberFor<T>) {
+			if !sp_io::offchain::is_validator() {
+				return
+			}
+
+			let mut lock = StorageLock::<BlockAndTime<frame_system::Pallet<T>>>::with_block_and_time_deadline(
+				OCW_LOCK_KEY,
+				OCW_LOCK_BLOCK_EXPIRATION,
+				Duration::from_millis(OCW_LOCK_TIMEOUT_MS),
+			);
+			let Ok(_guard) = lock.try_lock() else { return };
+
+			let last_run = StorageValueRef::persistent(OCW_LAST_RUN_KEY);
+			let claimed = last_run.mutate(|last: Result<Option<BlockNumberFor<T>>, _>| match last {
+				Ok(Some(last)) if last >= n => Err(()),
+				_ => Ok(n),
+			});
+			if claimed.is_ok() {
+				Self::submit_due_ticks();
+			}
+		}
+	}
+
+	// Extrinsic to record ticks for a stream
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Charge `viewer` for `ticks` seconds of a live stream and pay its creator.
+		///
+		/// Unsigned: submitted by the offchain worker and checked in `validate_unsigned`, with
+		/// `signature` by a registered `reporter` over `tick_payload`. Each tick must carry a
+		/// higher `nonce` than the reporter's last. Fails with `Unauthorized` unless the viewer
+		/// has joined the stream. A viewer whose reserve cannot cover the ticks is charged what
+		/// is left and taken off the stream.
+		#[pallet::call_index(0)]
+		#[pallet::weight((
+			T::WeightInfo::record_tick().saturating_add(T::SettlementExtension::weight()),
+			DispatchClass::Normal
+		))]
+		pub fn record_tick(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			viewer: T::AccountId,
+			ticks: u32,
+			reporter: T::AccountId,
+			nonce: u64,
+			signature: T::OffchainSignature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+			let payload = Self::tick_payload(stream_id, &viewer, ticks, nonce);
+			Self::accept_report(&reporter, nonce, &payload, &signature)?;
+
+			let mut stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.status == StreamStatus::Live, Error::<T>::StreamNotLive);
+			ensure!(stream.content_type == ContentType::Live, Error::<T>::BilledByPlayback);
+
+			let viewers = StreamViewers::<T>::get(stream_id);
+			let entry = SettlementEntry::Reported;
+			Self::do_settle(stream_id, &stream, &viewers, viewer, ticks, entry)?;
+
+			stream.last_tick = frame_system::Pallet::<T>::block_number();
+			Streams::<T>::insert(stream_id, stream);
+
+			Ok(())
+		}
+
+		/// Join a stream at any price, as `join_stream_v2` without a price limit.
+		///
+		/// Superseded by `join_stream_v2`; deposits `DeprecatedCallUsed` before joining.
+		#[pallet::call_index(1)]
+		#[pallet::weight((T::WeightInfo::join_stream(), DispatchClass::Normal))]
+		pub fn join_stream(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			seconds: u32,
+			referrer: Option<T::AccountId>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::deposit_event(Event::DeprecatedCallUsed {
+				who: who.clone(),
+				call: LegacyCall::JoinStream,
+			});
+			Self::join(who, stream_id, seconds, referrer, None)
+		}
+
+		/// Create a stream under the next free id, announced in `StreamCreated`.
+		///
+		/// Each viewer watches the first `free_preview_seconds` of the stream for free.
+		#[pallet::call_index(2)]
+		#[pallet::weight((T::WeightInfo::create_stream(), DispatchClass::Normal))]
+		pub fn create_stream(
+			origin: OriginFor<T>,
+			price_per_second: BalanceOf<T>,
+			max_duration_blocks: Option<BlockNumberFor<T>>,
+			free_preview_seconds: u32,
+		) -> DispatchResult {
+			let creator = ensure_signed(origin)?;
+
+			let stream_id = NextStreamId::<T>::get();
+			// Ids of streams imported with explicit ids must not be handed out again
+			ensure!(!Streams::<T>::contains_key(stream_id), Error::<T>::StreamAlreadyExists);
+			NextStreamId::<T>::put(stream_id.saturating_add(1));
+
+			Streams::<T>::insert(
+				stream_id,
+				Stream {
+					creator: creator.clone(),
+					price_per_second,
+					last_tick: Zero::zero(),
+					status: StreamStatus::Created,
+					max_duration: max_duration_blocks,
+					asset_id: None,
+					payout_account: None,
+					content_type: ContentType::Live,
+				},
+			);
+			StreamsByStatus::<T>::insert(StreamStatus::Created, stream_id, ());
+			if free_preview_seconds > 0 {
+				FreePreviews::<T>::insert(stream_id, free_preview_seconds);
+			}
+
+			Self::deposit_event(Event::StreamCreated { stream_id, creator, price_per_second });
+
+			Ok(())
+		}
+
+		/// Take a created or paused stream live.
+		///
+		/// Only the creator may, with their reporter bond taken first when one is required.
+		#[pallet::call_index(3)]
+		#[pallet::weight((T::WeightInfo::start_stream(), DispatchClass::Normal))]
+		pub fn start_stream(origin: OriginFor<T>, stream_id: u128) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_reporter_bond(&who)?;
+			Self::transition(&who, stream_id, StreamStatus::Live)
+		}
+
+		/// Take a live stream off air; ticks are rejected until it is started again.
+		#[pallet::call_index(4)]
+		#[pallet::weight((T::WeightInfo::pause_stream(), DispatchClass::Normal))]
+		pub fn pause_stream(origin: OriginFor<T>, stream_id: u128) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::transition(&who, stream_id, StreamStatus::Paused)
+		}
+
+		/// End a stream for good, refunding up to `MaxRefundsOnEnd` viewers' reserves.
+		///
+		/// Viewers left over are refunded by `sweep_expired`.
+		#[pallet::call_index(5)]
+		#[pallet::weight((
+			T::WeightInfo::end_stream(T::MaxRefundsOnEnd::get()),
+			DispatchClass::Normal
+		))]
+		pub fn end_stream(origin: OriginFor<T>, stream_id: u128) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::transition(&who, stream_id, StreamStatus::Ended)?;
+			let refunded = Self::refund_viewers(stream_id, T::MaxRefundsOnEnd::get());
+			Ok(Some(T::WeightInfo::end_stream(refunded)).into())
+		}
+
+		/// Settle one tick interval for many viewers of a stream at once.
+		///
+		/// Signed by a registered `reporter` over `batch_payload`, as `record_tick` is. Viewers
+		/// that have not joined or cannot cover their ticks are skipped rather than failing the
+		/// whole batch. The batch is weighed for settling every viewer, and the weight of
+		/// viewers turned away before they are charged is refunded.
+		///
+		/// `interval_index` keys the interval: each stream settles an interval at most once,
+		/// and in increasing order, so a batch two tickers both submit only charges viewers
+		/// once. Batches for an interval already settled fail with `IntervalAlreadySettled`.
+		#[pallet::call_index(6)]
+		#[pallet::weight((
+			T::WeightInfo::batch_tick(ticks.len() as u32).saturating_add(
+				T::SettlementExtension::weight().saturating_mul(ticks.len() as u64)
+			),
+			DispatchClass::Normal
+		))]
+		pub fn batch_tick(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			interval_index: u64,
+			ticks: BoundedVec<(T::AccountId, u32), T::MaxViewersPerBatch>,
+			reporter: T::AccountId,
+			nonce: u64,
+			signature: T::OffchainSignature,
+		) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+			ensure!(ticks.len() as u32 <= Self::batch_cap(), Error::<T>::BatchTooLarge);
+			let payload = Self::batch_payload(stream_id, interval_index, &ticks, nonce);
+			Self::accept_report(&reporter, nonce, &payload, &signature)?;
+			Self::ensure_interval_unsettled(stream_id, interval_index)?;
+			LastSettledInterval::<T>::insert(stream_id, interval_index);
+
+			let mut stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.status == StreamStatus::Live, Error::<T>::StreamNotLive);
+			ensure!(stream.content_type == ContentType::Live, Error::<T>::BilledByPlayback);
+
+			let viewers = StreamViewers::<T>::get(stream_id);
+			let batched = ticks.len() as u32;
+			// Viewers turned away before they are charged cost a couple of reads, and the
+			// weight paid for them up front is refunded
+			let mut attempted = 0u32;
+			let mut totals = IntervalTotals::default();
+			for (viewer, viewer_ticks) in ticks {
+				if Self::settle_batched(
+					stream_id,
+					&stream,
+					&viewers,
+					viewer,
+					viewer_ticks,
+					&mut totals,
+				) {
+					attempted = attempted.saturating_add(1);
+				}
+			}
+
+			stream.last_tick = frame_system::Pallet::<T>::block_number();
+			Streams::<T>::insert(stream_id, stream);
+			Self::deposit_event(Self::interval_settled(stream_id, totals));
+
+			let skipped = batched.saturating_sub(attempted);
+			let extension = T::SettlementExtension::weight().saturating_mul(attempted.into());
+			Ok(Some(
+				T::WeightInfo::batch_tick(attempted)
+					.saturating_add(extension)
+					.saturating_add(T::DbWeight::get().reads(2).saturating_mul(skipped.into())),
+			)
+			.into())
+		}
+
+		/// Join a stream with a voucher signed off-chain by its creator.
+		///
+		/// Each voucher nonce can be redeemed once; the voucher may pin the viewer and waive
+		/// or fix the per-second price for them.
+		#[pallet::call_index(7)]
+		#[pallet::weight((T::WeightInfo::join_with_voucher(), DispatchClass::Normal))]
+		pub fn join_with_voucher(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			voucher: JoinVoucherOf<T>,
+			signature: T::OffchainSignature,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(voucher.stream_id == stream_id, Error::<T>::InvalidVoucher);
+			ensure!(
+				voucher.viewer.as_ref().map_or(true, |viewer| *viewer == who),
+				Error::<T>::InvalidVoucher
+			);
+			ensure!(
+				frame_system::Pallet::<T>::block_number() <= voucher.expires_at,
+				Error::<T>::VoucherExpired
+			);
+			ensure!(
+				!ConsumedVouchers::<T>::contains_key(stream_id, voucher.nonce),
+				Error::<T>::VoucherAlreadyUsed
+			);
+			ensure!(
+				signature.verify(&voucher.encode()[..], &stream.creator),
+				Error::<T>::InvalidVoucherSignature
+			);
+
+			ConsumedVouchers::<T>::insert(stream_id, voucher.nonce, ());
+			let price = match voucher.price_per_second {
+				Some(price) => {
+					ViewerPrices::<T>::insert(stream_id, &who, price);
+					price
+				},
+				None => Self::price_for(stream_id, &stream, &who),
+			};
+			Self::do_join(who.clone(), stream_id, &stream, price, voucher.seconds)?;
+
+			Self::deposit_event(Event::VoucherRedeemed { stream_id, viewer: who, nonce: voucher.nonce });
+
+			Ok(())
+		}
+
+		/// Set the platform's commission on every tick payment.
+		///
+		/// Streams in a category with a fee set by `set_category_fee` pay that fee instead.
+		#[pallet::call_index(8)]
+		#[pallet::weight((T::WeightInfo::set_platform_fee(), DispatchClass::Operational))]
+		pub fn set_platform_fee(origin: OriginFor<T>, fee: Permill) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			PlatformFee::<T>::put(fee);
+			Self::deposit_event(Event::PlatformFeeSet { fee });
+			Ok(())
+		}
+
+		/// Share a stream's revenue with co-hosts.
+		///
+		/// Each co-host receives their share of every payment after the platform fee; the
+		/// creator keeps the rest. An empty list removes all co-hosts.
+		#[pallet::call_index(9)]
+		#[pallet::weight((
+			T::WeightInfo::set_revenue_split(co_hosts.len() as u32),
+			DispatchClass::Normal
+		))]
+		pub fn set_revenue_split(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			co_hosts: BoundedVec<(T::AccountId, Permill), T::MaxCoHosts>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.creator == who, Error::<T>::NotStreamCreator);
+
+			let total = co_hosts
+				.iter()
+				.try_fold(0u32, |total, (_, share)| total.checked_add(share.deconstruct()))
+				.ok_or(Error::<T>::InvalidRevenueSplit)?;
+			ensure!(total <= Permill::one().deconstruct(), Error::<T>::InvalidRevenueSplit);
+
+			let count = co_hosts.len() as u32;
+			if co_hosts.is_empty() {
+				RevenueSplits::<T>::remove(stream_id);
+			} else {
+				RevenueSplits::<T>::insert(stream_id, co_hosts);
+			}
+
+			Self::deposit_event(Event::RevenueSplitSet { stream_id, co_hosts: count });
+
+			Ok(())
+		}
+
+		/// Withdraw the caller's earnings from a stream's escrow.
+		///
+		/// Open to the creator and every co-host the stream has paid, during and after the
+		/// stream. The creator's earnings are paid to the stream's payout account, if it has
+		/// one. Earnings too little to open an empty account with are handled by the caller's
+		/// dust policy, and the caller's hedge policy swaps its share of the rest.
+		#[pallet::call_index(10)]
+		#[pallet::weight((
+			T::WeightInfo::withdraw().saturating_add(T::PayoutSwap::weight()),
+			DispatchClass::Normal
+		))]
+		pub fn withdraw(origin: OriginFor<T>, stream_id: u128) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_payouts_open(&who)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			let mut amount = Escrow::<T>::take(stream_id, &who);
+			ensure!(!amount.is_zero(), Error::<T>::NothingToWithdraw);
+			SweepsDue::<T>::remove(stream_id, &who);
+			if let Some(policy) = SweepPolicies::<T>::get(&who) {
+				let excess = amount.saturating_sub(policy.threshold);
+				if !excess.is_zero() {
+					Self::sweep(stream_id, &stream, &who, policy.cold_address, excess)?;
+					amount = policy.threshold;
+				}
+			}
+			if !amount.is_zero() {
+				let payee = Self::payee(&stream, &who);
+				if Self::is_dust(&stream, &payee, amount) {
+					return Self::handle_dust(stream_id, &stream, &who, amount)
+				}
+				let hedged = Self::hedge(stream_id, &stream, &who, &payee, amount);
+				let unhedged = amount.saturating_sub(hedged);
+				if !unhedged.is_zero() {
+					Self::pay_from_escrow(stream_id, &stream, &payee, unhedged)?;
+				}
+				Self::note_routed(stream_id, &stream, &who, amount);
+			}
+
+			Self::deposit_event(Event::Withdrawn { stream_id, who, amount });
+
+			Ok(())
+		}
+
+		/// Set which streams and event types relayers should notify the caller about.
+		///
+		/// Following no streams with no event types clears the preferences.
+		#[pallet::call_index(11)]
+		#[pallet::weight((
+			T::WeightInfo::set_notification_prefs(followed.len() as u32),
+			DispatchClass::Normal
+		))]
+		pub fn set_notification_prefs(
+			origin: OriginFor<T>,
+			followed: BoundedVec<u128, T::MaxFollowedStreams>,
+			events: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(events & !NOTIFY_ALL == 0, Error::<T>::InvalidNotificationPrefs);
+
+			if followed.is_empty() && events == 0 {
+				NotificationPreferences::<T>::remove(&who);
+			} else {
+				let followed = followed.into_inner();
+				NotificationPreferences::<T>::insert(&who, NotificationPrefs { followed, events });
+			}
+
+			Self::deposit_event(Event::NotificationPrefsSet { who });
+
+			Ok(())
+		}
+
+		/// Set the title, category and content CID of a stream.
+		#[pallet::call_index(12)]
+		#[pallet::weight((T::WeightInfo::set_metadata(), DispatchClass::Normal))]
+		pub fn set_metadata(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			title: BoundedVec<u8, T::MaxMetadataLen>,
+			category: StreamCategory,
+			content_cid: BoundedVec<u8, T::MaxMetadataLen>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.creator == who, Error::<T>::NotStreamCreator);
+
+			let created_at = StreamMetadata::<T>::get(stream_id)
+				.map_or_else(frame_system::Pallet::<T>::block_number, |metadata| metadata.created_at);
+			StreamMetadata::<T>::insert(
+				stream_id,
+				StreamInfo {
+					title: title.into_inner(),
+					category,
+					content_cid: content_cid.into_inner(),
+					created_at,
+				},
+			);
+
+			Self::deposit_event(Event::MetadataSet { stream_id });
+
+			Ok(())
+		}
+
+		/// Remove a stream's metadata.
+		#[pallet::call_index(13)]
+		#[pallet::weight((T::WeightInfo::clear_metadata(), DispatchClass::Normal))]
+		pub fn clear_metadata(origin: OriginFor<T>, stream_id: u128) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.creator == who, Error::<T>::NotStreamCreator);
+
+			StreamMetadata::<T>::remove(stream_id);
+
+			Self::deposit_event(Event::MetadataCleared { stream_id });
+
+			Ok(())
+		}
+
+		/// Refund the reservations viewers still hold on a stream that ended at least
+		/// `SettlementWindow` blocks ago.
+		///
+		/// Anyone may call this and is paid `SweepReward` from the platform account for every
+		/// reservation swept, as far as the platform account can afford it.
+		#[pallet::call_index(14)]
+		#[pallet::weight((
+			T::WeightInfo::sweep_expired(viewers.len() as u32),
+			DispatchClass::Normal
+		))]
+		pub fn sweep_expired(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			viewers: BoundedVec<T::AccountId, T::MaxViewersPerBatch>,
+		) -> DispatchResultWithPostInfo {
+			let sweeper = ensure_signed(origin)?;
+
+			let ended_at = EndedAt::<T>::get(stream_id).ok_or(Error::<T>::SettlementWindowOpen)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >=
+					ended_at.saturating_add(T::SettlementWindow::get()),
+				Error::<T>::SettlementWindowOpen
+			);
+
+			let listed = viewers.len() as u32;
+			let mut swept = 0u32;
+			for viewer in viewers {
+				if Balances::<T>::contains_key(stream_id, &viewer) {
+					Self::refund_viewer(stream_id, &viewer);
+					swept = swept.saturating_add(1);
+				}
+			}
+			ensure!(swept > 0, Error::<T>::NothingToSweep);
+
+			let mut reward = T::SweepReward::get().saturating_mul(swept.into());
+			if T::Currency::transfer(
+				&Self::platform_account(),
+				&sweeper,
+				reward,
+				ExistenceRequirement::KeepAlive,
+			)
+			.is_err()
+			{
+				reward = Zero::zero();
+			}
+
+			Self::deposit_event(Event::ExpiredSwept { stream_id, sweeper, swept, reward });
+
+			// Viewers with nothing left to sweep were only looked up
+			let skipped = listed.saturating_sub(swept);
+			Ok(Some(
+				T::WeightInfo::sweep_expired(swept)
+					.saturating_add(T::DbWeight::get().reads(skipped.into())),
+			)
+			.into())
+		}
+
+		/// Offer a discount to viewers who know a code.
+		///
+		/// Only the hash of the code is published; viewers redeem it with `commit_promo` and
+		/// `reveal_promo`.
+		#[pallet::call_index(15)]
+		#[pallet::weight((T::WeightInfo::create_promo(), DispatchClass::Normal))]
+		pub fn create_promo(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			code_hash: T::Hash,
+			discount: Permill,
+			max_uses: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.creator == who, Error::<T>::NotStreamCreator);
+
+			Promos::<T>::insert(stream_id, code_hash, Promo { discount, remaining_uses: max_uses });
+
+			Self::deposit_event(Event::PromoCreated { stream_id, code_hash });
+
+			Ok(())
+		}
+
+		/// Commit to redeeming a promo code without revealing it.
+		///
+		/// `commitment` is the hash of the code, the caller's SCALE-encoded account id and a
+		/// salt, concatenated. A new commitment replaces the previous one.
+		#[pallet::call_index(16)]
+		#[pallet::weight((T::WeightInfo::commit_promo(), DispatchClass::Normal))]
+		pub fn commit_promo(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			commitment: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Streams::<T>::contains_key(stream_id), Error::<T>::StreamNotFound);
+			let now = frame_system::Pallet::<T>::block_number();
+			PromoCommitments::<T>::insert(stream_id, &who, (commitment, now));
+
+			Self::deposit_event(Event::PromoCommitted { stream_id, viewer: who });
+
+			Ok(())
+		}
+
+		/// Reveal a committed promo code and watch the stream at the discounted price.
+		///
+		/// The commitment binds the code to the caller, so a code copied from the pool is
+		/// useless to anyone else.
+		#[pallet::call_index(17)]
+		#[pallet::weight((T::WeightInfo::reveal_promo(), DispatchClass::Normal))]
+		pub fn reveal_promo(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			code: BoundedVec<u8, T::MaxPromoCodeLen>,
+			salt: [u8; 32],
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			Self::check_join_gate(stream_id, &who)?;
+			let (commitment, committed_at) = PromoCommitments::<T>::get(stream_id, &who)
+				.ok_or(Error::<T>::NoPromoCommitment)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() > committed_at,
+				Error::<T>::RevealTooEarly
+			);
+			ensure!(
+				commitment == Self::promo_commitment(&code, &who, &salt),
+				Error::<T>::CommitmentMismatch
+			);
+
+			let code_hash = T::Hashing::hash(&code);
+			let mut promo = Promos::<T>::get(stream_id, code_hash).ok_or(Error::<T>::PromoNotFound)?;
+			ensure!(promo.remaining_uses > 0, Error::<T>::PromoExhausted);
+			promo.remaining_uses -= 1;
+			Promos::<T>::insert(stream_id, code_hash, promo.clone());
+			PromoCommitments::<T>::remove(stream_id, &who);
+
+			let price = Self::price_for(stream_id, &stream, &who);
+			let price_per_second = price.saturating_sub(promo.discount * price);
+			ViewerPrices::<T>::insert(stream_id, &who, price_per_second);
+
+			Self::deposit_event(Event::PromoRedeemed { stream_id, viewer: who, price_per_second });
+
+			Ok(())
+		}
+
+		/// Schedule a new per-second price for a stream.
+		///
+		/// The price takes effect `PriceChangeDelay` blocks from now, so viewers are charged
+		/// the rate they joined at until then. A new schedule replaces a pending one.
+		#[pallet::call_index(18)]
+		#[pallet::weight((T::WeightInfo::update_price(), DispatchClass::Normal))]
+		pub fn update_price(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			new_price: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.creator == who, Error::<T>::NotStreamCreator);
+			ensure!(stream.status != StreamStatus::Ended, Error::<T>::StreamEnded);
+
+			let effective_at = frame_system::Pallet::<T>::block_number()
+				.saturating_add(T::PriceChangeDelay::get().max(One::one()));
+			PendingPrices::<T>::insert(stream_id, (new_price, effective_at));
+			PriceChangesDue::<T>::append(effective_at, stream_id);
+
+			Self::deposit_event(Event::PriceChangeScheduled { stream_id, new_price, effective_at });
+
+			Ok(())
+		}
+
+		/// Reserve more watch time on a stream the caller is already watching.
+		#[pallet::call_index(19)]
+		#[pallet::weight((T::WeightInfo::top_up(), DispatchClass::Normal))]
+		pub fn top_up(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			additional_seconds: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.status != StreamStatus::Ended, Error::<T>::StreamEnded);
+			ensure!(ViewerLastTick::<T>::contains_key(stream_id, &who), Error::<T>::Unauthorized);
+
+			let amount = Self::price_for(stream_id, &stream, &who)
+				.checked_mul(&additional_seconds.into())
+				.ok_or(Error::<T>::ArithmeticOverflow)?;
+			Self::hold(stream_id, &stream, &who, amount)?;
+			Balances::<T>::mutate(stream_id, &who, |reserved| *reserved = reserved.saturating_add(amount));
+
+			Self::deposit_event(Event::ToppedUp { stream_id, viewer: who, amount });
+
+			Ok(())
+		}
+
+		/// Hand a stream over to another account.
+		///
+		/// Lets a creator whose key was recovered move their streams to a new key through
+		/// the recovery proxy. Earnings already escrowed stay with the previous creator and
+		/// are withdrawn by them. The stream's handle moves along with it, its deposit taken
+		/// from the new creator and given back to the previous one, and the new creator is
+		/// bonded as its reporter.
+		///
+		/// Streams with held payments, or whose creator has open disputes, cannot be handed
+		/// over until these are settled.
+		#[pallet::call_index(20)]
+		#[pallet::weight((T::WeightInfo::transfer_stream(), DispatchClass::Normal))]
+		pub fn transfer_stream(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			new_creator: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.creator == who, Error::<T>::NotStreamCreator);
+			ensure!(
+				PendingSettlements::<T>::iter_prefix(stream_id).next().is_none(),
+				Error::<T>::SettlementsPending
+			);
+			ensure!(OpenDisputes::<T>::get(&who) == 0, Error::<T>::DisputesOpen);
+
+			Self::ensure_reporter_bond(&new_creator)?;
+			if let Some(key) = StreamHandles::<T>::get(stream_id) {
+				Handles::<T>::try_mutate(&key, |claim| -> DispatchResult {
+					let Some(claim) = claim else { return Ok(()) };
+					Self::hold_native(HoldReason::HandleDeposit, &new_creator, claim.deposit)?;
+					Self::release_native(HoldReason::HandleDeposit, &claim.owner, claim.deposit);
+					claim.owner = new_creator.clone();
+					Ok(())
+				})?;
+			}
+
+			stream.creator = new_creator.clone();
+			// The new creator chooses where their own earnings go
+			stream.payout_account = None;
+			Streams::<T>::insert(stream_id, stream);
+
+			Self::deposit_event(Event::StreamTransferred { stream_id, from: who, to: new_creator });
+
+			Ok(())
+		}
+
+		/// Tip a live stream's creator, optionally with a message shown alongside the stream.
+		///
+		/// The platform fee is taken from the tip and the rest is paid straight to the
+		/// creator, or their payout account; co-hosts share only in watch-time payments. Tips
+		/// below the existential deposit fail with `BelowExistentialDeposit`, and only the
+		/// latest `MaxTipsPerStream` tips of a stream are kept.
+		#[pallet::call_index(21)]
+		#[pallet::weight((T::WeightInfo::tip(), DispatchClass::Normal))]
+		pub fn tip(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			amount: BalanceOf<T>,
+			message: Option<BoundedVec<u8, T::MaxTipMessageLen>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.status == StreamStatus::Live, Error::<T>::StreamNotLive);
+			ensure!(
+				amount >= T::Currency::minimum_balance(),
+				Error::<T>::BelowExistentialDeposit
+			);
+
+			let fee = Self::effective_fee(stream_id) * amount;
+			if !fee.is_zero() {
+				T::Currency::transfer(
+					&who,
+					&Self::platform_account(),
+					fee,
+					ExistenceRequirement::KeepAlive,
+				)?;
+			}
+			let payee = Self::payee(&stream, &stream.creator);
+			T::Currency::transfer(
+				&who,
+				&payee,
+				amount.saturating_sub(fee),
+				ExistenceRequirement::KeepAlive,
+			)?;
+			Self::note_routed(stream_id, &stream, &stream.creator, amount.saturating_sub(fee));
+
+			let message = message.map(BoundedVec::into_inner);
+			let tip = Tip {
+				tipper: who.clone(),
+				amount,
+				message: message.clone(),
+				block: frame_system::Pallet::<T>::block_number(),
+			};
+			// Once the stream keeps its most tips, the oldest makes room
+			Tips::<T>::mutate(stream_id, |tips| {
+				let _ = tips.force_insert_keep_right(tips.len(), tip);
+			});
+
+			Self::deposit_event(Event::TipReceived { stream_id, tipper: who, amount, message });
+
+			Ok(())
+		}
+
+		/// Leave a stream automatically once settling it takes the caller's total balance,
+		/// free and reserved, below `threshold`.
+		///
+		/// The remaining reserve is returned on leaving. A threshold of zero turns this off.
+		#[pallet::call_index(22)]
+		#[pallet::weight((T::WeightInfo::set_auto_leave_threshold(), DispatchClass::Normal))]
+		pub fn set_auto_leave_threshold(
+			origin: OriginFor<T>,
+			threshold: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			if threshold.is_zero() {
+				AutoLeaveThresholds::<T>::remove(&who);
+			} else {
+				AutoLeaveThresholds::<T>::insert(&who, threshold);
+			}
+
+			Self::deposit_event(Event::AutoLeaveThresholdSet { who, threshold });
+
+			Ok(())
+		}
+
+		/// Force up to `max_viewers` viewers off a stream and refund their reservations.
+		///
+		/// For incident response on a hijacked stream. Viewers still holding a reservation
+		/// after this call are no longer settled and are flagged by `more_remain`.
+		#[pallet::call_index(23)]
+		#[pallet::weight((
+			T::WeightInfo::force_leave_stream(*max_viewers),
+			DispatchClass::Operational
+		))]
+		pub fn force_leave_stream(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			max_viewers: u32,
+		) -> DispatchResultWithPostInfo {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			let revoked = Self::refund_viewers(stream_id, max_viewers);
+			// One read, however many are left: the caller revokes the rest in further calls
+			let more_remain = Balances::<T>::iter_key_prefix(stream_id).next().is_some();
+
+			Self::deposit_event(Event::StreamAccessRevoked { stream_id, revoked, more_remain });
+
+			Ok(Some(T::WeightInfo::force_leave_stream(revoked)).into())
+		}
+
+		/// Force an account off each of `stream_ids` and refund its reservations.
+		///
+		/// For incident response on a compromised account; streams the account is not
+		/// watching are skipped.
+		#[pallet::call_index(24)]
+		#[pallet::weight((
+			T::WeightInfo::force_leave_account(stream_ids.len() as u32),
+			DispatchClass::Operational
+		))]
+		pub fn force_leave_account(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			stream_ids: BoundedVec<u128, T::MaxViewersPerBatch>,
+		) -> DispatchResultWithPostInfo {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			let listed = stream_ids.len() as u32;
+			let mut revoked = 0u32;
+			for stream_id in stream_ids {
+				if !Balances::<T>::contains_key(stream_id, &who) {
+					continue
+				}
+				Self::remove_viewer(stream_id, &who);
+				Self::refund_viewer(stream_id, &who);
+				revoked = revoked.saturating_add(1);
+			}
+
+			Self::deposit_event(Event::AccountAccessRevoked { who, revoked });
+
+			// Streams the account was not watching were only looked up
+			let skipped = listed.saturating_sub(revoked);
+			Ok(Some(
+				T::WeightInfo::force_leave_account(revoked)
+					.saturating_add(T::DbWeight::get().reads(skipped.into())),
+			)
+			.into())
+		}
+
+		/// Take a stream live automatically at `starts_at`, in unix seconds UTC.
+		///
+		/// The start is converted to an estimated block and re-anchored on the clock as block
+		/// times drift. `utc_offset_minutes` is the creator's time zone, kept so the start can
+		/// be shown in their local time. Scheduling again replaces the previous schedule.
+		#[pallet::call_index(25)]
+		#[pallet::weight((T::WeightInfo::schedule_stream(), DispatchClass::Normal))]
+		pub fn schedule_stream(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			starts_at: u64,
+			utc_offset_minutes: i16,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.creator == who, Error::<T>::NotStreamCreator);
+			ensure!(stream.status == StreamStatus::Created, Error::<T>::InvalidStatusTransition);
+			ensure!((-720..=840).contains(&utc_offset_minutes), Error::<T>::InvalidUtcOffset);
+			Self::ensure_reporter_bond(&who)?;
+
+			let now_ms = T::UnixTime::now().as_millis().saturated_into::<u64>();
+			let starts_at_ms = starts_at.saturating_mul(1000);
+			ensure!(starts_at_ms > now_ms, Error::<T>::StartTimeInPast);
+
+			let block = frame_system::Pallet::<T>::block_number();
+			let estimated_block = Self::estimate_block(block, now_ms, starts_at_ms);
+			StreamSchedules::<T>::insert(
+				stream_id,
+				StreamSchedule { starts_at, utc_offset_minutes, estimated_block },
+			);
+			ScheduledStartsDue::<T>::append(estimated_block, stream_id);
+
+			Self::deposit_event(Event::StreamScheduled {
+				stream_id,
+				starts_at,
+				utc_offset_minutes,
+				estimated_block,
+			});
+
+			Ok(())
+		}
+
+		/// Reserve `amount` for watching any of `creator`'s streams.
+		///
+		/// Ticks are charged to the stream's own reserve first and to this budget once it runs
+		/// out, so viewers can join the creator's streams without reserving for each.
+		#[pallet::call_index(26)]
+		#[pallet::weight((T::WeightInfo::top_up_budget(), DispatchClass::Normal))]
+		pub fn top_up_budget(
+			origin: OriginFor<T>,
+			creator: T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::ensure_below_hold_cap(amount)?;
+			Self::hold_native(HoldReason::StreamDeposit, &who, amount)?;
+			CreatorBudgets::<T>::mutate(&who, &creator, |budget| {
+				budget.reserved = budget.reserved.saturating_add(amount)
+			});
+
+			Self::deposit_event(Event::BudgetToppedUp { viewer: who, creator, amount });
+
+			Ok(())
+		}
+
+		/// Return what is left of the caller's budget for `creator`'s streams.
+		///
+		/// The spend recorded for the creator is kept.
+		#[pallet::call_index(27)]
+		#[pallet::weight((T::WeightInfo::release_budget(), DispatchClass::Normal))]
+		pub fn release_budget(origin: OriginFor<T>, creator: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut budget = CreatorBudgets::<T>::get(&who, &creator);
+			let amount = sp_std::mem::take(&mut budget.reserved);
+			ensure!(!amount.is_zero(), Error::<T>::NoBudget);
+			Self::release_native(HoldReason::StreamDeposit, &who, amount);
+			CreatorBudgets::<T>::insert(&who, &creator, budget);
+
+			Self::deposit_event(Event::BudgetReleased { viewer: who, creator, amount });
+
+			Ok(())
+		}
+
+		/// Prune `viewer`'s watch history on `stream_ids`.
+		///
+		/// Viewers may prune their own history at any time; anyone else only history the viewer
+		/// has not added to for `ViewerStatsRetention` blocks.
+		#[pallet::call_index(28)]
+		#[pallet::weight((
+			T::WeightInfo::prune_viewer_stats(stream_ids.len() as u32),
+			DispatchClass::Normal
+		))]
+		pub fn prune_viewer_stats(
+			origin: OriginFor<T>,
+			viewer: T::AccountId,
+			stream_ids: BoundedVec<u128, T::MaxViewersPerBatch>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let listed = stream_ids.len() as u32;
+			let mut pruned = 0u32;
+			for stream_id in stream_ids {
+				let Some(stats) = ViewerStats::<T>::get(&viewer, stream_id) else { continue };
+				let stale = now >=
+					stats.last_watched_block.saturating_add(T::ViewerStatsRetention::get());
+				if who == viewer || stale {
+					ViewerStats::<T>::remove(&viewer, stream_id);
+					pruned = pruned.saturating_add(1);
+				}
+			}
+			ensure!(pruned > 0, Error::<T>::NothingToPrune);
+
+			Self::deposit_event(Event::ViewerStatsPruned { viewer, pruned });
+
+			// History left in place was only looked up
+			let skipped = listed.saturating_sub(pruned);
+			Ok(Some(
+				T::WeightInfo::prune_viewer_stats(pruned)
+					.saturating_add(T::DbWeight::get().reads(skipped.into())),
+			)
+			.into())
+		}
+
+		/// Offer a flat-rate pass to a stream, lasting `duration_blocks` for `price`.
+		///
+		/// Tiers are numbered in the order they are created.
+		#[pallet::call_index(29)]
+		#[pallet::weight((T::WeightInfo::create_subscription_tier(), DispatchClass::Normal))]
+		pub fn create_subscription_tier(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			price: BalanceOf<T>,
+			duration_blocks: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.creator == who, Error::<T>::NotStreamCreator);
+			ensure!(stream.status != StreamStatus::Ended, Error::<T>::StreamEnded);
+
+			let tier = SubscriptionTiers::<T>::try_mutate(stream_id, |tiers| {
+				tiers
+					.try_push(SubscriptionTier { price, duration_blocks })
+					.map_err(|_| Error::<T>::TooManyTiers)?;
+				Ok::<_, Error<T>>(tiers.len() as u32 - 1)
+			})?;
+
+			Self::deposit_event(Event::SubscriptionTierCreated {
+				stream_id,
+				tier,
+				price,
+				duration_blocks,
+			});
+
+			Ok(())
+		}
+
+		/// Buy a pass to a stream and join it.
+		///
+		/// Ticks are not charged while the pass lasts; watch time still accrues. Buying again
+		/// before it runs out extends it.
+		#[pallet::call_index(30)]
+		#[pallet::weight((T::WeightInfo::subscribe(), DispatchClass::Normal))]
+		pub fn subscribe(origin: OriginFor<T>, stream_id: u128, tier: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.status != StreamStatus::Ended, Error::<T>::StreamEnded);
+			let SubscriptionTier { price, duration_blocks } = SubscriptionTiers::<T>::get(stream_id)
+				.get(tier as usize)
+				.cloned()
+				.ok_or(Error::<T>::TierNotFound)?;
+
+			// The pass is paid like watch time, so the platform fee and co-host shares apply
+			Self::hold(stream_id, &stream, &who, price)?;
+			Self::split_revenue(stream_id, &stream, &who, price)?;
+			CreatorEarnings::<T>::mutate(&stream.creator, Self::current_era(), |earnings| {
+				earnings.gross = earnings.gross.saturating_add(price);
+			});
+			Self::record_settled_value(stream_id, &stream, 0, price);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let expires_at = Subscriptions::<T>::get(stream_id, &who)
+				.unwrap_or(now)
+				.max(now)
+				.saturating_add(duration_blocks);
+			Subscriptions::<T>::insert(stream_id, &who, expires_at);
+
+			Self::do_join(who.clone(), stream_id, &stream, Zero::zero(), 0)?;
+
+			Self::deposit_event(Event::Subscribed { stream_id, viewer: who, tier, expires_at });
+
+			Ok(())
+		}
+
+		/// Make a co-host's share of a stream vest linearly over `period` blocks.
+		///
+		/// Shares paid from then on are held back and released evenly until `period` blocks
+		/// after the latest payment, for the co-host to claim with `claim_vested`. A period of
+		/// zero pays the co-host's share straight to escrow again; earnings already vesting
+		/// keep vesting.
+		#[pallet::call_index(31)]
+		#[pallet::weight((T::WeightInfo::set_split_vesting(), DispatchClass::Normal))]
+		pub fn set_split_vesting(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			co_host: T::AccountId,
+			period: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.creator == who, Error::<T>::NotStreamCreator);
+			ensure!(
+				RevenueSplits::<T>::get(stream_id).iter().any(|(account, _)| *account == co_host),
+				Error::<T>::NotACoHost
+			);
+
+			if period.is_zero() {
+				SplitVesting::<T>::remove(stream_id, &co_host);
+			} else {
+				SplitVesting::<T>::insert(stream_id, &co_host, period);
+			}
+
+			Self::deposit_event(Event::SplitVestingSet { stream_id, co_host, period });
+
+			Ok(())
+		}
+
+		/// Claim the caller's earnings from a stream that have vested so far.
+		#[pallet::call_index(32)]
+		#[pallet::weight((T::WeightInfo::claim_vested(), DispatchClass::Normal))]
+		pub fn claim_vested(origin: OriginFor<T>, stream_id: u128) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_payouts_open(&who)?;
+
+			let mut schedule = VestingSchedules::<T>::get(stream_id, &who)
+				.ok_or(Error::<T>::NothingToWithdraw)?;
+			Self::release_vested(&mut schedule, frame_system::Pallet::<T>::block_number());
+			let amount = sp_std::mem::take(&mut schedule.claimable);
+			ensure!(!amount.is_zero(), Error::<T>::NothingToWithdraw);
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			Self::pay_from_escrow(stream_id, &stream, &who, amount)?;
+
+			schedule.claimed = schedule.claimed.saturating_add(amount);
+			if schedule.unvested.is_zero() {
+				VestingSchedules::<T>::remove(stream_id, &who);
+			} else {
+				VestingSchedules::<T>::insert(stream_id, &who, schedule);
+			}
+
+			Self::deposit_event(Event::VestedClaimed { stream_id, who, amount });
+
+			Ok(())
+		}
+
+		/// Dispute the caller's held payments on a stream for ticks `from_tick` to `to_tick`.
+		///
+		/// The payments are frozen on the caller's reserve until the admin origin resolves
+		/// the dispute with `resolve_dispute`.
+		#[pallet::call_index(33)]
+		#[pallet::weight((T::WeightInfo::dispute_ticks(), DispatchClass::Normal))]
+		pub fn dispute_ticks(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			from_tick: u64,
+			to_tick: u64,
+		) -> DispatchResult {
+			let viewer = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			let (disputed, held): (Vec<_>, Vec<_>) =
+				PendingSettlements::<T>::get(stream_id, &viewer).into_iter().partition(|pending| {
+					pending.from_tick >= from_tick && pending.to_tick <= to_tick
+				});
+			ensure!(!disputed.is_empty(), Error::<T>::NothingToDispute);
+			if held.is_empty() {
+				PendingSettlements::<T>::remove(stream_id, &viewer);
+			} else {
+				PendingSettlements::<T>::insert(stream_id, &viewer, held);
+			}
+
+			let amount = disputed.iter().fold(Zero::zero(), |total: BalanceOf<T>, pending| {
+				total.saturating_add(pending.amount)
+			});
+			let dispute_id = NextDisputeId::<T>::mutate(|next| {
+				let id = *next;
+				*next = next.saturating_add(1);
+				id
+			});
+			Disputes::<T>::insert(
+				dispute_id,
+				Dispute {
+					stream_id,
+					viewer: viewer.clone(),
+					reporter: stream.creator.clone(),
+					amount,
+					from_tick,
+					to_tick,
+				},
+			);
+			OpenDisputes::<T>::mutate(&stream.creator, |open| *open = open.saturating_add(1));
+
+			Self::deposit_event(Event::TicksDisputed { dispute_id, stream_id, viewer, amount });
+
+			Ok(())
+		}
+
+		/// Resolve a dispute.
+		///
+		/// An upheld dispute refunds the viewer and pays them up to the disputed amount out of
+		/// the creator's reporter bond, leaving the rest for other disputes; a rejected one
+		/// pays the frozen payments out as usual. The creator's streams are not settled again
+		/// until they top the bond back up with `bond_reporter`.
+		#[pallet::call_index(34)]
+		#[pallet::weight((T::WeightInfo::resolve_dispute(), DispatchClass::Operational))]
+		pub fn resolve_dispute(
+			origin: OriginFor<T>,
+			dispute_id: u32,
+			upheld: bool,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			let dispute = Disputes::<T>::take(dispute_id).ok_or(Error::<T>::DisputeNotFound)?;
+			OpenDisputes::<T>::mutate_exists(&dispute.reporter, |open| {
+				*open = open.unwrap_or_default().checked_sub(1).filter(|open| *open > 0)
+			});
+
+			let stream = Streams::<T>::get(dispute.stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			let slashed = if upheld {
+				Self::release(dispute.stream_id, &stream, &dispute.viewer, dispute.amount);
+				let bonded = ReporterStakes::<T>::get(&dispute.reporter)
+					.map_or_else(Zero::zero, |stake| stake.bonded);
+				let slashed = Self::pay_native_held(
+					HoldReason::DisputeBond,
+					&dispute.reporter,
+					&dispute.viewer,
+					bonded.min(dispute.amount),
+					Precision::BestEffort,
+				)?;
+				ReporterStakes::<T>::mutate(&dispute.reporter, |stake| {
+					if let Some(stake) = stake {
+						stake.bonded = stake.bonded.saturating_sub(slashed);
+					}
+				});
+				slashed
+			} else {
+				Self::split_revenue(dispute.stream_id, &stream, &dispute.viewer, dispute.amount)?;
+				Zero::zero()
+			};
+
+			Self::deposit_event(Event::DisputeResolved { dispute_id, upheld, slashed });
+
+			Ok(())
+		}
+
+		/// Release the caller's reporter bond.
+		///
+		/// Only once none of their streams' payments can still be disputed and every dispute
+		/// against them is resolved.
+		#[pallet::call_index(35)]
+		#[pallet::weight((T::WeightInfo::unbond_reporter(), DispatchClass::Normal))]
+		pub fn unbond_reporter(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stake = ReporterStakes::<T>::get(&who).ok_or(Error::<T>::NoReporterBond)?;
+			ensure!(OpenDisputes::<T>::get(&who) == 0, Error::<T>::DisputesOpen);
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				now >= stake.last_reported.saturating_add(T::SettlementDelay::get()),
+				Error::<T>::SettlementsPending
+			);
+
+			ReporterStakes::<T>::remove(&who);
+			Self::release_native(HoldReason::DisputeBond, &who, stake.bonded);
+
+			Self::deposit_event(Event::ReporterUnbonded { who, amount: stake.bonded });
+
+			Ok(())
+		}
+
+		/// Price a stream in `asset_id` instead of the native token, or in the native token
+		/// again with `None`, before anyone has paid into it.
+		///
+		/// The creator tops the stream's escrow account up to the native existential deposit
+		/// and the asset's minimum balance, so it can hold viewers' payments in the asset.
+		#[pallet::call_index(36)]
+		#[pallet::weight((T::WeightInfo::set_stream_asset(), DispatchClass::Normal))]
+		pub fn set_stream_asset(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			asset_id: Option<AssetIdOf<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.creator == who, Error::<T>::NotStreamCreator);
+			ensure!(stream.status == StreamStatus::Created, Error::<T>::InvalidStatusTransition);
+			ensure!(
+				Balances::<T>::iter_key_prefix(stream_id).next().is_none() &&
+					Escrow::<T>::iter_key_prefix(stream_id).next().is_none(),
+				Error::<T>::StreamInUse
+			);
+
+			if let Some(asset) = asset_id.clone() {
+				ensure!(T::Assets::asset_exists(asset.clone()), Error::<T>::UnknownAsset);
+				let escrow = Self::escrow_account(stream_id);
+				let missing = T::Currency::minimum_balance()
+					.saturating_sub(T::Currency::free_balance(&escrow));
+				if !missing.is_zero() {
+					T::Currency::transfer(&who, &escrow, missing, ExistenceRequirement::KeepAlive)?;
+				}
+				let missing = T::Assets::minimum_balance(asset.clone())
+					.saturating_sub(T::Assets::balance(asset.clone(), &escrow));
+				if !missing.is_zero() {
+					T::Assets::transfer(asset, &who, &escrow, missing, Preservation::Preserve)?;
+				}
+			}
+
+			stream.asset_id = asset_id.clone();
+			Streams::<T>::insert(stream_id, stream);
+			// A reporting unit chosen for the old currency may not fit the new one
+			ReportingUnits::<T>::remove(stream_id);
+
+			Self::deposit_event(Event::StreamAssetSet { stream_id, asset_id });
+
+			Ok(())
+		}
+
+		/// Gate who may join a stream and redeem its promo codes, or lift the gate with
+		/// `None`.
+		///
+		/// Viewers joining with one of the creator's vouchers or a subscription are let through.
+		#[pallet::call_index(37)]
+		#[pallet::weight((T::WeightInfo::set_join_gate(), DispatchClass::Normal))]
+		pub fn set_join_gate(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			gate: Option<JoinGate<BlockNumberFor<T>>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.creator == who, Error::<T>::NotStreamCreator);
+
+			match &gate {
+				Some(limits) => {
+					ensure!(
+						limits.min_account_age <= T::MaxGateAccountAge::get() &&
+							limits.min_watch_seconds <= T::MaxGateWatchSeconds::get(),
+						Error::<T>::JoinGateTooStrict
+					);
+					ensure!(
+						!limits.require_identity ||
+							T::IdentityVerifier::is_supported() ||
+							!AttestationSources::<T>::get().is_empty(),
+						Error::<T>::IdentityUnsupported
+					);
+					JoinGates::<T>::insert(stream_id, limits);
+				},
+				None => JoinGates::<T>::remove(stream_id),
+			}
+
+			Self::deposit_event(Event::JoinGateSet { stream_id, gate });
+
+			Ok(())
+		}
+
+		/// Let `host` anchor the stream's position alongside the creator, or take the right
+		/// back with `None`.
+		#[pallet::call_index(38)]
+		#[pallet::weight((T::WeightInfo::set_sync_host(), DispatchClass::Normal))]
+		pub fn set_sync_host(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			host: Option<T::AccountId>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.creator == who, Error::<T>::NotStreamCreator);
+
+			match &host {
+				Some(host) => SyncHosts::<T>::insert(stream_id, host),
+				None => SyncHosts::<T>::remove(stream_id),
+			}
+
+			Self::deposit_event(Event::SyncHostSet { stream_id, host });
+
+			Ok(())
+		}
+
+		/// Anchor where a live stream's media is at this block, in milliseconds, so watch
+		/// party clients can sync to it.
+		///
+		/// Only the latest `MaxPositionAnchors` anchors are kept; anchoring again in the same
+		/// block replaces that block's anchor.
+		#[pallet::call_index(39)]
+		#[pallet::weight((T::WeightInfo::anchor_position(), DispatchClass::Normal))]
+		pub fn anchor_position(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			media_timestamp: u64,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(
+				stream.creator == who || SyncHosts::<T>::get(stream_id).as_ref() == Some(&who),
+				Error::<T>::NotSyncHost
+			);
+			ensure!(stream.status == StreamStatus::Live, Error::<T>::StreamNotLive);
+
+			let block = frame_system::Pallet::<T>::block_number();
+			PositionAnchors::<T>::mutate(stream_id, |anchors| {
+				if anchors.last().map_or(false, |anchor| anchor.block == block) {
+					anchors.pop();
+				}
+				// Drops the oldest anchor once full
+				let anchor = PositionAnchor { block, media_timestamp };
+				let _ = anchors.force_insert_keep_right(anchors.len(), anchor);
+			});
+
+			Self::deposit_event(Event::PositionAnchored { stream_id, host: who, media_timestamp });
+
+			Ok(())
+		}
+
+		/// Stop `who` withdrawing or claiming earnings for `duration` blocks while suspected
+		/// fraud is investigated.
+		///
+		/// Freezing an account that is frozen already extends its freeze if it would end
+		/// later. Earnings keep accruing in escrow and can be claimed once the freeze runs out
+		/// or is lifted with `unfreeze_payouts`.
+		#[pallet::call_index(40)]
+		#[pallet::weight((T::WeightInfo::freeze_payouts(), DispatchClass::Operational))]
+		pub fn freeze_payouts(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			duration: BlockNumberFor<T>,
+		) -> DispatchResult {
+			T::PayoutFreezeOrigin::ensure_origin(origin)?;
+			ensure!(!duration.is_zero(), Error::<T>::InvalidFreezeDuration);
+
+			let until = frame_system::Pallet::<T>::block_number().saturating_add(duration);
+			match PayoutFreezes::<T>::get(&who) {
+				Some(frozen_until) if frozen_until >= until => return Ok(()),
+				Some(_) => Self::deposit_event(Event::PayoutFreezeExtended {
+					who: who.clone(),
+					until,
+				}),
+				None => Self::deposit_event(Event::PayoutsFrozen { who: who.clone(), until }),
+			}
+			PayoutFreezes::<T>::insert(&who, until);
+			PayoutFreezeExpiries::<T>::append(until, who);
+
+			Ok(())
+		}
+
+		/// Lift a payout freeze before it runs out, once an investigation has cleared `who`.
+		#[pallet::call_index(41)]
+		#[pallet::weight((T::WeightInfo::unfreeze_payouts(), DispatchClass::Operational))]
+		pub fn unfreeze_payouts(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::PayoutFreezeOrigin::ensure_origin(origin)?;
+
+			PayoutFreezes::<T>::take(&who).ok_or(Error::<T>::PayoutsNotFrozen)?;
+
+			Self::deposit_event(Event::PayoutsReleased { who });
+
+			Ok(())
+		}
+
+		/// Settle the ticks the block author found due, as an inherent.
+		///
+		/// Ticks that are not due or cannot be covered are skipped, so the inherent never
+		/// fails; each stream settled is reported in an `IntervalSettled` event.
+		#[pallet::call_index(42)]
+		#[pallet::weight((
+			T::WeightInfo::record_tick()
+				.saturating_add(T::SettlementExtension::weight())
+				.saturating_mul(ticks.len() as u64),
+			DispatchClass::Mandatory
+		))]
+		pub fn settle_inherent(
+			origin: OriginFor<T>,
+			ticks: BoundedVec<(u128, T::AccountId, u32), T::MaxViewersPerBatch>,
+		) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+
+			// Each stream's viewers with what has been settled on it
+			let mut streams = BTreeMap::new();
+			// Ticks turned away before they are charged cost a couple of reads, and the weight
+			// reserved for them is given back to the block
+			let batched = ticks.len() as u32;
+			let mut attempted = 0u32;
+			for (stream_id, viewer, viewer_ticks) in ticks {
+				if !streams.contains_key(&stream_id) {
+					match Streams::<T>::get(stream_id) {
+						Some(stream)
+							if stream.status == StreamStatus::Live &&
+								stream.content_type == ContentType::Live =>
+						{
+							let viewers = StreamViewers::<T>::get(stream_id);
+							streams.insert(stream_id, (stream, viewers, IntervalTotals::default()));
+						},
+						_ => {
+							Self::deposit_event(Event::TickSkipped { stream_id, viewer });
+							continue
+						},
+					}
+				}
+				let Some((stream, viewers, totals)) = streams.get_mut(&stream_id) else { continue };
+
+				if Self::settle_batched(
+					stream_id,
+					stream,
+					viewers,
+					viewer,
+					viewer_ticks,
+					totals,
+				) {
+					attempted = attempted.saturating_add(1);
+				}
+			}
+
+			let now = frame_system::Pallet::<T>::block_number();
+			for (stream_id, (mut stream, _, totals)) in streams {
+				if totals.viewers == 0 {
+					continue
+				}
+				stream.last_tick = now;
+				Streams::<T>::insert(stream_id, stream);
+				Self::deposit_event(Self::interval_settled(stream_id, totals));
+			}
+
+			let skipped = batched.saturating_sub(attempted);
+			Ok(Some(
+				T::WeightInfo::record_tick()
+					.saturating_add(T::SettlementExtension::weight())
+					.saturating_mul(attempted.into())
+					.saturating_add(T::DbWeight::get().reads(2).saturating_mul(skipped.into())),
+			)
+			.into())
+		}
+
+		/// Claim `handle` for one of the caller's streams, reserving `HandleDeposit`.
+		///
+		/// Handles are first come, first served and unique regardless of case, so frontends
+		/// can tell a creator's stream from copycats with similar titles. A creator can move
+		/// a handle they hold to another of their streams without a further deposit.
+		#[pallet::call_index(43)]
+		#[pallet::weight((T::WeightInfo::claim_handle(), DispatchClass::Normal))]
+		pub fn claim_handle(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			handle: HandleOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.creator == who, Error::<T>::NotStreamCreator);
+			ensure!(!StreamHandles::<T>::contains_key(stream_id), Error::<T>::StreamHasHandle);
+			ensure!(
+				!handle.is_empty() &&
+					handle.iter().all(|c| c.is_ascii_alphanumeric() || *c == b'_'),
+				Error::<T>::InvalidHandle
+			);
+
+			let key = HandleOf::<T>::truncate_from(handle.to_ascii_lowercase());
+			let deposit = match Handles::<T>::get(&key) {
+				Some(claim) if claim.owner == who => {
+					StreamHandles::<T>::remove(claim.stream_id);
+					Self::deposit_event(Event::HandleReleased {
+						stream_id: claim.stream_id,
+						handle: claim.handle,
+					});
+					claim.deposit
+				},
+				Some(_) => return Err(Error::<T>::HandleTaken.into()),
+				None => {
+					let deposit = T::HandleDeposit::get();
+					Self::hold_native(HoldReason::HandleDeposit, &who, deposit)?;
+					deposit
+				},
+			};
+			Handles::<T>::insert(
+				&key,
+				HandleClaim { handle: handle.clone(), owner: who, stream_id, deposit },
+			);
+			StreamHandles::<T>::insert(stream_id, key);
+
+			Self::deposit_event(Event::HandleClaimed { stream_id, handle });
+
+			Ok(())
+		}
+
+		/// Give up a stream's handle, returning its deposit to the creator who claimed it.
+		#[pallet::call_index(44)]
+		#[pallet::weight((T::WeightInfo::release_handle(), DispatchClass::Normal))]
+		pub fn release_handle(origin: OriginFor<T>, stream_id: u128) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let key = StreamHandles::<T>::get(stream_id).ok_or(Error::<T>::NoHandle)?;
+			let claim = Handles::<T>::get(&key).ok_or(Error::<T>::NoHandle)?;
+			ensure!(claim.owner == who, Error::<T>::NotStreamCreator);
+
+			StreamHandles::<T>::remove(stream_id);
+			Handles::<T>::remove(&key);
+			Self::release_native(HoldReason::HandleDeposit, &who, claim.deposit);
+
+			Self::deposit_event(Event::HandleReleased { stream_id, handle: claim.handle });
+
+			Ok(())
+		}
+
+		/// Limit what the caller is charged per session and per UTC day, or lift both limits
+		/// with `None`.
+		///
+		/// A tick that would go over a limit is not charged: the session is paused with
+		/// `SpendingLimitReached` until the viewer resumes it with `resume_session`.
+		#[pallet::call_index(45)]
+		#[pallet::weight((T::WeightInfo::set_spending_limit(), DispatchClass::Normal))]
+		pub fn set_spending_limit(
+			origin: OriginFor<T>,
+			max_per_stream: Option<BalanceOf<T>>,
+			max_per_day: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			if max_per_stream.is_none() && max_per_day.is_none() {
+				SpendingLimits::<T>::remove(&who);
+			} else {
+				SpendingLimits::<T>::insert(&who, SpendingLimit { max_per_stream, max_per_day });
+			}
+
+			Self::deposit_event(Event::SpendingLimitSet { who, max_per_stream, max_per_day });
+
+			Ok(())
+		}
+
+		/// Keep watching a stream paused on the caller's spending limit.
+		///
+		/// The per-stream limit starts over from the resume, so each resume allows up to another
+		/// limit's worth of charges on the stream. The daily limit, shared by every stream, only
+		/// starts over the next UTC day. The time spent paused is not charged.
+		#[pallet::call_index(46)]
+		#[pallet::weight((T::WeightInfo::resume_session(), DispatchClass::Normal))]
+		pub fn resume_session(origin: OriginFor<T>, stream_id: u128) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Self::is_paused(stream_id, &who), Error::<T>::SessionNotPaused);
+			Self::move_session(stream_id, &who, SessionState::Active)?;
+			SessionSpending::<T>::remove(stream_id, &who);
+			ViewerLastTick::<T>::insert(stream_id, &who, Self::now());
+
+			Self::deposit_event(Event::SessionResumed { stream_id, viewer: who });
+
+			Ok(())
+		}
+
+		/// Change how many seconds of the stream each viewer watches for free.
+		///
+		/// Seconds viewers have already watched count against the new preview.
+		#[pallet::call_index(47)]
+		#[pallet::weight((T::WeightInfo::set_free_preview(), DispatchClass::Normal))]
+		pub fn set_free_preview(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			seconds: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.creator == who, Error::<T>::NotStreamCreator);
+
+			if seconds == 0 {
+				FreePreviews::<T>::remove(stream_id);
+			} else {
+				FreePreviews::<T>::insert(stream_id, seconds);
+			}
+
+			Self::deposit_event(Event::FreePreviewSet { stream_id, seconds });
+
+			Ok(())
+		}
+
+		/// Start watching a joined stream on a device, under a session id the device picks.
+		///
+		/// Fails with `TooManyDevices` once the viewer has as many sessions open as the
+		/// stream's device limit, one unless its creator changed it or exempted the viewer.
+		#[pallet::call_index(48)]
+		#[pallet::weight((T::WeightInfo::open_session(), DispatchClass::Normal))]
+		pub fn open_session(origin: OriginFor<T>, stream_id: u128, session: u64) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(StreamViewers::<T>::get(stream_id).contains(&who), Error::<T>::Unauthorized);
+			let limit = if DeviceLimitExemptions::<T>::contains_key(stream_id, &who) {
+				T::MaxDevicesPerViewer::get()
+			} else {
+				DeviceLimits::<T>::get(stream_id).unwrap_or(1)
+			};
+			ActiveSessions::<T>::try_mutate(stream_id, &who, |sessions| {
+				ensure!(!sessions.contains(&session), Error::<T>::SessionAlreadyOpen);
+				ensure!((sessions.len() as u32) < limit, Error::<T>::TooManyDevices);
+				sessions.try_push(session).map_err(|_| Error::<T>::TooManyDevices)
+			})?;
+
+			Self::deposit_event(Event::SessionOpened { stream_id, viewer: who, session });
+
+			Ok(())
+		}
+
+		/// Stop watching a stream on a device, freeing its place under the device limit.
+		#[pallet::call_index(49)]
+		#[pallet::weight((T::WeightInfo::close_session(), DispatchClass::Normal))]
+		pub fn close_session(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			session: u64,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ActiveSessions::<T>::try_mutate_exists(stream_id, &who, |sessions| {
+				let open = sessions.as_mut().ok_or(Error::<T>::SessionNotOpen)?;
+				let index = open
+					.iter()
+					.position(|open| *open == session)
+					.ok_or(Error::<T>::SessionNotOpen)?;
+				open.remove(index);
+				if open.is_empty() {
+					*sessions = None;
+				}
+				Ok::<_, Error<T>>(())
+			})?;
+
+			Self::deposit_event(Event::SessionClosed { stream_id, viewer: who, session });
+
+			Ok(())
+		}
+
+		/// Change how many devices each viewer may watch the stream on at once.
+		///
+		/// Sessions already open over a lowered limit stay open.
+		#[pallet::call_index(50)]
+		#[pallet::weight((T::WeightInfo::set_device_limit(), DispatchClass::Normal))]
+		pub fn set_device_limit(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			limit: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.creator == who, Error::<T>::NotStreamCreator);
+			ensure!(
+				(1..=T::MaxDevicesPerViewer::get()).contains(&limit),
+				Error::<T>::InvalidDeviceLimit
+			);
+
+			if limit == 1 {
+				DeviceLimits::<T>::remove(stream_id);
+			} else {
+				DeviceLimits::<T>::insert(stream_id, limit);
+			}
+
+			Self::deposit_event(Event::DeviceLimitSet { stream_id, limit });
+
+			Ok(())
+		}
+
+		/// Let `viewer` watch the stream on up to `MaxDevicesPerViewer` devices regardless of
+		/// its device limit, or hold them to it again.
+		#[pallet::call_index(51)]
+		#[pallet::weight((T::WeightInfo::set_device_exemption(), DispatchClass::Normal))]
+		pub fn set_device_exemption(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			viewer: T::AccountId,
+			exempt: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.creator == who, Error::<T>::NotStreamCreator);
+
+			if exempt {
+				DeviceLimitExemptions::<T>::insert(stream_id, &viewer, ());
+			} else {
+				DeviceLimitExemptions::<T>::remove(stream_id, &viewer);
+			}
+
+			Self::deposit_event(Event::DeviceExemptionSet { stream_id, viewer, exempt });
+
+			Ok(())
+		}
+
+		/// Withdraw the caller's referral earnings from a stream's escrow.
+		#[pallet::call_index(52)]
+		#[pallet::weight((T::WeightInfo::claim_referral_earnings(), DispatchClass::Normal))]
+		pub fn claim_referral_earnings(origin: OriginFor<T>, stream_id: u128) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_payouts_open(&who)?;
+
+			// Earnings made before a migration stay under the id they were made on
+			let current_id = Self::resolve_stream_id(stream_id);
+			let stream = Streams::<T>::get(current_id).ok_or(Error::<T>::StreamNotFound)?;
+			let amount = ReferralEarnings::<T>::take(&who, stream_id);
+			ensure!(!amount.is_zero(), Error::<T>::NothingToWithdraw);
+			Self::pay_from_escrow(current_id, &stream, &who, amount)?;
+
+			Self::deposit_event(Event::ReferralEarningsClaimed { stream_id, who, amount });
+
+			Ok(())
+		}
+
+		/// Cap how many viewers may watch the stream at once, or lift the cap with `None`.
+		///
+		/// Joins beyond the cap fail with `StreamFull`; viewers already watching over a
+		/// lowered cap stay on.
+		#[pallet::call_index(53)]
+		#[pallet::weight((T::WeightInfo::set_max_viewers(), DispatchClass::Normal))]
+		pub fn set_max_viewers(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			max_viewers: Option<u32>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.creator == who, Error::<T>::NotStreamCreator);
+			ensure!(max_viewers != Some(0), Error::<T>::InvalidMaxViewers);
+
+			MaxViewers::<T>::set(stream_id, max_viewers);
+
+			Self::deposit_event(Event::MaxViewersSet { stream_id, max_viewers });
+
+			Ok(())
+		}
+
+		/// Charge streams in `category` a platform fee other than `PlatformFee`, or go back to
+		/// `PlatformFee` with `None`.
+		///
+		/// Applies to tick payments and tips from the next settlement on.
+		#[pallet::call_index(54)]
+		#[pallet::weight((T::WeightInfo::set_category_fee(), DispatchClass::Operational))]
+		pub fn set_category_fee(
+			origin: OriginFor<T>,
+			category: StreamCategory,
+			fee: Option<Permill>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			CategoryFees::<T>::set(category, fee);
+			Self::deposit_event(Event::CategoryFeeSet { category, fee });
+			Ok(())
+		}
+
+		/// Book a seat on a scheduled stream before it goes live, reserving
+		/// `PreBookingDeposit`.
+		///
+		/// Booked seats count against the stream's viewer cap. The viewer joins the stream with
+		/// their deposit as its reserve once it goes live; the deposit is refunded if the
+		/// stream ends first, or is still not live `PreBookingGracePeriod` blocks after its
+		/// scheduled start.
+		#[pallet::call_index(55)]
+		#[pallet::weight((T::WeightInfo::pre_book(), DispatchClass::Normal))]
+		pub fn pre_book(origin: OriginFor<T>, stream_id: u128) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			// Streams lose their schedule as soon as they go live or end
+			let schedule = StreamSchedules::<T>::get(stream_id).ok_or(Error::<T>::NotScheduled)?;
+			Self::check_join_gate(stream_id, &who)?;
+
+			let viewers = StreamViewers::<T>::get(stream_id);
+			let mut bookings = PreBookings::<T>::get(stream_id);
+			ensure!(
+				!viewers.contains(&who) && !bookings.iter().any(|(booked, _)| *booked == who),
+				Error::<T>::AlreadyBooked
+			);
+			if let Some(max_viewers) = MaxViewers::<T>::get(stream_id) {
+				let seats = viewers.len().saturating_add(bookings.len()) as u32;
+				ensure!(seats < max_viewers, Error::<T>::StreamFull);
+			}
+
+			let deposit = T::PreBookingDeposit::get();
+			bookings.try_push((who.clone(), deposit)).map_err(|_| Error::<T>::TooManyBookings)?;
+			Self::hold(stream_id, &stream, &who, deposit)?;
+			if bookings.len() == 1 {
+				let deadline =
+					schedule.estimated_block.saturating_add(T::PreBookingGracePeriod::get());
+				PreBookingDeadlines::<T>::append(deadline, stream_id);
+			}
+			PreBookings::<T>::insert(stream_id, bookings);
+
+			Self::deposit_event(Event::PreBooked { stream_id, viewer: who, deposit });
+
+			Ok(())
+		}
+
+		/// Buy a pass of `tier` to a stream for each of `recipients`.
+		///
+		/// The stream's bulk discount applies when enough passes are bought at once. Recipients
+		/// are not joined to the stream; their ticks are free whenever they join while the
+		/// pass lasts. Gifting to an account that already holds a pass extends it.
+		#[pallet::call_index(56)]
+		#[pallet::weight((
+			T::WeightInfo::gift_subscriptions(recipients.len() as u32),
+			DispatchClass::Normal
+		))]
+		pub fn gift_subscriptions(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			tier: u32,
+			recipients: BoundedVec<T::AccountId, T::MaxGiftRecipients>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!recipients.is_empty(), Error::<T>::NoRecipients);
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.status != StreamStatus::Ended, Error::<T>::StreamEnded);
+			let SubscriptionTier { price, duration_blocks } = SubscriptionTiers::<T>::get(stream_id)
+				.get(tier as usize)
+				.cloned()
+				.ok_or(Error::<T>::TierNotFound)?;
+
+			let passes = recipients.len() as u32;
+			let mut total =
+				price.checked_mul(&passes.into()).ok_or(Error::<T>::ArithmeticOverflow)?;
+			if let Some(bulk) = BulkDiscounts::<T>::get(stream_id) {
+				if passes >= bulk.min_passes {
+					total = total.saturating_sub(bulk.discount * total);
+				}
+			}
+
+			// Paid like `subscribe`, by the gifter
+			Self::hold(stream_id, &stream, &who, total)?;
+			Self::split_revenue(stream_id, &stream, &who, total)?;
+			CreatorEarnings::<T>::mutate(&stream.creator, Self::current_era(), |earnings| {
+				earnings.gross = earnings.gross.saturating_add(total);
+			});
+			Self::record_settled_value(stream_id, &stream, 0, total);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			for recipient in recipients {
+				let expires_at = Subscriptions::<T>::get(stream_id, &recipient)
+					.unwrap_or(now)
+					.max(now)
+					.saturating_add(duration_blocks);
+				Subscriptions::<T>::insert(stream_id, &recipient, expires_at);
+
+				Self::deposit_event(Event::SubscriptionGifted {
+					stream_id,
+					gifter: who.clone(),
+					recipient,
+					tier,
+					expires_at,
+				});
+			}
+
+			Ok(())
+		}
+
+		/// Discount passes to a stream gifted `min_passes` or more at once, or stop
+		/// discounting them with `None`.
+		#[pallet::call_index(57)]
+		#[pallet::weight((T::WeightInfo::set_bulk_discount(), DispatchClass::Normal))]
+		pub fn set_bulk_discount(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			discount: Option<BulkDiscount>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.creator == who, Error::<T>::NotStreamCreator);
+			ensure!(
+				!matches!(&discount, Some(bulk) if bulk.min_passes < 2),
+				Error::<T>::InvalidBulkDiscount
+			);
+
+			BulkDiscounts::<T>::set(stream_id, discount.clone());
+
+			Self::deposit_event(Event::BulkDiscountSet { stream_id, discount });
+
+			Ok(())
+		}
+
+		/// Forward the caller's earnings above `policy.threshold` to `policy.cold_address`, or
+		/// stop forwarding them with `None`.
+		///
+		/// The policy applies to each stream's earnings separately. Withdrawals pay out up to
+		/// the threshold and forward the rest, and at the start of every era escrowed earnings
+		/// above the threshold are forwarded without waiting for a withdrawal.
+		#[pallet::call_index(58)]
+		#[pallet::weight((T::WeightInfo::set_sweep_policy(), DispatchClass::Normal))]
+		pub fn set_sweep_policy(
+			origin: OriginFor<T>,
+			policy: Option<SweepPolicyOf<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			match &policy {
+				Some(policy) => {
+					ensure!(policy.cold_address != who, Error::<T>::InvalidColdAddress);
+					SweepPolicies::<T>::insert(&who, policy);
+				},
+				None => SweepPolicies::<T>::remove(&who),
+			}
+
+			Self::deposit_event(Event::SweepPolicySet { who, policy });
+
+			Ok(())
+		}
+
+		/// Pay the creator's tips and withdrawals from a stream to `payout_account` rather
+		/// than the creator, or pay them to the creator again with `None`.
+		///
+		/// Lets creators stream from a hot key while their earnings land in a cold wallet.
+		/// Earnings wait in escrow as before; only where they are paid to changes. The
+		/// payout account is reset when the stream is transferred.
+		#[pallet::call_index(59)]
+		#[pallet::weight((T::WeightInfo::set_payout_account(), DispatchClass::Normal))]
+		pub fn set_payout_account(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			payout_account: Option<T::AccountId>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Streams::<T>::try_mutate(stream_id, |maybe_stream| -> DispatchResult {
+				let stream = maybe_stream.as_mut().ok_or(Error::<T>::StreamNotFound)?;
+				ensure!(stream.creator == who, Error::<T>::NotStreamCreator);
+				stream.payout_account = payout_account.clone();
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::PayoutAccountSet {
+				stream_id,
+				creator: who,
+				payout_account,
+			});
+
+			Ok(())
+		}
+
+		/// Link the caller's account to `did`, replacing any DID it was linked to.
+		///
+		/// `signature` is the DID key's signature over `did_link_payload`, which embeds and
+		/// partner sites verify off chain to show a creator's cross-platform identity. Each
+		/// DID can be linked to one account at a time.
+		#[pallet::call_index(60)]
+		#[pallet::weight((T::WeightInfo::link_did(), DispatchClass::Normal))]
+		pub fn link_did(
+			origin: OriginFor<T>,
+			did: DidOf<T>,
+			document_hash: [u8; 32],
+			signature: [u8; 64],
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				DID_METHODS
+					.iter()
+					.any(|method| did.len() > method.len() && did.starts_with(method)),
+				Error::<T>::InvalidDid
+			);
+			ensure!(
+				DidAccounts::<T>::get(&did).map_or(true, |owner| owner == who),
+				Error::<T>::DidTaken
+			);
+
+			if let Some(old) = DidLinks::<T>::get(&who) {
+				DidAccounts::<T>::remove(&old.did);
+			}
+			DidAccounts::<T>::insert(&did, &who);
+			DidLinks::<T>::insert(
+				&who,
+				DidLink {
+					did: did.clone(),
+					document_hash,
+					signature,
+					linked_at: frame_system::Pallet::<T>::block_number(),
+				},
+			);
+
+			Self::deposit_event(Event::DidLinked { who, did, document_hash });
+
+			Ok(())
+		}
+
+		/// Remove the link between the caller's account and its DID.
+		#[pallet::call_index(61)]
+		#[pallet::weight((T::WeightInfo::unlink_did(), DispatchClass::Normal))]
+		pub fn unlink_did(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let link = DidLinks::<T>::take(&who).ok_or(Error::<T>::NoDidLink)?;
+			DidAccounts::<T>::remove(&link.did);
+
+			Self::deposit_event(Event::DidUnlinked { who, did: link.did });
+
+			Ok(())
+		}
+
+		/// Choose what happens to the caller's earnings that are too little to withdraw to an
+		/// empty account.
+		///
+		/// Such dust accumulates in escrow by default; it can instead be donated to the
+		/// platform or turned into credit towards watching streams.
+		#[pallet::call_index(62)]
+		#[pallet::weight((T::WeightInfo::set_dust_policy(), DispatchClass::Normal))]
+		pub fn set_dust_policy(origin: OriginFor<T>, policy: DustPolicy) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			match policy {
+				DustPolicy::Accumulate => DustPolicies::<T>::remove(&who),
+				_ => DustPolicies::<T>::insert(&who, policy),
+			}
+
+			Self::deposit_event(Event::DustPolicySet { who, policy });
+
+			Ok(())
+		}
+
+		/// Reserve `TickReporterBond` to report ticks with the caller's key.
+		#[pallet::call_index(63)]
+		#[pallet::weight((T::WeightInfo::register_reporter(), DispatchClass::Normal))]
+		pub fn register_reporter(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!Reporters::<T>::contains_key(&who), Error::<T>::ReporterAlreadyRegistered);
+			let bond = T::TickReporterBond::get();
+			Self::hold_native(HoldReason::ReporterBond, &who, bond)?;
+			Reporters::<T>::insert(&who, TickReporter { bond, nonce: 0, unbonds_at: None });
+
+			Self::deposit_event(Event::ReporterRegistered { who, bond });
+
+			Ok(())
+		}
+
+		/// Stop reporting ticks, then take the bond back once `ReporterCooldown` has passed.
+		///
+		/// The first call ends the caller's reporting at once; calling again after the
+		/// cooldown returns the bond.
+		#[pallet::call_index(64)]
+		#[pallet::weight((T::WeightInfo::deregister_reporter(), DispatchClass::Normal))]
+		pub fn deregister_reporter(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut reporter = Reporters::<T>::get(&who).ok_or(Error::<T>::NotReporter)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			match reporter.unbonds_at {
+				None => {
+					let unbonds_at = now.saturating_add(T::ReporterCooldown::get());
+					reporter.unbonds_at = Some(unbonds_at);
+					Reporters::<T>::insert(&who, reporter);
+					Self::deposit_event(Event::ReporterDeregistered { who, unbonds_at });
+				},
+				Some(unbonds_at) => {
+					ensure!(now >= unbonds_at, Error::<T>::ReporterCoolingDown);
+					Reporters::<T>::remove(&who);
+					Self::release_native(HoldReason::ReporterBond, &who, reporter.bond);
+					Self::deposit_event(Event::ReporterBondReturned { who, amount: reporter.bond });
+				},
+			}
+
+			Ok(())
+		}
+
+		/// Join a stream, reserving enough to watch `seconds` of it at the viewer's price.
+		///
+		/// Fails with `PriceAboveLimit` if that price is above `max_price_per_second`, so a
+		/// price change cannot catch the viewer out. `referrer` earns `ReferralShare` of the
+		/// viewer's tick payments, out of the creator's share, until the viewer leaves; a
+		/// viewer who rejoins keeps their first referrer. Any viewing credit the viewer holds
+		/// is paid to them first, towards the reserve. Fails with `StreamEnded` once the
+		/// stream is over and with a join gate error if the viewer does not meet the stream's
+		/// gate.
+		#[pallet::call_index(65)]
+		#[pallet::weight((T::WeightInfo::join_stream(), DispatchClass::Normal))]
+		pub fn join_stream_v2(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			seconds: u32,
+			referrer: Option<T::AccountId>,
+			max_price_per_second: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::join(who, stream_id, seconds, referrer, max_price_per_second)
+		}
+
+		/// Move a stream and everything kept about it to the unused id `new_id`.
+		///
+		/// Viewers' reserves and sessions, held payments, escrowed funds and the queues the
+		/// stream is due in all follow it, and `StreamMigrated` maps the old id to the new one.
+		/// `records` must be at least the number of per-viewer records, queued ticks and open
+		/// disputes looked through. Each account's `ViewerStats` and `ReferralEarnings`, and
+		/// watch time already awaiting `OnWatchTime`, stay under the old id; referral earnings
+		/// are still claimed with it.
+		#[pallet::call_index(66)]
+		#[pallet::weight((T::WeightInfo::migrate_stream(*records), DispatchClass::Operational))]
+		pub fn migrate_stream(
+			origin: OriginFor<T>,
+			old_id: u128,
+			new_id: u128,
+			records: u32,
+		) -> DispatchResultWithPostInfo {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let stream = Streams::<T>::get(old_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(!Streams::<T>::contains_key(new_id), Error::<T>::StreamAlreadyExists);
+
+			// The call is rolled back as a whole if the witness turns out too low
+			let moved = Self::move_viewer_records(old_id, new_id)
+				.saturating_add(Self::move_stream_records(old_id, new_id, &stream));
+			ensure!(moved <= records, Error::<T>::MigrationWitnessTooLow);
+			Self::move_escrow(old_id, new_id, &stream)?;
+
+			NextStreamId::<T>::mutate(|next| *next = (*next).max(new_id.saturating_add(1)));
+			MigratedStreams::<T>::insert(old_id, new_id);
+
+			Self::deposit_event(Event::StreamMigrated { old_id, new_id });
+
+			Ok(Some(T::WeightInfo::migrate_stream(moved)).into())
+		}
+
+		/// Make a stream broadcast live or played on demand, before anyone has joined it.
+		///
+		/// On-demand streams are billed by `report_playback` instead of by ticks.
+		#[pallet::call_index(67)]
+		#[pallet::weight((T::WeightInfo::set_content_type(), DispatchClass::Normal))]
+		pub fn set_content_type(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			content_type: ContentType,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Streams::<T>::try_mutate(stream_id, |maybe_stream| -> DispatchResult {
+				let stream = maybe_stream.as_mut().ok_or(Error::<T>::StreamNotFound)?;
+				ensure!(stream.creator == who, Error::<T>::NotStreamCreator);
+				ensure!(
+					stream.status == StreamStatus::Created,
+					Error::<T>::InvalidStatusTransition
+				);
+				ensure!(
+					Balances::<T>::iter_key_prefix(stream_id).next().is_none(),
+					Error::<T>::StreamInUse
+				);
+				stream.content_type = content_type;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ContentTypeSet { stream_id, content_type });
+
+			Ok(())
+		}
+
+		/// Report how far the caller has played an on-demand stream, charging them for the
+		/// seconds past the furthest position they reported before.
+		///
+		/// Seconds played again after seeking back are not charged. As with ticks, no more
+		/// seconds are charged than have passed since the caller's last report, and reports
+		/// come at most once every tick threshold.
+		#[pallet::call_index(68)]
+		#[pallet::weight((
+			T::WeightInfo::report_playback().saturating_add(T::SettlementExtension::weight()),
+			DispatchClass::Normal
+		))]
+		pub fn report_playback(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			position_seconds: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.status == StreamStatus::Live, Error::<T>::StreamNotLive);
+			ensure!(stream.content_type == ContentType::Vod, Error::<T>::NotOnDemand);
+
+			let furthest = PlaybackPositions::<T>::get(stream_id, &who);
+			let unwatched = position_seconds.saturating_sub(furthest);
+			if unwatched == 0 {
+				return Ok(())
+			}
+			let viewers = StreamViewers::<T>::get(stream_id);
+			let entry = SettlementEntry::Reported;
+			Self::do_settle(stream_id, &stream, &viewers, who.clone(), unwatched, entry)?;
+			PlaybackPositions::<T>::insert(stream_id, &who, position_seconds);
+
+			Ok(())
+		}
+
+		/// Cap the total the pallet may hold in the native token across all accounts, or lift
+		/// the cap with `None`.
+		///
+		/// Viewers cannot join streams, top up, book seats, buy passes or fund budgets past
+		/// the cap. What is held already stays held.
+		#[pallet::call_index(69)]
+		#[pallet::weight((T::WeightInfo::set_global_hold_cap(), DispatchClass::Operational))]
+		pub fn set_global_hold_cap(
+			origin: OriginFor<T>,
+			cap: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			GlobalHoldCap::<T>::set(cap);
+			Self::deposit_event(Event::GlobalHoldCapSet { cap });
+			Ok(())
+		}
+
+		/// Set who may join a stream.
+		///
+		/// Viewers already watching stay, and viewers joining with one of the creator's vouchers
+		/// are let through. A stream's allowlist is kept when it is opened up, so it applies
+		/// again if the allowlist policy is set back.
+		#[pallet::call_index(70)]
+		#[pallet::weight((T::WeightInfo::set_access_policy(), DispatchClass::Normal))]
+		pub fn set_access_policy(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			policy: AccessPolicyOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.creator == who, Error::<T>::NotStreamCreator);
+
+			match policy {
+				AccessPolicy::Public => AccessPolicies::<T>::remove(stream_id),
+				_ => AccessPolicies::<T>::insert(stream_id, &policy),
+			}
+
+			Self::deposit_event(Event::AccessPolicySet { stream_id, policy });
+
+			Ok(())
+		}
+
+		/// Let `who` join a stream with the allowlist policy.
+		#[pallet::call_index(71)]
+		#[pallet::weight((T::WeightInfo::add_to_allowlist(), DispatchClass::Normal))]
+		pub fn add_to_allowlist(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			who: T::AccountId,
+		) -> DispatchResult {
+			let creator = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.creator == creator, Error::<T>::NotStreamCreator);
+
+			Allowlists::<T>::insert(stream_id, &who, ());
+			Self::deposit_event(Event::AddedToAllowlist { stream_id, who });
+
+			Ok(())
+		}
+
+		/// Take `who` off a stream's allowlist.
+		///
+		/// They keep watching if they already are, but cannot join again.
+		#[pallet::call_index(72)]
+		#[pallet::weight((T::WeightInfo::remove_from_allowlist(), DispatchClass::Normal))]
+		pub fn remove_from_allowlist(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			who: T::AccountId,
+		) -> DispatchResult {
+			let creator = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.creator == creator, Error::<T>::NotStreamCreator);
+
+			Allowlists::<T>::remove(stream_id, &who);
+			Self::deposit_event(Event::RemovedFromAllowlist { stream_id, who });
+
+			Ok(())
+		}
+
+		/// Grant a new creator an onboarding grant out of the platform account, which vests in
+		/// two tranches: `streams_tranche` once they have taken `streams_target` streams live,
+		/// and `viewer_hours_tranche` once viewers have paid for `viewer_hours_target` hours of
+		/// their streams.
+		///
+		/// Both are counted from settlement from now on. The grant is held on the creator's
+		/// account until it vests, and what has not vested `duration` blocks from now goes back
+		/// to the platform account.
+		#[pallet::call_index(73)]
+		#[pallet::weight((T::WeightInfo::approve_grant(), DispatchClass::Operational))]
+		pub fn approve_grant(
+			origin: OriginFor<T>,
+			creator: T::AccountId,
+			streams_target: u32,
+			streams_tranche: BalanceOf<T>,
+			viewer_hours_target: u32,
+			viewer_hours_tranche: BalanceOf<T>,
+			duration: BlockNumberFor<T>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			ensure!(!CreatorGrants::<T>::contains_key(&creator), Error::<T>::GrantAlreadyApproved);
+			let amount = streams_tranche.saturating_add(viewer_hours_tranche);
+			ensure!(!amount.is_zero() && !duration.is_zero(), Error::<T>::InvalidGrant);
+			ensure!(streams_tranche.is_zero() || streams_target > 0, Error::<T>::InvalidGrant);
+			ensure!(
+				viewer_hours_tranche.is_zero() || viewer_hours_target > 0,
+				Error::<T>::InvalidGrant
+			);
+			Self::ensure_below_hold_cap(amount)?;
+
+			T::Currency::transfer(
+				&Self::platform_account(),
+				&creator,
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			Self::hold_native(HoldReason::CreatorGrant, &creator, amount)?;
+
+			let deadline = frame_system::Pallet::<T>::block_number().saturating_add(duration);
+			CreatorGrants::<T>::insert(
+				&creator,
+				CreatorGrant {
+					streams_tranche,
+					streams_target,
+					viewer_hours_tranche,
+					viewer_hours_target,
+					streams: 0,
+					paid_seconds: 0,
+					deadline,
+				},
+			);
+			GrantDeadlines::<T>::append(deadline, &creator);
+
+			Self::deposit_event(Event::GrantApproved { creator, amount, deadline });
+
+			Ok(())
+		}
+
+		/// Register chain `source` to import viewer attestations from, update its signer or
+		/// identity level, or stop importing from it with `None`.
+		///
+		/// Attestations already imported from a removed chain no longer count towards join
+		/// gates.
+		#[pallet::call_index(74)]
+		#[pallet::weight((T::WeightInfo::set_attestation_source(), DispatchClass::Operational))]
+		pub fn set_attestation_source(
+			origin: OriginFor<T>,
+			source: u32,
+			config: Option<AttestationSourceOf<T>>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			AttestationSources::<T>::try_mutate(|sources| -> DispatchResult {
+				let position = sources.iter().position(|(id, _)| *id == source);
+				match (&config, position) {
+					(Some(config), _) if config.identity_level == 0 =>
+						return Err(Error::<T>::InvalidAttestationSource.into()),
+					(Some(config), Some(i)) => sources[i].1 = config.clone(),
+					(Some(config), None) => sources
+						.try_push((source, config.clone()))
+						.map_err(|_| Error::<T>::TooManyAttestationSources)?,
+					(None, Some(i)) => {
+						sources.remove(i);
+					},
+					(None, None) => {},
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::AttestationSourceSet { source, config });
+
+			Ok(())
+		}
+
+		/// Import what a registered chain vouches for about `who`: the seconds they have
+		/// watched there and how far it has verified their identity.
+		///
+		/// Anyone, usually the source chain's bridge relayer, can submit an attestation signed
+		/// by the source's signer over `attestation_payload`. It replaces the viewer's previous
+		/// attestation from the same chain and, until it expires, counts towards the join gates
+		/// of streams.
+		#[pallet::call_index(75)]
+		#[pallet::weight((T::WeightInfo::import_attestation(), DispatchClass::Normal))]
+		pub fn import_attestation(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			attestation: ViewerAttestationOf<T>,
+			signature: T::OffchainSignature,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let sources = AttestationSources::<T>::get();
+			let (_, source) = sources
+				.iter()
+				.find(|(id, _)| *id == attestation.source)
+				.ok_or(Error::<T>::UnknownAttestationSource)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(attestation.expires_at > now, Error::<T>::AttestationExpired);
+			let payload = Self::attestation_payload(&who, &attestation);
+			ensure!(
+				signature.verify(&payload[..], &source.signer),
+				Error::<T>::InvalidAttestationSignature
+			);
+
+			ViewerAttestations::<T>::try_mutate(&who, |attestations| -> DispatchResult {
+				if let Some(previous) = attestations.iter().find(|a| a.source == attestation.source)
+				{
+					ensure!(attestation.nonce > previous.nonce, Error::<T>::StaleAttestation);
+				}
+				// Make room by dropping what no longer counts
+				attestations.retain(|a| {
+					a.source != attestation.source &&
+						a.expires_at > now &&
+						sources.iter().any(|(id, _)| *id == a.source)
+				});
+				attestations
+					.try_push(attestation.clone())
+					.map_err(|_| Error::<T>::TooManyAttestationSources)?;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::AttestationImported {
+				who,
+				source: attestation.source,
+				watch_seconds: attestation.watch_seconds,
+				verification_level: attestation.verification_level,
+			});
+
+			Ok(())
+		}
+
+		/// Choose the unit a stream's reports give its earnings in.
+		///
+		/// Reports convert nothing at today's rates: each payment is valued as it settled, in
+		/// the currency the stream is priced in and, for streams priced in the native token, in
+		/// fiat at the oracle's rate at the time. The oracle has no rates for other assets, so
+		/// only those units can be chosen. Pricing the stream in another currency resets it.
+		#[pallet::call_index(76)]
+		#[pallet::weight((T::WeightInfo::set_reporting_unit(), DispatchClass::Normal))]
+		pub fn set_reporting_unit(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			unit: ReportingUnitOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.creator == who, Error::<T>::NotStreamCreator);
+			let supported = match (&unit, &stream.asset_id) {
+				(ReportingUnit::Native | ReportingUnit::Fiat, None) => true,
+				(ReportingUnit::Asset(asset_id), Some(priced_in)) => asset_id == priced_in,
+				_ => false,
+			};
+			ensure!(supported, Error::<T>::UnsupportedReportingUnit);
+
+			ReportingUnits::<T>::insert(stream_id, &unit);
+
+			Self::deposit_event(Event::ReportingUnitSet { stream_id, unit });
+
+			Ok(())
+		}
+
+		/// Swap `policy.hedge_percent` of the caller's withdrawals into `policy.asset` as they
+		/// are paid out, or stop hedging them with `None`.
+		///
+		/// Each swap is quoted first and must fill within `policy.max_slippage` of the quote;
+		/// if it cannot, the withdrawal is paid out unswapped rather than failing. Withdrawals
+		/// from streams priced in the hedge asset are not swapped.
+		#[pallet::call_index(77)]
+		#[pallet::weight((T::WeightInfo::set_hedge_policy(), DispatchClass::Normal))]
+		pub fn set_hedge_policy(
+			origin: OriginFor<T>,
+			policy: Option<HedgePolicyOf<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			match &policy {
+				Some(policy) => {
+					ensure!(T::PayoutSwap::is_supported(), Error::<T>::HedgingUnsupported);
+					HedgePolicies::<T>::insert(&who, policy);
+				},
+				None => HedgePolicies::<T>::remove(&who),
+			}
+
+			Self::deposit_event(Event::HedgePolicySet { who, policy });
+
+			Ok(())
+		}
+
+		/// Open a poll of the caller's viewers for `duration` blocks.
+		///
+		/// Each vote weighs the voter's watch time on the caller's streams over the era it is
+		/// cast in and the `lookback_eras` eras before it. The poll is finalized, and its
+		/// results kept, in the block it closes in.
+		#[pallet::call_index(78)]
+		#[pallet::weight((T::WeightInfo::create_poll(), DispatchClass::Normal))]
+		pub fn create_poll(
+			origin: OriginFor<T>,
+			question: Vec<u8>,
+			options: Vec<Vec<u8>>,
+			duration: BlockNumberFor<T>,
+			lookback_eras: u32,
+		) -> DispatchResult {
+			let creator = ensure_signed(origin)?;
+
+			ensure!(
+				options.len() >= 2 && options.len() <= T::MaxPollOptions::get() as usize,
+				Error::<T>::InvalidPollOptions
+			);
+			ensure!(
+				!duration.is_zero() && lookback_eras <= T::MaxPollLookbackEras::get(),
+				Error::<T>::InvalidPollWindow
+			);
+			let question =
+				PollTextOf::<T>::try_from(question).map_err(|_| Error::<T>::PollTextTooLong)?;
+			let options = options
+				.into_iter()
+				.map(PollTextOf::<T>::try_from)
+				.collect::<Result<Vec<_>, _>>()
+				.map_err(|_| Error::<T>::PollTextTooLong)?;
+			let tallies = BoundedVec::truncate_from(sp_std::vec![0; options.len()]);
+			let options = BoundedVec::truncate_from(options);
+
+			let poll_id = NextPollId::<T>::mutate(|id| {
+				let poll_id = *id;
+				*id = id.saturating_add(1);
+				poll_id
+			});
+			let ends_at = frame_system::Pallet::<T>::block_number().saturating_add(duration);
+			Polls::<T>::insert(
+				poll_id,
+				Poll {
+					creator: creator.clone(),
+					question,
+					options,
+					tallies,
+					lookback_eras,
+					ends_at,
+					status: PollStatus::Open,
+				},
+			);
+			PollsEnding::<T>::append(ends_at, poll_id);
+
+			Self::deposit_event(Event::PollCreated { poll_id, creator, ends_at });
+
+			Ok(())
+		}
+
+		/// Vote for `option` in an open poll, weighted by the caller's watch time with its
+		/// creator. Voting again replaces the caller's vote, weighed afresh.
+		#[pallet::call_index(79)]
+		#[pallet::weight((T::WeightInfo::vote(), DispatchClass::Normal))]
+		pub fn vote(origin: OriginFor<T>, poll_id: u64, option: u8) -> DispatchResult {
+			let voter = ensure_signed(origin)?;
+
+			let mut poll = Polls::<T>::get(poll_id).ok_or(Error::<T>::PollNotFound)?;
+			ensure!(
+				poll.status == PollStatus::Open &&
+					frame_system::Pallet::<T>::block_number() < poll.ends_at,
+				Error::<T>::PollClosed
+			);
+			ensure!((option as usize) < poll.options.len(), Error::<T>::InvalidPollOption);
+			let weight = Self::poll_weight(&poll.creator, &voter, poll.lookback_eras);
+			ensure!(weight > 0, Error::<T>::NoWatchTime);
+
+			if let Some((previous, previous_weight)) = PollVotes::<T>::get(poll_id, &voter) {
+				if let Some(tally) = poll.tallies.get_mut(previous as usize) {
+					*tally = tally.saturating_sub(previous_weight);
+				}
+			}
+			if let Some(tally) = poll.tallies.get_mut(option as usize) {
+				*tally = tally.saturating_add(weight);
+			}
+			PollVotes::<T>::insert(poll_id, &voter, (option, weight));
+			Polls::<T>::insert(poll_id, poll);
+
+			Self::deposit_event(Event::PollVoted { poll_id, voter, option, weight });
+
+			Ok(())
+		}
+
+		/// Give `prize` to one of the caller's live stream's active viewers, drawn at random.
+		///
+		/// Every viewer with an `Active` session enters, other than the creator. The prize is
+		/// held from the creator before the draw and paid to the winner, and the seed, the
+		/// entrants' hash and the winner are kept in `Giveaways` for anyone to check the draw
+		/// against `select_winner`.
+		#[pallet::call_index(80)]
+		#[pallet::weight((T::WeightInfo::run_giveaway(), DispatchClass::Normal))]
+		pub fn run_giveaway(
+			origin: OriginFor<T>,
+			stream_id: u128,
+			prize: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.creator == who, Error::<T>::NotStreamCreator);
+			ensure!(stream.status == StreamStatus::Live, Error::<T>::StreamNotLive);
+			ensure!(!prize.is_zero(), Error::<T>::NoPrize);
+
+			let mut entrants = StreamViewers::<T>::get(stream_id)
+				.into_iter()
+				.filter(|viewer| {
+					*viewer != who &&
+						SessionStates::<T>::get(stream_id, viewer) == Some(SessionState::Active)
+				})
+				.collect::<Vec<_>>();
+			entrants.sort();
+			let giveaway_id = NextGiveawayId::<T>::get();
+			let seed = Self::random_seed(&(b"tick-stream/giveaway", giveaway_id).encode());
+			let winner =
+				select_winner(seed.as_ref(), &entrants).ok_or(Error::<T>::NoEligibleViewers)?;
+
+			Self::hold_native(HoldReason::StreamDeposit, &who, prize)?;
+			Self::pay_native_held(
+				HoldReason::StreamDeposit,
+				&who,
+				&winner,
+				prize,
+				Precision::Exact,
+			)?;
+
+			let giveaway = Giveaway {
+				stream_id,
+				winner: winner.clone(),
+				prize,
+				seed,
+				entrants_hash: T::Hashing::hash_of(&entrants),
+				entrants: entrants.len() as u32,
+				drawn_at: frame_system::Pallet::<T>::block_number(),
+			};
+			Giveaways::<T>::insert(giveaway_id, giveaway);
+			NextGiveawayId::<T>::put(giveaway_id.saturating_add(1));
+
+			Self::deposit_event(Event::GiveawayDrawn {
+				giveaway_id,
+				stream_id,
+				winner,
+				prize,
+				seed,
+				entrants: entrants.len() as u32,
+			});
+
+			Ok(())
+		}
+
+		/// Top the caller's reporter bond back up to `ReporterBond`, so their streams are
+		/// settled again after an upheld dispute slashed it.
+		#[pallet::call_index(81)]
+		#[pallet::weight((T::WeightInfo::bond_reporter(), DispatchClass::Normal))]
+		pub fn bond_reporter(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_reporter_bond(&who)
+		}
+
+		/// Switch which extrinsics the runtime accepts.
+		///
+		/// The runtime lets this call through in every mode, so the filter can always be
+		/// lifted again.
+		#[pallet::call_index(82)]
+		#[pallet::weight((T::WeightInfo::set_call_filter_mode(), DispatchClass::Operational))]
+		pub fn set_call_filter_mode(origin: OriginFor<T>, mode: CallFilterMode) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			CallFilter::<T>::put(mode);
+			Self::deposit_event(Event::CallFilterModeSet { mode });
+			Ok(())
+		}
+
+		/// Set the blocks that must pass between two ticks of a viewer on a stream, or reset
+		/// them to `TickThreshold` with `None`.
+		#[pallet::call_index(83)]
+		#[pallet::weight((T::WeightInfo::set_tick_threshold(), DispatchClass::Operational))]
+		pub fn set_tick_threshold(
+			origin: OriginFor<T>,
+			threshold: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			TickThresholdOverride::<T>::set(threshold);
+			Self::deposit_event(Event::TickThresholdSet { threshold });
+			Ok(())
+		}
+
+		/// Cap the viewers a single `batch_tick` or settlement inherent may settle below
+		/// `MaxViewersPerBatch`, or lift the cap back to it with `None`.
+		#[pallet::call_index(84)]
+		#[pallet::weight((T::WeightInfo::set_batch_cap(), DispatchClass::Operational))]
+		pub fn set_batch_cap(origin: OriginFor<T>, cap: Option<u32>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				cap.map_or(true, |cap| cap > 0 && cap <= T::MaxViewersPerBatch::get()),
+				Error::<T>::InvalidBatchCap
+			);
+			BatchCapOverride::<T>::set(cap);
+			Self::deposit_event(Event::BatchCapSet { cap });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Deposit `event` with a topic carrying its sequence number among the pallet's events
+		/// in the block.
+		///
+		/// Sequence numbers start from zero in every block and follow the order events are
+		/// deposited in, hooks included, so they order every economic event of a block
+		/// without relying on the extrinsic it came from. Events of failed calls are rolled
+		/// back with their sequence numbers, leaving no gaps.
+		pub(super) fn deposit_event(event: Event<T>) {
+			let now = frame_system::Pallet::<T>::block_number();
+			let seq = EventSequence::<T>::mutate(|(block, next)| {
+				if *block != now {
+					*block = now;
+					*next = 0;
+				}
+				let seq = *next;
+				*next = next.saturating_add(1);
+				seq
+			});
+			let topic = (crate::EVENT_SEQUENCE_TOPIC, seq).encode();
+			let topic = T::Hash::decode(&mut TrailingZeroInput::new(&topic)).unwrap_or_default();
+			let event = <T as Config>::RuntimeEvent::from(event);
+			frame_system::Pallet::<T>::deposit_event_indexed(&[topic], event.into());
+		}
+
+		/// Get the tick count for a stream
+		pub fn get_tick_count(stream_id: u128) -> u32 {
+			TickCount::<T>::get(stream_id)
+		}
+
+		/// Up to `limit` (stream, viewer, seconds) ticks that can be charged now, for every
+		/// viewer of every live stream who is due one.
+		pub fn due_ticks(limit: u32) -> Vec<(u128, T::AccountId, u32)> {
+			let (block, seconds) = Self::now();
+			let mut due = Vec::new();
+
+			for (stream_id, stream) in Streams::<T>::iter() {
+				if stream.status != StreamStatus::Live || stream.content_type != ContentType::Live {
+					continue
+				}
+
+				for (viewer, (last_block, last_seconds)) in ViewerLastTick::<T>::iter_prefix(stream_id) {
+					if due.len() >= limit as usize {
+						return due
+					}
+					if block < last_block.saturating_add(Self::tick_threshold()) ||
+						Self::is_paused(stream_id, &viewer)
+					{
+						continue
+					}
+
+					// Never bill more than the viewer has reserved
+					let elapsed = seconds.saturating_sub(last_seconds).saturated_into::<u32>();
+					let price = Self::price_for(stream_id, &stream, &viewer);
+					let subscribed = Subscriptions::<T>::get(stream_id, &viewer)
+						.is_some_and(|expires_at| block < expires_at);
+					let affordable = if price.is_zero() || subscribed {
+						elapsed
+					} else {
+						let available = Self::available_for(stream_id, &stream, &viewer);
+						(available / price)
+							.saturated_into::<u32>()
+							.saturating_add(Self::free_left(stream_id, &viewer))
+					};
+					let ticks = elapsed.min(affordable);
+					if ticks == 0 {
+						continue
+					}
+
+					due.push((stream_id, viewer, ticks));
+				}
+			}
+
+			due
+		}
+
+		/// The era the current block belongs to.
+		pub fn current_era() -> u32 {
+			let era_length = T::EraLength::get().max(One::one());
+			(frame_system::Pallet::<T>::block_number() / era_length).saturated_into()
+		}
+
+		/// Failed dispatch counts for an era as (call index, error index, count).
+		pub fn get_error_counts(era: u32) -> Vec<(u8, u8, u32)> {
+			ErrorCounts::<T>::iter_prefix(era)
+				.map(|((call_index, error_index), count)| (call_index, error_index, count))
+				.collect()
+		}
+
+		/// Notification preferences for up to `limit` accounts, in storage order, starting
+		/// after `start_after`.
+		///
+		/// Relayers page through every account by passing the last account of the previous
+		/// page.
+		pub fn notification_prefs_page(
+			start_after: Option<T::AccountId>,
+			limit: u32,
+		) -> Vec<(T::AccountId, NotificationPrefs)> {
+			let iter = match start_after {
+				Some(account) => NotificationPreferences::<T>::iter_from(
+					NotificationPreferences::<T>::hashed_key_for(account),
+				),
+				None => NotificationPreferences::<T>::iter(),
+			};
+			iter.take(limit as usize).collect()
+		}
+
+		/// Bump the failure counter for a call and error variant in the current era.
+		pub(crate) fn note_dispatch_error(call_index: u8, error_index: u8) {
+			ErrorCounts::<T>::mutate(Self::current_era(), (call_index, error_index), |count| {
+				*count = count.saturating_add(1)
+			});
+		}
+
+		/// Charge `viewer` for `ticks` seconds of `stream` out of their reserve and pay the creator.
+		/// Every settlement goes through here, checked as far as its `entry` still needs, and
+		/// returns the amount charged and its fiat value.
+		///
+		/// Nothing is written unless the whole settlement succeeds. Once the block has settled
+		/// `MaxSettlementsPerBlock` ticks, the tick is queued for a later block instead, and a
+		/// tick over the viewer's spending limit pauses their session instead; both return
+		/// `None`.
+		fn do_settle(
+			stream_id: u128,
+			stream: &StreamOf<T>,
+			viewers: &[T::AccountId],
+			viewer: T::AccountId,
+			ticks: u32,
+			entry: SettlementEntry,
+		) -> Result<Option<(BalanceOf<T>, Option<u128>)>, DispatchError> {
+			with_storage_layer(|| -> Result<_, DispatchError> {
+				if entry == SettlementEntry::Reported {
+					Self::ensure_tickable(stream_id, viewers, &viewer, ticks)?;
+				}
+				if entry == SettlementEntry::Queued {
+					ensure!(viewers.contains(&viewer), Error::<T>::Unauthorized);
+				} else {
+					Self::ensure_bonded(&stream.creator)?;
+					if !Self::within_spending_limit(stream_id, stream, &viewer, ticks) {
+						Self::move_session(stream_id, &viewer, SessionState::GracePaused)?;
+						Self::deposit_event(Event::SpendingLimitReached { stream_id, viewer });
+						return Ok(None)
+					}
+
+					// The tick is taken now, so it is neither submitted nor counted again while
+					// queued
+					ViewerLastTick::<T>::insert(stream_id, &viewer, Self::now());
+					if !Self::take_settlement() {
+						QueuedTickRange::<T>::mutate(|(_, next)| {
+							QueuedTicks::<T>::insert(*next, (stream_id, viewer.clone(), ticks));
+							*next = next.saturating_add(1);
+						});
+						Self::deposit_event(Event::TickQueued { stream_id, viewer, ticks });
+						return Ok(None)
+					}
+				}
+
+				let settled = Self::charge_ticks(stream_id, stream, &viewer, ticks)?;
+				Self::auto_leave(stream_id, viewer);
+				Ok(Some(settled))
+			})
+		}
+
+		/// Settle one viewer's ticks in a batch with `do_settle`, adding what they are charged
+		/// to `totals`. Viewers that cannot be settled are skipped with `TickSkipped`. Returns
+		/// whether the viewer got past `ensure_tickable`, and so was weighed as settled.
+		fn settle_batched(
+			stream_id: u128,
+			stream: &StreamOf<T>,
+			viewers: &[T::AccountId],
+			viewer: T::AccountId,
+			ticks: u32,
+			totals: &mut IntervalTotals<BalanceOf<T>>,
+		) -> bool {
+			if Self::ensure_tickable(stream_id, viewers, &viewer, ticks).is_err() {
+				Self::deposit_event(Event::TickSkipped { stream_id, viewer });
+				return false
+			}
+			let entry = SettlementEntry::Batched;
+			match Self::do_settle(stream_id, stream, viewers, viewer.clone(), ticks, entry) {
+				Ok(Some((amount, fiat_value))) => {
+					totals.viewers = totals.viewers.saturating_add(1);
+					totals.total_amount = totals.total_amount.saturating_add(amount);
+					totals.total_fiat_value =
+						totals.total_fiat_value.saturating_add(fiat_value.unwrap_or(0));
+				},
+				Ok(None) => {},
+				Err(_) => Self::deposit_event(Event::TickSkipped { stream_id, viewer }),
+			}
+			true
+		}
+
+		/// The `IntervalSettled` event for what a batch settled on a stream.
+		fn interval_settled(stream_id: u128, totals: IntervalTotals<BalanceOf<T>>) -> Event<T> {
+			let IntervalTotals { viewers, total_amount, total_fiat_value } = totals;
+			Event::IntervalSettled { stream_id, viewers, total_amount, total_fiat_value }
+		}
+
+		/// Fail unless `viewer` is among a stream's `viewers`, with their session running and
+		/// `ticks` seconds due. Writes nothing, so batches check viewers with it up front and
+		/// are charged little for those it turns away.
+		fn ensure_tickable(
+			stream_id: u128,
+			viewers: &[T::AccountId],
+			viewer: &T::AccountId,
+			ticks: u32,
+		) -> DispatchResult {
+			ensure!(viewers.contains(viewer), Error::<T>::Unauthorized);
+			ensure!(!Self::is_paused(stream_id, viewer), Error::<T>::SessionPaused);
+			Self::check_tick_rate(stream_id, viewer, ticks)?;
+			Ok(())
+		}
+
+		/// Whether charging `viewer` for `ticks` seconds of `stream` keeps them within their
+		/// spending limits.
+		fn within_spending_limit(
+			stream_id: u128,
+			stream: &StreamOf<T>,
+			viewer: &T::AccountId,
+			ticks: u32,
+		) -> bool {
+			let Some(limit) = SpendingLimits::<T>::get(viewer) else { return true };
+			// An amount too large for a balance is over any limit
+			let Ok(amount) = Self::tick_amount(stream_id, stream, viewer, ticks) else {
+				return false
+			};
+
+			let session = SessionSpending::<T>::get(stream_id, viewer).saturating_add(amount);
+			let (day, spent) = DailySpending::<T>::get(viewer);
+			let today = if day == Self::today() { spent } else { Zero::zero() };
+			limit.max_per_stream.map_or(true, |max| session <= max) &&
+				limit.max_per_day.map_or(true, |max| today.saturating_add(amount) <= max)
+		}
+
+		/// What `viewer` is charged for `ticks` seconds of `stream`: nothing for subscribers,
+		/// and nothing for the seconds left of their free preview.
+		///
+		/// Fails with `ArithmeticOverflow` if the amount does not fit in a balance.
+		fn tick_amount(
+			stream_id: u128,
+			stream: &StreamOf<T>,
+			viewer: &T::AccountId,
+			ticks: u32,
+		) -> Result<BalanceOf<T>, Error<T>> {
+			if Self::is_subscribed(stream_id, viewer) {
+				return Ok(Zero::zero())
+			}
+			let charged = ticks.saturating_sub(Self::free_left(stream_id, viewer));
+			Self::price_for(stream_id, stream, viewer)
+				.checked_mul(&charged.into())
+				.ok_or(Error::<T>::ArithmeticOverflow)
+		}
+
+		/// The seconds of `stream_id` that `viewer` can still watch for free, under the stream's
+		/// preview or the grace at the start of their session. The two overlap, as both start
+		/// with the first second watched.
+		fn free_left(stream_id: u128, viewer: &T::AccountId) -> u32 {
+			Self::preview_left(stream_id, viewer).max(Self::grace_left(stream_id, viewer))
+		}
+
+		/// The seconds of `viewer`'s session on `stream_id` still within
+		/// `FirstChargeGraceSeconds`. Sessions start when the viewer joins and end when their
+		/// reserve is refunded.
+		fn grace_left(stream_id: u128, viewer: &T::AccountId) -> u32 {
+			let watched = WatchedSeconds::<T>::get(stream_id, viewer);
+			u64::from(T::FirstChargeGraceSeconds::get()).saturating_sub(watched) as u32
+		}
+
+		/// The seconds of `stream_id`'s preview that `viewer` can still watch for free.
+		fn preview_left(stream_id: u128, viewer: &T::AccountId) -> u32 {
+			let watched = PreviewWatched::<T>::get(stream_id, viewer);
+			FreePreviews::<T>::get(stream_id).saturating_sub(watched)
+		}
+
+		/// The UTC day it is, counted from the Unix epoch.
+		fn today() -> u64 {
+			T::UnixTime::now().as_secs() / SECONDS_PER_DAY
+		}
+
+		/// Count a settlement against the block's `MaxSettlementsPerBlock`, unless it has
+		/// none left.
+		fn take_settlement() -> bool {
+			let now = frame_system::Pallet::<T>::block_number();
+			let settled = match BlockSettlements::<T>::get() {
+				(block, settled) if block == now => settled,
+				_ => 0,
+			};
+			if settled >= T::MaxSettlementsPerBlock::get() {
+				return false
+			}
+			BlockSettlements::<T>::put((now, settled.saturating_add(1)));
+			true
+		}
+
+		/// Report settlement stalled once no ticks have been settled for `AlertAfterBlocks`
+		/// while viewers watch live streams, and no longer once ticks are settled again or no
+		/// viewers are left.
+		///
+		/// Live streams are only looked through while settlement is overdue, stopping at the
+		/// first with viewers.
+		fn watch_settlements(n: BlockNumberFor<T>) -> Weight {
+			let (last_settled, _) = BlockSettlements::<T>::get();
+			let mut weight = T::DbWeight::get().reads(2);
+			let stalled = n.saturating_sub(last_settled) >= T::AlertAfterBlocks::get() &&
+				StreamsByStatus::<T>::iter_key_prefix(StreamStatus::Live).any(|stream_id| {
+					weight.saturating_accrue(T::DbWeight::get().reads(2));
+					ViewerCount::<T>::get(stream_id) > 0
+				});
+			if stalled == SettlementStalled::<T>::get() {
+				return weight
+			}
+
+			SettlementStalled::<T>::set(stalled);
+			if stalled {
+				Self::deposit_event(Event::SettlementStalled { last_settled });
+			} else {
+				Self::deposit_event(Event::SettlementResumed { last_settled });
+			}
+			weight.saturating_add(T::DbWeight::get().writes(1))
+		}
+
+		/// The seconds `viewer` has been charged for on `creator`'s streams over the current era
+		/// and the `lookback_eras` eras before it.
+		pub fn poll_weight(
+			creator: &T::AccountId,
+			viewer: &T::AccountId,
+			lookback_eras: u32,
+		) -> u64 {
+			let era = Self::current_era();
+			(era.saturating_sub(lookback_eras)..=era)
+				.map(|era| CreatorWatchTime::<T>::get(era, (creator, viewer)))
+				.fold(0, u64::saturating_add)
+		}
+
+		/// Close the polls ending in block `n` and record their results.
+		fn finalize_polls(n: BlockNumberFor<T>) -> Weight {
+			let ending = PollsEnding::<T>::take(n);
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+			for poll_id in ending {
+				weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+				let Some(mut poll) = Polls::<T>::get(poll_id) else { continue };
+				let most = poll.tallies.iter().copied().max().unwrap_or_default();
+				let mut leaders =
+					poll.tallies.iter().enumerate().filter(|(_, tally)| **tally == most);
+				let winner = match (leaders.next(), leaders.next()) {
+					(Some((option, _)), None) if most > 0 => Some(option as u8),
+					_ => None,
+				};
+				poll.status = PollStatus::Finalized { winner };
+				let tallies = poll.tallies.to_vec();
+				Polls::<T>::insert(poll_id, poll);
+
+				Self::deposit_event(Event::PollFinalized { poll_id, tallies, winner });
+			}
+
+			weight
+		}
+
+		/// At the start of an era, drop the creator watch time too old for any poll to look
+		/// back to.
+		fn prune_creator_watch_time(n: BlockNumberFor<T>) -> Weight {
+			let era_length = T::EraLength::get().max(One::one());
+			if !(n % era_length).is_zero() {
+				return Weight::zero()
+			}
+
+			let kept = T::MaxPollLookbackEras::get().saturating_add(1);
+			match Self::current_era().checked_sub(kept) {
+				Some(expired) => {
+					let removed = CreatorWatchTime::<T>::clear_prefix(expired, u32::MAX, None);
+					T::DbWeight::get().writes(removed.backend.into())
+				},
+				None => Weight::zero(),
+			}
+		}
+
+		/// Settle queued ticks, oldest first, while the block has settlements left.
+		///
+		/// Ticks that can no longer be settled, as the viewer left the stream or ran out of
+		/// reserve meanwhile, are skipped.
+		fn settle_queued_ticks() -> Weight {
+			let (mut first, next) = QueuedTickRange::<T>::get();
+			let mut weight = T::DbWeight::get().reads(1);
+			if first == next {
+				return weight
+			}
+
+			while first < next && Self::take_settlement() {
+				if let Some((stream_id, viewer, ticks)) = QueuedTicks::<T>::take(first) {
+					let settled = match Streams::<T>::get(stream_id) {
+						Some(stream) => {
+							let viewers = StreamViewers::<T>::get(stream_id);
+							Self::do_settle(
+								stream_id,
+								&stream,
+								&viewers,
+								viewer.clone(),
+								ticks,
+								SettlementEntry::Queued,
+							)
+						},
+						None => Err(Error::<T>::StreamNotFound.into()),
+					};
+					if settled.is_err() {
+						Self::deposit_event(Event::TickSkipped { stream_id, viewer });
+					}
+				}
+				first = first.saturating_add(1);
+				weight.saturating_accrue(T::WeightInfo::record_tick());
+				weight.saturating_accrue(T::SettlementExtension::weight());
+			}
+			QueuedTickRange::<T>::put((first, next));
+			weight.saturating_add(T::DbWeight::get().reads_writes(1, 2))
+		}
+
+		/// Move `ticks` seconds of `viewer`'s watch time on `stream` into the creator's
+		/// earnings. Returns the amount charged and its fiat value, if there was a rate.
+		///
+		/// A viewer whose reserve and budget cannot cover the ticks is charged what is left,
+		/// taken off the stream and notified with `ViewerFundsExhausted`.
+		fn charge_ticks(
+			stream_id: u128,
+			stream: &StreamOf<T>,
+			viewer: &T::AccountId,
+			ticks: u32,
+		) -> Result<(BalanceOf<T>, Option<u128>), DispatchError> {
+			// Settle the watched seconds out of the viewer's reserve, then their budget for the
+			// creator. Subscribers only accrue watch time.
+			let price = Self::price_for(stream_id, stream, viewer);
+			let amount = Self::tick_amount(stream_id, stream, viewer, ticks)?;
+			let reserved = Balances::<T>::get(stream_id, viewer);
+			// Budgets are held in the native token, so they only cover natively priced streams
+			let mut budget = match stream.asset_id {
+				None => CreatorBudgets::<T>::get(viewer, &stream.creator),
+				Some(_) => Default::default(),
+			};
+			let available = reserved.saturating_add(budget.reserved);
+			// A viewer who cannot cover the ticks pays what they have left and is taken off
+			// the stream below
+			let shortfall = amount.saturating_sub(available);
+			let amount = amount.min(available);
+			let watched = WatchedSeconds::<T>::get(stream_id, viewer);
+			let ticked = (watched, watched.saturating_add(ticks.into()));
+			T::SettlementExtension::before_settlement(&SettlementContext {
+				stream_id,
+				creator: stream.creator.clone(),
+				viewer: viewer.clone(),
+				seconds: ticks,
+				from_second: ticked.0,
+				to_second: ticked.1,
+				price_per_second: price,
+				amount,
+			})?;
+			Self::pay_or_hold(stream_id, stream, viewer, amount, ticked)?;
+			Balances::<T>::insert(stream_id, viewer, reserved.saturating_sub(amount));
+			budget.reserved = budget.reserved.saturating_sub(amount.saturating_sub(reserved));
+			budget.spent = budget.spent.saturating_add(amount);
+			if stream.asset_id.is_none() {
+				CreatorBudgets::<T>::insert(viewer, &stream.creator, budget);
+			}
+			let remaining = available.saturating_sub(amount);
+			let preview_left = Self::preview_left(stream_id, viewer);
+			if preview_left > 0 {
+				PreviewWatched::<T>::mutate(stream_id, viewer, |watched| {
+					*watched = watched.saturating_add(ticks.min(preview_left))
+				});
+			}
+			if SpendingLimits::<T>::contains_key(viewer) {
+				SessionSpending::<T>::mutate(stream_id, viewer, |spent| {
+					*spent = spent.saturating_add(amount)
+				});
+				let today = Self::today();
+				DailySpending::<T>::mutate(viewer, |(day, spent)| {
+					if *day != today {
+						*day = today;
+						*spent = Zero::zero();
+					}
+					*spent = spent.saturating_add(amount);
+				});
+			}
+			WatchedSeconds::<T>::insert(stream_id, viewer, ticked.1);
+			LifetimeWatchSeconds::<T>::mutate(viewer, |seconds| {
+				*seconds = seconds.saturating_add(ticks.into())
+			});
+			ViewerStats::<T>::mutate(viewer, stream_id, |stats| {
+				let stats = stats.get_or_insert_with(Default::default);
+				stats.total_seconds = stats.total_seconds.saturating_add(ticks.into());
+				stats.total_spent = stats.total_spent.saturating_add(amount);
+				stats.last_watched_block = frame_system::Pallet::<T>::block_number();
+			});
+
+			// Update the tick count
+			TickCount::<T>::mutate(stream_id, |count| *count = count.saturating_add(ticks));
+			CreatorEarnings::<T>::mutate(&stream.creator, Self::current_era(), |earnings| {
+				earnings.seconds = earnings.seconds.saturating_add(ticks.into());
+				earnings.gross = earnings.gross.saturating_add(amount);
+			});
+			if !amount.is_zero() {
+				Self::advance_grant(&stream.creator, 0, ticks.into());
+			}
+			let fiat_value = Self::record_settled_value(stream_id, stream, ticks, amount);
+			EraWatchTime::<T>::mutate(Self::current_era(), stream_id, |seconds| {
+				*seconds = seconds.saturating_add(ticks.into())
+			});
+			PendingWatchTime::<T>::mutate(Self::current_era(), (viewer, stream_id), |seconds| {
+				*seconds = seconds.saturating_add(ticks.into())
+			});
+			CreatorWatchTime::<T>::mutate(
+				Self::current_era(),
+				(&stream.creator, viewer),
+				|seconds| *seconds = seconds.saturating_add(ticks.into()),
+			);
+
+			// Warn once as the reserve and budget drop below `LowBalanceSeconds` of watch time
+			let low = price.saturating_mul(T::LowBalanceSeconds::get().into());
+			if !price.is_zero() && available >= low && remaining < low {
+				let seconds_remaining = (remaining / price).saturated_into();
+				Self::deposit_event(Event::LowBalanceWarning {
+					stream_id,
+					viewer: viewer.clone(),
+					seconds_remaining,
+				});
+			}
+
+			// The first settlement activates the session, and one the viewer cannot cover
+			// expires it
+			if !shortfall.is_zero() {
+				Self::move_session(stream_id, viewer, SessionState::Expired)?;
+			} else if SessionStates::<T>::get(stream_id, viewer) == Some(SessionState::Reserved) {
+				Self::move_session(stream_id, viewer, SessionState::Active)?;
+			}
+
+			// Emit an event
+			Self::deposit_event(Event::TickRecorded {
+				stream_id,
+				viewer: viewer.clone(),
+				ticks,
+				amount,
+				fiat_value,
+			});
+
+			if !shortfall.is_zero() {
+				Self::remove_viewer(stream_id, viewer);
+				Self::refund_viewer(stream_id, viewer);
+				Self::deposit_event(Event::ViewerFundsExhausted {
+					stream_id,
+					viewer: viewer.clone(),
+					charged: amount,
+					shortfall,
+				});
+			}
+
+			Ok((amount, fiat_value))
+		}
+
+		/// Take the viewer off the stream before they are drained further than they allow.
+		fn auto_leave(stream_id: u128, viewer: T::AccountId) {
+			if let Some(threshold) = AutoLeaveThresholds::<T>::get(&viewer) {
+				// Viewers whose funds ran out have been taken off already
+				if T::Currency::total_balance(&viewer) < threshold &&
+					StreamViewers::<T>::get(stream_id).contains(&viewer)
+				{
+					Self::remove_viewer(stream_id, &viewer);
+					Self::refund_viewer(stream_id, &viewer);
+					Self::deposit_event(Event::ViewerAutoLeft { stream_id, viewer });
+				}
+			}
+		}
+
+		/// Add a payment of `amount` for `seconds` of `stream_id` to the stream's earnings and
+		/// its creator's settled value for the current era, priced by the oracle at the current
+		/// rate. Returns the fiat value, if there was a rate.
+		///
+		/// The oracle prices the native token only, so payments in other assets are left out
+		/// of the creator's settled value.
+		fn record_settled_value(
+			stream_id: u128,
+			stream: &StreamOf<T>,
+			seconds: u32,
+			amount: BalanceOf<T>,
+		) -> Option<u128> {
+			let fiat_value = match stream.asset_id {
+				Some(_) => None,
+				None if amount.is_zero() => Some(0),
+				None => T::FiatOracle::fiat_value(amount),
+			};
+			StreamEraEarnings::<T>::mutate(Self::current_era(), stream_id, |earnings| {
+				earnings.seconds = earnings.seconds.saturating_add(seconds.into());
+				earnings.gross = earnings.gross.saturating_add(amount);
+				match fiat_value {
+					Some(fiat) => earnings.fiat = earnings.fiat.saturating_add(fiat),
+					None => earnings.unpriced = earnings.unpriced.saturating_add(amount),
+				}
+			});
+			if stream.asset_id.is_some() || amount.is_zero() {
+				return fiat_value
+			}
+
+			CreatorSettledValues::<T>::mutate(&stream.creator, Self::current_era(), |value| {
+				value.native = value.native.saturating_add(amount);
+				match fiat_value {
+					Some(fiat) => value.fiat = value.fiat.saturating_add(fiat),
+					None => value.unpriced = value.unpriced.saturating_add(amount),
+				}
+			});
+			fiat_value
+		}
+
+		/// The commitment a viewer submits before revealing a promo code: the hash of the code,
+		/// the viewer's encoded account id and the salt, concatenated.
+		pub fn promo_commitment(code: &[u8], viewer: &T::AccountId, salt: &[u8; 32]) -> T::Hash {
+			let mut preimage = code.to_vec();
+			preimage.extend(viewer.encode());
+			preimage.extend_from_slice(salt);
+			T::Hashing::hash(&preimage)
+		}
+
+		/// The account that collects the platform fee.
+		pub fn platform_account() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
+		}
+
+		/// The account holding a stream's earnings until they are withdrawn.
+		pub fn escrow_account(stream_id: u128) -> T::AccountId {
+			T::PalletId::get().into_sub_account_truncating(stream_id)
+		}
+
+		/// The id `stream_id` goes by now, following it through any migrations.
+		pub fn resolve_stream_id(mut stream_id: u128) -> u128 {
+			while let Some(new_id) = MigratedStreams::<T>::get(stream_id) {
+				stream_id = new_id;
+			}
+			stream_id
+		}
+
+		/// Move every entry of the double map `M` under `old_id` to `new_id`, returning how
+		/// many were moved.
+		fn move_prefix<M, K, V>(old_id: u128, new_id: u128) -> u32
+		where
+			M: IterableStorageDoubleMap<u128, K, V>,
+			K: FullCodec,
+			V: FullCodec,
+		{
+			let entries = M::drain_prefix(old_id).collect::<Vec<_>>();
+			for (key, value) in &entries {
+				M::insert(new_id, key, value);
+			}
+			entries.len() as u32
+		}
+
+		/// Move what is kept about each viewer and promo of a stream to `new_id`, returning
+		/// the number of records moved.
+		fn move_viewer_records(old_id: u128, new_id: u128) -> u32 {
+			let moved = [
+				Self::move_prefix::<Promos<T>, _, _>(old_id, new_id),
+				Self::move_prefix::<PromoCommitments<T>, _, _>(old_id, new_id),
+				Self::move_prefix::<Balances<T>, _, _>(old_id, new_id),
+				Self::move_prefix::<ViewerLastTick<T>, _, _>(old_id, new_id),
+				Self::move_prefix::<WatchedSeconds<T>, _, _>(old_id, new_id),
+				Self::move_prefix::<ViewerPrices<T>, _, _>(old_id, new_id),
+				Self::move_prefix::<ConsumedVouchers<T>, _, _>(old_id, new_id),
+				Self::move_prefix::<Escrow<T>, _, _>(old_id, new_id),
+				Self::move_prefix::<SplitVesting<T>, _, _>(old_id, new_id),
+				Self::move_prefix::<VestingSchedules<T>, _, _>(old_id, new_id),
+				Self::move_prefix::<PendingSettlements<T>, _, _>(old_id, new_id),
+				Self::move_prefix::<PreviewWatched<T>, _, _>(old_id, new_id),
+				Self::move_prefix::<DeviceLimitExemptions<T>, _, _>(old_id, new_id),
+				Self::move_prefix::<ActiveSessions<T>, _, _>(old_id, new_id),
+				Self::move_prefix::<Referrers<T>, _, _>(old_id, new_id),
+				Self::move_prefix::<SessionSpending<T>, _, _>(old_id, new_id),
+				Self::move_prefix::<SessionStates<T>, _, _>(old_id, new_id),
+				Self::move_prefix::<Subscriptions<T>, _, _>(old_id, new_id),
+				Self::move_prefix::<SweepsDue<T>, _, _>(old_id, new_id),
+				Self::move_prefix::<PlaybackPositions<T>, _, _>(old_id, new_id),
+				Self::move_prefix::<Allowlists<T>, _, _>(old_id, new_id),
+			];
+			moved.iter().sum()
+		}
+
+		/// Move a stream's own records to `new_id` and point the queues and indexes it is in
+		/// at the new id, returning the number of queued ticks and disputes looked through.
+		fn move_stream_records(old_id: u128, new_id: u128, stream: &StreamOf<T>) -> u32 {
+			Streams::<T>::swap(old_id, new_id);
+			StreamSchedules::<T>::swap(old_id, new_id);
+			Tips::<T>::swap(old_id, new_id);
+			EndedAt::<T>::swap(old_id, new_id);
+			StreamMetadata::<T>::swap(old_id, new_id);
+			TickCount::<T>::swap(old_id, new_id);
+			StreamViewers::<T>::swap(old_id, new_id);
+			PendingPrices::<T>::swap(old_id, new_id);
+			RevenueSplits::<T>::swap(old_id, new_id);
+			JoinGates::<T>::swap(old_id, new_id);
+			AccessPolicies::<T>::swap(old_id, new_id);
+			LastSettledInterval::<T>::swap(old_id, new_id);
+			SyncHosts::<T>::swap(old_id, new_id);
+			PositionAnchors::<T>::swap(old_id, new_id);
+			StreamHandles::<T>::swap(old_id, new_id);
+			FreePreviews::<T>::swap(old_id, new_id);
+			DeviceLimits::<T>::swap(old_id, new_id);
+			ViewerCount::<T>::swap(old_id, new_id);
+			MaxViewers::<T>::swap(old_id, new_id);
+			PreBookings::<T>::swap(old_id, new_id);
+			BulkDiscounts::<T>::swap(old_id, new_id);
+			SubscriptionTiers::<T>::swap(old_id, new_id);
+			ReportingUnits::<T>::swap(old_id, new_id);
+
+			StreamsByStatus::<T>::remove(stream.status, old_id);
+			StreamsByStatus::<T>::insert(stream.status, new_id, ());
+			if let Some(handle) = StreamHandles::<T>::get(new_id) {
+				Handles::<T>::mutate(handle, |claim| {
+					if let Some(claim) = claim {
+						claim.stream_id = new_id;
+					}
+				});
+			}
+			let era = Self::current_era();
+			for era in [era.saturating_sub(1), era] {
+				let seconds = EraWatchTime::<T>::take(era, old_id);
+				if seconds != 0 {
+					EraWatchTime::<T>::insert(era, new_id, seconds);
+				}
+			}
+			// Earlier eras' earnings are committed to under the old id already
+			let earnings = StreamEraEarnings::<T>::take(era, old_id);
+			if earnings != Default::default() {
+				StreamEraEarnings::<T>::insert(era, new_id, earnings);
+			}
+
+			// Entries left under the old id in the block-keyed queues are skipped once its
+			// records are gone, so the stream is queued again under the new id
+			if let Some(schedule) = StreamSchedules::<T>::get(new_id) {
+				ScheduledStartsDue::<T>::append(schedule.estimated_block, new_id);
+				if PreBookings::<T>::decode_len(new_id).unwrap_or_default() > 0 {
+					let deadline =
+						schedule.estimated_block.saturating_add(T::PreBookingGracePeriod::get());
+					PreBookingDeadlines::<T>::append(deadline, new_id);
+				}
+			}
+			if let Some((_, effective_at)) = PendingPrices::<T>::get(new_id) {
+				PriceChangesDue::<T>::append(effective_at, new_id);
+			}
+			if stream.status != StreamStatus::Ended && stream.max_duration.is_some() {
+				let expiry = StreamExpiries::<T>::iter()
+					.find(|(_, due)| due.contains(&old_id))
+					.map(|(expiry, _)| expiry);
+				if let Some(expiry) = expiry {
+					StreamExpiries::<T>::mutate(expiry, |due| {
+						due.iter_mut().filter(|id| **id == old_id).for_each(|id| *id = new_id)
+					});
+				}
+			}
+			for (viewer, pending) in PendingSettlements::<T>::iter_prefix(new_id) {
+				let mut due_at = pending.iter().map(|pending| pending.due_at).collect::<Vec<_>>();
+				due_at.dedup();
+				for due_at in due_at {
+					SettlementsDue::<T>::mutate(due_at, |due| {
+						due.iter_mut()
+							.filter(|(id, account)| *id == old_id && *account == viewer)
+							.for_each(|(id, _)| *id = new_id)
+					});
+				}
+			}
+
+			let (first, next) = QueuedTickRange::<T>::get();
+			for place in first..next {
+				if let Some((stream_id, viewer, ticks)) = QueuedTicks::<T>::get(place) {
+					if stream_id == old_id {
+						QueuedTicks::<T>::insert(place, (new_id, viewer, ticks));
+					}
+				}
+			}
+			let mut disputes = 0u32;
+			for (dispute_id, mut dispute) in Disputes::<T>::iter() {
+				disputes.saturating_inc();
+				if dispute.stream_id == old_id {
+					dispute.stream_id = new_id;
+					Disputes::<T>::insert(dispute_id, dispute);
+				}
+			}
+
+			(next.saturating_sub(first) as u32).saturating_add(disputes)
+		}
+
+		/// Move the funds in a stream's escrow account to the escrow account of `new_id`.
+		fn move_escrow(old_id: u128, new_id: u128, stream: &StreamOf<T>) -> DispatchResult {
+			let (from, to) = (Self::escrow_account(old_id), Self::escrow_account(new_id));
+			// Assets go first, so nothing keeps the old escrow account open once its native
+			// balance has left
+			if let Some(asset) = stream.asset_id.clone() {
+				let held = T::Assets::balance(asset.clone(), &from);
+				if !held.is_zero() {
+					T::Assets::transfer(asset, &from, &to, held, Preservation::Expendable)?;
+				}
+			}
+			let free = T::Currency::free_balance(&from);
+			if !free.is_zero() {
+				T::Currency::transfer(&from, &to, free, ExistenceRequirement::AllowDeath)?;
+			}
+			Ok(())
+		}
+
+		/// Pay `amount` out of `viewer`'s reserve, split between the platform, the stream's
+		/// co-hosts and its creator.
+		///
+		/// The platform fee is paid out directly; the creator's and co-hosts' shares are moved
+		/// to the stream's escrow account in a single transfer and credited in `Escrow`.
+		fn split_revenue(
+			stream_id: u128,
+			stream: &StreamOf<T>,
+			viewer: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let co_hosts = RevenueSplits::<T>::get(stream_id);
+			let (fee, cuts, mut creator_share) = split_payment(
+				amount,
+				Self::effective_fee(stream_id),
+				co_hosts.iter().map(|(_, share)| *share),
+			);
+			let referral = Referrers::<T>::get(stream_id, viewer).map(|referrer| {
+				let referral = T::ReferralShare::get().mul_floor(amount).min(creator_share);
+				creator_share = creator_share.saturating_sub(referral);
+				(referrer, referral)
+			});
+
+			if !fee.is_zero() {
+				let platform = Self::platform_account();
+				Self::pay_held(stream_id, stream, viewer, &platform, fee)?;
+				Self::deposit_event(Event::RevenueSplit { stream_id, beneficiary: platform, amount: fee });
+			}
+
+			// Everything else waits in the stream's escrow until its beneficiaries withdraw it
+			let escrowed = amount.saturating_sub(fee);
+			if escrowed.is_zero() {
+				return Ok(())
+			}
+			Self::pay_held(stream_id, stream, viewer, &Self::escrow_account(stream_id), escrowed)?;
+
+			let mut shares = sp_std::vec![(stream.creator.clone(), creator_share)];
+			shares.extend(co_hosts.into_iter().map(|(co_host, _)| co_host).zip(cuts));
+			for (beneficiary, share) in shares {
+				if share.is_zero() {
+					continue;
+				}
+				match SplitVesting::<T>::get(stream_id, &beneficiary) {
+					Some(period) => Self::vest(stream_id, &beneficiary, share, period),
+					None => {
+						let owed = Escrow::<T>::mutate(stream_id, &beneficiary, |owed| {
+							*owed = owed.saturating_add(share);
+							*owed
+						});
+						let policy = SweepPolicies::<T>::get(&beneficiary);
+						if policy.is_some_and(|policy| owed > policy.threshold) {
+							SweepsDue::<T>::insert(stream_id, &beneficiary, ());
+						}
+					},
+				}
+				Self::deposit_event(Event::RevenueSplit { stream_id, beneficiary, amount: share });
+			}
+			let referral = referral.filter(|(_, referral)| !referral.is_zero());
+			if let Some((referrer, referral)) = referral {
+				ReferralEarnings::<T>::mutate(&referrer, stream_id, |earned| {
+					*earned = earned.saturating_add(referral)
+				});
+				Self::deposit_event(Event::ReferralPaid {
+					stream_id,
+					referrer,
+					viewer: viewer.clone(),
+					amount: referral,
+				});
+			}
+
+			Ok(())
+		}
+
+		/// Set `amount` of `who`'s funds aside for a stream: reserved if the stream is priced in
+		/// the native token, moved into its escrow account if priced in another asset.
+		fn hold(
+			stream_id: u128,
+			stream: &StreamOf<T>,
+			who: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			match stream.asset_id.clone() {
+				None => {
+					Self::ensure_below_hold_cap(amount)?;
+					Self::hold_native(HoldReason::StreamDeposit, who, amount)
+				},
+				Some(asset) => T::Assets::transfer(
+					asset,
+					who,
+					&Self::escrow_account(stream_id),
+					amount,
+					Preservation::Preserve,
+				)
+				.map(|_| ())
+				.map_err(|_| Error::<T>::InsufficientBalance.into()),
+			}
+		}
+
+		/// Put `amount` of `who`'s native balance on hold for `reason`.
+		fn hold_native(
+			reason: HoldReason,
+			who: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			T::NativeBalance::hold(&reason.into(), who, amount)
+				.map_err(|_| Error::<T>::InsufficientBalance)?;
+			TotalHeld::<T>::mutate(|held| *held = held.saturating_add(amount));
+			Ok(())
+		}
+
+		/// Release up to `amount` of `who`'s native balance on hold for `reason`.
+		fn release_native(reason: HoldReason, who: &T::AccountId, amount: BalanceOf<T>) {
+			let released =
+				T::NativeBalance::release(&reason.into(), who, amount, Precision::BestEffort);
+			if let Ok(released) = released {
+				TotalHeld::<T>::mutate(|held| *held = held.saturating_sub(released));
+			}
+		}
+
+		/// Pay `amount` of `from`'s native balance on hold for `reason` to `to`, returning
+		/// the amount paid.
+		fn pay_native_held(
+			reason: HoldReason,
+			from: &T::AccountId,
+			to: &T::AccountId,
+			amount: BalanceOf<T>,
+			precision: Precision,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			let paid = T::NativeBalance::transfer_on_hold(
+				&reason.into(),
+				from,
+				to,
+				amount,
+				precision,
+				Restriction::Free,
+				Fortitude::Polite,
+			)?;
+			TotalHeld::<T>::mutate(|held| *held = held.saturating_sub(paid));
+			Ok(paid)
+		}
+
+		/// Fail with `GlobalCapReached` if holding `amount` more would take what the pallet
+		/// holds past `GlobalHoldCap`.
+		fn ensure_below_hold_cap(amount: BalanceOf<T>) -> DispatchResult {
+			if let Some(cap) = GlobalHoldCap::<T>::get() {
+				ensure!(
+					TotalHeld::<T>::get().saturating_add(amount) <= cap,
+					Error::<T>::GlobalCapReached
+				);
+			}
+			Ok(())
+		}
+
+		/// Give `amount` set aside with `hold` back to `who`.
+		fn release(
+			stream_id: u128,
+			stream: &StreamOf<T>,
+			who: &T::AccountId,
+			amount: BalanceOf<T>,
+		) {
+			match stream.asset_id.clone() {
+				None => Self::release_native(HoldReason::StreamDeposit, who, amount),
+				Some(asset) => {
+					let escrow = Self::escrow_account(stream_id);
+					let released =
+						T::Assets::transfer(asset, &escrow, who, amount, Preservation::Preserve);
+					if released.is_err() {
+						log::warn!(
+							target: "runtime::tick-stream",
+							"failed to release {:?} to a viewer of stream {}",
+							amount,
+							stream_id
+						);
+					}
+				},
+			}
+		}
+
+		/// Pay `amount` set aside by `viewer` with `hold` to `beneficiary`.
+		fn pay_held(
+			stream_id: u128,
+			stream: &StreamOf<T>,
+			viewer: &T::AccountId,
+			beneficiary: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			match stream.asset_id.clone() {
+				None => {
+					Self::pay_native_held(
+						HoldReason::StreamDeposit,
+						viewer,
+						beneficiary,
+						amount,
+						Precision::Exact,
+					)?;
+				},
+				// Held assets are in the escrow account already
+				Some(_) if *beneficiary == Self::escrow_account(stream_id) => {},
+				Some(_) => Self::pay_from_escrow(stream_id, stream, beneficiary, amount)?,
+			}
+			Ok(())
+		}
+
+		/// Pay `amount` out of a stream's escrow account, in the asset the stream is priced in.
+		fn pay_from_escrow(
+			stream_id: u128,
+			stream: &StreamOf<T>,
+			to: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let escrow = Self::escrow_account(stream_id);
+			match stream.asset_id.clone() {
+				None =>
+					T::Currency::transfer(&escrow, to, amount, ExistenceRequirement::AllowDeath),
+				Some(asset) => {
+					T::Assets::transfer(asset, &escrow, to, amount, Preservation::Preserve)?;
+					Ok(())
+				},
+			}
+		}
+
+		/// Pay `amount` for ticks `from_tick` to `to_tick` out of `viewer`'s reserve now, or
+		/// hold it for `SettlementDelay` blocks so the viewer can dispute it first.
+		fn pay_or_hold(
+			stream_id: u128,
+			stream: &StreamOf<T>,
+			viewer: &T::AccountId,
+			amount: BalanceOf<T>,
+			(from_tick, to_tick): (u64, u64),
+		) -> DispatchResult {
+			let delay = T::SettlementDelay::get();
+			if delay.is_zero() || amount.is_zero() {
+				return Self::split_revenue(stream_id, stream, viewer, amount)
+			}
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let due_at = now.saturating_add(delay);
+			PendingSettlements::<T>::append(
+				stream_id,
+				viewer,
+				PendingSettlement { from_tick, to_tick, amount, due_at },
+			);
+			let mut due = SettlementsDue::<T>::get(due_at);
+			if !due.iter().any(|(id, account)| *id == stream_id && account == viewer) {
+				due.push((stream_id, viewer.clone()));
+				SettlementsDue::<T>::insert(due_at, due);
+			}
+			ReporterStakes::<T>::mutate(&stream.creator, |stake| {
+				if let Some(stake) = stake {
+					stake.last_reported = now;
+				}
+			});
+
+			Ok(())
+		}
+
+		/// Fail with `PayoutsFrozen` while `who`'s payouts are frozen.
+		fn ensure_payouts_open(who: &T::AccountId) -> DispatchResult {
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				PayoutFreezes::<T>::get(who).map_or(true, |until| until <= now),
+				Error::<T>::PayoutsFrozen
+			);
+			Ok(())
+		}
+
+		/// The account `who`'s earnings from a stream are paid to: the stream's payout account
+		/// for its creator, and `who` for anyone else.
+		fn payee(stream: &StreamOf<T>, who: &T::AccountId) -> T::AccountId {
+			match &stream.payout_account {
+				Some(payout_account) if *who == stream.creator => payout_account.clone(),
+				_ => who.clone(),
+			}
+		}
+
+		/// Note that `amount` of `who`'s earnings from a stream were paid to the stream's payout
+		/// account, if `who` is its creator and it has one.
+		fn note_routed(
+			stream_id: u128,
+			stream: &StreamOf<T>,
+			who: &T::AccountId,
+			amount: BalanceOf<T>,
+		) {
+			match &stream.payout_account {
+				Some(payout_account) if *who == stream.creator =>
+					Self::deposit_event(Event::PayoutRouted {
+						stream_id,
+						creator: stream.creator.clone(),
+						payout_account: payout_account.clone(),
+						amount,
+					}),
+				_ => {},
+			}
+		}
+
+		/// The blocks that must pass between two ticks of a viewer on a stream.
+		pub fn tick_threshold() -> BlockNumberFor<T> {
+			TickThresholdOverride::<T>::get().unwrap_or_else(T::TickThreshold::get)
+		}
+
+		/// The most viewers a single batch may settle.
+		pub fn batch_cap() -> u32 {
+			BatchCapOverride::<T>::get().unwrap_or_else(T::MaxViewersPerBatch::get)
+		}
+
+		/// Whether `amount` of a stream's currency is too little to open `to`'s account with,
+		/// so paying it to `to` would fail.
+		fn is_dust(stream: &StreamOf<T>, to: &T::AccountId, amount: BalanceOf<T>) -> bool {
+			match stream.asset_id.clone() {
+				None =>
+					amount < T::Currency::minimum_balance() &&
+						T::Currency::total_balance(to).is_zero(),
+				Some(asset) =>
+					amount < T::Assets::minimum_balance(asset.clone()) &&
+						T::Assets::total_balance(asset, to).is_zero(),
+			}
+		}
+
+		/// Handle `amount` of `who`'s earnings withdrawn from a stream's escrow, which are too
+		/// little to pay out, by their dust policy.
+		fn handle_dust(
+			stream_id: u128,
+			stream: &StreamOf<T>,
+			who: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let policy = DustPolicies::<T>::get(who).unwrap_or(DustPolicy::Accumulate);
+			match policy {
+				DustPolicy::Donate => {
+					Self::pay_from_escrow(stream_id, stream, &Self::platform_account(), amount)?;
+					Self::deposit_event(Event::DustDonated {
+						stream_id,
+						who: who.clone(),
+						amount,
+					});
+				},
+				DustPolicy::Credit if stream.asset_id.is_none() => {
+					Self::pay_from_escrow(stream_id, stream, &Self::platform_account(), amount)?;
+					ViewingCredits::<T>::mutate(who, |credit| {
+						*credit = credit.saturating_add(amount)
+					});
+					Self::deposit_event(Event::DustCredited {
+						stream_id,
+						who: who.clone(),
+						amount,
+					});
+				},
+				_ => return Err(Error::<T>::BelowExistentialDeposit.into()),
+			}
+			Ok(())
+		}
+
+		/// Pay `who` their viewing credit from the platform account as they join a stream.
+		///
+		/// Credit the platform account cannot pay out yet is kept for a later join.
+		fn redeem_viewing_credit(stream_id: u128, who: &T::AccountId) {
+			let amount = ViewingCredits::<T>::get(who);
+			if amount.is_zero() {
+				return
+			}
+			let paid = with_storage_layer(|| {
+				T::Currency::transfer(
+					&Self::platform_account(),
+					who,
+					amount,
+					ExistenceRequirement::KeepAlive,
+				)
+			});
+			if paid.is_ok() {
+				ViewingCredits::<T>::remove(who);
+				Self::deposit_event(Event::ViewingCreditRedeemed {
+					stream_id,
+					who: who.clone(),
+					amount,
+				});
+			}
+		}
+
+		/// Swap `who`'s hedge policy's share of `amount`, withdrawn from a stream's escrow, into
+		/// its hedge asset for `payee`, returning how much of `amount` was swapped.
+		///
+		/// Nothing is swapped if the swap cannot be quoted or would fill more than the policy's
+		/// slippage below the quote.
+		fn hedge(
+			stream_id: u128,
+			stream: &StreamOf<T>,
+			who: &T::AccountId,
+			payee: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> BalanceOf<T> {
+			let Some(policy) = HedgePolicies::<T>::get(who) else { return Zero::zero() };
+			let share = policy.hedge_percent * amount;
+			if share.is_zero() || stream.asset_id.as_ref() == Some(&policy.asset) {
+				return Zero::zero()
+			}
+
+			let escrow = Self::escrow_account(stream_id);
+			let (from, to) = (stream.asset_id.clone(), policy.asset.clone());
+			let swapped = T::PayoutSwap::quote(from.clone(), to.clone(), share)
+				.ok_or(DispatchError::Unavailable)
+				.and_then(|quoted| {
+					let min_out = quoted.saturating_sub(policy.max_slippage * quoted);
+					let received = with_storage_layer(|| {
+						T::PayoutSwap::swap(&escrow, payee, from, to, share, min_out)
+					})?;
+					Ok((quoted, received))
+				});
+			match swapped {
+				Ok((quoted, received)) => {
+					Self::deposit_event(Event::EarningsHedged {
+						stream_id,
+						who: who.clone(),
+						asset: policy.asset,
+						amount: share,
+						received,
+						quoted,
+					});
+					share
+				},
+				Err(_) => {
+					Self::deposit_event(Event::HedgeSkipped {
+						stream_id,
+						who: who.clone(),
+						amount: share,
+					});
+					Zero::zero()
+				},
+			}
+		}
+
+		/// Pay `amount` of `who`'s earnings in a stream's escrow to their `cold_address`.
+		fn sweep(
+			stream_id: u128,
+			stream: &StreamOf<T>,
+			who: &T::AccountId,
+			cold_address: T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			Self::pay_from_escrow(stream_id, stream, &cold_address, amount)?;
+			Self::deposit_event(Event::SweptToCold {
+				stream_id,
+				who: who.clone(),
+				cold_address,
+				amount,
+			});
+			Ok(())
+		}
+
+		/// Fail with `ReporterBondShort` while `creator`'s reporter bond is below
+		/// `ReporterBond`, so payments are only held against a full bond.
+		fn ensure_bonded(creator: &T::AccountId) -> DispatchResult {
+			let required = T::ReporterBond::get();
+			if required.is_zero() {
+				return Ok(())
+			}
+			let bonded =
+				ReporterStakes::<T>::get(creator).map_or_else(Zero::zero, |stake| stake.bonded);
+			ensure!(bonded >= required, Error::<T>::ReporterBondShort);
+			Ok(())
+		}
+
+		/// Top `who`'s reporter bond up to `ReporterBond`.
+		fn ensure_reporter_bond(who: &T::AccountId) -> DispatchResult {
+			let required = T::ReporterBond::get();
+			if required.is_zero() {
+				return Ok(())
+			}
+
+			ReporterStakes::<T>::try_mutate(who, |stake| {
+				let stake = stake.get_or_insert_with(Default::default);
+				let shortfall = required.saturating_sub(stake.bonded);
+				if !shortfall.is_zero() {
+					Self::hold_native(HoldReason::DisputeBond, who, shortfall)?;
+					stake.bonded = required;
+					Self::deposit_event(Event::ReporterBonded {
+						who: who.clone(),
+						amount: shortfall,
+					});
+				}
+				Ok(())
+			})
+		}
+
+		/// Add `amount` to a co-host's vesting earnings, to be released over `period` blocks.
+		fn vest(
+			stream_id: u128,
+			co_host: &T::AccountId,
+			amount: BalanceOf<T>,
+			period: BlockNumberFor<T>,
+		) {
+			let now = frame_system::Pallet::<T>::block_number();
+			VestingSchedules::<T>::mutate(stream_id, co_host, |schedule| {
+				let schedule = schedule.get_or_insert_with(Default::default);
+				Self::release_vested(schedule, now);
+				schedule.unvested = schedule.unvested.saturating_add(amount);
+				schedule.updated_at = now;
+				schedule.ends_at = now.saturating_add(period);
+			});
+		}
+
+		/// Release the part of a schedule's unvested earnings that has vested by block `now`.
+		fn release_vested(schedule: &mut VestingScheduleOf<T>, now: BlockNumberFor<T>) {
+			if now <= schedule.updated_at {
+				return
+			}
+			let remaining = schedule.ends_at.saturating_sub(schedule.updated_at);
+			let elapsed = now.min(schedule.ends_at).saturating_sub(schedule.updated_at);
+			let released = if elapsed >= remaining {
+				schedule.unvested
+			} else {
+				Perbill::from_rational(
+					elapsed.saturated_into::<u64>(),
+					remaining.saturated_into::<u64>(),
+				)
+				.mul_floor(schedule.unvested)
+			};
+
+			schedule.unvested = schedule.unvested.saturating_sub(released);
+			schedule.claimable = schedule.claimable.saturating_add(released);
+			schedule.updated_at = now;
+		}
+
+		/// The current block number and unix time in seconds.
+		fn now() -> (BlockNumberFor<T>, u64) {
+			(frame_system::Pallet::<T>::block_number(), T::UnixTime::now().as_secs())
+		}
+
+		/// Check that `viewer` may be charged for `ticks` seconds of `stream_id` right now.
+		///
+		/// Returns the block the viewer was last ticked in.
+		fn check_tick_rate(
+			stream_id: u128,
+			viewer: &T::AccountId,
+			ticks: u32,
+		) -> Result<BlockNumberFor<T>, Error<T>> {
+			let (last_block, last_seconds) =
+				ViewerLastTick::<T>::get(stream_id, viewer).ok_or(Error::<T>::Unauthorized)?;
+			let (block, seconds) = Self::now();
+			ensure!(
+				block >= last_block.saturating_add(Self::tick_threshold()),
+				Error::<T>::TickTooEarly
+			);
+			ensure!(u64::from(ticks) <= seconds.saturating_sub(last_seconds), Error::<T>::TooManyTicks);
+			Ok(last_block)
+		}
+
+		/// Check an unsigned `record_tick` without settling it.
+		///
+		/// Returns the block the viewer was last ticked in.
+		fn validate_tick(
+			stream_id: u128,
+			viewer: &T::AccountId,
+			ticks: u32,
+		) -> Result<BlockNumberFor<T>, Error<T>> {
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(stream.status == StreamStatus::Live, Error::<T>::StreamNotLive);
+			ensure!(stream.content_type == ContentType::Live, Error::<T>::BilledByPlayback);
+			ensure!(StreamViewers::<T>::get(stream_id).contains(viewer), Error::<T>::Unauthorized);
+			ensure!(!Self::is_paused(stream_id, viewer), Error::<T>::SessionPaused);
+			Self::check_tick_rate(stream_id, viewer, ticks)
+		}
+
+		/// Map a pallet error to an invalid transaction carrying the error index.
+		fn invalid_transaction(error: Error<T>) -> InvalidTransaction {
+			match DispatchError::from(error) {
+				DispatchError::Module(ModuleError { error, .. }) => InvalidTransaction::Custom(error[0]),
+				_ => InvalidTransaction::Call,
+			}
+		}
+
+		/// Join `who` to a stream as `join_stream_v2` does.
+		fn join(
+			who: T::AccountId,
+			stream_id: u128,
+			seconds: u32,
+			referrer: Option<T::AccountId>,
+			max_price_per_second: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			let stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			Self::check_join_gate(stream_id, &who)?;
+			let price = Self::price_for(stream_id, &stream, &who);
+			ensure!(
+				max_price_per_second.map_or(true, |max| price <= max),
+				Error::<T>::PriceAboveLimit
+			);
+			if let Some(referrer) = referrer {
+				ensure!(referrer != who && referrer != stream.creator, Error::<T>::InvalidReferrer);
+				if !Referrers::<T>::contains_key(stream_id, &who) {
+					Referrers::<T>::insert(stream_id, &who, referrer);
+				}
+			}
+			if stream.asset_id.is_none() {
+				Self::redeem_viewing_credit(stream_id, &who);
+			}
+			Self::do_join(who, stream_id, &stream, price, seconds)
+		}
+
+		/// Reserve `seconds` of watch time at `price` for `who` and add them to the stream.
+		fn do_join(
+			who: T::AccountId,
+			stream_id: u128,
+			stream: &StreamOf<T>,
+			price: BalanceOf<T>,
+			seconds: u32,
+		) -> DispatchResult {
+			ensure!(stream.status != StreamStatus::Ended, Error::<T>::StreamEnded);
+			let viewers = StreamViewers::<T>::get(stream_id);
+			// Viewers already watching, or holding a booked seat, may join however full the
+			// stream is
+			if let Some(max_viewers) = MaxViewers::<T>::get(stream_id) {
+				let bookings = PreBookings::<T>::get(stream_id);
+				let seated =
+					viewers.contains(&who) || bookings.iter().any(|(booked, _)| *booked == who);
+				let seats = viewers.len().saturating_add(bookings.len()) as u32;
+				ensure!(seated || seats < max_viewers, Error::<T>::StreamFull);
+			}
+
+			// Reserve enough to watch the requested number of seconds
+			let deposit =
+				price.checked_mul(&seconds.into()).ok_or(Error::<T>::ArithmeticOverflow)?;
+			Self::hold(stream_id, stream, &who, deposit)?;
+			Balances::<T>::mutate(stream_id, &who, |reserved| *reserved = reserved.saturating_add(deposit));
+
+			// Add viewer to the stream viewers list
+			if !viewers.contains(&who) {
+				let mut viewers = viewers;
+				viewers.push(who.clone());
+				Self::set_viewers(stream_id, viewers);
+			}
+
+			// Watch time is measured from the moment the viewer first joins
+			if !ViewerLastTick::<T>::contains_key(stream_id, &who) {
+				ViewerLastTick::<T>::insert(stream_id, &who, Self::now());
+			}
+			Self::begin_session(stream_id, &who)?;
+
+			// Emit an event
+			Self::deposit_event(Event::ViewerJoined {
+				stream_id,
+				viewer: who,
+			});
+
+			Ok(())
+		}
+
+		/// Replace a stream's viewer list, keeping `ViewerCount` in step.
+		fn set_viewers(stream_id: u128, viewers: Vec<T::AccountId>) {
+			let count = viewers.len() as u32;
+			if viewers.is_empty() {
+				StreamViewers::<T>::remove(stream_id);
+			} else {
+				StreamViewers::<T>::insert(stream_id, viewers);
+			}
+
+			if ViewerCount::<T>::get(stream_id) != count {
+				if count == 0 {
+					ViewerCount::<T>::remove(stream_id);
+				} else {
+					ViewerCount::<T>::insert(stream_id, count);
+				}
+				Self::deposit_event(Event::ViewerCountChanged { stream_id, viewers: count });
+			}
+		}
+
+		/// Begin `viewer`'s session on a stream as `Reserved`, unless they have one already.
+		///
+		/// A viewer's open sessions share one consumer reference on their account, so it is not
+		/// reaped while they have funds on hold for a stream. Fails if the account cannot take
+		/// another consumer.
+		fn begin_session(stream_id: u128, viewer: &T::AccountId) -> DispatchResult {
+			if SessionStates::<T>::contains_key(stream_id, viewer) {
+				return Ok(())
+			}
+			if OpenSessions::<T>::get(viewer) == 0 {
+				frame_system::Pallet::<T>::inc_consumers(viewer)?;
+			}
+			OpenSessions::<T>::mutate(viewer, |open| *open = open.saturating_add(1));
+			SessionStates::<T>::insert(stream_id, viewer, SessionState::Reserved);
+			Self::deposit_event(Event::SessionStateChanged {
+				stream_id,
+				viewer: viewer.clone(),
+				from: None,
+				to: SessionState::Reserved,
+			});
+			Ok(())
+		}
+
+		/// Move `viewer`'s session on a stream from its state to `to`.
+		///
+		/// Fails with `InvalidSessionTransition` if the viewer has no session or it cannot move
+		/// to `to` from its state.
+		fn move_session(
+			stream_id: u128,
+			viewer: &T::AccountId,
+			to: SessionState,
+		) -> DispatchResult {
+			let from = SessionStates::<T>::get(stream_id, viewer)
+				.filter(|from| from.can_transition_to(to))
+				.ok_or(Error::<T>::InvalidSessionTransition)?;
+			SessionStates::<T>::insert(stream_id, viewer, to);
+			Self::deposit_event(Event::SessionStateChanged {
+				stream_id,
+				viewer: viewer.clone(),
+				from: Some(from),
+				to,
+			});
+			Ok(())
+		}
+
+		/// Settle and forget `viewer`'s session on a stream, if they have one. Sessions can be
+		/// settled from every state.
+		fn end_session(stream_id: u128, viewer: &T::AccountId) {
+			let Some(from) = SessionStates::<T>::take(stream_id, viewer) else { return };
+			OpenSessions::<T>::mutate_exists(viewer, |open| {
+				// Sessions the upgrade to version 10 could not reference are not counted, and
+				// have no reference to drop
+				let count = open.unwrap_or(0);
+				*open = count.checked_sub(1).filter(|open| *open > 0);
+				if count == 1 {
+					frame_system::Pallet::<T>::dec_consumers(viewer);
+				}
+			});
+			Self::deposit_event(Event::SessionStateChanged {
+				stream_id,
+				viewer: viewer.clone(),
+				from: Some(Self::expire_if_ended(stream_id, from)),
+				to: SessionState::Settled,
+			});
+		}
+
+		/// Whether `viewer`'s session on a stream is paused on their spending limit.
+		fn is_paused(stream_id: u128, viewer: &T::AccountId) -> bool {
+			SessionStates::<T>::get(stream_id, viewer) == Some(SessionState::GracePaused)
+		}
+
+		/// `state`, or `Expired` if the stream has ended.
+		fn expire_if_ended(stream_id: u128, state: SessionState) -> SessionState {
+			let ended = Streams::<T>::get(stream_id)
+				.map_or(true, |stream| stream.status == StreamStatus::Ended);
+			if ended {
+				SessionState::Expired
+			} else {
+				state
+			}
+		}
+
+		/// Take `who` off a stream's viewer list.
+		fn remove_viewer(stream_id: u128, who: &T::AccountId) {
+			let mut viewers = StreamViewers::<T>::get(stream_id);
+			viewers.retain(|watching| watching != who);
+			Self::set_viewers(stream_id, viewers);
+		}
+
+		/// Check that `who` meets the join gate of `stream_id`, if it has one, and that its
+		/// access policy lets them in.
+		fn check_join_gate(stream_id: u128, who: &T::AccountId) -> DispatchResult {
+			Self::check_access_policy(stream_id, who)?;
+			let Some(gate) = JoinGates::<T>::get(stream_id) else { return Ok(()) };
+
+			// Accounts created before their age was tracked count as created at genesis
+			let created_at = AccountCreatedAt::<T>::get(who).unwrap_or_else(Zero::zero);
+			let age = frame_system::Pallet::<T>::block_number().saturating_sub(created_at);
+			ensure!(age >= gate.min_account_age, Error::<T>::AccountTooNew);
+			let (attested_seconds, attested_identity) = Self::attested_reputation(who);
+			ensure!(
+				LifetimeWatchSeconds::<T>::get(who).saturating_add(attested_seconds) >=
+					gate.min_watch_seconds,
+				Error::<T>::NotEnoughWatchTime
+			);
+			ensure!(
+				!gate.require_identity ||
+					attested_identity ||
+					T::IdentityVerifier::has_identity(who),
+				Error::<T>::IdentityRequired
+			);
+
+			Ok(())
+		}
+
+		/// The seconds `who` has watched on other chains and whether one of them has verified
+		/// their identity, from their unexpired attestations by chains still registered.
+		fn attested_reputation(who: &T::AccountId) -> (u64, bool) {
+			let attestations = ViewerAttestations::<T>::get(who);
+			if attestations.is_empty() {
+				return (0, false)
+			}
+
+			let sources = AttestationSources::<T>::get();
+			let now = frame_system::Pallet::<T>::block_number();
+			attestations
+				.iter()
+				.filter(|a| a.expires_at > now)
+				.filter_map(|a| {
+					let (_, source) = sources.iter().find(|(id, _)| *id == a.source)?;
+					Some((a.watch_seconds, a.verification_level >= source.identity_level))
+				})
+				.fold((0, false), |(seconds, verified), (watched, verifies)| {
+					(seconds.saturating_add(watched), verified || verifies)
+				})
+		}
+
+		/// Check that the access policy of `stream_id` lets `who` join, failing with
+		/// `AccessDenied` otherwise. Creators can always join their own streams.
+		fn check_access_policy(stream_id: u128, who: &T::AccountId) -> DispatchResult {
+			let allowed = match AccessPolicies::<T>::get(stream_id) {
+				None | Some(AccessPolicy::Public) => true,
+				Some(_) if Streams::<T>::get(stream_id).is_some_and(|s| s.creator == *who) => true,
+				Some(AccessPolicy::Allowlist) => Allowlists::<T>::contains_key(stream_id, who),
+				Some(AccessPolicy::TokenGated { asset_id, min_balance }) =>
+					T::Assets::balance(asset_id, who) >= min_balance,
+			};
+			ensure!(allowed, Error::<T>::AccessDenied);
+			Ok(())
+		}
+
+		/// The per-second price `viewer` pays for `stream`, honouring any voucher price.
+		pub fn price_for(stream_id: u128, stream: &StreamOf<T>, viewer: &T::AccountId) -> BalanceOf<T> {
+			ViewerPrices::<T>::get(stream_id, viewer).unwrap_or(stream.price_per_second)
+		}
+
+		/// Whether `viewer` holds a pass to a stream, forgetting it once it has run out.
+		fn is_subscribed(stream_id: u128, viewer: &T::AccountId) -> bool {
+			let Some(expires_at) = Subscriptions::<T>::get(stream_id, viewer) else { return false };
+			if frame_system::Pallet::<T>::block_number() < expires_at {
+				return true
+			}
 
-	#[pallet::pallet]
-	pub struct Pallet<T>(_);
+			Subscriptions::<T>::remove(stream_id, viewer);
+			Self::deposit_event(Event::SubscriptionExpired { stream_id, viewer: viewer.clone() });
+			false
+		}
 
-	/// Configure the pallet by specifying the parameters and types on which it depends.
-	#[pallet::config]
-	pub trait Config: frame_system::Config {
-		/// Because this pallet emits events, it depends on the runtime's definition of an event.
-		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
-	}
+		/// A viewer's watch history on every stream they have watched and not pruned.
+		pub fn viewer_stats_of(viewer: &T::AccountId) -> Vec<(u128, WatchStatsOf<T>)> {
+			ViewerStats::<T>::iter_prefix(viewer).collect()
+		}
 
-	/// Stores the tick count for each stream
-	#[pallet::storage]
-	#[pallet::getter(fn tick_count)]
-	pub type TickCount<T: Config> = StorageMap<_, Blake2_128Concat, u128, u32, ValueQuery>;
+		/// What `viewer` can still be charged for a stream: their reserve for it and their
+		/// budget for its creator.
+		fn available_for(
+			stream_id: u128,
+			stream: &StreamOf<T>,
+			viewer: &T::AccountId,
+		) -> BalanceOf<T> {
+			Balances::<T>::get(stream_id, viewer)
+				.saturating_add(CreatorBudgets::<T>::get(viewer, &stream.creator).reserved)
+		}
 
-	/// Stores the viewers for each stream
-	#[pallet::storage]
-	#[pallet::getter(fn stream_viewers)]
-	pub type StreamViewers<T: Config> = StorageMap<_, Blake2_128Concat, u128, Vec<T::AccountId>, ValueQuery>;
+		/// The entry count and size of every storage item of the pallet.
+		///
+		/// Walks the whole pallet state, so it is meant for runtime API calls, not for
+		/// dispatchables.
+		pub fn storage_footprint() -> Vec<StorageFootprint> {
+			<Pallet<T> as PartialStorageInfoTrait>::partial_storage_info()
+				.into_iter()
+				.map(|info| {
+					let mut footprint =
+						StorageFootprint { name: info.storage_name, entries: 0, bytes: 0 };
+					let mut record = |key: &[u8]| {
+						if let Some(len) = sp_io::storage::read(key, &mut [], 0) {
+							footprint.entries = footprint.entries.saturating_add(1);
+							footprint.bytes =
+								footprint.bytes.saturating_add(key.len() as u64 + u64::from(len));
+						}
+					};
 
-	#[pallet::event]
-	#[pallet::generate_deposit(pub(super) fn deposit_event)]
-	pub enum Event<T: Config> {
-		/// A tick has been recorded for a stream
-		TickRecorded {
+					// A storage value lives at the prefix itself, map entries below it
+					record(&info.prefix);
+					let mut key = info.prefix.clone();
+					while let Some(next) =
+						sp_io::storage::next_key(&key).filter(|next| next.starts_with(&info.prefix))
+					{
+						record(&next);
+						key = next;
+					}
+					footprint
+				})
+				.collect()
+		}
+
+		/// A stream with its metadata, viewer count and tick count.
+		pub fn stream_details(
 			stream_id: u128,
-			viewer: T::AccountId,
-			ticks: u32,
-		},
-		/// A viewer has joined a stream
-		ViewerJoined {
+		) -> Option<StreamDetails<T::AccountId, BalanceOf<T>, BlockNumberFor<T>, AssetIdOf<T>>> {
+			let stream = Streams::<T>::get(stream_id)?;
+			Some(StreamDetails {
+				metadata: StreamMetadata::<T>::get(stream_id),
+				viewers: ViewerCount::<T>::get(stream_id),
+				tick_count: TickCount::<T>::get(stream_id),
+				handle: StreamHandles::<T>::get(stream_id)
+					.and_then(|key| Handles::<T>::get(key))
+					.map(|claim| claim.handle.into_inner()),
+				platform_fee: Self::effective_fee(stream_id),
+				access_policy: AccessPolicies::<T>::get(stream_id).unwrap_or(AccessPolicy::Public),
+				reporting_unit: Self::reporting_unit(stream_id, &stream),
+				stream,
+			})
+		}
+
+		/// The unit `stream_id`'s earnings are reported in: the one its creator chose, or else
+		/// the currency it is priced in.
+		pub fn reporting_unit(stream_id: u128, stream: &StreamOf<T>) -> ReportingUnitOf<T> {
+			ReportingUnits::<T>::get(stream_id).unwrap_or_else(|| match stream.asset_id.clone() {
+				Some(asset_id) => ReportingUnit::Asset(asset_id),
+				None => ReportingUnit::Native,
+			})
+		}
+
+		/// `stream_id`'s earnings over `era` in the unit its creator reports in, converted at
+		/// the rates stored as each payment settled.
+		pub fn stream_report(stream_id: u128, era: u32) -> Option<StreamReportOf<T>> {
+			let stream = Streams::<T>::get(stream_id)?;
+			let unit = Self::reporting_unit(stream_id, &stream);
+			let earnings = StreamEraEarnings::<T>::get(era, stream_id);
+			let (gross, unpriced) = match unit {
+				ReportingUnit::Fiat => (earnings.fiat, earnings.unpriced),
+				_ => (earnings.gross.saturated_into(), Zero::zero()),
+			};
+			Some(StreamReport { unit, seconds: earnings.seconds, gross, unpriced })
+		}
+
+		/// What `stream_id` earned over `era`, with the proof that leads from it to the era's
+		/// committed root. `None` until the era has been committed, or if nothing was settled
+		/// for the stream in it.
+		pub fn era_settlement_proof(
+			era: u32,
 			stream_id: u128,
-			viewer: T::AccountId,
-		},
-	}
+		) -> Option<EraSettlementProof<StreamEarnings<BalanceOf<T>>>> {
+			let commitment = EraSettlementCommitments::<T>::get(era)?;
+			let earnings = Self::era_earnings_by_stream(era);
+			let index = earnings.iter().position(|(id, _)| *id == stream_id)?;
+			let leaves = Self::era_settlement_leaves(&earnings);
+			let proof = settlement_digest::merkle_proof(&leaves, index)?;
+			Some(EraSettlementProof {
+				commitment,
+				earnings: earnings[index].1.clone(),
+				index: index as u32,
+				proof,
+			})
+		}
 
-	// Errors inform users that something went wrong.
-	#[pallet::error]
-	pub enum Error<T> {
-		/// The stream does not exist
-		StreamNotFound,
-		/// The viewer is not authorized
-		Unauthorized,
-	}
+		/// The platform fee on `stream_id`'s payments: its category's fee, if governance has
+		/// set one, and otherwise `PlatformFee`.
+		pub fn effective_fee(stream_id: u128) -> Permill {
+			StreamMetadata::<T>::get(stream_id)
+				.and_then(|metadata| CategoryFees::<T>::get(metadata.category))
+				.unwrap_or_else(PlatformFee::<T>::get)
+		}
 
-	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+		/// Up to `limit` streams matching `filter`, capped at `MAX_STREAMS_PER_PAGE`, after
+		/// skipping the first `offset` matches.
+		///
+		/// Streams come in storage order, which stays the same between calls as long as no
+		/// streams are created or change state.
+		pub fn list_streams(
+			offset: u32,
+			limit: u32,
+			filter: StreamFilter<T::AccountId>,
+		) -> Vec<StreamSummary<T::AccountId, BalanceOf<T>>> {
+			let streams: Box<dyn Iterator<Item = (u128, StreamOf<T>)>> = match filter.status {
+				Some(status) => Box::new(
+					StreamsByStatus::<T>::iter_key_prefix(status)
+						.filter_map(|stream_id| Some((stream_id, Streams::<T>::get(stream_id)?))),
+				),
+				None => Box::new(Streams::<T>::iter()),
+			};
 
-	// Extrinsic to record ticks for a stream
-	#[pallet::call]
-	impl<T: Config> Pallet<T> {
-		#[pallet::call_index(0)]
-		#[pallet::weight((10_000, DispatchClass::Normal))]
-		pub fn record_tick(
-			origin: OriginFor<T>,
+			streams
+				.filter(|(_, stream)| {
+					filter.creator.as_ref().map_or(true, |creator| *creator == stream.creator)
+				})
+				.skip(offset as usize)
+				.take(limit.min(MAX_STREAMS_PER_PAGE) as usize)
+				.map(|(stream_id, stream)| StreamSummary {
+					stream_id,
+					viewer_count: ViewerCount::<T>::get(stream_id),
+					creator: stream.creator,
+					price_per_second: stream.price_per_second,
+					status: stream.status,
+				})
+				.collect()
+		}
+
+		/// The scheduled streams of every creator whose streams `viewer` follows, soonest
+		/// first. Up to `limit` streams, capped at `MAX_STREAMS_PER_PAGE`, after skipping
+		/// `offset`.
+		pub fn upcoming_streams(
+			viewer: &T::AccountId,
+			offset: u32,
+			limit: u32,
+		) -> Vec<UpcomingStream<T::AccountId, BlockNumberFor<T>>> {
+			let Some(prefs) = NotificationPreferences::<T>::get(viewer) else { return Vec::new() };
+			let creators = prefs
+				.followed
+				.into_iter()
+				.filter_map(|stream_id| Some(Streams::<T>::get(stream_id)?.creator))
+				.collect::<BTreeSet<_>>();
+			if creators.is_empty() {
+				return Vec::new()
+			}
+
+			let mut upcoming = StreamSchedules::<T>::iter()
+				.filter_map(|(stream_id, schedule)| {
+					let creator = Streams::<T>::get(stream_id)?.creator;
+					creators.contains(&creator).then(|| UpcomingStream {
+						stream_id,
+						creator,
+						title: StreamMetadata::<T>::get(stream_id).map(|info| info.title),
+						schedule,
+					})
+				})
+				.collect::<Vec<_>>();
+			upcoming.sort_by_key(|stream| (stream.schedule.starts_at, stream.stream_id));
+			upcoming
+				.into_iter()
+				.skip(offset as usize)
+				.take(limit.min(MAX_STREAMS_PER_PAGE) as usize)
+				.collect()
+		}
+
+		/// Look up who holds `handle` and for which stream, regardless of its case.
+		pub fn resolve_handle(
+			handle: &[u8],
+		) -> Option<HandleClaim<T::AccountId, BalanceOf<T>, Vec<u8>>> {
+			let key = HandleOf::<T>::try_from(handle.to_ascii_lowercase()).ok()?;
+			let claim = Handles::<T>::get(key)?;
+			Some(HandleClaim {
+				handle: claim.handle.into_inner(),
+				owner: claim.owner,
+				stream_id: claim.stream_id,
+				deposit: claim.deposit,
+			})
+		}
+
+		/// The message a reporter signs to charge `viewer` for `ticks` seconds of a stream.
+		pub fn tick_payload(
 			stream_id: u128,
-			viewer: T::AccountId,
+			viewer: &T::AccountId,
 			ticks: u32,
+			nonce: u64,
+		) -> Vec<u8> {
+			(stream_id, viewer, ticks, nonce).encode()
+		}
+
+		/// The message a reporter signs to settle a batch of ticks for an interval of a stream.
+		pub fn batch_payload(
+			stream_id: u128,
+			interval_index: u64,
+			ticks: &[(T::AccountId, u32)],
+			nonce: u64,
+		) -> Vec<u8> {
+			(stream_id, interval_index, ticks, nonce).encode()
+		}
+
+		/// Check that `interval_index` comes after the last interval settled on the stream.
+		fn ensure_interval_unsettled(stream_id: u128, interval_index: u64) -> Result<(), Error<T>> {
+			let settled = LastSettledInterval::<T>::get(stream_id);
+			ensure!(
+				settled.map_or(true, |last| interval_index > last),
+				Error::<T>::IntervalAlreadySettled
+			);
+			Ok(())
+		}
+
+		/// Check that `reporter` is registered and signed `payload` under a nonce it has not
+		/// used yet.
+		fn check_report(
+			reporter: &T::AccountId,
+			nonce: u64,
+			payload: &[u8],
+			signature: &T::OffchainSignature,
+		) -> Result<(), Error<T>> {
+			let info = Reporters::<T>::get(reporter).ok_or(Error::<T>::NotReporter)?;
+			ensure!(info.unbonds_at.is_none(), Error::<T>::NotReporter);
+			ensure!(nonce > info.nonce, Error::<T>::StaleReporterNonce);
+			ensure!(signature.verify(payload, reporter), Error::<T>::InvalidReporterSignature);
+			Ok(())
+		}
+
+		/// Check a report as `check_report` does and use up its nonce.
+		fn accept_report(
+			reporter: &T::AccountId,
+			nonce: u64,
+			payload: &[u8],
+			signature: &T::OffchainSignature,
 		) -> DispatchResult {
-			ensure_signed(origin)?;
+			Self::check_report(reporter, nonce, payload, signature)?;
+			Reporters::<T>::mutate(reporter, |info| {
+				if let Some(info) = info {
+					info.nonce = nonce;
+				}
+			});
+			Ok(())
+		}
 
-			// Check if viewer is in the stream viewers list
-			let viewers = StreamViewers::<T>::get(stream_id);
-			ensure!(viewers.contains(&viewer), Error::<T>::Unauthorized);
+		/// The message a DID's key signs to link the DID to `who`.
+		pub fn did_link_payload(
+			who: &T::AccountId,
+			did: &[u8],
+			document_hash: &[u8; 32],
+		) -> Vec<u8> {
+			(DID_LINK_CONTEXT, who, did, document_hash).encode()
+		}
 
-			// Update the tick count
-			let current_ticks = TickCount::<T>::get(stream_id);
-			TickCount::<T>::insert(stream_id, current_ticks.saturating_add(ticks));
+		/// The message an attestation source's signer signs to vouch for `who`.
+		pub fn attestation_payload(
+			who: &T::AccountId,
+			attestation: &ViewerAttestationOf<T>,
+		) -> Vec<u8> {
+			(ATTESTATION_CONTEXT, who, attestation).encode()
+		}
 
-			// Emit an event
-			Self::deposit_event(Event::TickRecorded {
-				stream_id,
-				viewer,
-				ticks,
+		/// An account's DID link, with the DID as plain bytes.
+		pub fn did_link_of(who: &T::AccountId) -> Option<DidLink<Vec<u8>, BlockNumberFor<T>>> {
+			let link = DidLinks::<T>::get(who)?;
+			Some(DidLink {
+				did: link.did.into_inner(),
+				document_hash: link.document_hash,
+				signature: link.signature,
+				linked_at: link.linked_at,
+			})
+		}
+
+		/// What the pallet holds in the native token across all accounts, against its cap.
+		pub fn hold_utilization() -> HoldUtilization<BalanceOf<T>> {
+			HoldUtilization { held: TotalHeld::<T>::get(), cap: GlobalHoldCap::<T>::get() }
+		}
+
+		/// The pallet's config constants and the fees and caps governance sets, with a hash
+		/// over them, so that tooling can tell which parameters an upgrade changes.
+		pub fn config_fingerprint() -> ConfigFingerprint {
+			let mut parameters = Pallet::<T>::pallet_constants_metadata()
+				.into_iter()
+				.map(|constant| ConfigParameter {
+					name: constant.name.as_bytes().to_vec(),
+					value: constant.value,
+				})
+				.collect::<Vec<_>>();
+
+			let mut category_fees = CategoryFees::<T>::iter().collect::<Vec<_>>();
+			category_fees.sort_by_key(|(category, _)| *category as u8);
+			parameters.extend([
+				ConfigParameter {
+					name: b"PlatformFee".to_vec(),
+					value: PlatformFee::<T>::get().encode(),
+				},
+				ConfigParameter { name: b"CategoryFees".to_vec(), value: category_fees.encode() },
+				ConfigParameter {
+					name: b"GlobalHoldCap".to_vec(),
+					value: GlobalHoldCap::<T>::get().encode(),
+				},
+			]);
+			parameters.sort_by(|a, b| a.name.cmp(&b.name));
+
+			ConfigFingerprint { hash: sp_io::hashing::blake2_256(&parameters.encode()), parameters }
+		}
+
+		/// The block ticks were last settled in and whether settlement is reported stalled.
+		pub fn settlement_health() -> SettlementHealth<BlockNumberFor<T>> {
+			SettlementHealth {
+				last_settled: BlockSettlements::<T>::get().0,
+				stalled: SettlementStalled::<T>::get(),
+			}
+		}
+
+		/// The account `did` is linked to, if any.
+		pub fn resolve_did(did: &[u8]) -> Option<T::AccountId> {
+			DidAccounts::<T>::get(DidOf::<T>::try_from(did.to_vec()).ok()?)
+		}
+
+		/// A viewer's reserve, watch time and remaining watch time on a stream, or `None` if
+		/// they are not watching it.
+		///
+		/// The remaining watch time counts the viewer's budget for the stream's creator.
+		pub fn viewer_session(
+			stream_id: u128,
+			viewer: &T::AccountId,
+		) -> Option<ViewerSession<BalanceOf<T>>> {
+			let stream = Streams::<T>::get(stream_id)?;
+			if !ViewerLastTick::<T>::contains_key(stream_id, viewer) {
+				return None
+			}
+
+			let reserved = Balances::<T>::get(stream_id, viewer);
+			let available = Self::available_for(stream_id, &stream, viewer);
+			let price = Self::price_for(stream_id, &stream, viewer);
+			let seconds_remaining = (!price.is_zero())
+				.then(|| (available / price).saturated_into::<u64>());
+			Some(ViewerSession {
+				reserved,
+				seconds_watched: WatchedSeconds::<T>::get(stream_id, viewer),
+				seconds_remaining,
+				paused: Self::is_paused(stream_id, viewer),
+				free_seconds_left: Self::free_left(stream_id, viewer),
+			})
+		}
+
+		/// The state of a viewer's session on a stream, or `None` if they have none.
+		///
+		/// Sessions left on an ended stream are `Expired` until their reserve is refunded.
+		pub fn session_state(stream_id: u128, viewer: &T::AccountId) -> Option<SessionState> {
+			let state = SessionStates::<T>::get(stream_id, viewer)?;
+			Some(Self::expire_if_ended(stream_id, state))
+		}
+
+		/// Submit `record_tick` for every viewer of every live stream that can be charged now,
+		/// signed with the first registered reporter key the node holds.
+		///
+		/// Nonces count on from the last the worker signed, so ticks still in the pool keep
+		/// theirs.
+		fn submit_due_ticks() {
+			let reporter = T::TickSigner::reporter_keys().into_iter().find_map(|key| {
+				let account = key.clone().into_account();
+				let info = Reporters::<T>::get(&account).filter(|info| info.unbonds_at.is_none())?;
+				Some((key, account, info.nonce))
 			});
+			let Some((key, account, last_nonce)) = reporter else {
+				log::debug!(target: "runtime::tick-stream", "no reporter key to submit ticks with");
+				return
+			};
+
+			let signed_nonce = StorageValueRef::persistent(OCW_REPORTER_NONCE_KEY);
+			let mut nonce = signed_nonce.get::<u64>().ok().flatten().unwrap_or(0).max(last_nonce);
+			for (stream_id, viewer, ticks) in Self::due_ticks(u32::MAX) {
+				nonce = nonce.saturating_add(1);
+				let payload = Self::tick_payload(stream_id, &viewer, ticks, nonce);
+				let Some(signature) = T::TickSigner::sign(&key, &payload) else { return };
+				signed_nonce.set(&nonce);
+				let call = Call::record_tick {
+					stream_id,
+					viewer,
+					ticks,
+					reporter: account.clone(),
+					nonce,
+					signature,
+				};
+				if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
+					log::warn!(target: "runtime::tick-stream", "failed to submit tick for stream {}", stream_id);
+					let _ = StorageValueRef::persistent(OCW_REJECTED_TICKS_KEY).mutate(
+						|rejected: Result<Option<u64>, _>| {
+							Ok::<_, ()>(rejected.ok().flatten().unwrap_or(0).saturating_add(1))
+						},
+					);
+				}
+			}
+		}
+
+		/// Set a stream's `status`, keeping `StreamsByStatus` in step. The caller writes the
+		/// stream back.
+		fn set_status(stream_id: u128, stream: &mut StreamOf<T>, status: StreamStatus) {
+			if stream.status == StreamStatus::Created && status == StreamStatus::Live {
+				Self::advance_grant(&stream.creator, 1, 0);
+			}
+			StreamsByStatus::<T>::remove(stream.status, stream_id);
+			StreamsByStatus::<T>::insert(status, stream_id, ());
+			stream.status = status;
+		}
+
+		/// Move a stream to `status` on behalf of its creator.
+		fn transition(who: &T::AccountId, stream_id: u128, status: StreamStatus) -> DispatchResult {
+			Streams::<T>::try_mutate(stream_id, |maybe_stream| -> DispatchResult {
+				let stream = maybe_stream.as_mut().ok_or(Error::<T>::StreamNotFound)?;
+				ensure!(stream.creator == *who, Error::<T>::NotStreamCreator);
+				ensure!(stream.status.can_transition_to(status), Error::<T>::InvalidStatusTransition);
+
+				// A stream started or ended by hand no longer follows its schedule
+				StreamSchedules::<T>::remove(stream_id);
+
+				// The duration limit counts from the first time the stream goes live
+				if let (StreamStatus::Created, StreamStatus::Live, Some(max_duration)) =
+					(stream.status, status, stream.max_duration)
+				{
+					let expiry = frame_system::Pallet::<T>::block_number().saturating_add(max_duration);
+					StreamExpiries::<T>::append(expiry, stream_id);
+				}
+
+				// Bookings are only held while a stream has yet to go live
+				if status == StreamStatus::Live {
+					Self::seat_pre_bookings(stream_id, stream);
+				}
+				if status == StreamStatus::Ended {
+					EndedAt::<T>::insert(stream_id, frame_system::Pallet::<T>::block_number());
+					Tips::<T>::remove(stream_id);
+					Self::cancel_pre_bookings(stream_id, stream);
+				}
+
+				Self::set_status(stream_id, stream, status);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::StreamStatusChanged { stream_id, status });
 
 			Ok(())
 		}
 
-		#[pallet::call_index(1)]
-		#[pallet::weight((10_000, DispatchClass::Normal))]
-		pub fn join_stream(
-			origin: OriginFor<T>,
+		/// Join every viewer who booked a seat on a stream that has just gone live, their
+		/// deposit becoming their reserve for it. A viewer whose account cannot take the
+		/// session is refunded instead.
+		fn seat_pre_bookings(stream_id: u128, stream: &StreamOf<T>) {
+			let bookings = PreBookings::<T>::take(stream_id);
+			if bookings.is_empty() {
+				return
+			}
+
+			let mut viewers = StreamViewers::<T>::get(stream_id);
+			let now = Self::now();
+			let mut seated = 0u32;
+			for (viewer, deposit) in &bookings {
+				if Self::begin_session(stream_id, viewer).is_err() {
+					Self::release(stream_id, stream, viewer, *deposit);
+					Self::deposit_event(Event::ViewerRefunded {
+						stream_id,
+						viewer: viewer.clone(),
+						amount: *deposit,
+					});
+					continue
+				}
+				Balances::<T>::mutate(stream_id, viewer, |reserved| {
+					*reserved = reserved.saturating_add(*deposit)
+				});
+				if !viewers.contains(viewer) {
+					viewers.push(viewer.clone());
+				}
+				if !ViewerLastTick::<T>::contains_key(stream_id, viewer) {
+					ViewerLastTick::<T>::insert(stream_id, viewer, now);
+				}
+				seated.saturating_inc();
+				Self::deposit_event(Event::ViewerJoined { stream_id, viewer: viewer.clone() });
+			}
+			Self::set_viewers(stream_id, viewers);
+
+			Self::deposit_event(Event::PreBookingsSeated { stream_id, seated });
+		}
+
+		/// Refund the deposit of every seat booked on a stream. Returns the number refunded.
+		fn cancel_pre_bookings(stream_id: u128, stream: &StreamOf<T>) -> u32 {
+			let bookings = PreBookings::<T>::take(stream_id);
+			if bookings.is_empty() {
+				return 0
+			}
+
+			for (viewer, deposit) in &bookings {
+				Self::release(stream_id, stream, viewer, *deposit);
+			}
+			let refunded = bookings.len() as u32;
+			Self::deposit_event(Event::PreBookingsCancelled { stream_id, refunded });
+			refunded
+		}
+
+		/// Cancel the bookings of every stream that should have gone live
+		/// `PreBookingGracePeriod` blocks before `n` but has not.
+		fn cancel_unstarted_bookings(n: BlockNumberFor<T>) -> Weight {
+			let due = PreBookingDeadlines::<T>::take(n);
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+			for stream_id in due {
+				// Bookings are seated or refunded as soon as a stream goes live or ends
+				weight.saturating_accrue(T::DbWeight::get().reads(1));
+				if PreBookings::<T>::decode_len(stream_id).unwrap_or_default() == 0 {
+					continue
+				}
+
+				// A stream whose start has moved since it was booked has until its new deadline
+				weight.saturating_accrue(T::DbWeight::get().reads(2));
+				let Some(stream) = Streams::<T>::get(stream_id) else { continue };
+				if let Some(schedule) = StreamSchedules::<T>::get(stream_id) {
+					let deadline =
+						schedule.estimated_block.saturating_add(T::PreBookingGracePeriod::get());
+					if deadline > n {
+						PreBookingDeadlines::<T>::append(deadline, stream_id);
+						weight.saturating_accrue(T::DbWeight::get().writes(1));
+						continue
+					}
+				}
+
+				let refunded = Self::cancel_pre_bookings(stream_id, &stream);
+				weight.saturating_accrue(T::DbWeight::get().writes(u64::from(refunded) + 1));
+			}
+
+			weight
+		}
+
+		/// End every stream whose maximum duration runs out at block `n`.
+		fn end_expired_streams(n: BlockNumberFor<T>) -> Weight {
+			let expired = StreamExpiries::<T>::take(n);
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+			for stream_id in expired {
+				weight.saturating_accrue(T::DbWeight::get().reads(1));
+				let Some(mut stream) = Streams::<T>::get(stream_id) else { continue };
+				if stream.status == StreamStatus::Ended {
+					continue
+				}
+
+				Self::set_status(stream_id, &mut stream, StreamStatus::Ended);
+				Streams::<T>::insert(stream_id, stream);
+				EndedAt::<T>::insert(stream_id, n);
+				Tips::<T>::remove(stream_id);
+				let refunded = Self::refund_viewers(stream_id, T::MaxRefundsOnEnd::get());
+				weight.saturating_accrue(
+					T::DbWeight::get().writes(4u64.saturating_add(refunded.saturating_mul(4).into())),
+				);
+
+				Self::deposit_event(Event::StreamAutoEnded { stream_id });
+				Self::deposit_event(Event::StreamStatusChanged { stream_id, status: StreamStatus::Ended });
+			}
+
+			weight
+		}
+
+		/// The block expected at unix time `at_ms`, counting from `block` at `now_ms`.
+		///
+		/// Always at least one block after `block`.
+		fn estimate_block(
+			block: BlockNumberFor<T>,
+			now_ms: u64,
+			at_ms: u64,
+		) -> BlockNumberFor<T> {
+			let block_time = T::ExpectedBlockTime::get().max(1);
+			let blocks = at_ms.saturating_sub(now_ms).div_ceil(block_time).max(1);
+			block.saturating_add(blocks.saturated_into())
+		}
+
+		/// The current unix time in milliseconds, as seen from `on_initialize`.
+		///
+		/// The block's timestamp inherent has not been applied yet, so the clock still reads
+		/// the previous block's time; one expected block time makes up for it.
+		fn initialize_clock_ms() -> u64 {
+			T::UnixTime::now()
+				.as_millis()
+				.saturated_into::<u64>()
+				.saturating_add(T::ExpectedBlockTime::get())
+		}
+
+		/// Move a schedule to the block now expected for its start time, announcing the change.
+		fn reanchor(
 			stream_id: u128,
-		) -> DispatchResult {
-			let who = ensure_signed(origin)?;
+			mut schedule: StreamSchedule<BlockNumberFor<T>>,
+			block: BlockNumberFor<T>,
+			now_ms: u64,
+		) -> bool {
+			let estimated_block =
+				Self::estimate_block(block, now_ms, schedule.starts_at.saturating_mul(1000));
+			if estimated_block == schedule.estimated_block {
+				return false
+			}
 
-			// Add viewer to the stream viewers list
-			StreamViewers::<T>::mutate(stream_id, |viewers| {
-				if !viewers.contains(&who) {
-					viewers.push(who.clone());
+			schedule.estimated_block = estimated_block;
+			StreamSchedules::<T>::insert(stream_id, schedule);
+			ScheduledStartsDue::<T>::append(estimated_block, stream_id);
+			Self::deposit_event(Event::ScheduleAdjusted { stream_id, estimated_block });
+			true
+		}
+
+		/// Take live the scheduled streams expected to start at block `n`.
+		///
+		/// A stream whose start time is still more than half a block away is re-anchored
+		/// instead, as blocks have come faster than expected.
+		fn start_scheduled_streams(n: BlockNumberFor<T>) -> Weight {
+			let due = ScheduledStartsDue::<T>::take(n);
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			let now_ms = Self::initialize_clock_ms();
+
+			for stream_id in due {
+				weight.saturating_accrue(T::DbWeight::get().reads(1));
+				// Skip entries left behind by a reschedule or an earlier re-anchoring
+				let Some(schedule) = StreamSchedules::<T>::get(stream_id) else { continue };
+				if schedule.estimated_block != n {
+					continue
 				}
-			});
 
-			// Emit an event
-			Self::deposit_event(Event::ViewerJoined {
-				stream_id,
-				viewer: who,
+				let starts_at_ms = schedule.starts_at.saturating_mul(1000);
+				if now_ms.saturating_add(T::ExpectedBlockTime::get() / 2) < starts_at_ms {
+					Self::reanchor(stream_id, schedule, n, now_ms);
+					weight.saturating_accrue(T::DbWeight::get().writes(2));
+					continue
+				}
+
+				weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 3));
+				let Some(stream) = Streams::<T>::get(stream_id) else { continue };
+				if Self::transition(&stream.creator, stream_id, StreamStatus::Live).is_ok() {
+					Self::deposit_event(Event::ScheduledStreamStarted { stream_id });
+				}
+			}
+
+			weight
+		}
+
+		/// Re-estimate the start block of every scheduled stream at the start of an era.
+		fn reestimate_schedules(n: BlockNumberFor<T>) -> Weight {
+			let era_length = T::EraLength::get().max(One::one());
+			if !(n % era_length).is_zero() {
+				return Weight::zero()
+			}
+
+			let now_ms = Self::initialize_clock_ms();
+			let schedules = StreamSchedules::<T>::iter().collect::<Vec<_>>();
+			let mut weight = T::DbWeight::get().reads(schedules.len() as u64 + 1);
+			for (stream_id, schedule) in schedules {
+				if Self::reanchor(stream_id, schedule, n, now_ms) {
+					weight.saturating_accrue(T::DbWeight::get().writes(2));
+				}
+			}
+			weight
+		}
+
+		/// Apply the price changes scheduled for block `n`.
+		fn apply_price_changes(n: BlockNumberFor<T>) -> Weight {
+			let due = PriceChangesDue::<T>::take(n);
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+			for stream_id in due {
+				weight.saturating_accrue(T::DbWeight::get().reads(1));
+				// Skip schedules that were replaced by a later `update_price`
+				let Some((price_per_second, effective_at)) = PendingPrices::<T>::get(stream_id) else {
+					continue
+				};
+				if effective_at != n {
+					continue
+				}
+
+				PendingPrices::<T>::remove(stream_id);
+				weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 2));
+				let Some(mut stream) = Streams::<T>::get(stream_id) else { continue };
+				if stream.status == StreamStatus::Ended {
+					continue
+				}
+				stream.price_per_second = price_per_second;
+				Streams::<T>::insert(stream_id, stream);
+
+				Self::deposit_event(Event::PriceChanged { stream_id, price_per_second });
+			}
+
+			weight
+		}
+
+		/// Pay out the held tick payments falling due at block `n` that were not disputed.
+		///
+		/// A payment that cannot be paid out is given back to the viewer rather than dropped.
+		fn finalize_settlements(n: BlockNumberFor<T>) -> Weight {
+			let due = SettlementsDue::<T>::take(n);
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+			for (stream_id, viewer) in due {
+				weight.saturating_accrue(T::DbWeight::get().reads(2));
+				let Some(stream) = Streams::<T>::get(stream_id) else { continue };
+				let (settled, held): (Vec<_>, Vec<_>) =
+					PendingSettlements::<T>::get(stream_id, &viewer)
+						.into_iter()
+						.partition(|pending| pending.due_at <= n);
+				// Skip viewers whose payments due now were all disputed
+				if settled.is_empty() {
+					continue
+				}
+
+				let amount = settled.iter().fold(Zero::zero(), |total: BalanceOf<T>, pending| {
+					total.saturating_add(pending.amount)
+				});
+				weight.saturating_accrue(T::DbWeight::get().reads_writes(4, 5));
+				let paid =
+					with_storage_layer(|| Self::split_revenue(stream_id, &stream, &viewer, amount));
+				if held.is_empty() {
+					PendingSettlements::<T>::remove(stream_id, &viewer);
+				} else {
+					PendingSettlements::<T>::insert(stream_id, &viewer, held);
+				}
+				if paid.is_ok() {
+					Self::deposit_event(Event::SettlementFinalized { stream_id, viewer, amount });
+				} else {
+					Self::release(stream_id, &stream, &viewer, amount);
+					Self::deposit_event(Event::SettlementReturned { stream_id, viewer, amount });
+				}
+			}
+
+			weight
+		}
+
+		/// At the start of an era, forward the escrowed earnings above their owners' sweep
+		/// thresholds to their cold addresses.
+		///
+		/// Earnings of accounts whose payouts are frozen wait for the next era.
+		fn sweep_to_cold(n: BlockNumberFor<T>) -> Weight {
+			let era_length = T::EraLength::get().max(One::one());
+			if !(n % era_length).is_zero() {
+				return Weight::zero()
+			}
+
+			let due = SweepsDue::<T>::drain().collect::<Vec<_>>();
+			let mut weight = T::DbWeight::get().reads_writes(1, due.len() as u64);
+			for (stream_id, who, ()) in due {
+				weight.saturating_accrue(T::DbWeight::get().reads(3));
+				if Self::ensure_payouts_open(&who).is_err() {
+					SweepsDue::<T>::insert(stream_id, &who, ());
+					weight.saturating_accrue(T::DbWeight::get().writes(1));
+					continue
+				}
+				// The policy may have been lifted or raised since the earnings came in
+				let Some(policy) = SweepPolicies::<T>::get(&who) else { continue };
+				let Some(stream) = Streams::<T>::get(stream_id) else { continue };
+				let owed = Escrow::<T>::get(stream_id, &who);
+				let excess = owed.saturating_sub(policy.threshold);
+				if excess.is_zero() {
+					continue
+				}
+
+				weight.saturating_accrue(T::DbWeight::get().writes(3));
+				if Self::sweep(stream_id, &stream, &who, policy.cold_address, excess).is_ok() {
+					Escrow::<T>::insert(stream_id, &who, policy.threshold);
+				}
+			}
+
+			weight
+		}
+
+		/// Lift the payout freezes running out at `n` that have not been extended since.
+		fn release_payout_freezes(n: BlockNumberFor<T>) -> Weight {
+			let expiring = PayoutFreezeExpiries::<T>::take(n);
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+			for who in expiring {
+				weight.saturating_accrue(T::DbWeight::get().reads(1));
+				if PayoutFreezes::<T>::get(&who) != Some(n) {
+					continue
+				}
+				weight.saturating_accrue(T::DbWeight::get().writes(1));
+				PayoutFreezes::<T>::remove(&who);
+				Self::deposit_event(Event::PayoutsReleased { who });
+			}
+
+			weight
+		}
+
+		/// Count `streams` taken live and `paid_seconds` paid for towards the milestones of
+		/// `creator`'s grant, releasing the tranche of each milestone reached.
+		fn advance_grant(creator: &T::AccountId, streams: u32, paid_seconds: u64) {
+			let Some(mut grant) = CreatorGrants::<T>::get(creator) else { return };
+			grant.streams = grant.streams.saturating_add(streams);
+			grant.paid_seconds = grant.paid_seconds.saturating_add(paid_seconds);
+
+			let mut reached = Vec::new();
+			if !grant.streams_tranche.is_zero() && grant.streams >= grant.streams_target {
+				let amount = sp_std::mem::take(&mut grant.streams_tranche);
+				reached.push((GrantMilestone::Streams, amount));
+			}
+			let target_seconds = u64::from(grant.viewer_hours_target).saturating_mul(60 * 60);
+			if !grant.viewer_hours_tranche.is_zero() && grant.paid_seconds >= target_seconds {
+				let amount = sp_std::mem::take(&mut grant.viewer_hours_tranche);
+				reached.push((GrantMilestone::ViewerHours, amount));
+			}
+
+			for (milestone, amount) in reached {
+				Self::release_native(HoldReason::CreatorGrant, creator, amount);
+				Self::deposit_event(Event::GrantMilestoneReached {
+					creator: creator.clone(),
+					milestone,
+					amount,
+				});
+			}
+
+			// A fully vested grant is done with; its deadline finds nothing to claw back
+			if grant.streams_tranche.is_zero() && grant.viewer_hours_tranche.is_zero() {
+				CreatorGrants::<T>::remove(creator);
+			} else {
+				CreatorGrants::<T>::insert(creator, grant);
+			}
+		}
+
+		/// Claw back what has not vested of the grants lapsing at `n`, returning it to the
+		/// platform account.
+		fn claw_back_lapsed_grants(n: BlockNumberFor<T>) -> Weight {
+			let lapsing = GrantDeadlines::<T>::take(n);
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+			for creator in lapsing {
+				weight.saturating_accrue(T::DbWeight::get().reads(1));
+				let Some(grant) = CreatorGrants::<T>::get(&creator) else { continue };
+				if grant.deadline != n {
+					continue
+				}
+				weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 4));
+				CreatorGrants::<T>::remove(&creator);
+				let unvested = grant.streams_tranche.saturating_add(grant.viewer_hours_tranche);
+				let amount = Self::pay_native_held(
+					HoldReason::CreatorGrant,
+					&creator,
+					&Self::platform_account(),
+					unvested,
+					Precision::BestEffort,
+				)
+				.unwrap_or_default();
+				Self::deposit_event(Event::GrantClawedBack { creator, amount });
+			}
+
+			weight
+		}
+
+		/// A seed for the draw identified by `subject`, from `RandomnessSeed` if the chain has
+		/// one and `Randomness` otherwise.
+		fn random_seed(subject: &[u8]) -> T::Hash {
+			match RandomnessSeed::<T>::get() {
+				Some(fixed) => T::Hashing::hash(&[&fixed[..], subject].concat()),
+				None => T::Randomness::random(subject).0,
+			}
+		}
+
+		/// At the start of an era, draw its featured streams weighted by the watch time of the
+		/// era before and drop the watch time of older eras.
+		fn select_era_featured(n: BlockNumberFor<T>) -> Weight {
+			let era_length = T::EraLength::get().max(One::one());
+			if !(n % era_length).is_zero() {
+				return Weight::zero()
+			}
+			let era = Self::current_era();
+			let Some(previous) = era.checked_sub(1) else { return Weight::zero() };
+
+			let candidates = EraWatchTime::<T>::iter_prefix(previous).collect::<Vec<_>>();
+			let seed = Self::random_seed(&(b"tick-stream/featured", era).encode());
+			let streams = select_featured(seed.as_ref(), &candidates, T::FeaturedStreams::get());
+			Featured::<T>::insert(era, (seed, streams.clone()));
+			Self::deposit_event(Event::FeaturedSelected { era, seed, streams });
+
+			let mut weight = T::DbWeight::get().reads_writes(candidates.len() as u64 + 1, 1);
+			if let Some(expired) = previous.checked_sub(1) {
+				let removed = EraWatchTime::<T>::clear_prefix(expired, u32::MAX, None);
+				weight.saturating_accrue(T::DbWeight::get().writes(removed.backend.into()));
+			}
+			weight
+		}
+
+		/// Commit to what every stream earned in the era that ended with the previous block, in
+		/// storage and in this block's header.
+		fn commit_era_settlements(n: BlockNumberFor<T>) -> Weight {
+			let era_length = T::EraLength::get().max(One::one());
+			if !(n % era_length).is_zero() {
+				return Weight::zero()
+			}
+			let Some(era) = Self::current_era().checked_sub(1) else { return Weight::zero() };
+
+			let leaves = Self::era_settlement_leaves(&Self::era_earnings_by_stream(era));
+			let commitment = EraSettlementCommitment {
+				era,
+				root: settlement_digest::merkle_root(&leaves),
+				streams: leaves.len() as u32,
+			};
+			frame_system::Pallet::<T>::deposit_log(settlement_digest::digest_item(&commitment));
+			EraSettlementCommitments::<T>::insert(era, &commitment);
+			Self::deposit_event(Event::EraSettlementsCommitted {
+				era,
+				root: commitment.root,
+				streams: commitment.streams,
 			});
 
-			Ok(())
+			T::DbWeight::get().reads_writes(leaves.len() as u64, 2)
+		}
+
+		/// The leaves of `earnings`, in the same order.
+		fn era_settlement_leaves(
+			earnings: &[(u128, StreamEarnings<BalanceOf<T>>)],
+		) -> Vec<[u8; 32]> {
+			earnings
+				.iter()
+				.map(|(stream_id, earnings)| settlement_digest::leaf_hash(*stream_id, earnings))
+				.collect()
+		}
+
+		/// Every stream's earnings over `era`, in ascending stream id order, as the era's tree
+		/// has its leaves.
+		fn era_earnings_by_stream(era: u32) -> Vec<(u128, StreamEarnings<BalanceOf<T>>)> {
+			let mut earnings = StreamEraEarnings::<T>::iter_prefix(era).collect::<Vec<_>>();
+			earnings.sort_by_key(|(stream_id, _)| *stream_id);
+			earnings
+		}
+
+		/// Hand up to `WatchTimeBatchSize` watch time records of the oldest finished era that
+		/// still has some to `OnWatchTime`, and move on to the next era once it has none left.
+		fn deliver_watch_time() -> Weight {
+			let era = WatchTimeDeliveryEra::<T>::get();
+			let mut weight = T::DbWeight::get().reads(1);
+			if era >= Self::current_era() {
+				return weight
+			}
+
+			let batch_size = T::WatchTimeBatchSize::get().max(1);
+			let records = PendingWatchTime::<T>::drain_prefix(era)
+				.take(batch_size as usize)
+				.map(|((viewer, stream_id), seconds)| (viewer, stream_id, seconds))
+				.collect::<Vec<_>>();
+			weight.saturating_accrue(
+				T::DbWeight::get().reads_writes(records.len() as u64, records.len() as u64),
+			);
+			if !records.is_empty() {
+				weight.saturating_accrue(T::OnWatchTime::on_watch_time(era, &records));
+			}
+			if records.len() < batch_size as usize {
+				WatchTimeDeliveryEra::<T>::put(era.saturating_add(1));
+				weight.saturating_accrue(T::DbWeight::get().writes(1));
+			}
+			weight
+		}
+
+		/// Drop the error counters that have fallen out of the history window.
+		fn prune_error_counts(n: BlockNumberFor<T>) -> Weight {
+			let era_length = T::EraLength::get().max(One::one());
+			if !(n % era_length).is_zero() {
+				return Weight::zero()
+			}
+
+			match Self::current_era().checked_sub(ERROR_COUNT_HISTORY) {
+				Some(expired) => {
+					let removed = ErrorCounts::<T>::clear_prefix(expired, u32::MAX, None);
+					T::DbWeight::get().writes(removed.backend.into())
+				},
+				None => Weight::zero(),
+			}
+		}
+
+		/// Clear an ended stream's viewer list and return up to `limit` viewers' remaining
+		/// reserves.
+		///
+		/// Viewers beyond the limit keep their reservation until it is swept with
+		/// `sweep_expired`. Returns the number of viewers refunded.
+		fn refund_viewers(stream_id: u128, limit: u32) -> u32 {
+			Self::set_viewers(stream_id, Vec::new());
+			let viewers =
+				Balances::<T>::iter_key_prefix(stream_id).take(limit as usize).collect::<Vec<_>>();
+			for viewer in &viewers {
+				Self::refund_viewer(stream_id, viewer);
+			}
+			viewers.len() as u32
+		}
+
+		/// Return a viewer's remaining reserve for a stream and forget their session.
+		fn refund_viewer(stream_id: u128, viewer: &T::AccountId) {
+			let amount = Balances::<T>::take(stream_id, viewer);
+			ViewerLastTick::<T>::remove(stream_id, viewer);
+			ViewerPrices::<T>::remove(stream_id, viewer);
+			WatchedSeconds::<T>::remove(stream_id, viewer);
+			SessionSpending::<T>::remove(stream_id, viewer);
+			Self::end_session(stream_id, viewer);
+			ActiveSessions::<T>::remove(stream_id, viewer);
+			Referrers::<T>::remove(stream_id, viewer);
+			if amount.is_zero() {
+				return
+			}
+			if let Some(stream) = Streams::<T>::get(stream_id) {
+				Self::release(stream_id, &stream, viewer, amount);
+			}
+			Self::deposit_event(Event::ViewerRefunded { stream_id, viewer: viewer.clone(), amount });
 		}
 	}
 
-	impl<T: Config> Pallet<T> {
-		/// Get the tick count for a stream
-		pub fn get_tick_count(stream_id: u128) -> u32 {
-			TickCount::<T>::get(stream_id)
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let longevity = Self::tick_threshold().saturated_into::<u64>().max(1);
+
+			match call {
+				Call::record_tick { stream_id, viewer, ticks, reporter, nonce, signature } => {
+					// Tagging with the viewer's last tick makes a tick valid exactly once per interval
+					let last_tick = Self::validate_tick(*stream_id, viewer, *ticks)
+						.map_err(Self::invalid_transaction)?;
+					let payload = Self::tick_payload(*stream_id, viewer, *ticks, *nonce);
+					Self::check_report(reporter, *nonce, &payload, signature)
+						.map_err(Self::invalid_transaction)?;
+					ValidTransaction::with_tag_prefix("TickStream")
+						.priority(T::UnsignedPriority::get())
+						.and_provides((stream_id, viewer, last_tick))
+						.longevity(longevity)
+						.propagate(true)
+						.build()
+				},
+				Call::batch_tick {
+					stream_id,
+					interval_index,
+					ticks,
+					reporter,
+					nonce,
+					signature,
+				} => {
+					let stream = Streams::<T>::get(stream_id)
+						.ok_or(Self::invalid_transaction(Error::<T>::StreamNotFound))?;
+					if stream.status != StreamStatus::Live {
+						return Err(Self::invalid_transaction(Error::<T>::StreamNotLive).into())
+					}
+					if stream.content_type != ContentType::Live {
+						return Err(Self::invalid_transaction(Error::<T>::BilledByPlayback).into())
+					}
+					if ticks.len() as u32 > Self::batch_cap() {
+						return Err(Self::invalid_transaction(Error::<T>::BatchTooLarge).into())
+					}
+					Self::ensure_interval_unsettled(*stream_id, *interval_index)
+						.map_err(Self::invalid_transaction)?;
+					let payload = Self::batch_payload(*stream_id, *interval_index, ticks, *nonce);
+					Self::check_report(reporter, *nonce, &payload, signature)
+						.map_err(Self::invalid_transaction)?;
+					// Competing batches for the same interval replace each other in the pool
+					ValidTransaction::with_tag_prefix("TickStreamBatch")
+						.priority(T::UnsignedPriority::get())
+						.and_provides((stream_id, interval_index))
+						.longevity(longevity)
+						.propagate(true)
+						.build()
+				},
+				_ => InvalidTransaction::Call.into(),
+			}
+		}
+	}
+
+	#[pallet::inherent]
+	impl<T: Config> ProvideInherent for Pallet<T> {
+		type Call = Call<T>;
+		type Error = frame_support::inherent::MakeFatalError<()>;
+		const INHERENT_IDENTIFIER: InherentIdentifier = INHERENT_IDENTIFIER;
+
+		/// Settle the ticks the author's node found due, if it opted in to settling them in
+		/// blocks it authors.
+		fn create_inherent(data: &InherentData) -> Option<Self::Call> {
+			let mut ticks: Vec<(u128, T::AccountId, u32)> =
+				data.get_data(&INHERENT_IDENTIFIER).ok().flatten()?;
+			ticks.truncate(Self::batch_cap() as usize);
+			if ticks.is_empty() {
+				return None
+			}
+			Some(Call::settle_inherent { ticks: BoundedVec::truncate_from(ticks) })
+		}
+
+		fn is_inherent(call: &Self::Call) -> bool {
+			matches!(call, Call::settle_inherent { .. })
+		}
+	}
+
+	impl<T: Config> OnNewAccount<T::AccountId> for Pallet<T> {
+		fn on_new_account(who: &T::AccountId) {
+			AccountCreatedAt::<T>::insert(who, frame_system::Pallet::<T>::block_number());
+		}
+	}
+
+	impl<T: Config> OnKilledAccount<T::AccountId> for Pallet<T> {
+		fn on_killed_account(who: &T::AccountId) {
+			AccountCreatedAt::<T>::remove(who);
 		}
 	}
 
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T: Config> {
-		pub _phantom: PhantomData<T>,
+		/// Streams to create, under ids counting up from zero. They start out `Created`.
+		pub streams: Vec<GenesisStream<T::AccountId, BalanceOf<T>>>,
+		/// The platform's commission on every tick payment.
+		pub platform_fee: Permill,
+		/// The blocks between two ticks of a viewer, if not `TickThreshold`.
+		pub tick_threshold: Option<BlockNumberFor<T>>,
+		/// Tick reporters registered without a bond, so the chain can settle ticks from its
+		/// first block.
+		pub reporters: Vec<T::AccountId>,
+		/// A seed to draw featured streams with instead of `Randomness`, so that test chains
+		/// feature the same streams on every run.
+		pub randomness_seed: Option<[u8; 32]>,
 	}
 
 	#[pallet::genesis_build]
 	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
-		fn build(&self) {}
+		fn build(&self) {
+			PlatformFee::<T>::put(self.platform_fee);
+			if let Some(threshold) = self.tick_threshold {
+				TickThresholdOverride::<T>::put(threshold);
+			}
+			for reporter in &self.reporters {
+				let info = TickReporter { bond: Zero::zero(), nonce: 0, unbonds_at: None };
+				Reporters::<T>::insert(reporter, info);
+			}
+			if let Some(seed) = self.randomness_seed {
+				RandomnessSeed::<T>::put(seed);
+			}
+
+			// Streams are created as their creators would, so every index is kept in step
+			for stream in &self.streams {
+				let stream_id = NextStreamId::<T>::get();
+				let origin = || frame_system::RawOrigin::Signed(stream.creator.clone()).into();
+				Pallet::<T>::create_stream(origin(), stream.price_per_second, None, 0)
+					.expect("genesis streams are created under fresh ids; qed");
+				if let Some((title, category, content_cid)) = &stream.metadata {
+					let title = BoundedVec::try_from(title.clone())
+						.expect("genesis stream titles must fit in `MaxMetadataLen`");
+					let content_cid = BoundedVec::try_from(content_cid.clone())
+						.expect("genesis stream CIDs must fit in `MaxMetadataLen`");
+					Pallet::<T>::set_metadata(origin(), stream_id, title, *category, content_cid)
+						.expect("the stream was just created by its creator; qed");
+				}
+			}
+		}
 	}
-}
\ No newline at end of file
+}
@@ -0,0 +1,726 @@
+//! Storage migrations of the tick-stream pallet.
+//!
+//! Each migration checks the on-chain storage version itself, so it is run from the pallet's
+//! `on_runtime_upgrade` hook and does nothing once the chain has caught up.
+
+use crate::{
+	BalanceOf, Balances, Config, ContentType, CreatorBudgets, Disputes, Handles, HoldReason,
+	OpenSessions, Pallet, PendingSettlements, PreBookings, ReporterStakes, Reporters, SessionState,
+	SessionStates, Stream, StreamStatus, StreamViewers, Streams, StreamsByStatus, TipOf, Tips,
+	TotalHeld, ViewerCount, ViewerLastTick, WatchedSeconds,
+};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{
+		fungible::{InspectHold, MutateHold},
+		GetStorageVersion, OnRuntimeUpgrade, ReservableCurrency,
+		StorageVersion,
+	},
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::traits::{Saturating, Zero};
+use sp_std::{collections::btree_map::BTreeMap, marker::PhantomData, vec::Vec};
+
+/// The storage layout before streams could be priced in an asset.
+pub mod v1 {
+	use super::*;
+
+	/// A stream as stored at version 1, without its asset.
+	#[derive(Encode, Decode)]
+	pub struct Stream<AccountId, Balance, BlockNumber> {
+		pub creator: AccountId,
+		pub price_per_second: Balance,
+		pub last_tick: BlockNumber,
+		pub status: StreamStatus,
+		pub max_duration: Option<BlockNumber>,
+	}
+
+	pub type StreamOf<T> = Stream<
+		<T as frame_system::Config>::AccountId,
+		crate::BalanceOf<T>,
+		BlockNumberFor<T>,
+	>;
+}
+
+/// The storage layout before creators could route their payouts to another account.
+pub mod v4 {
+	use super::*;
+
+	/// A stream as stored from version 2 to version 4, without its payout account.
+	#[derive(Encode, Decode)]
+	pub struct Stream<AccountId, Balance, BlockNumber, AssetId> {
+		pub creator: AccountId,
+		pub price_per_second: Balance,
+		pub last_tick: BlockNumber,
+		pub status: StreamStatus,
+		pub max_duration: Option<BlockNumber>,
+		pub asset_id: Option<AssetId>,
+	}
+
+	pub type StreamOf<T> = Stream<
+		<T as frame_system::Config>::AccountId,
+		crate::BalanceOf<T>,
+		BlockNumberFor<T>,
+		crate::AssetIdOf<T>,
+	>;
+
+	/// `Streams` as stored from version 2 to version 4.
+	#[frame_support::storage_alias]
+	pub type Streams<T: Config> = StorageMap<Pallet<T>, Blake2_128Concat, u128, StreamOf<T>>;
+}
+
+/// The storage layout before streams could be played on demand.
+pub mod v5 {
+	use super::*;
+
+	/// A stream as stored at version 5, without its content type.
+	#[derive(Encode, Decode)]
+	pub struct Stream<AccountId, Balance, BlockNumber, AssetId> {
+		pub creator: AccountId,
+		pub price_per_second: Balance,
+		pub last_tick: BlockNumber,
+		pub status: StreamStatus,
+		pub max_duration: Option<BlockNumber>,
+		pub asset_id: Option<AssetId>,
+		pub payout_account: Option<AccountId>,
+	}
+
+	pub type StreamOf<T> = Stream<
+		<T as frame_system::Config>::AccountId,
+		crate::BalanceOf<T>,
+		BlockNumberFor<T>,
+		crate::AssetIdOf<T>,
+	>;
+
+	/// `Streams` as stored at version 5.
+	#[frame_support::storage_alias]
+	pub type Streams<T: Config> = StorageMap<Pallet<T>, Blake2_128Concat, u128, StreamOf<T>>;
+}
+
+/// The storage layout before sessions had an explicit state.
+pub mod v8 {
+	use super::*;
+
+	/// `PausedSessions` as stored at version 8, marking the sessions paused on a spending limit.
+	#[frame_support::storage_alias]
+	pub type PausedSessions<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		u128,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		(),
+		OptionQuery,
+	>;
+}
+
+/// Migrate streams to version 2, priced in the native token.
+///
+/// Chains without a storage version are taken to be at version 1.
+pub struct MigrateToV2<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 2 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		// Written in the layout of version 2, which later migrations start from
+		let mut translated = 0u64;
+		v4::Streams::<T>::translate::<v1::StreamOf<T>, _>(|_, old| {
+			translated.saturating_inc();
+			Some(v4::Stream {
+				creator: old.creator,
+				price_per_second: old.price_per_second,
+				last_tick: old.last_tick,
+				status: old.status,
+				max_duration: old.max_duration,
+				asset_id: None,
+			})
+		});
+		StorageVersion::new(2).put::<Pallet<T>>();
+		log::info!(
+			target: "runtime::tick-stream",
+			"migrated {} streams to storage version 2",
+			translated,
+		);
+
+		T::DbWeight::get().reads_writes(translated.saturating_add(1), translated.saturating_add(1))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		// Counted by key, since the old values no longer decode as streams
+		Ok((Streams::<T>::iter_keys().count() as u64).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		let streams = u64::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+		ensure!(Pallet::<T>::on_chain_storage_version() >= 2, "storage version was not bumped");
+		ensure!(
+			Streams::<T>::iter().count() as u64 == streams,
+			"streams were lost in translation"
+		);
+		Ok(())
+	}
+}
+
+/// Migrate to version 3, indexing every stream by its status in `StreamsByStatus`.
+pub struct MigrateToV3<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 3 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut indexed = 0u64;
+		for (stream_id, stream) in v4::Streams::<T>::iter() {
+			StreamsByStatus::<T>::insert(stream.status, stream_id, ());
+			indexed.saturating_inc();
+		}
+		StorageVersion::new(3).put::<Pallet<T>>();
+		log::info!(
+			target: "runtime::tick-stream",
+			"indexed {} streams by status for storage version 3",
+			indexed,
+		);
+
+		T::DbWeight::get().reads_writes(indexed.saturating_add(1), indexed.saturating_add(1))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		Ok((Streams::<T>::iter_keys().count() as u64).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		let streams = u64::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+		ensure!(Pallet::<T>::on_chain_storage_version() >= 3, "storage version was not bumped");
+		ensure!(
+			Streams::<T>::iter().all(|(stream_id, stream)| {
+				StreamsByStatus::<T>::contains_key(stream.status, stream_id)
+			}),
+			"a stream is missing from the status index"
+		);
+		ensure!(
+			StreamsByStatus::<T>::iter_keys().count() as u64 == streams,
+			"the status index holds streams that do not exist"
+		);
+		Ok(())
+	}
+}
+
+/// Migrate to version 4, counting every stream's viewers in `ViewerCount`.
+pub struct MigrateToV4<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 4 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut counted = 0u64;
+		for (stream_id, viewers) in StreamViewers::<T>::iter() {
+			if !viewers.is_empty() {
+				ViewerCount::<T>::insert(stream_id, viewers.len() as u32);
+			}
+			counted.saturating_inc();
+		}
+		StorageVersion::new(4).put::<Pallet<T>>();
+		log::info!(
+			target: "runtime::tick-stream",
+			"counted the viewers of {} streams for storage version 4",
+			counted,
+		);
+
+		T::DbWeight::get().reads_writes(counted.saturating_add(1), counted.saturating_add(1))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		Ok(Vec::new())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		ensure!(Pallet::<T>::on_chain_storage_version() >= 4, "storage version was not bumped");
+		ensure!(
+			StreamViewers::<T>::iter().all(|(stream_id, viewers)| {
+				ViewerCount::<T>::get(stream_id) == viewers.len() as u32
+			}),
+			"a stream's viewer count does not match its viewers"
+		);
+		Ok(())
+	}
+}
+
+/// Migrate streams to version 5, paying their earnings to their creators.
+pub struct MigrateToV5<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV5<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 5 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		// Written in the layout of version 5, which later migrations start from
+		let mut translated = 0u64;
+		v5::Streams::<T>::translate::<v4::StreamOf<T>, _>(|_, old| {
+			translated.saturating_inc();
+			Some(v5::Stream {
+				creator: old.creator,
+				price_per_second: old.price_per_second,
+				last_tick: old.last_tick,
+				status: old.status,
+				max_duration: old.max_duration,
+				asset_id: old.asset_id,
+				payout_account: None,
+			})
+		});
+		StorageVersion::new(5).put::<Pallet<T>>();
+		log::info!(
+			target: "runtime::tick-stream",
+			"migrated {} streams to storage version 5",
+			translated,
+		);
+
+		T::DbWeight::get().reads_writes(translated.saturating_add(1), translated.saturating_add(1))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		Ok((Streams::<T>::iter_keys().count() as u64).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		let streams = u64::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+		ensure!(Pallet::<T>::on_chain_storage_version() >= 5, "storage version was not bumped");
+		ensure!(
+			Streams::<T>::iter().count() as u64 == streams,
+			"streams were lost in translation"
+		);
+		Ok(())
+	}
+}
+
+/// Migrate streams to version 6, broadcast live.
+pub struct MigrateToV6<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV6<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 6 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut translated = 0u64;
+		Streams::<T>::translate::<v5::StreamOf<T>, _>(|_, old| {
+			translated.saturating_inc();
+			Some(Stream {
+				creator: old.creator,
+				price_per_second: old.price_per_second,
+				last_tick: old.last_tick,
+				status: old.status,
+				max_duration: old.max_duration,
+				asset_id: old.asset_id,
+				payout_account: old.payout_account,
+				content_type: ContentType::Live,
+			})
+		});
+		StorageVersion::new(6).put::<Pallet<T>>();
+		log::info!(
+			target: "runtime::tick-stream",
+			"migrated {} streams to storage version 6",
+			translated,
+		);
+
+		T::DbWeight::get().reads_writes(translated.saturating_add(1), translated.saturating_add(1))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		Ok((Streams::<T>::iter_keys().count() as u64).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		let streams = u64::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+		ensure!(Pallet::<T>::on_chain_storage_version() >= 6, "storage version was not bumped");
+		ensure!(
+			Streams::<T>::iter().count() as u64 == streams,
+			"streams were lost in translation"
+		);
+		Ok(())
+	}
+}
+
+/// Migrate to version 7, moving the deposits and bonds reserved with `Currency` onto holds
+/// under their `HoldReason`.
+///
+/// Other pallets reserve on the same accounts, so only what the pallet's own storage accounts
+/// for is moved.
+pub struct MigrateToV7<T>(PhantomData<T>);
+
+impl<T: Config> MigrateToV7<T> {
+	/// Count `amount` more as reserved on `who`.
+	fn add(
+		accounts: &mut BTreeMap<T::AccountId, BalanceOf<T>>,
+		who: T::AccountId,
+		amount: BalanceOf<T>,
+	) {
+		if !amount.is_zero() {
+			accounts.entry(who).or_insert_with(Zero::zero).saturating_accrue(amount);
+		}
+	}
+
+	/// What the pallet has reserved on each account, by the reason it is held for from
+	/// version 7, and the number of storage items read to find it.
+	fn reserved() -> ([(HoldReason, BTreeMap<T::AccountId, BalanceOf<T>>); 4], u64) {
+		let mut read = 0u64;
+		let mut deposits = BTreeMap::new();
+		let mut handles = BTreeMap::new();
+		let mut dispute_bonds = BTreeMap::new();
+		let mut reporter_bonds = BTreeMap::new();
+
+		// Deposits for streams priced in an asset are escrowed rather than reserved
+		let native: BTreeMap<u128, bool> = Streams::<T>::iter()
+			.map(|(stream_id, stream)| (stream_id, stream.asset_id.is_none()))
+			.collect();
+		read.saturating_accrue(native.len() as u64);
+		let is_native = |stream_id: &u128| native.get(stream_id).copied().unwrap_or(false);
+
+		for (stream_id, viewer, reserved) in Balances::<T>::iter() {
+			read.saturating_inc();
+			if is_native(&stream_id) {
+				Self::add(&mut deposits, viewer, reserved);
+			}
+		}
+		for (stream_id, viewer, settlements) in PendingSettlements::<T>::iter() {
+			read.saturating_inc();
+			if is_native(&stream_id) {
+				let amount = settlements
+					.iter()
+					.fold(Zero::zero(), |total: BalanceOf<T>, s| total.saturating_add(s.amount));
+				Self::add(&mut deposits, viewer, amount);
+			}
+		}
+		for (stream_id, bookings) in PreBookings::<T>::iter() {
+			read.saturating_inc();
+			if is_native(&stream_id) {
+				for (viewer, deposit) in bookings {
+					Self::add(&mut deposits, viewer, deposit);
+				}
+			}
+		}
+		for (_, dispute) in Disputes::<T>::iter() {
+			read.saturating_inc();
+			if is_native(&dispute.stream_id) {
+				Self::add(&mut deposits, dispute.viewer, dispute.amount);
+			}
+		}
+		for (viewer, _, budget) in CreatorBudgets::<T>::iter() {
+			read.saturating_inc();
+			Self::add(&mut deposits, viewer, budget.reserved);
+		}
+		for (_, claim) in Handles::<T>::iter() {
+			read.saturating_inc();
+			Self::add(&mut handles, claim.owner, claim.deposit);
+		}
+		for (creator, stake) in ReporterStakes::<T>::iter() {
+			read.saturating_inc();
+			Self::add(&mut dispute_bonds, creator, stake.bonded);
+		}
+		for (reporter, registration) in Reporters::<T>::iter() {
+			read.saturating_inc();
+			Self::add(&mut reporter_bonds, reporter, registration.bond);
+		}
+
+		let reserved = [
+			(HoldReason::StreamDeposit, deposits),
+			(HoldReason::HandleDeposit, handles),
+			(HoldReason::DisputeBond, dispute_bonds),
+			(HoldReason::ReporterBond, reporter_bonds),
+		];
+		(reserved, read)
+	}
+}
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV7<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 7 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let (reserved, read) = Self::reserved();
+		let mut converted = 0u64;
+		for (reason, accounts) in reserved {
+			for (who, amount) in accounts {
+				converted.saturating_inc();
+				let amount = amount.min(T::Currency::reserved_balance(&who));
+				T::Currency::unreserve(&who, amount);
+				if T::NativeBalance::hold(&reason.into(), &who, amount).is_err() {
+					log::warn!(
+						target: "runtime::tick-stream",
+						"could not hold the {:?} of {:?} for {:?}, leaving it free",
+						amount,
+						who,
+						reason,
+					);
+				}
+			}
+		}
+		StorageVersion::new(7).put::<Pallet<T>>();
+		log::info!(
+			target: "runtime::tick-stream",
+			"moved the reserves of {} accounts onto holds for storage version 7",
+			converted,
+		);
+
+		T::DbWeight::get().reads_writes(
+			read.saturating_add(converted.saturating_mul(2)).saturating_add(1),
+			converted.saturating_mul(2).saturating_add(1),
+		)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		Ok(Vec::new())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		ensure!(Pallet::<T>::on_chain_storage_version() >= 7, "storage version was not bumped");
+		let (reserved, _) = Self::reserved();
+		for (reason, accounts) in reserved {
+			ensure!(
+				accounts.iter().all(|(who, amount)| {
+					T::NativeBalance::balance_on_hold(&reason.into(), who) >= *amount
+				}),
+				"a deposit or bond is not on hold"
+			);
+		}
+		Ok(())
+	}
+}
+
+/// Migrate to version 8, counting what the pallet holds in `TotalHeld`.
+pub struct MigrateToV8<T>(PhantomData<T>);
+
+impl<T: Config> MigrateToV8<T> {
+	/// What the pallet holds across all accounts, and the number of storage items read to
+	/// find it.
+	fn held() -> (BalanceOf<T>, u64) {
+		let (reserved, mut read) = MigrateToV7::<T>::reserved();
+		let mut held = BalanceOf::<T>::zero();
+		for (reason, accounts) in reserved {
+			for who in accounts.keys() {
+				read.saturating_inc();
+				held.saturating_accrue(T::NativeBalance::balance_on_hold(&reason.into(), who));
+			}
+		}
+		(held, read)
+	}
+}
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV8<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 8 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let (held, read) = Self::held();
+		TotalHeld::<T>::put(held);
+		StorageVersion::new(8).put::<Pallet<T>>();
+		log::info!(
+			target: "runtime::tick-stream",
+			"counted {:?} held for storage version 8",
+			held,
+		);
+
+		T::DbWeight::get().reads_writes(read.saturating_add(1), 2)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		Ok(Vec::new())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		ensure!(Pallet::<T>::on_chain_storage_version() >= 8, "storage version was not bumped");
+		ensure!(TotalHeld::<T>::get() == Self::held().0, "the total held is miscounted");
+		Ok(())
+	}
+}
+
+/// Migrate to version 9, giving every open session its state in `SessionStates`, which takes
+/// the place of `PausedSessions`.
+///
+/// Sessions paused on a spending limit become `GracePaused`, those with watch time settled
+/// `Active` and the rest `Reserved`.
+pub struct MigrateToV9<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV9<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 9 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut sessions = 0u64;
+		for (stream_id, viewer, _) in ViewerLastTick::<T>::iter() {
+			sessions.saturating_inc();
+			let state = if v8::PausedSessions::<T>::contains_key(stream_id, &viewer) {
+				SessionState::GracePaused
+			} else if WatchedSeconds::<T>::get(stream_id, &viewer) > 0 {
+				SessionState::Active
+			} else {
+				SessionState::Reserved
+			};
+			SessionStates::<T>::insert(stream_id, viewer, state);
+		}
+		let paused = v8::PausedSessions::<T>::clear(u32::MAX, None).unique;
+		StorageVersion::new(9).put::<Pallet<T>>();
+		log::info!(
+			target: "runtime::tick-stream",
+			"gave {} sessions their state for storage version 9",
+			sessions,
+		);
+
+		T::DbWeight::get().reads_writes(
+			sessions.saturating_mul(3).saturating_add(1),
+			sessions.saturating_add(paused.into()).saturating_add(1),
+		)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		Ok((ViewerLastTick::<T>::iter_keys().count() as u64).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		let sessions = u64::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+		ensure!(Pallet::<T>::on_chain_storage_version() >= 9, "storage version was not bumped");
+		ensure!(
+			SessionStates::<T>::iter_keys().count() as u64 == sessions,
+			"a session was left without a state"
+		);
+		ensure!(v8::PausedSessions::<T>::iter_keys().next().is_none(), "paused sessions are left");
+		Ok(())
+	}
+}
+
+/// Migrate to version 10, giving every viewer with open sessions the consumer reference on
+/// their account that they now hold, so it is not reaped while they have funds on hold.
+///
+/// References are added past `MaxConsumers`, as the sessions are open already. A viewer whose
+/// account cannot take one is logged and left without, as before.
+pub struct MigrateToV10<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV10<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 10 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut sessions = BTreeMap::<T::AccountId, u32>::new();
+		for (_, viewer) in SessionStates::<T>::iter_keys() {
+			sessions.entry(viewer).or_default().saturating_inc();
+		}
+		let read = sessions.values().fold(0u64, |read, open| read.saturating_add((*open).into()));
+		let mut viewers = 0u64;
+		for (viewer, open) in sessions {
+			if frame_system::Pallet::<T>::inc_consumers_without_limit(&viewer).is_err() {
+				log::warn!(
+					target: "runtime::tick-stream",
+					"{:?} has {} open sessions but no account to hold them",
+					viewer,
+					open,
+				);
+				continue
+			}
+			OpenSessions::<T>::insert(viewer, open);
+			viewers.saturating_inc();
+		}
+		StorageVersion::new(10).put::<Pallet<T>>();
+		log::info!(
+			target: "runtime::tick-stream",
+			"referenced the accounts of {} viewers for storage version 10",
+			viewers,
+		);
+
+		T::DbWeight::get().reads_writes(
+			read.saturating_add(viewers).saturating_add(1),
+			viewers.saturating_mul(2).saturating_add(1),
+		)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		Ok(Vec::new())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		ensure!(Pallet::<T>::on_chain_storage_version() >= 10, "storage version was not bumped");
+		for (viewer, open) in OpenSessions::<T>::iter() {
+			ensure!(
+				SessionStates::<T>::iter_keys().filter(|(_, v)| *v == viewer).count() as u32 ==
+					open,
+				"open sessions are miscounted"
+			);
+			ensure!(
+				frame_system::Pallet::<T>::consumers(&viewer) > 0,
+				"open sessions hold no reference"
+			);
+		}
+		Ok(())
+	}
+}
+
+/// Migrate to version 11, keeping only the latest `MaxTipsPerStream` tips of each live stream.
+pub struct MigrateToV11<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV11<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 11 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let kept = T::MaxTipsPerStream::get() as usize;
+		let mut streams = 0u64;
+		let mut dropped = 0u64;
+		Tips::<T>::translate::<Vec<TipOf<T>>, _>(|_, mut tips| {
+			streams.saturating_inc();
+			let oldest = tips.len().saturating_sub(kept);
+			dropped.saturating_accrue(oldest as u64);
+			tips.drain(..oldest);
+			Some(BoundedVec::truncate_from(tips))
+		});
+		StorageVersion::new(11).put::<Pallet<T>>();
+		log::info!(
+			target: "runtime::tick-stream",
+			"dropped {} old tips of {} streams for storage version 11",
+			dropped,
+			streams,
+		);
+
+		T::DbWeight::get().reads_writes(streams.saturating_add(1), streams.saturating_add(1))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		Ok((Tips::<T>::iter_keys().count() as u64).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		let streams = u64::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+		ensure!(Pallet::<T>::on_chain_storage_version() >= 11, "storage version was not bumped");
+		ensure!(Tips::<T>::iter().count() as u64 == streams, "tips were lost in translation");
+		Ok(())
+	}
+}
@@ -1,12 +1,22 @@
 use crate as tick_stream;
+use crate::SettlementContext;
 use frame_support::traits::ConstU32;
 use frame_support::traits::ConstU64;
-use frame_support::{parameter_types, traits::Everything};
+use frame_support::{
+	parameter_types,
+	traits::{
+		fungibles, AsEnsureOriginWithArg, Currency, EitherOfDiverse, Everything,
+		ExistenceRequirement, Randomness,
+	},
+	weights::Weight,
+	PalletId,
+};
 use frame_system as system;
 use sp_core::H256;
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup},
-	BuildStorage,
+	testing::{TestSignature, TestXt, UintAuthorityId},
+	traits::{BlakeTwo256, Hash, IdentityLookup},
+	BuildStorage, DispatchError, DispatchResult, Permill,
 };
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -16,7 +26,12 @@ frame_support::construct_runtime!(
 	pub enum Test
 	{
 		System: frame_system,
+		Timestamp: pallet_timestamp,
+		Balances: pallet_balances,
+		Recovery: pallet_recovery,
+		Assets: pallet_assets,
 		TickStream: tick_stream,
+		Council: pallet_collective::<Instance1>,
 	}
 );
 
@@ -37,20 +52,413 @@ impl system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
-	type OnNewAccount = ();
-	type OnKilledAccount = ();
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = TickStream;
+	type OnKilledAccount = TickStream;
 	type SystemWeightInfo = ();
 	type SS58Prefix = ();
 	type OnSetCode = ();
 	type MaxConsumers = ConstU32<16>;
 }
 
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<1>;
+	type WeightInfo = ();
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ConstU32<50>;
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+}
+
+impl pallet_recovery::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type RuntimeCall = RuntimeCall;
+	type Currency = Balances;
+	type ConfigDepositBase = ConstU64<0>;
+	type FriendDepositFactor = ConstU64<0>;
+	type MaxFriends = ConstU32<3>;
+	type RecoveryDeposit = ConstU64<0>;
+}
+
+parameter_types! {
+	pub MaxProposalWeight: Weight = Weight::MAX;
+}
+
+impl pallet_collective::Config<pallet_collective::Instance1> for Test {
+	type RuntimeOrigin = RuntimeOrigin;
+	type Proposal = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type MotionDuration = ConstU64<5>;
+	type MaxProposals = ConstU32<10>;
+	type MaxMembers = ConstU32<5>;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = ();
+	type SetMembersOrigin = frame_system::EnsureRoot<u64>;
+	type MaxProposalWeight = MaxProposalWeight;
+}
+
+impl pallet_assets::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = u64;
+	type AssetId = u32;
+	type AssetIdParameter = u32;
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<frame_system::EnsureSigned<u64>>;
+	type ForceOrigin = frame_system::EnsureRoot<u64>;
+	type AssetDeposit = ConstU64<0>;
+	type AssetAccountDeposit = ConstU64<0>;
+	type MetadataDepositBase = ConstU64<0>;
+	type MetadataDepositPerByte = ConstU64<0>;
+	type ApprovalDeposit = ConstU64<0>;
+	type StringLimit = ConstU32<16>;
+	type Freezer = ();
+	type Extra = ();
+	type CallbackHandle = ();
+	type WeightInfo = ();
+	type RemoveItemsLimit = ConstU32<100>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Test
+where
+	RuntimeCall: From<C>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = Extrinsic;
+}
+
+parameter_types! {
+	pub const TickStreamPalletId: PalletId = PalletId(*b"vk/ticks");
+	pub const ReferralShare: Permill = Permill::from_percent(10);
+	// Ticks are paid out as recorded and no bond is needed, unless a test says otherwise
+	pub static SettlementDelay: u64 = 0;
+	// Settlement is never reported stalled, unless a test says otherwise
+	pub static AlertAfterBlocks: u64 = u64::MAX;
+	pub static ReporterBond: u64 = 0;
+	pub static MaxSettlementsPerBlock: u32 = 100;
+	pub static ExistentialDeposit: u64 = 1;
+	pub static ReporterKeys: Vec<u64> = vec![REPORTER];
+	pub static FirstChargeGraceSeconds: u32 = 0;
+}
+
+/// Root, or more than half of the council.
+pub type EnsureRootOrHalfCouncil = EitherOfDiverse<
+	frame_system::EnsureRoot<u64>,
+	pallet_collective::EnsureProportionMoreThan<u64, pallet_collective::Instance1, 1, 2>,
+>;
+
 impl tick_stream::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type NativeBalance = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type EraLength = ConstU64<10>;
+	type MaxViewersPerBatch = ConstU32<4>;
+	type UnixTime = Timestamp;
+	type TickThreshold = ConstU64<1>;
+	type UnsignedPriority = ConstU64<100>;
+	type OffchainSignature = TestSignature;
+	type OffchainPublic = UintAuthorityId;
+	type PalletId = TickStreamPalletId;
+	type MaxCoHosts = ConstU32<2>;
+	type MaxFollowedStreams = ConstU32<4>;
+	type MaxMetadataLen = ConstU32<16>;
+	type MaxRefundsOnEnd = ConstU32<2>;
+	type SettlementWindow = ConstU64<5>;
+	type SweepReward = ConstU64<1>;
+	type MaxPromoCodeLen = ConstU32<16>;
+	type PriceChangeDelay = ConstU64<3>;
+	type Randomness = TestRandomness;
+	type FeaturedStreams = ConstU32<2>;
+	type LowBalanceSeconds = ConstU32<5>;
+	type MaxTipMessageLen = ConstU32<16>;
+	type MaxTipsPerStream = ConstU32<2>;
+	type ExpectedBlockTime = ConstU64<MILLISECS_PER_BLOCK>;
+	type ViewerStatsRetention = ConstU64<100>;
+	type MaxSubscriptionTiers = ConstU32<2>;
+	type FiatOracle = TestFiatOracle;
+	type SettlementDelay = SettlementDelay;
+	type ReporterBond = ReporterBond;
+	type Assets = Assets;
+	type MaxGateAccountAge = ConstU64<100>;
+	type MaxGateWatchSeconds = ConstU64<1_000>;
+	type IdentityVerifier = ();
+	type OnWatchTime = TestRewards;
+	type WatchTimeBatchSize = ConstU32<2>;
+	type MaxPositionAnchors = ConstU32<3>;
+	type MaxSettlementsPerBlock = MaxSettlementsPerBlock;
+	type AlertAfterBlocks = AlertAfterBlocks;
+	type MaxHandleLen = ConstU32<16>;
+	type HandleDeposit = ConstU64<50>;
+	type MaxDevicesPerViewer = ConstU32<3>;
+	type ReferralShare = ReferralShare;
+	type PreBookingDeposit = ConstU64<20>;
+	type MaxPreBookings = ConstU32<2>;
+	type PreBookingGracePeriod = ConstU64<5>;
+	type MaxGiftRecipients = ConstU32<3>;
+	type MaxDidLen = ConstU32<32>;
+	type TickReporterBond = ConstU64<100>;
+	type ReporterCooldown = ConstU64<10>;
+	type MaxAttestationSources = ConstU32<2>;
+	type MaxPollOptions = ConstU32<4>;
+	type MaxPollTextLen = ConstU32<32>;
+	type MaxPollLookbackEras = ConstU32<2>;
+	type FirstChargeGraceSeconds = FirstChargeGraceSeconds;
+	type TickSigner = TestTickSigner;
+	type SettlementExtension = TestSettlementExtension;
+	type PayoutSwap = TestSwap;
+	type PayoutFreezeOrigin = frame_system::EnsureRoot<u64>;
+	type AdminOrigin = EnsureRootOrHalfCouncil;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = TestBenchmarkHelper;
+}
+
+/// Signs vouchers with test signatures and sets the mock clock.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct TestBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl tick_stream::BenchmarkHelper<UintAuthorityId, TestSignature, u32> for TestBenchmarkHelper {
+	fn signer() -> UintAuthorityId {
+		UintAuthorityId(CREATOR_KEY)
+	}
+
+	fn sign(signer: &UintAuthorityId, message: &[u8]) -> TestSignature {
+		TestSignature(signer.0, message.to_vec())
+	}
+
+	fn set_time(seconds: u64) {
+		pallet_timestamp::Now::<Test>::put(seconds * 1000);
+	}
+
+	fn create_asset() -> u32 {
+		Assets::force_create(RuntimeOrigin::root(), 0, 1, true, 1)
+			.expect("the mock starts without assets");
+		0
+	}
+}
+
+/// The key the benchmarks sign vouchers with.
+#[cfg(feature = "runtime-benchmarks")]
+const CREATOR_KEY: u64 = 100;
+
+/// The tick reporter registered at genesis, whose key the offchain worker holds.
+pub const REPORTER: u64 = 9;
+
+/// Signs ticks with test signatures for the keys in `ReporterKeys`.
+pub struct TestTickSigner;
+
+impl tick_stream::TickSigner<UintAuthorityId, TestSignature> for TestTickSigner {
+	fn reporter_keys() -> Vec<UintAuthorityId> {
+		ReporterKeys::get().into_iter().map(UintAuthorityId).collect()
+	}
+
+	fn sign(reporter: &UintAuthorityId, message: &[u8]) -> Option<TestSignature> {
+		ReporterKeys::get()
+			.contains(&reporter.0)
+			.then(|| TestSignature(reporter.0, message.to_vec()))
+	}
+}
+
+/// Deterministic randomness: the hash of the subject.
+pub struct TestRandomness;
+
+impl Randomness<H256, u64> for TestRandomness {
+	fn random(subject: &[u8]) -> (H256, u64) {
+		(BlakeTwo256::hash(subject), System::block_number())
+	}
+}
+
+thread_local! {
+	static FIAT_RATE: std::cell::Cell<Option<u128>> = const { std::cell::Cell::new(None) };
+}
+
+/// Prices the native token at a rate set by the test, or not at all by default.
+pub struct TestFiatOracle;
+
+impl TestFiatOracle {
+	/// Price every unit of the native token at `rate` fiat units from now on.
+	pub fn set_rate(rate: Option<u128>) {
+		FIAT_RATE.with(|current| current.set(rate));
+	}
+}
+
+impl tick_stream::FiatOracle<u64> for TestFiatOracle {
+	fn fiat_value(amount: u64) -> Option<u128> {
+		FIAT_RATE.with(|rate| rate.get()).map(|rate| u128::from(amount) * rate)
+	}
+}
+
+thread_local! {
+	static WATCH_TIME: std::cell::RefCell<Vec<(u32, Vec<(u64, u128, u64)>)>> =
+		const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// Keeps every batch of watch time handed to it, as a reward pallet would receive them.
+pub struct TestRewards;
+
+impl TestRewards {
+	/// The (era, records) batches received so far.
+	pub fn batches() -> Vec<(u32, Vec<(u64, u128, u64)>)> {
+		WATCH_TIME.with(|batches| batches.borrow().clone())
+	}
+}
+
+impl tick_stream::OnWatchTime<u64> for TestRewards {
+	fn on_watch_time(era: u32, records: &[(u64, u128, u64)]) -> frame_support::weights::Weight {
+		WATCH_TIME.with(|batches| batches.borrow_mut().push((era, records.to_vec())));
+		frame_support::weights::Weight::zero()
+	}
+}
+
+thread_local! {
+	static SETTLEMENTS: std::cell::RefCell<Vec<SettlementContext<u64, u64>>> =
+		const { std::cell::RefCell::new(Vec::new()) };
+	static SETTLEMENT_BUDGET: std::cell::Cell<Option<u64>> = const { std::cell::Cell::new(None) };
+}
+
+/// Keeps every settlement handed to it, and rejects those that would take the total charged
+/// past a budget set by the test, as a purchase-order check would.
+pub struct TestSettlementExtension;
+
+impl TestSettlementExtension {
+	/// Reject settlements once `budget` in total has been charged, or none with `None`.
+	pub fn set_budget(budget: Option<u64>) {
+		SETTLEMENT_BUDGET.with(|current| current.set(budget));
+	}
+
+	/// The settlements let through so far.
+	pub fn settlements() -> Vec<SettlementContext<u64, u64>> {
+		SETTLEMENTS.with(|settlements| settlements.borrow().clone())
+	}
+}
+
+impl tick_stream::SettlementExtension<u64, u64> for TestSettlementExtension {
+	fn weight() -> frame_support::weights::Weight {
+		frame_support::weights::Weight::zero()
+	}
+
+	fn before_settlement(context: &SettlementContext<u64, u64>) -> DispatchResult {
+		let charged: u64 = Self::settlements().iter().map(|settled| settled.amount).sum();
+		let budget = SETTLEMENT_BUDGET.with(|budget| budget.get());
+		if budget.is_some_and(|budget| charged + context.amount > budget) {
+			return Err(DispatchError::Other("over the purchase-order budget"))
+		}
+		SETTLEMENTS.with(|settlements| settlements.borrow_mut().push(context.clone()));
+		Ok(())
+	}
+}
+
+thread_local! {
+	static SWAP_RATES: std::cell::Cell<Option<(u64, u64)>> = const { std::cell::Cell::new(None) };
+}
+
+/// The account the test swap pool keeps what it is paid in.
+pub const SWAP_POOL: u64 = 8;
+
+/// Swaps the native token into assets at rates set by the test, minting what it pays out, or
+/// quotes nothing by default.
+pub struct TestSwap;
+
+impl TestSwap {
+	/// Quote `quoted` and fill at `filled` units of any asset per 100 native units from now
+	/// on, or quote nothing with `None`.
+	pub fn set_rates(rates: Option<(u64, u64)>) {
+		SWAP_RATES.with(|current| current.set(rates));
+	}
+}
+
+impl tick_stream::PayoutSwap<u64, u32, u64> for TestSwap {
+	fn is_supported() -> bool {
+		true
+	}
+
+	fn weight() -> frame_support::weights::Weight {
+		frame_support::weights::Weight::zero()
+	}
+
+	fn quote(from: Option<u32>, _to: u32, amount: u64) -> Option<u64> {
+		let (quoted, _) = SWAP_RATES.with(|rates| rates.get())?;
+		from.is_none().then(|| amount * quoted / 100)
+	}
+
+	fn swap(
+		source: &u64,
+		dest: &u64,
+		_from: Option<u32>,
+		to: u32,
+		amount: u64,
+		min_out: u64,
+	) -> Result<u64, DispatchError> {
+		let (_, filled) = SWAP_RATES.with(|rates| rates.get()).ok_or(DispatchError::Unavailable)?;
+		let received = amount * filled / 100;
+		if received < min_out {
+			return Err(DispatchError::Other("below the minimum out"))
+		}
+		<Balances as Currency<u64>>::transfer(
+			source,
+			&SWAP_POOL,
+			amount,
+			ExistenceRequirement::AllowDeath,
+		)?;
+		<Assets as fungibles::Mutate<u64>>::mint_into(to, dest, received)?;
+		Ok(received)
+	}
+}
+
+/// Milliseconds between blocks in the mock runtime.
+pub const MILLISECS_PER_BLOCK: u64 = 6000;
+
+/// Move the mock chain to block `n`, advancing the timestamp to match.
+pub fn run_to_block(n: u64) {
+	System::set_block_number(n);
+	Timestamp::set_timestamp(n * MILLISECS_PER_BLOCK);
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into()
+	let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 1_000), (2, 1_000), (3, 1_000), (4, 10)],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	tick_stream::GenesisConfig::<Test> { reporters: vec![REPORTER], ..Default::default() }
+		.assimilate_storage(&mut storage)
+		.unwrap();
+
+	TestFiatOracle::set_rate(None);
+	WATCH_TIME.with(|batches| batches.borrow_mut().clear());
+	SETTLEMENTS.with(|settlements| settlements.borrow_mut().clear());
+	TestSettlementExtension::set_budget(None);
+	TestSwap::set_rates(None);
+	SettlementDelay::set(0);
+	ReporterBond::set(0);
+	MaxSettlementsPerBlock::set(100);
+	AlertAfterBlocks::set(u64::MAX);
+	ExistentialDeposit::set(1);
+	ReporterKeys::set(vec![REPORTER]);
+	FirstChargeGraceSeconds::set(0);
+	let mut ext: sp_io::TestExternalities = storage.into();
+	// Events are not recorded in the genesis block.
+	ext.execute_with(|| run_to_block(1));
+	ext
 }
\ No newline at end of file
@@ -3,14 +3,114 @@
 use codec::Codec;
 use sp_runtime::traits::MaybeDisplay;
 use sp_runtime::traits::MaybeFromStr;
+use sp_std::vec::Vec;
+
+use crate::{
+	ConfigFingerprint, CreatorBudget, DidLink, EraEarnings, EraSettlementProof, HandleClaim,
+	HoldUtilization, NotificationPrefs, SessionState, SettlementHealth, StorageFootprint,
+	StreamDetails, StreamEarnings, StreamFilter, StreamReport, StreamSummary, UpcomingStream,
+	ViewerSession, WatchStats,
+};
 
 sp_api::decl_runtime_apis! {
 	/// The API to get tick count information.
-	pub trait TickStreamApi<AccountId> 
+	pub trait TickStreamApi<AccountId, Balance, BlockNumber, AssetId>
 	where
 		AccountId: Codec + MaybeDisplay + MaybeFromStr,
+		Balance: Codec,
+		BlockNumber: Codec,
+		AssetId: Codec,
 	{
 		/// Get the tick count for a stream.
 		fn get_tick_count(stream_id: u128) -> u32;
+
+		/// Get the failed dispatch counts for an era as (call index, error index, count).
+		fn get_error_counts(era: u32) -> Vec<(u8, u8, u32)>;
+
+		/// Get a creator's settled earnings for an era.
+		fn get_creator_earnings(creator: AccountId, era: u32) -> EraEarnings<Balance>;
+
+		/// Get a stream together with its metadata, viewer count and tick count.
+		fn get_stream_details(
+			stream_id: u128,
+		) -> Option<StreamDetails<AccountId, Balance, BlockNumber, AssetId>>;
+
+		/// Get the entry count and size in bytes of every storage item of the pallet.
+		fn get_storage_footprint() -> Vec<StorageFootprint>;
+
+		/// Get a viewer's reserve and watch time on a stream.
+		fn get_viewer_session(stream_id: u128, account: AccountId) -> Option<ViewerSession<Balance>>;
+
+		/// Get the notification preferences of each of `accounts`, in the same order.
+		fn get_notification_prefs(accounts: Vec<AccountId>) -> Vec<Option<NotificationPrefs>>;
+
+		/// Page through every account's notification preferences, starting after
+		/// `start_after`.
+		fn get_notification_prefs_page(
+			start_after: Option<AccountId>,
+			limit: u32,
+		) -> Vec<(AccountId, NotificationPrefs)>;
+
+		/// Get a viewer's budget for a creator's streams and their total spend on them.
+		fn get_creator_budget(viewer: AccountId, creator: AccountId) -> CreatorBudget<Balance>;
+
+		/// Get an account's lifetime watch time and spend on every stream it has watched.
+		fn get_viewer_stats(
+			account: AccountId,
+		) -> Vec<(u128, WatchStats<Balance, BlockNumber>)>;
+
+		/// Get up to a batch's worth of (stream, viewer, seconds) ticks that can be charged
+		/// now, for block authors settling them in an inherent.
+		fn get_due_ticks() -> Vec<(u128, AccountId, u32)>;
+
+		/// Look up who holds a handle and for which stream, regardless of its case.
+		fn resolve_handle(handle: Vec<u8>) -> Option<HandleClaim<AccountId, Balance, Vec<u8>>>;
+
+		/// Page through the streams matching `filter`, skipping the first `offset` and
+		/// returning at most `MAX_STREAMS_PER_PAGE`.
+		fn list_streams(
+			offset: u32,
+			limit: u32,
+			filter: StreamFilter<AccountId>,
+		) -> Vec<StreamSummary<AccountId, Balance>>;
+
+		/// Get the DID an account has linked itself to.
+		fn get_did_link(account: AccountId) -> Option<DidLink<Vec<u8>, BlockNumber>>;
+
+		/// Look up the account a DID is linked to.
+		fn resolve_did(did: Vec<u8>) -> Option<AccountId>;
+
+		/// Get the total the pallet holds in the native token and the cap on it.
+		fn get_hold_utilization() -> HoldUtilization<Balance>;
+
+		/// Get the pallet's config constants and governance-set fees and caps, with a hash
+		/// over them to compare runtimes by.
+		fn config_fingerprint() -> ConfigFingerprint;
+
+		/// Get a stream's earnings over an era in the unit its creator reports in, valued at
+		/// the rates of their settlements.
+		fn get_stream_report(stream_id: u128, era: u32) -> Option<StreamReport<Balance, AssetId>>;
+
+		/// Get what a stream earned over a committed era, with the proof that leads from it to
+		/// the root in the header of the era's first block after it.
+		fn get_era_settlement_proof(
+			era: u32,
+			stream_id: u128,
+		) -> Option<EraSettlementProof<StreamEarnings<Balance>>>;
+
+		/// Get the state of a viewer's session on a stream.
+		fn get_session_state(stream_id: u128, account: AccountId) -> Option<SessionState>;
+
+		/// Get the block ticks were last settled in and whether settlement is reported stalled.
+		fn get_settlement_health() -> SettlementHealth<BlockNumber>;
+
+		/// Page through the scheduled streams of the creators whose streams `viewer` follows,
+		/// soonest first, skipping the first `offset` and returning at most
+		/// `MAX_STREAMS_PER_PAGE`.
+		fn get_upcoming_streams(
+			viewer: AccountId,
+			offset: u32,
+			limit: u32,
+		) -> Vec<UpcomingStream<AccountId, BlockNumber>>;
 	}
 }
\ No newline at end of file
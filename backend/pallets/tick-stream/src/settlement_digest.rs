@@ -0,0 +1,155 @@
+//! Header-anchored commitments to each era's settlement totals.
+//!
+//! As an era ends, the pallet builds a binary Merkle tree over what every stream earned in it
+//! and deposits the root in the header of the era's first block after it. A light client or
+//! auditor holding that header checks a creator's claimed earnings for a stream against the
+//! root with the proof from `era_settlement_proof`, without replaying the era's events.
+//!
+//! Leaves are `blake2_256(0x00 ++ SCALE(stream_id, earnings))`, in ascending stream id order;
+//! inner nodes are `blake2_256(0x01 ++ left ++ right)`, and a node without a sibling moves up
+//! a level unchanged. An era nothing settled in has the all-zero root.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+use sp_io::hashing::blake2_256;
+use sp_runtime::DigestItem;
+use sp_std::vec::Vec;
+
+/// Prefix of the `Other` digest item an era's commitment is deposited as. The SCALE encoding
+/// of its `EraSettlementCommitment` follows.
+pub const ERA_SETTLEMENT_DIGEST: [u8; 8] = *b"tickera:";
+
+/// The root of an era's settlement totals, as deposited in a block header.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct EraSettlementCommitment {
+	/// The era the totals are for.
+	pub era: u32,
+	/// The Merkle root over every stream's totals.
+	pub root: [u8; 32],
+	/// The number of streams, and so of leaves.
+	pub streams: u32,
+}
+
+/// What a stream earned over an era, with the path from its leaf to the era's root.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct EraSettlementProof<Earnings> {
+	/// The commitment the proof leads to.
+	pub commitment: EraSettlementCommitment,
+	/// The stream's totals.
+	pub earnings: Earnings,
+	/// The position of the stream's leaf.
+	pub index: u32,
+	/// The sibling hashes from the leaf up, skipping levels where the node has none.
+	pub proof: Vec<[u8; 32]>,
+}
+
+/// The leaf of `stream_id`'s totals.
+pub fn leaf_hash<Earnings: Encode>(stream_id: u128, earnings: &Earnings) -> [u8; 32] {
+	let mut preimage = sp_std::vec![0u8];
+	(stream_id, earnings).encode_to(&mut preimage);
+	blake2_256(&preimage)
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+	let mut preimage = [0u8; 65];
+	preimage[0] = 1;
+	preimage[1..33].copy_from_slice(left);
+	preimage[33..].copy_from_slice(right);
+	blake2_256(&preimage)
+}
+
+/// The level above `nodes`.
+fn parents(nodes: &[[u8; 32]]) -> Vec<[u8; 32]> {
+	nodes
+		.chunks(2)
+		.map(|pair| match pair {
+			[left, right] => node_hash(left, right),
+			_ => pair[0],
+		})
+		.collect()
+}
+
+/// The root over `leaves`.
+pub fn merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+	if leaves.is_empty() {
+		return [0; 32]
+	}
+	let mut nodes = leaves.to_vec();
+	while nodes.len() > 1 {
+		nodes = parents(&nodes);
+	}
+	nodes[0]
+}
+
+/// The proof of the leaf at `index`, or `None` if there is no such leaf.
+pub fn merkle_proof(leaves: &[[u8; 32]], mut index: usize) -> Option<Vec<[u8; 32]>> {
+	if index >= leaves.len() {
+		return None
+	}
+	let mut proof = Vec::new();
+	let mut nodes = leaves.to_vec();
+	while nodes.len() > 1 {
+		if let Some(sibling) = nodes.get(index ^ 1) {
+			proof.push(*sibling);
+		}
+		nodes = parents(&nodes);
+		index /= 2;
+	}
+	Some(proof)
+}
+
+/// Whether `proof` leads from `leaf`, at `index` of `leaf_count` leaves, to `root`.
+pub fn verify_proof(
+	root: &[u8; 32],
+	leaf: [u8; 32],
+	mut index: u32,
+	mut leaf_count: u32,
+	proof: &[[u8; 32]],
+) -> bool {
+	if index >= leaf_count {
+		return false
+	}
+	let mut proof = proof.iter();
+	let mut hash = leaf;
+	while leaf_count > 1 {
+		if (index ^ 1) < leaf_count {
+			let Some(sibling) = proof.next() else { return false };
+			hash = match index % 2 {
+				0 => node_hash(&hash, sibling),
+				_ => node_hash(sibling, &hash),
+			};
+		}
+		index /= 2;
+		leaf_count = leaf_count / 2 + leaf_count % 2;
+	}
+	proof.next().is_none() && hash == *root
+}
+
+/// Whether `proof` shows that `stream_id` earned what it claims over the era of the
+/// commitment it leads to.
+pub fn verify_era_settlement<Earnings: Encode>(
+	stream_id: u128,
+	proof: &EraSettlementProof<Earnings>,
+) -> bool {
+	verify_proof(
+		&proof.commitment.root,
+		leaf_hash(stream_id, &proof.earnings),
+		proof.index,
+		proof.commitment.streams,
+		&proof.proof,
+	)
+}
+
+/// The digest item `commitment` is deposited as.
+pub fn digest_item(commitment: &EraSettlementCommitment) -> DigestItem {
+	let mut data = ERA_SETTLEMENT_DIGEST.to_vec();
+	commitment.encode_to(&mut data);
+	DigestItem::Other(data)
+}
+
+/// The era settlement commitment a header's digest item carries, if it is one.
+pub fn era_settlement(item: &DigestItem) -> Option<EraSettlementCommitment> {
+	let data = item.as_other()?.strip_prefix(&ERA_SETTLEMENT_DIGEST[..])?;
+	EraSettlementCommitment::decode(&mut &data[..]).ok()
+}
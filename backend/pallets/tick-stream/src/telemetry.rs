@@ -0,0 +1,83 @@
+//! Failed-dispatch telemetry for the tick stream pallet.
+//!
+//! A failing call has its storage changes rolled back, so the pallet cannot count its own
+//! errors from inside the call. [`TrackDispatchErrors`] is a signed extension whose
+//! `post_dispatch` runs after that rollback and bumps the per-era counter for the failing
+//! call and error variant.
+
+use crate::{Call, Config, Pallet};
+use codec::{Decode, Encode};
+use frame_support::traits::{GetCallIndex, IsSubType, PalletInfoAccess};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, PostDispatchInfoOf, SignedExtension},
+	transaction_validity::TransactionValidityError,
+	DispatchError, DispatchResult, ModuleError,
+};
+use sp_std::{fmt, marker::PhantomData};
+
+/// Records failed tick stream dispatches in `ErrorCounts`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct TrackDispatchErrors<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> TrackDispatchErrors<T> {
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T: Config + Send + Sync> Default for TrackDispatchErrors<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Config + Send + Sync> fmt::Debug for TrackDispatchErrors<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "TrackDispatchErrors")
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for TrackDispatchErrors<T>
+where
+	<T as frame_system::Config>::RuntimeCall: IsSubType<Call<T>>,
+{
+	const IDENTIFIER: &'static str = "TrackDispatchErrors";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::RuntimeCall;
+	type AdditionalSigned = ();
+	/// The call index within this pallet, if the extrinsic targets it.
+	type Pre = Option<u8>;
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn pre_dispatch(
+		self,
+		_who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		Ok(call.is_sub_type().map(|call| call.get_call_index()))
+	}
+
+	fn post_dispatch(
+		pre: Option<Self::Pre>,
+		_info: &DispatchInfoOf<Self::Call>,
+		_post_info: &PostDispatchInfoOf<Self::Call>,
+		_len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		if let (Some(Some(call_index)), Err(DispatchError::Module(ModuleError { index, error, .. }))) =
+			(pre, result)
+		{
+			if *index as usize == <Pallet<T> as PalletInfoAccess>::index() {
+				Pallet::<T>::note_dispatch_error(call_index, error[0]);
+			}
+		}
+		Ok(())
+	}
+}
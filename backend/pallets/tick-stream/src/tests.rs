@@ -1,41 +1,4199 @@
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	migrations, mock::*, settlement_digest, AccessPolicy, AttestationSource, BulkDiscount,
+	CallFilterMode, ContentType, CreatorBudget, DidLink, DidOf, DustPolicy, EraEarnings,
+	EraSettlementCommitment, Error, Event, GenesisConfig, GenesisStream, GrantMilestone,
+	HedgePolicy, HoldReason, HoldUtilization, JoinGate, JoinVoucher, LegacyCall, NotificationPrefs,
+	PollStatus, PositionAnchor, ReportingUnit, ScheduledStartsDue, SessionState, SettledValue,
+	SettlementContext, SettlementExtension, SettlementHealth, StreamCategory, StreamFilter,
+	StreamInfo, StreamReport, StreamSchedule, StreamStatus, SweepPolicy, Tip, TrackDispatchErrors,
+	VestingSchedule, ViewerAttestation, ViewerSession, WatchStats, WeightInfo, NOTIFY_LOW_BALANCE,
+	NOTIFY_STREAM_LIVE,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::{DispatchInfo, GetDispatchInfo, PostDispatchInfo},
+	inherent::{InherentData, ProvideInherent},
+	traits::{
+		fungible::InspectHold, Currency, GetStorageVersion, Hooks, OnRuntimeUpgrade,
+		ReservableCurrency, StorageVersion, UnfilteredDispatchable,
+	},
+	BoundedVec,
+};
+use codec::{Decode, Encode};
+use sp_core::offchain::{
+	testing::{TestOffchainExt, TestTransactionPoolExt},
+	OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
+};
+use sp_runtime::{
+	testing::TestSignature,
+	traits::{BlakeTwo256, Hash, SignedExtension, ValidateUnsigned},
+	transaction_validity::{InvalidTransaction, TransactionSource},
+	BuildStorage, DispatchError, DispatchResult, Percent, Permill,
+};
+
+const CREATOR: u64 = 1;
+const VIEWER: u64 = 2;
+const STREAM: u128 = 0;
+const PRICE: u64 = 10;
+
+type Batch = BoundedVec<(u64, u32), <Test as crate::Config>::MaxViewersPerBatch>;
+
+/// `REPORTER`'s report charging `viewer` for `ticks` seconds of `stream_id`, under its next
+/// nonce.
+fn tick_call(stream_id: u128, viewer: u64, ticks: u32) -> crate::Call<Test> {
+	let nonce = TickStream::reporter(REPORTER).map_or(0, |reporter| reporter.nonce) + 1;
+	let payload = TickStream::tick_payload(stream_id, &viewer, ticks, nonce);
+	let signature = TestSignature(REPORTER, payload);
+	crate::Call::record_tick { stream_id, viewer, ticks, reporter: REPORTER, nonce, signature }
+}
+
+/// `reporter`'s report settling `ticks` on `stream_id` for `interval_index`, under its next
+/// nonce.
+fn reported_batch(
+	reporter: u64,
+	stream_id: u128,
+	interval_index: u64,
+	ticks: Batch,
+) -> crate::Call<Test> {
+	let nonce = TickStream::reporter(reporter).map_or(0, |reporter| reporter.nonce) + 1;
+	let payload = TickStream::batch_payload(stream_id, interval_index, &ticks, nonce);
+	let signature = TestSignature(reporter, payload);
+	crate::Call::batch_tick { stream_id, interval_index, ticks, reporter, nonce, signature }
+}
+
+/// `REPORTER`'s report settling `ticks` on `stream_id`, keyed by the current block as the
+/// ticker keys its intervals.
+fn batch_call(stream_id: u128, ticks: Batch) -> crate::Call<Test> {
+	reported_batch(REPORTER, stream_id, System::block_number(), ticks)
+}
+
+/// Dispatch `REPORTER`'s tick unsigned, as the offchain worker submits it.
+fn record_tick(stream_id: u128, viewer: u64, ticks: u32) -> DispatchResult {
+	let call = tick_call(stream_id, viewer, ticks);
+	call.dispatch_bypass_filter(RuntimeOrigin::none()).map(|_| ()).map_err(|e| e.error)
+}
+
+/// Dispatch `REPORTER`'s batch unsigned, as the ticker submits it.
+fn batch_tick(stream_id: u128, ticks: Batch) -> DispatchResult {
+	dispatch_unsigned(batch_call(stream_id, ticks))
+}
+
+/// Dispatch `call` unsigned.
+fn dispatch_unsigned(call: crate::Call<Test>) -> DispatchResult {
+	call.dispatch_bypass_filter(RuntimeOrigin::none()).map(|_| ()).map_err(|e| e.error)
+}
+
+fn live_stream() {
+	assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0));
+	assert_ok!(TickStream::start_stream(RuntimeOrigin::signed(CREATOR), STREAM));
+}
 
 #[test]
 fn it_works_to_join_stream() {
 	new_test_ext().execute_with(|| {
+		live_stream();
+
 		// Dispatch a signed extrinsic.
-		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(1), 1));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
 
 		// Assert that the correct event was deposited
-		System::assert_last_event(Event::ViewerJoined { stream_id: 1, viewer: 1 }.into());
+		System::assert_last_event(Event::ViewerJoined { stream_id: STREAM, viewer: VIEWER }.into());
+
+		// The viewer's budget is held in reserve
+		assert_eq!(Balances::reserved_balance(VIEWER), 100);
+		assert_eq!(TickStream::balances(STREAM, VIEWER), 100);
 	});
 }
 
 #[test]
 fn it_works_to_record_tick() {
 	new_test_ext().execute_with(|| {
+		live_stream();
+
 		// First join the stream
-		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(1), 1));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		run_to_block(2);
 
 		// Then record a tick
-		assert_ok!(TickStream::record_tick(RuntimeOrigin::signed(1), 1, 1, 1));
+		assert_ok!(record_tick(STREAM, VIEWER, 1));
 
 		// Assert that the correct event was deposited
-		System::assert_last_event(Event::TickRecorded { stream_id: 1, viewer: 1, ticks: 1 }.into());
+		System::assert_last_event(
+			Event::TickRecorded {
+				stream_id: STREAM,
+				viewer: VIEWER,
+				ticks: 1,
+				amount: PRICE,
+				fiat_value: None,
+			}
+			.into(),
+		);
 
 		// Check that the tick count is correct
-		assert_eq!(TickStream::get_tick_count(1), 1);
+		assert_eq!(TickStream::get_tick_count(STREAM), 1);
+
+		// The creator has been paid into escrow out of the viewer's reserve
+		assert_eq!(TickStream::escrow(STREAM, CREATOR), PRICE);
+		assert_eq!(Balances::free_balance(TickStream::escrow_account(STREAM)), PRICE);
+		assert_eq!(TickStream::balances(STREAM, VIEWER), 90);
+
+		// And the earnings are attributed to the current era
+		assert_eq!(TickStream::creator_earnings(CREATOR, 0), EraEarnings { seconds: 1, gross: PRICE });
 	});
 }
 
 #[test]
 fn it_fails_to_record_tick_if_not_joined() {
 	new_test_ext().execute_with(|| {
+		live_stream();
+
 		// Try to record a tick without joining the stream
 		assert_noop!(
-			TickStream::record_tick(RuntimeOrigin::signed(1), 1, 1, 1),
+			record_tick(STREAM, VIEWER, 1),
 			Error::<Test>::Unauthorized
 		);
 	});
-}
\ No newline at end of file
+}
+
+#[test]
+fn it_fails_to_record_tick_unless_live() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+
+		assert_noop!(
+			record_tick(STREAM, VIEWER, 1),
+			Error::<Test>::StreamNotLive
+		);
+
+		assert_ok!(TickStream::start_stream(RuntimeOrigin::signed(CREATOR), STREAM));
+		assert_ok!(TickStream::pause_stream(RuntimeOrigin::signed(CREATOR), STREAM));
+		assert_noop!(
+			record_tick(STREAM, VIEWER, 1),
+			Error::<Test>::StreamNotLive
+		);
+	});
+}
+
+#[test]
+fn only_creator_can_change_stream_status() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0));
+
+		assert_noop!(
+			TickStream::start_stream(RuntimeOrigin::signed(VIEWER), STREAM),
+			Error::<Test>::NotStreamCreator
+		);
+		assert_noop!(
+			TickStream::pause_stream(RuntimeOrigin::signed(CREATOR), STREAM),
+			Error::<Test>::InvalidStatusTransition
+		);
+
+		assert_ok!(TickStream::start_stream(RuntimeOrigin::signed(CREATOR), STREAM));
+		System::assert_last_event(
+			Event::StreamStatusChanged { stream_id: STREAM, status: StreamStatus::Live }.into(),
+		);
+	});
+}
+
+#[test]
+fn ending_a_stream_refunds_viewers() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(3), STREAM, 5, None));
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 3));
+
+		assert_ok!(TickStream::end_stream(RuntimeOrigin::signed(CREATOR), STREAM));
+
+		assert_eq!(Balances::reserved_balance(VIEWER), 0);
+		assert_eq!(Balances::free_balance(VIEWER), 1_000 - 3 * PRICE);
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert!(TickStream::stream_viewers(STREAM).is_empty());
+		assert_eq!(TickStream::streams(STREAM).unwrap().status, StreamStatus::Ended);
+
+		// Ended streams cannot be restarted or joined
+		assert_noop!(
+			TickStream::start_stream(RuntimeOrigin::signed(CREATOR), STREAM),
+			Error::<Test>::InvalidStatusTransition
+		);
+		assert_noop!(
+			TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None),
+			Error::<Test>::StreamEnded
+		);
+	});
+}
+
+#[test]
+fn failed_dispatches_are_counted_per_era() {
+	new_test_ext().execute_with(|| {
+		let info = DispatchInfo::default();
+		let post_info = PostDispatchInfo::default();
+		let failed = Err(Error::<Test>::StreamNotLive.into());
+
+		// record_tick failing with StreamNotLive (error index 4), twice
+		for _ in 0..2 {
+			assert_ok!(TrackDispatchErrors::<Test>::post_dispatch(
+				Some(Some(0)),
+				&info,
+				&post_info,
+				0,
+				&failed
+			));
+		}
+		// Successful dispatches and calls to other pallets are ignored
+		assert_ok!(TrackDispatchErrors::<Test>::post_dispatch(
+			Some(Some(0)),
+			&info,
+			&post_info,
+			0,
+			&Ok(())
+		));
+		assert_ok!(TrackDispatchErrors::<Test>::post_dispatch(
+			Some(None),
+			&info,
+			&post_info,
+			0,
+			&failed
+		));
+
+		assert_eq!(TickStream::get_error_counts(0), vec![(0, 4, 2)]);
+
+		// Counters start afresh in the next era
+		System::set_block_number(10);
+		assert!(TickStream::get_error_counts(TickStream::current_era()).is_empty());
+	});
+}
+
+#[test]
+fn batch_tick_settles_viewers_and_skips_the_insolvent() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(3), STREAM, 1, None));
+
+		run_to_block(2);
+
+		let ticks = BoundedVec::truncate_from(vec![(VIEWER, 2), (3, 2), (4, 1)]);
+		assert_ok!(batch_tick(STREAM, ticks));
+
+		// Viewer 3 only reserved one second and viewer 4 never joined
+		System::assert_has_event(Event::TickSkipped { stream_id: STREAM, viewer: 3 }.into());
+		System::assert_has_event(Event::TickSkipped { stream_id: STREAM, viewer: 4 }.into());
+		System::assert_last_event(
+			Event::IntervalSettled {
+				stream_id: STREAM,
+				viewers: 1,
+				total_amount: 2 * PRICE,
+				total_fiat_value: 0,
+			}
+			.into(),
+		);
+
+		assert_eq!(TickStream::balances(STREAM, VIEWER), 80);
+		assert_eq!(TickStream::balances(STREAM, 3), PRICE);
+		assert_eq!(TickStream::get_tick_count(STREAM), 2);
+	});
+}
+
+#[test]
+fn an_interval_is_settled_once_however_many_tickers_submit_it() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		assert_ok!(TickStream::register_reporter(RuntimeOrigin::signed(3)));
+		run_to_block(2);
+
+		// Two tickers settle the same interval, and replace each other in the pool
+		let ticks: Batch = BoundedVec::truncate_from(vec![(VIEWER, 2)]);
+		let first = reported_batch(REPORTER, STREAM, 2, ticks.clone());
+		let second = reported_batch(3, STREAM, 2, ticks.clone());
+		let provides = |call: &crate::Call<Test>| {
+			TickStream::validate_unsigned(TransactionSource::External, call).unwrap().provides
+		};
+		assert_eq!(provides(&first), provides(&second));
+
+		// Whichever lands first charges the viewer, and the other is turned away
+		assert_ok!(dispatch_unsigned(first));
+		assert_eq!(TickStream::last_settled_interval(STREAM), Some(2));
+		assert_eq!(
+			TickStream::validate_unsigned(TransactionSource::External, &second),
+			Err(InvalidTransaction::Custom(82).into())
+		);
+		assert_noop!(dispatch_unsigned(second), Error::<Test>::IntervalAlreadySettled);
+		assert_eq!(TickStream::balances(STREAM, VIEWER), 80);
+		assert_eq!(TickStream::watched_seconds(STREAM, VIEWER), 2);
+
+		// So is a batch for an earlier interval that lands late, while the next one settles
+		run_to_block(3);
+		let late = reported_batch(3, STREAM, 1, ticks.clone());
+		assert_noop!(dispatch_unsigned(late), Error::<Test>::IntervalAlreadySettled);
+		assert_ok!(dispatch_unsigned(reported_batch(3, STREAM, 3, ticks)));
+		assert_eq!(TickStream::last_settled_interval(STREAM), Some(3));
+		assert_eq!(TickStream::balances(STREAM, VIEWER), 60);
+	});
+}
+
+#[test]
+fn streams_are_ended_after_their_maximum_duration() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, Some(5), 0));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+
+		// The limit only starts counting once the stream goes live
+		System::set_block_number(3);
+		assert_ok!(TickStream::start_stream(RuntimeOrigin::signed(CREATOR), STREAM));
+		assert_eq!(TickStream::stream_expiries(8), vec![STREAM]);
+
+		TickStream::on_initialize(7);
+		assert_eq!(TickStream::streams(STREAM).unwrap().status, StreamStatus::Live);
+
+		TickStream::on_initialize(8);
+		assert_eq!(TickStream::streams(STREAM).unwrap().status, StreamStatus::Ended);
+		assert_eq!(Balances::reserved_balance(VIEWER), 0);
+		System::assert_has_event(Event::StreamAutoEnded { stream_id: STREAM }.into());
+	});
+}
+
+#[test]
+fn ticks_are_rate_limited_by_blocks_and_wall_clock() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 20, None));
+
+		// Nothing can be charged in the block the viewer joined
+		assert_noop!(
+			record_tick(STREAM, VIEWER, 1),
+			Error::<Test>::TickTooEarly
+		);
+
+		// One block is six seconds of wall-clock time
+		run_to_block(2);
+		assert_noop!(
+			record_tick(STREAM, VIEWER, 7),
+			Error::<Test>::TooManyTicks
+		);
+		assert_ok!(record_tick(STREAM, VIEWER, 6));
+		assert_noop!(
+			record_tick(STREAM, VIEWER, 1),
+			Error::<Test>::TickTooEarly
+		);
+	});
+}
+
+#[test]
+fn joining_fails_without_the_free_balance_to_cover_the_reserve() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+
+		// Account 4 holds 10, enough for one second but not two
+		assert_noop!(
+			TickStream::join_stream(RuntimeOrigin::signed(4), STREAM, 2, None),
+			Error::<Test>::InsufficientBalance
+		);
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(4), STREAM, 1, None));
+		assert_eq!(Balances::reserved_balance(4), PRICE);
+		assert_eq!(Balances::free_balance(4), 0);
+
+		// Ticks beyond the reserve are charged what is left of it
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, 4, 2));
+		assert_eq!(Balances::reserved_balance(4), 0);
+		assert_eq!(TickStream::balances(STREAM, 4), 0);
+		assert_eq!(TickStream::escrow(STREAM, CREATOR), PRICE);
+	});
+}
+
+#[test]
+fn amounts_too_large_for_a_balance_are_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), u64::MAX, None, 0));
+		assert_ok!(TickStream::start_stream(RuntimeOrigin::signed(CREATOR), STREAM));
+
+		// Two seconds at the maximum price cost more than a balance can hold
+		assert_noop!(
+			TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 2, None),
+			Error::<Test>::ArithmeticOverflow
+		);
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 0, None));
+		assert_noop!(
+			TickStream::top_up(RuntimeOrigin::signed(VIEWER), STREAM, u32::MAX),
+			Error::<Test>::ArithmeticOverflow
+		);
+
+		run_to_block(2);
+		assert_noop!(
+			record_tick(STREAM, VIEWER, u32::MAX),
+			Error::<Test>::TooManyTicks
+		);
+		assert_noop!(
+			record_tick(STREAM, VIEWER, 6),
+			Error::<Test>::ArithmeticOverflow
+		);
+		// One second fits, but the viewer reserved nothing for it
+		assert_ok!(record_tick(STREAM, VIEWER, 1));
+		assert!(!TickStream::stream_viewers(STREAM).contains(&VIEWER));
+	});
+}
+
+#[test]
+fn only_ticks_are_accepted_unsigned() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0));
+
+		let call = crate::Call::withdraw { stream_id: STREAM };
+		assert_eq!(
+			TickStream::validate_unsigned(TransactionSource::External, &call),
+			Err(InvalidTransaction::Call.into())
+		);
+		// Batches are only valid for live streams
+		let call = batch_call(STREAM, BoundedVec::new());
+		assert_eq!(
+			TickStream::validate_unsigned(TransactionSource::External, &call),
+			Err(InvalidTransaction::Custom(4).into())
+		);
+		let call = tick_call(1, VIEWER, 1);
+		assert_eq!(
+			TickStream::validate_unsigned(TransactionSource::External, &call),
+			Err(InvalidTransaction::Custom(0).into())
+		);
+	});
+}
+
+/// The sequence number of every tick-stream event deposited so far, in deposit order.
+fn event_sequences() -> Vec<u32> {
+	System::events()
+		.into_iter()
+		.filter(|record| matches!(record.event, RuntimeEvent::TickStream(_)))
+		.map(|record| {
+			record
+				.topics
+				.iter()
+				.find_map(|topic| crate::event_sequence(topic.as_bytes()))
+				.expect("every event is numbered")
+		})
+		.collect()
+}
+
+#[test]
+fn events_are_numbered_in_deposit_order_within_each_block() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		// A failed call takes its sequence numbers with it
+		assert_noop!(
+			TickStream::join_stream(RuntimeOrigin::signed(4), STREAM, 2, None),
+			Error::<Test>::InsufficientBalance
+		);
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(3), STREAM, 10, None));
+		let sequences = event_sequences();
+		assert_eq!(sequences, (0..sequences.len() as u32).collect::<Vec<_>>());
+
+		// Numbering starts over in the next block, hooks included
+		run_to_block(2);
+		System::reset_events();
+		TickStream::on_initialize(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 3));
+		assert_ok!(record_tick(STREAM, 3, 3));
+		let sequences = event_sequences();
+		assert!(!sequences.is_empty());
+		assert_eq!(sequences, (0..sequences.len() as u32).collect::<Vec<_>>());
+	});
+}
+
+#[test]
+fn unsigned_ticks_are_tagged_by_viewer_and_last_tick() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 20, None));
+		run_to_block(2);
+
+		let call = tick_call(STREAM, VIEWER, 6);
+		let first = TickStream::validate_unsigned(TransactionSource::External, &call).unwrap();
+		assert_ok!(record_tick(STREAM, VIEWER, 6));
+
+		// Replaying the same tick in the same interval is rejected as `TickTooEarly`
+		assert_eq!(
+			TickStream::validate_unsigned(TransactionSource::External, &call),
+			Err(InvalidTransaction::Custom(8).into())
+		);
+
+		// The next interval provides a different tag
+		run_to_block(3);
+		let call = tick_call(STREAM, VIEWER, 6);
+		let second = TickStream::validate_unsigned(TransactionSource::External, &call).unwrap();
+		assert_ne!(first.provides, second.provides);
+	});
+}
+
+#[test]
+fn offchain_worker_submits_due_ticks_once_per_block() {
+	let mut ext = new_test_ext();
+	let (offchain, offchain_state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	offchain_state.write().is_validator = true;
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	ext.execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 20, None));
+		// Viewer 3 can only afford two seconds
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(3), STREAM, 2, None));
+		run_to_block(2);
+
+		TickStream::offchain_worker(2);
+		TickStream::offchain_worker(2);
+
+		// Each tick is signed by the reporter under a nonce of its own
+		let mut nonces = Vec::new();
+		let mut ticks: Vec<_> = pool_state
+			.read()
+			.transactions
+			.iter()
+			.map(|tx| Extrinsic::decode(&mut &tx[..]).unwrap())
+			.map(|tx| {
+				assert!(tx.signature.is_none());
+				let RuntimeCall::TickStream(crate::Call::record_tick {
+					stream_id,
+					viewer,
+					ticks,
+					reporter,
+					nonce,
+					signature,
+				}) = tx.call
+				else {
+					panic!("the worker only submits ticks")
+				};
+				let payload = TickStream::tick_payload(stream_id, &viewer, ticks, nonce);
+				assert_eq!((reporter, signature), (REPORTER, TestSignature(REPORTER, payload)));
+				nonces.push(nonce);
+				(stream_id, viewer, ticks)
+			})
+			.collect();
+		ticks.sort();
+		assert_eq!(ticks, vec![(STREAM, VIEWER, 6), (STREAM, 3, 2)]);
+		nonces.sort();
+		assert_eq!(nonces, vec![1, 2]);
+	});
+}
+
+#[test]
+fn vouchers_fix_the_price_and_can_only_be_used_once() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+
+		let voucher = JoinVoucher {
+			stream_id: STREAM,
+			nonce: 7,
+			viewer: Some(VIEWER),
+			price_per_second: Some(1),
+			seconds: 10,
+			expires_at: 5,
+		};
+		let signature = TestSignature(CREATOR, voucher.encode());
+
+		// Only the named viewer can redeem it, and only with the creator's signature
+		assert_noop!(
+			TickStream::join_with_voucher(RuntimeOrigin::signed(3), STREAM, voucher.clone(), signature.clone()),
+			Error::<Test>::InvalidVoucher
+		);
+		assert_noop!(
+			TickStream::join_with_voucher(
+				RuntimeOrigin::signed(VIEWER),
+				STREAM,
+				voucher.clone(),
+				TestSignature(3, voucher.encode())
+			),
+			Error::<Test>::InvalidVoucherSignature
+		);
+
+		assert_ok!(TickStream::join_with_voucher(
+			RuntimeOrigin::signed(VIEWER),
+			STREAM,
+			voucher.clone(),
+			signature.clone()
+		));
+		assert_eq!(TickStream::balances(STREAM, VIEWER), 10);
+
+		// Ticks are charged at the voucher price
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 4));
+		assert_eq!(TickStream::balances(STREAM, VIEWER), 6);
+
+		assert_noop!(
+			TickStream::join_with_voucher(RuntimeOrigin::signed(VIEWER), STREAM, voucher, signature),
+			Error::<Test>::VoucherAlreadyUsed
+		);
+	});
+}
+
+#[test]
+fn expired_vouchers_are_rejected() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+
+		let voucher = JoinVoucher {
+			stream_id: STREAM,
+			nonce: 1,
+			viewer: None,
+			price_per_second: Some(0),
+			seconds: 10,
+			expires_at: 1,
+		};
+		let signature = TestSignature(CREATOR, voucher.encode());
+
+		run_to_block(2);
+		assert_noop!(
+			TickStream::join_with_voucher(RuntimeOrigin::signed(VIEWER), STREAM, voucher, signature),
+			Error::<Test>::VoucherExpired
+		);
+	});
+}
+
+#[test]
+fn tick_payments_are_split_between_platform_co_hosts_and_creator() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::set_platform_fee(RuntimeOrigin::root(), Permill::from_percent(10)));
+		let co_hosts = BoundedVec::try_from(vec![(3, Permill::from_percent(50))]).unwrap();
+		assert_ok!(TickStream::set_revenue_split(RuntimeOrigin::signed(CREATOR), STREAM, co_hosts));
+
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		run_to_block(2);
+		// 6 seconds at 10 per second: 6 to the platform, 27 to the co-host, 27 to the creator
+		assert_ok!(record_tick(STREAM, VIEWER, 6));
+
+		let platform = TickStream::platform_account();
+		assert_eq!(Balances::free_balance(platform), 6);
+		assert_eq!(TickStream::escrow(STREAM, 3), 27);
+		assert_eq!(TickStream::escrow(STREAM, CREATOR), 27);
+		System::assert_has_event(
+			Event::RevenueSplit { stream_id: STREAM, beneficiary: 3, amount: 27 }.into(),
+		);
+		System::assert_has_event(
+			Event::RevenueSplit { stream_id: STREAM, beneficiary: CREATOR, amount: 27 }.into(),
+		);
+	});
+}
+
+#[test]
+fn category_fees_override_the_platform_fee() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::set_platform_fee(RuntimeOrigin::root(), Permill::from_percent(10)));
+		let fee = Some(Permill::from_percent(50));
+		assert_noop!(
+			TickStream::set_category_fee(RuntimeOrigin::signed(CREATOR), StreamCategory::Talk, fee),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(TickStream::set_category_fee(RuntimeOrigin::root(), StreamCategory::Talk, fee));
+		System::assert_last_event(
+			Event::CategoryFeeSet { category: StreamCategory::Talk, fee }.into(),
+		);
+
+		// Streams without metadata, or in other categories, pay the platform-wide fee
+		assert_eq!(TickStream::effective_fee(STREAM), Permill::from_percent(10));
+		let title = BoundedVec::try_from(b"Chat".to_vec()).unwrap();
+		let cid = BoundedVec::try_from(b"bafy".to_vec()).unwrap();
+		assert_ok!(TickStream::set_metadata(
+			RuntimeOrigin::signed(CREATOR),
+			STREAM,
+			title,
+			StreamCategory::Talk,
+			cid
+		));
+		assert_eq!(TickStream::stream_details(STREAM).unwrap().platform_fee, fee.unwrap());
+
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 6));
+		assert_eq!(Balances::free_balance(TickStream::platform_account()), 30);
+		assert_eq!(TickStream::escrow(STREAM, CREATOR), 30);
+
+		assert_ok!(TickStream::set_category_fee(RuntimeOrigin::root(), StreamCategory::Talk, None));
+		assert_eq!(TickStream::effective_fee(STREAM), Permill::from_percent(10));
+	});
+}
+
+#[test]
+fn revenue_splits_are_validated() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+
+		let too_much =
+			BoundedVec::try_from(vec![(3, Permill::from_percent(60)), (4, Permill::from_percent(50))])
+				.unwrap();
+		assert_noop!(
+			TickStream::set_revenue_split(RuntimeOrigin::signed(CREATOR), STREAM, too_much),
+			Error::<Test>::InvalidRevenueSplit
+		);
+		assert_noop!(
+			TickStream::set_revenue_split(RuntimeOrigin::signed(VIEWER), STREAM, Default::default()),
+			Error::<Test>::NotStreamCreator
+		);
+		assert_noop!(
+			TickStream::set_platform_fee(RuntimeOrigin::signed(CREATOR), Permill::from_percent(1)),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn creator_absorbs_split_rounding() {
+	let shares = [Permill::from_percent(33), Permill::from_percent(33)];
+	let (fee, cuts, creator) =
+		crate::split_payment(10u64, Permill::from_percent(5), shares.iter().copied());
+	// The 0.5 fee rounds down and each co-host's 3.3 rounds down; nothing is lost
+	assert_eq!((fee, cuts.clone(), creator), (0, vec![3, 3], 4));
+	assert_eq!(fee + cuts.iter().sum::<u64>() + creator, 10);
+}
+
+#[test]
+fn escrowed_earnings_are_withdrawn_in_one_transfer() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 3));
+		run_to_block(3);
+		assert_ok!(record_tick(STREAM, VIEWER, 2));
+		assert_eq!(Balances::free_balance(CREATOR), 1_000);
+
+		assert_ok!(TickStream::withdraw(RuntimeOrigin::signed(CREATOR), STREAM));
+		System::assert_last_event(
+			Event::Withdrawn { stream_id: STREAM, who: CREATOR, amount: 5 * PRICE }.into(),
+		);
+		assert_eq!(Balances::free_balance(CREATOR), 1_000 + 5 * PRICE);
+		assert_eq!(TickStream::escrow(STREAM, CREATOR), 0);
+
+		assert_noop!(
+			TickStream::withdraw(RuntimeOrigin::signed(CREATOR), STREAM),
+			Error::<Test>::NothingToWithdraw
+		);
+	});
+}
+
+#[test]
+fn earnings_above_the_sweep_threshold_are_forwarded_to_cold_storage() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		let policy = SweepPolicy { threshold: 20, cold_address: CREATOR };
+		assert_noop!(
+			TickStream::set_sweep_policy(RuntimeOrigin::signed(CREATOR), Some(policy)),
+			Error::<Test>::InvalidColdAddress
+		);
+		let policy = SweepPolicy { threshold: 20, cold_address: 5 };
+		assert_ok!(TickStream::set_sweep_policy(RuntimeOrigin::signed(CREATOR), Some(policy)));
+
+		// Withdrawals pay out up to the threshold
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 3));
+		assert_ok!(TickStream::withdraw(RuntimeOrigin::signed(CREATOR), STREAM));
+		System::assert_has_event(
+			Event::SweptToCold { stream_id: STREAM, who: CREATOR, cold_address: 5, amount: 10 }
+				.into(),
+		);
+		System::assert_last_event(
+			Event::Withdrawn { stream_id: STREAM, who: CREATOR, amount: 20 }.into(),
+		);
+		assert_eq!(Balances::free_balance(CREATOR), 1_020);
+		assert_eq!(Balances::free_balance(5), 10);
+
+		// Escrowed earnings are swept at the start of the next era
+		run_to_block(3);
+		assert_ok!(record_tick(STREAM, VIEWER, 3));
+		run_to_block(10);
+		TickStream::on_initialize(10);
+		System::assert_has_event(
+			Event::SweptToCold { stream_id: STREAM, who: CREATOR, cold_address: 5, amount: 10 }
+				.into(),
+		);
+		assert_eq!(TickStream::escrow(STREAM, CREATOR), 20);
+		assert_eq!(Balances::free_balance(5), 20);
+	});
+}
+
+#[test]
+fn creator_earnings_are_paid_to_the_payout_account() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_noop!(
+			TickStream::set_payout_account(RuntimeOrigin::signed(VIEWER), STREAM, Some(5)),
+			Error::<Test>::NotStreamCreator
+		);
+		assert_ok!(TickStream::set_payout_account(RuntimeOrigin::signed(CREATOR), STREAM, Some(5)));
+		System::assert_last_event(
+			Event::PayoutAccountSet { stream_id: STREAM, creator: CREATOR, payout_account: Some(5) }
+				.into(),
+		);
+
+		assert_ok!(TickStream::tip(RuntimeOrigin::signed(VIEWER), STREAM, 100, None));
+		System::assert_has_event(
+			Event::PayoutRouted {
+				stream_id: STREAM,
+				creator: CREATOR,
+				payout_account: 5,
+				amount: 100,
+			}
+			.into(),
+		);
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 3));
+		assert_ok!(TickStream::withdraw(RuntimeOrigin::signed(CREATOR), STREAM));
+		System::assert_has_event(
+			Event::PayoutRouted {
+				stream_id: STREAM,
+				creator: CREATOR,
+				payout_account: 5,
+				amount: 30,
+			}
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(CREATOR), 1_000);
+		assert_eq!(Balances::free_balance(5), 130);
+
+		// Transferring the stream hands its earnings back to the new creator
+		assert_ok!(TickStream::transfer_stream(RuntimeOrigin::signed(CREATOR), STREAM, 3));
+		assert_eq!(TickStream::streams(STREAM).unwrap().payout_account, None);
+	});
+}
+
+#[test]
+fn a_share_of_each_withdrawal_is_hedged_into_a_stable_asset() {
+	new_test_ext().execute_with(|| {
+		const USD: u32 = 7;
+		live_stream();
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), USD, CREATOR, true, 1));
+		let policy = HedgePolicy {
+			hedge_percent: Percent::from_percent(40),
+			asset: USD,
+			max_slippage: Permill::from_percent(5),
+		};
+		assert_ok!(TickStream::set_hedge_policy(
+			RuntimeOrigin::signed(CREATOR),
+			Some(policy.clone())
+		));
+		System::assert_last_event(
+			Event::HedgePolicySet { who: CREATOR, policy: Some(policy) }.into(),
+		);
+
+		// 40% of the withdrawal fills 4% below the quote
+		TestSwap::set_rates(Some((200, 192)));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 5));
+		assert_ok!(TickStream::withdraw(RuntimeOrigin::signed(CREATOR), STREAM));
+		System::assert_has_event(
+			Event::EarningsHedged {
+				stream_id: STREAM,
+				who: CREATOR,
+				asset: USD,
+				amount: 20,
+				received: 38,
+				quoted: 40,
+			}
+			.into(),
+		);
+		System::assert_last_event(
+			Event::Withdrawn { stream_id: STREAM, who: CREATOR, amount: 5 * PRICE }.into(),
+		);
+		assert_eq!(Balances::free_balance(CREATOR), 1_030);
+		assert_eq!(Balances::free_balance(SWAP_POOL), 20);
+		assert_eq!(Assets::balance(USD, CREATOR), 38);
+
+		// A fill further below the quote is skipped, paying the withdrawal out unswapped
+		TestSwap::set_rates(Some((200, 180)));
+		run_to_block(3);
+		assert_ok!(record_tick(STREAM, VIEWER, 3));
+		assert_ok!(TickStream::withdraw(RuntimeOrigin::signed(CREATOR), STREAM));
+		System::assert_has_event(
+			Event::HedgeSkipped { stream_id: STREAM, who: CREATOR, amount: 12 }.into(),
+		);
+		assert_eq!(Balances::free_balance(CREATOR), 1_060);
+		assert_eq!(Balances::free_balance(SWAP_POOL), 20);
+		assert_eq!(Assets::balance(USD, CREATOR), 38);
+
+		assert_ok!(TickStream::set_hedge_policy(RuntimeOrigin::signed(CREATOR), None));
+		assert_eq!(TickStream::hedge_policy(CREATOR), None);
+	});
+}
+
+#[test]
+fn dust_earnings_are_handled_by_the_creators_policy() {
+	new_test_ext().execute_with(|| {
+		ExistentialDeposit::set(50);
+		let platform = TickStream::platform_account();
+		Balances::make_free_balance_be(&platform, 100);
+		live_stream();
+		Balances::make_free_balance_be(&TickStream::escrow_account(STREAM), 50);
+		assert_ok!(TickStream::set_payout_account(RuntimeOrigin::signed(CREATOR), STREAM, Some(5)));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 20, None));
+
+		// Earnings below the existential deposit accumulate until they are enough to open
+		// the empty payout account with
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 4));
+		assert_noop!(
+			TickStream::withdraw(RuntimeOrigin::signed(CREATOR), STREAM),
+			Error::<Test>::BelowExistentialDeposit
+		);
+		run_to_block(3);
+		assert_ok!(record_tick(STREAM, VIEWER, 1));
+		assert_ok!(TickStream::withdraw(RuntimeOrigin::signed(CREATOR), STREAM));
+		assert_eq!(Balances::free_balance(5), 50);
+
+		// Once the account exists, any amount is paid out
+		run_to_block(4);
+		assert_ok!(record_tick(STREAM, VIEWER, 1));
+		assert_ok!(TickStream::withdraw(RuntimeOrigin::signed(CREATOR), STREAM));
+		assert_eq!(Balances::free_balance(5), 60);
+
+		assert_ok!(TickStream::set_payout_account(RuntimeOrigin::signed(CREATOR), STREAM, Some(6)));
+		assert_ok!(TickStream::set_dust_policy(RuntimeOrigin::signed(CREATOR), DustPolicy::Donate));
+		run_to_block(5);
+		assert_ok!(record_tick(STREAM, VIEWER, 4));
+		assert_ok!(TickStream::withdraw(RuntimeOrigin::signed(CREATOR), STREAM));
+		System::assert_last_event(
+			Event::DustDonated { stream_id: STREAM, who: CREATOR, amount: 40 }.into(),
+		);
+		assert_eq!(Balances::free_balance(&platform), 140);
+		assert_eq!(Balances::free_balance(6), 0);
+
+		assert_ok!(TickStream::set_dust_policy(RuntimeOrigin::signed(CREATOR), DustPolicy::Credit));
+		run_to_block(6);
+		assert_ok!(record_tick(STREAM, VIEWER, 3));
+		assert_ok!(TickStream::withdraw(RuntimeOrigin::signed(CREATOR), STREAM));
+		assert_eq!(TickStream::viewing_credit(CREATOR), 30);
+		assert_eq!(Balances::free_balance(&platform), 170);
+
+		// Credit is paid out as the creator joins a stream themselves
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(3), PRICE, None, 0));
+		assert_ok!(TickStream::start_stream(RuntimeOrigin::signed(3), STREAM + 1));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(CREATOR), STREAM + 1, 3, None));
+		System::assert_has_event(
+			Event::ViewingCreditRedeemed { stream_id: STREAM + 1, who: CREATOR, amount: 30 }
+				.into(),
+		);
+		assert_eq!(TickStream::viewing_credit(CREATOR), 0);
+		assert_eq!(Balances::free_balance(CREATOR), 1_000);
+		assert_eq!(Balances::free_balance(&platform), 140);
+	});
+}
+
+#[test]
+fn referrers_earn_a_share_of_referred_viewers_ticks() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_noop!(
+			TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, Some(VIEWER)),
+			Error::<Test>::InvalidReferrer
+		);
+		assert_noop!(
+			TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, Some(CREATOR)),
+			Error::<Test>::InvalidReferrer
+		);
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, Some(3)));
+		assert_eq!(TickStream::referrer(STREAM, VIEWER), Some(3));
+
+		run_to_block(2);
+		// 6 seconds at 10 per second: 10% to the referrer, out of the creator's share
+		assert_ok!(record_tick(STREAM, VIEWER, 6));
+		assert_eq!(TickStream::referral_earnings(3, STREAM), 6);
+		assert_eq!(TickStream::escrow(STREAM, CREATOR), 54);
+		System::assert_has_event(
+			Event::ReferralPaid { stream_id: STREAM, referrer: 3, viewer: VIEWER, amount: 6 }
+				.into(),
+		);
+
+		assert_ok!(TickStream::claim_referral_earnings(RuntimeOrigin::signed(3), STREAM));
+		System::assert_last_event(
+			Event::ReferralEarningsClaimed { stream_id: STREAM, who: 3, amount: 6 }.into(),
+		);
+		assert_eq!(Balances::free_balance(3), 1_006);
+		assert_noop!(
+			TickStream::claim_referral_earnings(RuntimeOrigin::signed(3), STREAM),
+			Error::<Test>::NothingToWithdraw
+		);
+	});
+}
+
+#[test]
+fn notification_prefs_can_be_paged_by_relayers() {
+	new_test_ext().execute_with(|| {
+		let followed = BoundedVec::try_from(vec![STREAM, 7]).unwrap();
+		for who in [1, 2, 3] {
+			assert_ok!(TickStream::set_notification_prefs(
+				RuntimeOrigin::signed(who),
+				followed.clone(),
+				NOTIFY_STREAM_LIVE | NOTIFY_LOW_BALANCE
+			));
+		}
+		assert_eq!(
+			TickStream::notification_prefs(VIEWER),
+			Some(NotificationPrefs { followed: vec![STREAM, 7], events: 0b101 })
+		);
+
+		// Two pages cover every account exactly once
+		let first = TickStream::notification_prefs_page(None, 2);
+		let second = TickStream::notification_prefs_page(Some(first[1].0), 2);
+		let mut accounts = first.iter().chain(&second).map(|(who, _)| *who).collect::<Vec<_>>();
+		accounts.sort();
+		assert_eq!(accounts, vec![1, 2, 3]);
+
+		// Unknown event bits are rejected and empty preferences are cleared
+		assert_noop!(
+			TickStream::set_notification_prefs(RuntimeOrigin::signed(VIEWER), followed, 1 << 31),
+			Error::<Test>::InvalidNotificationPrefs
+		);
+		assert_ok!(TickStream::set_notification_prefs(
+			RuntimeOrigin::signed(VIEWER),
+			Default::default(),
+			0
+		));
+		assert_eq!(TickStream::notification_prefs(VIEWER), None);
+	});
+}
+
+#[test]
+fn viewer_session_reports_watch_time_and_remaining_reserve() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_eq!(TickStream::viewer_session(STREAM, &VIEWER), None);
+
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 4));
+
+		assert_eq!(
+			TickStream::viewer_session(STREAM, &VIEWER),
+			Some(ViewerSession {
+				reserved: 6 * PRICE,
+				seconds_watched: 4,
+				seconds_remaining: Some(6),
+				paused: false,
+				free_seconds_left: 0,
+			})
+		);
+	});
+}
+
+#[test]
+fn creators_manage_stream_metadata() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		let title = BoundedVec::try_from(b"Speedrun".to_vec()).unwrap();
+		let cid = BoundedVec::try_from(b"bafy".to_vec()).unwrap();
+
+		assert_noop!(
+			TickStream::set_metadata(
+				RuntimeOrigin::signed(VIEWER),
+				STREAM,
+				title.clone(),
+				StreamCategory::Gaming,
+				cid.clone()
+			),
+			Error::<Test>::NotStreamCreator
+		);
+		assert_ok!(TickStream::set_metadata(
+			RuntimeOrigin::signed(CREATOR),
+			STREAM,
+			title.clone(),
+			StreamCategory::Gaming,
+			cid.clone()
+		));
+
+		// Updating keeps the block the metadata was first set in
+		run_to_block(5);
+		assert_ok!(TickStream::set_metadata(
+			RuntimeOrigin::signed(CREATOR),
+			STREAM,
+			title,
+			StreamCategory::Talk,
+			cid
+		));
+		let details = TickStream::stream_details(STREAM).unwrap();
+		assert_eq!(
+			details.metadata,
+			Some(StreamInfo {
+				title: b"Speedrun".to_vec(),
+				category: StreamCategory::Talk,
+				content_cid: b"bafy".to_vec(),
+				created_at: 1,
+			})
+		);
+
+		assert_ok!(TickStream::clear_metadata(RuntimeOrigin::signed(CREATOR), STREAM));
+		assert_eq!(TickStream::stream_details(STREAM).unwrap().metadata, None);
+	});
+}
+
+#[test]
+fn leftover_reservations_are_swept_after_the_settlement_window() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		for viewer in [CREATOR, VIEWER, 3] {
+			assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(viewer), STREAM, 5, None));
+		}
+		Balances::make_free_balance_be(&TickStream::platform_account(), 100);
+
+		// Only `MaxRefundsOnEnd` viewers are refunded as the stream ends
+		assert_ok!(TickStream::end_stream(RuntimeOrigin::signed(CREATOR), STREAM));
+		let left = [CREATOR, VIEWER, 3]
+			.into_iter()
+			.filter(|viewer| Balances::reserved_balance(viewer) > 0)
+			.collect::<Vec<_>>();
+		assert_eq!(left.len(), 1);
+
+		let viewers = BoundedVec::try_from(vec![CREATOR, VIEWER, 3]).unwrap();
+		assert_noop!(
+			TickStream::sweep_expired(RuntimeOrigin::signed(4), STREAM, viewers.clone()),
+			Error::<Test>::SettlementWindowOpen
+		);
+
+		run_to_block(6);
+		assert_ok!(TickStream::sweep_expired(RuntimeOrigin::signed(4), STREAM, viewers.clone()));
+		assert_eq!(Balances::reserved_balance(left[0]), 0);
+		System::assert_last_event(
+			Event::ExpiredSwept { stream_id: STREAM, sweeper: 4, swept: 1, reward: 1 }.into(),
+		);
+		assert_eq!(Balances::free_balance(4), 11);
+
+		assert_noop!(
+			TickStream::sweep_expired(RuntimeOrigin::signed(4), STREAM, viewers),
+			Error::<Test>::NothingToSweep
+		);
+	});
+}
+
+#[test]
+fn stream_ids_are_assigned_sequentially() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0));
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(VIEWER), PRICE, None, 0));
+		System::assert_last_event(
+			Event::StreamCreated { stream_id: 1, creator: VIEWER, price_per_second: PRICE }.into(),
+		);
+		assert_eq!(TickStream::next_stream_id(), 2);
+
+		// An id already taken, e.g. by a migrated stream, is never reused
+		crate::NextStreamId::<Test>::put(STREAM);
+		assert_noop!(
+			TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0),
+			Error::<Test>::StreamAlreadyExists
+		);
+	});
+}
+
+#[test]
+fn streams_are_listed_by_status_and_creator() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0));
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(VIEWER), PRICE, None, 0));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+
+		let ids = |offset, limit, status, creator| {
+			let filter = StreamFilter { status, creator };
+			let mut ids = TickStream::list_streams(offset, limit, filter)
+				.into_iter()
+				.map(|summary| summary.stream_id)
+				.collect::<Vec<_>>();
+			ids.sort();
+			ids
+		};
+		let filter = StreamFilter { status: Some(StreamStatus::Live), creator: None };
+		let live = TickStream::list_streams(0, 10, filter);
+		assert_eq!(live.len(), 1);
+		assert_eq!(live[0].stream_id, STREAM);
+		assert_eq!(live[0].viewer_count, 1);
+		assert_eq!(ids(0, 10, Some(StreamStatus::Created), None), [1, 2]);
+		assert_eq!(ids(0, 10, Some(StreamStatus::Created), Some(CREATOR)), [1]);
+		assert_eq!(ids(0, 10, None, Some(CREATOR)), [0, 1]);
+
+		// Pages follow on from each other
+		let mut paged = [ids(0, 2, None, None), ids(2, 2, None, None)].concat();
+		paged.sort();
+		assert_eq!(paged, [0, 1, 2]);
+		assert_eq!(ids(3, 2, None, None), Vec::<u128>::new());
+
+		assert_ok!(TickStream::end_stream(RuntimeOrigin::signed(CREATOR), STREAM));
+		assert!(ids(0, 10, Some(StreamStatus::Live), None).is_empty());
+		assert_eq!(ids(0, 10, Some(StreamStatus::Ended), None), [STREAM]);
+
+		// Chains from before the index have their streams indexed on upgrade
+		let _ = crate::StreamsByStatus::<Test>::clear(u32::MAX, None);
+		StorageVersion::new(2).put::<TickStream>();
+		migrations::MigrateToV3::<Test>::on_runtime_upgrade();
+		assert_eq!(ids(0, 10, Some(StreamStatus::Created), None), [1, 2]);
+		assert_eq!(TickStream::on_chain_storage_version(), 3);
+	});
+}
+
+#[test]
+fn creators_cap_how_many_viewers_watch_at_once() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_noop!(
+			TickStream::set_max_viewers(RuntimeOrigin::signed(VIEWER), STREAM, Some(1)),
+			Error::<Test>::NotStreamCreator
+		);
+		assert_noop!(
+			TickStream::set_max_viewers(RuntimeOrigin::signed(CREATOR), STREAM, Some(0)),
+			Error::<Test>::InvalidMaxViewers
+		);
+		assert_ok!(TickStream::set_max_viewers(RuntimeOrigin::signed(CREATOR), STREAM, Some(1)));
+		System::assert_last_event(
+			Event::MaxViewersSet { stream_id: STREAM, max_viewers: Some(1) }.into(),
+		);
+
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		System::assert_has_event(
+			Event::ViewerCountChanged { stream_id: STREAM, viewers: 1 }.into(),
+		);
+		assert_eq!(TickStream::viewer_count(STREAM), 1);
+		assert_noop!(
+			TickStream::join_stream(RuntimeOrigin::signed(3), STREAM, 10, None),
+			Error::<Test>::StreamFull
+		);
+		// Viewers already watching can still top up
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		assert_eq!(TickStream::viewer_count(STREAM), 1);
+
+		// A seat frees up as soon as a viewer leaves
+		let streams = BoundedVec::try_from(vec![STREAM]).unwrap();
+		assert_ok!(TickStream::force_leave_account(RuntimeOrigin::root(), VIEWER, streams));
+		System::assert_has_event(
+			Event::ViewerCountChanged { stream_id: STREAM, viewers: 0 }.into(),
+		);
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(3), STREAM, 10, None));
+
+		assert_ok!(TickStream::set_max_viewers(RuntimeOrigin::signed(CREATOR), STREAM, None));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		assert_eq!(TickStream::stream_details(STREAM).unwrap().viewers, 2);
+	});
+}
+
+#[test]
+fn promo_codes_are_redeemed_by_commit_and_reveal() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		let code = BoundedVec::try_from(b"LAUNCH".to_vec()).unwrap();
+		let salt = [7u8; 32];
+		assert_ok!(TickStream::create_promo(
+			RuntimeOrigin::signed(CREATOR),
+			STREAM,
+			BlakeTwo256::hash(&code),
+			Permill::from_percent(50),
+			1
+		));
+
+		let commitment = TickStream::promo_commitment(&code, &VIEWER, &salt);
+		assert_ok!(TickStream::commit_promo(RuntimeOrigin::signed(VIEWER), STREAM, commitment));
+		assert_noop!(
+			TickStream::reveal_promo(RuntimeOrigin::signed(VIEWER), STREAM, code.clone(), salt),
+			Error::<Test>::RevealTooEarly
+		);
+
+		// Someone who saw the code in the reveal cannot reuse the viewer's commitment
+		assert_ok!(TickStream::commit_promo(RuntimeOrigin::signed(3), STREAM, commitment));
+		run_to_block(2);
+		assert_noop!(
+			TickStream::reveal_promo(RuntimeOrigin::signed(3), STREAM, code.clone(), salt),
+			Error::<Test>::CommitmentMismatch
+		);
+
+		assert_ok!(TickStream::reveal_promo(RuntimeOrigin::signed(VIEWER), STREAM, code, salt));
+		assert_eq!(TickStream::viewer_prices(STREAM, VIEWER), Some(PRICE / 2));
+		assert_eq!(TickStream::promos(STREAM, BlakeTwo256::hash(b"LAUNCH")).unwrap().remaining_uses, 0);
+	});
+}
+
+#[test]
+fn price_changes_take_effect_after_the_delay() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		assert_noop!(
+			TickStream::update_price(RuntimeOrigin::signed(VIEWER), STREAM, 2 * PRICE),
+			Error::<Test>::NotStreamCreator
+		);
+		assert_ok!(TickStream::update_price(RuntimeOrigin::signed(CREATOR), STREAM, 2 * PRICE));
+		System::assert_last_event(
+			Event::PriceChangeScheduled { stream_id: STREAM, new_price: 2 * PRICE, effective_at: 4 }
+				.into(),
+		);
+
+		// Until the effective block the viewer keeps paying the old rate
+		run_to_block(3);
+		TickStream::on_initialize(3);
+		assert_ok!(record_tick(STREAM, VIEWER, 2));
+		assert_eq!(TickStream::balances(STREAM, VIEWER), 8 * PRICE);
+
+		run_to_block(4);
+		TickStream::on_initialize(4);
+		assert_eq!(TickStream::streams(STREAM).unwrap().price_per_second, 2 * PRICE);
+		assert_eq!(TickStream::pending_prices(STREAM), None);
+		System::assert_has_event(
+			Event::PriceChanged { stream_id: STREAM, price_per_second: 2 * PRICE }.into(),
+		);
+	});
+}
+
+#[test]
+fn featured_streams_are_drawn_by_watch_time_each_era() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 5));
+		assert_eq!(TickStream::era_watch_time(0, STREAM), 5);
+
+		// Era 1 starts at block 10 and features the only watched stream
+		run_to_block(10);
+		TickStream::on_initialize(10);
+		let (seed, streams) = TickStream::featured(1).unwrap();
+		assert_eq!(streams, vec![STREAM]);
+		assert_eq!(crate::select_featured(seed.as_ref(), &[(STREAM, 5)], 2), streams);
+
+		// A fixed seed replaces the chain's randomness, the same on every run
+		crate::RandomnessSeed::<Test>::put([7; 32]);
+		run_to_block(20);
+		TickStream::on_initialize(20);
+		let subject = (b"tick-stream/featured", 2u32).encode();
+		let expected = BlakeTwo256::hash(&[&[7; 32][..], &subject].concat());
+		assert_eq!(TickStream::featured(2).unwrap().0, expected);
+	});
+}
+
+#[test]
+fn featured_selection_is_weighted_and_distinct() {
+	let candidates = [(1, 1), (2, 0), (3, 1_000_000)];
+	let mut heavy = 0;
+	for seed in 0u8..20 {
+		let featured = crate::select_featured(&[seed], &candidates, 2);
+		// Streams nobody watched are never featured, and no stream is featured twice
+		assert_eq!(featured.len(), 2);
+		assert!(!featured.contains(&2));
+		if featured[0] == 3 {
+			heavy += 1;
+		}
+	}
+	assert!(heavy >= 19);
+}
+
+#[test]
+fn storage_footprint_counts_entries_per_item() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(3), STREAM, 10, None));
+
+		let footprint = TickStream::storage_footprint();
+		let item = |name: &[u8]| footprint.iter().find(|item| item.name == name).unwrap().clone();
+		assert_eq!(item(b"Streams").entries, 1);
+		assert_eq!(item(b"Balances").entries, 2);
+		assert_eq!(item(b"NextStreamId").entries, 1);
+		assert_eq!(item(b"Escrow").entries, 0);
+		assert!(item(b"Balances").bytes > item(b"Streams").bytes);
+	});
+}
+
+#[test]
+fn config_fingerprint_changes_with_the_parameters() {
+	new_test_ext().execute_with(|| {
+		let before = TickStream::config_fingerprint();
+		let value = |name: &[u8]| {
+			before.parameters.iter().find(|parameter| parameter.name == name).unwrap().value.clone()
+		};
+		assert_eq!(value(b"EraLength"), 10u64.encode());
+		assert_eq!(value(b"PlatformFee"), Permill::zero().encode());
+		assert_eq!(value(b"LowBalanceSeconds"), 5u32.encode());
+		let names = before.parameters.iter().map(|parameter| &parameter.name).collect::<Vec<_>>();
+		assert!(names.windows(2).all(|pair| pair[0] < pair[1]));
+		assert_eq!(TickStream::config_fingerprint(), before);
+
+		// Stream state is not a parameter, but governance's fees are
+		live_stream();
+		assert_eq!(TickStream::config_fingerprint().hash, before.hash);
+		let fee = Some(Permill::from_percent(5));
+		assert_ok!(TickStream::set_category_fee(RuntimeOrigin::root(), StreamCategory::Talk, fee));
+		assert_ne!(TickStream::config_fingerprint().hash, before.hash);
+	});
+}
+
+#[test]
+fn viewers_are_warned_on_low_balance_and_can_top_up() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_noop!(
+			TickStream::top_up(RuntimeOrigin::signed(VIEWER), STREAM, 10),
+			Error::<Test>::Unauthorized
+		);
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 6));
+		System::assert_has_event(
+			Event::LowBalanceWarning { stream_id: STREAM, viewer: VIEWER, seconds_remaining: 4 }
+				.into(),
+		);
+
+		assert_ok!(TickStream::top_up(RuntimeOrigin::signed(VIEWER), STREAM, 20));
+		assert_eq!(TickStream::balances(STREAM, VIEWER), 24 * PRICE);
+		assert_eq!(Balances::reserved_balance(VIEWER), 24 * PRICE);
+	});
+}
+
+#[test]
+fn recovered_creators_claim_earnings_and_streams_through_the_proxy() {
+	const FRIEND: u64 = 3;
+	const RESCUER: u64 = 4;
+
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 5));
+
+		// The creator's key is lost; a friend vouches for the rescuer
+		assert_ok!(Recovery::create_recovery(RuntimeOrigin::signed(CREATOR), vec![FRIEND], 1, 0));
+		assert_ok!(Recovery::initiate_recovery(RuntimeOrigin::signed(RESCUER), CREATOR));
+		assert_ok!(Recovery::vouch_recovery(RuntimeOrigin::signed(FRIEND), CREATOR, RESCUER));
+		assert_ok!(Recovery::claim_recovery(RuntimeOrigin::signed(RESCUER), CREATOR));
+
+		let as_creator = |call: crate::Call<Test>| {
+			Recovery::as_recovered(
+				RuntimeOrigin::signed(RESCUER),
+				CREATOR,
+				Box::new(RuntimeCall::TickStream(call)),
+			)
+		};
+		assert_ok!(as_creator(crate::Call::withdraw { stream_id: STREAM }));
+		System::assert_has_event(
+			Event::Withdrawn { stream_id: STREAM, who: CREATOR, amount: 5 * PRICE }.into(),
+		);
+		assert_eq!(Balances::free_balance(CREATOR), 1_000 + 5 * PRICE);
+
+		assert_ok!(as_creator(crate::Call::transfer_stream {
+			stream_id: STREAM,
+			new_creator: RESCUER
+		}));
+		assert_eq!(TickStream::streams(STREAM).unwrap().creator, RESCUER);
+		assert_ok!(TickStream::pause_stream(RuntimeOrigin::signed(RESCUER), STREAM));
+		assert_noop!(
+			TickStream::pause_stream(RuntimeOrigin::signed(CREATOR), STREAM),
+			Error::<Test>::NotStreamCreator
+		);
+	});
+}
+
+#[test]
+fn tips_pay_the_creator_and_are_kept_until_the_stream_ends() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0));
+		assert_noop!(
+			TickStream::tip(RuntimeOrigin::signed(VIEWER), STREAM, 100, None),
+			Error::<Test>::StreamNotLive
+		);
+		assert_ok!(TickStream::start_stream(RuntimeOrigin::signed(CREATOR), STREAM));
+		assert_ok!(TickStream::set_platform_fee(RuntimeOrigin::root(), Permill::from_percent(10)));
+
+		let message = BoundedVec::try_from(b"great set".to_vec()).unwrap();
+		assert_ok!(TickStream::tip(RuntimeOrigin::signed(VIEWER), STREAM, 100, Some(message)));
+		System::assert_last_event(
+			Event::TipReceived {
+				stream_id: STREAM,
+				tipper: VIEWER,
+				amount: 100,
+				message: Some(b"great set".to_vec()),
+			}
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(VIEWER), 900);
+		assert_eq!(Balances::free_balance(CREATOR), 1_090);
+		assert_eq!(Balances::free_balance(TickStream::platform_account()), 10);
+		assert_eq!(
+			TickStream::tips(STREAM),
+			vec![Tip { tipper: VIEWER, amount: 100, message: Some(b"great set".to_vec()), block: 1 }]
+		);
+
+		// Tips must open an account, and only the latest two are kept
+		assert_noop!(
+			TickStream::tip(RuntimeOrigin::signed(VIEWER), STREAM, 0, None),
+			Error::<Test>::BelowExistentialDeposit
+		);
+		assert_ok!(TickStream::tip(RuntimeOrigin::signed(VIEWER), STREAM, 20, None));
+		assert_ok!(TickStream::tip(RuntimeOrigin::signed(3), STREAM, 30, None));
+		assert_eq!(
+			TickStream::tips(STREAM),
+			vec![
+				Tip { tipper: VIEWER, amount: 20, message: None, block: 1 },
+				Tip { tipper: 3, amount: 30, message: None, block: 1 },
+			]
+		);
+
+		assert_ok!(TickStream::end_stream(RuntimeOrigin::signed(CREATOR), STREAM));
+		assert!(TickStream::tips(STREAM).is_empty());
+	});
+}
+
+#[test]
+fn only_the_latest_tips_are_kept_on_upgrade() {
+	new_test_ext().execute_with(|| {
+		let tips: Vec<_> = (1..=3)
+			.map(|amount| Tip { tipper: VIEWER, amount, message: None, block: 1 })
+			.collect();
+		// Tips as version 10 stored them, however many were sent
+		frame_support::storage::unhashed::put(&crate::Tips::<Test>::hashed_key_for(STREAM), &tips);
+		StorageVersion::new(10).put::<TickStream>();
+
+		migrations::MigrateToV11::<Test>::on_runtime_upgrade();
+		assert_eq!(TickStream::on_chain_storage_version(), 11);
+		assert_eq!(TickStream::tips(STREAM), tips[1..].to_vec());
+	});
+}
+
+#[test]
+fn viewers_leave_automatically_below_their_threshold() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 20, None));
+		assert_ok!(TickStream::set_auto_leave_threshold(RuntimeOrigin::signed(VIEWER), 950));
+
+		// 1_000 - 30 is still above the threshold
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 3));
+		assert_eq!(TickStream::stream_viewers(STREAM), vec![VIEWER]);
+
+		run_to_block(3);
+		assert_ok!(record_tick(STREAM, VIEWER, 3));
+		System::assert_has_event(Event::ViewerAutoLeft { stream_id: STREAM, viewer: VIEWER }.into());
+		assert!(TickStream::stream_viewers(STREAM).is_empty());
+		assert_eq!(TickStream::balances(STREAM, VIEWER), 0);
+		assert_eq!(Balances::reserved_balance(VIEWER), 0);
+		assert_eq!(Balances::free_balance(VIEWER), 1_000 - 6 * PRICE);
+	});
+}
+
+#[test]
+fn root_can_revoke_access_by_stream_and_by_account() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0));
+		assert_ok!(TickStream::start_stream(RuntimeOrigin::signed(CREATOR), 1));
+		for viewer in [VIEWER, 3] {
+			assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(viewer), STREAM, 10, None));
+			assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(viewer), 1, 10, None));
+		}
+		assert_noop!(
+			TickStream::force_leave_stream(RuntimeOrigin::signed(CREATOR), STREAM, 10),
+			DispatchError::BadOrigin
+		);
+
+		// One reservation at a time leaves the other to a follow-up call
+		assert_ok!(TickStream::force_leave_stream(RuntimeOrigin::root(), STREAM, 1));
+		System::assert_last_event(
+			Event::StreamAccessRevoked { stream_id: STREAM, revoked: 1, more_remain: true }.into(),
+		);
+		assert!(TickStream::stream_viewers(STREAM).is_empty());
+		assert_ok!(TickStream::force_leave_stream(RuntimeOrigin::root(), STREAM, 10));
+		System::assert_last_event(
+			Event::StreamAccessRevoked { stream_id: STREAM, revoked: 1, more_remain: false }.into(),
+		);
+		assert_eq!(Balances::reserved_balance(3), 10 * PRICE);
+
+		let streams = BoundedVec::try_from(vec![STREAM, 1]).unwrap();
+		assert_ok!(TickStream::force_leave_account(RuntimeOrigin::root(), 3, streams));
+		System::assert_last_event(Event::AccountAccessRevoked { who: 3, revoked: 1 }.into());
+		assert_eq!(TickStream::stream_viewers(1), vec![VIEWER]);
+		assert_eq!(Balances::reserved_balance(3), 0);
+	});
+}
+
+#[test]
+fn scheduled_streams_go_live_at_their_wall_clock_time() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0));
+		assert_noop!(
+			TickStream::schedule_stream(RuntimeOrigin::signed(CREATOR), STREAM, 6, 0),
+			Error::<Test>::StartTimeInPast
+		);
+		assert_noop!(
+			TickStream::schedule_stream(RuntimeOrigin::signed(CREATOR), STREAM, 60, 900),
+			Error::<Test>::InvalidUtcOffset
+		);
+
+		// 54 seconds away at 6 second blocks
+		assert_ok!(TickStream::schedule_stream(RuntimeOrigin::signed(CREATOR), STREAM, 60, -300));
+		System::assert_last_event(
+			Event::StreamScheduled {
+				stream_id: STREAM,
+				starts_at: 60,
+				utc_offset_minutes: -300,
+				estimated_block: 10,
+			}
+			.into(),
+		);
+
+		// Blocks came every 3 seconds, so the start is moved later
+		System::set_block_number(10);
+		Timestamp::set_timestamp(30_000);
+		TickStream::on_initialize(10);
+		System::assert_has_event(
+			Event::ScheduleAdjusted { stream_id: STREAM, estimated_block: 14 }.into(),
+		);
+		assert_eq!(TickStream::streams(STREAM).unwrap().status, StreamStatus::Created);
+		assert_eq!(
+			TickStream::stream_schedule(STREAM),
+			Some(StreamSchedule { starts_at: 60, utc_offset_minutes: -300, estimated_block: 14 })
+		);
+
+		System::set_block_number(14);
+		Timestamp::set_timestamp(57_000);
+		TickStream::on_initialize(14);
+		System::assert_has_event(Event::ScheduledStreamStarted { stream_id: STREAM }.into());
+		assert_eq!(TickStream::streams(STREAM).unwrap().status, StreamStatus::Live);
+		assert_eq!(TickStream::stream_schedule(STREAM), None);
+	});
+}
+
+#[test]
+fn booked_seats_are_taken_when_a_scheduled_stream_goes_live() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0));
+		assert_noop!(
+			TickStream::pre_book(RuntimeOrigin::signed(VIEWER), STREAM),
+			Error::<Test>::NotScheduled
+		);
+		assert_ok!(TickStream::schedule_stream(RuntimeOrigin::signed(CREATOR), STREAM, 60, 0));
+		assert_ok!(TickStream::set_max_viewers(RuntimeOrigin::signed(CREATOR), STREAM, Some(2)));
+
+		assert_ok!(TickStream::pre_book(RuntimeOrigin::signed(VIEWER), STREAM));
+		System::assert_last_event(
+			Event::PreBooked { stream_id: STREAM, viewer: VIEWER, deposit: 20 }.into(),
+		);
+		assert_eq!(Balances::reserved_balance(VIEWER), 20);
+		assert_noop!(
+			TickStream::pre_book(RuntimeOrigin::signed(VIEWER), STREAM),
+			Error::<Test>::AlreadyBooked
+		);
+		assert_ok!(TickStream::pre_book(RuntimeOrigin::signed(3), STREAM));
+
+		// Booked seats count against the cap
+		assert_noop!(
+			TickStream::join_stream(RuntimeOrigin::signed(4), STREAM, 0, None),
+			Error::<Test>::StreamFull
+		);
+		assert_ok!(TickStream::set_max_viewers(RuntimeOrigin::signed(CREATOR), STREAM, None));
+		assert_noop!(
+			TickStream::pre_book(RuntimeOrigin::signed(4), STREAM),
+			Error::<Test>::TooManyBookings
+		);
+
+		// The deposits become the viewers' reserves once the stream goes live
+		run_to_block(10);
+		TickStream::on_initialize(10);
+		System::assert_has_event(Event::PreBookingsSeated { stream_id: STREAM, seated: 2 }.into());
+		assert_eq!(TickStream::streams(STREAM).unwrap().status, StreamStatus::Live);
+		assert_eq!(TickStream::balances(STREAM, VIEWER), 20);
+		assert_eq!(TickStream::viewer_count(STREAM), 2);
+		assert!(TickStream::pre_bookings(STREAM).is_empty());
+
+		// Ending a stream before it goes live refunds its bookings
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0));
+		assert_ok!(TickStream::schedule_stream(RuntimeOrigin::signed(CREATOR), 1, 120, 0));
+		assert_ok!(TickStream::pre_book(RuntimeOrigin::signed(VIEWER), 1));
+		assert_ok!(TickStream::end_stream(RuntimeOrigin::signed(CREATOR), 1));
+		System::assert_has_event(Event::PreBookingsCancelled { stream_id: 1, refunded: 1 }.into());
+		assert_eq!(Balances::reserved_balance(VIEWER), 20);
+
+		// So does a stream still not live a grace period after its start
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0));
+		assert_ok!(TickStream::schedule_stream(RuntimeOrigin::signed(CREATOR), 2, 126, 0));
+		assert_ok!(TickStream::pre_book(RuntimeOrigin::signed(VIEWER), 2));
+		// The stream misses its start at block 21
+		ScheduledStartsDue::<Test>::remove(21);
+		for n in [21, 26] {
+			run_to_block(n);
+			TickStream::on_initialize(n);
+		}
+		System::assert_has_event(Event::PreBookingsCancelled { stream_id: 2, refunded: 1 }.into());
+		assert_eq!(TickStream::streams(2).unwrap().status, StreamStatus::Created);
+		assert_eq!(Balances::reserved_balance(VIEWER), 20);
+	});
+}
+
+#[test]
+fn viewers_see_the_scheduled_streams_of_creators_they_follow() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		for (creator, starts_at) in [(CREATOR, 120), (CREATOR, 60), (3, 90)] {
+			assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(creator), PRICE, None, 0));
+			let stream_id = TickStream::next_stream_id() - 1;
+			assert_ok!(TickStream::schedule_stream(
+				RuntimeOrigin::signed(creator),
+				stream_id,
+				starts_at,
+				0
+			));
+		}
+		let title = BoundedVec::try_from(b"Finals".to_vec()).unwrap();
+		assert_ok!(TickStream::set_metadata(
+			RuntimeOrigin::signed(CREATOR),
+			1,
+			title,
+			StreamCategory::Gaming,
+			Default::default()
+		));
+		assert!(TickStream::upcoming_streams(&VIEWER, 0, 10).is_empty());
+
+		// Following one of a creator's streams brings up all of their scheduled ones, soonest
+		// first
+		let followed = BoundedVec::try_from(vec![STREAM]).unwrap();
+		assert_ok!(TickStream::set_notification_prefs(
+			RuntimeOrigin::signed(VIEWER),
+			followed,
+			NOTIFY_STREAM_LIVE
+		));
+		let upcoming = TickStream::upcoming_streams(&VIEWER, 0, 10);
+		let ids = upcoming.iter().map(|stream| stream.stream_id).collect::<Vec<_>>();
+		assert_eq!(ids, [2, 1]);
+		assert_eq!(upcoming[1].creator, CREATOR);
+		assert_eq!(upcoming[1].title, Some(b"Finals".to_vec()));
+		assert_eq!(upcoming[1].schedule.starts_at, 120);
+		assert_eq!(TickStream::upcoming_streams(&VIEWER, 1, 1)[0].stream_id, 1);
+
+		// A stream leaves the feed once it goes live
+		assert_ok!(TickStream::start_stream(RuntimeOrigin::signed(CREATOR), 2));
+		assert_eq!(TickStream::upcoming_streams(&VIEWER, 0, 10).len(), 1);
+	});
+}
+
+#[test]
+fn a_budget_pays_for_any_of_the_creators_streams() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0));
+		assert_ok!(TickStream::start_stream(RuntimeOrigin::signed(CREATOR), 1));
+		assert_ok!(TickStream::top_up_budget(RuntimeOrigin::signed(VIEWER), CREATOR, 100));
+		System::assert_last_event(
+			Event::BudgetToppedUp { viewer: VIEWER, creator: CREATOR, amount: 100 }.into(),
+		);
+		for stream_id in [STREAM, 1] {
+			assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), stream_id, 0, None));
+		}
+
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 3));
+		assert_ok!(record_tick(1, VIEWER, 3));
+		assert_eq!(
+			TickStream::creator_budget(VIEWER, CREATOR),
+			CreatorBudget { reserved: 40, spent: 60 }
+		);
+
+		assert_ok!(TickStream::release_budget(RuntimeOrigin::signed(VIEWER), CREATOR));
+		assert_eq!(Balances::reserved_balance(VIEWER), 0);
+		assert_eq!(Balances::free_balance(VIEWER), 1_000 - 60);
+		assert_eq!(TickStream::creator_budget(VIEWER, CREATOR).spent, 60);
+		assert_noop!(
+			TickStream::release_budget(RuntimeOrigin::signed(VIEWER), CREATOR),
+			Error::<Test>::NoBudget
+		);
+	});
+}
+
+#[test]
+fn watch_history_outlives_sessions_until_pruned() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 20, None));
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 3));
+		run_to_block(3);
+		assert_ok!(record_tick(STREAM, VIEWER, 4));
+
+		// The stats are kept after the session is refunded
+		assert_ok!(TickStream::end_stream(RuntimeOrigin::signed(CREATOR), STREAM));
+		let stats = WatchStats { total_seconds: 7, total_spent: 7 * PRICE, last_watched_block: 3 };
+		assert_eq!(TickStream::viewer_stats_of(&VIEWER), vec![(STREAM, stats)]);
+
+		let streams = BoundedVec::try_from(vec![STREAM]).unwrap();
+		assert_noop!(
+			TickStream::prune_viewer_stats(RuntimeOrigin::signed(3), VIEWER, streams.clone()),
+			Error::<Test>::NothingToPrune
+		);
+		run_to_block(103);
+		assert_ok!(TickStream::prune_viewer_stats(RuntimeOrigin::signed(3), VIEWER, streams));
+		System::assert_last_event(Event::ViewerStatsPruned { viewer: VIEWER, pruned: 1 }.into());
+		assert_eq!(TickStream::viewer_stats(VIEWER, STREAM), None);
+	});
+}
+
+#[test]
+fn subscribers_accrue_watch_time_without_being_charged_until_their_pass_expires() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_noop!(
+			TickStream::create_subscription_tier(RuntimeOrigin::signed(VIEWER), STREAM, 100, 10),
+			Error::<Test>::NotStreamCreator
+		);
+		assert_ok!(TickStream::create_subscription_tier(
+			RuntimeOrigin::signed(CREATOR),
+			STREAM,
+			100,
+			10
+		));
+		assert_ok!(TickStream::create_subscription_tier(
+			RuntimeOrigin::signed(CREATOR),
+			STREAM,
+			250,
+			30
+		));
+		assert_noop!(
+			TickStream::create_subscription_tier(RuntimeOrigin::signed(CREATOR), STREAM, 1, 1),
+			Error::<Test>::TooManyTiers
+		);
+		assert_noop!(
+			TickStream::subscribe(RuntimeOrigin::signed(VIEWER), STREAM, 2),
+			Error::<Test>::TierNotFound
+		);
+
+		assert_ok!(TickStream::subscribe(RuntimeOrigin::signed(VIEWER), STREAM, 0));
+		System::assert_last_event(
+			Event::Subscribed { stream_id: STREAM, viewer: VIEWER, tier: 0, expires_at: 11 }.into(),
+		);
+		assert_eq!(TickStream::stream_viewers(STREAM), vec![VIEWER]);
+		assert_eq!(Balances::free_balance(VIEWER), 900);
+		assert_eq!(TickStream::escrow(STREAM, CREATOR), 100);
+
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 3));
+		assert_eq!(Balances::free_balance(VIEWER), 900);
+		assert_eq!(TickStream::tick_count(STREAM), 3);
+
+		// Once the pass runs out the viewer is metered from their reserve again
+		assert_ok!(TickStream::top_up(RuntimeOrigin::signed(VIEWER), STREAM, 20));
+		run_to_block(11);
+		assert_ok!(record_tick(STREAM, VIEWER, 3));
+		System::assert_has_event(
+			Event::SubscriptionExpired { stream_id: STREAM, viewer: VIEWER }.into(),
+		);
+		assert_eq!(TickStream::subscription(STREAM, VIEWER), None);
+		assert_eq!(TickStream::balances(STREAM, VIEWER), 17 * PRICE);
+	});
+}
+
+#[test]
+fn passes_gifted_in_bulk_are_discounted() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::create_subscription_tier(
+			RuntimeOrigin::signed(CREATOR),
+			STREAM,
+			100,
+			10
+		));
+		let bulk = BulkDiscount { min_passes: 3, discount: Permill::from_percent(10) };
+		assert_noop!(
+			TickStream::set_bulk_discount(
+				RuntimeOrigin::signed(VIEWER),
+				STREAM,
+				Some(bulk.clone())
+			),
+			Error::<Test>::NotStreamCreator
+		);
+		assert_noop!(
+			TickStream::set_bulk_discount(
+				RuntimeOrigin::signed(CREATOR),
+				STREAM,
+				Some(BulkDiscount { min_passes: 1, ..bulk.clone() })
+			),
+			Error::<Test>::InvalidBulkDiscount
+		);
+		assert_ok!(TickStream::set_bulk_discount(
+			RuntimeOrigin::signed(CREATOR),
+			STREAM,
+			Some(bulk.clone())
+		));
+		System::assert_last_event(
+			Event::BulkDiscountSet { stream_id: STREAM, discount: Some(bulk) }.into(),
+		);
+
+		assert_noop!(
+			TickStream::gift_subscriptions(
+				RuntimeOrigin::signed(VIEWER),
+				STREAM,
+				0,
+				BoundedVec::new()
+			),
+			Error::<Test>::NoRecipients
+		);
+		// Too few passes for the discount
+		let recipients = BoundedVec::try_from(vec![3]).unwrap();
+		assert_ok!(TickStream::gift_subscriptions(
+			RuntimeOrigin::signed(VIEWER),
+			STREAM,
+			0,
+			recipients
+		));
+		System::assert_last_event(
+			Event::SubscriptionGifted {
+				stream_id: STREAM,
+				gifter: VIEWER,
+				recipient: 3,
+				tier: 0,
+				expires_at: 11,
+			}
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(VIEWER), 900);
+
+		let recipients = BoundedVec::try_from(vec![3, 4, 5]).unwrap();
+		assert_ok!(TickStream::gift_subscriptions(
+			RuntimeOrigin::signed(VIEWER),
+			STREAM,
+			0,
+			recipients
+		));
+		assert_eq!(Balances::free_balance(VIEWER), 630);
+		assert_eq!(TickStream::escrow(STREAM, CREATOR), 370);
+		assert_eq!(TickStream::subscription(STREAM, 3), Some(21));
+		assert_eq!(TickStream::subscription(STREAM, 5), Some(11));
+		// Recipients join when they choose to
+		assert!(TickStream::stream_viewers(STREAM).is_empty());
+	});
+}
+
+#[test]
+fn vesting_co_host_shares_are_released_linearly_and_claimed() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		let co_hosts = BoundedVec::try_from(vec![(3, Permill::from_percent(50))]).unwrap();
+		assert_ok!(TickStream::set_revenue_split(RuntimeOrigin::signed(CREATOR), STREAM, co_hosts));
+		assert_noop!(
+			TickStream::set_split_vesting(RuntimeOrigin::signed(CREATOR), STREAM, VIEWER, 10),
+			Error::<Test>::NotACoHost
+		);
+		assert_ok!(TickStream::set_split_vesting(RuntimeOrigin::signed(CREATOR), STREAM, 3, 10));
+
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		run_to_block(2);
+		// 6 seconds at 10 per second: 30 to the creator's escrow, 30 vesting until block 12
+		assert_ok!(record_tick(STREAM, VIEWER, 6));
+		assert_eq!(TickStream::escrow(STREAM, CREATOR), 30);
+		assert_eq!(TickStream::escrow(STREAM, 3), 0);
+
+		run_to_block(7);
+		assert_ok!(TickStream::claim_vested(RuntimeOrigin::signed(3), STREAM));
+		System::assert_last_event(
+			Event::VestedClaimed { stream_id: STREAM, who: 3, amount: 15 }.into(),
+		);
+		assert_eq!(Balances::free_balance(3), 1_015);
+		assert_eq!(
+			TickStream::vesting_schedule(STREAM, 3),
+			Some(VestingSchedule {
+				unvested: 15,
+				claimable: 0,
+				claimed: 15,
+				updated_at: 7,
+				ends_at: 12,
+			})
+		);
+		assert_noop!(
+			TickStream::claim_vested(RuntimeOrigin::signed(3), STREAM),
+			Error::<Test>::NothingToWithdraw
+		);
+
+		run_to_block(20);
+		assert_ok!(TickStream::claim_vested(RuntimeOrigin::signed(3), STREAM));
+		assert_eq!(Balances::free_balance(3), 1_030);
+		assert_eq!(TickStream::vesting_schedule(STREAM, 3), None);
+	});
+}
+
+#[test]
+fn settlements_keep_the_fiat_value_of_their_time() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+
+		// Without a rate the payment is recorded as unpriced
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 2));
+
+		TestFiatOracle::set_rate(Some(3));
+		run_to_block(3);
+		assert_ok!(record_tick(STREAM, VIEWER, 1));
+		System::assert_last_event(
+			Event::TickRecorded {
+				stream_id: STREAM,
+				viewer: VIEWER,
+				ticks: 1,
+				amount: PRICE,
+				fiat_value: Some(3 * PRICE as u128),
+			}
+			.into(),
+		);
+
+		// A later rate leaves what was already settled alone
+		TestFiatOracle::set_rate(Some(5));
+		run_to_block(4);
+		assert_ok!(record_tick(STREAM, VIEWER, 1));
+		assert_eq!(
+			TickStream::creator_settled_value(CREATOR, 0),
+			SettledValue {
+				native: 4 * PRICE,
+				fiat: (3 * PRICE + 5 * PRICE) as u128,
+				unpriced: 2 * PRICE,
+			}
+		);
+	});
+}
+
+#[test]
+fn streams_report_earnings_in_the_unit_their_creator_chose() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		assert_noop!(
+			TickStream::set_reporting_unit(
+				RuntimeOrigin::signed(VIEWER),
+				STREAM,
+				ReportingUnit::Fiat
+			),
+			Error::<Test>::NotStreamCreator
+		);
+		// The stream is priced in the native token, which no asset rate converts
+		assert_noop!(
+			TickStream::set_reporting_unit(
+				RuntimeOrigin::signed(CREATOR),
+				STREAM,
+				ReportingUnit::Asset(1)
+			),
+			Error::<Test>::UnsupportedReportingUnit
+		);
+
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 2));
+		TestFiatOracle::set_rate(Some(3));
+		run_to_block(3);
+		assert_ok!(record_tick(STREAM, VIEWER, 1));
+		let report = |unit, gross, unpriced| StreamReport { unit, seconds: 3, gross, unpriced };
+		assert_eq!(
+			TickStream::stream_report(STREAM, 0),
+			Some(report(ReportingUnit::Native, 3 * PRICE as u128, 0))
+		);
+
+		// Fiat reports use the rate of each settlement, leaving out what had no rate
+		assert_ok!(TickStream::set_reporting_unit(
+			RuntimeOrigin::signed(CREATOR),
+			STREAM,
+			ReportingUnit::Fiat
+		));
+		System::assert_last_event(
+			Event::ReportingUnitSet { stream_id: STREAM, unit: ReportingUnit::Fiat }.into(),
+		);
+		TestFiatOracle::set_rate(Some(5));
+		assert_eq!(
+			TickStream::stream_report(STREAM, 0),
+			Some(report(ReportingUnit::Fiat, 3 * PRICE as u128, 2 * PRICE))
+		);
+		assert_eq!(TickStream::stream_details(STREAM).unwrap().reporting_unit, ReportingUnit::Fiat);
+		assert_eq!(TickStream::stream_report(STREAM + 1, 0), None);
+	});
+}
+
+#[test]
+fn each_eras_settlements_are_committed_to_in_the_header() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0));
+		assert_ok!(TickStream::start_stream(RuntimeOrigin::signed(CREATOR), STREAM + 1));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(3), STREAM + 1, 10, None));
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 2));
+		assert_ok!(record_tick(STREAM + 1, 3, 3));
+
+		// Nothing is committed to until the era ends
+		assert_eq!(TickStream::era_settlement_proof(0, STREAM), None);
+		run_to_block(10);
+		TickStream::on_initialize(10);
+		let commitment = TickStream::era_settlement_commitment(0).unwrap();
+		assert_eq!(commitment.streams, 2);
+		assert!(System::digest()
+			.logs()
+			.iter()
+			.any(|item| settlement_digest::era_settlement(item) == Some(commitment.clone())));
+		System::assert_has_event(
+			Event::EraSettlementsCommitted { era: 0, root: commitment.root, streams: 2 }.into(),
+		);
+
+		// Each stream's earnings check out against the header's root, and nothing else does
+		for (stream_id, seconds) in [(STREAM, 2), (STREAM + 1, 3)] {
+			let proof = TickStream::era_settlement_proof(0, stream_id).unwrap();
+			assert_eq!(proof.commitment, commitment);
+			assert_eq!(proof.earnings.seconds, seconds);
+			assert_eq!(proof.earnings.gross, seconds * PRICE);
+			assert!(settlement_digest::verify_era_settlement(stream_id, &proof));
+
+			let mut inflated = proof.clone();
+			inflated.earnings.gross += 1;
+			assert!(!settlement_digest::verify_era_settlement(stream_id, &inflated));
+			assert!(!settlement_digest::verify_era_settlement(stream_id + 2, &proof));
+		}
+		assert_eq!(TickStream::era_settlement_proof(0, STREAM + 2), None);
+
+		// An era nothing settled in still gets a commitment
+		run_to_block(20);
+		TickStream::on_initialize(20);
+		assert_eq!(
+			TickStream::era_settlement_commitment(1),
+			Some(EraSettlementCommitment { era: 1, root: [0; 32], streams: 0 })
+		);
+	});
+}
+
+#[test]
+fn disputed_ticks_are_held_back_and_slash_the_reporter_bond() {
+	new_test_ext().execute_with(|| {
+		SettlementDelay::set(3);
+		ReporterBond::set(50);
+		live_stream();
+		assert_eq!(Balances::reserved_balance(CREATOR), 50);
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+
+		// Ticks are held on the viewer's reserve until their payments fall due
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 2));
+		run_to_block(3);
+		assert_ok!(record_tick(STREAM, VIEWER, 1));
+		assert_eq!(TickStream::escrow(STREAM, CREATOR), 0);
+
+		assert_noop!(
+			TickStream::dispute_ticks(RuntimeOrigin::signed(VIEWER), STREAM, 1, 2),
+			Error::<Test>::NothingToDispute
+		);
+		assert_ok!(TickStream::dispute_ticks(RuntimeOrigin::signed(VIEWER), STREAM, 2, 3));
+		System::assert_last_event(
+			Event::TicksDisputed { dispute_id: 0, stream_id: STREAM, viewer: VIEWER, amount: PRICE }
+				.into(),
+		);
+		assert_noop!(
+			TickStream::unbond_reporter(RuntimeOrigin::signed(CREATOR)),
+			Error::<Test>::DisputesOpen
+		);
+
+		// The undisputed payment goes through, the disputed one stays frozen
+		TickStream::on_initialize(5);
+		TickStream::on_initialize(6);
+		assert_eq!(TickStream::escrow(STREAM, CREATOR), 2 * PRICE);
+		assert!(TickStream::pending_settlements(STREAM, VIEWER).is_empty());
+
+		// Upholding the dispute refunds the viewer and hands them as much of the creator's
+		// bond again
+		assert_ok!(TickStream::resolve_dispute(RuntimeOrigin::root(), 0, true));
+		System::assert_last_event(
+			Event::DisputeResolved { dispute_id: 0, upheld: true, slashed: PRICE }.into(),
+		);
+		assert_eq!(Balances::reserved_balance(VIEWER), 100 - 3 * PRICE);
+		assert_eq!(Balances::free_balance(VIEWER), 1_000 - 100 + 2 * PRICE);
+		assert_eq!(Balances::reserved_balance(CREATOR), 50 - PRICE);
+		assert_eq!(TickStream::reporter_stake(CREATOR).map(|stake| stake.bonded), Some(50 - PRICE));
+
+		// The stream is not settled again until the bond is topped back up
+		run_to_block(7);
+		assert_noop!(record_tick(STREAM, VIEWER, 1), Error::<Test>::ReporterBondShort);
+		assert_ok!(TickStream::bond_reporter(RuntimeOrigin::signed(CREATOR)));
+		System::assert_last_event(Event::ReporterBonded { who: CREATOR, amount: PRICE }.into());
+		assert_eq!(Balances::reserved_balance(CREATOR), 50);
+		assert_ok!(record_tick(STREAM, VIEWER, 1));
+	});
+}
+
+#[test]
+fn streams_are_handed_over_with_their_handle_and_a_fresh_bond() {
+	new_test_ext().execute_with(|| {
+		SettlementDelay::set(3);
+		ReporterBond::set(50);
+		live_stream();
+		let handle = BoundedVec::truncate_from(b"alice".to_vec());
+		assert_ok!(TickStream::claim_handle(RuntimeOrigin::signed(CREATOR), STREAM, handle));
+		assert_eq!(Balances::reserved_balance(CREATOR), 100);
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 2));
+
+		// Held payments are paid out by the creator who reported them first
+		assert_noop!(
+			TickStream::transfer_stream(RuntimeOrigin::signed(CREATOR), STREAM, 3),
+			Error::<Test>::SettlementsPending
+		);
+		assert_ok!(TickStream::dispute_ticks(RuntimeOrigin::signed(VIEWER), STREAM, 0, 2));
+		assert_noop!(
+			TickStream::transfer_stream(RuntimeOrigin::signed(CREATOR), STREAM, 3),
+			Error::<Test>::DisputesOpen
+		);
+		assert_ok!(TickStream::resolve_dispute(RuntimeOrigin::root(), 0, false));
+
+		// The new creator is bonded and takes the handle over on their own deposit
+		assert_ok!(TickStream::transfer_stream(RuntimeOrigin::signed(CREATOR), STREAM, 3));
+		System::assert_has_event(Event::ReporterBonded { who: 3, amount: 50 }.into());
+		assert_eq!(TickStream::reporter_stake(3).map(|stake| stake.bonded), Some(50));
+		assert_eq!(TickStream::resolve_handle(b"alice").map(|claim| claim.owner), Some(3));
+		assert_eq!(Balances::reserved_balance(3), 100);
+		assert_eq!(Balances::reserved_balance(CREATOR), 50);
+		assert_ok!(TickStream::release_handle(RuntimeOrigin::signed(3), STREAM));
+		assert_noop!(
+			TickStream::release_handle(RuntimeOrigin::signed(CREATOR), STREAM),
+			Error::<Test>::NoHandle
+		);
+	});
+}
+
+#[test]
+fn held_payments_that_cannot_be_paid_out_go_back_to_the_viewer() {
+	new_test_ext().execute_with(|| {
+		SettlementDelay::set(3);
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 2));
+
+		// The payment is too small to open the stream's escrow account with
+		ExistentialDeposit::set(50);
+		TickStream::on_initialize(5);
+		System::assert_last_event(
+			Event::SettlementReturned { stream_id: STREAM, viewer: VIEWER, amount: 2 * PRICE }
+				.into(),
+		);
+		assert!(TickStream::pending_settlements(STREAM, VIEWER).is_empty());
+		assert_eq!(TickStream::escrow(STREAM, CREATOR), 0);
+		assert_eq!(Balances::reserved_balance(VIEWER), 100 - 2 * PRICE);
+		assert_eq!(Balances::free_balance(VIEWER), 1_000 - 100 + 2 * PRICE);
+	});
+}
+
+#[test]
+fn upheld_disputes_each_slash_what_they_disputed() {
+	new_test_ext().execute_with(|| {
+		SettlementDelay::set(3);
+		ReporterBond::set(50);
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(3), STREAM, 10, None));
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 4));
+		assert_ok!(record_tick(STREAM, 3, 2));
+		assert_ok!(TickStream::dispute_ticks(RuntimeOrigin::signed(VIEWER), STREAM, 0, 4));
+		assert_ok!(TickStream::dispute_ticks(RuntimeOrigin::signed(3), STREAM, 0, 2));
+
+		assert_ok!(TickStream::resolve_dispute(RuntimeOrigin::root(), 0, true));
+		assert_ok!(TickStream::resolve_dispute(RuntimeOrigin::root(), 1, true));
+		System::assert_has_event(
+			Event::DisputeResolved { dispute_id: 0, upheld: true, slashed: 4 * PRICE }.into(),
+		);
+		// Only what is left of the bond can be slashed
+		System::assert_last_event(
+			Event::DisputeResolved { dispute_id: 1, upheld: true, slashed: 50 - 4 * PRICE }.into(),
+		);
+		assert_eq!(TickStream::reporter_stake(CREATOR).map(|stake| stake.bonded), Some(0));
+		assert_eq!(Balances::reserved_balance(CREATOR), 0);
+	});
+}
+
+#[test]
+fn streams_priced_in_an_asset_are_paid_in_it() {
+	new_test_ext().execute_with(|| {
+		const USD: u32 = 7;
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), USD, CREATOR, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(CREATOR), USD, CREATOR, 10));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(CREATOR), USD, VIEWER, 500));
+
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0));
+		assert_noop!(
+			TickStream::set_stream_asset(RuntimeOrigin::signed(CREATOR), STREAM, Some(9)),
+			Error::<Test>::UnknownAsset
+		);
+		assert_ok!(TickStream::set_stream_asset(RuntimeOrigin::signed(CREATOR), STREAM, Some(USD)));
+		assert_ok!(TickStream::start_stream(RuntimeOrigin::signed(CREATOR), STREAM));
+
+		// Watch time is held in the stream's escrow account rather than reserved
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		assert_eq!(Assets::balance(USD, VIEWER), 400);
+		assert_eq!(Balances::reserved_balance(VIEWER), 0);
+
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 2));
+		assert_ok!(TickStream::withdraw(RuntimeOrigin::signed(CREATOR), STREAM));
+		assert_eq!(Assets::balance(USD, CREATOR), 10 - 1 + 2 * PRICE);
+
+		// What is left of the viewer's reserve comes back in the asset
+		assert_ok!(TickStream::end_stream(RuntimeOrigin::signed(CREATOR), STREAM));
+		assert_eq!(Assets::balance(USD, VIEWER), 500 - 2 * PRICE);
+	});
+}
+
+#[test]
+fn join_gates_keep_new_and_idle_accounts_out() {
+	new_test_ext().execute_with(|| {
+		// The viewer builds up watch time on an open stream
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 2));
+		assert_eq!(TickStream::lifetime_watch_seconds(VIEWER), 2);
+
+		// A fresh account, created in block 2
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(3), 5, 100));
+
+		let gated = STREAM + 1;
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0));
+		let gate = JoinGate { min_account_age: 2, min_watch_seconds: 2, require_identity: false };
+		assert_noop!(
+			TickStream::set_join_gate(
+				RuntimeOrigin::signed(CREATOR),
+				gated,
+				Some(JoinGate { min_account_age: 101, ..gate.clone() })
+			),
+			Error::<Test>::JoinGateTooStrict
+		);
+		assert_noop!(
+			TickStream::set_join_gate(
+				RuntimeOrigin::signed(CREATOR),
+				gated,
+				Some(JoinGate { require_identity: true, ..gate.clone() })
+			),
+			Error::<Test>::IdentityUnsupported
+		);
+		assert_ok!(TickStream::set_join_gate(RuntimeOrigin::signed(CREATOR), gated, Some(gate)));
+
+		run_to_block(3);
+		assert_noop!(
+			TickStream::join_stream(RuntimeOrigin::signed(5), gated, 1, None),
+			Error::<Test>::AccountTooNew
+		);
+		assert_noop!(
+			TickStream::join_stream(RuntimeOrigin::signed(3), gated, 1, None),
+			Error::<Test>::NotEnoughWatchTime
+		);
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), gated, 1, None));
+
+		// Once old enough, the new account is held back by its watch time alone
+		run_to_block(4);
+		assert_noop!(
+			TickStream::join_stream(RuntimeOrigin::signed(5), gated, 1, None),
+			Error::<Test>::NotEnoughWatchTime
+		);
+	});
+}
+
+#[test]
+fn watch_time_is_handed_on_in_batches_after_each_era() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0));
+		assert_ok!(TickStream::start_stream(RuntimeOrigin::signed(CREATOR), STREAM + 1));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM + 1, 10, None));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(3), STREAM, 10, None));
+
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 2));
+		assert_ok!(record_tick(STREAM + 1, VIEWER, 1));
+		assert_ok!(record_tick(STREAM, 3, 1));
+		run_to_block(3);
+		assert_ok!(record_tick(STREAM, VIEWER, 1));
+
+		// Nothing is handed on while the era is running
+		TickStream::on_initialize(3);
+		assert!(TestRewards::batches().is_empty());
+
+		// The era's three records go out two per block
+		run_to_block(10);
+		TickStream::on_initialize(10);
+		run_to_block(11);
+		TickStream::on_initialize(11);
+		let batches = TestRewards::batches();
+		assert_eq!(batches.iter().map(|(_, records)| records.len()).collect::<Vec<_>>(), [2, 1]);
+		assert!(batches.iter().all(|(era, _)| *era == 0));
+		let mut records = batches.into_iter().flat_map(|(_, records)| records).collect::<Vec<_>>();
+		records.sort();
+		assert_eq!(records, [(VIEWER, STREAM, 3), (VIEWER, STREAM + 1, 1), (3, STREAM, 1)]);
+
+		run_to_block(12);
+		TickStream::on_initialize(12);
+		assert_eq!(TestRewards::batches().len(), 2);
+	});
+}
+
+#[test]
+fn streams_are_migrated_to_v2_v5_and_v6() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<TickStream>();
+		let old = migrations::v1::Stream::<u64, u64, u64> {
+			creator: CREATOR,
+			price_per_second: PRICE,
+			last_tick: 5,
+			status: StreamStatus::Live,
+			max_duration: Some(100),
+		};
+		let key = crate::Streams::<Test>::hashed_key_for(STREAM);
+		frame_support::storage::unhashed::put(&key, &old);
+
+		migrations::MigrateToV2::<Test>::on_runtime_upgrade();
+		assert_eq!(TickStream::on_chain_storage_version(), 2);
+		migrations::MigrateToV5::<Test>::on_runtime_upgrade();
+		assert_eq!(TickStream::on_chain_storage_version(), 5);
+		migrations::MigrateToV6::<Test>::on_runtime_upgrade();
+		let stream = TickStream::streams(STREAM).unwrap();
+		assert_eq!(stream.creator, CREATOR);
+		assert_eq!(stream.max_duration, Some(100));
+		assert_eq!(stream.asset_id, None);
+		assert_eq!(stream.payout_account, None);
+		assert_eq!(stream.content_type, ContentType::Live);
+		assert_eq!(TickStream::on_chain_storage_version(), 6);
+
+		// A second run leaves migrated streams alone
+		migrations::MigrateToV2::<Test>::on_runtime_upgrade();
+		migrations::MigrateToV5::<Test>::on_runtime_upgrade();
+		migrations::MigrateToV6::<Test>::on_runtime_upgrade();
+		assert_eq!(TickStream::streams(STREAM), Some(stream));
+	});
+}
+
+#[test]
+fn reserves_are_moved_onto_holds_on_upgrade() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		// A viewer's reserve from before version 7, next to another pallet's
+		crate::Balances::<Test>::insert(STREAM, VIEWER, 50 * PRICE);
+		assert_ok!(Balances::reserve(&VIEWER, 50 * PRICE));
+		assert_ok!(Balances::reserve(&VIEWER, 7));
+		StorageVersion::new(6).put::<TickStream>();
+
+		migrations::MigrateToV7::<Test>::on_runtime_upgrade();
+		assert_eq!(TickStream::on_chain_storage_version(), 7);
+		let deposit = HoldReason::StreamDeposit.into();
+		assert_eq!(Balances::balance_on_hold(&deposit, &VIEWER), 50 * PRICE);
+		assert_eq!(Balances::reserved_balance(&VIEWER), 50 * PRICE + 7);
+
+		// A second run moves nothing more
+		migrations::MigrateToV7::<Test>::on_runtime_upgrade();
+		assert_eq!(Balances::balance_on_hold(&deposit, &VIEWER), 50 * PRICE);
+
+		// Refunds release the hold and leave the other pallet's reserve
+		assert_ok!(TickStream::end_stream(RuntimeOrigin::signed(CREATOR), STREAM));
+		assert_eq!(Balances::balance_on_hold(&deposit, &VIEWER), 0);
+		assert_eq!(Balances::reserved_balance(&VIEWER), 7);
+	});
+}
+
+#[test]
+fn creators_and_sync_hosts_anchor_positions() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_noop!(
+			TickStream::anchor_position(RuntimeOrigin::signed(3), STREAM, 1_000),
+			Error::<Test>::NotSyncHost
+		);
+		assert_noop!(
+			TickStream::set_sync_host(RuntimeOrigin::signed(3), STREAM, Some(3)),
+			Error::<Test>::NotStreamCreator
+		);
+		assert_ok!(TickStream::set_sync_host(RuntimeOrigin::signed(CREATOR), STREAM, Some(3)));
+
+		// Anchoring twice in a block keeps the later position
+		assert_ok!(TickStream::anchor_position(RuntimeOrigin::signed(3), STREAM, 1_000));
+		assert_ok!(TickStream::anchor_position(RuntimeOrigin::signed(3), STREAM, 1_500));
+		System::assert_last_event(
+			Event::PositionAnchored { stream_id: STREAM, host: 3, media_timestamp: 1_500 }.into(),
+		);
+		assert_eq!(
+			TickStream::position_anchors(STREAM).into_inner(),
+			[PositionAnchor { block: 1, media_timestamp: 1_500 }]
+		);
+
+		// Only the latest `MaxPositionAnchors` are kept
+		for block in 2..=4 {
+			run_to_block(block);
+			assert_ok!(TickStream::anchor_position(
+				RuntimeOrigin::signed(CREATOR),
+				STREAM,
+				block * 1_000
+			));
+		}
+		let blocks = TickStream::position_anchors(STREAM)
+			.iter()
+			.map(|anchor| anchor.block)
+			.collect::<Vec<_>>();
+		assert_eq!(blocks, [2, 3, 4]);
+
+		assert_ok!(TickStream::set_sync_host(RuntimeOrigin::signed(CREATOR), STREAM, None));
+		assert_noop!(
+			TickStream::anchor_position(RuntimeOrigin::signed(3), STREAM, 5_000),
+			Error::<Test>::NotSyncHost
+		);
+		assert_ok!(TickStream::pause_stream(RuntimeOrigin::signed(CREATOR), STREAM));
+		assert_noop!(
+			TickStream::anchor_position(RuntimeOrigin::signed(CREATOR), STREAM, 5_000),
+			Error::<Test>::StreamNotLive
+		);
+	});
+}
+
+/// The name and index of every variant of `T`, as the metadata lists them, and whether the
+/// variant is documented.
+fn metadata_variants<T: scale_info::TypeInfo + 'static>() -> Vec<(u8, &'static str, bool)> {
+	match T::type_info().type_def {
+		scale_info::TypeDef::Variant(def) => def
+			.variants
+			.iter()
+			.map(|variant| (variant.index, variant.name, !variant.docs.is_empty()))
+			.collect(),
+		_ => panic!("not an enum"),
+	}
+}
+
+/// Calls, errors and events are identified by index in extrinsics, event records and UIs built
+/// from the metadata, so new ones may only be appended.
+#[test]
+fn call_error_and_event_indices_never_shift() {
+	let calls = [
+		"record_tick", "join_stream", "create_stream", "start_stream", "pause_stream",
+		"end_stream", "batch_tick", "join_with_voucher", "set_platform_fee", "set_revenue_split",
+		"withdraw", "set_notification_prefs", "set_metadata", "clear_metadata", "sweep_expired",
+		"create_promo", "commit_promo", "reveal_promo", "update_price", "top_up",
+		"transfer_stream", "tip", "set_auto_leave_threshold", "force_leave_stream",
+		"force_leave_account", "schedule_stream", "top_up_budget", "release_budget",
+		"prune_viewer_stats", "create_subscription_tier", "subscribe", "set_split_vesting",
+		"claim_vested", "dispute_ticks", "resolve_dispute", "unbond_reporter", "set_stream_asset",
+		"set_join_gate", "set_sync_host", "anchor_position", "freeze_payouts", "unfreeze_payouts",
+		"settle_inherent", "claim_handle", "release_handle", "set_spending_limit", "resume_session",
+		"set_free_preview", "open_session", "close_session", "set_device_limit",
+		"set_device_exemption", "claim_referral_earnings", "set_max_viewers",
+		"set_category_fee", "pre_book", "gift_subscriptions", "set_bulk_discount",
+		"set_sweep_policy", "set_payout_account", "link_did", "unlink_did",
+		"set_dust_policy", "register_reporter", "deregister_reporter", "join_stream_v2",
+		"migrate_stream", "set_content_type", "report_playback", "set_global_hold_cap",
+		"set_access_policy", "add_to_allowlist", "remove_from_allowlist", "approve_grant",
+		"set_attestation_source", "import_attestation", "set_reporting_unit", "set_hedge_policy",
+		"create_poll", "vote", "run_giveaway", "bond_reporter", "set_call_filter_mode",
+		"set_tick_threshold", "set_batch_cap",
+	];
+	let mut calls_in_metadata = metadata_variants::<crate::Call<Test>>();
+	calls_in_metadata.sort();
+	assert_eq!(calls_in_metadata.iter().map(|(_, name, _)| *name).collect::<Vec<_>>(), calls);
+	assert!(calls_in_metadata.iter().enumerate().all(|(i, (index, _, _))| *index as usize == i));
+
+	let errors = [
+		"StreamNotFound", "Unauthorized", "StreamAlreadyExists", "NotStreamCreator",
+		"StreamNotLive", "StreamEnded", "InvalidStatusTransition", "InsufficientBalance",
+		"TickTooEarly", "TooManyTicks", "InvalidVoucher", "InvalidVoucherSignature",
+		"VoucherExpired", "VoucherAlreadyUsed", "InvalidRevenueSplit", "NothingToWithdraw",
+		"InvalidNotificationPrefs", "SettlementWindowOpen", "NothingToSweep", "PromoNotFound",
+		"PromoExhausted", "NoPromoCommitment", "RevealTooEarly", "CommitmentMismatch",
+		"StartTimeInPast", "InvalidUtcOffset", "NoBudget", "NothingToPrune", "TierNotFound",
+		"TooManyTiers", "NotACoHost", "NothingToDispute", "DisputeNotFound", "NoReporterBond",
+		"DisputesOpen", "SettlementsPending", "UnknownAsset", "StreamInUse", "JoinGateTooStrict",
+		"IdentityUnsupported", "AccountTooNew", "NotEnoughWatchTime", "IdentityRequired",
+		"NotSyncHost", "PayoutsFrozen", "PayoutsNotFrozen", "InvalidFreezeDuration",
+		"InvalidHandle", "HandleTaken", "StreamHasHandle", "NoHandle", "SessionPaused",
+		"SessionNotPaused", "TooManyDevices", "SessionAlreadyOpen", "SessionNotOpen",
+		"InvalidDeviceLimit", "InvalidReferrer", "StreamFull", "InvalidMaxViewers",
+		"NotScheduled", "AlreadyBooked", "TooManyBookings", "NoRecipients", "InvalidBulkDiscount",
+		"InvalidColdAddress", "ArithmeticOverflow", "InvalidDid", "DidTaken", "NoDidLink",
+		"BelowExistentialDeposit", "ReporterAlreadyRegistered", "NotReporter",
+		"ReporterCoolingDown", "InvalidReporterSignature", "StaleReporterNonce", "PriceAboveLimit",
+		"MigrationWitnessTooLow", "BilledByPlayback", "NotOnDemand", "GlobalCapReached",
+		"AccessDenied", "IntervalAlreadySettled", "GrantAlreadyApproved", "InvalidGrant",
+		"TooManyAttestationSources", "InvalidAttestationSource", "UnknownAttestationSource",
+		"AttestationExpired", "StaleAttestation", "InvalidAttestationSignature",
+		"UnsupportedReportingUnit", "HedgingUnsupported", "InvalidSessionTransition",
+		"InvalidPollOptions", "PollTextTooLong", "InvalidPollWindow", "PollNotFound", "PollClosed",
+		"InvalidPollOption", "NoWatchTime", "NoPrize", "NoEligibleViewers", "ReporterBondShort",
+		"InvalidBatchCap", "BatchTooLarge",
+	];
+	let mut errors_in_metadata = metadata_variants::<Error<Test>>();
+	errors_in_metadata.sort();
+	assert_eq!(errors_in_metadata.iter().map(|(_, name, _)| *name).collect::<Vec<_>>(), errors);
+
+	let events = [
+		"TickRecorded", "ViewerJoined", "StreamCreated", "StreamStatusChanged", "ViewerRefunded",
+		"TickSkipped", "IntervalSettled", "StreamAutoEnded", "VoucherRedeemed", "PlatformFeeSet",
+		"RevenueSplitSet", "RevenueSplit", "Withdrawn", "NotificationPrefsSet", "MetadataSet",
+		"MetadataCleared", "ExpiredSwept", "PromoCreated", "PromoCommitted", "PromoRedeemed",
+		"StreamTransferred", "AutoLeaveThresholdSet", "ViewerAutoLeft", "StreamAccessRevoked",
+		"AccountAccessRevoked", "StreamScheduled", "ScheduleAdjusted", "ScheduledStreamStarted",
+		"BudgetToppedUp", "BudgetReleased", "ViewerStatsPruned", "SubscriptionTierCreated",
+		"Subscribed", "SubscriptionExpired", "SplitVestingSet", "VestedClaimed",
+		"SettlementFinalized", "TicksDisputed", "DisputeResolved", "ReporterBonded",
+		"ReporterUnbonded", "StreamAssetSet", "JoinGateSet", "SyncHostSet", "PositionAnchored",
+		"PayoutsFrozen", "PayoutFreezeExtended", "PayoutsReleased", "TickQueued", "HandleClaimed",
+		"HandleReleased", "SpendingLimitSet", "SpendingLimitReached", "SessionResumed",
+		"FreePreviewSet", "SessionOpened", "SessionClosed", "DeviceLimitSet", "DeviceExemptionSet",
+		"ReferralPaid", "ReferralEarningsClaimed", "ViewerCountChanged", "MaxViewersSet",
+		"CategoryFeeSet", "PreBooked", "PreBookingsSeated", "PreBookingsCancelled",
+		"SubscriptionGifted", "BulkDiscountSet", "SweepPolicySet", "SweptToCold",
+		"PayoutAccountSet", "PayoutRouted", "DidLinked", "DidUnlinked", "DustPolicySet",
+		"DustDonated", "DustCredited", "ViewingCreditRedeemed", "ReporterRegistered",
+		"ReporterDeregistered", "ReporterBondReturned", "DeprecatedCallUsed", "StreamMigrated",
+		"ContentTypeSet", "GlobalHoldCapSet", "AccessPolicySet", "AddedToAllowlist",
+		"RemovedFromAllowlist", "ViewerFundsExhausted", "GrantApproved", "GrantMilestoneReached",
+		"GrantClawedBack", "AttestationSourceSet", "AttestationImported", "ReportingUnitSet",
+		"EraSettlementsCommitted", "HedgePolicySet", "EarningsHedged", "HedgeSkipped",
+		"SettlementStalled", "SettlementResumed", "PollCreated", "PollVoted", "PollFinalized",
+		"GiveawayDrawn", "TipReceived", "PriceChangeScheduled", "PriceChanged", "FeaturedSelected",
+		"LowBalanceWarning", "ToppedUp", "SessionStateChanged", "SettlementReturned",
+		"CallFilterModeSet", "TickThresholdSet", "BatchCapSet",
+	];
+	let mut events_in_metadata = metadata_variants::<Event<Test>>();
+	events_in_metadata.sort();
+	assert_eq!(events_in_metadata.iter().map(|(_, name, _)| *name).collect::<Vec<_>>(), events);
+	assert!(events_in_metadata.iter().enumerate().all(|(i, (index, _, _))| *index as usize == i));
+
+	// UIs show these docs to explain calls and failures
+	for (_, name, documented) in calls_in_metadata.iter().chain(&errors_in_metadata) {
+		assert!(documented, "{} has no docs", name);
+	}
+}
+
+#[test]
+fn frozen_payouts_are_released_when_the_freeze_runs_out() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 3));
+
+		assert_noop!(
+			TickStream::freeze_payouts(RuntimeOrigin::signed(VIEWER), CREATOR, 10),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			TickStream::freeze_payouts(RuntimeOrigin::root(), CREATOR, 0),
+			Error::<Test>::InvalidFreezeDuration
+		);
+		assert_ok!(TickStream::freeze_payouts(RuntimeOrigin::root(), CREATOR, 10));
+		System::assert_last_event(Event::PayoutsFrozen { who: CREATOR, until: 12 }.into());
+		assert_noop!(
+			TickStream::withdraw(RuntimeOrigin::signed(CREATOR), STREAM),
+			Error::<Test>::PayoutsFrozen
+		);
+
+		// A shorter freeze leaves the longer one in place; a longer one extends it
+		assert_ok!(TickStream::freeze_payouts(RuntimeOrigin::root(), CREATOR, 5));
+		assert_eq!(TickStream::payout_freeze(CREATOR), Some(12));
+		assert_ok!(TickStream::freeze_payouts(RuntimeOrigin::root(), CREATOR, 20));
+		System::assert_last_event(Event::PayoutFreezeExtended { who: CREATOR, until: 22 }.into());
+
+		// The freeze's original end no longer releases it
+		run_to_block(12);
+		TickStream::on_initialize(12);
+		assert_noop!(
+			TickStream::withdraw(RuntimeOrigin::signed(CREATOR), STREAM),
+			Error::<Test>::PayoutsFrozen
+		);
+
+		run_to_block(22);
+		TickStream::on_initialize(22);
+		System::assert_has_event(Event::PayoutsReleased { who: CREATOR }.into());
+		assert_eq!(TickStream::payout_freeze(CREATOR), None);
+		assert_ok!(TickStream::withdraw(RuntimeOrigin::signed(CREATOR), STREAM));
+
+		assert_ok!(TickStream::freeze_payouts(RuntimeOrigin::root(), CREATOR, 10));
+		assert_ok!(TickStream::unfreeze_payouts(RuntimeOrigin::root(), CREATOR));
+		assert_noop!(
+			TickStream::unfreeze_payouts(RuntimeOrigin::root(), CREATOR),
+			Error::<Test>::PayoutsNotFrozen
+		);
+	});
+}
+
+#[test]
+fn due_ticks_are_settled_by_inherent() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(3), STREAM, 10, None));
+		run_to_block(3);
+
+		let due = TickStream::due_ticks(u32::MAX);
+		assert_eq!(due.len(), 2);
+		assert!(due.iter().all(|(stream_id, _, ticks)| *stream_id == STREAM && *ticks > 0));
+
+		// Nodes that do not provide the data leave settlement to unsigned ticks
+		assert_eq!(TickStream::create_inherent(&InherentData::new()), None);
+
+		let mut data = InherentData::new();
+		let mut ticks = due.clone();
+		// An unjoined viewer and a stream that does not exist are skipped
+		ticks.push((STREAM, 4, 1));
+		ticks.push((STREAM + 1, VIEWER, 1));
+		data.put_data(crate::INHERENT_IDENTIFIER, &ticks).unwrap();
+		let call = TickStream::create_inherent(&data).unwrap();
+		assert!(TickStream::is_inherent(&call));
+		assert_ok!(call.dispatch_bypass_filter(RuntimeOrigin::none()));
+
+		System::assert_has_event(Event::TickSkipped { stream_id: STREAM, viewer: 4 }.into());
+		System::assert_has_event(
+			Event::TickSkipped { stream_id: STREAM + 1, viewer: VIEWER }.into(),
+		);
+		let settled = System::events().into_iter().any(|record| {
+			matches!(
+				record.event,
+				RuntimeEvent::TickStream(Event::IntervalSettled { viewers: 2, .. })
+			)
+		});
+		assert!(settled);
+		assert!(TickStream::due_ticks(u32::MAX).is_empty());
+	});
+}
+
+#[test]
+fn ticks_beyond_the_block_cap_are_settled_in_later_blocks() {
+	new_test_ext().execute_with(|| {
+		MaxSettlementsPerBlock::set(1);
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(3), STREAM, 10, None));
+		run_to_block(2);
+
+		let ticks = BoundedVec::truncate_from(vec![(VIEWER, 2), (3, 2)]);
+		assert_ok!(batch_tick(STREAM, ticks));
+		System::assert_has_event(
+			Event::TickQueued { stream_id: STREAM, viewer: 3, ticks: 2 }.into(),
+		);
+		System::assert_last_event(
+			Event::IntervalSettled {
+				stream_id: STREAM,
+				viewers: 1,
+				total_amount: 2 * PRICE,
+				total_fiat_value: 0,
+			}
+			.into(),
+		);
+		assert_eq!(TickStream::balances(STREAM, 3), 100);
+		assert_eq!(TickStream::queued_tick_range(), (0, 1));
+
+		// The queued tick takes the next block's only settlement, so the next tick queues in turn
+		run_to_block(3);
+		TickStream::on_initialize(3);
+		assert_eq!(TickStream::balances(STREAM, 3), 80);
+		assert_eq!(TickStream::queued_tick_range(), (1, 1));
+		assert_ok!(record_tick(STREAM, VIEWER, 1));
+		assert_eq!(TickStream::queued_tick_range(), (1, 2));
+		assert_eq!(TickStream::balances(STREAM, VIEWER), 80);
+	});
+}
+
+/// The events of settlements so far: ticks charged, viewers run out and sessions paused.
+fn settlement_events() -> Vec<Event<Test>> {
+	System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			RuntimeEvent::TickStream(
+				event @ (Event::TickRecorded { .. } |
+				Event::ViewerFundsExhausted { .. } |
+				Event::SpendingLimitReached { .. }),
+			) => Some(event),
+			_ => None,
+		})
+		.collect()
+}
+
+/// Settle the same watch time with `settle` on a stream of `content_type`, and check it is
+/// settled as through every other entry point: viewer 3 runs out part way, viewer 5 reaches
+/// their spending limit and viewer 4, who never joined, is turned away.
+fn assert_settles_alike(content_type: ContentType, settle: impl FnOnce(Vec<(u64, u32)>)) {
+	assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0));
+	assert_ok!(TickStream::set_content_type(RuntimeOrigin::signed(CREATOR), STREAM, content_type));
+	assert_ok!(TickStream::start_stream(RuntimeOrigin::signed(CREATOR), STREAM));
+	assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+	assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(3), STREAM, 2, None));
+	assert_ok!(TickStream::top_up_budget(RuntimeOrigin::signed(3), CREATOR, 15));
+	Balances::make_free_balance_be(&5, 1_000);
+	assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(5), STREAM, 10, None));
+	assert_ok!(TickStream::set_spending_limit(RuntimeOrigin::signed(5), Some(3 * PRICE), None));
+	run_to_block(2);
+
+	settle(vec![(VIEWER, 2), (3, 5), (5, 4), (4, 1)]);
+
+	let events = settlement_events();
+	let expected = [
+		Event::TickRecorded {
+			stream_id: STREAM,
+			viewer: VIEWER,
+			ticks: 2,
+			amount: 2 * PRICE,
+			fiat_value: None,
+		},
+		Event::TickRecorded {
+			stream_id: STREAM,
+			viewer: 3,
+			ticks: 5,
+			amount: 35,
+			fiat_value: None,
+		},
+		Event::ViewerFundsExhausted { stream_id: STREAM, viewer: 3, charged: 35, shortfall: 15 },
+		Event::SpendingLimitReached { stream_id: STREAM, viewer: 5 },
+	];
+	assert_eq!(events.len(), expected.len());
+	for event in expected {
+		assert!(events.contains(&event), "{:?} is missing", event);
+	}
+
+	assert_eq!(TickStream::balances(STREAM, VIEWER), 80);
+	assert_eq!(TickStream::balances(STREAM, 3), 0);
+	assert_eq!(TickStream::creator_budget(3, CREATOR).reserved, 0);
+	assert_eq!(TickStream::balances(STREAM, 5), 100);
+	assert!(TickStream::viewer_session(STREAM, &5).unwrap().paused);
+	assert_eq!(TickStream::stream_viewers(STREAM), vec![VIEWER, 5]);
+	assert_eq!(TickStream::escrow(STREAM, CREATOR), 55);
+	assert_eq!(TickStream::get_tick_count(STREAM), 7);
+}
+
+/// The `IntervalSettled` event of a batch settling viewers 2 and 3 in
+/// `assert_settles_alike`.
+fn settled_interval() -> RuntimeEvent {
+	Event::IntervalSettled { stream_id: STREAM, viewers: 2, total_amount: 55, total_fiat_value: 0 }
+		.into()
+}
+
+#[test]
+fn single_ticks_are_settled_alike() {
+	new_test_ext().execute_with(|| {
+		assert_settles_alike(ContentType::Live, |ticks| {
+			for (viewer, seconds) in ticks {
+				if viewer == 4 {
+					assert_noop!(record_tick(STREAM, viewer, seconds), Error::<Test>::Unauthorized);
+				} else {
+					assert_ok!(record_tick(STREAM, viewer, seconds));
+				}
+			}
+		});
+	});
+}
+
+#[test]
+fn batched_ticks_are_settled_alike() {
+	new_test_ext().execute_with(|| {
+		assert_settles_alike(ContentType::Live, |ticks| {
+			assert_ok!(batch_tick(STREAM, BoundedVec::truncate_from(ticks)));
+			System::assert_has_event(Event::TickSkipped { stream_id: STREAM, viewer: 4 }.into());
+			System::assert_last_event(settled_interval());
+		});
+	});
+}
+
+#[test]
+fn inherent_ticks_are_settled_alike() {
+	new_test_ext().execute_with(|| {
+		assert_settles_alike(ContentType::Live, |ticks| {
+			let ticks: Vec<_> =
+				ticks.into_iter().map(|(viewer, seconds)| (STREAM, viewer, seconds)).collect();
+			let mut data = InherentData::new();
+			data.put_data(crate::INHERENT_IDENTIFIER, &ticks).unwrap();
+			let call = TickStream::create_inherent(&data).unwrap();
+			assert_ok!(call.dispatch_bypass_filter(RuntimeOrigin::none()));
+			System::assert_has_event(Event::TickSkipped { stream_id: STREAM, viewer: 4 }.into());
+			System::assert_last_event(settled_interval());
+		});
+	});
+}
+
+#[test]
+fn played_seconds_are_settled_alike() {
+	new_test_ext().execute_with(|| {
+		assert_settles_alike(ContentType::Vod, |ticks| {
+			for (viewer, seconds) in ticks {
+				let origin = RuntimeOrigin::signed(viewer);
+				if viewer == 4 {
+					assert_noop!(
+						TickStream::report_playback(origin, STREAM, seconds),
+						Error::<Test>::Unauthorized
+					);
+				} else {
+					assert_ok!(TickStream::report_playback(origin, STREAM, seconds));
+				}
+			}
+			assert_eq!(TickStream::playback_position(STREAM, 3), 5);
+		});
+	});
+}
+
+#[test]
+fn queued_ticks_are_settled_alike() {
+	new_test_ext().execute_with(|| {
+		assert_settles_alike(ContentType::Live, |ticks| {
+			// The block has no settlements left, so the ticks are settled by the next
+			MaxSettlementsPerBlock::set(0);
+			assert_ok!(batch_tick(STREAM, BoundedVec::truncate_from(ticks)));
+			System::assert_has_event(Event::TickSkipped { stream_id: STREAM, viewer: 4 }.into());
+			System::assert_has_event(
+				Event::TickQueued { stream_id: STREAM, viewer: 3, ticks: 5 }.into(),
+			);
+			assert_eq!(TickStream::queued_tick_range(), (0, 2));
+
+			MaxSettlementsPerBlock::set(100);
+			run_to_block(3);
+			TickStream::on_initialize(3);
+			assert_eq!(TickStream::queued_tick_range(), (2, 2));
+		});
+	});
+}
+
+#[test]
+fn settlement_is_reported_stalled_while_viewers_go_unsettled() {
+	new_test_ext().execute_with(|| {
+		AlertAfterBlocks::set(5);
+		live_stream();
+		run_to_block(5);
+		TickStream::on_initialize(5);
+		// Nobody is watching, so there is nothing to settle
+		assert!(!TickStream::settlement_stalled());
+
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		run_to_block(6);
+		TickStream::on_initialize(6);
+		System::assert_last_event(Event::SettlementStalled { last_settled: 0 }.into());
+		assert_eq!(
+			TickStream::settlement_health(),
+			SettlementHealth { last_settled: 0, stalled: true }
+		);
+
+		// Settling a tick clears the alert in the next block
+		assert_ok!(record_tick(STREAM, VIEWER, 2));
+		run_to_block(7);
+		TickStream::on_initialize(7);
+		System::assert_last_event(Event::SettlementResumed { last_settled: 6 }.into());
+		assert!(!TickStream::settlement_stalled());
+
+		// As does the last viewer leaving
+		run_to_block(11);
+		TickStream::on_initialize(11);
+		assert!(TickStream::settlement_stalled());
+		assert_ok!(TickStream::end_stream(RuntimeOrigin::signed(CREATOR), STREAM));
+		run_to_block(12);
+		TickStream::on_initialize(12);
+		System::assert_last_event(Event::SettlementResumed { last_settled: 6 }.into());
+	});
+}
+
+#[test]
+fn polls_weigh_votes_by_watch_time_with_the_creator() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		let poll = |options: Vec<&[u8]>, duration, lookback_eras| {
+			TickStream::create_poll(
+				RuntimeOrigin::signed(CREATOR),
+				b"Next game?".to_vec(),
+				options.into_iter().map(|option| option.to_vec()).collect(),
+				duration,
+				lookback_eras,
+			)
+		};
+		assert_noop!(poll(vec![b"Chess"], 20, 1), Error::<Test>::InvalidPollOptions);
+		assert_noop!(poll(vec![b"Chess", b"Go"], 0, 1), Error::<Test>::InvalidPollWindow);
+		assert_noop!(poll(vec![b"Chess", b"Go"], 20, 3), Error::<Test>::InvalidPollWindow);
+		assert_noop!(poll(vec![b"Chess", &[b'x'; 33]], 20, 1), Error::<Test>::PollTextTooLong);
+		assert_ok!(poll(vec![b"Chess", b"Go"], 20, 1));
+		System::assert_last_event(
+			Event::PollCreated { poll_id: 0, creator: CREATOR, ends_at: 21 }.into(),
+		);
+
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(3), STREAM, 10, None));
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 3));
+		assert_ok!(record_tick(STREAM, 3, 5));
+
+		// Only viewers who watched the creator can vote, with their watch time
+		assert_noop!(
+			TickStream::vote(RuntimeOrigin::signed(4), 0, 0),
+			Error::<Test>::NoWatchTime
+		);
+		assert_noop!(
+			TickStream::vote(RuntimeOrigin::signed(VIEWER), 0, 2),
+			Error::<Test>::InvalidPollOption
+		);
+		assert_ok!(TickStream::vote(RuntimeOrigin::signed(VIEWER), 0, 0));
+		System::assert_last_event(
+			Event::PollVoted { poll_id: 0, voter: VIEWER, option: 0, weight: 3 }.into(),
+		);
+		assert_ok!(TickStream::vote(RuntimeOrigin::signed(3), 0, 1));
+
+		// A vote in the next era counts both eras' watch time and replaces the earlier one
+		run_to_block(12);
+		assert_ok!(record_tick(STREAM, VIEWER, 2));
+		assert_eq!(TickStream::poll_weight(&CREATOR, &VIEWER, 0), 2);
+		assert_ok!(TickStream::vote(RuntimeOrigin::signed(VIEWER), 0, 1));
+		assert_eq!(TickStream::poll_vote(0, VIEWER), Some((1, 5)));
+		assert_eq!(TickStream::polls(0).unwrap().tallies.to_vec(), vec![0, 10]);
+
+		run_to_block(21);
+		TickStream::on_initialize(21);
+		System::assert_last_event(
+			Event::PollFinalized { poll_id: 0, tallies: vec![0, 10], winner: Some(1) }.into(),
+		);
+		assert_eq!(TickStream::polls(0).unwrap().status, PollStatus::Finalized { winner: Some(1) });
+		assert_noop!(
+			TickStream::vote(RuntimeOrigin::signed(3), 0, 0),
+			Error::<Test>::PollClosed
+		);
+		assert_noop!(TickStream::vote(RuntimeOrigin::signed(3), 1, 0), Error::<Test>::PollNotFound);
+	});
+}
+
+#[test]
+fn giveaways_are_drawn_verifiably_among_active_viewers() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		Balances::make_free_balance_be(&4, 1_000);
+		for viewer in [VIEWER, 3, 4] {
+			assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(viewer), STREAM, 10, None));
+		}
+		assert_noop!(
+			TickStream::run_giveaway(RuntimeOrigin::signed(CREATOR), STREAM, 100),
+			Error::<Test>::NoEligibleViewers
+		);
+
+		// Viewer 4 has yet to be charged, so only the other two are watching
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 1));
+		assert_ok!(record_tick(STREAM, 3, 1));
+		assert_noop!(
+			TickStream::run_giveaway(RuntimeOrigin::signed(VIEWER), STREAM, 100),
+			Error::<Test>::NotStreamCreator
+		);
+		assert_noop!(
+			TickStream::run_giveaway(RuntimeOrigin::signed(CREATOR), STREAM, 0),
+			Error::<Test>::NoPrize
+		);
+
+		let creator_before = Balances::free_balance(CREATOR);
+		let viewers_before = [VIEWER, 3].map(Balances::free_balance);
+		assert_ok!(TickStream::run_giveaway(RuntimeOrigin::signed(CREATOR), STREAM, 100));
+		let giveaway = TickStream::giveaways(0).unwrap();
+		System::assert_last_event(
+			Event::GiveawayDrawn {
+				giveaway_id: 0,
+				stream_id: STREAM,
+				winner: giveaway.winner,
+				prize: 100,
+				seed: giveaway.seed,
+				entrants: 2,
+			}
+			.into(),
+		);
+
+		// Anyone can redraw the winner from the seed and the entrants
+		assert_eq!(giveaway.entrants_hash, BlakeTwo256::hash_of(&vec![VIEWER, 3]));
+		let winner = crate::select_winner(giveaway.seed.as_ref(), &[3, VIEWER]);
+		assert_eq!(winner, Some(giveaway.winner));
+		assert_eq!(Balances::free_balance(CREATOR), creator_before - 100);
+		let index = [VIEWER, 3].iter().position(|viewer| *viewer == giveaway.winner).unwrap();
+		assert_eq!(Balances::free_balance(giveaway.winner), viewers_before[index] + 100);
+	});
+}
+
+#[test]
+fn handles_are_unique_regardless_of_case() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0));
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(3), PRICE, None, 0));
+		let handle = |name: &[u8]| BoundedVec::truncate_from(name.to_vec());
+
+		assert_ok!(TickStream::claim_handle(
+			RuntimeOrigin::signed(CREATOR),
+			STREAM,
+			handle(b"AliceGaming")
+		));
+		assert_eq!(Balances::reserved_balance(CREATOR), 50);
+		let claim = TickStream::resolve_handle(b"alicegaming").unwrap();
+		assert_eq!(claim.handle, b"AliceGaming".to_vec());
+		assert_eq!((claim.owner, claim.stream_id), (CREATOR, STREAM));
+		let details = TickStream::stream_details(STREAM).unwrap();
+		assert_eq!(details.handle, Some(b"AliceGaming".to_vec()));
+
+		// Copycats differing in case, and handles that are not plain words, are turned away
+		assert_noop!(
+			TickStream::claim_handle(RuntimeOrigin::signed(3), STREAM + 2, handle(b"ALICEGAMING")),
+			Error::<Test>::HandleTaken
+		);
+		assert_noop!(
+			TickStream::claim_handle(RuntimeOrigin::signed(3), STREAM + 2, handle(b"Alice Gaming")),
+			Error::<Test>::InvalidHandle
+		);
+		assert_noop!(
+			TickStream::claim_handle(RuntimeOrigin::signed(3), STREAM, handle(b"copycat")),
+			Error::<Test>::NotStreamCreator
+		);
+
+		// The owner moves the handle to their next stream on the same deposit
+		assert_ok!(TickStream::claim_handle(
+			RuntimeOrigin::signed(CREATOR),
+			STREAM + 1,
+			handle(b"alicegaming")
+		));
+		assert_eq!(Balances::reserved_balance(CREATOR), 50);
+		assert_eq!(TickStream::stream_handle(STREAM), None);
+		assert_eq!(TickStream::resolve_handle(b"AliceGaming").unwrap().stream_id, STREAM + 1);
+
+		assert_ok!(TickStream::release_handle(RuntimeOrigin::signed(CREATOR), STREAM + 1));
+		assert_eq!(Balances::reserved_balance(CREATOR), 0);
+		assert_eq!(TickStream::resolve_handle(b"alicegaming"), None);
+		assert_noop!(
+			TickStream::release_handle(RuntimeOrigin::signed(CREATOR), STREAM + 1),
+			Error::<Test>::NoHandle
+		);
+	});
+}
+
+#[test]
+fn accounts_link_to_one_did_at_a_time() {
+	new_test_ext().execute_with(|| {
+		let did = |did: &[u8]| DidOf::<Test>::truncate_from(did.to_vec());
+		let (hash, signature) = ([1; 32], [2; 64]);
+		for invalid in [&b"did:plc:alice"[..], b"did:web:", b"alice.example"] {
+			assert_noop!(
+				TickStream::link_did(RuntimeOrigin::signed(CREATOR), did(invalid), hash, signature),
+				Error::<Test>::InvalidDid
+			);
+		}
+
+		let web = did(b"did:web:alice.example");
+		assert_ok!(TickStream::link_did(
+			RuntimeOrigin::signed(CREATOR),
+			web.clone(),
+			hash,
+			signature
+		));
+		System::assert_last_event(
+			Event::DidLinked { who: CREATOR, did: web.clone(), document_hash: hash }.into(),
+		);
+		assert_eq!(
+			TickStream::did_link_of(&CREATOR),
+			Some(DidLink {
+				did: b"did:web:alice.example".to_vec(),
+				document_hash: hash,
+				signature,
+				linked_at: 1,
+			})
+		);
+		assert_eq!(TickStream::resolve_did(b"did:web:alice.example"), Some(CREATOR));
+		assert_noop!(
+			TickStream::link_did(RuntimeOrigin::signed(VIEWER), web.clone(), hash, signature),
+			Error::<Test>::DidTaken
+		);
+
+		// Relinking frees the DID linked before
+		let key = did(b"did:key:z6MkAlice");
+		assert_ok!(TickStream::link_did(RuntimeOrigin::signed(CREATOR), key, [3; 32], [4; 64]));
+		assert_eq!(TickStream::resolve_did(b"did:web:alice.example"), None);
+		assert_ok!(TickStream::link_did(RuntimeOrigin::signed(VIEWER), web, hash, signature));
+
+		assert_ok!(TickStream::unlink_did(RuntimeOrigin::signed(CREATOR)));
+		assert_eq!(TickStream::did_link(CREATOR), None);
+		assert_eq!(TickStream::resolve_did(b"did:key:z6MkAlice"), None);
+		assert_noop!(
+			TickStream::unlink_did(RuntimeOrigin::signed(CREATOR)),
+			Error::<Test>::NoDidLink
+		);
+	});
+}
+
+#[test]
+fn sessions_pause_at_the_viewers_spending_limit() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		assert_ok!(TickStream::set_spending_limit(
+			RuntimeOrigin::signed(VIEWER),
+			Some(3 * PRICE),
+			None
+		));
+
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 2));
+		assert_eq!(TickStream::balances(STREAM, VIEWER), 80);
+
+		// Two more seconds would go over the limit, so they are not charged
+		run_to_block(3);
+		assert_ok!(record_tick(STREAM, VIEWER, 2));
+		System::assert_last_event(
+			Event::SpendingLimitReached { stream_id: STREAM, viewer: VIEWER }.into(),
+		);
+		assert_eq!(TickStream::balances(STREAM, VIEWER), 80);
+		assert!(TickStream::viewer_session(STREAM, &VIEWER).unwrap().paused);
+		assert!(TickStream::due_ticks(u32::MAX).is_empty());
+		assert_noop!(
+			record_tick(STREAM, VIEWER, 1),
+			Error::<Test>::SessionPaused
+		);
+
+		// Resuming starts the limit over without charging for the pause
+		assert_ok!(TickStream::resume_session(RuntimeOrigin::signed(VIEWER), STREAM));
+		assert_noop!(
+			TickStream::resume_session(RuntimeOrigin::signed(VIEWER), STREAM),
+			Error::<Test>::SessionNotPaused
+		);
+		run_to_block(4);
+		assert_noop!(
+			record_tick(STREAM, VIEWER, 7),
+			Error::<Test>::TooManyTicks
+		);
+		assert_ok!(record_tick(STREAM, VIEWER, 3));
+		assert_eq!(TickStream::balances(STREAM, VIEWER), 50);
+	});
+}
+
+#[test]
+fn resuming_a_session_keeps_the_daily_limit_of_every_stream() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0));
+		assert_ok!(TickStream::start_stream(RuntimeOrigin::signed(CREATOR), 1));
+		for stream_id in [STREAM, 1] {
+			assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), stream_id, 10, None));
+		}
+		assert_ok!(TickStream::set_spending_limit(
+			RuntimeOrigin::signed(VIEWER),
+			None,
+			Some(5 * PRICE)
+		));
+
+		// Three more seconds on the second stream would go over the day's limit
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 3));
+		assert_ok!(record_tick(1, VIEWER, 3));
+		System::assert_last_event(
+			Event::SpendingLimitReached { stream_id: 1, viewer: VIEWER }.into(),
+		);
+
+		// Resuming leaves what was spent today counted, on both streams
+		assert_ok!(TickStream::resume_session(RuntimeOrigin::signed(VIEWER), 1));
+		run_to_block(3);
+		assert_ok!(record_tick(1, VIEWER, 2));
+		assert_eq!(TickStream::balances(1, VIEWER), 80);
+		assert_ok!(record_tick(STREAM, VIEWER, 1));
+		System::assert_last_event(
+			Event::SpendingLimitReached { stream_id: STREAM, viewer: VIEWER }.into(),
+		);
+		assert_eq!(TickStream::balances(STREAM, VIEWER), 70);
+
+		// The limit starts over the next day
+		assert_ok!(TickStream::resume_session(RuntimeOrigin::signed(VIEWER), STREAM));
+		run_to_block(86_400_000 / MILLISECS_PER_BLOCK + 1);
+		assert_ok!(record_tick(STREAM, VIEWER, 5));
+		assert_eq!(TickStream::balances(STREAM, VIEWER), 20);
+	});
+}
+
+#[test]
+fn sessions_move_through_explicit_states() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		let changed = |from, to| {
+			System::assert_has_event(
+				Event::SessionStateChanged { stream_id: STREAM, viewer: VIEWER, from, to }.into(),
+			)
+		};
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		changed(None, SessionState::Reserved);
+		assert_eq!(TickStream::session_state(STREAM, &VIEWER), Some(SessionState::Reserved));
+
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 2));
+		changed(Some(SessionState::Reserved), SessionState::Active);
+
+		// A spending limit pauses the session until the viewer resumes it
+		assert_ok!(TickStream::set_spending_limit(
+			RuntimeOrigin::signed(VIEWER),
+			Some(2 * PRICE),
+			None
+		));
+		run_to_block(3);
+		assert_ok!(record_tick(STREAM, VIEWER, 2));
+		changed(Some(SessionState::Active), SessionState::GracePaused);
+		assert_eq!(TickStream::session_state(STREAM, &VIEWER), Some(SessionState::GracePaused));
+		assert_ok!(TickStream::resume_session(RuntimeOrigin::signed(VIEWER), STREAM));
+		changed(Some(SessionState::GracePaused), SessionState::Active);
+
+		// Ending the stream expires the session, and the refund settles it
+		assert_ok!(TickStream::end_stream(RuntimeOrigin::signed(CREATOR), STREAM));
+		changed(Some(SessionState::Expired), SessionState::Settled);
+		assert_eq!(TickStream::session_state(STREAM, &VIEWER), None);
+
+		assert!(SessionState::Expired.can_transition_to(&SessionState::Settled));
+		assert!(!SessionState::Expired.can_transition_to(&SessionState::Active));
+		assert!(!SessionState::Settled.can_transition_to(&SessionState::Reserved));
+	});
+}
+
+#[test]
+fn sessions_get_their_state_on_upgrade() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		Balances::make_free_balance_be(&4, 1_000);
+		for viewer in [VIEWER, 3, 4] {
+			assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(viewer), STREAM, 10, None));
+		}
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, 3, 1));
+		assert_ok!(record_tick(STREAM, 4, 1));
+		// Sessions as version 8 left them, with viewer 4 paused on a spending limit
+		let _ = crate::SessionStates::<Test>::clear(u32::MAX, None);
+		migrations::v8::PausedSessions::<Test>::insert(STREAM, 4, ());
+		StorageVersion::new(8).put::<TickStream>();
+
+		migrations::MigrateToV9::<Test>::on_runtime_upgrade();
+		assert_eq!(TickStream::on_chain_storage_version(), 9);
+		assert_eq!(TickStream::session_state(STREAM, &VIEWER), Some(SessionState::Reserved));
+		assert_eq!(TickStream::session_state(STREAM, &3), Some(SessionState::Active));
+		assert_eq!(TickStream::session_state(STREAM, &4), Some(SessionState::GracePaused));
+		assert!(!migrations::v8::PausedSessions::<Test>::contains_key(STREAM, 4));
+	});
+}
+
+#[test]
+fn viewers_accounts_outlive_their_sessions() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0));
+		assert_ok!(TickStream::start_stream(RuntimeOrigin::signed(CREATOR), 1));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		let consumers = System::consumers(&VIEWER);
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), 1, 10, None));
+
+		// Both sessions share one reference, which keeps the account from being reaped
+		assert_eq!(TickStream::open_sessions(VIEWER), 2);
+		assert_eq!(System::consumers(&VIEWER), consumers);
+		assert_eq!(System::dec_providers(&VIEWER), Err(DispatchError::ConsumerRemaining));
+		assert_ok!(Balances::transfer_all(RuntimeOrigin::signed(VIEWER), 3, false));
+		assert!(System::account_exists(&VIEWER));
+
+		assert_ok!(TickStream::end_stream(RuntimeOrigin::signed(CREATOR), 1));
+		assert_eq!(TickStream::open_sessions(VIEWER), 1);
+		assert_ok!(TickStream::end_stream(RuntimeOrigin::signed(CREATOR), STREAM));
+		assert_eq!(TickStream::open_sessions(VIEWER), 0);
+		assert_eq!(System::consumers(&VIEWER), 0);
+	});
+}
+
+#[test]
+fn open_sessions_reference_their_accounts_on_upgrade() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		let consumers = System::consumers(&VIEWER);
+		// Sessions as version 9 left them, without a reference
+		crate::OpenSessions::<Test>::remove(VIEWER);
+		System::dec_consumers(&VIEWER);
+		StorageVersion::new(9).put::<TickStream>();
+
+		migrations::MigrateToV10::<Test>::on_runtime_upgrade();
+		assert_eq!(TickStream::on_chain_storage_version(), 10);
+		assert_eq!(TickStream::open_sessions(VIEWER), 1);
+		assert_eq!(System::consumers(&VIEWER), consumers);
+	});
+}
+
+#[test]
+fn ending_an_unreferenced_session_keeps_other_consumers() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		// A session the upgrade could not reference, on an account another pallet consumes
+		crate::OpenSessions::<Test>::remove(VIEWER);
+		System::dec_consumers(&VIEWER);
+		assert_ok!(System::inc_consumers(&VIEWER));
+
+		assert_ok!(TickStream::end_stream(RuntimeOrigin::signed(CREATOR), STREAM));
+		assert_eq!(TickStream::open_sessions(VIEWER), 0);
+		assert_eq!(System::consumers(&VIEWER), 1);
+	});
+}
+
+#[test]
+fn viewers_are_charged_once_the_free_preview_is_watched() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 5));
+		assert_ok!(TickStream::start_stream(RuntimeOrigin::signed(CREATOR), STREAM));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 4));
+		assert_eq!(TickStream::balances(STREAM, VIEWER), 100);
+
+		// One second of the preview is left
+		run_to_block(3);
+		assert_ok!(record_tick(STREAM, VIEWER, 3));
+		assert_eq!(TickStream::balances(STREAM, VIEWER), 100 - 2 * PRICE);
+
+		// A longer preview only frees the seconds the viewer has not watched yet
+		assert_noop!(
+			TickStream::set_free_preview(RuntimeOrigin::signed(VIEWER), STREAM, 10),
+			Error::<Test>::NotStreamCreator
+		);
+		assert_ok!(TickStream::set_free_preview(RuntimeOrigin::signed(CREATOR), STREAM, 10));
+		run_to_block(4);
+		assert_ok!(record_tick(STREAM, VIEWER, 6));
+		assert_eq!(TickStream::balances(STREAM, VIEWER), 100 - 3 * PRICE);
+		assert_eq!(TickStream::free_preview(STREAM), 10);
+	});
+}
+
+#[test]
+fn the_first_seconds_after_joining_are_not_charged() {
+	new_test_ext().execute_with(|| {
+		FirstChargeGraceSeconds::set(3);
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		let free_left = || TickStream::viewer_session(STREAM, &VIEWER).unwrap().free_seconds_left;
+		assert_eq!(free_left(), 3);
+
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 2));
+		assert_eq!(TickStream::balances(STREAM, VIEWER), 100);
+		assert_eq!(free_left(), 1);
+
+		// Only the second of grace left is free
+		run_to_block(3);
+		assert_ok!(record_tick(STREAM, VIEWER, 4));
+		assert_eq!(TickStream::balances(STREAM, VIEWER), 100 - 3 * PRICE);
+		assert_eq!(free_left(), 0);
+	});
+}
+
+#[test]
+fn viewers_watch_on_as_many_devices_as_the_creator_allows() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_noop!(
+			TickStream::open_session(RuntimeOrigin::signed(VIEWER), STREAM, 1),
+			Error::<Test>::Unauthorized
+		);
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+
+		// One device at a time unless the creator says otherwise
+		assert_ok!(TickStream::open_session(RuntimeOrigin::signed(VIEWER), STREAM, 1));
+		assert_noop!(
+			TickStream::open_session(RuntimeOrigin::signed(VIEWER), STREAM, 1),
+			Error::<Test>::SessionAlreadyOpen
+		);
+		assert_noop!(
+			TickStream::open_session(RuntimeOrigin::signed(VIEWER), STREAM, 2),
+			Error::<Test>::TooManyDevices
+		);
+		assert_ok!(TickStream::close_session(RuntimeOrigin::signed(VIEWER), STREAM, 1));
+		assert_ok!(TickStream::open_session(RuntimeOrigin::signed(VIEWER), STREAM, 2));
+
+		assert_noop!(
+			TickStream::set_device_limit(RuntimeOrigin::signed(CREATOR), STREAM, 4),
+			Error::<Test>::InvalidDeviceLimit
+		);
+		assert_ok!(TickStream::set_device_limit(RuntimeOrigin::signed(CREATOR), STREAM, 2));
+		assert_ok!(TickStream::open_session(RuntimeOrigin::signed(VIEWER), STREAM, 3));
+		assert_noop!(
+			TickStream::open_session(RuntimeOrigin::signed(VIEWER), STREAM, 4),
+			Error::<Test>::TooManyDevices
+		);
+
+		// Exempt viewers are only held to `MaxDevicesPerViewer`
+		assert_ok!(TickStream::set_device_exemption(
+			RuntimeOrigin::signed(CREATOR),
+			STREAM,
+			VIEWER,
+			true
+		));
+		assert_ok!(TickStream::open_session(RuntimeOrigin::signed(VIEWER), STREAM, 4));
+		assert_eq!(TickStream::active_sessions(STREAM, VIEWER).into_inner(), vec![2, 3, 4]);
+
+		assert_ok!(TickStream::end_stream(RuntimeOrigin::signed(CREATOR), STREAM));
+		assert!(TickStream::active_sessions(STREAM, VIEWER).is_empty());
+	});
+}
+
+#[test]
+fn streams_and_platform_parameters_are_seeded_at_genesis() {
+	let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	GenesisConfig::<Test> {
+		streams: vec![
+			GenesisStream {
+				creator: CREATOR,
+				price_per_second: PRICE,
+				metadata: Some((b"Chess".to_vec(), StreamCategory::Gaming, b"cid".to_vec())),
+			},
+			GenesisStream { creator: VIEWER, price_per_second: 3, metadata: None },
+		],
+		platform_fee: Permill::from_percent(10),
+		tick_threshold: Some(5),
+		reporters: vec![],
+		randomness_seed: Some([7; 32]),
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+
+	sp_io::TestExternalities::from(storage).execute_with(|| {
+		let stream = TickStream::streams(0).unwrap();
+		assert_eq!((stream.creator, stream.price_per_second), (CREATOR, PRICE));
+		assert_eq!(stream.status, StreamStatus::Created);
+		let metadata = TickStream::stream_metadata(0).unwrap();
+		assert_eq!(metadata.title, b"Chess".to_vec());
+		assert_eq!(metadata.category, StreamCategory::Gaming);
+		assert_eq!(TickStream::streams(1).unwrap().creator, VIEWER);
+		assert!(TickStream::stream_metadata(1).is_none());
+		assert_eq!(TickStream::next_stream_id(), 2);
+
+		assert_eq!(TickStream::platform_fee(), Permill::from_percent(10));
+		assert_eq!(TickStream::tick_threshold(), 5);
+		assert_eq!(TickStream::randomness_seed(), Some([7; 32]));
+	});
+}
+
+#[test]
+fn only_bonded_reporters_can_report_ticks() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 20, None));
+		run_to_block(2);
+
+		// Ticks signed by anyone but a registered reporter, or replayed, are refused
+		let payload = TickStream::tick_payload(STREAM, &VIEWER, 6, 1);
+		let forged = crate::Call::<Test>::record_tick {
+			stream_id: STREAM,
+			viewer: VIEWER,
+			ticks: 6,
+			reporter: 3,
+			nonce: 1,
+			signature: TestSignature(3, payload.clone()),
+		};
+		assert_eq!(
+			TickStream::validate_unsigned(TransactionSource::External, &forged),
+			Err(InvalidTransaction::Custom(72).into())
+		);
+		assert_noop!(
+			TickStream::record_tick(
+				RuntimeOrigin::none(),
+				STREAM,
+				VIEWER,
+				6,
+				REPORTER,
+				1,
+				TestSignature(3, payload)
+			),
+			Error::<Test>::InvalidReporterSignature
+		);
+		assert_ok!(record_tick(STREAM, VIEWER, 6));
+		run_to_block(3);
+		let payload = TickStream::tick_payload(STREAM, &VIEWER, 6, 1);
+		assert_noop!(
+			TickStream::record_tick(
+				RuntimeOrigin::none(),
+				STREAM,
+				VIEWER,
+				6,
+				REPORTER,
+				1,
+				TestSignature(REPORTER, payload)
+			),
+			Error::<Test>::StaleReporterNonce
+		);
+
+		// Registering reserves the bond
+		assert_ok!(TickStream::register_reporter(RuntimeOrigin::signed(3)));
+		assert_noop!(
+			TickStream::register_reporter(RuntimeOrigin::signed(3)),
+			Error::<Test>::ReporterAlreadyRegistered
+		);
+		assert_eq!(Balances::reserved_balance(3), 100);
+		let payload = TickStream::tick_payload(STREAM, &VIEWER, 6, 1);
+		assert_ok!(TickStream::record_tick(
+			RuntimeOrigin::none(),
+			STREAM,
+			VIEWER,
+			6,
+			3,
+			1,
+			TestSignature(3, payload)
+		));
+
+		// Deregistering ends reporting at once and returns the bond after the cooldown
+		assert_ok!(TickStream::deregister_reporter(RuntimeOrigin::signed(3)));
+		System::assert_last_event(Event::ReporterDeregistered { who: 3, unbonds_at: 13 }.into());
+		run_to_block(4);
+		let payload = TickStream::tick_payload(STREAM, &VIEWER, 6, 2);
+		assert_noop!(
+			TickStream::record_tick(
+				RuntimeOrigin::none(),
+				STREAM,
+				VIEWER,
+				6,
+				3,
+				2,
+				TestSignature(3, payload)
+			),
+			Error::<Test>::NotReporter
+		);
+		assert_noop!(
+			TickStream::deregister_reporter(RuntimeOrigin::signed(3)),
+			Error::<Test>::ReporterCoolingDown
+		);
+		run_to_block(13);
+		assert_ok!(TickStream::deregister_reporter(RuntimeOrigin::signed(3)));
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert!(TickStream::reporter(3).is_none());
+	});
+}
+
+#[test]
+fn legacy_calls_announce_their_versioned_replacement() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		System::assert_has_event(
+			Event::DeprecatedCallUsed { who: VIEWER, call: LegacyCall::JoinStream }.into(),
+		);
+
+		// The versioned call bounds the price the viewer joins at
+		System::reset_events();
+		assert_noop!(
+			TickStream::join_stream_v2(RuntimeOrigin::signed(3), STREAM, 10, None, Some(PRICE - 1)),
+			Error::<Test>::PriceAboveLimit
+		);
+		assert_ok!(TickStream::join_stream_v2(
+			RuntimeOrigin::signed(3),
+			STREAM,
+			10,
+			None,
+			Some(PRICE)
+		));
+		System::assert_last_event(Event::ViewerJoined { stream_id: STREAM, viewer: 3 }.into());
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::TickStream(Event::DeprecatedCallUsed { .. })
+		)));
+	});
+}
+
+#[test]
+fn governance_can_move_a_stream_to_a_new_id() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, Some(3)));
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 6));
+		let reserved = TickStream::balances(STREAM, VIEWER);
+		let escrowed = Balances::free_balance(TickStream::escrow_account(STREAM));
+
+		assert_noop!(
+			TickStream::migrate_stream(RuntimeOrigin::signed(CREATOR), STREAM, 5, 100),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			TickStream::migrate_stream(RuntimeOrigin::root(), STREAM, 5, 0),
+			Error::<Test>::MigrationWitnessTooLow
+		);
+		assert_ok!(TickStream::migrate_stream(RuntimeOrigin::root(), STREAM, 5, 100));
+		System::assert_last_event(Event::StreamMigrated { old_id: STREAM, new_id: 5 }.into());
+
+		// The stream, its viewers and its escrowed earnings all moved
+		assert_eq!(TickStream::streams(STREAM), None);
+		assert_eq!(TickStream::streams(5).map(|stream| stream.creator), Some(CREATOR));
+		assert_eq!(TickStream::stream_viewers(5), vec![VIEWER]);
+		assert_eq!(TickStream::balances(5, VIEWER), reserved);
+		assert_eq!(TickStream::escrow(5, CREATOR), 54);
+		assert_eq!(Balances::free_balance(TickStream::escrow_account(5)), escrowed);
+		assert_eq!(Balances::free_balance(TickStream::escrow_account(STREAM)), 0);
+		assert_eq!(TickStream::migrated_stream(STREAM), Some(5));
+		assert_noop!(
+			TickStream::migrate_stream(RuntimeOrigin::root(), STREAM, 6, 100),
+			Error::<Test>::StreamNotFound
+		);
+
+		// Viewers keep watching under the new id, and new streams come after it
+		run_to_block(3);
+		assert_ok!(record_tick(5, VIEWER, 2));
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0));
+		System::assert_last_event(
+			Event::StreamCreated { stream_id: 6, creator: CREATOR, price_per_second: PRICE }.into(),
+		);
+
+		// Referral earnings made before the move are claimed with the old id
+		assert_ok!(TickStream::claim_referral_earnings(RuntimeOrigin::signed(3), STREAM));
+		assert_eq!(Balances::free_balance(3), 1_006);
+	});
+}
+
+#[test]
+fn on_demand_streams_are_billed_once_per_second_played() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0));
+		assert_noop!(
+			TickStream::set_content_type(RuntimeOrigin::signed(VIEWER), STREAM, ContentType::Vod),
+			Error::<Test>::NotStreamCreator
+		);
+		assert_ok!(TickStream::set_content_type(
+			RuntimeOrigin::signed(CREATOR),
+			STREAM,
+			ContentType::Vod
+		));
+		System::assert_last_event(
+			Event::ContentTypeSet { stream_id: STREAM, content_type: ContentType::Vod }.into(),
+		);
+		assert_ok!(TickStream::start_stream(RuntimeOrigin::signed(CREATOR), STREAM));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 50, None));
+
+		// Watch time is reported by the viewer's player, not by tick reporters
+		run_to_block(2);
+		assert_noop!(record_tick(STREAM, VIEWER, 6), Error::<Test>::BilledByPlayback);
+		assert_ok!(TickStream::report_playback(RuntimeOrigin::signed(VIEWER), STREAM, 6));
+		System::assert_has_event(
+			Event::TickRecorded {
+				stream_id: STREAM,
+				viewer: VIEWER,
+				ticks: 6,
+				amount: 6 * PRICE,
+				fiat_value: None,
+			}
+			.into(),
+		);
+		assert_eq!(TickStream::playback_position(STREAM, VIEWER), 6);
+
+		// Seeking back replays seconds already paid for
+		run_to_block(3);
+		System::reset_events();
+		assert_ok!(TickStream::report_playback(RuntimeOrigin::signed(VIEWER), STREAM, 3));
+		assert!(System::events().is_empty());
+		assert_eq!(TickStream::playback_position(STREAM, VIEWER), 6);
+
+		run_to_block(4);
+		assert_ok!(TickStream::report_playback(RuntimeOrigin::signed(VIEWER), STREAM, 8));
+		assert_eq!(TickStream::tick_count(STREAM), 8);
+		assert_eq!(TickStream::balances(STREAM, VIEWER), 42 * PRICE);
+
+		// Live streams keep being billed by ticks
+		assert_ok!(TickStream::create_stream(RuntimeOrigin::signed(CREATOR), PRICE, None, 0));
+		assert_ok!(TickStream::start_stream(RuntimeOrigin::signed(CREATOR), 1));
+		assert_noop!(
+			TickStream::report_playback(RuntimeOrigin::signed(VIEWER), 1, 6),
+			Error::<Test>::NotOnDemand
+		);
+		assert_noop!(
+			TickStream::set_content_type(RuntimeOrigin::signed(CREATOR), 1, ContentType::Vod),
+			Error::<Test>::InvalidStatusTransition
+		);
+	});
+}
+
+#[test]
+fn settlement_extensions_see_each_settlement_before_funds_move() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 20, None));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(3), STREAM, 20, None));
+		TestSettlementExtension::set_budget(Some(8 * PRICE));
+
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 6));
+		assert_eq!(
+			TestSettlementExtension::settlements(),
+			vec![SettlementContext {
+				stream_id: STREAM,
+				creator: CREATOR,
+				viewer: VIEWER,
+				seconds: 6,
+				from_second: 0,
+				to_second: 6,
+				price_per_second: PRICE,
+				amount: 6 * PRICE,
+			}]
+		);
+
+		// A rejected settlement moves nothing
+		let reserved = TickStream::balances(STREAM, 3);
+		assert_noop!(
+			record_tick(STREAM, 3, 6),
+			DispatchError::Other("over the purchase-order budget")
+		);
+
+		// and is skipped within a batch
+		run_to_block(3);
+		let ticks = BoundedVec::truncate_from(vec![(3, 6), (VIEWER, 2)]);
+		assert_ok!(batch_tick(STREAM, ticks));
+		System::assert_has_event(Event::TickSkipped { stream_id: STREAM, viewer: 3 }.into());
+		assert_eq!(TickStream::balances(STREAM, 3), reserved);
+		assert_eq!(TickStream::watched_seconds(STREAM, VIEWER), 8);
+		assert_eq!(TestSettlementExtension::settlements().len(), 2);
+	});
+}
+
+#[test]
+fn what_the_pallet_holds_is_capped_across_all_accounts() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		let cap = Some(100 * PRICE);
+		assert_noop!(
+			TickStream::set_global_hold_cap(RuntimeOrigin::signed(CREATOR), cap),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(TickStream::set_global_hold_cap(RuntimeOrigin::root(), cap));
+		System::assert_last_event(Event::GlobalHoldCapSet { cap }.into());
+
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 60, None));
+		assert_noop!(
+			TickStream::join_stream(RuntimeOrigin::signed(3), STREAM, 50, None),
+			Error::<Test>::GlobalCapReached
+		);
+		assert_noop!(
+			TickStream::top_up_budget(RuntimeOrigin::signed(3), CREATOR, 41 * PRICE),
+			Error::<Test>::GlobalCapReached
+		);
+		assert_ok!(TickStream::top_up_budget(RuntimeOrigin::signed(3), CREATOR, 40 * PRICE));
+		assert_eq!(TickStream::hold_utilization(), HoldUtilization { held: 100 * PRICE, cap });
+
+		// Released funds make room again, and lifting the cap lets anything be held
+		assert_ok!(TickStream::release_budget(RuntimeOrigin::signed(3), CREATOR));
+		assert_eq!(TickStream::total_held(), 60 * PRICE);
+		assert_ok!(TickStream::set_global_hold_cap(RuntimeOrigin::root(), None));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(3), STREAM, 50, None));
+		assert_eq!(TickStream::total_held(), 110 * PRICE);
+	});
+}
+
+#[test]
+fn the_council_sets_the_tick_threshold_and_batch_cap() {
+	new_test_ext().execute_with(|| {
+		let council = |ayes: u32| {
+			RuntimeOrigin::from(
+				pallet_collective::RawOrigin::<u64, pallet_collective::Instance1>::Members(ayes, 3),
+			)
+		};
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(3), STREAM, 10, None));
+
+		// Caps must fit within `MaxViewersPerBatch`, and a minority of the council sets none
+		assert_noop!(TickStream::set_batch_cap(council(1), Some(1)), DispatchError::BadOrigin);
+		assert_noop!(
+			TickStream::set_batch_cap(council(2), Some(0)),
+			Error::<Test>::InvalidBatchCap
+		);
+		assert_noop!(
+			TickStream::set_batch_cap(council(2), Some(5)),
+			Error::<Test>::InvalidBatchCap
+		);
+		assert_ok!(TickStream::set_batch_cap(council(2), Some(1)));
+		System::assert_last_event(Event::BatchCapSet { cap: Some(1) }.into());
+		assert_eq!(TickStream::batch_cap(), 1);
+
+		run_to_block(2);
+		let ticks = BoundedVec::truncate_from(vec![(VIEWER, 1), (3, 1)]);
+		assert_noop!(batch_tick(STREAM, ticks), Error::<Test>::BatchTooLarge);
+		assert_ok!(batch_tick(STREAM, BoundedVec::truncate_from(vec![(VIEWER, 1)])));
+
+		assert_noop!(TickStream::set_tick_threshold(council(1), Some(5)), DispatchError::BadOrigin);
+		assert_ok!(TickStream::set_tick_threshold(council(2), Some(5)));
+		System::assert_last_event(Event::TickThresholdSet { threshold: Some(5) }.into());
+		assert_eq!(TickStream::tick_threshold(), 5);
+
+		// Resetting them goes back to the runtime's constants
+		assert_ok!(TickStream::set_tick_threshold(council(2), None));
+		assert_ok!(TickStream::set_batch_cap(council(2), None));
+		assert_eq!(TickStream::tick_threshold(), 1);
+		assert_eq!(TickStream::batch_cap(), 4);
+	});
+}
+
+#[test]
+fn the_call_filter_mode_is_switched_by_the_admin() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(TickStream::call_filter_mode(), CallFilterMode::Normal);
+		let frozen = CallFilterMode::Frozen;
+		assert_noop!(
+			TickStream::set_call_filter_mode(RuntimeOrigin::signed(CREATOR), frozen),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(TickStream::set_call_filter_mode(RuntimeOrigin::root(), frozen));
+		System::assert_last_event(Event::CallFilterModeSet { mode: frozen }.into());
+		assert_eq!(TickStream::call_filter_mode(), frozen);
+	});
+}
+
+#[test]
+fn access_policies_decide_who_may_join() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		let policy = AccessPolicy::Allowlist;
+		assert_noop!(
+			TickStream::set_access_policy(RuntimeOrigin::signed(VIEWER), STREAM, policy.clone()),
+			Error::<Test>::NotStreamCreator
+		);
+		assert_ok!(TickStream::set_access_policy(
+			RuntimeOrigin::signed(CREATOR),
+			STREAM,
+			policy.clone()
+		));
+		System::assert_last_event(Event::AccessPolicySet { stream_id: STREAM, policy }.into());
+		assert_noop!(
+			TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None),
+			Error::<Test>::AccessDenied
+		);
+		assert_ok!(TickStream::add_to_allowlist(RuntimeOrigin::signed(CREATOR), STREAM, VIEWER));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+
+		// Removed viewers keep watching but cannot join again
+		assert_ok!(TickStream::remove_from_allowlist(
+			RuntimeOrigin::signed(CREATOR),
+			STREAM,
+			VIEWER
+		));
+		assert!(TickStream::stream_viewers(STREAM).contains(&VIEWER));
+		assert_noop!(
+			TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None),
+			Error::<Test>::AccessDenied
+		);
+
+		const USD: u32 = 7;
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), USD, CREATOR, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(CREATOR), USD, 3, 4));
+		let policy = AccessPolicy::TokenGated { asset_id: USD, min_balance: 5 };
+		assert_ok!(TickStream::set_access_policy(
+			RuntimeOrigin::signed(CREATOR),
+			STREAM,
+			policy.clone()
+		));
+		assert_eq!(TickStream::stream_details(STREAM).unwrap().access_policy, policy);
+		assert_noop!(
+			TickStream::join_stream(RuntimeOrigin::signed(3), STREAM, 10, None),
+			Error::<Test>::AccessDenied
+		);
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(CREATOR), USD, 3, 1));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(3), STREAM, 10, None));
+	});
+}
+
+#[test]
+fn viewers_who_run_out_are_charged_what_is_left_and_taken_off() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 2, None));
+		assert_ok!(TickStream::top_up_budget(RuntimeOrigin::signed(VIEWER), CREATOR, 15));
+
+		// Five seconds cost 50, but only the reserve of 20 and the budget of 15 are left
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 5));
+		System::assert_has_event(
+			Event::TickRecorded {
+				stream_id: STREAM,
+				viewer: VIEWER,
+				ticks: 5,
+				amount: 35,
+				fiat_value: None,
+			}
+			.into(),
+		);
+		System::assert_last_event(
+			Event::ViewerFundsExhausted {
+				stream_id: STREAM,
+				viewer: VIEWER,
+				charged: 35,
+				shortfall: 15,
+			}
+			.into(),
+		);
+		assert_eq!(TickStream::escrow(STREAM, CREATOR), 35);
+		assert_eq!(Balances::reserved_balance(VIEWER), 0);
+		assert_eq!(TickStream::creator_budget(VIEWER, CREATOR).reserved, 0);
+		assert!(!TickStream::stream_viewers(STREAM).contains(&VIEWER));
+
+		// Further ticks are refused before they are checked any further
+		run_to_block(3);
+		let call = tick_call(STREAM, VIEWER, 1);
+		assert_eq!(
+			TickStream::validate_unsigned(TransactionSource::External, &call),
+			Err(InvalidTransaction::Custom(1).into())
+		);
+	});
+}
+
+#[test]
+fn creator_grants_vest_on_milestones_and_lapse_back_to_the_platform() {
+	new_test_ext().execute_with(|| {
+		let platform = TickStream::platform_account();
+		Balances::make_free_balance_be(&platform, 1_000);
+		assert_noop!(
+			TickStream::approve_grant(RuntimeOrigin::root(), CREATOR, 0, 100, 1, 200, 10),
+			Error::<Test>::InvalidGrant
+		);
+		assert_ok!(TickStream::approve_grant(RuntimeOrigin::root(), CREATOR, 1, 100, 1, 200, 10));
+		System::assert_last_event(
+			Event::GrantApproved { creator: CREATOR, amount: 300, deadline: 11 }.into(),
+		);
+		assert_noop!(
+			TickStream::approve_grant(RuntimeOrigin::root(), CREATOR, 1, 100, 1, 200, 10),
+			Error::<Test>::GrantAlreadyApproved
+		);
+		let grant = HoldReason::CreatorGrant.into();
+		assert_eq!(Balances::balance_on_hold(&grant, &CREATOR), 300);
+		assert_eq!(Balances::free_balance(platform), 700);
+
+		// Taking the first stream live vests its tranche
+		live_stream();
+		System::assert_has_event(
+			Event::GrantMilestoneReached {
+				creator: CREATOR,
+				milestone: GrantMilestone::Streams,
+				amount: 100,
+			}
+			.into(),
+		);
+		assert_eq!(Balances::balance_on_hold(&grant, &CREATOR), 200);
+
+		// Paid watch time counts towards the viewer-hours, which fall short of an hour
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		run_to_block(2);
+		assert_ok!(record_tick(STREAM, VIEWER, 5));
+		assert_eq!(TickStream::creator_grant(CREATOR).unwrap().paid_seconds, 5);
+
+		// What has not vested by the deadline goes back to the platform
+		run_to_block(11);
+		TickStream::on_initialize(11);
+		System::assert_has_event(Event::GrantClawedBack { creator: CREATOR, amount: 200 }.into());
+		assert_eq!(Balances::balance_on_hold(&grant, &CREATOR), 0);
+		assert_eq!(Balances::free_balance(platform), 900);
+		assert!(TickStream::creator_grant(CREATOR).is_none());
+	});
+}
+
+#[test]
+fn batches_refund_the_weight_of_what_they_skip() {
+	new_test_ext().execute_with(|| {
+		live_stream();
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 10, None));
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(3), STREAM, 10, None));
+		run_to_block(2);
+
+		// Account 4 is not watching, and the viewer's second entry is already settled
+		let ticks = vec![(VIEWER, 1), (3, 1), (4, 1), (VIEWER, 1)];
+		let call = batch_call(STREAM, BoundedVec::truncate_from(ticks));
+		let declared = call.get_dispatch_info().weight;
+		let post = call.dispatch_bypass_filter(RuntimeOrigin::none()).unwrap();
+		let settled = <Test as crate::Config>::WeightInfo::batch_tick(2)
+			.saturating_add(TestSettlementExtension::weight().saturating_mul(2));
+		assert_eq!(post.actual_weight, Some(settled));
+		assert!(settled.all_lt(declared));
+		assert_eq!(TickStream::get_tick_count(STREAM), 2);
+
+		// Only the streams the account was watching are weighed as revoked
+		let streams = BoundedVec::truncate_from(vec![STREAM, 1, 2]);
+		let post = TickStream::force_leave_account(RuntimeOrigin::root(), VIEWER, streams).unwrap();
+		assert_eq!(
+			post.actual_weight,
+			Some(<Test as crate::Config>::WeightInfo::force_leave_account(1))
+		);
+	});
+}
+
+#[test]
+fn attestations_from_other_chains_count_towards_join_gates() {
+	new_test_ext().execute_with(|| {
+		const PARTNER: u32 = 2_000;
+		const PARTNER_SIGNER: u64 = 7;
+		let attestation = |watch_seconds, verification_level, nonce, expires_at| ViewerAttestation {
+			source: PARTNER,
+			watch_seconds,
+			verification_level,
+			nonce,
+			expires_at,
+		};
+		let signed = |signer, who, attestation: ViewerAttestation<u64>| {
+			let payload = TickStream::attestation_payload(&who, &attestation);
+			let signature = TestSignature(signer, payload);
+			TickStream::import_attestation(RuntimeOrigin::signed(3), who, attestation, signature)
+		};
+		let import = |who, attestation| signed(PARTNER_SIGNER, who, attestation);
+
+		// Identities can only be required once a chain that verifies them is registered
+		live_stream();
+		let gate = JoinGate { min_account_age: 0, min_watch_seconds: 100, require_identity: true };
+		assert_noop!(
+			TickStream::set_join_gate(RuntimeOrigin::signed(CREATOR), STREAM, Some(gate.clone())),
+			Error::<Test>::IdentityUnsupported
+		);
+		let source = AttestationSource { signer: PARTNER_SIGNER, identity_level: 2 };
+		assert_noop!(
+			TickStream::set_attestation_source(
+				RuntimeOrigin::root(),
+				PARTNER,
+				Some(AttestationSource { identity_level: 0, ..source.clone() })
+			),
+			Error::<Test>::InvalidAttestationSource
+		);
+		assert_noop!(
+			import(VIEWER, attestation(100, 2, 1, 10)),
+			Error::<Test>::UnknownAttestationSource
+		);
+		assert_ok!(TickStream::set_attestation_source(
+			RuntimeOrigin::root(),
+			PARTNER,
+			Some(source.clone())
+		));
+		assert_ok!(TickStream::set_join_gate(RuntimeOrigin::signed(CREATOR), STREAM, Some(gate)));
+		assert_noop!(
+			TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 1, None),
+			Error::<Test>::NotEnoughWatchTime
+		);
+
+		// Only attestations signed by the chain's signer are imported
+		assert_noop!(
+			signed(8, VIEWER, attestation(100, 2, 1, 10)),
+			Error::<Test>::InvalidAttestationSignature
+		);
+
+		// Watch time elsewhere counts, but the identity is not verified far enough
+		assert_ok!(import(VIEWER, attestation(100, 1, 1, 10)));
+		System::assert_last_event(
+			Event::AttestationImported {
+				who: VIEWER,
+				source: PARTNER,
+				watch_seconds: 100,
+				verification_level: 1,
+			}
+			.into(),
+		);
+		assert_noop!(
+			TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 1, None),
+			Error::<Test>::IdentityRequired
+		);
+		assert_noop!(import(VIEWER, attestation(100, 2, 1, 10)), Error::<Test>::StaleAttestation);
+		assert_ok!(import(VIEWER, attestation(100, 2, 2, 10)));
+		assert_eq!(
+			TickStream::viewer_attestations(VIEWER).into_inner(),
+			vec![attestation(100, 2, 2, 10)]
+		);
+		assert_ok!(TickStream::join_stream(RuntimeOrigin::signed(VIEWER), STREAM, 1, None));
+
+		// Attestations stop counting once they expire or their chain is removed
+		assert_ok!(import(3, attestation(100, 2, 1, 5)));
+		run_to_block(5);
+		assert_noop!(
+			TickStream::join_stream(RuntimeOrigin::signed(3), STREAM, 1, None),
+			Error::<Test>::NotEnoughWatchTime
+		);
+		assert_noop!(import(3, attestation(100, 2, 2, 5)), Error::<Test>::AttestationExpired);
+		assert_ok!(import(3, attestation(100, 2, 2, 50)));
+		assert_ok!(TickStream::set_attestation_source(RuntimeOrigin::root(), PARTNER, None));
+		assert_noop!(
+			TickStream::join_stream(RuntimeOrigin::signed(3), STREAM, 1, None),
+			Error::<Test>::NotEnoughWatchTime
+		);
+	});
+}
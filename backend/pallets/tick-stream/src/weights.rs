@@ -0,0 +1,1005 @@
+//! Weights for `tick_stream`.
+//!
+//! NOT BENCHMARKED: the figures below are hand estimates from the storage accesses of each
+//! call, not the output of `benchmark pallet`, and must not be relied on in production until
+//! they are regenerated on reference hardware with:
+//!
+//! ```sh
+//! cargo build --release --features runtime-benchmarks -p vilokanam-node
+//! ./target/release/vilokanam-node benchmark pallet \
+//! 	--chain dev --pallet tick_stream --extrinsic '*' --steps 50 --repeat 20 \
+//! 	--output pallets/tick-stream/src/weights.rs
+//! ```
+//!
+//! The benchmarks of calls that settle payments use a platform fee and `MaxCoHosts`
+//! co-hosts, the most expensive split.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `tick_stream`.
+pub trait WeightInfo {
+	fn record_tick() -> Weight;
+	fn join_stream() -> Weight;
+	fn create_stream() -> Weight;
+	fn start_stream() -> Weight;
+	fn pause_stream() -> Weight;
+	fn end_stream(r: u32, ) -> Weight;
+	fn batch_tick(n: u32, ) -> Weight;
+	fn join_with_voucher() -> Weight;
+	fn set_platform_fee() -> Weight;
+	fn set_revenue_split(c: u32, ) -> Weight;
+	fn withdraw() -> Weight;
+	fn set_notification_prefs(f: u32, ) -> Weight;
+	fn set_metadata() -> Weight;
+	fn clear_metadata() -> Weight;
+	fn sweep_expired(n: u32, ) -> Weight;
+	fn create_promo() -> Weight;
+	fn commit_promo() -> Weight;
+	fn reveal_promo() -> Weight;
+	fn update_price() -> Weight;
+	fn top_up() -> Weight;
+	fn transfer_stream() -> Weight;
+	fn tip() -> Weight;
+	fn set_auto_leave_threshold() -> Weight;
+	fn force_leave_stream(r: u32, ) -> Weight;
+	fn force_leave_account(s: u32, ) -> Weight;
+	fn schedule_stream() -> Weight;
+	fn top_up_budget() -> Weight;
+	fn release_budget() -> Weight;
+	fn prune_viewer_stats(n: u32, ) -> Weight;
+	fn create_subscription_tier() -> Weight;
+	fn subscribe() -> Weight;
+	fn set_split_vesting() -> Weight;
+	fn claim_vested() -> Weight;
+	fn dispute_ticks() -> Weight;
+	fn resolve_dispute() -> Weight;
+	fn unbond_reporter() -> Weight;
+	fn set_stream_asset() -> Weight;
+	fn set_join_gate() -> Weight;
+	fn set_sync_host() -> Weight;
+	fn anchor_position() -> Weight;
+	fn freeze_payouts() -> Weight;
+	fn unfreeze_payouts() -> Weight;
+	fn claim_handle() -> Weight;
+	fn release_handle() -> Weight;
+	fn set_spending_limit() -> Weight;
+	fn resume_session() -> Weight;
+	fn set_free_preview() -> Weight;
+	fn open_session() -> Weight;
+	fn close_session() -> Weight;
+	fn set_device_limit() -> Weight;
+	fn set_device_exemption() -> Weight;
+	fn claim_referral_earnings() -> Weight;
+	fn set_max_viewers() -> Weight;
+	fn set_category_fee() -> Weight;
+	fn pre_book() -> Weight;
+	fn gift_subscriptions(n: u32, ) -> Weight;
+	fn set_bulk_discount() -> Weight;
+	fn set_sweep_policy() -> Weight;
+	fn set_payout_account() -> Weight;
+	fn link_did() -> Weight;
+	fn unlink_did() -> Weight;
+	fn set_dust_policy() -> Weight;
+	fn register_reporter() -> Weight;
+	fn deregister_reporter() -> Weight;
+	fn migrate_stream(r: u32, ) -> Weight;
+	fn set_content_type() -> Weight;
+	fn report_playback() -> Weight;
+	fn set_global_hold_cap() -> Weight;
+	fn set_access_policy() -> Weight;
+	fn add_to_allowlist() -> Weight;
+	fn remove_from_allowlist() -> Weight;
+	fn approve_grant() -> Weight;
+	fn set_attestation_source() -> Weight;
+	fn import_attestation() -> Weight;
+	fn set_reporting_unit() -> Weight;
+	fn set_hedge_policy() -> Weight;
+	fn create_poll() -> Weight;
+	fn vote() -> Weight;
+	fn run_giveaway() -> Weight;
+	fn bond_reporter() -> Weight;
+	fn set_call_filter_mode() -> Weight;
+	fn set_tick_threshold() -> Weight;
+	fn set_batch_cap() -> Weight;
+}
+
+/// Weights for `tick_stream` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn record_tick() -> Weight {
+		Weight::from_parts(121_700_000, 9_570)
+			.saturating_add(T::DbWeight::get().reads(58_u64))
+			.saturating_add(T::DbWeight::get().writes(43_u64))
+	}
+	fn join_stream() -> Weight {
+		Weight::from_parts(41_200_000, 6_196)
+			.saturating_add(T::DbWeight::get().reads(21_u64))
+			.saturating_add(T::DbWeight::get().writes(13_u64))
+	}
+	fn create_stream() -> Weight {
+		Weight::from_parts(17_600_000, 3_513)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn start_stream() -> Weight {
+		Weight::from_parts(19_800_000, 3_513)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	fn pause_stream() -> Weight {
+		Weight::from_parts(15_300_000, 3_513)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// The range of component `r` is `[0, 64]`.
+	fn end_stream(r: u32, ) -> Weight {
+		Weight::from_parts(24_100_000, 3_513)
+			.saturating_add(Weight::from_parts(27_350_000, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().writes((7_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(r.into()))
+	}
+	/// The range of component `n` is `[1, 256]`.
+	fn batch_tick(n: u32, ) -> Weight {
+		Weight::from_parts(24_100_000, 3_513)
+			.saturating_add(Weight::from_parts(109_400_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().reads((53_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((41_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 6_057).saturating_mul(n.into()))
+	}
+	fn join_with_voucher() -> Weight {
+		Weight::from_parts(92_700_000, 6_196)
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	fn set_platform_fee() -> Weight {
+		Weight::from_parts(7_100_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// The range of component `c` is `[0, 8]`.
+	fn set_revenue_split(c: u32, ) -> Weight {
+		Weight::from_parts(16_900_000, 3_513)
+			.saturating_add(Weight::from_parts(412_000, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn withdraw() -> Weight {
+		Weight::from_parts(54_100_000, 6_196)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// The range of component `f` is `[0, 64]`.
+	fn set_notification_prefs(f: u32, ) -> Weight {
+		Weight::from_parts(12_400_000, 0)
+			.saturating_add(Weight::from_parts(27_600, 0).saturating_mul(f.into()))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn set_metadata() -> Weight {
+		Weight::from_parts(21_500_000, 3_775)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn clear_metadata() -> Weight {
+		Weight::from_parts(18_200_000, 3_513)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// The range of component `n` is `[1, 256]`.
+	fn sweep_expired(n: u32, ) -> Weight {
+		Weight::from_parts(58_600_000, 6_196)
+			.saturating_add(Weight::from_parts(24_900_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((7_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(n.into()))
+	}
+	fn create_promo() -> Weight {
+		Weight::from_parts(17_300_000, 3_513)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn commit_promo() -> Weight {
+		Weight::from_parts(16_800_000, 3_513)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn reveal_promo() -> Weight {
+		Weight::from_parts(34_900_000, 3_601)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn update_price() -> Weight {
+		Weight::from_parts(20_600_000, 3_513)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn top_up() -> Weight {
+		Weight::from_parts(38_400_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	fn transfer_stream() -> Weight {
+		Weight::from_parts(52_400_000, 6_196)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	fn tip() -> Weight {
+		Weight::from_parts(79_900_000, 25_746)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	fn set_auto_leave_threshold() -> Weight {
+		Weight::from_parts(11_900_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// The range of component `r` is `[0, 256]`.
+	fn force_leave_stream(r: u32, ) -> Weight {
+		Weight::from_parts(19_300_000, 3_513)
+			.saturating_add(Weight::from_parts(27_350_000, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((7_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(r.into()))
+	}
+	/// The range of component `s` is `[0, 256]`.
+	fn force_leave_account(s: u32, ) -> Weight {
+		Weight::from_parts(9_800_000, 0)
+			.saturating_add(Weight::from_parts(31_200_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(s.into())))
+			.saturating_add(T::DbWeight::get().writes((9_u64).saturating_mul(s.into())))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(s.into()))
+	}
+	fn schedule_stream() -> Weight {
+		Weight::from_parts(18_400_000, 3_513)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn top_up_budget() -> Weight {
+		Weight::from_parts(29_600_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn release_budget() -> Weight {
+		Weight::from_parts(28_100_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// The range of component `n` is `[1, 256]`.
+	fn prune_viewer_stats(n: u32, ) -> Weight {
+		Weight::from_parts(9_600_000, 0)
+			.saturating_add(Weight::from_parts(6_900_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_539).saturating_mul(n.into()))
+	}
+	fn create_subscription_tier() -> Weight {
+		Weight::from_parts(16_900_000, 3_513)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn subscribe() -> Weight {
+		Weight::from_parts(88_300_000, 6_196)
+			.saturating_add(T::DbWeight::get().reads(28_u64))
+			.saturating_add(T::DbWeight::get().writes(17_u64))
+	}
+	fn set_split_vesting() -> Weight {
+		Weight::from_parts(18_200_000, 3_513)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn claim_vested() -> Weight {
+		Weight::from_parts(54_100_000, 6_196)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn dispute_ticks() -> Weight {
+		Weight::from_parts(31_600_000, 4_106)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	fn resolve_dispute() -> Weight {
+		Weight::from_parts(97_400_000, 9_570)
+			.saturating_add(T::DbWeight::get().reads(14_u64))
+			.saturating_add(T::DbWeight::get().writes(11_u64))
+	}
+	fn unbond_reporter() -> Weight {
+		Weight::from_parts(29_800_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn set_stream_asset() -> Weight {
+		Weight::from_parts(61_900_000, 6_208)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	fn set_join_gate() -> Weight {
+		Weight::from_parts(17_600_000, 3_513)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn set_sync_host() -> Weight {
+		Weight::from_parts(16_900_000, 3_513)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn anchor_position() -> Weight {
+		Weight::from_parts(24_300_000, 5_220)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn freeze_payouts() -> Weight {
+		Weight::from_parts(19_400_000, 3_513)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn unfreeze_payouts() -> Weight {
+		Weight::from_parts(16_100_000, 3_513)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn claim_handle() -> Weight {
+		Weight::from_parts(31_800_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn release_handle() -> Weight {
+		Weight::from_parts(27_400_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn set_spending_limit() -> Weight {
+		Weight::from_parts(13_900_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn resume_session() -> Weight {
+		Weight::from_parts(21_700_000, 3_558)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn set_free_preview() -> Weight {
+		Weight::from_parts(17_200_000, 3_513)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn open_session() -> Weight {
+		Weight::from_parts(24_100_000, 4_096)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn close_session() -> Weight {
+		Weight::from_parts(18_300_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn set_device_limit() -> Weight {
+		Weight::from_parts(17_500_000, 3_513)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn set_device_exemption() -> Weight {
+		Weight::from_parts(17_100_000, 3_513)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn claim_referral_earnings() -> Weight {
+		Weight::from_parts(52_600_000, 6_196)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn set_max_viewers() -> Weight {
+		Weight::from_parts(17_300_000, 3_513)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn set_category_fee() -> Weight {
+		Weight::from_parts(8_900_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn pre_book() -> Weight {
+		Weight::from_parts(31_200_000, 4_180)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// The range of component `n` is `[1, 100]`.
+	fn gift_subscriptions(n: u32, ) -> Weight {
+		Weight::from_parts(58_300_000, 6_196)
+			.saturating_add(Weight::from_parts(6_420_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_539).saturating_mul(n.into()))
+	}
+	fn set_bulk_discount() -> Weight {
+		Weight::from_parts(14_600_000, 3_513)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn set_sweep_policy() -> Weight {
+		Weight::from_parts(11_300_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn set_payout_account() -> Weight {
+		Weight::from_parts(15_100_000, 3_513)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn link_did() -> Weight {
+		Weight::from_parts(22_400_000, 3_722)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn unlink_did() -> Weight {
+		Weight::from_parts(18_900_000, 3_722)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn set_dust_policy() -> Weight {
+		Weight::from_parts(10_900_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn register_reporter() -> Weight {
+		Weight::from_parts(27_300_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn deregister_reporter() -> Weight {
+		Weight::from_parts(26_800_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// The range of component `r` is `[0, 1000]`.
+	fn migrate_stream(r: u32, ) -> Weight {
+		Weight::from_parts(74_600_000, 6_196)
+			.saturating_add(Weight::from_parts(11_300_000, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(41_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(47_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(r.into()))
+	}
+	fn set_content_type() -> Weight {
+		Weight::from_parts(19_400_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn report_playback() -> Weight {
+		Weight::from_parts(78_300_000, 9_570)
+			.saturating_add(T::DbWeight::get().reads(56_u64))
+			.saturating_add(T::DbWeight::get().writes(42_u64))
+	}
+	fn set_global_hold_cap() -> Weight {
+		Weight::from_parts(8_200_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn set_access_policy() -> Weight {
+		Weight::from_parts(15_600_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn add_to_allowlist() -> Weight {
+		Weight::from_parts(14_900_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn remove_from_allowlist() -> Weight {
+		Weight::from_parts(14_700_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn approve_grant() -> Weight {
+		Weight::from_parts(58_400_000, 4_764)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	fn set_attestation_source() -> Weight {
+		Weight::from_parts(9_800_000, 1_690)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn import_attestation() -> Weight {
+		Weight::from_parts(38_400_000, 3_715)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn set_reporting_unit() -> Weight {
+		Weight::from_parts(15_300_000, 3_513)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn set_hedge_policy() -> Weight {
+		Weight::from_parts(12_900_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn create_poll() -> Weight {
+		Weight::from_parts(21_400_000, 1_489)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn vote() -> Weight {
+		Weight::from_parts(58_900_000, 84_212)
+			.saturating_add(T::DbWeight::get().reads(33_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn run_giveaway() -> Weight {
+		Weight::from_parts(74_600_000, 29_876)
+			.saturating_add(T::DbWeight::get().reads(20_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	fn bond_reporter() -> Weight {
+		Weight::from_parts(29_100_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn set_call_filter_mode() -> Weight {
+		Weight::from_parts(9_700_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn set_tick_threshold() -> Weight {
+		Weight::from_parts(9_600_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn set_batch_cap() -> Weight {
+		Weight::from_parts(9_900_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	fn record_tick() -> Weight {
+		Weight::from_parts(121_700_000, 9_570)
+			.saturating_add(RocksDbWeight::get().reads(58_u64))
+			.saturating_add(RocksDbWeight::get().writes(43_u64))
+	}
+	fn join_stream() -> Weight {
+		Weight::from_parts(41_200_000, 6_196)
+			.saturating_add(RocksDbWeight::get().reads(21_u64))
+			.saturating_add(RocksDbWeight::get().writes(13_u64))
+	}
+	fn create_stream() -> Weight {
+		Weight::from_parts(17_600_000, 3_513)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn start_stream() -> Weight {
+		Weight::from_parts(19_800_000, 3_513)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	fn pause_stream() -> Weight {
+		Weight::from_parts(15_300_000, 3_513)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// The range of component `r` is `[0, 64]`.
+	fn end_stream(r: u32, ) -> Weight {
+		Weight::from_parts(24_100_000, 3_513)
+			.saturating_add(Weight::from_parts(27_350_000, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((5_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().writes((7_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(r.into()))
+	}
+	/// The range of component `n` is `[1, 256]`.
+	fn batch_tick(n: u32, ) -> Weight {
+		Weight::from_parts(24_100_000, 3_513)
+			.saturating_add(Weight::from_parts(109_400_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().reads((53_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((41_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 6_057).saturating_mul(n.into()))
+	}
+	fn join_with_voucher() -> Weight {
+		Weight::from_parts(92_700_000, 6_196)
+			.saturating_add(RocksDbWeight::get().reads(12_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	fn set_platform_fee() -> Weight {
+		Weight::from_parts(7_100_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// The range of component `c` is `[0, 8]`.
+	fn set_revenue_split(c: u32, ) -> Weight {
+		Weight::from_parts(16_900_000, 3_513)
+			.saturating_add(Weight::from_parts(412_000, 0).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn withdraw() -> Weight {
+		Weight::from_parts(54_100_000, 6_196)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// The range of component `f` is `[0, 64]`.
+	fn set_notification_prefs(f: u32, ) -> Weight {
+		Weight::from_parts(12_400_000, 0)
+			.saturating_add(Weight::from_parts(27_600, 0).saturating_mul(f.into()))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_metadata() -> Weight {
+		Weight::from_parts(21_500_000, 3_775)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn clear_metadata() -> Weight {
+		Weight::from_parts(18_200_000, 3_513)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// The range of component `n` is `[1, 256]`.
+	fn sweep_expired(n: u32, ) -> Weight {
+		Weight::from_parts(58_600_000, 6_196)
+			.saturating_add(Weight::from_parts(24_900_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((7_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(n.into()))
+	}
+	fn create_promo() -> Weight {
+		Weight::from_parts(17_300_000, 3_513)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn commit_promo() -> Weight {
+		Weight::from_parts(16_800_000, 3_513)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn reveal_promo() -> Weight {
+		Weight::from_parts(34_900_000, 3_601)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn update_price() -> Weight {
+		Weight::from_parts(20_600_000, 3_513)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn top_up() -> Weight {
+		Weight::from_parts(38_400_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	fn transfer_stream() -> Weight {
+		Weight::from_parts(52_400_000, 6_196)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	fn tip() -> Weight {
+		Weight::from_parts(79_900_000, 25_746)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	fn set_auto_leave_threshold() -> Weight {
+		Weight::from_parts(11_900_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// The range of component `r` is `[0, 256]`.
+	fn force_leave_stream(r: u32, ) -> Weight {
+		Weight::from_parts(19_300_000, 3_513)
+			.saturating_add(Weight::from_parts(27_350_000, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((7_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(r.into()))
+	}
+	/// The range of component `s` is `[0, 256]`.
+	fn force_leave_account(s: u32, ) -> Weight {
+		Weight::from_parts(9_800_000, 0)
+			.saturating_add(Weight::from_parts(31_200_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(s.into())))
+			.saturating_add(RocksDbWeight::get().writes((9_u64).saturating_mul(s.into())))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(s.into()))
+	}
+	fn schedule_stream() -> Weight {
+		Weight::from_parts(18_400_000, 3_513)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn top_up_budget() -> Weight {
+		Weight::from_parts(29_600_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn release_budget() -> Weight {
+		Weight::from_parts(28_100_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// The range of component `n` is `[1, 256]`.
+	fn prune_viewer_stats(n: u32, ) -> Weight {
+		Weight::from_parts(9_600_000, 0)
+			.saturating_add(Weight::from_parts(6_900_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_539).saturating_mul(n.into()))
+	}
+	fn create_subscription_tier() -> Weight {
+		Weight::from_parts(16_900_000, 3_513)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn subscribe() -> Weight {
+		Weight::from_parts(88_300_000, 6_196)
+			.saturating_add(RocksDbWeight::get().reads(28_u64))
+			.saturating_add(RocksDbWeight::get().writes(17_u64))
+	}
+	fn set_split_vesting() -> Weight {
+		Weight::from_parts(18_200_000, 3_513)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn claim_vested() -> Weight {
+		Weight::from_parts(54_100_000, 6_196)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn dispute_ticks() -> Weight {
+		Weight::from_parts(31_600_000, 4_106)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	fn resolve_dispute() -> Weight {
+		Weight::from_parts(97_400_000, 9_570)
+			.saturating_add(RocksDbWeight::get().reads(14_u64))
+			.saturating_add(RocksDbWeight::get().writes(11_u64))
+	}
+	fn unbond_reporter() -> Weight {
+		Weight::from_parts(29_800_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn set_stream_asset() -> Weight {
+		Weight::from_parts(61_900_000, 6_208)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	fn set_join_gate() -> Weight {
+		Weight::from_parts(17_600_000, 3_513)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_sync_host() -> Weight {
+		Weight::from_parts(16_900_000, 3_513)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn anchor_position() -> Weight {
+		Weight::from_parts(24_300_000, 5_220)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn freeze_payouts() -> Weight {
+		Weight::from_parts(19_400_000, 3_513)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn unfreeze_payouts() -> Weight {
+		Weight::from_parts(16_100_000, 3_513)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn claim_handle() -> Weight {
+		Weight::from_parts(31_800_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn release_handle() -> Weight {
+		Weight::from_parts(27_400_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn set_spending_limit() -> Weight {
+		Weight::from_parts(13_900_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn resume_session() -> Weight {
+		Weight::from_parts(21_700_000, 3_558)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn set_free_preview() -> Weight {
+		Weight::from_parts(17_200_000, 3_513)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn open_session() -> Weight {
+		Weight::from_parts(24_100_000, 4_096)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn close_session() -> Weight {
+		Weight::from_parts(18_300_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_device_limit() -> Weight {
+		Weight::from_parts(17_500_000, 3_513)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_device_exemption() -> Weight {
+		Weight::from_parts(17_100_000, 3_513)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn claim_referral_earnings() -> Weight {
+		Weight::from_parts(52_600_000, 6_196)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn set_max_viewers() -> Weight {
+		Weight::from_parts(17_300_000, 3_513)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_category_fee() -> Weight {
+		Weight::from_parts(8_900_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn pre_book() -> Weight {
+		Weight::from_parts(31_200_000, 4_180)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// The range of component `n` is `[1, 100]`.
+	fn gift_subscriptions(n: u32, ) -> Weight {
+		Weight::from_parts(58_300_000, 6_196)
+			.saturating_add(Weight::from_parts(6_420_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(12_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_539).saturating_mul(n.into()))
+	}
+	fn set_bulk_discount() -> Weight {
+		Weight::from_parts(14_600_000, 3_513)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_sweep_policy() -> Weight {
+		Weight::from_parts(11_300_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_payout_account() -> Weight {
+		Weight::from_parts(15_100_000, 3_513)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn link_did() -> Weight {
+		Weight::from_parts(22_400_000, 3_722)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn unlink_did() -> Weight {
+		Weight::from_parts(18_900_000, 3_722)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn set_dust_policy() -> Weight {
+		Weight::from_parts(10_900_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn register_reporter() -> Weight {
+		Weight::from_parts(27_300_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn deregister_reporter() -> Weight {
+		Weight::from_parts(26_800_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// The range of component `r` is `[0, 1000]`.
+	fn migrate_stream(r: u32, ) -> Weight {
+		Weight::from_parts(74_600_000, 6_196)
+			.saturating_add(Weight::from_parts(11_300_000, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(41_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(47_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(r.into()))
+	}
+	fn set_content_type() -> Weight {
+		Weight::from_parts(19_400_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn report_playback() -> Weight {
+		Weight::from_parts(78_300_000, 9_570)
+			.saturating_add(RocksDbWeight::get().reads(56_u64))
+			.saturating_add(RocksDbWeight::get().writes(42_u64))
+	}
+	fn set_global_hold_cap() -> Weight {
+		Weight::from_parts(8_200_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_access_policy() -> Weight {
+		Weight::from_parts(15_600_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn add_to_allowlist() -> Weight {
+		Weight::from_parts(14_900_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn remove_from_allowlist() -> Weight {
+		Weight::from_parts(14_700_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn approve_grant() -> Weight {
+		Weight::from_parts(58_400_000, 4_764)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	fn set_attestation_source() -> Weight {
+		Weight::from_parts(9_800_000, 1_690)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn import_attestation() -> Weight {
+		Weight::from_parts(38_400_000, 3_715)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_reporting_unit() -> Weight {
+		Weight::from_parts(15_300_000, 3_513)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_hedge_policy() -> Weight {
+		Weight::from_parts(12_900_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn create_poll() -> Weight {
+		Weight::from_parts(21_400_000, 1_489)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn vote() -> Weight {
+		Weight::from_parts(58_900_000, 84_212)
+			.saturating_add(RocksDbWeight::get().reads(33_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn run_giveaway() -> Weight {
+		Weight::from_parts(74_600_000, 29_876)
+			.saturating_add(RocksDbWeight::get().reads(20_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	fn bond_reporter() -> Weight {
+		Weight::from_parts(29_100_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn set_call_filter_mode() -> Weight {
+		Weight::from_parts(9_700_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_tick_threshold() -> Weight {
+		Weight::from_parts(9_600_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_batch_cap() -> Weight {
+		Weight::from_parts(9_900_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
@@ -16,9 +16,10 @@ use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{
-		AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, One, Verify,
+		AccountIdLookup, BlakeTwo256, Block as BlockT, ConvertInto, IdentifyAccount, NumberFor,
+		One, OpaqueKeys, Verify,
 	},
-	transaction_validity::{TransactionSource, TransactionValidity},
+	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature,
 };
 use sp_std::prelude::*;
@@ -30,27 +31,32 @@ use sp_version::RuntimeVersion;
 pub use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{
-		ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness,
-		StorageInfo,
+		ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, Contains, EitherOfDiverse,
+		KeyOwnerProofSystem, Randomness, StorageInfo,
 	},
 	weights::{
 		constants::{
 			BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_REF_TIME_PER_SECOND,
 		},
-		IdentityFee, Weight,
+		ConstantMultiplier, IdentityFee, Weight, WeightToFeeCoefficient, WeightToFeeCoefficients,
+		WeightToFeePolynomial,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use frame_system::Call as SystemCall;
+use frame_support::traits::{AsEnsureOriginWithArg, Get};
+use frame_system::{EnsureRoot, EnsureSigned};
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
 use pallet_transaction_payment::{ConstFeeMultiplier, CurrencyAdapter, Multiplier};
+use smallvec::smallvec;
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
 pub use sp_runtime::{Perbill, Permill};
 
 /// Import the tick-stream pallet.
 pub use tick_stream;
+use tick_stream::CallFilterMode;
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -65,6 +71,9 @@ pub type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::Account
 /// Balance of an account.
 pub type Balance = u128;
 
+/// Identifier of a fungible asset.
+pub type AssetId = u32;
+
 /// Index of a transaction in the chain.
 pub type Index = u32;
 
@@ -110,7 +119,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_version: 100,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -153,11 +162,55 @@ parameter_types! {
 	pub const SS58Prefix: u8 = 42;
 }
 
+/// Filters calls according to the active [`CallFilterMode`], which the admin origin switches
+/// with `TickStream::set_call_filter_mode`.
+///
+/// System, timestamp, finality, sudo and council calls are always accepted so the chain keeps
+/// producing blocks and governance can lift the filter; root-dispatched calls bypass it.
+pub struct RuntimeCallFilter;
+
+impl Contains<RuntimeCall> for RuntimeCallFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		let mode = TickStream::call_filter_mode();
+		if mode == CallFilterMode::Normal {
+			return true
+		}
+
+		match call {
+			RuntimeCall::System(_) |
+			RuntimeCall::Timestamp(_) |
+			RuntimeCall::Grandpa(_) |
+			RuntimeCall::Sudo(_) |
+			RuntimeCall::PlatformCouncil(_) => true,
+			// The recovered call is filtered again under the lost account's origin
+			RuntimeCall::Recovery(pallet_recovery::Call::as_recovered { .. }) => true,
+			RuntimeCall::TickStream(tick_stream::Call::set_call_filter_mode { .. }) => true,
+			RuntimeCall::TickStream(
+				tick_stream::Call::pause_stream { .. } | tick_stream::Call::end_stream { .. },
+			) => true,
+			// Viewers can always leave and take back what they reserved
+			RuntimeCall::TickStream(
+				tick_stream::Call::close_session { .. } |
+				tick_stream::Call::release_budget { .. } |
+				tick_stream::Call::sweep_expired { .. } |
+				tick_stream::Call::force_leave_stream { .. } |
+				tick_stream::Call::force_leave_account { .. },
+			) => true,
+			RuntimeCall::TickStream(
+				tick_stream::Call::record_tick { .. } |
+				tick_stream::Call::batch_tick { .. } |
+				tick_stream::Call::settle_inherent { .. },
+			) => mode == CallFilterMode::SettlementOnly,
+			_ => false,
+		}
+	}
+}
+
 // Configure FRAME pallets to include in runtime.
 
 impl frame_system::Config for Runtime {
 	/// The basic call filter to use in dispatchable.
-	type BaseCallFilter = frame_support::traits::Everything;
+	type BaseCallFilter = RuntimeCallFilter;
 	/// The block type for the runtime.
 	type Block = Block;
 	/// Block & extrinsics weights: base values and limits.
@@ -188,10 +241,10 @@ impl frame_system::Config for Runtime {
 	///
 	/// This type is being generated by `construct_runtime!`.
 	type PalletInfo = PalletInfo;
-	/// What to do if a new account is created.
-	type OnNewAccount = ();
+	/// What to do if a new account is created. Tick-stream records its age for join gates.
+	type OnNewAccount = TickStream;
 	/// What to do if an account is fully reaped from the system.
-	type OnKilledAccount = ();
+	type OnKilledAccount = TickStream;
 	/// The data to be stored in an account.
 	type AccountData = pallet_balances::AccountData<Balance>;
 	/// Weight information for the extrinsics of this pallet.
@@ -200,6 +253,8 @@ impl frame_system::Config for Runtime {
 	type SS58Prefix = SS58Prefix;
 	/// The set code logic, just the default since we're not a parachain.
 	type OnSetCode = ();
+	/// The most modules that may depend on an account at once. Tick-stream takes a single
+	/// reference for all of a viewer's sessions, next to the balances pallet's for holds.
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
@@ -222,6 +277,25 @@ impl pallet_grandpa::Config for Runtime {
 	type EquivocationReportSystem = ();
 }
 
+parameter_types! {
+	pub const SessionPeriod: BlockNumber = HOURS;
+	pub const SessionOffset: BlockNumber = 0;
+}
+
+// Validators register their Aura and GRANDPA keys through `set_keys`; new keys take effect at
+// the next session. The validator set itself is the one configured at genesis.
+impl pallet_session::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ValidatorId = <Self as frame_system::Config>::AccountId;
+	type ValidatorIdOf = ConvertInto;
+	type ShouldEndSession = pallet_session::PeriodicSessions<SessionPeriod, SessionOffset>;
+	type NextSessionRotation = pallet_session::PeriodicSessions<SessionPeriod, SessionOffset>;
+	type SessionManager = ();
+	type SessionHandler = <opaque::SessionKeys as OpaqueKeys>::KeyTypeIdProviders;
+	type Keys = opaque::SessionKeys;
+	type WeightInfo = pallet_session::weights::SubstrateWeight<Runtime>;
+}
+
 impl pallet_timestamp::Config for Runtime {
 	/// A timestamp: milliseconds since the unix epoch.
 	type Moment = u64;
@@ -247,25 +321,283 @@ impl pallet_balances::Config for Runtime {
 	type WeightInfo = pallet_balances::weights::SubstrateWeight<Runtime>;
 	type FreezeIdentifier = ();
 	type MaxFreezes = ();
-	type RuntimeHoldReason = ();
-	type MaxHolds = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type MaxHolds = ConstU32<5>;
+}
+
+/// The fee for an extrinsic of `ExtrinsicBaseWeight`, a tenth of the existential deposit.
+pub const EXTRINSIC_BASE_FEE: Balance = EXISTENTIAL_DEPOSIT / 10;
+
+/// Charges fees linearly in weight, scaled so an extrinsic of `ExtrinsicBaseWeight` costs
+/// `EXTRINSIC_BASE_FEE`.
+///
+/// Viewers join and top up streams many times a session, so the usual calls cost a fraction
+/// of the existential deposit rather than one unit per picosecond of weight.
+pub struct WeightToFee;
+impl WeightToFeePolynomial for WeightToFee {
+	type Balance = Balance;
+	fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+		let p = EXTRINSIC_BASE_FEE;
+		let q = Balance::from(ExtrinsicBaseWeight::get().ref_time());
+		smallvec![WeightToFeeCoefficient {
+			degree: 1,
+			negative: false,
+			coeff_frac: Perbill::from_rational(p % q, q),
+			coeff_integer: p / q,
+		}]
+	}
 }
 
 parameter_types! {
 	pub FeeMultiplier: Multiplier = Multiplier::one();
+	/// The fee per byte of an extrinsic.
+	pub const TransactionByteFee: Balance = 1;
 }
 
 impl pallet_transaction_payment::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
 	type OperationalFeeMultiplier = ConstU8<5>;
-	type WeightToFee = IdentityFee<Balance>;
-	type LengthToFee = IdentityFee<Balance>;
+	type WeightToFee = WeightToFee;
+	type LengthToFee = ConstantMultiplier<Balance, TransactionByteFee>;
 	type FeeMultiplierUpdate = ConstFeeMultiplier<FeeMultiplier>;
 }
 
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	RuntimeCall: From<C>,
+{
+	type Extrinsic = UncheckedExtrinsic;
+	type OverarchingCall = RuntimeCall;
+}
+
+parameter_types! {
+	pub const TickStreamUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+	pub const TickStreamPalletId: PalletId = PalletId(*b"vk/ticks");
+	pub const TickStreamReferralShare: Permill = Permill::from_percent(5);
+}
+
 impl tick_stream::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type NativeBalance = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type EraLength = ConstU32<DAYS>;
+	type MaxViewersPerBatch = ConstU32<256>;
+	type UnixTime = Timestamp;
+	type TickThreshold = ConstU32<1>;
+	type UnsignedPriority = TickStreamUnsignedPriority;
+	type OffchainSignature = Signature;
+	type OffchainPublic = <Signature as Verify>::Signer;
+	type PalletId = TickStreamPalletId;
+	type MaxCoHosts = ConstU32<8>;
+	type MaxFollowedStreams = ConstU32<64>;
+	type MaxMetadataLen = ConstU32<128>;
+	type MaxRefundsOnEnd = ConstU32<64>;
+	type SettlementWindow = ConstU32<HOURS>;
+	type SweepReward = ConstU128<50>;
+	type MaxPromoCodeLen = ConstU32<32>;
+	type PriceChangeDelay = ConstU32<{ 10 * MINUTES }>;
+	type Randomness = RandomnessCollectiveFlip;
+	type FeaturedStreams = ConstU32<10>;
+	type LowBalanceSeconds = ConstU32<60>;
+	type MaxTipMessageLen = ConstU32<140>;
+	type MaxTipsPerStream = ConstU32<100>;
+	type ExpectedBlockTime = ConstU64<MILLISECS_PER_BLOCK>;
+	type ViewerStatsRetention = ConstU32<{ 90 * DAYS }>;
+	type MaxSubscriptionTiers = ConstU32<8>;
+	// No price feed yet: settlements are recorded in the native token only
+	type FiatOracle = ();
+	type SettlementDelay = ConstU32<{ 10 * MINUTES }>;
+	type ReporterBond = ConstU128<{ 100 * EXISTENTIAL_DEPOSIT }>;
+	type Assets = Assets;
+	type MaxGateAccountAge = ConstU32<{ 30 * DAYS }>;
+	type MaxGateWatchSeconds = ConstU64<{ 100 * 60 * 60 }>;
+	// No identity pallet yet: streams cannot require a verified identity
+	type IdentityVerifier = ();
+	// No reward pallet consumes watch time yet
+	type OnWatchTime = ();
+	type WatchTimeBatchSize = ConstU32<256>;
+	type MaxPositionAnchors = ConstU32<64>;
+	type MaxSettlementsPerBlock = ConstU32<1_024>;
+	type AlertAfterBlocks = ConstU32<{ 5 * MINUTES }>;
+	type MaxHandleLen = ConstU32<32>;
+	type HandleDeposit = ConstU128<{ 10 * EXISTENTIAL_DEPOSIT }>;
+	type MaxDevicesPerViewer = ConstU32<8>;
+	type ReferralShare = TickStreamReferralShare;
+	type PreBookingDeposit = ConstU128<{ 100 * EXISTENTIAL_DEPOSIT }>;
+	type MaxPreBookings = ConstU32<1_000>;
+	type PreBookingGracePeriod = ConstU32<HOURS>;
+	type MaxGiftRecipients = ConstU32<100>;
+	type MaxDidLen = ConstU32<256>;
+	type TickReporterBond = ConstU128<{ 1_000 * EXISTENTIAL_DEPOSIT }>;
+	type ReporterCooldown = ConstU32<{ 7 * DAYS }>;
+	type MaxAttestationSources = ConstU32<16>;
+	type MaxPollOptions = ConstU32<16>;
+	type MaxPollTextLen = ConstU32<256>;
+	// A month of daily eras
+	type MaxPollLookbackEras = ConstU32<30>;
+	type FirstChargeGraceSeconds = ConstU32<5>;
+	type TickSigner = TickStreamSigner;
+	type SettlementExtension = ();
+	// No asset-conversion pallet yet: payouts cannot be hedged
+	type PayoutSwap = ();
+	// No finance council yet: the council freezes payouts
+	type PayoutFreezeOrigin = EnsureRootOrHalfCouncil;
+	type AdminOrigin = EnsureRootOrHalfCouncil;
+	type WeightInfo = tick_stream::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = TickStreamBenchmarkHelper;
+}
+
+/// Signs the offchain worker's ticks with the node's sr25519 keys of the tick reporter key type.
+pub struct TickStreamSigner;
+
+impl tick_stream::TickSigner<<Signature as Verify>::Signer, Signature> for TickStreamSigner {
+	fn reporter_keys() -> Vec<<Signature as Verify>::Signer> {
+		sp_io::crypto::sr25519_public_keys(tick_stream::REPORTER_KEY_TYPE)
+			.into_iter()
+			.map(Into::into)
+			.collect()
+	}
+
+	fn sign(reporter: &<Signature as Verify>::Signer, message: &[u8]) -> Option<Signature> {
+		let sp_runtime::MultiSigner::Sr25519(public) = reporter else { return None };
+		sp_io::crypto::sr25519_sign(tick_stream::REPORTER_KEY_TYPE, public, message).map(Into::into)
+	}
+}
+
+/// Signs join vouchers with a key generated in the benchmark keystore.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct TickStreamBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl tick_stream::BenchmarkHelper<<Signature as Verify>::Signer, Signature, AssetId>
+	for TickStreamBenchmarkHelper
+{
+	fn signer() -> <Signature as Verify>::Signer {
+		sp_io::crypto::sr25519_generate(0.into(), None).into()
+	}
+
+	fn sign(signer: &<Signature as Verify>::Signer, message: &[u8]) -> Signature {
+		let sp_runtime::MultiSigner::Sr25519(public) = signer else {
+			panic!("benchmark signers are sr25519 keys")
+		};
+		sp_io::crypto::sr25519_sign(0.into(), public, message)
+			.expect("the key was generated in the keystore")
+			.into()
+	}
+
+	fn set_time(seconds: u64) {
+		// Written directly: `Timestamp::set_timestamp` would trip Aura's slot check
+		pallet_timestamp::Now::<Runtime>::put(seconds * 1000);
+	}
+
+	fn create_asset() -> AssetId {
+		use frame_support::traits::fungibles::Create;
+
+		let id = 0;
+		let admin = TickStream::platform_account();
+		<Assets as Create<AccountId>>::create(id, admin, true, 1)
+			.expect("no asset exists in the benchmarks yet");
+		id
+	}
+}
+
+// Insecure randomness is enough to seed the featured rotation on this development chain.
+impl pallet_insecure_randomness_collective_flip::Config for Runtime {}
+
+parameter_types! {
+	pub const RecoveryConfigDepositBase: Balance = 10 * EXISTENTIAL_DEPOSIT;
+	pub const RecoveryFriendDepositFactor: Balance = EXISTENTIAL_DEPOSIT;
+	pub const RecoveryDeposit: Balance = 10 * EXISTENTIAL_DEPOSIT;
+}
+
+// Social recovery, so a creator who loses their key can still reach their streams and escrow.
+impl pallet_recovery::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_recovery::weights::SubstrateWeight<Runtime>;
+	type RuntimeCall = RuntimeCall;
+	type Currency = Balances;
+	type ConfigDepositBase = RecoveryConfigDepositBase;
+	type FriendDepositFactor = RecoveryFriendDepositFactor;
+	type MaxFriends = ConstU32<9>;
+	type RecoveryDeposit = RecoveryDeposit;
+}
+
+parameter_types! {
+	pub const CouncilMotionDuration: BlockNumber = 3 * DAYS;
+	pub const CouncilMaxProposals: u32 = 100;
+	pub const CouncilMaxMembers: u32 = 13;
+	pub MaxProposalWeight: Weight = Perbill::from_percent(50) * BlockWeights::get().max_block;
+}
+
+/// The Platform Council, which sets platform parameters without sudo or a runtime upgrade.
+pub type PlatformCouncilInstance = pallet_collective::Instance1;
+
+/// Root, or more than half of the Platform Council.
+pub type EnsureRootOrHalfCouncil = EitherOfDiverse<
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionMoreThan<AccountId, PlatformCouncilInstance, 1, 2>,
+>;
+
+impl pallet_collective::Config<PlatformCouncilInstance> for Runtime {
+	type RuntimeOrigin = RuntimeOrigin;
+	type Proposal = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type MotionDuration = CouncilMotionDuration;
+	type MaxProposals = CouncilMaxProposals;
+	type MaxMembers = CouncilMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+	// Members are managed through `PlatformCouncilMembership`
+	type SetMembersOrigin = EnsureRoot<AccountId>;
+	type MaxProposalWeight = MaxProposalWeight;
+}
+
+impl pallet_membership::Config<pallet_membership::Instance1> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type AddOrigin = EnsureRootOrHalfCouncil;
+	type RemoveOrigin = EnsureRootOrHalfCouncil;
+	type SwapOrigin = EnsureRootOrHalfCouncil;
+	type ResetOrigin = EnsureRootOrHalfCouncil;
+	type PrimeOrigin = EnsureRootOrHalfCouncil;
+	type MembershipInitialized = PlatformCouncil;
+	type MembershipChanged = PlatformCouncil;
+	type MaxMembers = CouncilMaxMembers;
+	type WeightInfo = pallet_membership::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const AssetDeposit: Balance = 100 * EXISTENTIAL_DEPOSIT;
+	pub const AssetAccountDeposit: Balance = EXISTENTIAL_DEPOSIT;
+	pub const AssetMetadataDepositBase: Balance = 10 * EXISTENTIAL_DEPOSIT;
+	pub const AssetMetadataDepositPerByte: Balance = EXISTENTIAL_DEPOSIT / 10;
+	pub const AssetApprovalDeposit: Balance = EXISTENTIAL_DEPOSIT;
+}
+
+// Fungible assets, such as stablecoins, that creators can price their streams in.
+impl pallet_assets::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type AssetId = AssetId;
+	type AssetIdParameter = codec::Compact<AssetId>;
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
+	type ForceOrigin = EnsureRootOrHalfCouncil;
+	type AssetDeposit = AssetDeposit;
+	type AssetAccountDeposit = AssetAccountDeposit;
+	type MetadataDepositBase = AssetMetadataDepositBase;
+	type MetadataDepositPerByte = AssetMetadataDepositPerByte;
+	type ApprovalDeposit = AssetApprovalDeposit;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type CallbackHandle = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+	type RemoveItemsLimit = ConstU32<1000>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
 }
 
 impl pallet_sudo::Config for Runtime {
@@ -279,13 +611,20 @@ construct_runtime!(
 	pub struct Runtime {
 		System: frame_system,
 		Timestamp: pallet_timestamp,
+		// Session hands the genesis authorities to Aura and GRANDPA, so it comes first.
+		Session: pallet_session,
 		Aura: pallet_aura,
 		Grandpa: pallet_grandpa,
 		Balances: pallet_balances,
 		TransactionPayment: pallet_transaction_payment,
 		Sudo: pallet_sudo,
+		RandomnessCollectiveFlip: pallet_insecure_randomness_collective_flip,
+		Recovery: pallet_recovery,
+		PlatformCouncil: pallet_collective::<Instance1>,
+		PlatformCouncilMembership: pallet_membership::<Instance1>,
 		// Include the custom logic from the tick-stream pallet in the runtime.
 		TickStream: tick_stream,
+		Assets: pallet_assets,
 	}
 );
 
@@ -305,6 +644,7 @@ pub type SignedExtra = (
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	tick_stream::TrackDispatchErrors<Runtime>,
 );
 
 /// All migrations of the runtime, aside from the ones declared in the pallets.
@@ -333,6 +673,9 @@ mod benches {
 	frame_benchmarking::define_benchmarks!(
 		[frame_benchmarking, BaselineBench::<Runtime>]
 		[frame_system, SystemBench::<Runtime>]
+		[pallet_assets, Assets]
+		[pallet_collective, PlatformCouncil]
+		[pallet_membership, PlatformCouncilMembership]
 		[tick_stream, TickStream]
 	);
 }
@@ -482,10 +825,157 @@ impl_runtime_apis! {
 		}
 	}
 
-	impl tick_stream::TickStreamApi<Block> for Runtime {
+	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentCallApi<Block, Balance, RuntimeCall>
+		for Runtime
+	{
+		fn query_call_info(
+			call: RuntimeCall,
+			len: u32,
+		) -> pallet_transaction_payment::RuntimeDispatchInfo<Balance> {
+			TransactionPayment::query_call_info(call, len)
+		}
+		fn query_call_fee_details(
+			call: RuntimeCall,
+			len: u32,
+		) -> pallet_transaction_payment::FeeDetails<Balance> {
+			TransactionPayment::query_call_fee_details(call, len)
+		}
+		fn query_weight_to_fee(weight: Weight) -> Balance {
+			TransactionPayment::weight_to_fee(weight)
+		}
+		fn query_length_to_fee(length: u32) -> Balance {
+			TransactionPayment::length_to_fee(length)
+		}
+	}
+
+	impl tick_stream::TickStreamApi<Block, AccountId, Balance, BlockNumber, AssetId> for Runtime {
 		fn get_tick_count(stream_id: u128) -> u32 {
 			TickStream::get_tick_count(stream_id)
 		}
+
+		fn get_error_counts(era: u32) -> Vec<(u8, u8, u32)> {
+			TickStream::get_error_counts(era)
+		}
+
+		fn get_creator_earnings(creator: AccountId, era: u32) -> tick_stream::EraEarnings<Balance> {
+			TickStream::creator_earnings(creator, era)
+		}
+
+		fn get_stream_details(
+			stream_id: u128,
+		) -> Option<tick_stream::StreamDetails<AccountId, Balance, BlockNumber, AssetId>> {
+			TickStream::stream_details(stream_id)
+		}
+
+		fn get_storage_footprint() -> Vec<tick_stream::StorageFootprint> {
+			TickStream::storage_footprint()
+		}
+
+		fn get_viewer_session(
+			stream_id: u128,
+			account: AccountId,
+		) -> Option<tick_stream::ViewerSession<Balance>> {
+			TickStream::viewer_session(stream_id, &account)
+		}
+
+		fn get_notification_prefs(
+			accounts: Vec<AccountId>,
+		) -> Vec<Option<tick_stream::NotificationPrefs>> {
+			accounts.into_iter().map(TickStream::notification_prefs).collect()
+		}
+
+		fn get_notification_prefs_page(
+			start_after: Option<AccountId>,
+			limit: u32,
+		) -> Vec<(AccountId, tick_stream::NotificationPrefs)> {
+			TickStream::notification_prefs_page(start_after, limit.min(1_000))
+		}
+
+		fn get_creator_budget(
+			viewer: AccountId,
+			creator: AccountId,
+		) -> tick_stream::CreatorBudget<Balance> {
+			TickStream::creator_budget(viewer, creator)
+		}
+
+		fn get_viewer_stats(
+			account: AccountId,
+		) -> Vec<(u128, tick_stream::WatchStats<Balance, BlockNumber>)> {
+			TickStream::viewer_stats_of(&account)
+		}
+
+		fn get_due_ticks() -> Vec<(u128, AccountId, u32)> {
+			// A frozen chain refuses the settlement inherent, so there is nothing to settle
+			if TickStream::call_filter_mode() == CallFilterMode::Frozen {
+				return Vec::new()
+			}
+			TickStream::due_ticks(TickStream::batch_cap())
+		}
+
+		fn resolve_handle(
+			handle: Vec<u8>,
+		) -> Option<tick_stream::HandleClaim<AccountId, Balance, Vec<u8>>> {
+			TickStream::resolve_handle(&handle)
+		}
+
+		fn list_streams(
+			offset: u32,
+			limit: u32,
+			filter: tick_stream::StreamFilter<AccountId>,
+		) -> Vec<tick_stream::StreamSummary<AccountId, Balance>> {
+			TickStream::list_streams(offset, limit, filter)
+		}
+
+		fn get_did_link(
+			account: AccountId,
+		) -> Option<tick_stream::DidLink<Vec<u8>, BlockNumber>> {
+			TickStream::did_link_of(&account)
+		}
+
+		fn resolve_did(did: Vec<u8>) -> Option<AccountId> {
+			TickStream::resolve_did(&did)
+		}
+
+		fn get_hold_utilization() -> tick_stream::HoldUtilization<Balance> {
+			TickStream::hold_utilization()
+		}
+
+		fn config_fingerprint() -> tick_stream::ConfigFingerprint {
+			TickStream::config_fingerprint()
+		}
+
+		fn get_stream_report(
+			stream_id: u128,
+			era: u32,
+		) -> Option<tick_stream::StreamReport<Balance, AssetId>> {
+			TickStream::stream_report(stream_id, era)
+		}
+
+		fn get_era_settlement_proof(
+			era: u32,
+			stream_id: u128,
+		) -> Option<tick_stream::EraSettlementProof<tick_stream::StreamEarnings<Balance>>> {
+			TickStream::era_settlement_proof(era, stream_id)
+		}
+
+		fn get_session_state(
+			stream_id: u128,
+			account: AccountId,
+		) -> Option<tick_stream::SessionState> {
+			TickStream::session_state(stream_id, &account)
+		}
+
+		fn get_settlement_health() -> tick_stream::SettlementHealth<BlockNumber> {
+			TickStream::settlement_health()
+		}
+
+		fn get_upcoming_streams(
+			viewer: AccountId,
+			offset: u32,
+			limit: u32,
+		) -> Vec<tick_stream::UpcomingStream<AccountId, BlockNumber>> {
+			TickStream::upcoming_streams(&viewer, offset, limit)
+		}
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
@@ -599,4 +1089,22 @@ mod tests {
 			whitelist.contains("26aa394eea5630e07c48ae0c9558cef780d41e5e16056765bc8461851072c9d7")
 		);
 	}
+
+	#[test]
+	fn joining_a_stream_costs_less_than_the_existential_deposit() {
+		use frame_support::weights::WeightToFee as _;
+		use tick_stream::WeightInfo as _;
+
+		assert_eq!(WeightToFee::weight_to_fee(&ExtrinsicBaseWeight::get()), EXTRINSIC_BASE_FEE);
+
+		// A signed extrinsic is around 150 bytes
+		for weight in [
+			<Runtime as tick_stream::Config>::WeightInfo::join_stream(),
+			<Runtime as tick_stream::Config>::WeightInfo::top_up(),
+		] {
+			let fee = WeightToFee::weight_to_fee(&ExtrinsicBaseWeight::get().saturating_add(weight))
+				.saturating_add(150 * TransactionByteFee::get());
+			assert!(fee < EXISTENTIAL_DEPOSIT, "fee of {fee} for {weight:?}");
+		}
+	}
 }
\ No newline at end of file
@@ -0,0 +1,174 @@
+// frontend/packages/sdk/src/__tests__/watcher.test.ts
+
+/**
+ * Stream Watcher Tests
+ *
+ * This file contains tests for the stream watcher's cache, which follows
+ * the chain's heads and survives reorganizations.
+ */
+
+import { StreamWatcher, WatchedStream } from '../watcher';
+
+// Chain state by block hash, as the storage queries return it
+interface BlockState {
+  price: number;
+  status: string;
+  viewers: number;
+  balance: number;
+  watched: number;
+}
+
+const codec = (value: number | string) => ({
+  toString: () => String(value),
+  toNumber: () => Number(value)
+});
+
+const header = (hash: string, parentHash: string, number: number) => ({
+  hash: { toHex: () => hash },
+  parentHash: { toHex: () => parentHash },
+  number: { toNumber: () => number }
+});
+
+const mockApi = (states: Record<string, BlockState | null>) => {
+  const heads: { best?: (header: any) => void; finalized?: (header: any) => void } = {};
+  const api = {
+    rpc: {
+      chain: {
+        subscribeNewHeads: jest.fn(async (callback) => {
+          heads.best = callback;
+          return jest.fn();
+        }),
+        subscribeFinalizedHeads: jest.fn(async (callback) => {
+          heads.finalized = callback;
+          return jest.fn();
+        })
+      }
+    },
+    at: jest.fn(async (hash: string) => {
+      const state = states[hash];
+      const read = <T>(value: T) => Promise.resolve(value);
+      return {
+        query: {
+          tickStream: {
+            streams: () =>
+              read(
+                state
+                  ? {
+                      isSome: true,
+                      unwrap: () => ({
+                        creator: codec('5Creator'),
+                        status: { type: state.status },
+                        pricePerSecond: codec(state.price)
+                      })
+                    }
+                  : { isSome: false }
+              ),
+            viewerCount: () => read(codec(state ? state.viewers : 0)),
+            balances: () => read(codec(state ? state.balance : 0)),
+            watchedSeconds: () => read(codec(state ? state.watched : 0))
+          }
+        }
+      };
+    })
+  };
+  return { api: api as any, heads };
+};
+
+// Let every pending read settle
+const flush = () => new Promise((resolve) => setTimeout(resolve, 0));
+
+describe('StreamWatcher', () => {
+  it('should cache the stream and session as finalized heads come in', async () => {
+    const { api, heads } = mockApi({
+      '0x01': { price: 10, status: 'Live', viewers: 1, balance: 100, watched: 0 },
+      '0x02': { price: 10, status: 'Live', viewers: 2, balance: 80, watched: 2 }
+    });
+    const watcher = new StreamWatcher(api, '7', { account: '5Viewer' });
+    const seen: (WatchedStream | null)[] = [];
+    watcher.subscribe((state) => seen.push(state));
+    await watcher.start();
+    expect(api.rpc.chain.subscribeFinalizedHeads).toHaveBeenCalled();
+
+    heads.finalized!(header('0x01', '0x00', 1));
+    await flush();
+    heads.finalized!(header('0x02', '0x01', 2));
+    await flush();
+
+    expect(watcher.state).toEqual({
+      streamId: '7',
+      blockHash: '0x02',
+      blockNumber: 2,
+      stream: { creator: '5Creator', status: 'Live', pricePerSecond: BigInt(10), viewerCount: 2 },
+      session: { balance: BigInt(80), watchedSeconds: 2 }
+    });
+    expect(seen.map((state) => state && state.blockNumber)).toEqual([null, 1, 2]);
+  });
+
+  it('should report a stream that does not exist', async () => {
+    const { api, heads } = mockApi({ '0x01': null });
+    const watcher = new StreamWatcher(api, '7');
+    await watcher.start();
+
+    heads.finalized!(header('0x01', '0x00', 1));
+    await flush();
+
+    expect(watcher.state).toMatchObject({ blockNumber: 1, stream: null, session: null });
+  });
+
+  it('should drop what it read on a branch that was reorganized away', async () => {
+    const { api, heads } = mockApi({
+      '0x01': { price: 10, status: 'Live', viewers: 1, balance: 100, watched: 0 },
+      '0x2a': { price: 10, status: 'Live', viewers: 1, balance: 80, watched: 2 },
+      '0x2b': { price: 10, status: 'Ended', viewers: 0, balance: 0, watched: 0 }
+    });
+    const watcher = new StreamWatcher(api, '7', { account: '5Viewer', follow: 'best' });
+    const seen: (WatchedStream | null)[] = [];
+    watcher.subscribe((state) => seen.push(state));
+    await watcher.start();
+    expect(api.rpc.chain.subscribeNewHeads).toHaveBeenCalled();
+
+    heads.best!(header('0x01', '0x00', 1));
+    await flush();
+    heads.best!(header('0x2a', '0x01', 2));
+    await flush();
+    expect(watcher.state!.session!.balance).toBe(BigInt(80));
+
+    // Another block 2 replaces the one read
+    heads.best!(header('0x2b', '0x01', 2));
+    await flush();
+
+    expect(watcher.state!.blockHash).toBe('0x2b');
+    expect(watcher.state!.stream!.status).toBe('Ended');
+    expect(seen.map((state) => state && state.blockHash)).toEqual([
+      null,
+      '0x01',
+      '0x2a',
+      null,
+      '0x2b'
+    ]);
+  });
+
+  it('should never let a slow read overwrite a newer one', async () => {
+    const { api, heads } = mockApi({
+      '0x01': { price: 10, status: 'Live', viewers: 1, balance: 0, watched: 0 },
+      '0x02': { price: 20, status: 'Live', viewers: 1, balance: 0, watched: 0 }
+    });
+    const at = api.at.getMockImplementation();
+    let release: () => void = () => {};
+    api.at.mockImplementationOnce(async (hash: string) => {
+      await new Promise<void>((resolve) => (release = resolve));
+      return at(hash);
+    });
+    const watcher = new StreamWatcher(api, '7');
+    await watcher.start();
+
+    heads.finalized!(header('0x01', '0x00', 1));
+    heads.finalized!(header('0x02', '0x01', 2));
+    await flush();
+    release();
+    await flush();
+
+    expect(watcher.state!.blockNumber).toBe(2);
+    expect(watcher.state!.stream!.pricePerSecond).toBe(BigInt(20));
+  });
+});
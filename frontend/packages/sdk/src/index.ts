@@ -1,5 +1,6 @@
 import { ApiPromise, WsProvider } from '@polkadot/api';
 import { useEffect, useState } from 'react';
+import { StreamWatcher, StreamWatcherOptions, WatchedStream } from './watcher';
 
 // Define types
 type Hash = string;
@@ -63,11 +64,63 @@ export const useTickStream = (streamId: string) => {
   return { tickCount, isConnected, error };
 };
 
-// Function to join a stream
-export const joinStream = async (streamId: string, account: any): Promise<string> => {
+// Custom hook to follow a stream's state, and the account's session on it, as blocks come in
+export const useStreamWatcher = (streamId: string, options: StreamWatcherOptions = {}) => {
+  const [state, setState] = useState<WatchedStream | null>(null);
+  const [isConnected, setIsConnected] = useState<boolean>(false);
+  const [error, setError] = useState<string | null>(null);
+  const { account, follow } = options;
+
+  useEffect(() => {
+    let watcher: StreamWatcher | null = null;
+    let isMounted = true;
+
+    const connect = async () => {
+      try {
+        const api = await initializeApi();
+        if (!isMounted) {
+          return;
+        }
+        watcher = new StreamWatcher(api, streamId, { account, follow });
+        watcher.subscribe((watched, err) => {
+          if (isMounted) {
+            setState(watched);
+            setError(err ? err.message : null);
+          }
+        });
+        await watcher.start();
+        setIsConnected(true);
+      } catch (err) {
+        if (isMounted) {
+          setError(err instanceof Error ? err.message : 'Unknown error');
+          setIsConnected(false);
+        }
+      }
+    };
+
+    connect();
+
+    return () => {
+      isMounted = false;
+      if (watcher) {
+        watcher.stop();
+      }
+    };
+  }, [streamId, account, follow]);
+
+  return { state, isConnected, error };
+};
+
+// Function to join a stream, failing if its price per second is above maxPricePerSecond
+export const joinStream = async (
+  streamId: string,
+  seconds: number,
+  account: any,
+  maxPricePerSecond: string | null = null
+): Promise<string> => {
   try {
     const api = await initializeApi();
-    const tx = api.tx.tickStream.joinStream(streamId);
+    const tx = api.tx.tickStream.joinStreamV2(streamId, seconds, null, maxPricePerSecond);
     const hash: string = (await tx.signAndSend(account)) as unknown as string;
     return hash;
   } catch (err) {
@@ -94,4 +147,7 @@ export * from './streaming';
 export * from './api';
 
 // Re-export signaling client
-export * from './signaling';
\ No newline at end of file
+export * from './signaling';
+
+// Re-export the stream watcher
+export * from './watcher';
\ No newline at end of file
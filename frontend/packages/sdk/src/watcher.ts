@@ -0,0 +1,211 @@
+// frontend/packages/sdk/src/watcher.ts
+
+/**
+ * Stream Watcher
+ *
+ * Keeps a cached, typed view of a stream's on-chain state, and of one
+ * account's session on it, in step with the chain's heads, so apps
+ * subscribe to changes instead of polling.
+ */
+
+import type { ApiPromise } from '@polkadot/api';
+
+export type StreamStatus = 'Created' | 'Live' | 'Paused' | 'Ended';
+
+export interface WatchedStream {
+  streamId: string;
+  /** The block the state was read at */
+  blockHash: string;
+  blockNumber: number;
+  /** The stream, or null if it does not exist at the block */
+  stream: {
+    creator: string;
+    status: StreamStatus;
+    pricePerSecond: bigint;
+    viewerCount: number;
+  } | null;
+  /** The watched account's session, or null if no account is watched */
+  session: {
+    /** What the account still has reserved for the stream */
+    balance: bigint;
+    /** Seconds of the stream the account has been charged for */
+    watchedSeconds: number;
+  } | null;
+}
+
+export interface StreamWatcherOptions {
+  /** The account whose balance and watch time to track */
+  account?: string;
+  /**
+   * Follow finalized heads, the default, or best heads, which are quicker
+   * but can be reorganized away
+   */
+  follow?: 'finalized' | 'best';
+}
+
+/** Called with the cached state, null until it is known, and the last read's error */
+export type StreamWatcherListener = (state: WatchedStream | null, error: Error | null) => void;
+
+interface Head {
+  hash: string;
+  parentHash: string;
+  number: number;
+}
+
+// How many recent heads are remembered to recognize a reorganization
+const TRACKED_HEADS = 64;
+
+export class StreamWatcher {
+  private current: WatchedStream | null = null;
+  private error: Error | null = null;
+  private listeners = new Set<StreamWatcherListener>();
+  private unsubscribe: (() => void) | null = null;
+  // The hash of each recent head, by number
+  private heads = new Map<number, string>();
+  private latest: Head | null = null;
+  // Counts reads, so that a slow read never overwrites a newer one
+  private reads = 0;
+
+  constructor(
+    private api: ApiPromise,
+    readonly streamId: string,
+    private options: StreamWatcherOptions = {}
+  ) {}
+
+  /** The cached state, or null until it is known */
+  get state(): WatchedStream | null {
+    return this.current;
+  }
+
+  /** Call `listener` now and whenever the cached state changes */
+  subscribe(listener: StreamWatcherListener): () => void {
+    this.listeners.add(listener);
+    listener(this.current, this.error);
+    return () => {
+      this.listeners.delete(listener);
+    };
+  }
+
+  /** Start following the chain's heads */
+  async start(): Promise<void> {
+    if (this.unsubscribe) {
+      return;
+    }
+    const onHead = (header: any) =>
+      this.onHead({
+        hash: header.hash.toHex(),
+        parentHash: header.parentHash.toHex(),
+        number: header.number.toNumber()
+      });
+    const unsubscribe =
+      this.options.follow === 'best'
+        ? await this.api.rpc.chain.subscribeNewHeads(onHead)
+        : await this.api.rpc.chain.subscribeFinalizedHeads(onHead);
+    this.unsubscribe = unsubscribe as unknown as () => void;
+  }
+
+  /** Stop following the chain; the cached state is kept */
+  stop(): void {
+    if (this.unsubscribe) {
+      this.unsubscribe();
+      this.unsubscribe = null;
+    }
+    this.heads.clear();
+    this.latest = null;
+  }
+
+  /** Drop the cached state and read it again at the latest head */
+  invalidate(): void {
+    this.reads++;
+    this.current = null;
+    this.emit();
+    if (this.latest) {
+      this.load(this.latest);
+    }
+  }
+
+  private onHead(head: Head): void {
+    // A head replacing one already seen at its height, or not building on the
+    // last one, means the chain reorganized, and what was read on the
+    // abandoned branch may be wrong
+    const replaced = this.heads.get(head.number);
+    const orphaned =
+      this.latest !== null &&
+      head.number === this.latest.number + 1 &&
+      head.parentHash !== this.latest.hash;
+    if ((replaced !== undefined && replaced !== head.hash) || orphaned) {
+      for (const number of Array.from(this.heads.keys())) {
+        if (number >= head.number) {
+          this.heads.delete(number);
+        }
+      }
+      this.latest = head;
+      this.invalidate();
+    } else {
+      this.latest = head;
+      this.load(head);
+    }
+
+    this.heads.set(head.number, head.hash);
+    this.heads.delete(head.number - TRACKED_HEADS);
+  }
+
+  private async load(head: Head): Promise<void> {
+    const read = ++this.reads;
+    try {
+      const state = await this.read(head);
+      if (read !== this.reads) {
+        return;
+      }
+      this.current = state;
+      this.error = null;
+    } catch (err) {
+      if (read !== this.reads) {
+        return;
+      }
+      this.error = err instanceof Error ? err : new Error('Unknown error');
+    }
+    this.emit();
+  }
+
+  private async read(head: Head): Promise<WatchedStream> {
+    const at = await this.api.at(head.hash);
+    const { account } = this.options;
+    const [stream, viewerCount, balance, watchedSeconds]: any[] = await Promise.all([
+      at.query.tickStream.streams(this.streamId),
+      at.query.tickStream.viewerCount(this.streamId),
+      account ? at.query.tickStream.balances(this.streamId, account) : null,
+      account ? at.query.tickStream.watchedSeconds(this.streamId, account) : null
+    ]);
+
+    let details = null;
+    if (stream.isSome) {
+      const unwrapped = stream.unwrap();
+      details = {
+        creator: unwrapped.creator.toString(),
+        status: unwrapped.status.type as StreamStatus,
+        pricePerSecond: BigInt(unwrapped.pricePerSecond.toString()),
+        viewerCount: viewerCount.toNumber()
+      };
+    }
+
+    return {
+      streamId: this.streamId,
+      blockHash: head.hash,
+      blockNumber: head.number,
+      stream: details,
+      session: account
+        ? {
+            balance: BigInt(balance.toString()),
+            watchedSeconds: watchedSeconds.toNumber()
+          }
+        : null
+    };
+  }
+
+  private emit(): void {
+    for (const listener of Array.from(this.listeners)) {
+      listener(this.current, this.error);
+    }
+  }
+}